@@ -123,20 +123,26 @@ wait_for_approval() {
   deposit_id=$(graphql_output '.data.depositRecord.deposit.depositId')
   [[ "$deposit_id" != "null" ]] || exit 1
 
+  beneficiary_id=$(register_beneficiary "$customer_id")
+  cache_value "beneficiary_id" $beneficiary_id
+
   # usd_balance=$(graphql_output '.data.depositRecord.deposit.customer.depositAccount.balance.checking.settled')
   # [[ "$usd_balance" == "150000" ]] || exit 1
 }
 
 @test "customer: withdraw can be cancelled" {
   deposit_account_id=$(read_value 'deposit_account_id')
+  beneficiary_id=$(read_value 'beneficiary_id')
 
   variables=$(
     jq -n \
       --arg depositAccountId "$deposit_account_id" \
+      --arg beneficiaryId "$beneficiary_id" \
     --arg date "$(date +%s%N)" \
     '{
       input: {
         depositAccountId: $depositAccountId,
+        beneficiaryId: $beneficiaryId,
         amount: 150000,
         reference: ("withdrawal-ref-" + $date)
       }
@@ -183,14 +189,17 @@ wait_for_approval() {
 
 @test "customer: can withdraw" {
   deposit_account_id=$(read_value 'deposit_account_id')
+  beneficiary_id=$(read_value 'beneficiary_id')
 
   variables=$(
     jq -n \
       --arg depositAccountId "$deposit_account_id" \
+      --arg beneficiaryId "$beneficiary_id" \
     --arg date "$(date +%s%N)" \
     '{
       input: {
         depositAccountId: $depositAccountId,
+        beneficiaryId: $beneficiaryId,
         amount: 120000,
         reference: ("withdrawal-ref-" + $date)
       }
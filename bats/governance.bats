@@ -24,13 +24,17 @@ trigger_withdraw_approval_process() {
   )
   exec_admin_graphql 'record-deposit' "$variables"
 
+  beneficiary_id=$(register_beneficiary "$2")
+
   variables=$(
     jq -n \
       --arg deposit_account_id "$1" \
+      --arg beneficiary_id "$beneficiary_id" \
     --arg date "$(date +%s%N)" \
     '{
       input: {
         depositAccountId: $deposit_account_id,
+        beneficiaryId: $beneficiary_id,
         amount: 150000,
         reference: ("withdrawal-ref-" + $date)
       }
@@ -60,7 +64,7 @@ trigger_withdraw_approval_process() {
   deposit_account_id=$(graphql_output .data.customer.depositAccount.depositAccountId)
   cache_value "deposit_account_id" $deposit_account_id
 
-  process_id=$(trigger_withdraw_approval_process $deposit_account_id)
+  process_id=$(trigger_withdraw_approval_process $deposit_account_id $customer_id)
   variables=$(
     jq -n \
       --arg id "$process_id" \
@@ -288,6 +288,30 @@ create_customer() {
   echo $customer_id
 }
 
+register_beneficiary() {
+  variables=$(
+    jq -n \
+      --arg customerId "$1" \
+      '{
+      input: {
+        customerId: $customerId,
+        label: "Own account",
+        bankAccount: {
+          accountHolderName: "Jane Doe",
+          routingNumber: "021000021",
+          accountNumber: "000123456789",
+          accountType: "CHECKING"
+        }
+      }
+    }'
+  )
+
+  exec_admin_graphql 'beneficiary-register' "$variables"
+  beneficiary_id=$(graphql_output .data.beneficiaryRegister.beneficiary.beneficiaryId)
+  [[ "$beneficiary_id" != "null" ]] || exit 1
+  echo $beneficiary_id
+}
+
 assert_balance_sheet_balanced() {
   variables=$(
     jq -n \
@@ -0,0 +1,60 @@
+use chrono::{Datelike, Months, NaiveDate};
+
+use super::{entity::PostingKind, error::AccountingPeriodError, repo::AccountingPeriodRepo};
+
+/// Guards postings against closed accounting periods. Unlike
+/// `AccountingPeriods` it needs no permissions, so the modules posting their
+/// own ledger templates can hold one.
+#[derive(Clone)]
+pub struct AccountingPeriodCheck {
+    repo: AccountingPeriodRepo,
+}
+
+impl AccountingPeriodCheck {
+    pub fn new(pool: &sqlx::PgPool) -> Self {
+        Self {
+            repo: AccountingPeriodRepo::new(pool),
+        }
+    }
+
+    /// Dates not covered by any accounting period are always open.
+    pub async fn ensure_accepts_posting(
+        &self,
+        effective: NaiveDate,
+        kind: PostingKind,
+    ) -> Result<(), AccountingPeriodError> {
+        match self.repo.find_covering(effective).await? {
+            Some(period) => period.ensure_accepts_posting(effective, kind),
+            None => Ok(()),
+        }
+    }
+
+    /// Checks the period within the operation that posts, so the period
+    /// cannot be closed between the check and the posting.
+    pub async fn ensure_accepts_posting_in_op(
+        &self,
+        op: &mut es_entity::DbOp<'_>,
+        effective: NaiveDate,
+        kind: PostingKind,
+    ) -> Result<(), AccountingPeriodError> {
+        match self.repo.find_covering_in_op(op, effective).await? {
+            Some(period) => period.ensure_accepts_posting(effective, kind),
+            None => Ok(()),
+        }
+    }
+
+    /// The first day after the accounting period covering `date`. Dates not
+    /// covered by any period fall back to calendar months.
+    pub async fn next_period_start(
+        &self,
+        date: NaiveDate,
+    ) -> Result<NaiveDate, AccountingPeriodError> {
+        let next = match self.repo.find_covering(date).await? {
+            Some(period) => period.period_end.succ_opt(),
+            None => date
+                .with_day(1)
+                .and_then(|first| first.checked_add_months(Months::new(1))),
+        };
+        Ok(next.expect("date out of range"))
+    }
+}
@@ -0,0 +1,321 @@
+use chrono::NaiveDate;
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use audit::AuditInfo;
+use es_entity::*;
+
+use crate::primitives::{AccountingPeriodId, CalaTxId};
+
+use super::error::AccountingPeriodError;
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+pub enum AccountingPeriodStatus {
+    Open,
+    /// Only manual adjustments can still be posted into the period.
+    SoftClosed,
+    Closed,
+}
+
+/// What is attempting to post into a period. Soft-closed periods still accept
+/// manual adjustments but reject everything posted by the other ledger templates.
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum PostingKind {
+    ManualAdjustment,
+    System,
+}
+
+#[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[es_event(id = "AccountingPeriodId")]
+pub enum AccountingPeriodEvent {
+    Initialized {
+        id: AccountingPeriodId,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+        audit_info: AuditInfo,
+    },
+    SoftClosed {
+        audit_info: AuditInfo,
+    },
+    Reopened {
+        audit_info: AuditInfo,
+    },
+    Closed {
+        closing_transaction_id: Option<CalaTxId>,
+        audit_info: AuditInfo,
+    },
+}
+
+#[derive(EsEntity, Builder)]
+#[builder(pattern = "owned", build_fn(error = "EsEntityError"))]
+pub struct AccountingPeriod {
+    pub id: AccountingPeriodId,
+    pub period_start: NaiveDate,
+    /// Last day of the period, inclusive.
+    pub period_end: NaiveDate,
+    #[builder(default)]
+    pub closing_transaction_id: Option<CalaTxId>,
+    events: EntityEvents<AccountingPeriodEvent>,
+}
+
+impl AccountingPeriod {
+    pub fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.events
+            .entity_first_persisted_at()
+            .expect("No events for accounting period")
+    }
+
+    pub fn status(&self) -> AccountingPeriodStatus {
+        let mut status = AccountingPeriodStatus::Open;
+        for event in self.events.iter_all() {
+            match event {
+                AccountingPeriodEvent::SoftClosed { .. } => {
+                    status = AccountingPeriodStatus::SoftClosed
+                }
+                AccountingPeriodEvent::Reopened { .. } => status = AccountingPeriodStatus::Open,
+                AccountingPeriodEvent::Closed { .. } => return AccountingPeriodStatus::Closed,
+                AccountingPeriodEvent::Initialized { .. } => (),
+            }
+        }
+        status
+    }
+
+    pub fn covers(&self, date: NaiveDate) -> bool {
+        self.period_start <= date && date <= self.period_end
+    }
+
+    pub fn overlaps(&self, period_start: NaiveDate, period_end: NaiveDate) -> bool {
+        self.period_start <= period_end && period_start <= self.period_end
+    }
+
+    pub fn ensure_accepts_posting(
+        &self,
+        effective: NaiveDate,
+        kind: PostingKind,
+    ) -> Result<(), AccountingPeriodError> {
+        match (self.status(), kind) {
+            (AccountingPeriodStatus::Open, _) => Ok(()),
+            (AccountingPeriodStatus::SoftClosed, PostingKind::ManualAdjustment) => Ok(()),
+            (AccountingPeriodStatus::SoftClosed, PostingKind::System) => {
+                Err(AccountingPeriodError::PeriodSoftClosed(effective))
+            }
+            (AccountingPeriodStatus::Closed, _) => {
+                Err(AccountingPeriodError::PeriodClosed(effective))
+            }
+        }
+    }
+
+    pub fn soft_close(
+        &mut self,
+        audit_info: AuditInfo,
+    ) -> Result<Idempotent<()>, AccountingPeriodError> {
+        match self.status() {
+            AccountingPeriodStatus::Closed => Err(AccountingPeriodError::AlreadyClosed(self.id)),
+            AccountingPeriodStatus::SoftClosed => Ok(Idempotent::Ignored),
+            AccountingPeriodStatus::Open => {
+                self.events
+                    .push(AccountingPeriodEvent::SoftClosed { audit_info });
+                Ok(Idempotent::Executed(()))
+            }
+        }
+    }
+
+    pub fn reopen(
+        &mut self,
+        audit_info: AuditInfo,
+    ) -> Result<Idempotent<()>, AccountingPeriodError> {
+        match self.status() {
+            AccountingPeriodStatus::Closed => Err(AccountingPeriodError::AlreadyClosed(self.id)),
+            AccountingPeriodStatus::Open => Ok(Idempotent::Ignored),
+            AccountingPeriodStatus::SoftClosed => {
+                self.events
+                    .push(AccountingPeriodEvent::Reopened { audit_info });
+                Ok(Idempotent::Executed(()))
+            }
+        }
+    }
+
+    /// Closing is final. `closing_transaction_id` is set when the close posted
+    /// year-end closing entries.
+    pub fn close(
+        &mut self,
+        closing_transaction_id: Option<CalaTxId>,
+        audit_info: AuditInfo,
+    ) -> Idempotent<()> {
+        idempotency_guard!(self.events.iter_all(), AccountingPeriodEvent::Closed { .. });
+        self.closing_transaction_id = closing_transaction_id;
+        self.events.push(AccountingPeriodEvent::Closed {
+            closing_transaction_id,
+            audit_info,
+        });
+        Idempotent::Executed(())
+    }
+}
+
+impl TryFromEvents<AccountingPeriodEvent> for AccountingPeriod {
+    fn try_from_events(events: EntityEvents<AccountingPeriodEvent>) -> Result<Self, EsEntityError> {
+        let mut builder = AccountingPeriodBuilder::default();
+        for event in events.iter_all() {
+            match event {
+                AccountingPeriodEvent::Initialized {
+                    id,
+                    period_start,
+                    period_end,
+                    ..
+                } => {
+                    builder = builder
+                        .id(*id)
+                        .period_start(*period_start)
+                        .period_end(*period_end)
+                }
+                AccountingPeriodEvent::Closed {
+                    closing_transaction_id,
+                    ..
+                } => builder = builder.closing_transaction_id(*closing_transaction_id),
+                AccountingPeriodEvent::SoftClosed { .. } => (),
+                AccountingPeriodEvent::Reopened { .. } => (),
+            }
+        }
+        builder.events(events).build()
+    }
+}
+
+#[derive(Debug, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct NewAccountingPeriod {
+    #[builder(setter(into))]
+    pub(super) id: AccountingPeriodId,
+    pub(super) period_start: NaiveDate,
+    pub(super) period_end: NaiveDate,
+    #[builder(setter(into))]
+    pub audit_info: AuditInfo,
+}
+
+impl NewAccountingPeriod {
+    pub fn builder() -> NewAccountingPeriodBuilder {
+        NewAccountingPeriodBuilder::default()
+    }
+}
+
+impl NewAccountingPeriodBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match (self.period_start, self.period_end) {
+            (Some(start), Some(end)) if start > end => {
+                Err("Accounting period cannot end before it starts".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl IntoEvents<AccountingPeriodEvent> for NewAccountingPeriod {
+    fn into_events(self) -> EntityEvents<AccountingPeriodEvent> {
+        EntityEvents::init(
+            self.id,
+            [AccountingPeriodEvent::Initialized {
+                id: self.id,
+                period_start: self.period_start,
+                period_end: self.period_end,
+                audit_info: self.audit_info,
+            }],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use audit::{AuditEntryId, AuditInfo};
+
+    use super::*;
+
+    fn dummy_audit_info() -> AuditInfo {
+        AuditInfo {
+            audit_entry_id: AuditEntryId::from(1),
+            sub: "sub".to_string(),
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn new_period(start: &str, end: &str) -> Result<NewAccountingPeriod, String> {
+        NewAccountingPeriod::builder()
+            .id(AccountingPeriodId::new())
+            .period_start(date(start))
+            .period_end(date(end))
+            .audit_info(dummy_audit_info())
+            .build()
+            .map_err(|e| e.to_string())
+    }
+
+    fn period(start: &str, end: &str) -> AccountingPeriod {
+        AccountingPeriod::try_from_events(new_period(start, end).unwrap().into_events()).unwrap()
+    }
+
+    #[test]
+    fn cannot_end_before_it_starts() {
+        assert!(new_period("2025-02-01", "2025-01-31").is_err());
+        assert!(new_period("2025-01-01", "2025-01-01").is_ok());
+    }
+
+    #[test]
+    fn covers_and_overlaps_are_inclusive() {
+        let period = period("2025-01-01", "2025-01-31");
+        assert!(period.covers(date("2025-01-01")));
+        assert!(period.covers(date("2025-01-31")));
+        assert!(!period.covers(date("2025-02-01")));
+        assert!(period.overlaps(date("2025-01-31"), date("2025-02-28")));
+        assert!(!period.overlaps(date("2025-02-01"), date("2025-02-28")));
+    }
+
+    #[test]
+    fn soft_closed_period_only_accepts_manual_adjustments() {
+        let mut period = period("2025-01-01", "2025-01-31");
+        let effective = date("2025-01-15");
+        assert!(
+            period
+                .ensure_accepts_posting(effective, PostingKind::System)
+                .is_ok()
+        );
+
+        assert!(period.soft_close(dummy_audit_info()).unwrap().did_execute());
+        assert_eq!(period.status(), AccountingPeriodStatus::SoftClosed);
+        assert!(matches!(
+            period.ensure_accepts_posting(effective, PostingKind::System),
+            Err(AccountingPeriodError::PeriodSoftClosed(_))
+        ));
+        assert!(
+            period
+                .ensure_accepts_posting(effective, PostingKind::ManualAdjustment)
+                .is_ok()
+        );
+
+        assert!(period.reopen(dummy_audit_info()).unwrap().did_execute());
+        assert_eq!(period.status(), AccountingPeriodStatus::Open);
+    }
+
+    #[test]
+    fn closed_period_rejects_everything_and_is_final() {
+        let mut period = period("2025-01-01", "2025-01-31");
+        let closing_tx_id = CalaTxId::new();
+        assert!(
+            period
+                .close(Some(closing_tx_id), dummy_audit_info())
+                .did_execute()
+        );
+        assert_eq!(period.status(), AccountingPeriodStatus::Closed);
+        assert_eq!(period.closing_transaction_id, Some(closing_tx_id));
+        assert!(matches!(
+            period.ensure_accepts_posting(date("2025-01-15"), PostingKind::ManualAdjustment),
+            Err(AccountingPeriodError::PeriodClosed(_))
+        ));
+        assert!(period.close(None, dummy_audit_info()).was_ignored());
+        assert!(matches!(
+            period.reopen(dummy_audit_info()),
+            Err(AccountingPeriodError::AlreadyClosed(_))
+        ));
+    }
+}
@@ -0,0 +1,31 @@
+use thiserror::Error;
+
+use crate::primitives::AccountingPeriodId;
+
+#[derive(Error, Debug)]
+pub enum AccountingPeriodError {
+    #[error("AccountingPeriodError - Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("AccountingPeriodError - EsEntityError: {0}")]
+    EsEntityError(es_entity::EsEntityError),
+    #[error("AccountingPeriodError - CursorDestructureError: {0}")]
+    CursorDestructureError(#[from] es_entity::CursorDestructureError),
+    #[error("AccountingPeriodError - AuthorizationError: {0}")]
+    AuthorizationError(#[from] authz::error::AuthorizationError),
+    #[error("AccountingPeriodError - AuditError: {0}")]
+    AuditError(#[from] audit::error::AuditError),
+    #[error("AccountingPeriodError - AccountingPeriodLedgerError: {0}")]
+    AccountingPeriodLedgerError(#[from] super::ledger::error::AccountingPeriodLedgerError),
+    #[error("AccountingPeriodError - NewAccountingPeriodBuilderError: {0}")]
+    NewAccountingPeriodBuilderError(#[from] super::entity::NewAccountingPeriodBuilderError),
+    #[error("AccountingPeriodError - Overlaps with accounting period {0}")]
+    Overlapping(AccountingPeriodId),
+    #[error("AccountingPeriodError - Accounting period {0} is already closed")]
+    AlreadyClosed(AccountingPeriodId),
+    #[error("AccountingPeriodError - Effective date {0} falls in a closed accounting period")]
+    PeriodClosed(chrono::NaiveDate),
+    #[error("AccountingPeriodError - Effective date {0} falls in a soft-closed accounting period")]
+    PeriodSoftClosed(chrono::NaiveDate),
+}
+
+es_entity::from_es_entity_error!(AccountingPeriodError);
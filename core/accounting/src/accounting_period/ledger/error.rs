@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum AccountingPeriodLedgerError {
+    #[error("AccountingPeriodLedgerError - Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("AccountingPeriodLedgerError - CalaLedger: {0}")]
+    CalaLedger(#[from] cala_ledger::error::LedgerError),
+    #[error("AccountingPeriodLedgerError - CalaAccountSetError: {0}")]
+    CalaAccountSet(#[from] cala_ledger::account_set::error::AccountSetError),
+    #[error("AccountingPeriodLedgerError - CalaAccountError: {0}")]
+    CalaAccount(#[from] cala_ledger::account::error::AccountError),
+    #[error("AccountingPeriodLedgerError - CalaBalanceError: {0}")]
+    CalaBalance(#[from] cala_ledger::balance::error::BalanceError),
+    #[error("AccountingPeriodLedgerError - CalaTxTemplateError: {0}")]
+    TxTemplate(#[from] cala_ledger::tx_template::error::TxTemplateError),
+    #[error("AccountingPeriodLedgerError - Code not found in chart: {0}")]
+    CodeNotFoundInChart(crate::primitives::AccountCode),
+}
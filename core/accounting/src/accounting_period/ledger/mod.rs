@@ -0,0 +1,291 @@
+pub mod error;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use cala_ledger::{
+    AccountId, AccountSetId, CalaLedger, Currency, DebitOrCredit, JournalId,
+    account::NewAccount,
+    account_set::{AccountSetMemberId, AccountSetMembersByCreatedAtCursor},
+};
+
+use crate::{
+    chart_of_accounts::Chart,
+    manual_transaction::{EntryParams, ManualTransactionParams, ManualTransactionTemplate},
+    primitives::{AccountCode, CalaTxId},
+};
+
+use error::*;
+
+const RETAINED_EARNINGS_NAME: &str = "Retained Earnings";
+const MAX_ACCOUNT_SET_DEPTH: usize = 10;
+
+/// The chart codes needed to post year-end closing entries.
+pub struct YearEndCloseAccounts<'a> {
+    pub equity_code: &'a AccountCode,
+    pub income_statement_codes: Vec<&'a AccountCode>,
+}
+
+#[derive(Clone)]
+pub struct AccountingPeriodLedger {
+    cala: CalaLedger,
+    journal_id: JournalId,
+}
+
+impl AccountingPeriodLedger {
+    pub fn new(cala: &CalaLedger, journal_id: JournalId) -> Self {
+        Self {
+            cala: cala.clone(),
+            journal_id,
+        }
+    }
+
+    /// The entries zeroing every USD revenue and expense account as of
+    /// `period_end` into retained earnings. Empty when there is nothing to close.
+    pub async fn year_end_closing_entries(
+        &self,
+        chart: &Chart,
+        accounts: YearEndCloseAccounts<'_>,
+        period_end: NaiveDate,
+    ) -> Result<Vec<EntryParams>, AccountingPeriodLedgerError> {
+        let mut account_ids = Vec::new();
+        for code in accounts.income_statement_codes {
+            let (_, account_set_id) = chart
+                .account_spec(code)
+                .ok_or_else(|| AccountingPeriodLedgerError::CodeNotFoundInChart(code.clone()))?;
+            account_ids.extend(self.find_leaf_accounts(*account_set_id).await?);
+        }
+
+        let balances = self.settled_usd_balances(&account_ids, period_end).await?;
+        let retained_earnings_account_id = self
+            .find_or_create_retained_earnings_account(chart, accounts.equity_code)
+            .await?;
+        Ok(closing_entries(balances, retained_earnings_account_id))
+    }
+
+    pub async fn post_closing_entries(
+        &self,
+        op: es_entity::DbOp<'_>,
+        tx_id: CalaTxId,
+        entry_params: Vec<EntryParams>,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> Result<(), AccountingPeriodLedgerError> {
+        let template = ManualTransactionTemplate::init(&self.cala, entry_params.len()).await?;
+        let mut op = self.cala.ledger_operation_from_db_op(op);
+        self.cala
+            .post_transaction_in_op(
+                &mut op,
+                tx_id,
+                &template.code(),
+                ManualTransactionParams {
+                    journal_id: self.journal_id,
+                    description: format!(
+                        "Year-end closing entries for {} to {}",
+                        period_start, period_end
+                    ),
+                    effective: period_end,
+                    entry_params,
+                },
+            )
+            .await?;
+        op.commit().await?;
+        Ok(())
+    }
+
+    async fn find_leaf_accounts(
+        &self,
+        account_set_id: AccountSetId,
+    ) -> Result<Vec<AccountId>, AccountingPeriodLedgerError> {
+        let mut account_ids = Vec::new();
+        let mut pending = vec![(account_set_id, 0)];
+        while let Some((id, depth)) = pending.pop() {
+            if depth > MAX_ACCOUNT_SET_DEPTH {
+                continue;
+            }
+            let mut query = es_entity::PaginatedQueryArgs::<AccountSetMembersByCreatedAtCursor> {
+                first: 100,
+                after: None,
+            };
+            loop {
+                let res = self
+                    .cala
+                    .account_sets()
+                    .list_members_by_created_at(id, query)
+                    .await?;
+                for member in res.entities {
+                    match member.id {
+                        AccountSetMemberId::Account(account_id) => account_ids.push(account_id),
+                        AccountSetMemberId::AccountSet(set_id) => pending.push((set_id, depth + 1)),
+                    }
+                }
+                match res.end_cursor {
+                    Some(after) if res.has_next_page => {
+                        query = es_entity::PaginatedQueryArgs {
+                            first: 100,
+                            after: Some(after),
+                        }
+                    }
+                    _ => break,
+                }
+            }
+        }
+        Ok(account_ids)
+    }
+
+    async fn settled_usd_balances(
+        &self,
+        account_ids: &[AccountId],
+        as_of: NaiveDate,
+    ) -> Result<Vec<(AccountId, Decimal)>, AccountingPeriodLedgerError> {
+        let balance_ids = account_ids
+            .iter()
+            .map(|id| (self.journal_id, *id, Currency::USD))
+            .collect::<Vec<_>>();
+        let mut balances = self
+            .cala
+            .balances()
+            .effective()
+            .find_all_in_range(&balance_ids, as_of, Some(as_of))
+            .await?;
+
+        Ok(account_ids
+            .iter()
+            .filter_map(|id| {
+                balances
+                    .remove(&(self.journal_id, *id, Currency::USD))
+                    .map(|range| {
+                        let settled = range.close.details.settled;
+                        (*id, settled.dr_balance - settled.cr_balance)
+                    })
+            })
+            .collect())
+    }
+
+    async fn find_or_create_retained_earnings_account(
+        &self,
+        chart: &Chart,
+        equity_code: &AccountCode,
+    ) -> Result<AccountId, AccountingPeriodLedgerError> {
+        let external_id = equity_code.retained_earnings_account_external_id(chart.id);
+        match self
+            .cala
+            .accounts()
+            .find_by_external_id(external_id.clone())
+            .await
+        {
+            Ok(existing) => return Ok(existing.id()),
+            Err(e) if e.was_not_found() => (),
+            Err(e) => return Err(e.into()),
+        }
+
+        let (_, equity_account_set_id) = chart
+            .account_spec(equity_code)
+            .ok_or_else(|| AccountingPeriodLedgerError::CodeNotFoundInChart(equity_code.clone()))?;
+        let account = self
+            .cala
+            .accounts()
+            .create(
+                NewAccount::builder()
+                    .id(AccountId::new())
+                    .name(format!("{} {}", equity_code, RETAINED_EARNINGS_NAME))
+                    .code(external_id.clone())
+                    .external_id(external_id)
+                    .normal_balance_type(DebitOrCredit::Credit)
+                    .build()
+                    .expect("Could not build retained earnings account"),
+            )
+            .await?;
+        self.cala
+            .account_sets()
+            .add_member(*equity_account_set_id, account.id)
+            .await?;
+
+        Ok(account.id)
+    }
+}
+
+/// Builds one entry per account bringing its balance to zero plus the
+/// offsetting entry into retained earnings. `balances` are debit minus credit.
+fn closing_entries(
+    balances: Vec<(AccountId, Decimal)>,
+    retained_earnings_account_id: AccountId,
+) -> Vec<EntryParams> {
+    let mut entries = Vec::new();
+    let mut net = Decimal::ZERO;
+    for (account_id, balance) in balances {
+        if balance.is_zero() {
+            continue;
+        }
+        net += balance;
+        entries.push(EntryParams {
+            account_id,
+            currency: Currency::USD,
+            amount: balance.abs(),
+            description: "Year-end close".to_string(),
+            direction: if balance > Decimal::ZERO {
+                DebitOrCredit::Credit
+            } else {
+                DebitOrCredit::Debit
+            },
+        });
+    }
+    if entries.is_empty() {
+        return entries;
+    }
+    if !net.is_zero() {
+        entries.push(EntryParams {
+            account_id: retained_earnings_account_id,
+            currency: Currency::USD,
+            amount: net.abs(),
+            description: "Year-end close to retained earnings".to_string(),
+            direction: if net > Decimal::ZERO {
+                DebitOrCredit::Debit
+            } else {
+                DebitOrCredit::Credit
+            },
+        });
+    }
+    entries
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    #[test]
+    fn closing_entries_zero_accounts_into_retained_earnings() {
+        let revenue = AccountId::new();
+        let expense = AccountId::new();
+        let untouched = AccountId::new();
+        let retained_earnings = AccountId::new();
+
+        let entries = closing_entries(
+            vec![
+                (revenue, dec!(-1000)),
+                (expense, dec!(400)),
+                (untouched, Decimal::ZERO),
+            ],
+            retained_earnings,
+        );
+
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].account_id, revenue);
+        assert_eq!(entries[0].direction, DebitOrCredit::Debit);
+        assert_eq!(entries[0].amount, dec!(1000));
+        assert_eq!(entries[1].account_id, expense);
+        assert_eq!(entries[1].direction, DebitOrCredit::Credit);
+        assert_eq!(entries[1].amount, dec!(400));
+        assert_eq!(entries[2].account_id, retained_earnings);
+        assert_eq!(entries[2].direction, DebitOrCredit::Credit);
+        assert_eq!(entries[2].amount, dec!(600));
+    }
+
+    #[test]
+    fn no_closing_entries_without_activity() {
+        let entries = closing_entries(vec![(AccountId::new(), Decimal::ZERO)], AccountId::new());
+        assert!(entries.is_empty());
+    }
+}
@@ -0,0 +1,262 @@
+mod check;
+mod entity;
+pub mod error;
+mod ledger;
+mod repo;
+
+use std::collections::HashMap;
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use cala_ledger::{CalaLedger, JournalId};
+use chrono::NaiveDate;
+
+use crate::{
+    balance_sheet::ChartOfAccountsIntegrationConfig,
+    chart_of_accounts::Chart,
+    primitives::{AccountingPeriodId, CalaTxId, CoreAccountingAction, CoreAccountingObject},
+};
+
+pub use check::AccountingPeriodCheck;
+pub(super) use entity::*;
+pub use entity::{AccountingPeriod, AccountingPeriodStatus, PostingKind};
+use error::*;
+use ledger::*;
+use repo::*;
+
+#[derive(Clone)]
+pub struct AccountingPeriods<Perms>
+where
+    Perms: PermissionCheck,
+{
+    authz: Perms,
+    repo: AccountingPeriodRepo,
+    ledger: AccountingPeriodLedger,
+}
+
+impl<Perms> AccountingPeriods<Perms>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreAccountingAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreAccountingObject>,
+{
+    pub fn new(
+        pool: &sqlx::PgPool,
+        authz: &Perms,
+        cala: &CalaLedger,
+        journal_id: JournalId,
+    ) -> Self {
+        Self {
+            authz: authz.clone(),
+            repo: AccountingPeriodRepo::new(pool),
+            ledger: AccountingPeriodLedger::new(cala, journal_id),
+        }
+    }
+
+    pub async fn create(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+    ) -> Result<AccountingPeriod, AccountingPeriodError> {
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_accounting_periods(),
+                CoreAccountingAction::ACCOUNTING_PERIOD_CREATE,
+            )
+            .await?;
+
+        if let Some(existing) = self
+            .repo
+            .list_all()
+            .await?
+            .into_iter()
+            .find(|p| p.overlaps(period_start, period_end))
+        {
+            return Err(AccountingPeriodError::Overlapping(existing.id));
+        }
+
+        let new_period = NewAccountingPeriod::builder()
+            .id(AccountingPeriodId::new())
+            .period_start(period_start)
+            .period_end(period_end)
+            .audit_info(audit_info)
+            .build()?;
+        self.repo.create(new_period).await
+    }
+
+    pub async fn find_by_id(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<AccountingPeriodId> + std::fmt::Debug,
+    ) -> Result<Option<AccountingPeriod>, AccountingPeriodError> {
+        let id = id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::accounting_period(id),
+                CoreAccountingAction::ACCOUNTING_PERIOD_READ,
+            )
+            .await?;
+
+        match self.repo.find_by_id(id).await {
+            Ok(period) => Ok(Some(period)),
+            Err(e) if e.was_not_found() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// All accounting periods, ordered by the date they end on.
+    pub async fn list(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+    ) -> Result<Vec<AccountingPeriod>, AccountingPeriodError> {
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_accounting_periods(),
+                CoreAccountingAction::ACCOUNTING_PERIOD_LIST,
+            )
+            .await?;
+
+        self.repo.list_all().await
+    }
+
+    pub async fn find_all<T: From<AccountingPeriod>>(
+        &self,
+        ids: &[AccountingPeriodId],
+    ) -> Result<HashMap<AccountingPeriodId, T>, AccountingPeriodError> {
+        self.repo.find_all(ids).await
+    }
+
+    pub async fn soft_close(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<AccountingPeriodId> + std::fmt::Debug,
+    ) -> Result<AccountingPeriod, AccountingPeriodError> {
+        let id = id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::accounting_period(id),
+                CoreAccountingAction::ACCOUNTING_PERIOD_SOFT_CLOSE,
+            )
+            .await?;
+
+        let mut period = self.repo.find_by_id(id).await?;
+        if period.soft_close(audit_info)?.did_execute() {
+            self.repo.update(&mut period).await?;
+        }
+        Ok(period)
+    }
+
+    pub async fn reopen(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<AccountingPeriodId> + std::fmt::Debug,
+    ) -> Result<AccountingPeriod, AccountingPeriodError> {
+        let id = id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::accounting_period(id),
+                CoreAccountingAction::ACCOUNTING_PERIOD_REOPEN,
+            )
+            .await?;
+
+        let mut period = self.repo.find_by_id(id).await?;
+        if period.reopen(audit_info)?.did_execute() {
+            self.repo.update(&mut period).await?;
+        }
+        Ok(period)
+    }
+
+    pub async fn close(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<AccountingPeriodId> + std::fmt::Debug,
+    ) -> Result<AccountingPeriod, AccountingPeriodError> {
+        let id = id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::accounting_period(id),
+                CoreAccountingAction::ACCOUNTING_PERIOD_CLOSE,
+            )
+            .await?;
+
+        let mut period = self.repo.find_by_id(id).await?;
+        if period.close(None, audit_info).did_execute() {
+            self.repo.update(&mut period).await?;
+        }
+        Ok(period)
+    }
+
+    /// Closes the period and posts, effective on its last day, the entries
+    /// that bring every revenue, cost of revenue and expense account to zero
+    /// against a retained earnings account under the configured equity code.
+    /// Soft-close the period first so nothing is posted while balances are read.
+    pub async fn close_year_end(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        chart: &Chart,
+        config: &ChartOfAccountsIntegrationConfig,
+        id: impl Into<AccountingPeriodId> + std::fmt::Debug,
+    ) -> Result<AccountingPeriod, AccountingPeriodError> {
+        let id = id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::accounting_period(id),
+                CoreAccountingAction::ACCOUNTING_PERIOD_CLOSE_YEAR_END,
+            )
+            .await?;
+
+        let mut period = self.repo.find_by_id(id).await?;
+        if period.status() == AccountingPeriodStatus::Closed {
+            return Err(AccountingPeriodError::AlreadyClosed(id));
+        }
+
+        let entries = self
+            .ledger
+            .year_end_closing_entries(
+                chart,
+                YearEndCloseAccounts {
+                    equity_code: &config.chart_of_accounts_equity_code,
+                    income_statement_codes: vec![
+                        &config.chart_of_accounts_revenue_code,
+                        &config.chart_of_accounts_cost_of_revenue_code,
+                        &config.chart_of_accounts_expenses_code,
+                    ],
+                },
+                period.period_end,
+            )
+            .await?;
+
+        let tx_id = (!entries.is_empty()).then(CalaTxId::new);
+        let _ = period.close(tx_id, audit_info);
+        let mut db = self.repo.begin_op().await?;
+        self.repo.update_in_op(&mut db, &mut period).await?;
+        match tx_id {
+            Some(tx_id) => {
+                self.ledger
+                    .post_closing_entries(
+                        db,
+                        tx_id,
+                        entries,
+                        period.period_start,
+                        period.period_end,
+                    )
+                    .await?
+            }
+            None => db.commit().await?,
+        }
+        Ok(period)
+    }
+}
@@ -0,0 +1,95 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+
+use es_entity::*;
+
+use crate::primitives::AccountingPeriodId;
+
+use super::{entity::*, error::*};
+
+#[derive(EsRepo)]
+#[es_repo(
+    entity = "AccountingPeriod",
+    err = "AccountingPeriodError",
+    columns(period_start(ty = "NaiveDate"), period_end(ty = "NaiveDate", list_by)),
+    tbl_prefix = "core"
+)]
+pub struct AccountingPeriodRepo {
+    pool: PgPool,
+}
+
+impl Clone for AccountingPeriodRepo {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+impl AccountingPeriodRepo {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+
+    pub async fn list_all(&self) -> Result<Vec<AccountingPeriod>, AccountingPeriodError> {
+        let mut periods = Vec::new();
+        let mut query = es_entity::PaginatedQueryArgs {
+            first: 100,
+            after: None,
+        };
+        loop {
+            let res = self
+                .list_by_period_end(query, es_entity::ListDirection::Ascending)
+                .await?;
+            periods.extend(res.entities);
+            match res.end_cursor {
+                Some(after) if res.has_next_page => {
+                    query = es_entity::PaginatedQueryArgs {
+                        first: 100,
+                        after: Some(after),
+                    }
+                }
+                _ => break,
+            }
+        }
+        Ok(periods)
+    }
+
+    /// Periods cannot overlap, so at most one period covers a given date.
+    pub async fn find_covering(
+        &self,
+        date: NaiveDate,
+    ) -> Result<Option<AccountingPeriod>, AccountingPeriodError> {
+        let (mut periods, _) = es_query!(
+            "core",
+            self.pool(),
+            "SELECT id FROM core_accounting_periods WHERE period_start <= $1 AND period_end >= $1",
+            date,
+        )
+        .fetch_n(1)
+        .await?;
+        Ok(periods.pop())
+    }
+
+    /// Like `find_covering`, but holds a share lock on the covering period
+    /// until the operation ends, so it cannot be closed while a posting
+    /// checked against it is still in flight.
+    pub async fn find_covering_in_op(
+        &self,
+        op: &mut es_entity::DbOp<'_>,
+        date: NaiveDate,
+    ) -> Result<Option<AccountingPeriod>, AccountingPeriodError> {
+        let row = sqlx::query!(
+            r#"SELECT id AS "id: AccountingPeriodId" FROM core_accounting_periods
+            WHERE period_start <= $1 AND period_end >= $1
+            FOR SHARE"#,
+            date,
+        )
+        .fetch_optional(&mut **op.tx())
+        .await?;
+        match row {
+            Some(row) => Ok(Some(self.find_by_id_in_tx(op.tx(), row.id).await?)),
+            None => Ok(None),
+        }
+    }
+}
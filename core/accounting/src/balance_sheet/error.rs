@@ -12,6 +12,8 @@ pub enum BalanceSheetError {
     BalanceSheetLedgerError(#[from] super::ledger::error::BalanceSheetLedgerError),
     #[error("BalanceSheetError - ChartOfAccountsError: {0}")]
     ChartOfAccountsError(#[from] crate::chart_of_accounts::error::ChartOfAccountsError),
+    #[error("BalanceSheetError - ComparativeStatementError: {0}")]
+    ComparativeStatementError(#[from] crate::comparative::error::ComparativeStatementError),
     #[error("BalanceSheetError - CreditConfigAlreadyExists")]
     CreditConfigAlreadyExists,
     #[error("BalanceSheetError - ChartIdMismatch")]
@@ -10,6 +10,9 @@ use chrono::NaiveDate;
 use crate::{
     LedgerAccountId,
     chart_of_accounts::Chart,
+    comparative::{
+        ComparativeMeasure, ComparativeStatement, ComparativeStatementLedger, StatementPeriod,
+    },
     primitives::{BalanceRange, CalaAccountSetId, CoreAccountingAction, CoreAccountingObject},
 };
 
@@ -72,6 +75,7 @@ where
     pool: sqlx::PgPool,
     authz: Perms,
     balance_sheet_ledger: BalanceSheetLedger,
+    comparative_ledger: ComparativeStatementLedger,
 }
 
 impl<Perms> BalanceSheets<Perms>
@@ -87,10 +91,12 @@ where
         journal_id: cala_ledger::JournalId,
     ) -> Self {
         let balance_sheet_ledger = BalanceSheetLedger::new(cala, journal_id);
+        let comparative_ledger = ComparativeStatementLedger::new(cala, journal_id);
 
         Self {
             pool: pool.clone(),
             balance_sheet_ledger,
+            comparative_ledger,
             authz: authz.clone(),
         }
     }
@@ -213,6 +219,36 @@ where
             .get_balance_sheet(reference, from, until)
             .await?)
     }
+
+    /// Closing balances as of the end of each period, one column per period.
+    pub async fn comparative_balance_sheet(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        reference: String,
+        periods: Vec<StatementPeriod>,
+    ) -> Result<ComparativeStatement, BalanceSheetError> {
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_balance_sheet(),
+                CoreAccountingAction::BALANCE_SHEET_READ,
+            )
+            .await?;
+
+        self.find_comparative_balance_sheet(reference, periods)
+            .await
+    }
+
+    pub(crate) async fn find_comparative_balance_sheet(
+        &self,
+        reference: String,
+        periods: Vec<StatementPeriod>,
+    ) -> Result<ComparativeStatement, BalanceSheetError> {
+        Ok(self
+            .comparative_ledger
+            .get_comparative_statement(reference, periods, ComparativeMeasure::ClosingBalance)
+            .await?)
+    }
 }
 
 #[derive(Clone)]
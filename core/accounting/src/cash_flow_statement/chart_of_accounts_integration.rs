@@ -0,0 +1,91 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use std::collections::HashSet;
+
+use crate::primitives::{AccountCode, ChartId};
+
+#[derive(Builder, Debug, Serialize, Deserialize, Clone)]
+pub struct ChartOfAccountsIntegrationConfig {
+    #[builder(setter(into))]
+    pub chart_of_accounts_id: ChartId,
+    pub chart_of_accounts_cash_code: AccountCode,
+    pub chart_of_accounts_revenue_code: AccountCode,
+    pub chart_of_accounts_cost_of_revenue_code: AccountCode,
+    pub chart_of_accounts_expenses_code: AccountCode,
+    #[builder(default)]
+    pub chart_of_accounts_operating_codes: Vec<AccountCode>,
+    #[builder(default)]
+    pub chart_of_accounts_investing_codes: Vec<AccountCode>,
+    #[builder(default)]
+    pub chart_of_accounts_financing_codes: Vec<AccountCode>,
+}
+
+impl ChartOfAccountsIntegrationConfig {
+    pub fn builder() -> ChartOfAccountsIntegrationConfigBuilder {
+        ChartOfAccountsIntegrationConfigBuilder::default()
+    }
+
+    /// A code mapped twice would be counted twice in the statement.
+    pub(super) fn first_duplicate_code(&self) -> Option<&AccountCode> {
+        let mut seen = HashSet::new();
+        [
+            &self.chart_of_accounts_cash_code,
+            &self.chart_of_accounts_revenue_code,
+            &self.chart_of_accounts_cost_of_revenue_code,
+            &self.chart_of_accounts_expenses_code,
+        ]
+        .into_iter()
+        .chain(self.chart_of_accounts_operating_codes.iter())
+        .chain(self.chart_of_accounts_investing_codes.iter())
+        .chain(self.chart_of_accounts_financing_codes.iter())
+        .find(|code| !seen.insert(*code))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn code(s: &str) -> AccountCode {
+        s.parse().unwrap()
+    }
+
+    fn config(operating: &[&str], investing: &[&str]) -> ChartOfAccountsIntegrationConfig {
+        ChartOfAccountsIntegrationConfig::builder()
+            .chart_of_accounts_id(ChartId::new())
+            .chart_of_accounts_cash_code(code("11"))
+            .chart_of_accounts_revenue_code(code("4"))
+            .chart_of_accounts_cost_of_revenue_code(code("5"))
+            .chart_of_accounts_expenses_code(code("6"))
+            .chart_of_accounts_operating_codes(operating.iter().map(|c| code(c)).collect())
+            .chart_of_accounts_investing_codes(investing.iter().map(|c| code(c)).collect())
+            .build()
+            .unwrap()
+    }
+
+    #[test]
+    fn distinct_codes_have_no_duplicate() {
+        assert!(
+            config(&["12", "21"], &["13"])
+                .first_duplicate_code()
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn code_in_two_sections_is_a_duplicate() {
+        assert_eq!(
+            config(&["12", "13"], &["13"]).first_duplicate_code(),
+            Some(&code("13"))
+        );
+    }
+
+    #[test]
+    fn cash_code_in_a_section_is_a_duplicate() {
+        assert_eq!(
+            config(&["11"], &[]).first_duplicate_code(),
+            Some(&code("11"))
+        );
+    }
+}
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum CashFlowStatementError {
+    #[error("CashFlowStatementError - Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("CashFlowStatementError - AuditError: {0}")]
+    AuditError(#[from] audit::error::AuditError),
+    #[error("CashFlowStatementError - AuthorizationError: {0}")]
+    AuthorizationError(#[from] authz::error::AuthorizationError),
+    #[error("CashFlowStatementError - CashFlowStatementLedgerError: {0}")]
+    CashFlowStatementLedgerError(#[from] super::ledger::error::CashFlowStatementLedgerError),
+    #[error("CashFlowStatementError - ChartOfAccountsError: {0}")]
+    ChartOfAccountsError(#[from] crate::chart_of_accounts::error::ChartOfAccountsError),
+    #[error("CashFlowStatementError - ChartConfigAlreadyExists")]
+    ChartConfigAlreadyExists,
+    #[error("CashFlowStatementError - ChartIdMismatch")]
+    ChartIdMismatch,
+    #[error("CashFlowStatementError - DuplicateCode: {0}")]
+    DuplicateCode(crate::primitives::AccountCode),
+}
@@ -0,0 +1,585 @@
+pub mod error;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use cala_ledger::{
+    AccountSetId, BalanceId, CalaLedger, Currency, DebitOrCredit, JournalId, LedgerOperation,
+    account_set::{AccountSet, AccountSetMemberId, AccountSetUpdate, NewAccountSet},
+};
+
+use audit::AuditInfo;
+
+use crate::primitives::{CalaAccountBalance, CalaBalanceRange};
+
+use super::{
+    CASH_NAME, CashFlowAmount, CashFlowLine, CashFlowSection, CashFlowStatement,
+    CashFlowStatementIds, ChartOfAccountsIntegrationConfig, FINANCING_ACTIVITIES_NAME,
+    INVESTING_ACTIVITIES_NAME, NET_INCOME_NAME, OPERATING_ACTIVITIES_NAME, WORKING_CAPITAL_NAME,
+};
+
+use error::*;
+
+#[derive(Clone)]
+pub struct CashFlowStatementLedger {
+    cala: CalaLedger,
+    journal_id: JournalId,
+}
+
+impl CashFlowStatementLedger {
+    pub fn new(cala: &CalaLedger, journal_id: JournalId) -> Self {
+        Self {
+            cala: cala.clone(),
+            journal_id,
+        }
+    }
+
+    async fn create_unique_account_set(
+        &self,
+        op: &mut LedgerOperation<'_>,
+        external_id: &str,
+        name: &str,
+        normal_balance_type: DebitOrCredit,
+    ) -> Result<AccountSetId, CashFlowStatementLedgerError> {
+        let id = AccountSetId::new();
+        let new_account_set = NewAccountSet::builder()
+            .id(id)
+            .journal_id(self.journal_id)
+            .external_id(external_id)
+            .name(name)
+            .description(name)
+            .normal_balance_type(normal_balance_type)
+            .build()
+            .expect("Could not build new account set");
+        self.cala
+            .account_sets()
+            .create_in_op(op, new_account_set)
+            .await?;
+
+        Ok(id)
+    }
+
+    async fn create_account_set(
+        &self,
+        op: &mut LedgerOperation<'_>,
+        reference: &str,
+        normal_balance_type: DebitOrCredit,
+        parents: Vec<AccountSetId>,
+    ) -> Result<AccountSetId, CashFlowStatementLedgerError> {
+        let id = AccountSetId::new();
+        let new_account_set = NewAccountSet::builder()
+            .id(id)
+            .journal_id(self.journal_id)
+            .name(reference)
+            .description(reference)
+            .normal_balance_type(normal_balance_type)
+            .build()
+            .expect("Could not build new account set");
+        self.cala
+            .account_sets()
+            .create_in_op(op, new_account_set)
+            .await?;
+
+        for parent_id in parents {
+            self.cala
+                .account_sets()
+                .add_member_in_op(op, parent_id, id)
+                .await?;
+        }
+
+        Ok(id)
+    }
+
+    async fn get_member_account_sets(
+        &self,
+        id: impl Into<AccountSetId> + Copy,
+    ) -> Result<Vec<AccountSet>, CashFlowStatementLedgerError> {
+        let id = id.into();
+
+        let member_ids = self
+            .cala
+            .account_sets()
+            .list_members_by_created_at(id, Default::default())
+            .await?
+            .entities
+            .into_iter()
+            .map(|m| match m.id {
+                AccountSetMemberId::AccountSet(id) => Ok(id),
+                _ => Err(CashFlowStatementLedgerError::NonAccountSetMemberTypeFound),
+            })
+            .collect::<Result<Vec<AccountSetId>, CashFlowStatementLedgerError>>()?;
+
+        let mut account_sets = Vec::with_capacity(member_ids.len());
+        for id in member_ids {
+            account_sets.push(self.cala.account_sets().find(id).await?);
+        }
+
+        Ok(account_sets)
+    }
+
+    async fn get_member_account_set_ids_and_names(
+        &self,
+        id: impl Into<AccountSetId> + Copy,
+    ) -> Result<HashMap<String, AccountSetId>, CashFlowStatementLedgerError> {
+        Ok(self
+            .get_member_account_sets(id)
+            .await?
+            .into_iter()
+            .map(|account_set| {
+                let values = account_set.into_values();
+                (values.name, values.id)
+            })
+            .collect())
+    }
+
+    async fn get_balances_by_id(
+        &self,
+        all_account_set_ids: Vec<AccountSetId>,
+        from: NaiveDate,
+        until: Option<NaiveDate>,
+    ) -> Result<HashMap<BalanceId, CalaBalanceRange>, CashFlowStatementLedgerError> {
+        let balance_ids = all_account_set_ids
+            .iter()
+            .flat_map(|id| {
+                [
+                    (self.journal_id, (*id).into(), Currency::USD),
+                    (self.journal_id, (*id).into(), Currency::BTC),
+                ]
+            })
+            .collect::<Vec<_>>();
+        let res = self
+            .cala
+            .balances()
+            .effective()
+            .find_all_in_range(&balance_ids, from, until)
+            .await?;
+
+        Ok(res)
+    }
+
+    pub async fn attach_chart_of_accounts_account_sets(
+        &self,
+        reference: String,
+        charts_integration_meta: ChartOfAccountsIntegrationMeta,
+    ) -> Result<(), CashFlowStatementLedgerError> {
+        let mut op = self.cala.begin_operation().await?;
+
+        let account_set_ids = self.get_ids_from_reference(reference).await?;
+        let mut account_sets = self
+            .cala
+            .account_sets()
+            .find_all_in_op::<AccountSet>(&mut op, &account_set_ids.internal_ids())
+            .await?;
+
+        let ChartOfAccountsIntegrationMeta {
+            config: _,
+            audit_info: _,
+
+            cash_child_account_set_id_from_chart,
+            net_income_child_account_set_ids_from_chart,
+            working_capital_child_account_set_ids_from_chart,
+            investing_child_account_set_ids_from_chart,
+            financing_child_account_set_ids_from_chart,
+        } = &charts_integration_meta;
+
+        self.attach_charts_account_sets(
+            &mut op,
+            &mut account_sets,
+            account_set_ids.cash,
+            &[*cash_child_account_set_id_from_chart],
+            &charts_integration_meta,
+            |meta| vec![meta.cash_child_account_set_id_from_chart],
+        )
+        .await?;
+        self.attach_charts_account_sets(
+            &mut op,
+            &mut account_sets,
+            account_set_ids.net_income,
+            net_income_child_account_set_ids_from_chart,
+            &charts_integration_meta,
+            |meta| meta.net_income_child_account_set_ids_from_chart,
+        )
+        .await?;
+        self.attach_charts_account_sets(
+            &mut op,
+            &mut account_sets,
+            account_set_ids.working_capital,
+            working_capital_child_account_set_ids_from_chart,
+            &charts_integration_meta,
+            |meta| meta.working_capital_child_account_set_ids_from_chart,
+        )
+        .await?;
+        self.attach_charts_account_sets(
+            &mut op,
+            &mut account_sets,
+            account_set_ids.investing,
+            investing_child_account_set_ids_from_chart,
+            &charts_integration_meta,
+            |meta| meta.investing_child_account_set_ids_from_chart,
+        )
+        .await?;
+        self.attach_charts_account_sets(
+            &mut op,
+            &mut account_sets,
+            account_set_ids.financing,
+            financing_child_account_set_ids_from_chart,
+            &charts_integration_meta,
+            |meta| meta.financing_child_account_set_ids_from_chart,
+        )
+        .await?;
+
+        op.commit().await?;
+
+        Ok(())
+    }
+
+    async fn attach_charts_account_sets<F>(
+        &self,
+        op: &mut LedgerOperation<'_>,
+        account_sets: &mut HashMap<AccountSetId, AccountSet>,
+        internal_account_set_id: AccountSetId,
+        child_account_set_ids_from_chart: &[AccountSetId],
+        new_meta: &ChartOfAccountsIntegrationMeta,
+        old_parent_ids_getter: F,
+    ) -> Result<(), CashFlowStatementLedgerError>
+    where
+        F: FnOnce(ChartOfAccountsIntegrationMeta) -> Vec<AccountSetId>,
+    {
+        let mut internal_account_set = account_sets
+            .remove(&internal_account_set_id)
+            .expect("internal account set not found");
+
+        let mut old_child_account_set_ids_from_chart = Vec::new();
+        if let Some(old_meta) = internal_account_set.values().metadata.as_ref() {
+            let old_meta: ChartOfAccountsIntegrationMeta =
+                serde_json::from_value(old_meta.clone()).expect("Could not deserialize metadata");
+            old_child_account_set_ids_from_chart = old_parent_ids_getter(old_meta);
+            for old_id in old_child_account_set_ids_from_chart.iter() {
+                if !child_account_set_ids_from_chart.contains(old_id) {
+                    self.cala
+                        .account_sets()
+                        .remove_member_in_op(op, internal_account_set_id, *old_id)
+                        .await?;
+                }
+            }
+        }
+
+        for child_id in child_account_set_ids_from_chart {
+            if old_child_account_set_ids_from_chart.contains(child_id) {
+                continue;
+            }
+            self.cala
+                .account_sets()
+                .add_member_in_op(op, internal_account_set_id, *child_id)
+                .await?;
+        }
+        let mut update = AccountSetUpdate::default();
+        update
+            .metadata(new_meta)
+            .expect("Could not update metadata");
+        internal_account_set.update(update);
+        self.cala
+            .account_sets()
+            .persist_in_op(op, &mut internal_account_set)
+            .await?;
+
+        Ok(())
+    }
+
+    pub async fn create(
+        &self,
+        op: es_entity::DbOp<'_>,
+        reference: &str,
+    ) -> Result<CashFlowStatementIds, CashFlowStatementLedgerError> {
+        let mut op = self.cala.ledger_operation_from_db_op(op);
+
+        // Every section is credit-normal so that its balance change reads
+        // directly as cash generated (positive) or used (negative).
+        let statement_id = self
+            .create_unique_account_set(&mut op, reference, reference, DebitOrCredit::Credit)
+            .await?;
+
+        let operating_id = self
+            .create_account_set(
+                &mut op,
+                OPERATING_ACTIVITIES_NAME,
+                DebitOrCredit::Credit,
+                vec![statement_id],
+            )
+            .await?;
+        let net_income_id = self
+            .create_account_set(
+                &mut op,
+                NET_INCOME_NAME,
+                DebitOrCredit::Credit,
+                vec![operating_id],
+            )
+            .await?;
+        let working_capital_id = self
+            .create_account_set(
+                &mut op,
+                WORKING_CAPITAL_NAME,
+                DebitOrCredit::Credit,
+                vec![operating_id],
+            )
+            .await?;
+        let investing_id = self
+            .create_account_set(
+                &mut op,
+                INVESTING_ACTIVITIES_NAME,
+                DebitOrCredit::Credit,
+                vec![statement_id],
+            )
+            .await?;
+        let financing_id = self
+            .create_account_set(
+                &mut op,
+                FINANCING_ACTIVITIES_NAME,
+                DebitOrCredit::Credit,
+                vec![statement_id],
+            )
+            .await?;
+
+        // Cash is kept outside the statement so that it does not offset the
+        // sections it is reconciled against.
+        let cash_id = self
+            .create_unique_account_set(
+                &mut op,
+                &cash_external_id(reference),
+                CASH_NAME,
+                DebitOrCredit::Debit,
+            )
+            .await?;
+
+        op.commit().await?;
+
+        Ok(CashFlowStatementIds {
+            id: statement_id,
+            operating: operating_id,
+            net_income: net_income_id,
+            working_capital: working_capital_id,
+            investing: investing_id,
+            financing: financing_id,
+            cash: cash_id,
+        })
+    }
+
+    pub async fn get_cash_flow_statement(
+        &self,
+        reference: String,
+        from: NaiveDate,
+        until: Option<NaiveDate>,
+    ) -> Result<CashFlowStatement, CashFlowStatementLedgerError> {
+        let ids = self.get_ids_from_reference(reference).await?;
+        let statement = self.cala.account_sets().find(ids.id).await?.into_values();
+
+        let working_capital_lines = self.get_member_account_sets(ids.working_capital).await?;
+        let investing_lines = self.get_member_account_sets(ids.investing).await?;
+        let financing_lines = self.get_member_account_sets(ids.financing).await?;
+
+        let all_account_set_ids = [
+            ids.operating,
+            ids.net_income,
+            ids.working_capital,
+            ids.investing,
+            ids.financing,
+            ids.cash,
+        ]
+        .into_iter()
+        .chain(
+            working_capital_lines
+                .iter()
+                .chain(investing_lines.iter())
+                .chain(financing_lines.iter())
+                .map(|account_set| account_set.values().id),
+        )
+        .collect();
+
+        let balances_by_id = self
+            .get_balances_by_id(all_account_set_ids, from, until)
+            .await?;
+        let balances = StatementBalances {
+            journal_id: self.journal_id,
+            balances_by_id,
+        };
+
+        let net_income = CashFlowLine {
+            id: ids.net_income.into(),
+            name: NET_INCOME_NAME.to_string(),
+            amount: balances.cash_effect(ids.net_income),
+        };
+        let operating_activities = CashFlowSection {
+            id: ids.operating.into(),
+            name: OPERATING_ACTIVITIES_NAME.to_string(),
+            total: balances.cash_effect(ids.operating),
+            lines: balances.lines(working_capital_lines),
+        };
+        let investing_activities = CashFlowSection {
+            id: ids.investing.into(),
+            name: INVESTING_ACTIVITIES_NAME.to_string(),
+            total: balances.cash_effect(ids.investing),
+            lines: balances.lines(investing_lines),
+        };
+        let financing_activities = CashFlowSection {
+            id: ids.financing.into(),
+            name: FINANCING_ACTIVITIES_NAME.to_string(),
+            total: balances.cash_effect(ids.financing),
+            lines: balances.lines(financing_lines),
+        };
+
+        Ok(CashFlowStatement {
+            id: statement.id.into(),
+            name: statement.name,
+            net_income,
+            operating_activities,
+            investing_activities,
+            financing_activities,
+            opening_cash: balances.settled(ids.cash, |range| &range.open),
+            closing_cash: balances.settled(ids.cash, |range| &range.close),
+        })
+    }
+
+    pub async fn get_chart_of_accounts_integration_config(
+        &self,
+        reference: String,
+    ) -> Result<Option<ChartOfAccountsIntegrationConfig>, CashFlowStatementLedgerError> {
+        let account_set_id = self
+            .get_ids_from_reference(reference)
+            .await?
+            .account_set_id_for_config();
+
+        let account_set = self.cala.account_sets().find(account_set_id).await?;
+        if let Some(meta) = account_set.values().metadata.as_ref() {
+            let meta: ChartOfAccountsIntegrationMeta =
+                serde_json::from_value(meta.clone()).expect("Could not deserialize metadata");
+            Ok(Some(meta.config))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn get_ids_from_reference(
+        &self,
+        reference: String,
+    ) -> Result<CashFlowStatementIds, CashFlowStatementLedgerError> {
+        let cash_id = self
+            .cala
+            .account_sets()
+            .find_by_external_id(cash_external_id(&reference))
+            .await?
+            .id;
+        let statement_id = self
+            .cala
+            .account_sets()
+            .find_by_external_id(reference)
+            .await?
+            .id;
+
+        let statement_members = self
+            .get_member_account_set_ids_and_names(statement_id)
+            .await?;
+        let member = |members: &HashMap<String, AccountSetId>, name: &str| {
+            members
+                .get(name)
+                .copied()
+                .ok_or(CashFlowStatementLedgerError::NotFound(name.to_string()))
+        };
+
+        let operating_id = member(&statement_members, OPERATING_ACTIVITIES_NAME)?;
+        let investing_id = member(&statement_members, INVESTING_ACTIVITIES_NAME)?;
+        let financing_id = member(&statement_members, FINANCING_ACTIVITIES_NAME)?;
+
+        let operating_members = self
+            .get_member_account_set_ids_and_names(operating_id)
+            .await?;
+        let net_income_id = member(&operating_members, NET_INCOME_NAME)?;
+        let working_capital_id = member(&operating_members, WORKING_CAPITAL_NAME)?;
+
+        Ok(CashFlowStatementIds {
+            id: statement_id,
+            operating: operating_id,
+            net_income: net_income_id,
+            working_capital: working_capital_id,
+            investing: investing_id,
+            financing: financing_id,
+            cash: cash_id,
+        })
+    }
+}
+
+fn cash_external_id(reference: &str) -> String {
+    format!("{reference}:{CASH_NAME}")
+}
+
+struct StatementBalances {
+    journal_id: JournalId,
+    balances_by_id: HashMap<BalanceId, CalaBalanceRange>,
+}
+
+impl StatementBalances {
+    fn range(&self, id: AccountSetId, currency: Currency) -> Option<&CalaBalanceRange> {
+        self.balances_by_id
+            .get(&(self.journal_id, id.into(), currency))
+    }
+
+    /// Credits increase cash on the other side of the entry and debits
+    /// decrease it, whatever the normal balance of the account.
+    fn cash_effect(&self, id: AccountSetId) -> CashFlowAmount {
+        let effect = |currency| {
+            self.range(id, currency)
+                .map(|range| {
+                    range.period.details.settled.cr_balance
+                        - range.period.details.settled.dr_balance
+                })
+                .unwrap_or(Decimal::ZERO)
+        };
+        CashFlowAmount {
+            usd: effect(Currency::USD),
+            btc: effect(Currency::BTC),
+        }
+    }
+
+    fn settled(
+        &self,
+        id: AccountSetId,
+        balance: impl Fn(&CalaBalanceRange) -> &CalaAccountBalance,
+    ) -> CashFlowAmount {
+        let settled = |currency| {
+            self.range(id, currency)
+                .map(|range| balance(range).settled())
+                .unwrap_or(Decimal::ZERO)
+        };
+        CashFlowAmount {
+            usd: settled(Currency::USD),
+            btc: settled(Currency::BTC),
+        }
+    }
+
+    fn lines(&self, account_sets: Vec<AccountSet>) -> Vec<CashFlowLine> {
+        account_sets
+            .into_iter()
+            .map(|account_set| {
+                let values = account_set.into_values();
+                CashFlowLine {
+                    id: values.id.into(),
+                    amount: self.cash_effect(values.id),
+                    name: values.name,
+                }
+            })
+            .collect()
+    }
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct ChartOfAccountsIntegrationMeta {
+    pub config: ChartOfAccountsIntegrationConfig,
+    pub audit_info: AuditInfo,
+
+    pub cash_child_account_set_id_from_chart: AccountSetId,
+    pub net_income_child_account_set_ids_from_chart: Vec<AccountSetId>,
+    pub working_capital_child_account_set_ids_from_chart: Vec<AccountSetId>,
+    pub investing_child_account_set_ids_from_chart: Vec<AccountSetId>,
+    pub financing_child_account_set_ids_from_chart: Vec<AccountSetId>,
+}
@@ -0,0 +1,307 @@
+mod chart_of_accounts_integration;
+pub mod error;
+pub mod ledger;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use cala_ledger::CalaLedger;
+
+use crate::{
+    LedgerAccountId,
+    chart_of_accounts::Chart,
+    primitives::{CalaAccountSetId, CoreAccountingAction, CoreAccountingObject},
+};
+
+pub use chart_of_accounts_integration::ChartOfAccountsIntegrationConfig;
+use error::*;
+use ledger::*;
+
+pub(crate) const OPERATING_ACTIVITIES_NAME: &str = "Operating Activities";
+pub(crate) const NET_INCOME_NAME: &str = "Net Income";
+pub(crate) const WORKING_CAPITAL_NAME: &str = "Changes in Working Capital";
+pub(crate) const INVESTING_ACTIVITIES_NAME: &str = "Investing Activities";
+pub(crate) const FINANCING_ACTIVITIES_NAME: &str = "Financing Activities";
+pub(crate) const CASH_NAME: &str = "Cash and Cash Equivalents";
+
+#[derive(Clone, Copy)]
+pub struct CashFlowStatementIds {
+    pub id: CalaAccountSetId,
+    pub operating: CalaAccountSetId,
+    pub net_income: CalaAccountSetId,
+    pub working_capital: CalaAccountSetId,
+    pub investing: CalaAccountSetId,
+    pub financing: CalaAccountSetId,
+    pub cash: CalaAccountSetId,
+}
+
+impl CashFlowStatementIds {
+    fn internal_ids(&self) -> Vec<CalaAccountSetId> {
+        let Self {
+            id: _id,
+            operating: _operating,
+            net_income,
+            working_capital,
+            investing,
+            financing,
+            cash,
+        } = self;
+
+        vec![*net_income, *working_capital, *investing, *financing, *cash]
+    }
+
+    fn account_set_id_for_config(&self) -> CalaAccountSetId {
+        self.net_income
+    }
+}
+
+#[derive(Clone)]
+pub struct CashFlowStatements<Perms>
+where
+    Perms: PermissionCheck,
+{
+    pool: sqlx::PgPool,
+    authz: Perms,
+    cash_flow_statement_ledger: CashFlowStatementLedger,
+}
+
+impl<Perms> CashFlowStatements<Perms>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreAccountingAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreAccountingObject>,
+{
+    pub fn new(
+        pool: &sqlx::PgPool,
+        authz: &Perms,
+        cala: &CalaLedger,
+        journal_id: cala_ledger::JournalId,
+    ) -> Self {
+        let cash_flow_statement_ledger = CashFlowStatementLedger::new(cala, journal_id);
+
+        Self {
+            pool: pool.clone(),
+            cash_flow_statement_ledger,
+            authz: authz.clone(),
+        }
+    }
+
+    pub async fn create_cash_flow_statement(
+        &self,
+        name: String,
+    ) -> Result<(), CashFlowStatementError> {
+        let mut op = es_entity::DbOp::init(&self.pool).await?;
+
+        self.authz
+            .audit()
+            .record_system_entry_in_tx(
+                op.tx(),
+                CoreAccountingObject::all_cash_flow_statement(),
+                CoreAccountingAction::CASH_FLOW_STATEMENT_CREATE,
+            )
+            .await?;
+
+        match self.cash_flow_statement_ledger.create(op, &name).await {
+            Ok(_) => Ok(()),
+            Err(e) if e.account_set_exists() => Ok(()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub async fn get_chart_of_accounts_integration_config(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        reference: String,
+    ) -> Result<Option<ChartOfAccountsIntegrationConfig>, CashFlowStatementError> {
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_cash_flow_statement_configuration(),
+                CoreAccountingAction::CASH_FLOW_STATEMENT_CONFIGURATION_READ,
+            )
+            .await?;
+        Ok(self
+            .cash_flow_statement_ledger
+            .get_chart_of_accounts_integration_config(reference)
+            .await?)
+    }
+
+    pub async fn set_chart_of_accounts_integration_config(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        reference: String,
+        chart: &Chart,
+        config: ChartOfAccountsIntegrationConfig,
+    ) -> Result<ChartOfAccountsIntegrationConfig, CashFlowStatementError> {
+        if chart.id != config.chart_of_accounts_id {
+            return Err(CashFlowStatementError::ChartIdMismatch);
+        }
+        if let Some(code) = config.first_duplicate_code() {
+            return Err(CashFlowStatementError::DuplicateCode(code.clone()));
+        }
+
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_cash_flow_statement_configuration(),
+                CoreAccountingAction::CASH_FLOW_STATEMENT_CONFIGURATION_UPDATE,
+            )
+            .await?;
+
+        if self
+            .cash_flow_statement_ledger
+            .get_chart_of_accounts_integration_config(reference.to_string())
+            .await?
+            .is_some()
+        {
+            return Err(CashFlowStatementError::ChartConfigAlreadyExists);
+        }
+
+        let account_set_ids_from_chart = |codes: &[crate::primitives::AccountCode]| {
+            codes
+                .iter()
+                .map(|code| chart.account_set_id_from_code(code))
+                .collect::<Result<Vec<_>, _>>()
+        };
+
+        let cash_child_account_set_id_from_chart =
+            chart.account_set_id_from_code(&config.chart_of_accounts_cash_code)?;
+        let net_income_child_account_set_ids_from_chart = account_set_ids_from_chart(&[
+            config.chart_of_accounts_revenue_code.clone(),
+            config.chart_of_accounts_cost_of_revenue_code.clone(),
+            config.chart_of_accounts_expenses_code.clone(),
+        ])?;
+        let working_capital_child_account_set_ids_from_chart =
+            account_set_ids_from_chart(&config.chart_of_accounts_operating_codes)?;
+        let investing_child_account_set_ids_from_chart =
+            account_set_ids_from_chart(&config.chart_of_accounts_investing_codes)?;
+        let financing_child_account_set_ids_from_chart =
+            account_set_ids_from_chart(&config.chart_of_accounts_financing_codes)?;
+
+        let charts_integration_meta = ChartOfAccountsIntegrationMeta {
+            audit_info,
+            config: config.clone(),
+
+            cash_child_account_set_id_from_chart,
+            net_income_child_account_set_ids_from_chart,
+            working_capital_child_account_set_ids_from_chart,
+            investing_child_account_set_ids_from_chart,
+            financing_child_account_set_ids_from_chart,
+        };
+
+        self.cash_flow_statement_ledger
+            .attach_chart_of_accounts_account_sets(reference, charts_integration_meta)
+            .await?;
+
+        Ok(config)
+    }
+
+    pub async fn cash_flow_statement(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        reference: String,
+        from: NaiveDate,
+        until: Option<NaiveDate>,
+    ) -> Result<CashFlowStatement, CashFlowStatementError> {
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_cash_flow_statement(),
+                CoreAccountingAction::CASH_FLOW_STATEMENT_READ,
+            )
+            .await?;
+
+        self.find_cash_flow_statement(reference, from, until).await
+    }
+
+    pub(crate) async fn find_cash_flow_statement(
+        &self,
+        reference: String,
+        from: NaiveDate,
+        until: Option<NaiveDate>,
+    ) -> Result<CashFlowStatement, CashFlowStatementError> {
+        Ok(self
+            .cash_flow_statement_ledger
+            .get_cash_flow_statement(reference, from, until)
+            .await?)
+    }
+}
+
+/// A cash movement over the statement period, in USD and BTC.
+/// Positive amounts are inflows of cash and negative amounts are outflows.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct CashFlowAmount {
+    pub usd: Decimal,
+    pub btc: Decimal,
+}
+
+impl std::ops::Add for CashFlowAmount {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            usd: self.usd + other.usd,
+            btc: self.btc + other.btc,
+        }
+    }
+}
+
+impl std::ops::Sub for CashFlowAmount {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Self {
+            usd: self.usd - other.usd,
+            btc: self.btc - other.btc,
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct CashFlowLine {
+    pub id: LedgerAccountId,
+    pub name: String,
+    pub amount: CashFlowAmount,
+}
+
+#[derive(Clone, Debug)]
+pub struct CashFlowSection {
+    pub id: LedgerAccountId,
+    pub name: String,
+    pub total: CashFlowAmount,
+    pub lines: Vec<CashFlowLine>,
+}
+
+#[derive(Clone, Debug)]
+pub struct CashFlowStatement {
+    pub id: LedgerAccountId,
+    pub name: String,
+    pub net_income: CashFlowLine,
+    /// Net income plus the working-capital adjustments listed in `lines`.
+    pub operating_activities: CashFlowSection,
+    pub investing_activities: CashFlowSection,
+    pub financing_activities: CashFlowSection,
+    pub opening_cash: CashFlowAmount,
+    pub closing_cash: CashFlowAmount,
+}
+
+impl CashFlowStatement {
+    pub fn net_change_in_cash(&self) -> CashFlowAmount {
+        self.operating_activities.total
+            + self.investing_activities.total
+            + self.financing_activities.total
+    }
+
+    /// Cash movement not explained by the mapped sections, which is non-zero
+    /// when balance sheet accounts are missing from the configuration.
+    pub fn unexplained_change_in_cash(&self) -> CashFlowAmount {
+        self.closing_cash - self.opening_cash - self.net_change_in_cash()
+    }
+
+    pub fn is_reconciled(&self) -> bool {
+        self.unexplained_change_in_cash() == CashFlowAmount::default()
+    }
+}
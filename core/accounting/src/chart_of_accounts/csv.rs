@@ -1,17 +1,39 @@
 use cala_ledger::DebitOrCredit;
-use csv::{ReaderBuilder, Trim};
+use csv::{ReaderBuilder, Trim, Writer};
 use std::io::Cursor;
 
 use crate::primitives::{
-    AccountCodeSection, AccountCodeSectionParseError, AccountName, AccountSpec,
+    AccountCode, AccountCodeSection, AccountCodeSectionParseError, AccountName, AccountSpec,
 };
 
+use super::{Chart, tree::TreeNode};
+
 use thiserror::Error;
 
 #[derive(Error, Debug)]
 #[error("CsvParseError")]
 pub struct CsvParseError;
 
+#[derive(Error, Debug)]
+#[error("CsvExportError: {0}")]
+pub struct CsvExportError(String);
+
+const SECTION_COLUMNS: usize = 3;
+const NORMAL_BALANCE_TYPE_COLUMN: usize = 4;
+const PARENT_COLUMN: usize = 5;
+const STATUS_COLUMN: usize = 6;
+const INACTIVE_STATUS: &str = "Inactive";
+
+/// An account row of the CSV. Rows for accounts already in the chart are
+/// applied as updates to their name, parent and status. Deactivation cannot
+/// be undone, so a row not marked `Inactive` leaves the status of its node
+/// as it is.
+#[derive(Debug, Clone)]
+pub struct CsvAccount {
+    pub spec: AccountSpec,
+    pub inactive: bool,
+}
+
 pub struct CsvParser {
     data: String,
 }
@@ -20,7 +42,19 @@ impl CsvParser {
         Self { data }
     }
 
+    #[cfg(test)]
     pub fn account_specs(self) -> Result<Vec<AccountSpec>, CsvParseError> {
+        Ok(self
+            .accounts()?
+            .into_iter()
+            .map(|account| account.spec)
+            .collect())
+    }
+
+    /// Parses the accounts of the CSV. Besides the code sections, name and
+    /// normal balance type, a row can name its parent code explicitly and be
+    /// marked as `Inactive`.
+    pub fn accounts(self) -> Result<Vec<CsvAccount>, CsvParseError> {
         let mut rdr = ReaderBuilder::new()
             .flexible(true)
             .trim(Trim::All)
@@ -28,6 +62,7 @@ impl CsvParser {
             .from_reader(Cursor::new(self.data));
 
         let mut specs: Vec<AccountSpec> = vec![];
+        let mut inactive: Vec<bool> = vec![];
         for result in rdr.records() {
             match result {
                 Ok(record) => {
@@ -38,27 +73,36 @@ impl CsvParser {
                     }
 
                     let normal_balance_type = record
-                        .get(4)
-                        .and_then(|b| b.parse::<DebitOrCredit>().ok())
-                        .unwrap_or_default();
+                        .get(NORMAL_BALANCE_TYPE_COLUMN)
+                        .and_then(|b| b.parse::<DebitOrCredit>().ok());
+                    let explicit_parent = match record.get(PARENT_COLUMN) {
+                        Some(parent) if !parent.is_empty() => {
+                            Some(parent.parse::<AccountCode>().map_err(|_| CsvParseError)?)
+                        }
+                        _ => None,
+                    };
+                    let is_inactive = record
+                        .get(STATUS_COLUMN)
+                        .is_some_and(|status| status.eq_ignore_ascii_case(INACTIVE_STATUS));
 
                     for (idx, field) in record.iter().enumerate() {
                         if let Ok(category) = field.parse::<AccountName>() {
+                            inactive.push(is_inactive);
                             if let Some(s) = specs.iter().rposition(|s| s.code.is_parent(&sections))
                             {
                                 specs.push(AccountSpec::new(
-                                    Some(specs[s].code.clone()),
+                                    explicit_parent.or_else(|| Some(specs[s].code.clone())),
                                     sections,
                                     category,
-                                    specs[s].normal_balance_type,
+                                    normal_balance_type.unwrap_or(specs[s].normal_balance_type),
                                 ));
                                 break;
                             }
                             specs.push(AccountSpec::new(
-                                None,
+                                explicit_parent,
                                 sections,
                                 category,
-                                normal_balance_type,
+                                normal_balance_type.unwrap_or_default(),
                             ));
                             break;
                         }
@@ -88,7 +132,107 @@ impl CsvParser {
             }
         }
 
-        Ok(specs)
+        Ok(specs
+            .into_iter()
+            .zip(inactive)
+            .map(|(spec, inactive)| CsvAccount { spec, inactive })
+            .collect())
+    }
+}
+
+/// Writes a chart in the layout read by `CsvParser`, so that importing the
+/// export into the same chart changes nothing.
+pub struct CsvExporter<'a> {
+    chart: &'a Chart,
+}
+
+impl<'a> CsvExporter<'a> {
+    pub fn new(chart: &'a Chart) -> Self {
+        Self { chart }
+    }
+
+    pub fn to_csv(&self) -> Result<Vec<u8>, CsvExportError> {
+        let mut wtr = Writer::from_writer(vec![]);
+        for record in self.records()? {
+            wtr.write_record(&record)
+                .map_err(|e| CsvExportError(e.to_string()))?;
+        }
+        wtr.into_inner().map_err(|e| CsvExportError(e.to_string()))
+    }
+
+    /// One record per node, depth first. Every nested node names its parent
+    /// explicitly. Top level nodes whose code would be read as the child of an
+    /// earlier row come first, longest code first, so they stay at the top level.
+    fn records(&self) -> Result<Vec<Vec<String>>, CsvExportError> {
+        let mut nodes = Vec::new();
+        for node in self.chart.chart().children {
+            flatten(node, &mut nodes);
+        }
+
+        let mut detached_roots: Vec<&TreeNode> = Vec::new();
+        let mut rows: Vec<&TreeNode> = Vec::new();
+        for node in nodes.iter() {
+            let is_read_as_child = rows
+                .iter()
+                .any(|row| row.code.is_parent(node.code.sections()));
+            if node.parent.is_none() && is_read_as_child {
+                detached_roots.push(node);
+            } else {
+                rows.push(node);
+            }
+        }
+        detached_roots.sort_by(|a, b| b.code.cmp(&a.code));
+
+        detached_roots
+            .into_iter()
+            .chain(rows)
+            .map(|node| self.record(node))
+            .collect()
+    }
+
+    fn record(&self, node: &TreeNode) -> Result<Vec<String>, CsvExportError> {
+        let sections = node.code.sections();
+        if sections.len() > SECTION_COLUMNS {
+            return Err(CsvExportError(format!(
+                "account code {} has more than {SECTION_COLUMNS} sections",
+                node.code
+            )));
+        }
+        let (spec, _) = self
+            .chart
+            .account_spec(&node.code)
+            .expect("tree node exists in chart");
+
+        let mut record: Vec<String> = sections.iter().map(|s| s.to_string()).collect();
+        record.resize(SECTION_COLUMNS, String::new());
+        record.push(node.name.to_string());
+        record.push(
+            match spec.normal_balance_type {
+                DebitOrCredit::Debit => "Debit",
+                DebitOrCredit::Credit => "Credit",
+            }
+            .to_string(),
+        );
+        record.push(
+            node.parent
+                .as_ref()
+                .map(|parent| parent.to_string())
+                .unwrap_or_default(),
+        );
+        record.push(if node.deactivated {
+            INACTIVE_STATUS.to_string()
+        } else {
+            String::new()
+        });
+        Ok(record)
+    }
+}
+
+fn flatten(mut node: TreeNode, nodes: &mut Vec<TreeNode>) {
+    let children = std::mem::take(&mut node.children);
+    nodes.push(node);
+    for child in children {
+        flatten(child, nodes);
     }
 }
 
@@ -171,4 +315,164 @@ mod tests {
         assert_eq!(Some(&specs[2].code), specs[4].parent.as_ref());
         assert_eq!(&specs[4].code.to_string(), "11.01.0102");
     }
+
+    #[test]
+    fn parse_explicit_parent_and_status() {
+        let data = r#"
+        1,,,Assets,Debit,,
+        2,,,Liabilities,Credit,,
+        11,,,Current Assets,,2,Inactive
+        "#;
+
+        let parser = CsvParser::new(data.to_string());
+        let accounts = parser.accounts().unwrap();
+
+        assert_eq!(accounts.len(), 3);
+        assert!(!accounts[0].inactive);
+        assert_eq!(
+            accounts[2].spec.parent.as_ref().map(|p| p.to_string()),
+            Some("2".to_string())
+        );
+        assert!(accounts[2].inactive);
+    }
+
+    mod export {
+        use audit::{AuditEntryId, AuditInfo};
+        use es_entity::*;
+
+        use crate::{
+            chart_of_accounts::{NewChart, apply_csv_accounts},
+            primitives::{CalaJournalId, ChartId},
+        };
+
+        use super::*;
+
+        fn dummy_audit_info() -> AuditInfo {
+            AuditInfo {
+                audit_entry_id: AuditEntryId::from(1),
+                sub: "sub".to_string(),
+            }
+        }
+
+        fn new_chart() -> Chart {
+            let new_chart = NewChart::builder()
+                .id(ChartId::new())
+                .name("Test Chart".to_string())
+                .reference("ref-01".to_string())
+                .audit_info(dummy_audit_info())
+                .build()
+                .unwrap();
+            Chart::try_from_events(new_chart.into_events()).unwrap()
+        }
+
+        fn export(chart: &Chart) -> String {
+            String::from_utf8(CsvExporter::new(chart).to_csv().unwrap()).unwrap()
+        }
+
+        fn chart_with_updates() -> Chart {
+            let mut chart = new_chart();
+            let data = r#"
+            1,,,Assets,Debit,,
+            11,,,Current Assets,,,
+            11,01,,Cash,,,
+            11,02,,Allowance for Losses,Credit,,
+            2,,,Liabilities,Credit,,
+            21,,,Deposits,,,
+            21,01,,Retail Deposits,,,
+            "#;
+            let accounts = CsvParser::new(data.to_string()).accounts().unwrap();
+            apply_csv_accounts(
+                &mut chart,
+                accounts,
+                CalaJournalId::new(),
+                dummy_audit_info(),
+            )
+            .unwrap();
+
+            let code = |s: &str| s.parse::<AccountCode>().unwrap();
+            let _ = chart
+                .rename_node(
+                    &code("11.01"),
+                    "Cash and Equivalents".parse().unwrap(),
+                    dummy_audit_info(),
+                )
+                .unwrap();
+            let _ = chart
+                .move_node(&code("21"), None, dummy_audit_info())
+                .unwrap();
+            let _ = chart
+                .deactivate_node(&code("11.02"), dummy_audit_info())
+                .unwrap();
+            chart
+        }
+
+        #[test]
+        fn reimporting_export_is_a_no_op() {
+            let mut chart = chart_with_updates();
+            let data = export(&chart);
+
+            let accounts = CsvParser::new(data).accounts().unwrap();
+            let (diff, updates) = apply_csv_accounts(
+                &mut chart,
+                accounts,
+                CalaJournalId::new(),
+                dummy_audit_info(),
+            )
+            .unwrap();
+
+            assert!(diff.is_empty());
+            assert!(updates.new_account_sets.is_empty());
+            assert!(updates.renames.is_empty());
+            assert!(updates.moves.is_empty());
+        }
+
+        #[test]
+        fn export_imports_into_an_identical_chart() {
+            let chart = chart_with_updates();
+            let data = export(&chart);
+
+            let mut copy = new_chart();
+            let accounts = CsvParser::new(data.clone()).accounts().unwrap();
+            apply_csv_accounts(
+                &mut copy,
+                accounts,
+                CalaJournalId::new(),
+                dummy_audit_info(),
+            )
+            .unwrap();
+
+            assert_eq!(export(&copy), data);
+            let allowance = copy
+                .account_spec(&"11.02".parse().unwrap())
+                .map(|(spec, _)| spec.normal_balance_type);
+            assert_eq!(allowance, Some(DebitOrCredit::Credit));
+        }
+
+        #[test]
+        fn row_without_status_keeps_node_deactivated() {
+            let mut chart = chart_with_updates();
+            let data = r#"
+            11,02,,Allowance for Losses,Credit,11,
+            "#;
+
+            let accounts = CsvParser::new(data.to_string()).accounts().unwrap();
+            let (diff, _) = apply_csv_accounts(
+                &mut chart,
+                accounts,
+                CalaJournalId::new(),
+                dummy_audit_info(),
+            )
+            .unwrap();
+
+            assert!(diff.is_empty());
+            assert!(chart.is_deactivated(&"11.02".parse().unwrap()));
+        }
+    }
+
+    #[test]
+    fn parse_invalid_explicit_parent() {
+        let data = r#"1,,,Assets,Debit,not a code,"#;
+        let parser = CsvParser::new(data.to_string());
+        assert!(parser.accounts().is_err());
+    }
 }
@@ -0,0 +1,33 @@
+use crate::primitives::{AccountCode, AccountName, AccountSpec};
+
+#[derive(Debug, Clone)]
+pub struct NodeRename {
+    pub code: AccountCode,
+    pub from: AccountName,
+    pub to: AccountName,
+}
+
+#[derive(Debug, Clone)]
+pub struct NodeParentChange {
+    pub code: AccountCode,
+    pub from: Option<AccountCode>,
+    pub to: Option<AccountCode>,
+}
+
+/// The changes a CSV import makes to a chart of accounts.
+#[derive(Debug, Clone, Default)]
+pub struct ChartImportDiff {
+    pub added: Vec<AccountSpec>,
+    pub renamed: Vec<NodeRename>,
+    pub moved: Vec<NodeParentChange>,
+    pub deactivated: Vec<AccountCode>,
+}
+
+impl ChartImportDiff {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty()
+            && self.renamed.is_empty()
+            && self.moved.is_empty()
+            && self.deactivated.is_empty()
+    }
+}
@@ -1,7 +1,7 @@
 use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use audit::AuditInfo;
 
@@ -26,6 +26,28 @@ pub enum ChartEvent {
         ledger_account_set_id: CalaAccountSetId,
         audit_info: AuditInfo,
     },
+    NodeRenamed {
+        code: AccountCode,
+        name: AccountName,
+        audit_info: AuditInfo,
+    },
+    NodeMoved {
+        code: AccountCode,
+        parent: Option<AccountCode>,
+        audit_info: AuditInfo,
+    },
+    NodeDeactivated {
+        code: AccountCode,
+        audit_info: AuditInfo,
+    },
+}
+
+/// The ledger account set memberships to change when a node is moved.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct NodeMove {
+    pub account_set_id: CalaAccountSetId,
+    pub from_parent: Option<CalaAccountSetId>,
+    pub to_parent: Option<CalaAccountSetId>,
 }
 
 #[derive(EsEntity, Builder)]
@@ -35,6 +57,7 @@ pub struct Chart {
     pub reference: String,
     pub name: String,
     all_accounts: HashMap<AccountCode, (AccountSpec, CalaAccountSetId)>,
+    deactivated: HashSet<AccountCode>,
 
     events: EntityEvents<ChartEvent>,
 }
@@ -64,6 +87,131 @@ impl Chart {
         Idempotent::Executed((parent, ledger_account_set_id))
     }
 
+    pub fn rename_node(
+        &mut self,
+        code: &AccountCode,
+        name: AccountName,
+        audit_info: AuditInfo,
+    ) -> Result<Idempotent<CalaAccountSetId>, ChartOfAccountsError> {
+        let (spec, account_set_id) = self
+            .all_accounts
+            .get_mut(code)
+            .ok_or_else(|| ChartOfAccountsError::CodeNotFoundInChart(code.clone()))?;
+        if spec.name == name {
+            return Ok(Idempotent::Ignored);
+        }
+        spec.name = name.clone();
+        let account_set_id = *account_set_id;
+        self.events.push(ChartEvent::NodeRenamed {
+            code: code.clone(),
+            name,
+            audit_info,
+        });
+        Ok(Idempotent::Executed(account_set_id))
+    }
+
+    /// Moves the node at `code` under `parent`, or to the top level of the chart.
+    pub fn move_node(
+        &mut self,
+        code: &AccountCode,
+        parent: Option<AccountCode>,
+        audit_info: AuditInfo,
+    ) -> Result<Idempotent<NodeMove>, ChartOfAccountsError> {
+        let (spec, account_set_id) = self
+            .account_spec(code)
+            .ok_or_else(|| ChartOfAccountsError::CodeNotFoundInChart(code.clone()))?;
+        if spec.parent == parent {
+            return Ok(Idempotent::Ignored);
+        }
+        let account_set_id = *account_set_id;
+        let from_parent = spec
+            .parent
+            .as_ref()
+            .and_then(|p| self.all_accounts.get(p))
+            .map(|(_, id)| *id);
+
+        let to_parent = match parent.as_ref() {
+            Some(parent_code) => {
+                let (_, parent_id) = self.account_spec(parent_code).ok_or_else(|| {
+                    ChartOfAccountsError::ParentNotFoundInChart(parent_code.clone())
+                })?;
+                if self.is_deactivated(parent_code) {
+                    return Err(ChartOfAccountsError::NodeDeactivated(parent_code.clone()));
+                }
+                if parent_code == code
+                    || self
+                        .ancestors::<CalaAccountSetId>(parent_code)
+                        .contains(&account_set_id)
+                {
+                    return Err(ChartOfAccountsError::MoveUnderOwnDescendant(code.clone()));
+                }
+                Some(*parent_id)
+            }
+            None => None,
+        };
+
+        self.all_accounts
+            .get_mut(code)
+            .expect("node exists")
+            .0
+            .parent
+            .clone_from(&parent);
+        self.events.push(ChartEvent::NodeMoved {
+            code: code.clone(),
+            parent,
+            audit_info,
+        });
+        Ok(Idempotent::Executed(NodeMove {
+            account_set_id,
+            from_parent,
+            to_parent,
+        }))
+    }
+
+    /// A deactivated node stays in the chart with its history, but can no
+    /// longer be posted to.
+    pub fn deactivate_node(
+        &mut self,
+        code: &AccountCode,
+        audit_info: AuditInfo,
+    ) -> Result<Idempotent<CalaAccountSetId>, ChartOfAccountsError> {
+        let (_, account_set_id) = self
+            .account_spec(code)
+            .ok_or_else(|| ChartOfAccountsError::CodeNotFoundInChart(code.clone()))?;
+        let account_set_id = *account_set_id;
+        if !self.deactivated.insert(code.clone()) {
+            return Ok(Idempotent::Ignored);
+        }
+        self.events.push(ChartEvent::NodeDeactivated {
+            code: code.clone(),
+            audit_info,
+        });
+        Ok(Idempotent::Executed(account_set_id))
+    }
+
+    /// Whether the node at `code` or any of its ancestors was deactivated.
+    pub fn is_deactivated(&self, code: &AccountCode) -> bool {
+        let mut current = Some(code);
+        while let Some(code) = current {
+            if self.deactivated.contains(code) {
+                return true;
+            }
+            current = self
+                .all_accounts
+                .get(code)
+                .and_then(|(spec, _)| spec.parent.as_ref());
+        }
+        false
+    }
+
+    /// The code of the node backed by the ledger account set `account_set_id`.
+    pub fn code_of(&self, account_set_id: CalaAccountSetId) -> Option<&AccountCode> {
+        self.all_accounts
+            .iter()
+            .find(|(_, (_, id))| *id == account_set_id)
+            .map(|(code, _)| code)
+    }
+
     pub fn all_trial_balance_accounts(
         &self,
     ) -> impl Iterator<Item = &(AccountSpec, CalaAccountSetId)> {
@@ -147,7 +295,9 @@ impl Chart {
 impl TryFromEvents<ChartEvent> for Chart {
     fn try_from_events(events: EntityEvents<ChartEvent>) -> Result<Self, EsEntityError> {
         let mut builder = ChartBuilder::default();
-        let mut all_accounts = HashMap::new();
+        let mut all_accounts: HashMap<AccountCode, (AccountSpec, CalaAccountSetId)> =
+            HashMap::new();
+        let mut deactivated = HashSet::new();
         for event in events.iter_all() {
             match event {
                 ChartEvent::Initialized {
@@ -168,9 +318,26 @@ impl TryFromEvents<ChartEvent> for Chart {
                 } => {
                     all_accounts.insert(spec.code.clone(), (spec.clone(), *ledger_account_set_id));
                 }
+                ChartEvent::NodeRenamed { code, name, .. } => {
+                    if let Some((spec, _)) = all_accounts.get_mut(code) {
+                        spec.name = name.clone();
+                    }
+                }
+                ChartEvent::NodeMoved { code, parent, .. } => {
+                    if let Some((spec, _)) = all_accounts.get_mut(code) {
+                        spec.parent.clone_from(parent);
+                    }
+                }
+                ChartEvent::NodeDeactivated { code, .. } => {
+                    deactivated.insert(code.clone());
+                }
             }
         }
-        builder.all_accounts(all_accounts).events(events).build()
+        builder
+            .all_accounts(all_accounts)
+            .deactivated(deactivated)
+            .events(events)
+            .build()
     }
 }
 
@@ -203,3 +370,152 @@ impl IntoEvents<ChartEvent> for NewChart {
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use audit::{AuditEntryId, AuditInfo};
+    use cala_ledger::DebitOrCredit;
+
+    use super::*;
+
+    fn dummy_audit_info() -> AuditInfo {
+        AuditInfo {
+            audit_entry_id: AuditEntryId::from(1),
+            sub: "sub".to_string(),
+        }
+    }
+
+    fn code(s: &str) -> AccountCode {
+        s.parse().unwrap()
+    }
+
+    fn spec(parent: Option<&str>, code_str: &str, name: &str) -> AccountSpec {
+        AccountSpec {
+            parent: parent.map(code),
+            code: code(code_str),
+            name: name.parse().unwrap(),
+            normal_balance_type: DebitOrCredit::Debit,
+        }
+    }
+
+    fn chart_with_nodes() -> Chart {
+        let new_chart = NewChart::builder()
+            .id(ChartId::new())
+            .name("Test Chart".to_string())
+            .reference("ref-01".to_string())
+            .audit_info(dummy_audit_info())
+            .build()
+            .unwrap();
+        let mut chart = Chart::try_from_events(new_chart.into_events()).unwrap();
+        for spec in [
+            spec(None, "1", "Assets"),
+            spec(Some("1"), "11", "Current Assets"),
+            spec(Some("11"), "11.01", "Cash"),
+            spec(None, "2", "Liabilities"),
+        ] {
+            let _ = chart.create_node(&spec, dummy_audit_info());
+        }
+        chart
+    }
+
+    #[test]
+    fn rename_node() {
+        let mut chart = chart_with_nodes();
+        let name: AccountName = "Cash and Equivalents".parse().unwrap();
+
+        assert!(
+            chart
+                .rename_node(&code("11.01"), name.clone(), dummy_audit_info())
+                .unwrap()
+                .did_execute()
+        );
+        assert!(
+            chart
+                .rename_node(&code("11.01"), name.clone(), dummy_audit_info())
+                .unwrap()
+                .was_ignored()
+        );
+
+        let chart = Chart::try_from_events(chart.events).unwrap();
+        assert_eq!(chart.account_spec(&code("11.01")).unwrap().0.name, name);
+    }
+
+    #[test]
+    fn move_node_updates_ancestors() {
+        let mut chart = chart_with_nodes();
+        let liabilities_id = chart.account_spec(&code("2")).unwrap().1;
+        let current_assets_id = chart.account_spec(&code("11")).unwrap().1;
+
+        let node_move = match chart
+            .move_node(&code("11.01"), Some(code("2")), dummy_audit_info())
+            .unwrap()
+        {
+            Idempotent::Executed(node_move) => node_move,
+            Idempotent::Ignored => panic!("move was ignored"),
+        };
+        assert_eq!(node_move.from_parent, Some(current_assets_id));
+        assert_eq!(node_move.to_parent, Some(liabilities_id));
+
+        let chart = Chart::try_from_events(chart.events).unwrap();
+        assert_eq!(
+            chart.ancestors::<CalaAccountSetId>(&code("11.01")),
+            vec![liabilities_id]
+        );
+        let tree = chart.chart();
+        let liabilities = tree.children.iter().find(|n| n.code == code("2")).unwrap();
+        assert_eq!(liabilities.children[0].code, code("11.01"));
+    }
+
+    #[test]
+    fn cannot_move_node_under_its_descendant() {
+        let mut chart = chart_with_nodes();
+        assert!(matches!(
+            chart.move_node(&code("1"), Some(code("11.01")), dummy_audit_info()),
+            Err(ChartOfAccountsError::MoveUnderOwnDescendant(_))
+        ));
+        assert!(matches!(
+            chart.move_node(&code("11"), Some(code("3")), dummy_audit_info()),
+            Err(ChartOfAccountsError::ParentNotFoundInChart(_))
+        ));
+    }
+
+    #[test]
+    fn deactivated_node_stays_in_chart() {
+        let mut chart = chart_with_nodes();
+        assert!(
+            chart
+                .deactivate_node(&code("11.01"), dummy_audit_info())
+                .unwrap()
+                .did_execute()
+        );
+        assert!(
+            chart
+                .deactivate_node(&code("11.01"), dummy_audit_info())
+                .unwrap()
+                .was_ignored()
+        );
+        assert!(matches!(
+            chart.move_node(&code("2"), Some(code("11.01")), dummy_audit_info()),
+            Err(ChartOfAccountsError::NodeDeactivated(_))
+        ));
+
+        let chart = Chart::try_from_events(chart.events).unwrap();
+        assert!(chart.is_deactivated(&code("11.01")));
+        assert!(chart.account_spec(&code("11.01")).is_some());
+    }
+
+    #[test]
+    fn deactivating_node_deactivates_descendants() {
+        let mut chart = chart_with_nodes();
+        let _ = chart
+            .deactivate_node(&code("1"), dummy_audit_info())
+            .unwrap();
+        assert!(chart.is_deactivated(&code("11")));
+        assert!(chart.is_deactivated(&code("11.01")));
+        assert!(!chart.is_deactivated(&code("2")));
+        assert!(matches!(
+            chart.move_node(&code("2"), Some(code("11")), dummy_audit_info()),
+            Err(ChartOfAccountsError::NodeDeactivated(_))
+        ));
+    }
+}
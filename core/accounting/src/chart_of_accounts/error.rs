@@ -12,6 +12,12 @@ pub enum ChartOfAccountsError {
     AuthorizationError(#[from] authz::error::AuthorizationError),
     #[error("ChartOfAccountsError - CodeNotFoundInChart: {0}")]
     CodeNotFoundInChart(crate::primitives::AccountCode),
+    #[error("ChartOfAccountsError - ParentNotFoundInChart: {0}")]
+    ParentNotFoundInChart(crate::primitives::AccountCode),
+    #[error("ChartOfAccountsError - NodeDeactivated: {0}")]
+    NodeDeactivated(crate::primitives::AccountCode),
+    #[error("ChartOfAccountsError - MoveUnderOwnDescendant: {0}")]
+    MoveUnderOwnDescendant(crate::primitives::AccountCode),
     #[error("ChartOfAccountsError - CsvParseError: {0}")]
     CsvParse(#[from] super::CsvParseError),
     #[error("ChartOfAccountsError - CalaLedgerError: {0}")]
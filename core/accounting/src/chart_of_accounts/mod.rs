@@ -1,23 +1,42 @@
 mod csv;
+mod diff;
 mod entity;
 pub mod error;
 mod repo;
 pub mod tree;
 
-pub(super) use csv::{CsvParseError, CsvParser};
+pub(super) use csv::{CsvAccount, CsvExporter, CsvParseError, CsvParser};
+pub use diff::*;
 pub use entity::Chart;
 pub(super) use entity::*;
 pub(super) use repo::*;
 
-use audit::AuditSvc;
+use std::collections::HashSet;
+
+use audit::{AuditInfo, AuditSvc};
 use authz::PermissionCheck;
 
-use cala_ledger::{CalaLedger, account_set::NewAccountSet};
+use cala_ledger::{
+    CalaLedger, LedgerOperation,
+    account_set::{AccountSetUpdate, NewAccountSet},
+};
 use tracing::instrument;
 
-use crate::primitives::{CalaJournalId, ChartId, CoreAccountingAction, CoreAccountingObject};
+use crate::primitives::{
+    AccountCode, AccountName, CalaAccountSetId, CalaJournalId, ChartId, CoreAccountingAction,
+    CoreAccountingObject,
+};
 use error::*;
 
+/// The account set changes in the ledger that follow changes to the chart.
+#[derive(Default)]
+struct ChartLedgerUpdates {
+    new_account_sets: Vec<NewAccountSet>,
+    new_connections: Vec<(CalaAccountSetId, CalaAccountSetId)>,
+    renames: Vec<(CalaAccountSetId, AccountName)>,
+    moves: Vec<NodeMove>,
+}
+
 pub struct ChartOfAccounts<Perms>
 where
     Perms: PermissionCheck,
@@ -114,46 +133,186 @@ where
             .await?;
         let mut chart = self.repo.find_by_id(id).await?;
 
-        let data = data.as_ref().to_string();
-        let account_specs = CsvParser::new(data).account_specs()?;
-        let mut new_account_sets = Vec::new();
-        let mut new_connections = Vec::new();
-        for spec in account_specs {
-            if let es_entity::Idempotent::Executed((parent, set_id)) =
-                chart.create_node(&spec, audit_info.clone())
-            {
-                let new_account_set = NewAccountSet::builder()
-                    .id(set_id)
-                    .journal_id(self.journal_id)
-                    .name(spec.name.to_string())
-                    .description(spec.name.to_string())
-                    .external_id(spec.code.account_set_external_id(id))
-                    .normal_balance_type(spec.normal_balance_type)
-                    .build()
-                    .expect("Could not build new account set");
-                new_account_sets.push(new_account_set);
-                if let Some(parent) = parent {
-                    new_connections.push((parent, set_id));
-                }
-            }
+        let accounts = CsvParser::new(data.as_ref().to_string()).accounts()?;
+        let (_, updates) = apply_csv_accounts(&mut chart, accounts, self.journal_id, audit_info)?;
+        self.persist_with_ledger_updates(&mut chart, updates)
+            .await?;
+        Ok(chart)
+    }
+
+    /// Dry run of `import_from_csv`: returns the changes the import would make
+    /// without persisting them.
+    #[instrument(name = "chart_of_account.preview_csv_import", skip(self, data))]
+    pub async fn preview_csv_import(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<ChartId> + std::fmt::Debug,
+        data: impl AsRef<str>,
+    ) -> Result<ChartImportDiff, ChartOfAccountsError> {
+        let id = id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::chart(id),
+                CoreAccountingAction::CHART_IMPORT_ACCOUNTS,
+            )
+            .await?;
+        let mut chart = self.repo.find_by_id(id).await?;
+
+        let accounts = CsvParser::new(data.as_ref().to_string()).accounts()?;
+        let (diff, _) = apply_csv_accounts(&mut chart, accounts, self.journal_id, audit_info)?;
+        Ok(diff)
+    }
+
+    #[instrument(name = "chart_of_accounts.rename_node", skip(self))]
+    pub async fn rename_node(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<ChartId> + std::fmt::Debug,
+        code: &AccountCode,
+        name: AccountName,
+    ) -> Result<Chart, ChartOfAccountsError> {
+        let id = id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::chart(id),
+                CoreAccountingAction::CHART_UPDATE_NODES,
+            )
+            .await?;
+        let mut chart = self.repo.find_by_id(id).await?;
+
+        if let es_entity::Idempotent::Executed(account_set_id) =
+            chart.rename_node(code, name.clone(), audit_info)?
+        {
+            let updates = ChartLedgerUpdates {
+                renames: vec![(account_set_id, name)],
+                ..Default::default()
+            };
+            self.persist_with_ledger_updates(&mut chart, updates)
+                .await?;
         }
+        Ok(chart)
+    }
+
+    /// Moves a node under another parent, or to the top level of the chart,
+    /// moving its account set along in the ledger.
+    #[instrument(name = "chart_of_accounts.move_node", skip(self))]
+    pub async fn move_node(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<ChartId> + std::fmt::Debug,
+        code: &AccountCode,
+        parent: Option<AccountCode>,
+    ) -> Result<Chart, ChartOfAccountsError> {
+        let id = id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::chart(id),
+                CoreAccountingAction::CHART_UPDATE_NODES,
+            )
+            .await?;
+        let mut chart = self.repo.find_by_id(id).await?;
+
+        if let es_entity::Idempotent::Executed(node_move) =
+            chart.move_node(code, parent, audit_info)?
+        {
+            let updates = ChartLedgerUpdates {
+                moves: vec![node_move],
+                ..Default::default()
+            };
+            self.persist_with_ledger_updates(&mut chart, updates)
+                .await?;
+        }
+        Ok(chart)
+    }
+
+    #[instrument(name = "chart_of_accounts.deactivate_node", skip(self))]
+    pub async fn deactivate_node(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<ChartId> + std::fmt::Debug,
+        code: &AccountCode,
+    ) -> Result<Chart, ChartOfAccountsError> {
+        let id = id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::chart(id),
+                CoreAccountingAction::CHART_UPDATE_NODES,
+            )
+            .await?;
+        let mut chart = self.repo.find_by_id(id).await?;
+
+        if chart.deactivate_node(code, audit_info)?.did_execute() {
+            self.repo.update(&mut chart).await?;
+        }
+        Ok(chart)
+    }
+
+    async fn persist_with_ledger_updates(
+        &self,
+        chart: &mut Chart,
+        updates: ChartLedgerUpdates,
+    ) -> Result<(), ChartOfAccountsError> {
         let mut op = self.repo.begin_op().await?;
-        self.repo.update_in_op(&mut op, &mut chart).await?;
+        self.repo.update_in_op(&mut op, chart).await?;
 
         let mut op = self.cala.ledger_operation_from_db_op(op);
+        self.update_ledger_in_op(&mut op, updates).await?;
+        op.commit().await?;
+        Ok(())
+    }
+
+    async fn update_ledger_in_op(
+        &self,
+        op: &mut LedgerOperation<'_>,
+        updates: ChartLedgerUpdates,
+    ) -> Result<(), ChartOfAccountsError> {
         self.cala
             .account_sets()
-            .create_all_in_op(&mut op, new_account_sets)
+            .create_all_in_op(op, updates.new_account_sets)
             .await?;
 
-        for (parent, child) in new_connections {
+        for (parent, child) in updates.new_connections {
             self.cala
                 .account_sets()
-                .add_member_in_op(&mut op, parent, child)
+                .add_member_in_op(op, parent, child)
                 .await?;
         }
-        op.commit().await?;
-        Ok(chart)
+
+        for (account_set_id, name) in updates.renames {
+            let mut account_set = self.cala.account_sets().find(account_set_id).await?;
+            let mut update = AccountSetUpdate::default();
+            update.name(name.to_string()).description(name.to_string());
+            account_set.update(update);
+            self.cala
+                .account_sets()
+                .persist_in_op(op, &mut account_set)
+                .await?;
+        }
+
+        for node_move in updates.moves {
+            if let Some(from_parent) = node_move.from_parent {
+                self.cala
+                    .account_sets()
+                    .remove_member_in_op(op, from_parent, node_move.account_set_id)
+                    .await?;
+            }
+            if let Some(to_parent) = node_move.to_parent {
+                self.cala
+                    .account_sets()
+                    .add_member_in_op(op, to_parent, node_move.account_set_id)
+                    .await?;
+            }
+        }
+
+        Ok(())
     }
 
     #[instrument(name = "chart_of_accounts.find_by_id", skip(self), err)]
@@ -204,3 +363,74 @@ where
         self.repo.find_all(ids).await
     }
 }
+
+/// Applies the accounts of a CSV to the chart in memory: new codes are added
+/// and existing ones renamed, moved or deactivated to match their row.
+fn apply_csv_accounts(
+    chart: &mut Chart,
+    accounts: Vec<CsvAccount>,
+    journal_id: CalaJournalId,
+    audit_info: AuditInfo,
+) -> Result<(ChartImportDiff, ChartLedgerUpdates), ChartOfAccountsError> {
+    let mut diff = ChartImportDiff::default();
+    let mut updates = ChartLedgerUpdates::default();
+    let mut added_codes = HashSet::new();
+
+    for CsvAccount { spec, inactive } in accounts {
+        if let es_entity::Idempotent::Executed((parent, set_id)) =
+            chart.create_node(&spec, audit_info.clone())
+        {
+            let new_account_set = NewAccountSet::builder()
+                .id(set_id)
+                .journal_id(journal_id)
+                .name(spec.name.to_string())
+                .description(spec.name.to_string())
+                .external_id(spec.code.account_set_external_id(chart.id))
+                .normal_balance_type(spec.normal_balance_type)
+                .build()
+                .expect("Could not build new account set");
+            updates.new_account_sets.push(new_account_set);
+            if let Some(parent) = parent {
+                updates.new_connections.push((parent, set_id));
+            }
+            added_codes.insert(spec.code.clone());
+            diff.added.push(spec.clone());
+        } else if !added_codes.contains(&spec.code) {
+            let (current, _) = chart
+                .account_spec(&spec.code)
+                .cloned()
+                .expect("node exists in chart");
+
+            if let es_entity::Idempotent::Executed(set_id) =
+                chart.rename_node(&spec.code, spec.name.clone(), audit_info.clone())?
+            {
+                updates.renames.push((set_id, spec.name.clone()));
+                diff.renamed.push(NodeRename {
+                    code: spec.code.clone(),
+                    from: current.name,
+                    to: spec.name.clone(),
+                });
+            }
+            if let es_entity::Idempotent::Executed(node_move) =
+                chart.move_node(&spec.code, spec.parent.clone(), audit_info.clone())?
+            {
+                updates.moves.push(node_move);
+                diff.moved.push(NodeParentChange {
+                    code: spec.code.clone(),
+                    from: current.parent,
+                    to: spec.parent.clone(),
+                });
+            }
+        }
+
+        if inactive
+            && chart
+                .deactivate_node(&spec.code, audit_info.clone())?
+                .did_execute()
+        {
+            diff.deactivated.push(spec.code);
+        }
+    }
+
+    Ok((diff, updates))
+}
@@ -1,8 +1,4 @@
-use std::{
-    cell::RefCell,
-    collections::HashMap,
-    rc::{Rc, Weak},
-};
+use std::collections::{HashMap, HashSet};
 
 use super::entity::ChartEvent;
 use crate::primitives::{AccountCode, AccountName, AccountSpec, CalaAccountSetId, ChartId};
@@ -20,39 +16,10 @@ pub struct TreeNode {
     pub code: AccountCode,
     pub name: AccountName,
     pub parent: Option<AccountCode>,
+    pub deactivated: bool,
     pub children: Vec<TreeNode>,
 }
 
-#[derive(Debug, Clone)]
-pub struct TreeNodeWithRef {
-    id: CalaAccountSetId,
-    code: AccountCode,
-    name: AccountName,
-    parent: Option<AccountCode>,
-    children: Vec<Rc<RefCell<TreeNodeWithRef>>>,
-}
-
-impl TreeNodeWithRef {
-    fn into_node(self) -> TreeNode {
-        TreeNode {
-            id: self.id,
-            code: self.code,
-            name: self.name,
-            parent: self.parent,
-            children: self
-                .children
-                .into_iter()
-                .map(|child_rc| {
-                    let child = Rc::try_unwrap(child_rc)
-                        .expect("Child has multiple owners")
-                        .into_inner();
-                    child.into_node()
-                })
-                .collect(),
-        }
-    }
-}
-
 #[derive(Debug, Clone)]
 pub struct EntityNode {
     pub id: CalaAccountSetId,
@@ -62,7 +29,8 @@ pub struct EntityNode {
 pub(super) fn project<'a>(events: impl DoubleEndedIterator<Item = &'a ChartEvent>) -> ChartTree {
     let mut id: Option<ChartId> = None;
     let mut name: Option<String> = None;
-    let mut entity_nodes: Vec<EntityNode> = vec![];
+    let mut entity_nodes: HashMap<AccountCode, EntityNode> = HashMap::new();
+    let mut deactivated: HashSet<AccountCode> = HashSet::new();
 
     for event in events {
         match event {
@@ -78,59 +46,74 @@ pub(super) fn project<'a>(events: impl DoubleEndedIterator<Item = &'a ChartEvent
                 ledger_account_set_id: id,
                 spec,
                 ..
-            } => entity_nodes.push(EntityNode {
-                id: *id,
-                spec: spec.clone(),
-            }),
+            } => {
+                entity_nodes.insert(
+                    spec.code.clone(),
+                    EntityNode {
+                        id: *id,
+                        spec: spec.clone(),
+                    },
+                );
+            }
+            ChartEvent::NodeRenamed { code, name, .. } => {
+                if let Some(node) = entity_nodes.get_mut(code) {
+                    node.spec.name = name.clone();
+                }
+            }
+            ChartEvent::NodeMoved { code, parent, .. } => {
+                if let Some(node) = entity_nodes.get_mut(code) {
+                    node.spec.parent.clone_from(parent);
+                }
+            }
+            ChartEvent::NodeDeactivated { code, .. } => {
+                deactivated.insert(code.clone());
+            }
         }
     }
 
-    let mut chart_children: Vec<Rc<RefCell<TreeNodeWithRef>>> = vec![];
-    let mut tree_nodes_by_code: HashMap<AccountCode, Weak<RefCell<TreeNodeWithRef>>> =
-        HashMap::new();
-
+    // Nodes can be moved under a parent with a higher code, so children are
+    // grouped by parent before building the tree top down.
+    let mut entity_nodes: Vec<EntityNode> = entity_nodes.into_values().collect();
     entity_nodes.sort_by_key(|l| l.spec.code.clone());
+    let mut children_by_parent: HashMap<Option<AccountCode>, Vec<EntityNode>> = HashMap::new();
     for node in entity_nodes {
-        let node_rc = Rc::new(RefCell::new(TreeNodeWithRef {
-            id: node.id,
-            code: node.spec.code.clone(),
-            name: node.spec.name.clone(),
-            parent: node.spec.parent.clone(),
-            children: vec![],
-        }));
-        if let Some(parent) = node.spec.parent {
-            tree_nodes_by_code
-                .get_mut(&parent)
-                .expect("Parent missing in tree_nodes_by_code for code")
-                .upgrade()
-                .expect("Parent node for code was dropped")
-                .borrow_mut()
-                .children
-                .push(Rc::clone(&node_rc));
-        } else {
-            chart_children.push(Rc::clone(&node_rc));
-        }
-
-        tree_nodes_by_code
-            .entry(node.spec.code)
-            .or_insert_with(|| Rc::downgrade(&node_rc));
+        children_by_parent
+            .entry(node.spec.parent.clone())
+            .or_default()
+            .push(node);
     }
 
     ChartTree {
         id: id.expect("chart id is missing"),
         name: name.expect("chart name is missing"),
-        children: chart_children
-            .into_iter()
-            .map(|child_rc| {
-                let child_refcell = Rc::try_unwrap(child_rc)
-                    .expect("Child has multiple owners")
-                    .into_inner();
-                child_refcell.into_node()
-            })
-            .collect(),
+        children: build_children(None, &mut children_by_parent, &deactivated),
     }
 }
 
+fn build_children(
+    parent: Option<AccountCode>,
+    children_by_parent: &mut HashMap<Option<AccountCode>, Vec<EntityNode>>,
+    deactivated: &HashSet<AccountCode>,
+) -> Vec<TreeNode> {
+    children_by_parent
+        .remove(&parent)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|node| TreeNode {
+            id: node.id,
+            deactivated: deactivated.contains(&node.spec.code),
+            children: build_children(
+                Some(node.spec.code.clone()),
+                children_by_parent,
+                deactivated,
+            ),
+            code: node.spec.code,
+            name: node.spec.name,
+            parent: node.spec.parent,
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use cala_ledger::DebitOrCredit;
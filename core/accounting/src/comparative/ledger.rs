@@ -0,0 +1,145 @@
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+
+use cala_ledger::{
+    AccountSetId, BalanceId, CalaLedger, Currency, JournalId, account_set::AccountSetMemberId,
+};
+
+use crate::primitives::CalaBalanceRange;
+
+use super::{
+    ComparativeAmount, ComparativeLine, ComparativeMeasure, ComparativeStatement, StatementPeriod,
+    error::ComparativeStatementError,
+};
+
+/// Levels below the statement that are reported as line items.
+const LINE_ITEM_DEPTH: usize = 3;
+
+#[derive(Clone)]
+pub struct ComparativeStatementLedger {
+    cala: CalaLedger,
+    journal_id: JournalId,
+}
+
+impl ComparativeStatementLedger {
+    pub fn new(cala: &CalaLedger, journal_id: JournalId) -> Self {
+        Self {
+            cala: cala.clone(),
+            journal_id,
+        }
+    }
+
+    pub async fn get_comparative_statement(
+        &self,
+        reference: String,
+        periods: Vec<StatementPeriod>,
+        measure: ComparativeMeasure,
+    ) -> Result<ComparativeStatement, ComparativeStatementError> {
+        if periods.is_empty() {
+            return Err(ComparativeStatementError::NoPeriods);
+        }
+        if let Some(period) = periods.iter().find(|p| p.from > p.until) {
+            return Err(ComparativeStatementError::InvalidPeriod(
+                period.from,
+                period.until,
+            ));
+        }
+
+        let statement = self
+            .cala
+            .account_sets()
+            .find_by_external_id(reference)
+            .await?
+            .into_values();
+        let nodes = self.get_line_item_nodes(statement.id).await?;
+
+        let balance_ids = nodes
+            .iter()
+            .flat_map(|(id, _, _)| {
+                [
+                    (self.journal_id, (*id).into(), Currency::USD),
+                    (self.journal_id, (*id).into(), Currency::BTC),
+                ]
+            })
+            .collect::<Vec<_>>();
+        let mut columns = Vec::with_capacity(periods.len());
+        for period in periods.iter() {
+            columns.push(
+                self.cala
+                    .balances()
+                    .effective()
+                    .find_all_in_range(&balance_ids, period.from, Some(period.until))
+                    .await?,
+            );
+        }
+
+        let lines = nodes
+            .into_iter()
+            .map(|(id, name, depth)| {
+                let amounts = columns
+                    .iter()
+                    .map(|balances| self.amount(balances, id, measure))
+                    .collect();
+                ComparativeLine::new(id, name, depth, amounts)
+            })
+            .collect();
+
+        Ok(ComparativeStatement {
+            name: statement.name,
+            periods,
+            lines,
+        })
+    }
+
+    /// The statement and its nested account sets in depth-first order.
+    async fn get_line_item_nodes(
+        &self,
+        statement_id: AccountSetId,
+    ) -> Result<Vec<(AccountSetId, String, usize)>, ComparativeStatementError> {
+        let mut nodes = Vec::new();
+        let mut stack = vec![(statement_id, 0)];
+        while let Some((id, depth)) = stack.pop() {
+            let account_set = self.cala.account_sets().find(id).await?.into_values();
+            nodes.push((id, account_set.name, depth));
+            if depth == LINE_ITEM_DEPTH {
+                continue;
+            }
+
+            let members = self
+                .cala
+                .account_sets()
+                .list_members_by_created_at(id, Default::default())
+                .await?
+                .entities;
+            for member in members.into_iter().rev() {
+                if let AccountSetMemberId::AccountSet(member_id) = member.id {
+                    stack.push((member_id, depth + 1));
+                }
+            }
+        }
+
+        Ok(nodes)
+    }
+
+    fn amount(
+        &self,
+        balances: &HashMap<BalanceId, CalaBalanceRange>,
+        id: AccountSetId,
+        measure: ComparativeMeasure,
+    ) -> ComparativeAmount {
+        let amount = |currency| {
+            balances
+                .get(&(self.journal_id, id.into(), currency))
+                .map(|range| match measure {
+                    ComparativeMeasure::ClosingBalance => range.close.settled(),
+                    ComparativeMeasure::PeriodActivity => range.period.settled(),
+                })
+                .unwrap_or(Decimal::ZERO)
+        };
+        ComparativeAmount {
+            usd: amount(Currency::USD),
+            btc: amount(Currency::BTC),
+        }
+    }
+}
@@ -0,0 +1,247 @@
+pub mod error;
+mod ledger;
+
+use chrono::{Datelike, Months, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::primitives::LedgerAccountId;
+
+pub(crate) use ledger::ComparativeStatementLedger;
+
+const QUARTERS_IN_TREND: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatementPeriod {
+    pub from: NaiveDate,
+    pub until: NaiveDate,
+}
+
+impl StatementPeriod {
+    pub fn new(from: NaiveDate, until: NaiveDate) -> Self {
+        Self { from, until }
+    }
+}
+
+/// Common column layouts, ordered from the most recent period to the oldest.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+pub enum ComparativePeriodPreset {
+    MonthOverMonth,
+    YearToDate,
+    QuarterlyTrend,
+}
+
+impl ComparativePeriodPreset {
+    pub fn periods(&self, as_of: NaiveDate) -> Vec<StatementPeriod> {
+        match self {
+            Self::MonthOverMonth => {
+                let start = first_of_month(as_of);
+                let prior_until = start.pred_opt().expect("date out of range");
+                vec![
+                    StatementPeriod::new(start, as_of),
+                    StatementPeriod::new(first_of_month(prior_until), prior_until),
+                ]
+            }
+            Self::YearToDate => {
+                let prior_until = as_of
+                    .checked_sub_months(Months::new(12))
+                    .expect("date out of range");
+                vec![
+                    StatementPeriod::new(first_of_year(as_of), as_of),
+                    StatementPeriod::new(first_of_year(prior_until), prior_until),
+                ]
+            }
+            Self::QuarterlyTrend => {
+                let mut periods = Vec::with_capacity(QUARTERS_IN_TREND);
+                let mut until = as_of;
+                for _ in 0..QUARTERS_IN_TREND {
+                    let start = first_of_quarter(until);
+                    periods.push(StatementPeriod::new(start, until));
+                    until = start.pred_opt().expect("date out of range");
+                }
+                periods
+            }
+        }
+    }
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).expect("first of month")
+}
+
+fn first_of_quarter(date: NaiveDate) -> NaiveDate {
+    NaiveDate::from_ymd_opt(date.year(), date.month0() / 3 * 3 + 1, 1).expect("first of quarter")
+}
+
+fn first_of_year(date: NaiveDate) -> NaiveDate {
+    NaiveDate::from_ymd_opt(date.year(), 1, 1).expect("first of year")
+}
+
+/// Which figure of a balance range a statement reports.
+#[derive(Debug, Clone, Copy)]
+pub(crate) enum ComparativeMeasure {
+    /// Balance at the end of the period, as on a balance sheet.
+    ClosingBalance,
+    /// Movement within the period, as on a profit and loss statement.
+    PeriodActivity,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ComparativeAmount {
+    pub usd: Decimal,
+    pub btc: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ComparativeVariance {
+    pub absolute: ComparativeAmount,
+    pub usd_percentage: Option<Decimal>,
+    pub btc_percentage: Option<Decimal>,
+}
+
+impl ComparativeVariance {
+    fn between(current: ComparativeAmount, prior: ComparativeAmount) -> Self {
+        let percentage = |current: Decimal, prior: Decimal| {
+            if prior.is_zero() {
+                None
+            } else {
+                Some(((current - prior) / prior.abs() * Decimal::ONE_HUNDRED).round_dp(2))
+            }
+        };
+        Self {
+            absolute: ComparativeAmount {
+                usd: current.usd - prior.usd,
+                btc: current.btc - prior.btc,
+            },
+            usd_percentage: percentage(current.usd, prior.usd),
+            btc_percentage: percentage(current.btc, prior.btc),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ComparativeValue {
+    pub amount: ComparativeAmount,
+    /// Change against the next period in the list; `None` for the last one.
+    pub variance: Option<ComparativeVariance>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ComparativeLine {
+    pub id: LedgerAccountId,
+    pub name: String,
+    /// 0 for the statement total, 1 for its categories and so on.
+    pub depth: usize,
+    /// One value per period, aligned with `ComparativeStatement::periods`.
+    pub values: Vec<ComparativeValue>,
+}
+
+impl ComparativeLine {
+    fn new(
+        id: impl Into<LedgerAccountId>,
+        name: String,
+        depth: usize,
+        amounts: Vec<ComparativeAmount>,
+    ) -> Self {
+        let values = amounts
+            .iter()
+            .enumerate()
+            .map(|(i, amount)| ComparativeValue {
+                amount: *amount,
+                variance: amounts
+                    .get(i + 1)
+                    .map(|prior| ComparativeVariance::between(*amount, *prior)),
+            })
+            .collect();
+        Self {
+            id: id.into(),
+            name,
+            depth,
+            values,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ComparativeStatement {
+    pub name: String,
+    pub periods: Vec<StatementPeriod>,
+    pub lines: Vec<ComparativeLine>,
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn period(from: &str, until: &str) -> StatementPeriod {
+        StatementPeriod::new(date(from), date(until))
+    }
+
+    #[test]
+    fn month_over_month() {
+        assert_eq!(
+            ComparativePeriodPreset::MonthOverMonth.periods(date("2025-03-15")),
+            vec![
+                period("2025-03-01", "2025-03-15"),
+                period("2025-02-01", "2025-02-28"),
+            ]
+        );
+    }
+
+    #[test]
+    fn year_to_date_clamps_leap_day() {
+        assert_eq!(
+            ComparativePeriodPreset::YearToDate.periods(date("2024-02-29")),
+            vec![
+                period("2024-01-01", "2024-02-29"),
+                period("2023-01-01", "2023-02-28"),
+            ]
+        );
+    }
+
+    #[test]
+    fn quarterly_trend_crosses_year_end() {
+        assert_eq!(
+            ComparativePeriodPreset::QuarterlyTrend.periods(date("2025-05-10")),
+            vec![
+                period("2025-04-01", "2025-05-10"),
+                period("2025-01-01", "2025-03-31"),
+                period("2024-10-01", "2024-12-31"),
+                period("2024-07-01", "2024-09-30"),
+            ]
+        );
+    }
+
+    #[test]
+    fn variance_against_next_period() {
+        let line = ComparativeLine::new(
+            LedgerAccountId::new(),
+            "Assets".to_string(),
+            1,
+            vec![
+                ComparativeAmount {
+                    usd: dec!(150),
+                    btc: dec!(1),
+                },
+                ComparativeAmount {
+                    usd: dec!(-200),
+                    btc: dec!(0),
+                },
+            ],
+        );
+
+        let variance = line.values[0].variance.as_ref().unwrap();
+        assert_eq!(variance.absolute.usd, dec!(350));
+        assert_eq!(variance.usd_percentage, Some(dec!(175)));
+        assert_eq!(variance.absolute.btc, dec!(1));
+        assert_eq!(variance.btc_percentage, None);
+        assert!(line.values[1].variance.is_none());
+    }
+}
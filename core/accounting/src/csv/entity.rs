@@ -7,11 +7,15 @@ use es_entity::*;
 
 use crate::csv::primitives::{
     AccountingCsvLocationInCloud, AccountingCsvStatus, AccountingCsvType,
+    ComparativeStatementCsvParams, ManualTransactionImportFile, StatementCsvParams,
 };
-use crate::primitives::{AccountingCsvId, LedgerAccountId};
+use crate::journal::JournalEntryFilter;
+use crate::manual_transaction::ManualTransactionImportError;
+use crate::primitives::{AccountingCsvId, ChartId, LedgerAccountId, ManualTransactionId};
 
 use super::error::AccountingCsvError;
 
+#[allow(clippy::large_enum_variant)]
 #[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[es_event(id = "AccountingCsvId")]
@@ -20,8 +24,23 @@ pub enum AccountingCsvEvent {
         id: AccountingCsvId,
         csv_type: AccountingCsvType,
         ledger_account_id: Option<LedgerAccountId>,
+        chart_id: Option<ChartId>,
+        statement: Option<StatementCsvParams>,
+        comparative_statement: Option<ComparativeStatementCsvParams>,
+        journal_entry_filter: Option<JournalEntryFilter>,
+        manual_transaction_import: Option<ManualTransactionImportFile>,
         audit_info: AuditInfo,
     },
+    ManualTransactionsImported {
+        manual_transaction_ids: Vec<ManualTransactionId>,
+        audit_info: AuditInfo,
+        recorded_at: DateTime<Utc>,
+    },
+    ManualTransactionImportRejected {
+        errors: Vec<ManualTransactionImportError>,
+        audit_info: AuditInfo,
+        recorded_at: DateTime<Utc>,
+    },
     FileUploaded {
         path_in_bucket: String,
         bucket: String,
@@ -48,6 +67,16 @@ pub struct AccountingCsv {
     pub csv_type: AccountingCsvType,
     #[builder(setter(strip_option), default)]
     pub ledger_account_id: Option<LedgerAccountId>,
+    #[builder(setter(strip_option), default)]
+    pub chart_id: Option<ChartId>,
+    #[builder(setter(strip_option), default)]
+    pub statement: Option<StatementCsvParams>,
+    #[builder(setter(strip_option), default)]
+    pub comparative_statement: Option<ComparativeStatementCsvParams>,
+    #[builder(setter(strip_option), default)]
+    pub journal_entry_filter: Option<JournalEntryFilter>,
+    #[builder(setter(strip_option), default)]
+    pub manual_transaction_import: Option<ManualTransactionImportFile>,
     events: EntityEvents<AccountingCsvEvent>,
 }
 
@@ -78,6 +107,90 @@ impl AccountingCsv {
         None
     }
 
+    /// Who uploaded the file, recorded when the CSV was created.
+    pub(super) fn created_by(&self) -> &AuditInfo {
+        match self.events.iter_all().next() {
+            Some(AccountingCsvEvent::Initialized { audit_info, .. }) => audit_info,
+            _ => unreachable!("first event is always Initialized"),
+        }
+    }
+
+    /// Whether an import has already either posted or been rejected.
+    pub(super) fn import_concluded(&self) -> bool {
+        self.events.iter_all().any(|e| {
+            matches!(
+                e,
+                AccountingCsvEvent::ManualTransactionsImported { .. }
+                    | AccountingCsvEvent::ManualTransactionImportRejected { .. }
+            )
+        })
+    }
+
+    pub fn imported_manual_transaction_ids(&self) -> &[ManualTransactionId] {
+        self.events
+            .iter_all()
+            .find_map(|e| match e {
+                AccountingCsvEvent::ManualTransactionsImported {
+                    manual_transaction_ids,
+                    ..
+                } => Some(manual_transaction_ids.as_slice()),
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    pub fn import_errors(&self) -> &[ManualTransactionImportError] {
+        self.events
+            .iter_all()
+            .find_map(|e| match e {
+                AccountingCsvEvent::ManualTransactionImportRejected { errors, .. } => {
+                    Some(errors.as_slice())
+                }
+                _ => None,
+            })
+            .unwrap_or_default()
+    }
+
+    pub(super) fn manual_transactions_imported(
+        &mut self,
+        manual_transaction_ids: Vec<ManualTransactionId>,
+        audit_info: AuditInfo,
+    ) -> Idempotent<()> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            AccountingCsvEvent::ManualTransactionsImported { .. }
+                | AccountingCsvEvent::ManualTransactionImportRejected { .. }
+        );
+
+        self.events
+            .push(AccountingCsvEvent::ManualTransactionsImported {
+                manual_transaction_ids,
+                audit_info,
+                recorded_at: Utc::now(),
+            });
+        Idempotent::Executed(())
+    }
+
+    pub(super) fn manual_transaction_import_rejected(
+        &mut self,
+        errors: Vec<ManualTransactionImportError>,
+        audit_info: AuditInfo,
+    ) -> Idempotent<()> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            AccountingCsvEvent::ManualTransactionsImported { .. }
+                | AccountingCsvEvent::ManualTransactionImportRejected { .. }
+        );
+
+        self.events
+            .push(AccountingCsvEvent::ManualTransactionImportRejected {
+                errors,
+                audit_info,
+                recorded_at: Utc::now(),
+            });
+        Idempotent::Executed(())
+    }
+
     pub fn file_uploaded(
         &mut self,
         path_in_bucket: String,
@@ -127,7 +240,7 @@ impl AccountingCsv {
     pub fn download_link_generated(
         &mut self,
         audit_info: AuditInfo,
-    ) -> Result<AccountingCsvLocationInCloud, AccountingCsvError> {
+    ) -> Result<AccountingCsvLocationInCloud<'_>, AccountingCsvError> {
         if self.status() != AccountingCsvStatus::Completed {
             return Err(AccountingCsvError::CsvNotReady);
         }
@@ -185,6 +298,11 @@ impl TryFromEvents<AccountingCsvEvent> for AccountingCsv {
                 id,
                 csv_type,
                 ledger_account_id,
+                chart_id,
+                statement,
+                comparative_statement,
+                journal_entry_filter,
+                manual_transaction_import,
                 ..
             } = event
             {
@@ -192,6 +310,21 @@ impl TryFromEvents<AccountingCsvEvent> for AccountingCsv {
                 if let Some(account_id) = ledger_account_id {
                     builder = builder.ledger_account_id(*account_id);
                 }
+                if let Some(chart_id) = chart_id {
+                    builder = builder.chart_id(*chart_id);
+                }
+                if let Some(statement) = statement {
+                    builder = builder.statement(statement.clone());
+                }
+                if let Some(comparative_statement) = comparative_statement {
+                    builder = builder.comparative_statement(comparative_statement.clone());
+                }
+                if let Some(journal_entry_filter) = journal_entry_filter {
+                    builder = builder.journal_entry_filter(journal_entry_filter.clone());
+                }
+                if let Some(manual_transaction_import) = manual_transaction_import {
+                    builder = builder.manual_transaction_import(manual_transaction_import.clone());
+                }
             }
         }
         builder.events(events).build()
@@ -206,6 +339,16 @@ pub struct NewAccountingCsv {
     pub(super) csv_type: AccountingCsvType,
     #[builder(setter(strip_option), default)]
     pub(super) ledger_account_id: Option<LedgerAccountId>,
+    #[builder(setter(strip_option), default)]
+    pub(super) chart_id: Option<ChartId>,
+    #[builder(setter(strip_option), default)]
+    pub(super) statement: Option<StatementCsvParams>,
+    #[builder(setter(strip_option), default)]
+    pub(super) comparative_statement: Option<ComparativeStatementCsvParams>,
+    #[builder(setter(strip_option), default)]
+    pub(super) journal_entry_filter: Option<JournalEntryFilter>,
+    #[builder(setter(strip_option), default)]
+    pub(super) manual_transaction_import: Option<ManualTransactionImportFile>,
     #[builder(setter(into))]
     pub(super) audit_info: AuditInfo,
 }
@@ -223,6 +366,11 @@ impl IntoEvents<AccountingCsvEvent> for NewAccountingCsv {
                 id: self.id,
                 csv_type: self.csv_type,
                 ledger_account_id: self.ledger_account_id,
+                chart_id: self.chart_id,
+                statement: self.statement,
+                comparative_statement: self.comparative_statement,
+                journal_entry_filter: self.journal_entry_filter,
+                manual_transaction_import: self.manual_transaction_import,
                 audit_info: self.audit_info,
             }],
         )
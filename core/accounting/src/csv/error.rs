@@ -10,8 +10,22 @@ pub enum AccountingCsvError {
     CursorDestructureError(#[from] es_entity::CursorDestructureError),
     #[error("AccountingCsvError - AuthorizationError: {0}")]
     AuthorizationError(#[from] authz::error::AuthorizationError),
+    #[error("AccountingCsvError - AuditError: {0}")]
+    AuditError(#[from] audit::error::AuditError),
     #[error("AccountingCsvError - LedgerAccountError: {0}")]
     LedgerAccountError(#[from] crate::ledger_account::error::LedgerAccountError),
+    #[error("AccountingCsvError - ChartOfAccountsError: {0}")]
+    ChartOfAccountsError(#[from] crate::chart_of_accounts::error::ChartOfAccountsError),
+    #[error("AccountingCsvError - BalanceSheetError: {0}")]
+    BalanceSheetError(#[from] crate::balance_sheet::error::BalanceSheetError),
+    #[error("AccountingCsvError - ProfitAndLossStatementError: {0}")]
+    ProfitAndLossStatementError(#[from] crate::profit_and_loss::error::ProfitAndLossStatementError),
+    #[error("AccountingCsvError - CashFlowStatementError: {0}")]
+    CashFlowStatementError(#[from] crate::cash_flow_statement::error::CashFlowStatementError),
+    #[error("AccountingCsvError - ManualTransactionError: {0}")]
+    ManualTransactionError(#[from] crate::manual_transaction::error::ManualTransactionError),
+    #[error("AccountingCsvError - JournalError: {0}")]
+    JournalError(#[from] crate::journal::error::JournalError),
     #[error("AccountingCsvError - StorageError: {0}")]
     StorageError(#[from] cloud_storage::error::StorageError),
     #[error("AccountingCsvError - JobError: {0}")]
@@ -6,11 +6,24 @@ use authz::PermissionCheck;
 use cala_ledger::DebitOrCredit;
 
 use crate::{
-    CoreAccountingAction, CoreAccountingObject, ledger_account::LedgerAccounts,
-    primitives::LedgerAccountId,
+    CoreAccountingAction, CoreAccountingObject,
+    balance_sheet::BalanceSheets,
+    cash_flow_statement::{CashFlowAmount, CashFlowStatements},
+    chart_of_accounts::{ChartOfAccounts, CsvExporter},
+    comparative::ComparativeStatement,
+    journal::{Journal, JournalEntryCursor, JournalEntryFilter},
+    ledger_account::LedgerAccounts,
+    primitives::{ChartId, LedgerAccountId},
+    profit_and_loss::ProfitAndLossStatements,
 };
 
-use super::error::AccountingCsvError;
+use super::{
+    error::AccountingCsvError,
+    primitives::{ComparativeStatementCsvParams, StatementCsvParams},
+};
+
+const LINE_INDENT: &str = "  ";
+const JOURNAL_ENTRY_SEARCH_PAGE_SIZE: usize = 1000;
 
 pub struct GenerateCsv<Perms>
 where
@@ -18,7 +31,12 @@ where
     <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreAccountingAction>,
     <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreAccountingObject>,
 {
+    journal: Journal<Perms>,
     ledger_accounts: LedgerAccounts<Perms>,
+    chart_of_accounts: ChartOfAccounts<Perms>,
+    balance_sheets: BalanceSheets<Perms>,
+    profit_and_loss: ProfitAndLossStatements<Perms>,
+    cash_flow_statements: CashFlowStatements<Perms>,
 }
 
 impl<Perms> GenerateCsv<Perms>
@@ -27,9 +45,21 @@ where
     <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreAccountingAction>,
     <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreAccountingObject>,
 {
-    pub fn new(ledger_accounts: &LedgerAccounts<Perms>) -> Self {
+    pub fn new(
+        journal: &Journal<Perms>,
+        ledger_accounts: &LedgerAccounts<Perms>,
+        chart_of_accounts: &ChartOfAccounts<Perms>,
+        balance_sheets: &BalanceSheets<Perms>,
+        profit_and_loss: &ProfitAndLossStatements<Perms>,
+        cash_flow_statements: &CashFlowStatements<Perms>,
+    ) -> Self {
         Self {
+            journal: journal.clone(),
             ledger_accounts: ledger_accounts.clone(),
+            chart_of_accounts: chart_of_accounts.clone(),
+            balance_sheets: balance_sheets.clone(),
+            profit_and_loss: profit_and_loss.clone(),
+            cash_flow_statements: cash_flow_statements.clone(),
         }
     }
 
@@ -79,4 +109,189 @@ where
 
         Ok(csv_data)
     }
+
+    pub async fn generate_journal_entry_search_csv(
+        &self,
+        filter: JournalEntryFilter,
+    ) -> Result<Vec<u8>, AccountingCsvError> {
+        let mut wtr = Writer::from_writer(vec![]);
+        wtr.write_record([
+            "Recorded At",
+            "Transaction Id",
+            "Ledger Account Id",
+            "Currency",
+            "Debit Amount",
+            "Credit Amount",
+            "Description",
+            "Entry Type",
+        ])
+        .map_err(|e| AccountingCsvError::CsvError(e.to_string()))?;
+
+        let mut after: Option<JournalEntryCursor> = None;
+        loop {
+            let page = self
+                .journal
+                .find_matching_entries(
+                    &filter,
+                    es_entity::PaginatedQueryArgs {
+                        first: JOURNAL_ENTRY_SEARCH_PAGE_SIZE,
+                        after,
+                    },
+                )
+                .await?;
+
+            for entry in page.entities {
+                let formatted_amount = entry.amount.to_display_amount();
+                let currency = entry.amount.currency_code();
+
+                let (debit_amount, credit_amount) = match entry.direction {
+                    DebitOrCredit::Debit => (formatted_amount, Decimal::from(0).to_string()),
+                    DebitOrCredit::Credit => (Decimal::from(0).to_string(), formatted_amount),
+                };
+
+                wtr.write_record(&[
+                    entry.created_at.to_rfc3339(),
+                    entry.ledger_transaction_id.to_string(),
+                    entry.ledger_account_id.to_string(),
+                    currency,
+                    debit_amount,
+                    credit_amount,
+                    entry.description.unwrap_or_default(),
+                    entry.entry_type,
+                ])
+                .map_err(|e| AccountingCsvError::CsvError(e.to_string()))?;
+            }
+
+            if !page.has_next_page {
+                break;
+            }
+            after = page.end_cursor;
+        }
+
+        wtr.into_inner()
+            .map_err(|e| AccountingCsvError::CsvError(e.to_string()))
+    }
+
+    pub async fn generate_chart_of_accounts_csv(
+        &self,
+        chart_id: ChartId,
+    ) -> Result<Vec<u8>, AccountingCsvError> {
+        let chart = self.chart_of_accounts.find_by_id(chart_id).await?;
+        CsvExporter::new(&chart)
+            .to_csv()
+            .map_err(|e| AccountingCsvError::CsvError(e.to_string()))
+    }
+
+    pub async fn generate_cash_flow_statement_csv(
+        &self,
+        params: StatementCsvParams,
+    ) -> Result<Vec<u8>, AccountingCsvError> {
+        let statement = self
+            .cash_flow_statements
+            .find_cash_flow_statement(params.reference, params.from, params.until)
+            .await?;
+
+        let mut wtr = Writer::from_writer(vec![]);
+        wtr.write_record(["Section", "Line", "USD", "BTC"])
+            .map_err(|e| AccountingCsvError::CsvError(e.to_string()))?;
+
+        let mut write = |section: &str, line: &str, amount: CashFlowAmount| {
+            let (usd, btc) = (amount.usd.to_string(), amount.btc.to_string());
+            wtr.write_record([section, line, usd.as_str(), btc.as_str()])
+                .map_err(|e| AccountingCsvError::CsvError(e.to_string()))
+        };
+
+        write(
+            &statement.operating_activities.name,
+            &statement.net_income.name,
+            statement.net_income.amount,
+        )?;
+        for section in [
+            &statement.operating_activities,
+            &statement.investing_activities,
+            &statement.financing_activities,
+        ] {
+            for line in section.lines.iter() {
+                write(&section.name, &line.name, line.amount)?;
+            }
+            write(&section.name, "Total", section.total)?;
+        }
+        write("Cash", "Net Change in Cash", statement.net_change_in_cash())?;
+        write("Cash", "Opening Cash", statement.opening_cash)?;
+        write("Cash", "Closing Cash", statement.closing_cash)?;
+
+        let csv_data = wtr
+            .into_inner()
+            .map_err(|e| AccountingCsvError::CsvError(e.to_string()))?;
+
+        Ok(csv_data)
+    }
+
+    pub async fn generate_comparative_balance_sheet_csv(
+        &self,
+        params: ComparativeStatementCsvParams,
+    ) -> Result<Vec<u8>, AccountingCsvError> {
+        let statement = self
+            .balance_sheets
+            .find_comparative_balance_sheet(params.reference, params.periods)
+            .await?;
+
+        comparative_statement_csv(&statement)
+    }
+
+    pub async fn generate_comparative_pl_statement_csv(
+        &self,
+        params: ComparativeStatementCsvParams,
+    ) -> Result<Vec<u8>, AccountingCsvError> {
+        let statement = self
+            .profit_and_loss
+            .find_comparative_pl_statement(params.reference, params.periods)
+            .await?;
+
+        comparative_statement_csv(&statement)
+    }
+}
+
+/// One row per line item with a block of columns per period. Each block holds
+/// the amounts and their change against the following period.
+fn comparative_statement_csv(
+    statement: &ComparativeStatement,
+) -> Result<Vec<u8>, AccountingCsvError> {
+    let mut wtr = Writer::from_writer(vec![]);
+
+    let mut header = vec!["Line".to_string()];
+    for period in statement.periods.iter() {
+        let label = format!("{} to {}", period.from, period.until);
+        for column in [
+            "USD",
+            "BTC",
+            "USD Change",
+            "USD Change %",
+            "BTC Change",
+            "BTC Change %",
+        ] {
+            header.push(format!("{label} {column}"));
+        }
+    }
+    wtr.write_record(&header)
+        .map_err(|e| AccountingCsvError::CsvError(e.to_string()))?;
+
+    let optional = |value: Option<Decimal>| value.map(|v| v.to_string()).unwrap_or_default();
+    for line in statement.lines.iter() {
+        let mut record = vec![format!("{}{}", LINE_INDENT.repeat(line.depth), line.name)];
+        for value in line.values.iter() {
+            let variance = value.variance.as_ref();
+            record.push(value.amount.usd.to_string());
+            record.push(value.amount.btc.to_string());
+            record.push(optional(variance.map(|v| v.absolute.usd)));
+            record.push(optional(variance.and_then(|v| v.usd_percentage)));
+            record.push(optional(variance.map(|v| v.absolute.btc)));
+            record.push(optional(variance.and_then(|v| v.btc_percentage)));
+        }
+        wtr.write_record(&record)
+            .map_err(|e| AccountingCsvError::CsvError(e.to_string()))?;
+    }
+
+    wtr.into_inner()
+        .map_err(|e| AccountingCsvError::CsvError(e.to_string()))
 }
@@ -0,0 +1,308 @@
+use async_trait::async_trait;
+use csv::Writer;
+
+use audit::{AuditSvc, SystemSubject};
+use authz::PermissionCheck;
+use cloud_storage::Storage;
+use governance::{GovernanceAction, GovernanceEvent, GovernanceObject};
+use job::*;
+use outbox::OutboxEventMarker;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    chart_of_accounts::ChartOfAccounts,
+    manual_transaction::{
+        ManualTransaction, ManualTransactionStatus, ManualTransactions, NewManualTransaction,
+        error::ManualTransactionError, parse_manual_transactions_csv,
+    },
+    primitives::AccountingCsvId,
+};
+
+use super::{
+    CoreAccountingAction, CoreAccountingObject, entity::AccountingCsv, error::AccountingCsvError,
+    repo::AccountingCsvRepo,
+};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ImportManualTransactionsCsvConfig<Perms, E> {
+    pub accounting_csv_id: AccountingCsvId,
+    pub _phantom: std::marker::PhantomData<(Perms, E)>,
+}
+
+impl<Perms, E> JobConfig for ImportManualTransactionsCsvConfig<Perms, E>
+where
+    E: OutboxEventMarker<GovernanceEvent>,
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreAccountingAction> + From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreAccountingObject> + From<GovernanceObject>,
+    governance::CommitteeMemberId:
+        for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+{
+    type Initializer = ImportManualTransactionsCsvInitializer<Perms, E>;
+}
+
+pub struct ImportManualTransactionsCsvInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    repo: AccountingCsvRepo,
+    storage: Storage,
+    chart_of_accounts: ChartOfAccounts<Perms>,
+    manual_transactions: ManualTransactions<Perms, E>,
+    audit: Perms::Audit,
+}
+
+impl<Perms, E> ImportManualTransactionsCsvInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    pub fn new(
+        repo: &AccountingCsvRepo,
+        storage: &Storage,
+        chart_of_accounts: &ChartOfAccounts<Perms>,
+        manual_transactions: &ManualTransactions<Perms, E>,
+        audit: &Perms::Audit,
+    ) -> Self {
+        Self {
+            repo: repo.clone(),
+            storage: storage.clone(),
+            chart_of_accounts: chart_of_accounts.clone(),
+            manual_transactions: manual_transactions.clone(),
+            audit: audit.clone(),
+        }
+    }
+}
+
+pub const IMPORT_MANUAL_TRANSACTIONS_CSV_JOB: JobType =
+    JobType::new("import-manual-transactions-csv");
+
+impl<Perms, E> JobInitializer for ImportManualTransactionsCsvInitializer<Perms, E>
+where
+    E: OutboxEventMarker<GovernanceEvent>,
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreAccountingAction> + From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreAccountingObject> + From<GovernanceObject>,
+    governance::CommitteeMemberId:
+        for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        IMPORT_MANUAL_TRANSACTIONS_CSV_JOB
+    }
+
+    fn init(&self, job: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(ImportManualTransactionsCsvJobRunner::<Perms, E> {
+            config: job.config()?,
+            repo: self.repo.clone(),
+            storage: self.storage.clone(),
+            chart_of_accounts: self.chart_of_accounts.clone(),
+            manual_transactions: self.manual_transactions.clone(),
+            audit: self.audit.clone(),
+        }))
+    }
+}
+
+pub struct ImportManualTransactionsCsvJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    config: ImportManualTransactionsCsvConfig<Perms, E>,
+    repo: AccountingCsvRepo,
+    storage: Storage,
+    chart_of_accounts: ChartOfAccounts<Perms>,
+    manual_transactions: ManualTransactions<Perms, E>,
+    audit: Perms::Audit,
+}
+
+impl<Perms, E> ImportManualTransactionsCsvJobRunner<Perms, E>
+where
+    E: OutboxEventMarker<GovernanceEvent>,
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreAccountingAction> + From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreAccountingObject> + From<GovernanceObject>,
+    governance::CommitteeMemberId:
+        for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+{
+    /// Posts every transaction in the file or, when any line is invalid or
+    /// any transaction would need approval votes, none of them and records
+    /// the errors found instead.
+    async fn import(&self, import: &mut AccountingCsv) -> Result<(), AccountingCsvError> {
+        let file = import.manual_transaction_import.clone().ok_or_else(|| {
+            AccountingCsvError::MissingRequiredField("manual_transaction_import".to_string())
+        })?;
+        let chart_id = import
+            .chart_id
+            .ok_or_else(|| AccountingCsvError::MissingRequiredField("chart_id".to_string()))?;
+        let chart = self.chart_of_accounts.find_by_id(chart_id).await?;
+        let created_by = import.created_by().clone();
+
+        let prepared = match parse_manual_transactions_csv(&file.content) {
+            Ok(imported) => {
+                self.manual_transactions
+                    .prepare_import(&chart, imported, created_by.clone())
+                    .await
+            }
+            Err(errors) => Err(ManualTransactionError::ImportRejected(errors)),
+        };
+        let result = match prepared {
+            Ok(new_txs) => self.post(import, &created_by.sub, new_txs).await,
+            Err(e) => Err(e.into()),
+        };
+
+        match result {
+            Err(AccountingCsvError::ManualTransactionError(
+                ManualTransactionError::ImportRejected(errors),
+            )) => {
+                *import = self.repo.find_by_id(import.id).await?;
+                let audit_info = self
+                    .audit
+                    .record_system_entry(
+                        CoreAccountingObject::all_manual_transactions(),
+                        CoreAccountingAction::MANUAL_TRANSACTION_CREATE,
+                    )
+                    .await?;
+                let _ = import.manual_transaction_import_rejected(errors, audit_info);
+                self.repo.update(import).await?;
+                Ok(())
+            }
+            res => res,
+        }
+    }
+
+    async fn post(
+        &self,
+        import: &mut AccountingCsv,
+        initiated_by: &str,
+        new_txs: Vec<(u64, NewManualTransaction)>,
+    ) -> Result<(), AccountingCsvError> {
+        let initiated_by = initiated_by
+            .parse::<<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>()
+            .unwrap_or_else(|_| <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject::system());
+        let mut db = self.repo.begin_op().await?;
+        let audit_info = self
+            .audit
+            .record_system_entry_in_tx(
+                db.tx(),
+                CoreAccountingObject::all_manual_transactions(),
+                CoreAccountingAction::MANUAL_TRANSACTION_CREATE,
+            )
+            .await?;
+        let _ = import.manual_transactions_imported(
+            new_txs.iter().map(|(_, tx)| tx.id).collect(),
+            audit_info,
+        );
+        self.repo.update_in_op(&mut db, import).await?;
+        self.manual_transactions
+            .import_in_op(db, &initiated_by, new_txs)
+            .await?;
+        Ok(())
+    }
+
+    async fn report(&self, import: &AccountingCsv) -> Result<Vec<u8>, AccountingCsvError> {
+        let mut wtr = Writer::from_writer(vec![]);
+        if import.import_errors().is_empty() {
+            let ids = import.imported_manual_transaction_ids();
+            let mut manual_txs = self
+                .manual_transactions
+                .find_all::<ManualTransaction>(ids)
+                .await?;
+            wtr.write_record(["Reference", "Manual Transaction Id", "Status"])
+                .map_err(|e| AccountingCsvError::CsvError(e.to_string()))?;
+            for manual_tx in ids.iter().filter_map(|id| manual_txs.remove(id)) {
+                let status = match manual_tx.status() {
+                    ManualTransactionStatus::PendingApproval => "Pending Approval",
+                    ManualTransactionStatus::Posted => "Posted",
+                    ManualTransactionStatus::Rejected => "Rejected",
+                    ManualTransactionStatus::Reversed => "Reversed",
+                };
+                wtr.write_record(&[
+                    manual_tx.reference.clone(),
+                    manual_tx.id.to_string(),
+                    status.to_string(),
+                ])
+                .map_err(|e| AccountingCsvError::CsvError(e.to_string()))?;
+            }
+        } else {
+            wtr.write_record(["Line", "Reference", "Error"])
+                .map_err(|e| AccountingCsvError::CsvError(e.to_string()))?;
+            for error in import.import_errors() {
+                wtr.write_record(&[
+                    error.line.to_string(),
+                    error.reference.clone().unwrap_or_default(),
+                    error.message.clone(),
+                ])
+                .map_err(|e| AccountingCsvError::CsvError(e.to_string()))?;
+            }
+        }
+        wtr.into_inner()
+            .map_err(|e| AccountingCsvError::CsvError(e.to_string()))
+    }
+}
+
+#[async_trait]
+impl<Perms, E> JobRunner for ImportManualTransactionsCsvJobRunner<Perms, E>
+where
+    E: OutboxEventMarker<GovernanceEvent>,
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreAccountingAction> + From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreAccountingObject> + From<GovernanceObject>,
+    governance::CommitteeMemberId:
+        for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+{
+    async fn run(
+        &self,
+        _current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let mut import = self.repo.find_by_id(self.config.accounting_csv_id).await?;
+        if !import.import_concluded() {
+            self.import(&mut import).await?;
+        }
+
+        let mut db = self.repo.begin_op().await?;
+        let audit_info = self
+            .audit
+            .record_system_entry_in_tx(
+                db.tx(),
+                CoreAccountingObject::all_accounting_csvs(),
+                CoreAccountingAction::ACCOUNTING_CSV_GENERATE,
+            )
+            .await?;
+
+        let path_in_bucket = format!("accounting_csvs/{}.csv", import.id);
+        let report = self.report(&import).await?;
+        match self
+            .storage
+            .upload(report, &path_in_bucket, "text/csv")
+            .await
+        {
+            Ok(_) => {
+                let _ = import.file_uploaded(
+                    path_in_bucket,
+                    self.storage.bucket_name().to_string(),
+                    audit_info,
+                );
+            }
+            Err(e) => {
+                import.upload_failed(e.to_string(), audit_info);
+            }
+        }
+
+        self.repo.update_in_op(&mut db, &mut import).await?;
+        let (now, tx) = (db.now(), db.into_tx());
+        let db_static = es_entity::DbOp::new(tx, now);
+        Ok(JobCompletion::CompleteWithOp(db_static))
+    }
+}
@@ -7,7 +7,11 @@ use cloud_storage::Storage;
 use job::*;
 use serde::{Deserialize, Serialize};
 
-use crate::{ledger_account::LedgerAccounts, primitives::AccountingCsvId};
+use crate::{
+    balance_sheet::BalanceSheets, cash_flow_statement::CashFlowStatements,
+    chart_of_accounts::ChartOfAccounts, journal::Journal, ledger_account::LedgerAccounts,
+    primitives::AccountingCsvId, profit_and_loss::ProfitAndLossStatements,
+};
 
 use super::{
     CoreAccountingAction, CoreAccountingObject, error::AccountingCsvError, generate::GenerateCsv,
@@ -37,7 +41,12 @@ where
 {
     repo: AccountingCsvRepo,
     storage: Storage,
+    journal: Journal<Perms>,
     ledger_accounts: LedgerAccounts<Perms>,
+    chart_of_accounts: ChartOfAccounts<Perms>,
+    balance_sheets: BalanceSheets<Perms>,
+    profit_and_loss: ProfitAndLossStatements<Perms>,
+    cash_flow_statements: CashFlowStatements<Perms>,
     audit: Perms::Audit,
 }
 
@@ -47,16 +56,27 @@ where
     <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreAccountingAction>,
     <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreAccountingObject>,
 {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         repo: &AccountingCsvRepo,
         storage: &Storage,
+        journal: &Journal<Perms>,
         ledger_accounts: &LedgerAccounts<Perms>,
+        chart_of_accounts: &ChartOfAccounts<Perms>,
+        balance_sheets: &BalanceSheets<Perms>,
+        profit_and_loss: &ProfitAndLossStatements<Perms>,
+        cash_flow_statements: &CashFlowStatements<Perms>,
         audit: &Perms::Audit,
     ) -> Self {
         Self {
             repo: repo.clone(),
             storage: storage.clone(),
+            journal: journal.clone(),
             ledger_accounts: ledger_accounts.clone(),
+            chart_of_accounts: chart_of_accounts.clone(),
+            balance_sheets: balance_sheets.clone(),
+            profit_and_loss: profit_and_loss.clone(),
+            cash_flow_statements: cash_flow_statements.clone(),
             audit: audit.clone(),
         }
     }
@@ -82,7 +102,14 @@ where
             config: job.config()?,
             repo: self.repo.clone(),
             storage: self.storage.clone(),
-            generator: GenerateCsv::new(&self.ledger_accounts),
+            generator: GenerateCsv::new(
+                &self.journal,
+                &self.ledger_accounts,
+                &self.chart_of_accounts,
+                &self.balance_sheets,
+                &self.profit_and_loss,
+                &self.cash_flow_statements,
+            ),
             audit: self.audit.clone(),
         }))
     }
@@ -136,6 +163,54 @@ where
             }
             AccountingCsvType::ProfitAndLoss => Err(AccountingCsvError::UnsupportedCsvType),
             AccountingCsvType::BalanceSheet => Err(AccountingCsvError::UnsupportedCsvType),
+            AccountingCsvType::ChartOfAccounts => {
+                let chart_id = export.chart_id.ok_or_else(|| {
+                    AccountingCsvError::MissingRequiredField("chart_id".to_string())
+                })?;
+
+                self.generator
+                    .generate_chart_of_accounts_csv(chart_id)
+                    .await
+            }
+            AccountingCsvType::CashFlowStatement => {
+                let statement = export.statement.clone().ok_or_else(|| {
+                    AccountingCsvError::MissingRequiredField("statement".to_string())
+                })?;
+
+                self.generator
+                    .generate_cash_flow_statement_csv(statement)
+                    .await
+            }
+            AccountingCsvType::ComparativeBalanceSheet => {
+                let params = export.comparative_statement.clone().ok_or_else(|| {
+                    AccountingCsvError::MissingRequiredField("comparative_statement".to_string())
+                })?;
+
+                self.generator
+                    .generate_comparative_balance_sheet_csv(params)
+                    .await
+            }
+            AccountingCsvType::ComparativeProfitAndLoss => {
+                let params = export.comparative_statement.clone().ok_or_else(|| {
+                    AccountingCsvError::MissingRequiredField("comparative_statement".to_string())
+                })?;
+
+                self.generator
+                    .generate_comparative_pl_statement_csv(params)
+                    .await
+            }
+            AccountingCsvType::JournalEntrySearch => {
+                let filter = export.journal_entry_filter.clone().ok_or_else(|| {
+                    AccountingCsvError::MissingRequiredField("journal_entry_filter".to_string())
+                })?;
+
+                self.generator
+                    .generate_journal_entry_search_csv(filter)
+                    .await
+            }
+            AccountingCsvType::ManualTransactionImport => {
+                Err(AccountingCsvError::UnsupportedCsvType)
+            }
         };
 
         match csv_result {
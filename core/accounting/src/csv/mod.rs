@@ -1,6 +1,7 @@
 mod entity;
 pub mod error;
 mod generate;
+mod import;
 mod job;
 mod primitives;
 mod repo;
@@ -9,18 +10,28 @@ use crate::Jobs;
 use crate::Storage;
 use audit::AuditSvc;
 use authz::PermissionCheck;
+use governance::{GovernanceAction, GovernanceEvent, GovernanceObject};
+use outbox::OutboxEventMarker;
 
 use es_entity::ListDirection;
 pub use repo::accounting_csv_cursor::AccountingCsvsByCreatedAtCursor;
 
 use super::{
     CoreAccountingAction, CoreAccountingObject,
+    balance_sheet::BalanceSheets,
+    cash_flow_statement::CashFlowStatements,
+    chart_of_accounts::ChartOfAccounts,
+    comparative::StatementPeriod,
+    journal::{Journal, JournalEntryFilter},
     ledger_account::LedgerAccounts,
-    primitives::{AccountingCsvId, LedgerAccountId},
+    manual_transaction::ManualTransactions,
+    primitives::{AccountingCsvId, ChartId, LedgerAccountId},
+    profit_and_loss::ProfitAndLossStatements,
 };
 
 pub use entity::*;
 use error::*;
+use import::*;
 use job::*;
 pub use primitives::*;
 use repo::*;
@@ -42,19 +53,30 @@ where
     <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreAccountingAction>,
     <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreAccountingObject>,
 {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         pool: &sqlx::PgPool,
         authz: &Perms,
         jobs: &Jobs,
         storage: &Storage,
+        journal: &Journal<Perms>,
         ledger_accounts: &LedgerAccounts<Perms>,
+        chart_of_accounts: &ChartOfAccounts<Perms>,
+        balance_sheets: &BalanceSheets<Perms>,
+        profit_and_loss: &ProfitAndLossStatements<Perms>,
+        cash_flow_statements: &CashFlowStatements<Perms>,
     ) -> Self {
         let repo = AccountingCsvRepo::new(pool);
 
         jobs.add_initializer(GenerateAccountingCsvInitializer::new(
             &repo,
             storage,
+            journal,
             ledger_accounts,
+            chart_of_accounts,
+            balance_sheets,
+            profit_and_loss,
+            cash_flow_statements,
             authz.audit(),
         ));
 
@@ -91,6 +113,250 @@ where
             .build()
             .expect("Could not build new Accounting CSV");
 
+        self.create_and_spawn(new_csv).await
+    }
+
+    /// Exports the chart in the layout accepted by the chart of accounts CSV import.
+    pub async fn create_chart_of_accounts_csv(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        chart_id: impl Into<ChartId> + std::fmt::Debug,
+    ) -> Result<AccountingCsv, AccountingCsvError> {
+        let chart_id = chart_id.into();
+        let id = AccountingCsvId::new();
+
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_accounting_csvs(),
+                CoreAccountingAction::ACCOUNTING_CSV_CREATE,
+            )
+            .await?;
+
+        let new_csv = NewAccountingCsv::builder()
+            .id(id)
+            .csv_type(AccountingCsvType::ChartOfAccounts)
+            .chart_id(chart_id)
+            .audit_info(audit_info)
+            .build()
+            .expect("Could not build new Accounting CSV");
+
+        self.create_and_spawn(new_csv).await
+    }
+
+    pub async fn create_cash_flow_statement_csv(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        reference: String,
+        from: chrono::NaiveDate,
+        until: Option<chrono::NaiveDate>,
+    ) -> Result<AccountingCsv, AccountingCsvError> {
+        let id = AccountingCsvId::new();
+
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_accounting_csvs(),
+                CoreAccountingAction::ACCOUNTING_CSV_CREATE,
+            )
+            .await?;
+
+        let new_csv = NewAccountingCsv::builder()
+            .id(id)
+            .csv_type(AccountingCsvType::CashFlowStatement)
+            .statement(StatementCsvParams {
+                reference,
+                from,
+                until,
+            })
+            .audit_info(audit_info)
+            .build()
+            .expect("Could not build new Accounting CSV");
+
+        self.create_and_spawn(new_csv).await
+    }
+
+    pub async fn create_comparative_balance_sheet_csv(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        reference: String,
+        periods: Vec<StatementPeriod>,
+    ) -> Result<AccountingCsv, AccountingCsvError> {
+        self.create_comparative_statement_csv(
+            sub,
+            AccountingCsvType::ComparativeBalanceSheet,
+            ComparativeStatementCsvParams { reference, periods },
+        )
+        .await
+    }
+
+    pub async fn create_comparative_pl_statement_csv(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        reference: String,
+        periods: Vec<StatementPeriod>,
+    ) -> Result<AccountingCsv, AccountingCsvError> {
+        self.create_comparative_statement_csv(
+            sub,
+            AccountingCsvType::ComparativeProfitAndLoss,
+            ComparativeStatementCsvParams { reference, periods },
+        )
+        .await
+    }
+
+    async fn create_comparative_statement_csv(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        csv_type: AccountingCsvType,
+        params: ComparativeStatementCsvParams,
+    ) -> Result<AccountingCsv, AccountingCsvError> {
+        if params.periods.is_empty() {
+            return Err(AccountingCsvError::MissingRequiredField(
+                "periods".to_string(),
+            ));
+        }
+        let id = AccountingCsvId::new();
+
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_accounting_csvs(),
+                CoreAccountingAction::ACCOUNTING_CSV_CREATE,
+            )
+            .await?;
+
+        let new_csv = NewAccountingCsv::builder()
+            .id(id)
+            .csv_type(csv_type)
+            .comparative_statement(params)
+            .audit_info(audit_info)
+            .build()
+            .expect("Could not build new Accounting CSV");
+
+        self.create_and_spawn(new_csv).await
+    }
+
+    /// Exports every journal entry matching the filter, not only the page
+    /// being viewed.
+    pub async fn create_journal_entry_search_csv(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        filter: JournalEntryFilter,
+    ) -> Result<AccountingCsv, AccountingCsvError> {
+        let id = AccountingCsvId::new();
+
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_accounting_csvs(),
+                CoreAccountingAction::ACCOUNTING_CSV_CREATE,
+            )
+            .await?;
+
+        let new_csv = NewAccountingCsv::builder()
+            .id(id)
+            .csv_type(AccountingCsvType::JournalEntrySearch)
+            .journal_entry_filter(filter)
+            .audit_info(audit_info)
+            .build()
+            .expect("Could not build new Accounting CSV");
+
+        self.create_and_spawn(new_csv).await
+    }
+
+    /// Registers the job importing uploaded manual transaction CSVs, which
+    /// needs the manual transactions service the exports do not.
+    pub(crate) fn add_manual_transaction_import_initializer<E>(
+        &self,
+        chart_of_accounts: &ChartOfAccounts<Perms>,
+        manual_transactions: &ManualTransactions<Perms, E>,
+    ) where
+        E: OutboxEventMarker<GovernanceEvent>,
+        <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<GovernanceAction>,
+        <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<GovernanceObject>,
+        governance::CommitteeMemberId:
+            for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+    {
+        self.jobs
+            .add_initializer(ImportManualTransactionsCsvInitializer::new(
+                &self.repo,
+                &self.storage,
+                chart_of_accounts,
+                manual_transactions,
+                self.authz.audit(),
+            ));
+    }
+
+    /// Stores an uploaded CSV of manual transactions and spawns the job that
+    /// imports it. The outcome, including an error for every invalid line, is
+    /// recorded on the returned CSV and offered as a report to download.
+    pub async fn create_manual_transaction_import<E>(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        chart_id: impl Into<ChartId> + std::fmt::Debug,
+        file_name: String,
+        content: String,
+    ) -> Result<AccountingCsv, AccountingCsvError>
+    where
+        E: OutboxEventMarker<GovernanceEvent>,
+        <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<GovernanceAction>,
+        <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<GovernanceObject>,
+        governance::CommitteeMemberId:
+            for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+    {
+        let chart_id = chart_id.into();
+        let id = AccountingCsvId::new();
+
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_accounting_csvs(),
+                CoreAccountingAction::ACCOUNTING_CSV_CREATE,
+            )
+            .await?;
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_manual_transactions(),
+                CoreAccountingAction::MANUAL_TRANSACTION_CREATE,
+            )
+            .await?;
+
+        let new_csv = NewAccountingCsv::builder()
+            .id(id)
+            .csv_type(AccountingCsvType::ManualTransactionImport)
+            .chart_id(chart_id)
+            .manual_transaction_import(ManualTransactionImportFile { file_name, content })
+            .audit_info(audit_info)
+            .build()
+            .expect("Could not build new Accounting CSV");
+
+        let mut db = self.repo.begin_op().await?;
+        let csv = self.repo.create_in_op(&mut db, new_csv).await?;
+        self.jobs
+            .create_and_spawn_in_op::<ImportManualTransactionsCsvConfig<Perms, E>>(
+                &mut db,
+                csv.id,
+                ImportManualTransactionsCsvConfig {
+                    accounting_csv_id: csv.id,
+                    _phantom: std::marker::PhantomData,
+                },
+            )
+            .await?;
+
+        db.commit().await?;
+        Ok(csv)
+    }
+
+    async fn create_and_spawn(
+        &self,
+        new_csv: NewAccountingCsv,
+    ) -> Result<AccountingCsv, AccountingCsvError> {
         let mut db = self.repo.begin_op().await?;
         let csv = self.repo.create_in_op(&mut db, new_csv).await?;
         self.jobs
@@ -169,6 +435,52 @@ where
         Ok(csvs)
     }
 
+    pub async fn list_for_chart_id(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        query: es_entity::PaginatedQueryArgs<AccountingCsvsByCreatedAtCursor>,
+        chart_id: impl Into<ChartId> + std::fmt::Debug,
+    ) -> Result<
+        es_entity::PaginatedQueryRet<AccountingCsv, AccountingCsvsByCreatedAtCursor>,
+        AccountingCsvError,
+    > {
+        let chart_id = chart_id.into();
+
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_accounting_csvs(),
+                CoreAccountingAction::ACCOUNTING_CSV_LIST,
+            )
+            .await?;
+
+        self.repo
+            .list_for_chart_id_by_created_at(Some(chart_id), query, ListDirection::Descending)
+            .await
+    }
+
+    pub async fn list_for_csv_type(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        query: es_entity::PaginatedQueryArgs<AccountingCsvsByCreatedAtCursor>,
+        csv_type: AccountingCsvType,
+    ) -> Result<
+        es_entity::PaginatedQueryRet<AccountingCsv, AccountingCsvsByCreatedAtCursor>,
+        AccountingCsvError,
+    > {
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_accounting_csvs(),
+                CoreAccountingAction::ACCOUNTING_CSV_LIST,
+            )
+            .await?;
+
+        self.repo
+            .list_for_csv_type_by_created_at(csv_type, query, ListDirection::Descending)
+            .await
+    }
+
     pub async fn find_all<T: From<AccountingCsv>>(
         &self,
         ids: &[AccountingCsvId],
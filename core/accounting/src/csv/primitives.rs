@@ -1,6 +1,7 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 
-use crate::primitives::AccountingCsvId;
+use crate::{comparative::StatementPeriod, primitives::AccountingCsvId};
 
 #[derive(
     Debug, Clone, Serialize, Deserialize, PartialEq, strum::Display, strum::EnumString, Copy,
@@ -10,6 +11,34 @@ pub enum AccountingCsvType {
     LedgerAccount,
     ProfitAndLoss,
     BalanceSheet,
+    ChartOfAccounts,
+    CashFlowStatement,
+    ComparativeBalanceSheet,
+    ComparativeProfitAndLoss,
+    JournalEntrySearch,
+    ManualTransactionImport,
+}
+
+/// The statement and period a financial statement export is generated for.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct StatementCsvParams {
+    pub reference: String,
+    pub from: NaiveDate,
+    pub until: Option<NaiveDate>,
+}
+
+/// The statement and the periods laid out side by side in a comparative export.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ComparativeStatementCsvParams {
+    pub reference: String,
+    pub periods: Vec<StatementPeriod>,
+}
+
+/// An uploaded CSV of manual transactions waiting to be imported.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManualTransactionImportFile {
+    pub file_name: String,
+    pub content: String,
 }
 
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
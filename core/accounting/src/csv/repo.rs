@@ -2,7 +2,7 @@ use sqlx::PgPool;
 
 use es_entity::*;
 
-use crate::primitives::{AccountingCsvId, LedgerAccountId};
+use crate::primitives::{AccountingCsvId, ChartId, LedgerAccountId};
 
 use super::{entity::*, error::*, primitives::*};
 
@@ -11,8 +11,9 @@ use super::{entity::*, error::*, primitives::*};
     entity = "AccountingCsv",
     err = "AccountingCsvError",
     columns(
-        csv_type(ty = "AccountingCsvType"),
+        csv_type(ty = "AccountingCsvType", list_for),
         ledger_account_id(ty = "Option<LedgerAccountId>", list_for),
+        chart_id(ty = "Option<ChartId>", list_for),
     )
 )]
 pub struct AccountingCsvRepo {
@@ -10,6 +10,10 @@ pub enum CoreAccountingError {
     LedgerAccountError(#[from] super::ledger_account::error::LedgerAccountError),
     #[error("CoreAccountingError - ManualTransaction: {0}")]
     ManualTransactionError(#[from] super::manual_transaction::error::ManualTransactionError),
+    #[error("CoreAccountingError - RecurringTransaction: {0}")]
+    RecurringTransactionError(
+        #[from] super::recurring_transaction::error::RecurringTransactionError,
+    ),
     #[error("CoreAccountingError - LedgerTransaction: {0}")]
     LedgerTransactionError(#[from] super::ledger_transaction::error::LedgerTransactionError),
     #[error("CoreAccountingError - AccountCodeParseError: {0}")]
@@ -18,4 +22,12 @@ pub enum CoreAccountingError {
     AccountingCsvError(#[from] super::csv::error::AccountingCsvError),
     #[error("CoreAccountingError - TrialBalanceError: {0}")]
     TrialBalance(#[from] super::trial_balance::error::TrialBalanceError),
+    #[error("CoreAccountingError - BalanceSheetError: {0}")]
+    BalanceSheet(#[from] super::balance_sheet::error::BalanceSheetError),
+    #[error("CoreAccountingError - BalanceSheetNotConfigured: {0}")]
+    BalanceSheetNotConfigured(String),
+    #[error("CoreAccountingError - JournalError: {0}")]
+    Journal(#[from] super::journal_error::JournalError),
+    #[error("CoreAccountingError - AccountingPeriodError: {0}")]
+    AccountingPeriod(#[from] super::accounting_period::error::AccountingPeriodError),
 }
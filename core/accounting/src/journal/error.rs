@@ -8,6 +8,14 @@ pub enum JournalError {
     CalaLedger(#[from] cala_ledger::error::LedgerError),
     #[error("JournalError - CalaEntryError: {0}")]
     CalaEntry(#[from] cala_ledger::entry::error::EntryError),
+    #[error("JournalError - CalaTransactionError: {0}")]
+    CalaTransaction(#[from] cala_ledger::transaction::error::TransactionError),
+    #[error("JournalError - CalaTxTemplateError: {0}")]
+    CalaTxTemplate(#[from] cala_ledger::tx_template::error::TxTemplateError),
+    #[error("JournalError - CalaAccountSetError: {0}")]
+    CalaAccountSet(#[from] cala_ledger::account_set::error::AccountSetError),
+    #[error("JournalError - JobError: {0}")]
+    JobError(#[from] job::error::JobError),
     #[error("JournalError - AuthorizationError: {0}")]
     AuthorizationError(#[from] authz::error::AuthorizationError),
     #[error("JournalError - UnexpectedCurrency")]
@@ -1,15 +1,34 @@
 mod entry;
 pub mod error;
+mod projection_job;
+mod repo;
+mod search;
+
+use std::collections::HashMap;
 
 pub use entry::*;
 use error::*;
+pub use search::*;
 
 use audit::AuditSvc;
 use authz::PermissionCheck;
 
-use cala_ledger::CalaLedger;
+use cala_ledger::{
+    AccountSetId, CalaLedger,
+    account_set::{AccountSetMemberId, AccountSetMembersByCreatedAtCursor, error::AccountSetError},
+    entry::Entry,
+};
+use job::Jobs;
+use uuid::Uuid;
+
+use crate::primitives::{
+    CalaJournalId, CoreAccountingAction, CoreAccountingObject, LedgerAccountId,
+};
+
+use projection_job::*;
+use repo::*;
 
-use crate::primitives::{CalaJournalId, CoreAccountingAction, CoreAccountingObject};
+const MAX_ACCOUNT_SET_DEPTH: usize = 10;
 
 #[derive(Clone)]
 pub struct Journal<Perms>
@@ -19,6 +38,7 @@ where
     authz: Perms,
     cala: CalaLedger,
     journal_id: CalaJournalId,
+    search: JournalEntrySearchRepo,
 }
 
 impl<Perms> Journal<Perms>
@@ -27,12 +47,25 @@ where
     <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreAccountingAction>,
     <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreAccountingObject>,
 {
-    pub fn new(authz: &Perms, cala: &CalaLedger, journal_id: CalaJournalId) -> Self {
-        Self {
+    pub async fn init(
+        pool: &sqlx::PgPool,
+        authz: &Perms,
+        cala: &CalaLedger,
+        journal_id: CalaJournalId,
+        jobs: &Jobs,
+    ) -> Result<Self, JournalError> {
+        let search = JournalEntrySearchRepo::new(pool);
+        jobs.add_initializer_and_spawn_unique(
+            JournalEntrySearchProjectionJobInitializer::new(cala, &search),
+            JournalEntrySearchProjectionJobConfig,
+        )
+        .await?;
+        Ok(Self {
             authz: authz.clone(),
             cala: cala.clone(),
             journal_id,
-        }
+            search,
+        })
     }
 
     pub async fn entries(
@@ -77,4 +110,99 @@ where
             end_cursor: ret.end_cursor.map(JournalEntryCursor::from),
         })
     }
+
+    pub async fn search_entries(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        filter: &JournalEntryFilter,
+        args: es_entity::PaginatedQueryArgs<JournalEntryCursor>,
+    ) -> Result<es_entity::PaginatedQueryRet<JournalEntry, JournalEntryCursor>, JournalError> {
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::journal(self.journal_id),
+                CoreAccountingAction::JOURNAL_READ_ENTRIES,
+            )
+            .await?;
+
+        self.find_matching_entries(filter, args).await
+    }
+
+    pub(crate) async fn find_matching_entries(
+        &self,
+        filter: &JournalEntryFilter,
+        args: es_entity::PaginatedQueryArgs<JournalEntryCursor>,
+    ) -> Result<es_entity::PaginatedQueryRet<JournalEntry, JournalEntryCursor>, JournalError> {
+        let account_ids = match filter.ledger_account_id {
+            Some(id) => Some(self.posting_account_ids(id).await?),
+            None => None,
+        };
+        let (ids, has_next_page) = self
+            .search
+            .find_matching_ids(self.journal_id, account_ids.as_deref(), filter, &args)
+            .await?;
+
+        let mut entries: HashMap<_, Entry> = self.cala.entries().find_all(&ids).await?;
+
+        let entities = ids
+            .iter()
+            .filter_map(|id| entries.remove(id))
+            .map(JournalEntry::try_from)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let end_cursor = entities.last().map(JournalEntryCursor::from);
+        Ok(es_entity::PaginatedQueryRet {
+            entities,
+            has_next_page,
+            end_cursor,
+        })
+    }
+
+    /// The accounts an entry has to be posted to for it to count towards the
+    /// ledger account: the account itself or, when it is an account set,
+    /// every account below it.
+    async fn posting_account_ids(&self, id: LedgerAccountId) -> Result<Vec<Uuid>, JournalError> {
+        match self.cala.account_sets().find(id.into()).await {
+            Ok(_) => (),
+            Err(AccountSetError::CouldNotFindById(_)) => return Ok(vec![id.into()]),
+            Err(e) => return Err(e.into()),
+        }
+
+        let mut account_ids = Vec::new();
+        let mut pending = vec![(AccountSetId::from(id), 0)];
+        while let Some((id, depth)) = pending.pop() {
+            if depth > MAX_ACCOUNT_SET_DEPTH {
+                continue;
+            }
+            let mut query = es_entity::PaginatedQueryArgs::<AccountSetMembersByCreatedAtCursor> {
+                first: 100,
+                after: None,
+            };
+            loop {
+                let res = self
+                    .cala
+                    .account_sets()
+                    .list_members_by_created_at(id, query)
+                    .await?;
+                for member in res.entities {
+                    match member.id {
+                        AccountSetMemberId::Account(account_id) => {
+                            account_ids.push(account_id.into())
+                        }
+                        AccountSetMemberId::AccountSet(set_id) => pending.push((set_id, depth + 1)),
+                    }
+                }
+                match res.end_cursor {
+                    Some(after) if res.has_next_page => {
+                        query = es_entity::PaginatedQueryArgs {
+                            first: 100,
+                            after: Some(after),
+                        }
+                    }
+                    _ => break,
+                }
+            }
+        }
+        Ok(account_ids)
+    }
 }
@@ -0,0 +1,132 @@
+use async_trait::async_trait;
+use futures::StreamExt;
+use std::collections::HashMap;
+
+use cala_ledger::{
+    CalaLedger, TxTemplateId,
+    outbox::{EventSequence, OutboxEventPayload},
+    tx_template::TxTemplate,
+};
+use job::*;
+
+use super::repo::JournalEntrySearchRepo;
+
+#[derive(serde::Serialize)]
+pub struct JournalEntrySearchProjectionJobConfig;
+impl JobConfig for JournalEntrySearchProjectionJobConfig {
+    type Initializer = JournalEntrySearchProjectionJobInitializer;
+}
+
+pub struct JournalEntrySearchProjectionJobInitializer {
+    cala: CalaLedger,
+    repo: JournalEntrySearchRepo,
+}
+
+impl JournalEntrySearchProjectionJobInitializer {
+    pub fn new(cala: &CalaLedger, repo: &JournalEntrySearchRepo) -> Self {
+        Self {
+            cala: cala.clone(),
+            repo: repo.clone(),
+        }
+    }
+}
+
+const JOURNAL_ENTRY_SEARCH_PROJECTION_JOB: JobType =
+    JobType::new("journal-entry-search-projection");
+impl JobInitializer for JournalEntrySearchProjectionJobInitializer {
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        JOURNAL_ENTRY_SEARCH_PROJECTION_JOB
+    }
+
+    fn init(&self, _: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(JournalEntrySearchProjectionJobRunner {
+            cala: self.cala.clone(),
+            repo: self.repo.clone(),
+        }))
+    }
+
+    fn retry_on_error_settings() -> RetrySettings
+    where
+        Self: Sized,
+    {
+        RetrySettings::repeat_indefinitely()
+    }
+}
+
+#[derive(Default, Clone, Copy, serde::Deserialize, serde::Serialize)]
+struct JournalEntrySearchProjectionJobData {
+    sequence: EventSequence,
+}
+
+pub struct JournalEntrySearchProjectionJobRunner {
+    cala: CalaLedger,
+    repo: JournalEntrySearchRepo,
+}
+
+#[async_trait]
+impl JobRunner for JournalEntrySearchProjectionJobRunner {
+    async fn run(
+        &self,
+        mut current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let mut state = current_job
+            .execution_state::<JournalEntrySearchProjectionJobData>()?
+            .unwrap_or_default();
+        let mut template_codes: HashMap<TxTemplateId, String> = HashMap::new();
+        let mut stream = self
+            .cala
+            .register_outbox_listener(Some(state.sequence))
+            .await?;
+
+        while let Some(message) = stream.next().await {
+            // Entries are created together with their transaction, so the
+            // whole transaction is projected when it shows up.
+            let OutboxEventPayload::TransactionCreated { transaction, .. } = &message.payload
+            else {
+                continue;
+            };
+
+            let template_id = transaction.tx_template_id;
+            if !template_codes.contains_key(&template_id) {
+                let templates: HashMap<TxTemplateId, TxTemplate> =
+                    self.cala.tx_templates().find_all(&[template_id]).await?;
+                template_codes.extend(
+                    templates
+                        .into_iter()
+                        .map(|(id, template)| (id, template.into_values().code)),
+                );
+            }
+            let entries = self
+                .cala
+                .entries()
+                .find_all(&transaction.entry_ids)
+                .await?
+                .into_values()
+                .map(|entry| (entry.values().clone(), entry.created_at()))
+                .collect::<Vec<_>>();
+
+            let mut db = self.repo.begin().await?;
+            self.repo
+                .insert_in_tx(
+                    &mut db,
+                    transaction,
+                    template_codes
+                        .get(&template_id)
+                        .map(String::as_str)
+                        .unwrap_or_default(),
+                    &entries,
+                )
+                .await?;
+            state.sequence = message.sequence;
+            current_job
+                .update_execution_state_in_tx(&mut db, &state)
+                .await?;
+            db.commit().await?;
+        }
+
+        Ok(JobCompletion::RescheduleNow)
+    }
+}
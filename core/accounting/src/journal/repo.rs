@@ -0,0 +1,142 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use cala_ledger::{EntryId, entry::EntryValues, transaction::TransactionValues};
+
+use crate::primitives::CalaJournalId;
+
+use super::{JournalEntryCursor, JournalEntryFilter, JournalEntryOrigin, error::JournalError};
+
+/// The searchable projection of the ledger entries, kept up to date from the
+/// ledger outbox so that searches don't have to walk the journal.
+#[derive(Clone)]
+pub struct JournalEntrySearchRepo {
+    pool: PgPool,
+}
+
+impl JournalEntrySearchRepo {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+
+    pub async fn begin(&self) -> Result<sqlx::Transaction<'_, sqlx::Postgres>, JournalError> {
+        Ok(self.pool.begin().await?)
+    }
+
+    /// Entries already in the projection are left untouched so that outbox
+    /// events can be replayed.
+    pub async fn insert_in_tx(
+        &self,
+        tx: &mut sqlx::Transaction<'_, sqlx::Postgres>,
+        transaction: &TransactionValues,
+        template_code: &str,
+        entries: &[(EntryValues, chrono::DateTime<chrono::Utc>)],
+    ) -> Result<(), JournalError> {
+        if entries.is_empty() {
+            return Ok(());
+        }
+        let manual = JournalEntryOrigin::of_template(template_code) == JournalEntryOrigin::Manual;
+        let mut entry_ids = Vec::with_capacity(entries.len());
+        let mut journal_ids = Vec::with_capacity(entries.len());
+        let mut account_ids = Vec::with_capacity(entries.len());
+        let mut units = Vec::with_capacity(entries.len());
+        let mut descriptions = Vec::with_capacity(entries.len());
+        let mut created_ats = Vec::with_capacity(entries.len());
+        for (entry, created_at) in entries {
+            entry_ids.push(Uuid::from(entry.id));
+            journal_ids.push(Uuid::from(entry.journal_id));
+            account_ids.push(Uuid::from(entry.account_id));
+            units.push(entry.units);
+            descriptions.push(
+                [&entry.description, &transaction.description]
+                    .into_iter()
+                    .flatten()
+                    .map(String::as_str)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            );
+            created_ats.push(*created_at);
+        }
+
+        sqlx::query!(
+            r#"
+            INSERT INTO core_journal_entry_search
+              (entry_id, journal_id, account_id, units, effective, template_code, manual, description, created_at)
+            SELECT entry_id, journal_id, account_id, units, $5, $6, $7, description, created_at
+            FROM UNNEST($1::uuid[], $2::uuid[], $3::uuid[], $4::numeric[], $8::text[], $9::timestamptz[])
+              AS e(entry_id, journal_id, account_id, units, description, created_at)
+            ON CONFLICT (entry_id) DO NOTHING
+            "#,
+            &entry_ids,
+            &journal_ids,
+            &account_ids,
+            &units,
+            transaction.effective,
+            template_code,
+            manual,
+            &descriptions,
+            &created_ats,
+        )
+        .execute(&mut **tx)
+        .await?;
+        Ok(())
+    }
+
+    /// Ids of the entries matching the filter, newest first, together with
+    /// whether more entries follow the page. `account_ids` are the accounts
+    /// the ledger account filter resolves to.
+    pub async fn find_matching_ids(
+        &self,
+        journal_id: CalaJournalId,
+        account_ids: Option<&[Uuid]>,
+        filter: &JournalEntryFilter,
+        args: &es_entity::PaginatedQueryArgs<JournalEntryCursor>,
+    ) -> Result<(Vec<EntryId>, bool), JournalError> {
+        let journal_id: Uuid = journal_id.into();
+        let (after_created_at, after_id) = match &args.after {
+            Some(cursor) => (Some(cursor.created_at), Some(Uuid::from(cursor.entry_id))),
+            None => (None, None),
+        };
+
+        let rows = sqlx::query!(
+            r#"
+            SELECT entry_id
+            FROM core_journal_entry_search
+            WHERE journal_id = $1
+              AND ($2::uuid[] IS NULL OR account_id = ANY($2))
+              AND ($3::numeric IS NULL OR units >= $3)
+              AND ($4::numeric IS NULL OR units <= $4)
+              AND ($5::date IS NULL OR effective >= $5)
+              AND ($6::date IS NULL OR effective <= $6)
+              AND ($7::varchar IS NULL OR template_code = $7)
+              AND ($8::boolean IS NULL OR manual = $8)
+              AND ($9::varchar IS NULL OR description ILIKE $9)
+              AND ($10::timestamptz IS NULL OR (created_at, entry_id) < ($10, $11::uuid))
+            ORDER BY created_at DESC, entry_id DESC
+            LIMIT $12
+            "#,
+            journal_id,
+            account_ids as Option<&[Uuid]>,
+            filter.min_amount,
+            filter.max_amount,
+            filter.effective_from,
+            filter.effective_until,
+            filter.template_code,
+            filter.manual_only(),
+            filter.description_pattern(),
+            after_created_at,
+            after_id,
+            (args.first + 1) as i64,
+        )
+        .fetch_all(&self.pool)
+        .await?;
+
+        let has_next_page = rows.len() > args.first;
+        let ids = rows
+            .into_iter()
+            .take(args.first)
+            .map(|row| EntryId::from(row.entry_id))
+            .collect();
+        Ok((ids, has_next_page))
+    }
+}
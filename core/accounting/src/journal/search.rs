@@ -0,0 +1,100 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use crate::{manual_transaction::ManualTransactionTemplate, primitives::LedgerAccountId};
+
+/// Whether an entry was posted through a manual transaction or by the system.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[serde(rename_all = "snake_case")]
+pub enum JournalEntryOrigin {
+    Manual,
+    System,
+}
+
+/// Criteria journal entries are searched by. Every criterion is optional and
+/// the ones that are set must all match.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct JournalEntryFilter {
+    /// Matches entries posted to the account itself or, when it is an account
+    /// set, to any account below it.
+    pub ledger_account_id: Option<LedgerAccountId>,
+    /// Bounds on the entry amount in the major unit of its currency.
+    pub min_amount: Option<Decimal>,
+    pub max_amount: Option<Decimal>,
+    pub effective_from: Option<NaiveDate>,
+    pub effective_until: Option<NaiveDate>,
+    pub template_code: Option<String>,
+    pub origin: Option<JournalEntryOrigin>,
+    /// Case insensitive match against the entry and transaction descriptions.
+    pub description: Option<String>,
+}
+
+impl JournalEntryFilter {
+    pub(super) fn description_pattern(&self) -> Option<String> {
+        self.description
+            .as_deref()
+            .map(str::trim)
+            .filter(|text| !text.is_empty())
+            .map(|text| {
+                let escaped = text
+                    .replace('\\', "\\\\")
+                    .replace('%', "\\%")
+                    .replace('_', "\\_");
+                format!("%{escaped}%")
+            })
+    }
+
+    pub(super) fn manual_only(&self) -> Option<bool> {
+        self.origin
+            .map(|origin| origin == JournalEntryOrigin::Manual)
+    }
+}
+
+impl JournalEntryOrigin {
+    /// Manual transactions and their reversals are posted through the manual
+    /// transaction templates, everything else is posted by the system.
+    pub(super) fn of_template(template_code: &str) -> Self {
+        if ManualTransactionTemplate::is_manual_code(template_code) {
+            Self::Manual
+        } else {
+            Self::System
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn description_pattern_escapes_wildcards() {
+        let filter = JournalEntryFilter {
+            description: Some(" 50%_fee ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            filter.description_pattern().as_deref(),
+            Some("%50\\%\\_fee%")
+        );
+
+        let blank = JournalEntryFilter {
+            description: Some("  ".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(blank.description_pattern(), None);
+    }
+
+    #[test]
+    fn manual_transaction_templates_are_manual_origin() {
+        assert_eq!(
+            JournalEntryOrigin::of_template("MANUAL_TRANSACTION_2"),
+            JournalEntryOrigin::Manual
+        );
+        assert_eq!(
+            JournalEntryOrigin::of_template("RECORD_DEPOSIT"),
+            JournalEntryOrigin::System
+        );
+    }
+}
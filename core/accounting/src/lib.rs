@@ -1,8 +1,11 @@
 #![cfg_attr(feature = "fail-on-warnings", deny(warnings))]
 #![cfg_attr(feature = "fail-on-warnings", deny(clippy::all))]
 
+pub mod accounting_period;
 pub mod balance_sheet;
+pub mod cash_flow_statement;
 pub mod chart_of_accounts;
+pub mod comparative;
 pub mod csv;
 pub mod error;
 pub mod journal;
@@ -11,6 +14,7 @@ pub mod ledger_transaction;
 pub mod manual_transaction;
 mod primitives;
 pub mod profit_and_loss;
+pub mod recurring_transaction;
 pub mod transaction_templates;
 pub mod trial_balance;
 
@@ -20,98 +24,156 @@ use audit::AuditSvc;
 use authz::PermissionCheck;
 use cala_ledger::CalaLedger;
 use cloud_storage::Storage;
+use governance::{Governance, GovernanceEvent};
 use job::Jobs;
-use manual_transaction::ManualTransactions;
+use manual_transaction::{AccountIdOrCode, ManualTransactions};
+use outbox::{Outbox, OutboxEventMarker};
 use tracing::instrument;
 
+pub use accounting_period::{
+    AccountingPeriod, AccountingPeriodCheck, AccountingPeriodStatus, AccountingPeriods, PostingKind,
+};
 pub use balance_sheet::{BalanceSheet, BalanceSheets};
-pub use chart_of_accounts::{Chart, ChartOfAccounts, error as chart_of_accounts_error, tree};
+pub use cash_flow_statement::{CashFlowStatement, CashFlowStatements};
+pub use chart_of_accounts::{
+    Chart, ChartImportDiff, ChartOfAccounts, error as chart_of_accounts_error, tree,
+};
+pub use comparative::{ComparativePeriodPreset, ComparativeStatement, StatementPeriod};
 pub use csv::AccountingCsvs;
 use error::CoreAccountingError;
-pub use journal::{Journal, error as journal_error};
+pub use journal::{Journal, JournalEntryFilter, JournalEntryOrigin, error as journal_error};
 pub use ledger_account::{LedgerAccount, LedgerAccountChildrenCursor, LedgerAccounts};
 pub use ledger_transaction::{LedgerTransaction, LedgerTransactions};
-pub use manual_transaction::ManualEntryInput;
+pub use manual_transaction::{
+    APPROVE_MANUAL_TRANSACTION_PROCESS, ManualEntryInput, ManualTransaction,
+    ManualTransactionImportError, ManualTransactionStatus,
+};
 pub use primitives::*;
 pub use profit_and_loss::{ProfitAndLossStatement, ProfitAndLossStatements};
+pub use recurring_transaction::{
+    RecurrenceSchedule, RecurringTransaction, RecurringTransactionStatus, RecurringTransactions,
+};
 pub use transaction_templates::TransactionTemplates;
 pub use trial_balance::{TrialBalanceRoot, TrialBalances};
 
-pub struct CoreAccounting<Perms>
+pub struct CoreAccounting<Perms, E>
 where
     Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>,
 {
     authz: Perms,
+    accounting_periods: AccountingPeriods<Perms>,
     chart_of_accounts: ChartOfAccounts<Perms>,
     journal: Journal<Perms>,
     ledger_accounts: LedgerAccounts<Perms>,
     ledger_transactions: LedgerTransactions<Perms>,
-    manual_transactions: ManualTransactions<Perms>,
+    manual_transactions: ManualTransactions<Perms, E>,
     profit_and_loss: ProfitAndLossStatements<Perms>,
+    recurring_transactions: RecurringTransactions<Perms, E>,
     transaction_templates: TransactionTemplates<Perms>,
     balance_sheets: BalanceSheets<Perms>,
+    cash_flow_statements: CashFlowStatements<Perms>,
     csvs: AccountingCsvs<Perms>,
     trial_balances: TrialBalances<Perms>,
 }
 
-impl<Perms> Clone for CoreAccounting<Perms>
+impl<Perms, E> Clone for CoreAccounting<Perms, E>
 where
     Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>,
 {
     fn clone(&self) -> Self {
         Self {
             authz: self.authz.clone(),
+            accounting_periods: self.accounting_periods.clone(),
             chart_of_accounts: self.chart_of_accounts.clone(),
             journal: self.journal.clone(),
             ledger_accounts: self.ledger_accounts.clone(),
             manual_transactions: self.manual_transactions.clone(),
             ledger_transactions: self.ledger_transactions.clone(),
             profit_and_loss: self.profit_and_loss.clone(),
+            recurring_transactions: self.recurring_transactions.clone(),
             transaction_templates: self.transaction_templates.clone(),
             balance_sheets: self.balance_sheets.clone(),
+            cash_flow_statements: self.cash_flow_statements.clone(),
             csvs: self.csvs.clone(),
             trial_balances: self.trial_balances.clone(),
         }
     }
 }
 
-impl<Perms> CoreAccounting<Perms>
+impl<Perms, E> CoreAccounting<Perms, E>
 where
     Perms: PermissionCheck,
-    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreAccountingAction>,
-    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreAccountingObject>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreAccountingAction> + From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreAccountingObject> + From<GovernanceObject>,
+    E: OutboxEventMarker<GovernanceEvent>,
 {
-    pub fn new(
+    #[allow(clippy::too_many_arguments)]
+    pub async fn init(
         pool: &sqlx::PgPool,
         authz: &Perms,
         cala: &CalaLedger,
         journal_id: CalaJournalId,
         storage: &Storage,
         jobs: &Jobs,
-    ) -> Self {
+        governance: &Governance<Perms, E>,
+        outbox: &Outbox<E>,
+    ) -> Result<Self, CoreAccountingError>
+    where
+        governance::CommitteeMemberId:
+            for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+    {
+        let accounting_periods = AccountingPeriods::new(pool, authz, cala, journal_id);
         let chart_of_accounts = ChartOfAccounts::new(pool, authz, cala, journal_id);
-        let journal = Journal::new(authz, cala, journal_id);
+        let journal = Journal::init(pool, authz, cala, journal_id, jobs).await?;
         let ledger_accounts = LedgerAccounts::new(authz, cala, journal_id);
-        let manual_transactions = ManualTransactions::new(pool, authz, cala, journal_id);
+        let manual_transactions =
+            ManualTransactions::init(pool, authz, cala, journal_id, governance, outbox, jobs)
+                .await?;
+        let recurring_transactions =
+            RecurringTransactions::init(pool, authz, &manual_transactions, jobs).await?;
         let ledger_transactions = LedgerTransactions::new(authz, cala);
         let profit_and_loss = ProfitAndLossStatements::new(pool, authz, cala, journal_id);
         let transaction_templates = TransactionTemplates::new(authz, cala);
         let balance_sheets = BalanceSheets::new(pool, authz, cala, journal_id);
-        let csvs = AccountingCsvs::new(pool, authz, jobs, storage, &ledger_accounts);
-        let trial_balances = TrialBalances::new(pool, authz, cala, journal_id);
-        Self {
+        let cash_flow_statements = CashFlowStatements::new(pool, authz, cala, journal_id);
+        let csvs = AccountingCsvs::new(
+            pool,
+            authz,
+            jobs,
+            storage,
+            &journal,
+            &ledger_accounts,
+            &chart_of_accounts,
+            &balance_sheets,
+            &profit_and_loss,
+            &cash_flow_statements,
+        );
+        csvs.add_manual_transaction_import_initializer(&chart_of_accounts, &manual_transactions);
+        let trial_balances = TrialBalances::new(pool, authz, cala, journal_id, jobs);
+        Ok(Self {
             authz: authz.clone(),
+            accounting_periods,
             chart_of_accounts,
             journal,
             ledger_accounts,
             ledger_transactions,
             manual_transactions,
             profit_and_loss,
+            recurring_transactions,
             transaction_templates,
             balance_sheets,
+            cash_flow_statements,
             csvs,
             trial_balances,
-        }
+        })
+    }
+
+    pub fn accounting_periods(&self) -> &AccountingPeriods<Perms> {
+        &self.accounting_periods
     }
 
     pub fn chart_of_accounts(&self) -> &ChartOfAccounts<Perms> {
@@ -130,10 +192,14 @@ where
         &self.ledger_transactions
     }
 
-    pub fn manual_transactions(&self) -> &ManualTransactions<Perms> {
+    pub fn manual_transactions(&self) -> &ManualTransactions<Perms, E> {
         &self.manual_transactions
     }
 
+    pub fn recurring_transactions(&self) -> &RecurringTransactions<Perms, E> {
+        &self.recurring_transactions
+    }
+
     pub fn profit_and_loss(&self) -> &ProfitAndLossStatements<Perms> {
         &self.profit_and_loss
     }
@@ -150,6 +216,10 @@ where
         &self.balance_sheets
     }
 
+    pub fn cash_flow_statements(&self) -> &CashFlowStatements<Perms> {
+        &self.cash_flow_statements
+    }
+
     pub fn trial_balances(&self) -> &TrialBalances<Perms> {
         &self.trial_balances
     }
@@ -208,6 +278,30 @@ where
         Ok(self.ledger_accounts.find_all(&chart, ids).await?)
     }
 
+    /// Resolves an account reference to the ledger account it designates. A
+    /// chart code resolves to the account set of that node.
+    pub async fn resolve_ledger_account_id(
+        &self,
+        chart_ref: &str,
+        account: &AccountIdOrCode,
+    ) -> Result<LedgerAccountId, CoreAccountingError> {
+        match account {
+            AccountIdOrCode::Id(id) => Ok(*id),
+            AccountIdOrCode::Code(code) => {
+                let chart = self
+                    .chart_of_accounts
+                    .find_by_reference(chart_ref)
+                    .await?
+                    .ok_or_else(move || {
+                        CoreAccountingError::ChartOfAccountsNotFoundByReference(
+                            chart_ref.to_string(),
+                        )
+                    })?;
+                Ok(chart.account_set_id_from_code(code)?.into())
+            }
+        }
+    }
+
     pub async fn list_account_children(
         &self,
         sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
@@ -234,6 +328,7 @@ where
             .await?)
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn execute_manual_transaction(
         &self,
         sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
@@ -242,7 +337,12 @@ where
         description: String,
         effective: Option<chrono::NaiveDate>,
         entries: Vec<ManualEntryInput>,
-    ) -> Result<LedgerTransaction, CoreAccountingError> {
+        auto_reverse: bool,
+    ) -> Result<ManualTransaction, CoreAccountingError>
+    where
+        governance::CommitteeMemberId:
+            for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+    {
         let chart = self
             .chart_of_accounts
             .find_by_reference(chart_ref)
@@ -251,7 +351,7 @@ where
                 CoreAccountingError::ChartOfAccountsNotFoundByReference(chart_ref.to_string())
             })?;
 
-        let tx = self
+        Ok(self
             .manual_transactions
             .execute(
                 sub,
@@ -260,14 +360,104 @@ where
                 description,
                 effective.unwrap_or_else(|| chrono::Utc::now().date_naive()),
                 entries,
+                auto_reverse,
             )
-            .await?;
+            .await?)
+    }
+
+    /// Imports the transactions of a CSV with one entry per line in the
+    /// background. They are posted all together or, when any line is
+    /// invalid, not at all; see [`AccountingCsvs::create_manual_transaction_import`].
+    pub async fn import_manual_transactions_csv(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        chart_ref: &str,
+        file_name: String,
+        data: String,
+    ) -> Result<csv::AccountingCsv, CoreAccountingError>
+    where
+        governance::CommitteeMemberId:
+            for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+    {
+        let chart = self
+            .chart_of_accounts
+            .find_by_reference(chart_ref)
+            .await?
+            .ok_or_else(move || {
+                CoreAccountingError::ChartOfAccountsNotFoundByReference(chart_ref.to_string())
+            })?;
+
+        Ok(self
+            .csvs
+            .create_manual_transaction_import::<E>(sub, chart.id, file_name, data)
+            .await?)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create_recurring_transaction(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        chart_ref: &str,
+        reference: String,
+        description: String,
+        entries: Vec<ManualEntryInput>,
+        schedule: RecurrenceSchedule,
+        start_date: chrono::NaiveDate,
+        end_date: Option<chrono::NaiveDate>,
+    ) -> Result<RecurringTransaction, CoreAccountingError>
+    where
+        governance::CommitteeMemberId:
+            for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+    {
+        let chart = self
+            .chart_of_accounts
+            .find_by_reference(chart_ref)
+            .await?
+            .ok_or_else(move || {
+                CoreAccountingError::ChartOfAccountsNotFoundByReference(chart_ref.to_string())
+            })?;
+
+        Ok(self
+            .recurring_transactions
+            .create(
+                sub,
+                &chart,
+                reference,
+                description,
+                entries,
+                schedule,
+                start_date,
+                end_date,
+            )
+            .await?)
+    }
+
+    pub async fn close_accounting_period_year_end(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        chart_ref: &str,
+        balance_sheet_ref: &str,
+        id: impl Into<AccountingPeriodId> + std::fmt::Debug,
+    ) -> Result<AccountingPeriod, CoreAccountingError> {
+        let chart = self
+            .chart_of_accounts
+            .find_by_reference(chart_ref)
+            .await?
+            .ok_or_else(move || {
+                CoreAccountingError::ChartOfAccountsNotFoundByReference(chart_ref.to_string())
+            })?;
+        let config = self
+            .balance_sheets
+            .get_chart_of_accounts_integration_config(sub, balance_sheet_ref.to_string())
+            .await?
+            .ok_or_else(move || {
+                CoreAccountingError::BalanceSheetNotConfigured(balance_sheet_ref.to_string())
+            })?;
 
-        let ledger_tx_id = tx.ledger_transaction_id;
-        let mut txs = self.ledger_transactions.find_all(&[ledger_tx_id]).await?;
-        Ok(txs
-            .remove(&ledger_tx_id)
-            .expect("Could not find LedgerTransaction"))
+        Ok(self
+            .accounting_periods
+            .close_year_end(sub, &chart, &config, id)
+            .await?)
     }
 
     pub async fn import_csv(
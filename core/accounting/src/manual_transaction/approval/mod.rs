@@ -0,0 +1,171 @@
+mod job;
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use cala_ledger::JournalId;
+use governance::{
+    ApprovalProcess, ApprovalProcessStatus, ApprovalProcessType, Governance, GovernanceAction,
+    GovernanceEvent, GovernanceObject,
+};
+use outbox::OutboxEventMarker;
+
+use crate::{
+    accounting_period::{AccountingPeriodCheck, PostingKind},
+    primitives::{CoreAccountingAction, CoreAccountingObject, ManualTransactionId},
+};
+
+use super::{
+    entity::ManualTransaction,
+    error::ManualTransactionError,
+    ledger::{EntryParams, ManualTransactionLedger, ManualTransactionParams},
+    repo::ManualTransactionRepo,
+};
+
+pub use job::*;
+
+pub const APPROVE_MANUAL_TRANSACTION_PROCESS: ApprovalProcessType =
+    ApprovalProcessType::new("manual-transaction");
+
+pub struct ApproveManualTransaction<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    repo: ManualTransactionRepo,
+    ledger: ManualTransactionLedger,
+    periods: AccountingPeriodCheck,
+    journal_id: JournalId,
+    audit: Perms::Audit,
+    governance: Governance<Perms, E>,
+}
+
+impl<Perms, E> Clone for ApproveManualTransaction<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            repo: self.repo.clone(),
+            ledger: self.ledger.clone(),
+            periods: self.periods.clone(),
+            journal_id: self.journal_id,
+            audit: self.audit.clone(),
+            governance: self.governance.clone(),
+        }
+    }
+}
+
+impl<Perms, E> ApproveManualTransaction<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreAccountingAction> + From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreAccountingObject> + From<GovernanceObject>,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    pub fn new(
+        repo: &ManualTransactionRepo,
+        ledger: &ManualTransactionLedger,
+        periods: &AccountingPeriodCheck,
+        journal_id: JournalId,
+        audit: &Perms::Audit,
+        governance: &Governance<Perms, E>,
+    ) -> Self {
+        Self {
+            repo: repo.clone(),
+            ledger: ledger.clone(),
+            periods: periods.clone(),
+            journal_id,
+            audit: audit.clone(),
+            governance: governance.clone(),
+        }
+    }
+
+    pub async fn execute_from_svc(
+        &self,
+        manual_tx: &ManualTransaction,
+    ) -> Result<Option<ManualTransaction>, ManualTransactionError> {
+        if manual_tx.is_approved_or_denied().is_some() {
+            return Ok(None);
+        }
+
+        let process: ApprovalProcess = self
+            .governance
+            .find_all_approval_processes(&[manual_tx.approval_process_id])
+            .await?
+            .remove(&manual_tx.approval_process_id)
+            .expect("approval process not found");
+
+        let res = match process.status() {
+            ApprovalProcessStatus::Approved => Some(self.execute(manual_tx.id, true).await?),
+            ApprovalProcessStatus::Denied => Some(self.execute(manual_tx.id, false).await?),
+            _ => None,
+        };
+        Ok(res)
+    }
+
+    #[es_entity::retry_on_concurrent_modification]
+    pub async fn execute(
+        &self,
+        id: impl es_entity::RetryableInto<ManualTransactionId>,
+        approved: bool,
+    ) -> Result<ManualTransaction, ManualTransactionError> {
+        let id = id.into();
+        let mut manual_tx = self.repo.find_by_id(id).await?;
+        if manual_tx.is_approved_or_denied().is_some() {
+            return Ok(manual_tx);
+        }
+        let mut db = self.repo.begin_op().await?;
+        let audit_info = self
+            .audit
+            .record_system_entry_in_tx(
+                db.tx(),
+                CoreAccountingObject::manual_transaction(id),
+                CoreAccountingAction::MANUAL_TRANSACTION_CONCLUDE_APPROVAL_PROCESS,
+            )
+            .await?;
+        if manual_tx
+            .approval_process_concluded(approved, audit_info)
+            .was_ignored()
+        {
+            return Ok(manual_tx);
+        }
+        self.repo.update_in_op(&mut db, &mut manual_tx).await?;
+
+        if !approved {
+            db.commit().await?;
+            return Ok(manual_tx);
+        }
+
+        self.periods
+            .ensure_accepts_posting_in_op(
+                &mut db,
+                manual_tx.effective,
+                PostingKind::ManualAdjustment,
+            )
+            .await?;
+
+        let entry_params = manual_tx
+            .entries
+            .iter()
+            .cloned()
+            .map(EntryParams::from)
+            .collect();
+        self.ledger
+            .execute(
+                db,
+                manual_tx.ledger_transaction_id,
+                ManualTransactionParams {
+                    journal_id: self.journal_id,
+                    description: manual_tx.description.clone(),
+                    entry_params,
+                    effective: manual_tx.effective,
+                },
+            )
+            .await?;
+
+        Ok(manual_tx)
+    }
+}
@@ -0,0 +1,231 @@
+use async_trait::async_trait;
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use cala_ledger::JournalId;
+use job::*;
+
+use crate::{
+    accounting_period::{AccountingPeriodCheck, PostingKind},
+    primitives::{CalaTxId, CoreAccountingAction, CoreAccountingObject, ManualTransactionId},
+};
+
+use super::{
+    ManualTransaction, ManualTransactionStatus,
+    error::ManualTransactionError,
+    ledger::ManualTransactionLedger,
+    repo::{ManualTransactionRepo, manual_transaction_cursor::*},
+    reversal_params,
+};
+
+const AUTO_REVERSAL_REASON: &str = "Automatic reversal of accrual";
+
+#[derive(serde::Serialize)]
+pub struct AutoReverseManualTransactionsJobConfig<Perms> {
+    _phantom: std::marker::PhantomData<Perms>,
+}
+impl<Perms> AutoReverseManualTransactionsJobConfig<Perms> {
+    pub fn new() -> Self {
+        Self {
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+impl<Perms> JobConfig for AutoReverseManualTransactionsJobConfig<Perms>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreAccountingAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreAccountingObject>,
+{
+    type Initializer = AutoReverseManualTransactionsJobInitializer<Perms>;
+}
+
+pub struct AutoReverseManualTransactionsJobInitializer<Perms>
+where
+    Perms: PermissionCheck,
+{
+    repo: ManualTransactionRepo,
+    ledger: ManualTransactionLedger,
+    periods: AccountingPeriodCheck,
+    journal_id: JournalId,
+    audit: Perms::Audit,
+}
+
+impl<Perms> AutoReverseManualTransactionsJobInitializer<Perms>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreAccountingAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreAccountingObject>,
+{
+    pub fn new(
+        repo: &ManualTransactionRepo,
+        ledger: &ManualTransactionLedger,
+        periods: &AccountingPeriodCheck,
+        journal_id: JournalId,
+        audit: &Perms::Audit,
+    ) -> Self {
+        Self {
+            repo: repo.clone(),
+            ledger: ledger.clone(),
+            periods: periods.clone(),
+            journal_id,
+            audit: audit.clone(),
+        }
+    }
+}
+
+const AUTO_REVERSE_MANUAL_TRANSACTIONS_JOB: JobType =
+    JobType::new("auto-reverse-manual-transactions");
+impl<Perms> JobInitializer for AutoReverseManualTransactionsJobInitializer<Perms>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreAccountingAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreAccountingObject>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        AUTO_REVERSE_MANUAL_TRANSACTIONS_JOB
+    }
+
+    fn init(&self, _: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(AutoReverseManualTransactionsJobRunner::<Perms> {
+            repo: self.repo.clone(),
+            ledger: self.ledger.clone(),
+            periods: self.periods.clone(),
+            journal_id: self.journal_id,
+            audit: self.audit.clone(),
+        }))
+    }
+}
+
+pub struct AutoReverseManualTransactionsJobRunner<Perms>
+where
+    Perms: PermissionCheck,
+{
+    repo: ManualTransactionRepo,
+    ledger: ManualTransactionLedger,
+    periods: AccountingPeriodCheck,
+    journal_id: JournalId,
+    audit: Perms::Audit,
+}
+
+impl<Perms> AutoReverseManualTransactionsJobRunner<Perms>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreAccountingAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreAccountingObject>,
+{
+    async fn reverse(
+        &self,
+        manual_tx: &mut ManualTransaction,
+        reverse_on: chrono::NaiveDate,
+    ) -> Result<(), ManualTransactionError> {
+        let mut db = self.repo.begin_op().await?;
+        self.periods
+            .ensure_accepts_posting_in_op(&mut db, reverse_on, PostingKind::ManualAdjustment)
+            .await?;
+        let audit_info = self
+            .audit
+            .record_system_entry_in_tx(
+                db.tx(),
+                CoreAccountingObject::manual_transaction(manual_tx.id),
+                CoreAccountingAction::MANUAL_TRANSACTION_REVERSE,
+            )
+            .await?;
+        let reversal_tx_id = CalaTxId::new();
+        let params = reversal_params(manual_tx, self.journal_id, reverse_on, AUTO_REVERSAL_REASON);
+        manual_tx.reverse(
+            reversal_tx_id,
+            reverse_on,
+            AUTO_REVERSAL_REASON.to_string(),
+            audit_info,
+        )?;
+        self.repo.update_in_op(&mut db, manual_tx).await?;
+        self.ledger.execute(db, reversal_tx_id, params).await?;
+        Ok(())
+    }
+
+    async fn record_failure(
+        &self,
+        id: ManualTransactionId,
+        error: String,
+    ) -> Result<(), ManualTransactionError> {
+        let mut manual_tx = self.repo.find_by_id(id).await?;
+        let mut db = self.repo.begin_op().await?;
+        let audit_info = self
+            .audit
+            .record_system_entry_in_tx(
+                db.tx(),
+                CoreAccountingObject::manual_transaction(id),
+                CoreAccountingAction::MANUAL_TRANSACTION_REVERSE,
+            )
+            .await?;
+        if manual_tx
+            .auto_reversal_failed(error, audit_info)
+            .did_execute()
+        {
+            self.repo.update_in_op(&mut db, &mut manual_tx).await?;
+            db.commit().await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<Perms> JobRunner for AutoReverseManualTransactionsJobRunner<Perms>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreAccountingAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreAccountingObject>,
+{
+    async fn run(
+        &self,
+        _current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let now = chrono::Utc::now();
+        let today = now.date_naive();
+
+        let mut after = None;
+        'pages: loop {
+            let ret =
+                self.repo
+                    .list_by_pending_auto_reverse_on(
+                        es_entity::PaginatedQueryArgs::<
+                            ManualTransactionsByPendingAutoReverseOnCursor,
+                        > {
+                            first: 100,
+                            after,
+                        },
+                        es_entity::ListDirection::Ascending,
+                    )
+                    .await?;
+
+            for mut manual_tx in ret.entities.into_iter() {
+                let reverse_on = match manual_tx.pending_auto_reverse_on() {
+                    Some(reverse_on) if reverse_on <= today => reverse_on,
+                    _ => break 'pages,
+                };
+                // Still awaiting approval.
+                if manual_tx.status() != ManualTransactionStatus::Posted {
+                    continue;
+                }
+
+                // A failed reversal, e.g. into a closed period, is recorded
+                // and left to be reversed by hand rather than holding up the
+                // accruals after it.
+                if let Err(e) = self.reverse(&mut manual_tx, reverse_on).await {
+                    self.record_failure(manual_tx.id, e.to_string()).await?;
+                }
+            }
+
+            if !ret.has_next_page {
+                break;
+            }
+            after = ret.end_cursor;
+        }
+
+        Ok(JobCompletion::RescheduleAt(now + chrono::Duration::days(1)))
+    }
+}
@@ -0,0 +1,286 @@
+use std::{collections::HashMap, io::Cursor};
+
+use cala_ledger::{Currency, DebitOrCredit};
+use chrono::NaiveDate;
+use csv::{ReaderBuilder, Trim};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use super::primitives::{AccountIdOrCode, ManualEntryInput};
+
+const COLUMNS: [&str; 7] = [
+    "reference",
+    "effective_date",
+    "account_code",
+    "direction",
+    "amount",
+    "currency",
+    "description",
+];
+
+/// A problem with a line of an imported CSV. Line numbers count the header
+/// as the first line.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManualTransactionImportError {
+    pub line: u64,
+    pub reference: Option<String>,
+    pub message: String,
+}
+
+impl ManualTransactionImportError {
+    pub(super) fn new(line: u64, reference: Option<&str>, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            reference: reference.map(str::to_string),
+            message: message.into(),
+        }
+    }
+}
+
+/// The lines of the CSV that share a reference, which make up one
+/// transaction. Entries keep the line they were read from.
+pub(crate) struct ImportedManualTransaction {
+    pub(super) reference: String,
+    pub(super) effective: NaiveDate,
+    pub(super) description: String,
+    pub(super) entries: Vec<(u64, ManualEntryInput)>,
+}
+
+/// Parses a CSV with one entry per line and groups the entries into
+/// transactions by reference. Fails with every problem found when any line
+/// is invalid or any transaction does not balance in each currency.
+pub(crate) fn parse_manual_transactions_csv(
+    data: &str,
+) -> Result<Vec<ImportedManualTransaction>, Vec<ManualTransactionImportError>> {
+    let mut rdr = ReaderBuilder::new()
+        .flexible(true)
+        .trim(Trim::All)
+        .from_reader(Cursor::new(data));
+
+    let headers = rdr
+        .headers()
+        .map_err(|e| vec![ManualTransactionImportError::new(1, None, e.to_string())])?
+        .clone();
+    let mut positions = HashMap::new();
+    let mut errors = vec![];
+    for column in COLUMNS {
+        match headers.iter().position(|h| h.eq_ignore_ascii_case(column)) {
+            Some(idx) => {
+                positions.insert(column, idx);
+            }
+            None => errors.push(ManualTransactionImportError::new(
+                1,
+                None,
+                format!("missing column '{column}'"),
+            )),
+        }
+    }
+    if !errors.is_empty() {
+        return Err(errors);
+    }
+
+    let mut transactions: Vec<ImportedManualTransaction> = vec![];
+    for result in rdr.records() {
+        let record = match result {
+            Ok(record) => record,
+            Err(e) => {
+                let line = e.position().map(|p| p.line()).unwrap_or_default();
+                errors.push(ManualTransactionImportError::new(line, None, e.to_string()));
+                continue;
+            }
+        };
+        if record.iter().all(|field| field.is_empty()) {
+            continue;
+        }
+        let line = record.position().map(|p| p.line()).unwrap_or_default();
+        let field = |column: &str| record.get(positions[column]).unwrap_or_default();
+
+        let reference = field("reference");
+        if reference.is_empty() {
+            errors.push(ManualTransactionImportError::new(
+                line,
+                None,
+                "reference is required",
+            ));
+            continue;
+        }
+        let mut error = |message: String| {
+            errors.push(ManualTransactionImportError::new(
+                line,
+                Some(reference),
+                message,
+            ))
+        };
+
+        let effective = field("effective_date").parse::<NaiveDate>().ok();
+        let account = field("account_code").parse::<AccountIdOrCode>().ok();
+        let direction = parse_direction(field("direction"));
+        let amount = field("amount").parse::<Decimal>().ok();
+        let currency = field("currency").parse::<Currency>().ok();
+
+        let (Some(effective), Some(account), Some(direction), Some(amount), Some(currency)) =
+            (effective, account.clone(), direction, amount, currency)
+        else {
+            if effective.is_none() {
+                error(format!(
+                    "invalid effective date '{}'",
+                    field("effective_date")
+                ));
+            }
+            if account.is_none() {
+                error(format!("invalid account code '{}'", field("account_code")));
+            }
+            if direction.is_none() {
+                error(format!("invalid direction '{}'", field("direction")));
+            }
+            if amount.is_none() {
+                error(format!("invalid amount '{}'", field("amount")));
+            }
+            if currency.is_none() {
+                error(format!("invalid currency '{}'", field("currency")));
+            }
+            continue;
+        };
+        if amount <= Decimal::ZERO {
+            error("amount must be positive".to_string());
+            continue;
+        }
+
+        let entry = ManualEntryInput {
+            account_id_or_code: account,
+            amount,
+            currency,
+            description: field("description").to_string(),
+            direction,
+        };
+        match transactions.iter_mut().find(|tx| tx.reference == reference) {
+            Some(tx) if tx.effective != effective => {
+                error(format!(
+                    "effective date differs from the {} used by earlier lines",
+                    tx.effective
+                ));
+            }
+            Some(tx) => tx.entries.push((line, entry)),
+            None => transactions.push(ImportedManualTransaction {
+                reference: reference.to_string(),
+                effective,
+                description: entry.description.clone(),
+                entries: vec![(line, entry)],
+            }),
+        }
+    }
+
+    for tx in transactions.iter() {
+        errors.extend(unbalanced_currencies(tx));
+    }
+    if !errors.is_empty() {
+        errors.sort_by_key(|e| e.line);
+        return Err(errors);
+    }
+    Ok(transactions)
+}
+
+fn parse_direction(direction: &str) -> Option<DebitOrCredit> {
+    if direction.eq_ignore_ascii_case("debit") {
+        Some(DebitOrCredit::Debit)
+    } else if direction.eq_ignore_ascii_case("credit") {
+        Some(DebitOrCredit::Credit)
+    } else {
+        None
+    }
+}
+
+fn unbalanced_currencies(tx: &ImportedManualTransaction) -> Vec<ManualTransactionImportError> {
+    let first_line = tx
+        .entries
+        .first()
+        .map(|(line, _)| *line)
+        .unwrap_or_default();
+    let mut totals: Vec<(Currency, Decimal)> = vec![];
+    for (_, entry) in tx.entries.iter() {
+        let signed = match entry.direction {
+            DebitOrCredit::Debit => entry.amount,
+            DebitOrCredit::Credit => -entry.amount,
+        };
+        match totals.iter_mut().find(|(c, _)| *c == entry.currency) {
+            Some((_, total)) => *total += signed,
+            None => totals.push((entry.currency, signed)),
+        }
+    }
+    totals
+        .into_iter()
+        .filter(|(_, total)| !total.is_zero())
+        .map(|(currency, total)| {
+            ManualTransactionImportError::new(
+                first_line,
+                Some(&tx.reference),
+                format!("{currency} debits and credits differ by {}", total.abs()),
+            )
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HEADER: &str =
+        "reference,effective_date,account_code,direction,amount,currency,description";
+
+    #[test]
+    fn groups_lines_into_transactions_by_reference() {
+        let data = format!(
+            "{HEADER}\n\
+             ADJ-1,2025-01-31,1,debit,100,USD,Accrual\n\
+             ADJ-2,2025-01-31,1,debit,0.5,BTC,Transfer\n\
+             ADJ-1,2025-01-31,2,credit,100,USD,Accrual\n\
+             ADJ-2,2025-01-31,2,credit,0.5,BTC,Transfer\n"
+        );
+
+        let transactions = parse_manual_transactions_csv(&data).unwrap();
+
+        assert_eq!(transactions.len(), 2);
+        assert_eq!(transactions[0].reference, "ADJ-1");
+        assert_eq!(
+            transactions[0]
+                .entries
+                .iter()
+                .map(|(line, _)| *line)
+                .collect::<Vec<_>>(),
+            vec![2, 4]
+        );
+        assert_eq!(transactions[1].entries.len(), 2);
+    }
+
+    #[test]
+    fn reports_every_invalid_line() {
+        let data = format!(
+            "{HEADER}\n\
+             ADJ-1,2025-01-31,1,debit,100,USD,Accrual\n\
+             ADJ-1,2025-01-31,2,credit,90,USD,Accrual\n\
+             ADJ-2,not-a-date,1,sideways,10,USD,Fee\n"
+        );
+
+        let errors = match parse_manual_transactions_csv(&data) {
+            Err(errors) => errors,
+            Ok(_) => panic!("expected the import to be rejected"),
+        };
+
+        assert_eq!(errors.len(), 3);
+        assert_eq!(errors[0].line, 2);
+        assert_eq!(errors[0].reference.as_deref(), Some("ADJ-1"));
+        assert!(errors[0].message.contains("differ by 10"));
+        assert!(errors[1..].iter().all(|e| e.line == 4));
+    }
+
+    #[test]
+    fn rejects_missing_columns() {
+        let errors = match parse_manual_transactions_csv("reference,amount\nADJ-1,10\n") {
+            Err(errors) => errors,
+            Ok(_) => panic!("expected the import to be rejected"),
+        };
+
+        assert_eq!(errors.len(), 5);
+        assert!(errors.iter().all(|e| e.line == 1));
+    }
+}
@@ -2,9 +2,18 @@ use derive_builder::Builder;
 use serde::{Deserialize, Serialize};
 
 use audit::AuditInfo;
+use cala_ledger::{Currency, DebitOrCredit};
+use core_money::ConversionError;
 use es_entity::*;
 
-use crate::primitives::{CalaTxId, ManualTransactionId};
+use crate::primitives::{
+    ApprovalProcessId, CalaTxId, ManualTransactionId, RecurringTransactionId, UsdCents,
+};
+
+use super::{
+    error::ManualTransactionError,
+    primitives::{ManualEntry, ManualTransactionStatus},
+};
 
 #[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -13,8 +22,29 @@ pub enum ManualTransactionEvent {
     Initialized {
         id: ManualTransactionId,
         ledger_transaction_id: CalaTxId,
+        approval_process_id: ApprovalProcessId,
         description: String,
         reference: String,
+        effective: chrono::NaiveDate,
+        entries: Vec<ManualEntry>,
+        auto_reverse_on: Option<chrono::NaiveDate>,
+        recurring_transaction_id: Option<RecurringTransactionId>,
+        audit_info: AuditInfo,
+    },
+    ApprovalProcessConcluded {
+        approval_process_id: ApprovalProcessId,
+        approved: bool,
+        audit_info: AuditInfo,
+    },
+    Reversed {
+        reversal_ledger_transaction_id: CalaTxId,
+        effective: chrono::NaiveDate,
+        reason: String,
+        audit_info: AuditInfo,
+    },
+    AutoReversalFailed {
+        reverse_on: chrono::NaiveDate,
+        error: String,
         audit_info: AuditInfo,
     },
 }
@@ -26,6 +56,17 @@ pub struct ManualTransaction {
     pub reference: String,
     pub description: String,
     pub ledger_transaction_id: CalaTxId,
+    pub approval_process_id: ApprovalProcessId,
+    pub effective: chrono::NaiveDate,
+    pub entries: Vec<ManualEntry>,
+    /// Set for accruals that reverse automatically on this date.
+    #[builder(default)]
+    pub auto_reverse_on: Option<chrono::NaiveDate>,
+    /// Set when posted on behalf of a recurring transaction.
+    #[builder(default)]
+    pub recurring_transaction_id: Option<RecurringTransactionId>,
+    #[builder(default)]
+    pub reversal_ledger_transaction_id: Option<CalaTxId>,
     events: EntityEvents<ManualTransactionEvent>,
 }
 
@@ -35,6 +76,137 @@ impl ManualTransaction {
             .entity_first_persisted_at()
             .expect("No events for deposit")
     }
+
+    pub fn is_approved_or_denied(&self) -> Option<bool> {
+        self.events.iter_all().find_map(|e| {
+            if let ManualTransactionEvent::ApprovalProcessConcluded { approved, .. } = e {
+                Some(*approved)
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn status(&self) -> ManualTransactionStatus {
+        match self.is_approved_or_denied() {
+            None => ManualTransactionStatus::PendingApproval,
+            Some(true) if self.reversal_ledger_transaction_id.is_some() => {
+                ManualTransactionStatus::Reversed
+            }
+            Some(true) => ManualTransactionStatus::Posted,
+            Some(false) => ManualTransactionStatus::Rejected,
+        }
+    }
+
+    /// The date an auto-reversing accrual is still waiting to be reversed on.
+    /// Accruals that were rejected, or failed to reverse automatically and
+    /// have to be reversed by hand, are no longer waiting.
+    pub(super) fn pending_auto_reverse_on(&self) -> Option<chrono::NaiveDate> {
+        if self.auto_reversal_error().is_some() {
+            return None;
+        }
+        match self.status() {
+            ManualTransactionStatus::PendingApproval | ManualTransactionStatus::Posted => {
+                self.auto_reverse_on
+            }
+            ManualTransactionStatus::Rejected | ManualTransactionStatus::Reversed => None,
+        }
+    }
+
+    /// Why the automatic reversal of the accrual failed, if it did.
+    pub fn auto_reversal_error(&self) -> Option<&str> {
+        self.events.iter_all().find_map(|e| match e {
+            ManualTransactionEvent::AutoReversalFailed { error, .. } => Some(error.as_str()),
+            _ => None,
+        })
+    }
+
+    /// Records that the accrual could not be reversed on its date, taking it
+    /// off the automatic reversal schedule.
+    pub(super) fn auto_reversal_failed(
+        &mut self,
+        error: String,
+        audit_info: AuditInfo,
+    ) -> Idempotent<()> {
+        let Some(reverse_on) = self.pending_auto_reverse_on() else {
+            return Idempotent::Ignored;
+        };
+        self.events
+            .push(ManualTransactionEvent::AutoReversalFailed {
+                reverse_on,
+                error,
+                audit_info,
+            });
+        Idempotent::Executed(())
+    }
+
+    /// The entries of the transaction with their directions flipped.
+    pub(super) fn reversal_entries(&self) -> Vec<ManualEntry> {
+        self.entries
+            .iter()
+            .cloned()
+            .map(|e| ManualEntry {
+                direction: match e.direction {
+                    DebitOrCredit::Debit => DebitOrCredit::Credit,
+                    DebitOrCredit::Credit => DebitOrCredit::Debit,
+                },
+                ..e
+            })
+            .collect()
+    }
+
+    /// Records the reversal of a posted transaction. The caller is expected to
+    /// post `reversal_entries()` under `reversal_ledger_transaction_id` in the
+    /// same operation.
+    pub fn reverse(
+        &mut self,
+        reversal_ledger_transaction_id: CalaTxId,
+        effective: chrono::NaiveDate,
+        reason: String,
+        audit_info: AuditInfo,
+    ) -> Result<(), ManualTransactionError> {
+        match self.status() {
+            ManualTransactionStatus::Posted => (),
+            ManualTransactionStatus::Reversed => {
+                return Err(ManualTransactionError::AlreadyReversed(self.id));
+            }
+            _ => return Err(ManualTransactionError::NotPosted(self.id)),
+        }
+        if effective < self.effective {
+            return Err(ManualTransactionError::ReversalBeforeOriginal(
+                self.effective,
+            ));
+        }
+
+        self.reversal_ledger_transaction_id = Some(reversal_ledger_transaction_id);
+        self.events.push(ManualTransactionEvent::Reversed {
+            reversal_ledger_transaction_id,
+            effective,
+            reason,
+            audit_info,
+        });
+        Ok(())
+    }
+
+    /// Records the outcome of the approval process. When approved the caller
+    /// is expected to post the ledger transaction in the same operation.
+    pub fn approval_process_concluded(
+        &mut self,
+        approved: bool,
+        audit_info: AuditInfo,
+    ) -> Idempotent<()> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            ManualTransactionEvent::ApprovalProcessConcluded { .. }
+        );
+        self.events
+            .push(ManualTransactionEvent::ApprovalProcessConcluded {
+                approval_process_id: self.approval_process_id,
+                approved,
+                audit_info,
+            });
+        Idempotent::Executed(())
+    }
 }
 
 impl TryFromEvents<ManualTransactionEvent> for ManualTransaction {
@@ -49,6 +221,11 @@ impl TryFromEvents<ManualTransactionEvent> for ManualTransaction {
                     reference,
                     description,
                     ledger_transaction_id,
+                    approval_process_id,
+                    effective,
+                    entries,
+                    auto_reverse_on,
+                    recurring_transaction_id,
                     ..
                 } => {
                     builder = builder
@@ -56,6 +233,20 @@ impl TryFromEvents<ManualTransactionEvent> for ManualTransaction {
                         .reference(reference.clone())
                         .description(description.clone())
                         .ledger_transaction_id(*ledger_transaction_id)
+                        .approval_process_id(*approval_process_id)
+                        .effective(*effective)
+                        .entries(entries.clone())
+                        .auto_reverse_on(*auto_reverse_on)
+                        .recurring_transaction_id(*recurring_transaction_id)
+                }
+                ManualTransactionEvent::ApprovalProcessConcluded { .. }
+                | ManualTransactionEvent::AutoReversalFailed { .. } => {}
+                ManualTransactionEvent::Reversed {
+                    reversal_ledger_transaction_id,
+                    ..
+                } => {
+                    builder = builder
+                        .reversal_ledger_transaction_id(Some(*reversal_ledger_transaction_id))
                 }
             }
         }
@@ -66,10 +257,18 @@ impl TryFromEvents<ManualTransactionEvent> for ManualTransaction {
 #[derive(Debug, Builder)]
 pub struct NewManualTransaction {
     #[builder(setter(into))]
-    pub(super) id: ManualTransactionId,
+    pub(crate) id: ManualTransactionId,
     reference: Option<String>,
     pub(super) ledger_transaction_id: CalaTxId,
+    #[builder(setter(into))]
+    pub(super) approval_process_id: ApprovalProcessId,
     description: String,
+    pub(super) effective: chrono::NaiveDate,
+    entries: Vec<ManualEntry>,
+    #[builder(default)]
+    pub(super) auto_reverse_on: Option<chrono::NaiveDate>,
+    #[builder(default)]
+    recurring_transaction_id: Option<RecurringTransactionId>,
     #[builder(setter(into))]
     pub audit_info: AuditInfo,
 }
@@ -86,6 +285,25 @@ impl NewManualTransaction {
             Some(reference) => reference.to_string(),
         }
     }
+
+    /// The amount the approval policy is evaluated against: the sum of the
+    /// USD debit entries. Entries in other currencies cannot be valued here,
+    /// so a transaction with any of them reaches the highest approval tier.
+    pub(super) fn approval_amount(&self) -> Result<Option<UsdCents>, ConversionError> {
+        if self.entries.iter().any(|e| e.currency != Currency::USD) {
+            return Ok(Some(UsdCents::from(u64::MAX)));
+        }
+        let total = self
+            .entries
+            .iter()
+            .filter(|e| e.direction == DebitOrCredit::Debit)
+            .map(|e| e.amount)
+            .sum::<rust_decimal::Decimal>();
+        if total.is_zero() {
+            return Ok(None);
+        }
+        Ok(Some(UsdCents::try_from_usd(total)?))
+    }
 }
 
 impl IntoEvents<ManualTransactionEvent> for NewManualTransaction {
@@ -96,9 +314,221 @@ impl IntoEvents<ManualTransactionEvent> for NewManualTransaction {
                 reference: self.reference(),
                 id: self.id,
                 ledger_transaction_id: self.ledger_transaction_id,
+                approval_process_id: self.approval_process_id,
                 description: self.description,
+                effective: self.effective,
+                entries: self.entries,
+                auto_reverse_on: self.auto_reverse_on,
+                recurring_transaction_id: self.recurring_transaction_id,
                 audit_info: self.audit_info,
             }],
         )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use audit::{AuditEntryId, AuditInfo};
+    use cala_ledger::AccountId as CalaAccountId;
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn dummy_audit_info() -> AuditInfo {
+        AuditInfo {
+            audit_entry_id: AuditEntryId::from(1),
+            sub: "sub".to_string(),
+        }
+    }
+
+    fn entry(amount: rust_decimal::Decimal, direction: DebitOrCredit) -> ManualEntry {
+        ManualEntry {
+            account_id: CalaAccountId::new(),
+            amount,
+            currency: Currency::USD,
+            description: "entry".to_string(),
+            direction,
+        }
+    }
+
+    fn btc_entry(amount: rust_decimal::Decimal, direction: DebitOrCredit) -> ManualEntry {
+        ManualEntry {
+            currency: Currency::BTC,
+            ..entry(amount, direction)
+        }
+    }
+
+    fn new_tx(entries: Vec<ManualEntry>) -> NewManualTransaction {
+        let id = ManualTransactionId::new();
+        NewManualTransaction::builder()
+            .id(id)
+            .ledger_transaction_id(CalaTxId::new())
+            .approval_process_id(id)
+            .reference(None)
+            .description("test".to_string())
+            .effective(chrono::Utc::now().date_naive())
+            .entries(entries)
+            .audit_info(dummy_audit_info())
+            .build()
+            .unwrap()
+    }
+
+    fn manual_tx_from(new_tx: NewManualTransaction) -> ManualTransaction {
+        ManualTransaction::try_from_events(new_tx.into_events()).unwrap()
+    }
+
+    #[test]
+    fn approval_amount_sums_usd_debits() {
+        let tx = new_tx(vec![
+            entry(dec!(100), DebitOrCredit::Debit),
+            entry(dec!(25.5), DebitOrCredit::Debit),
+            entry(dec!(125.5), DebitOrCredit::Credit),
+        ]);
+        assert_eq!(tx.approval_amount().unwrap(), Some(UsdCents::from(12_550)));
+        assert_eq!(new_tx(vec![]).approval_amount().unwrap(), None);
+    }
+
+    #[test]
+    fn approval_amount_of_btc_entries_reaches_highest_tier() {
+        let tx = new_tx(vec![
+            btc_entry(dec!(0.0001), DebitOrCredit::Debit),
+            btc_entry(dec!(0.0001), DebitOrCredit::Credit),
+        ]);
+        assert_eq!(
+            tx.approval_amount().unwrap(),
+            Some(UsdCents::from(u64::MAX))
+        );
+    }
+
+    #[test]
+    fn status_follows_approval_outcome() {
+        let mut tx = manual_tx_from(new_tx(vec![]));
+        assert_eq!(tx.status(), ManualTransactionStatus::PendingApproval);
+
+        assert!(
+            tx.approval_process_concluded(false, dummy_audit_info())
+                .did_execute()
+        );
+        assert_eq!(tx.status(), ManualTransactionStatus::Rejected);
+        assert!(
+            tx.approval_process_concluded(true, dummy_audit_info())
+                .was_ignored()
+        );
+        assert_eq!(tx.status(), ManualTransactionStatus::Rejected);
+    }
+
+    #[test]
+    fn reversal_mirrors_entries_and_happens_once() {
+        let mut tx = manual_tx_from(new_tx(vec![
+            entry(dec!(100), DebitOrCredit::Debit),
+            entry(dec!(100), DebitOrCredit::Credit),
+        ]));
+        let effective = tx.effective;
+        assert!(matches!(
+            tx.reverse(
+                CalaTxId::new(),
+                effective,
+                "typo".to_string(),
+                dummy_audit_info()
+            ),
+            Err(ManualTransactionError::NotPosted(_))
+        ));
+
+        let _ = tx.approval_process_concluded(true, dummy_audit_info());
+        let reversal = tx.reversal_entries();
+        assert_eq!(reversal[0].direction, DebitOrCredit::Credit);
+        assert_eq!(reversal[1].direction, DebitOrCredit::Debit);
+        assert!(matches!(
+            tx.reverse(
+                CalaTxId::new(),
+                effective.pred_opt().unwrap(),
+                "typo".to_string(),
+                dummy_audit_info()
+            ),
+            Err(ManualTransactionError::ReversalBeforeOriginal(_))
+        ));
+
+        let reversal_tx_id = CalaTxId::new();
+        tx.reverse(
+            reversal_tx_id,
+            effective,
+            "typo".to_string(),
+            dummy_audit_info(),
+        )
+        .unwrap();
+        assert_eq!(tx.status(), ManualTransactionStatus::Reversed);
+        assert_eq!(tx.reversal_ledger_transaction_id, Some(reversal_tx_id));
+        assert!(matches!(
+            tx.reverse(
+                CalaTxId::new(),
+                effective,
+                "typo".to_string(),
+                dummy_audit_info()
+            ),
+            Err(ManualTransactionError::AlreadyReversed(_))
+        ));
+    }
+
+    fn accrual(reverse_on: chrono::NaiveDate) -> ManualTransaction {
+        let id = ManualTransactionId::new();
+        manual_tx_from(
+            NewManualTransaction::builder()
+                .id(id)
+                .ledger_transaction_id(CalaTxId::new())
+                .approval_process_id(id)
+                .reference(None)
+                .description("accrual".to_string())
+                .effective(chrono::Utc::now().date_naive())
+                .entries(vec![])
+                .auto_reverse_on(Some(reverse_on))
+                .audit_info(dummy_audit_info())
+                .build()
+                .unwrap(),
+        )
+    }
+
+    #[test]
+    fn auto_reversal_is_pending_until_reversed_or_rejected() {
+        let reverse_on = chrono::Utc::now().date_naive().succ_opt().unwrap();
+        let mut tx = accrual(reverse_on);
+        assert_eq!(tx.pending_auto_reverse_on(), Some(reverse_on));
+
+        let _ = tx.approval_process_concluded(true, dummy_audit_info());
+        assert_eq!(tx.pending_auto_reverse_on(), Some(reverse_on));
+
+        tx.reverse(
+            CalaTxId::new(),
+            reverse_on,
+            "accrual reversal".to_string(),
+            dummy_audit_info(),
+        )
+        .unwrap();
+        assert_eq!(tx.pending_auto_reverse_on(), None);
+    }
+
+    #[test]
+    fn denied_accrual_is_not_pending() {
+        let reverse_on = chrono::Utc::now().date_naive().succ_opt().unwrap();
+        let mut tx = accrual(reverse_on);
+        let _ = tx.approval_process_concluded(false, dummy_audit_info());
+        assert_eq!(tx.pending_auto_reverse_on(), None);
+    }
+
+    #[test]
+    fn failed_auto_reversal_is_not_pending() {
+        let reverse_on = chrono::Utc::now().date_naive().succ_opt().unwrap();
+        let mut tx = accrual(reverse_on);
+        let _ = tx.approval_process_concluded(true, dummy_audit_info());
+        assert!(
+            tx.auto_reversal_failed("period closed".to_string(), dummy_audit_info())
+                .did_execute()
+        );
+        assert_eq!(tx.pending_auto_reverse_on(), None);
+        assert_eq!(tx.auto_reversal_error(), Some("period closed"));
+        assert_eq!(tx.status(), ManualTransactionStatus::Posted);
+        assert!(
+            tx.auto_reversal_failed("again".to_string(), dummy_audit_info())
+                .was_ignored()
+        );
+    }
+}
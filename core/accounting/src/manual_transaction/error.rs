@@ -20,6 +20,26 @@ pub enum ManualTransactionError {
     AuthorizationError(#[from] authz::error::AuthorizationError),
     #[error("ManualTransactionError - Unknown account code: {0}")]
     UnknownAccountCode(String),
+    #[error("ManualTransactionError - Account code is deactivated: {0}")]
+    DeactivatedAccountCode(String),
+    #[error("ManualTransactionError - GovernanceError: {0}")]
+    GovernanceError(#[from] governance::error::GovernanceError),
+    #[error("ManualTransactionError - JobError: {0}")]
+    JobError(#[from] job::error::JobError),
+    #[error("ManualTransactionError - AuditError: {0}")]
+    AuditError(#[from] audit::error::AuditError),
+    #[error("ManualTransactionError - ConversionError: {0}")]
+    ConversionError(#[from] core_money::ConversionError),
+    #[error("ManualTransactionError - AccountingPeriodError: {0}")]
+    AccountingPeriodError(#[from] crate::accounting_period::error::AccountingPeriodError),
+    #[error("ManualTransactionError - Manual transaction {0} has not been posted")]
+    NotPosted(crate::primitives::ManualTransactionId),
+    #[error("ManualTransactionError - Manual transaction {0} has already been reversed")]
+    AlreadyReversed(crate::primitives::ManualTransactionId),
+    #[error("ManualTransactionError - Reversal cannot be effective before {0}")]
+    ReversalBeforeOriginal(chrono::NaiveDate),
+    #[error("ManualTransactionError - Import rejected with {} errors", .0.len())]
+    ImportRejected(Vec<crate::manual_transaction::ManualTransactionImportError>),
 }
 
 es_entity::from_es_entity_error!(ManualTransactionError);
@@ -1,17 +1,36 @@
 mod template;
 
-use cala_ledger::{AccountId, AccountSetId, CalaLedger, account::NewAccount};
+use cala_ledger::{
+    AccountId, AccountSetId, CalaLedger, account::NewAccount, account_set::AccountSetMemberId,
+};
 
 use crate::{
     Chart,
     primitives::{AccountCode, CalaTxId},
 };
 
-use super::{error::ManualTransactionError, primitives::AccountIdOrCode};
+use super::{
+    error::ManualTransactionError,
+    primitives::{AccountIdOrCode, ManualEntry},
+};
+
+const MAX_DEPTH_BETWEEN_LEAF_AND_CHART: usize = 2;
 
-use template::*;
+pub(crate) use template::ManualTransactionTemplate;
 pub use template::{EntryParams, ManualTransactionParams};
 
+impl From<ManualEntry> for EntryParams {
+    fn from(entry: ManualEntry) -> Self {
+        Self {
+            account_id: entry.account_id,
+            amount: entry.amount,
+            currency: entry.currency,
+            direction: entry.direction,
+            description: entry.description,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct ManualTransactionLedger {
     cala: CalaLedger,
@@ -27,15 +46,27 @@ impl ManualTransactionLedger {
         op: es_entity::DbOp<'_>,
         tx_id: CalaTxId,
         params: ManualTransactionParams,
+    ) -> Result<(), ManualTransactionError> {
+        self.execute_all(op, vec![(tx_id, params)]).await
+    }
+
+    /// Posts the transactions in a single ledger operation, so either all of
+    /// them are recorded or none is.
+    pub async fn execute_all(
+        &self,
+        op: es_entity::DbOp<'_>,
+        transactions: Vec<(CalaTxId, ManualTransactionParams)>,
     ) -> Result<(), ManualTransactionError> {
         let mut op = self.cala.ledger_operation_from_db_op(op);
 
-        let template =
-            ManualTransactionTemplate::init(&self.cala, params.entry_params.len()).await?;
+        for (tx_id, params) in transactions {
+            let template =
+                ManualTransactionTemplate::init(&self.cala, params.entry_params.len()).await?;
 
-        self.cala
-            .post_transaction_in_op(&mut op, tx_id, &template.code(), params)
-            .await?;
+            self.cala
+                .post_transaction_in_op(&mut op, tx_id, &template.code(), params)
+                .await?;
+        }
 
         op.commit().await?;
 
@@ -48,7 +79,18 @@ impl ManualTransactionLedger {
         account_id_or_code: &AccountIdOrCode,
     ) -> Result<AccountId, ManualTransactionError> {
         match account_id_or_code {
-            AccountIdOrCode::Id(account_id) => Ok((*account_id).into()),
+            AccountIdOrCode::Id(account_id) => {
+                let account_id = AccountId::from(*account_id);
+                if let Some(code) = self.deactivated_parent_code(chart, account_id).await? {
+                    return Err(ManualTransactionError::DeactivatedAccountCode(
+                        code.to_string(),
+                    ));
+                }
+                Ok(account_id)
+            }
+            AccountIdOrCode::Code(code) if chart.is_deactivated(code) => Err(
+                ManualTransactionError::DeactivatedAccountCode(code.to_string()),
+            ),
             AccountIdOrCode::Code(code) => match chart.account_spec(code) {
                 Some((_, parent_id)) => {
                     self.find_or_create_manual_account(
@@ -63,6 +105,39 @@ impl ManualTransactionLedger {
         }
     }
 
+    /// The code of the deactivated chart node the account is posted under,
+    /// looking through the account sets between the account and the chart.
+    async fn deactivated_parent_code<'a>(
+        &self,
+        chart: &'a Chart,
+        account_id: AccountId,
+    ) -> Result<Option<&'a AccountCode>, ManualTransactionError> {
+        let mut members = vec![AccountSetMemberId::from(account_id)];
+        for _ in 0..=MAX_DEPTH_BETWEEN_LEAF_AND_CHART {
+            let mut parents = vec![];
+            for member in members {
+                let sets = self
+                    .cala
+                    .account_sets()
+                    .find_where_member(member, Default::default())
+                    .await?
+                    .entities;
+                for set in sets {
+                    match chart.code_of(set.id) {
+                        Some(code) if chart.is_deactivated(code) => return Ok(Some(code)),
+                        Some(_) => {}
+                        None => parents.push(AccountSetMemberId::from(set.id)),
+                    }
+                }
+            }
+            if parents.is_empty() {
+                break;
+            }
+            members = parents;
+        }
+        Ok(None)
+    }
+
     async fn find_or_create_manual_account(
         &self,
         parent_id: &AccountSetId,
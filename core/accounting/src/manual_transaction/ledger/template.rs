@@ -135,13 +135,22 @@ impl ManualTransactionParams {
     }
 }
 
-pub(super) struct ManualTransactionTemplate {
+pub(crate) struct ManualTransactionTemplate {
     pub n_entries: usize,
 }
 
+const MANUAL_TRANSACTION_CODE_PREFIX: &str = "MANUAL_TRANSACTION_";
+
 impl ManualTransactionTemplate {
     pub fn code(&self) -> String {
-        format!("MANUAL_TRANSACTION_{}", self.n_entries)
+        format!("{MANUAL_TRANSACTION_CODE_PREFIX}{}", self.n_entries)
+    }
+
+    /// Whether a transaction template is one of the manual transaction ones,
+    /// which manual transactions and their reversals are posted through.
+    pub fn is_manual_code(code: &str) -> bool {
+        code.strip_prefix(MANUAL_TRANSACTION_CODE_PREFIX)
+            .is_some_and(|n_entries| n_entries.parse::<usize>().is_ok())
     }
 
     pub async fn init(ledger: &CalaLedger, n_entries: usize) -> Result<Self, TxTemplateError> {
@@ -1,3 +1,6 @@
+mod approval;
+mod auto_reversal_job;
+mod csv_import;
 mod entity;
 pub mod error;
 mod ledger;
@@ -6,53 +9,135 @@ mod repo;
 
 use std::collections::HashMap;
 
-use audit::AuditSvc;
+use audit::{AuditInfo, AuditSvc};
 use authz::PermissionCheck;
 use cala_ledger::{CalaLedger, JournalId};
-use ledger::{EntryParams, ManualTransactionLedger, ManualTransactionParams};
+use governance::{
+    ApprovalProcessStatus, Governance, GovernanceAction, GovernanceEvent, GovernanceObject,
+};
+use job::Jobs;
+use ledger::ManualTransactionLedger;
+pub(crate) use ledger::{EntryParams, ManualTransactionParams, ManualTransactionTemplate};
+use outbox::{Outbox, OutboxEventMarker};
 
 use crate::{
     Chart,
+    accounting_period::{AccountingPeriodCheck, PostingKind, error::AccountingPeriodError},
     primitives::{CalaTxId, CoreAccountingAction, CoreAccountingObject, ManualTransactionId},
 };
+use approval::*;
+use auto_reversal_job::*;
 use error::*;
 
+pub use approval::APPROVE_MANUAL_TRANSACTION_PROCESS;
+pub use csv_import::ManualTransactionImportError;
+pub(crate) use csv_import::{ImportedManualTransaction, parse_manual_transactions_csv};
 pub use entity::ManualTransaction;
 pub(super) use entity::*;
 pub use primitives::*;
 pub use repo::manual_transaction_cursor::ManualTransactionsByCreatedAtCursor;
 use repo::*;
 
-#[derive(Clone)]
-pub struct ManualTransactions<Perms>
+pub struct ManualTransactions<Perms, E>
 where
     Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>,
 {
     ledger: ManualTransactionLedger,
     authz: Perms,
-    journal_id: JournalId,
     repo: ManualTransactionRepo,
+    governance: Governance<Perms, E>,
+    approve_manual_transaction: ApproveManualTransaction<Perms, E>,
+    periods: AccountingPeriodCheck,
+    journal_id: JournalId,
+}
+
+impl<Perms, E> Clone for ManualTransactions<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            ledger: self.ledger.clone(),
+            authz: self.authz.clone(),
+            repo: self.repo.clone(),
+            governance: self.governance.clone(),
+            approve_manual_transaction: self.approve_manual_transaction.clone(),
+            periods: self.periods.clone(),
+            journal_id: self.journal_id,
+        }
+    }
 }
 
-impl<Perms> ManualTransactions<Perms>
+impl<Perms, E> ManualTransactions<Perms, E>
 where
     Perms: PermissionCheck,
-    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreAccountingAction>,
-    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreAccountingObject>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreAccountingAction> + From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreAccountingObject> + From<GovernanceObject>,
+    E: OutboxEventMarker<GovernanceEvent>,
 {
-    pub fn new(
+    pub async fn init(
         pool: &sqlx::PgPool,
         authz: &Perms,
         cala: &CalaLedger,
         journal_id: JournalId,
-    ) -> Self {
+        governance: &Governance<Perms, E>,
+        outbox: &Outbox<E>,
+        jobs: &Jobs,
+    ) -> Result<Self, ManualTransactionError> {
         let repo = ManualTransactionRepo::new(pool);
-        Self {
-            ledger: ManualTransactionLedger::new(cala),
-            authz: authz.clone(),
+        let ledger = ManualTransactionLedger::new(cala);
+        let periods = AccountingPeriodCheck::new(pool);
+        let approve_manual_transaction = ApproveManualTransaction::new(
+            &repo,
+            &ledger,
+            &periods,
             journal_id,
-            repo,
+            authz.audit(),
+            governance,
+        );
+
+        jobs.add_initializer_and_spawn_unique(
+            ManualTransactionApprovalJobInitializer::new(outbox, &approve_manual_transaction),
+            ManualTransactionApprovalJobConfig::<Perms, E>::new(),
+        )
+        .await?;
+
+        jobs.add_initializer_and_spawn_unique(
+            AutoReverseManualTransactionsJobInitializer::<Perms>::new(
+                &repo,
+                &ledger,
+                &periods,
+                journal_id,
+                authz.audit(),
+            ),
+            AutoReverseManualTransactionsJobConfig::<Perms>::new(),
+        )
+        .await?;
+
+        match governance
+            .init_policy(APPROVE_MANUAL_TRANSACTION_PROCESS)
+            .await
+        {
+            Err(governance::error::GovernanceError::PolicyError(
+                governance::policy_error::PolicyError::DuplicateApprovalProcessType,
+            )) => (),
+            Err(e) => return Err(e.into()),
+            _ => (),
         }
+
+        Ok(Self {
+            ledger,
+            authz: authz.clone(),
+            repo,
+            governance: governance.clone(),
+            approve_manual_transaction,
+            periods,
+            journal_id,
+        })
     }
 
     pub async fn find_manual_transaction_by_id(
@@ -104,6 +189,13 @@ where
         self.repo.find_all(ids).await
     }
 
+    /// Submits a manual transaction for approval. It is posted to the ledger
+    /// once the approval process concludes in its favour, which happens right
+    /// away when the policy does not require any votes for its amount.
+    /// Back-dating into a closed accounting period is rejected.
+    /// With `auto_reverse` set the transaction is an accrual that gets
+    /// reversed on the first day of the next accounting period.
+    #[allow(clippy::too_many_arguments)]
     pub async fn execute(
         &self,
         sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
@@ -112,7 +204,12 @@ where
         description: String,
         effective: chrono::NaiveDate,
         entries: Vec<ManualEntryInput>,
-    ) -> Result<ManualTransaction, ManualTransactionError> {
+        auto_reverse: bool,
+    ) -> Result<ManualTransaction, ManualTransactionError>
+    where
+        governance::CommitteeMemberId:
+            for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+    {
         let audit_info = self
             .authz
             .enforce_permission(
@@ -122,28 +219,42 @@ where
             )
             .await?;
 
-        let ledger_tx_id = CalaTxId::new();
-        let manual_tx_id = ManualTransactionId::new();
+        let auto_reverse_on = if auto_reverse {
+            Some(self.periods.next_period_start(effective).await?)
+        } else {
+            None
+        };
+        let resolved_entries = self.resolve_entries(chart, entries).await?;
 
+        let manual_tx_id = ManualTransactionId::new();
         let new_tx = NewManualTransaction::builder()
             .id(manual_tx_id)
-            .ledger_transaction_id(ledger_tx_id)
-            .description(description.clone())
+            .ledger_transaction_id(CalaTxId::new())
+            .approval_process_id(manual_tx_id)
+            .description(description)
             .reference(reference)
+            .effective(effective)
+            .entries(resolved_entries)
+            .auto_reverse_on(auto_reverse_on)
             .audit_info(audit_info)
             .build()
             .expect("Couldn't build new manual transaction");
 
-        let mut db = self.repo.begin_op().await?;
-        let manual_transaction = self.repo.create_in_op(&mut db, new_tx).await?;
+        self.submit(sub, new_tx).await
+    }
 
-        let mut entry_params = vec![];
+    pub(crate) async fn resolve_entries(
+        &self,
+        chart: &Chart,
+        entries: Vec<ManualEntryInput>,
+    ) -> Result<Vec<ManualEntry>, ManualTransactionError> {
+        let mut resolved_entries = Vec::with_capacity(entries.len());
         for e in entries {
             let account_id = self
                 .ledger
                 .resolve_account_id(chart, &e.account_id_or_code)
                 .await?;
-            entry_params.push(EntryParams {
+            resolved_entries.push(ManualEntry {
                 account_id,
                 amount: e.amount,
                 currency: e.currency,
@@ -151,20 +262,269 @@ where
                 description: e.description,
             });
         }
+        Ok(resolved_entries)
+    }
+
+    /// Starts the approval process for an already authorized manual
+    /// transaction and posts it right away if no votes are required.
+    pub(crate) async fn submit(
+        &self,
+        initiated_by: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        new_tx: NewManualTransaction,
+    ) -> Result<ManualTransaction, ManualTransactionError>
+    where
+        governance::CommitteeMemberId:
+            for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+    {
+        self.periods
+            .ensure_accepts_posting(new_tx.effective, PostingKind::ManualAdjustment)
+            .await?;
+        let amount = new_tx.approval_amount()?;
+
+        let mut db = self.repo.begin_op().await?;
+        self.governance
+            .start_process(
+                &mut db,
+                new_tx.id,
+                new_tx.id.to_string(),
+                APPROVE_MANUAL_TRANSACTION_PROCESS,
+                amount,
+                initiated_by,
+            )
+            .await?;
+        let manual_transaction = self.repo.create_in_op(&mut db, new_tx).await?;
+        db.commit().await?;
+
+        Ok(self
+            .approve_manual_transaction
+            .execute_from_svc(&manual_transaction)
+            .await?
+            .unwrap_or(manual_transaction))
+    }
+
+    /// Resolves the accounts of imported transactions and checks that their
+    /// references are unused and their periods accept postings. Every problem
+    /// found is reported against the CSV line it comes from.
+    pub(crate) async fn prepare_import(
+        &self,
+        chart: &Chart,
+        imported: Vec<ImportedManualTransaction>,
+        audit_info: AuditInfo,
+    ) -> Result<Vec<(u64, NewManualTransaction)>, ManualTransactionError> {
+        let mut errors = vec![];
+        let mut new_txs = Vec::with_capacity(imported.len());
+        for tx in imported {
+            let first_line = tx
+                .entries
+                .first()
+                .map(|(line, _)| *line)
+                .unwrap_or_default();
+            match self.repo.find_by_reference(tx.reference.clone()).await {
+                Ok(_) => errors.push(ManualTransactionImportError::new(
+                    first_line,
+                    Some(&tx.reference),
+                    "reference is already in use",
+                )),
+                Err(e) if e.was_not_found() => (),
+                Err(e) => return Err(e),
+            }
+            match self
+                .periods
+                .ensure_accepts_posting(tx.effective, PostingKind::ManualAdjustment)
+                .await
+            {
+                Err(
+                    e @ (AccountingPeriodError::PeriodClosed(_)
+                    | AccountingPeriodError::PeriodSoftClosed(_)),
+                ) => errors.push(ManualTransactionImportError::new(
+                    first_line,
+                    Some(&tx.reference),
+                    e.to_string(),
+                )),
+                res => res?,
+            }
+
+            let mut entries = Vec::with_capacity(tx.entries.len());
+            for (line, entry) in tx.entries {
+                match self
+                    .ledger
+                    .resolve_account_id(chart, &entry.account_id_or_code)
+                    .await
+                {
+                    Ok(account_id) => entries.push(ManualEntry {
+                        account_id,
+                        amount: entry.amount,
+                        currency: entry.currency,
+                        direction: entry.direction,
+                        description: entry.description,
+                    }),
+                    Err(
+                        e @ (ManualTransactionError::UnknownAccountCode(_)
+                        | ManualTransactionError::DeactivatedAccountCode(_)),
+                    ) => errors.push(ManualTransactionImportError::new(
+                        line,
+                        Some(&tx.reference),
+                        e.to_string(),
+                    )),
+                    Err(e) => return Err(e),
+                }
+            }
+
+            let manual_tx_id = ManualTransactionId::new();
+            new_txs.push((
+                first_line,
+                NewManualTransaction::builder()
+                    .id(manual_tx_id)
+                    .ledger_transaction_id(CalaTxId::new())
+                    .approval_process_id(manual_tx_id)
+                    .description(tx.description)
+                    .reference(Some(tx.reference))
+                    .effective(tx.effective)
+                    .entries(entries)
+                    .audit_info(audit_info.clone())
+                    .build()
+                    .expect("Couldn't build new manual transaction"),
+            ));
+        }
+
+        if !errors.is_empty() {
+            errors.sort_by_key(|e| e.line);
+            return Err(ManualTransactionError::ImportRejected(errors));
+        }
+        Ok(new_txs)
+    }
+
+    /// Creates and posts prepared transactions in the given operation. An
+    /// import posts all of its transactions or none of them, so it is
+    /// rejected, and the operation rolled back, when the approval policy
+    /// would hold any transaction for votes.
+    pub(crate) async fn import_in_op(
+        &self,
+        mut db: es_entity::DbOp<'_>,
+        initiated_by: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        new_txs: Vec<(u64, NewManualTransaction)>,
+    ) -> Result<Vec<ManualTransaction>, ManualTransactionError>
+    where
+        governance::CommitteeMemberId:
+            for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+    {
+        let mut manual_txs = Vec::with_capacity(new_txs.len());
+        let mut postings = vec![];
+        let mut errors = vec![];
+        for (line, new_tx) in new_txs {
+            let amount = new_tx.approval_amount()?;
+            let process = self
+                .governance
+                .start_process(
+                    &mut db,
+                    new_tx.id,
+                    new_tx.id.to_string(),
+                    APPROVE_MANUAL_TRANSACTION_PROCESS,
+                    amount,
+                    initiated_by,
+                )
+                .await?;
+            let mut manual_tx = self.repo.create_in_op(&mut db, new_tx).await?;
+
+            if !matches!(process.status(), ApprovalProcessStatus::Approved) {
+                errors.push(ManualTransactionImportError::new(
+                    line,
+                    Some(&manual_tx.reference),
+                    "requires approval and cannot be imported",
+                ));
+            } else if errors.is_empty() {
+                self.periods
+                    .ensure_accepts_posting_in_op(
+                        &mut db,
+                        manual_tx.effective,
+                        PostingKind::ManualAdjustment,
+                    )
+                    .await?;
+                let audit_info = self
+                    .authz
+                    .audit()
+                    .record_system_entry_in_tx(
+                        db.tx(),
+                        CoreAccountingObject::manual_transaction(manual_tx.id),
+                        CoreAccountingAction::MANUAL_TRANSACTION_CONCLUDE_APPROVAL_PROCESS,
+                    )
+                    .await?;
+                let _ = manual_tx.approval_process_concluded(true, audit_info);
+                self.repo.update_in_op(&mut db, &mut manual_tx).await?;
+                postings.push((
+                    manual_tx.ledger_transaction_id,
+                    ManualTransactionParams {
+                        journal_id: self.journal_id,
+                        description: manual_tx.description.clone(),
+                        entry_params: manual_tx
+                            .entries
+                            .iter()
+                            .cloned()
+                            .map(EntryParams::from)
+                            .collect(),
+                        effective: manual_tx.effective,
+                    },
+                ));
+            }
+            manual_txs.push(manual_tx);
+        }
+
+        if !errors.is_empty() {
+            return Err(ManualTransactionError::ImportRejected(errors));
+        }
+        self.ledger.execute_all(db, postings).await?;
+        Ok(manual_txs)
+    }
 
-        self.ledger
-            .execute(
-                db,
-                ledger_tx_id,
-                ManualTransactionParams {
-                    journal_id: self.journal_id,
-                    description,
-                    entry_params,
-                    effective,
-                },
+    /// Posts the mirror image of a posted manual transaction. A transaction
+    /// can only be reversed once.
+    pub async fn reverse(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<ManualTransactionId> + std::fmt::Debug,
+        effective: chrono::NaiveDate,
+        reason: String,
+    ) -> Result<ManualTransaction, ManualTransactionError> {
+        let id = id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::manual_transaction(id),
+                CoreAccountingAction::MANUAL_TRANSACTION_REVERSE,
             )
             .await?;
 
-        Ok(manual_transaction)
+        let mut manual_tx = self.repo.find_by_id(id).await?;
+        let reversal_tx_id = CalaTxId::new();
+        let params = reversal_params(&manual_tx, self.journal_id, effective, &reason);
+        manual_tx.reverse(reversal_tx_id, effective, reason, audit_info)?;
+
+        let mut db = self.repo.begin_op().await?;
+        self.periods
+            .ensure_accepts_posting_in_op(&mut db, effective, PostingKind::ManualAdjustment)
+            .await?;
+        self.repo.update_in_op(&mut db, &mut manual_tx).await?;
+        self.ledger.execute(db, reversal_tx_id, params).await?;
+
+        Ok(manual_tx)
+    }
+}
+
+fn reversal_params(
+    manual_tx: &ManualTransaction,
+    journal_id: JournalId,
+    effective: chrono::NaiveDate,
+    reason: &str,
+) -> ManualTransactionParams {
+    ManualTransactionParams {
+        journal_id,
+        description: format!("Reversal of {}: {}", manual_tx.reference, reason),
+        entry_params: manual_tx
+            .reversal_entries()
+            .into_iter()
+            .map(EntryParams::from)
+            .collect(),
+        effective,
     }
 }
@@ -1,6 +1,7 @@
 use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
 
-use cala_ledger::{Currency, DebitOrCredit};
+use cala_ledger::{AccountId as CalaAccountId, Currency, DebitOrCredit};
 use rust_decimal::Decimal;
 
 use crate::primitives::{AccountCode, LedgerAccountId};
@@ -40,3 +41,23 @@ impl ManualEntryInput {
         ManualEntryInputBuilder::default()
     }
 }
+
+/// An entry of a manual transaction with its account already resolved, kept
+/// on the transaction until it is posted.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct ManualEntry {
+    pub account_id: CalaAccountId,
+    pub amount: Decimal,
+    pub currency: Currency,
+    pub description: String,
+    pub direction: DebitOrCredit,
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+pub enum ManualTransactionStatus {
+    PendingApproval,
+    Posted,
+    Rejected,
+    Reversed,
+}
@@ -1,3 +1,4 @@
+use chrono::NaiveDate;
 use sqlx::PgPool;
 
 use es_entity::*;
@@ -12,7 +13,13 @@ use super::{entity::*, error::*};
     err = "ManualTransactionError",
     columns(
         reference(ty = "String", create(accessor = "reference()")),
-        ledger_transaction_id(ty = "CalaTxId")
+        ledger_transaction_id(ty = "CalaTxId"),
+        pending_auto_reverse_on(
+            ty = "Option<NaiveDate>",
+            list_by,
+            create(accessor = "auto_reverse_on"),
+            update(accessor = "pending_auto_reverse_on()")
+        )
     ),
     tbl_prefix = "core"
 )]
@@ -18,16 +18,21 @@ pub use cala_ledger::{
 };
 
 pub use core_money::{Satoshis, UsdCents};
+pub use governance::{ApprovalProcessId, GovernanceAction, GovernanceObject};
 
 es_entity::entity_id! {
     ChartId,
     ManualTransactionId,
     LedgerAccountId,
-    AccountingCsvId;
+    AccountingCsvId,
+    AccountingPeriodId,
+    RecurringTransactionId,
+    TrialBalanceSnapshotId;
 
     LedgerAccountId => CalaAccountId,
     LedgerAccountId => CalaAccountSetId,
     AccountingCsvId => job::JobId,
+    ManualTransactionId => ApprovalProcessId,
 }
 
 impl From<cala_ledger::account_set::AccountSetMemberId> for LedgerAccountId {
@@ -50,7 +55,7 @@ pub enum AccountNameParseError {
     StartsWithDigit,
 }
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
 pub struct AccountName {
     name: String,
 }
@@ -139,6 +144,10 @@ impl AccountCode {
         format!("{}.{}.manual", chart_id, self)
     }
 
+    pub(super) fn retained_earnings_account_external_id(&self, chart_id: ChartId) -> String {
+        format!("{}.{}.retained-earnings", chart_id, self)
+    }
+
     pub fn len_sections(&self) -> usize {
         self.sections.len()
     }
@@ -147,6 +156,10 @@ impl AccountCode {
         self.len_sections() - 1
     }
 
+    pub fn sections(&self) -> &[AccountCodeSection] {
+        &self.sections
+    }
+
     pub fn section(&self, idx: usize) -> Option<&AccountCodeSection> {
         self.sections.get(idx)
     }
@@ -278,6 +291,11 @@ pub type BalanceSheetAllOrOne = AllOrOne<LedgerAccountId>;
 pub type BalanceSheetConfigurationAllOrOne = AllOrOne<LedgerAccountId>;
 pub type AccountingCsvAllOrOne = AllOrOne<AccountingCsvId>;
 pub type TrialBalanceAllOrOne = AllOrOne<LedgerAccountId>; // what to do if there is only All
+pub type AccountingPeriodAllOrOne = AllOrOne<AccountingPeriodId>;
+pub type RecurringTransactionAllOrOne = AllOrOne<RecurringTransactionId>;
+pub type CashFlowStatementAllOrOne = AllOrOne<LedgerAccountId>;
+pub type CashFlowStatementConfigurationAllOrOne = AllOrOne<LedgerAccountId>;
+pub type TrialBalanceSnapshotAllOrOne = AllOrOne<TrialBalanceSnapshotId>;
 // option
 
 #[derive(Clone, Copy, Debug, PartialEq, strum::EnumDiscriminants)]
@@ -296,6 +314,11 @@ pub enum CoreAccountingAction {
     BalanceSheetConfigurationAction(BalanceSheetConfigurationAction),
     AccountingCsv(AccountingCsvAction),
     TrialBalanceAction(TrialBalanceAction),
+    AccountingPeriodAction(AccountingPeriodAction),
+    RecurringTransactionAction(RecurringTransactionAction),
+    CashFlowStatementAction(CashFlowStatementAction),
+    CashFlowStatementConfigurationAction(CashFlowStatementConfigurationAction),
+    TrialBalanceSnapshotAction(TrialBalanceSnapshotAction),
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, strum::EnumDiscriminants)]
@@ -314,6 +337,11 @@ pub enum CoreAccountingObject {
     BalanceSheetConfiguration(BalanceSheetConfigurationAllOrOne),
     AccountingCsv(AccountingCsvAllOrOne),
     TrialBalance(TrialBalanceAllOrOne),
+    AccountingPeriod(AccountingPeriodAllOrOne),
+    RecurringTransaction(RecurringTransactionAllOrOne),
+    CashFlowStatement(CashFlowStatementAllOrOne),
+    CashFlowStatementConfiguration(CashFlowStatementConfigurationAllOrOne),
+    TrialBalanceSnapshot(TrialBalanceSnapshotAllOrOne),
 }
 
 impl CoreAccountingObject {
@@ -395,6 +423,38 @@ impl CoreAccountingObject {
     pub fn all_trial_balance() -> Self {
         CoreAccountingObject::TrialBalance(AllOrOne::All)
     }
+
+    pub fn all_accounting_periods() -> Self {
+        CoreAccountingObject::AccountingPeriod(AllOrOne::All)
+    }
+
+    pub fn accounting_period(id: AccountingPeriodId) -> Self {
+        CoreAccountingObject::AccountingPeriod(AllOrOne::ById(id))
+    }
+
+    pub fn all_recurring_transactions() -> Self {
+        CoreAccountingObject::RecurringTransaction(AllOrOne::All)
+    }
+
+    pub fn recurring_transaction(id: RecurringTransactionId) -> Self {
+        CoreAccountingObject::RecurringTransaction(AllOrOne::ById(id))
+    }
+
+    pub fn all_cash_flow_statement() -> Self {
+        CoreAccountingObject::CashFlowStatement(AllOrOne::All)
+    }
+
+    pub fn all_cash_flow_statement_configuration() -> Self {
+        CoreAccountingObject::CashFlowStatementConfiguration(AllOrOne::All)
+    }
+
+    pub fn all_trial_balance_snapshots() -> Self {
+        CoreAccountingObject::TrialBalanceSnapshot(AllOrOne::All)
+    }
+
+    pub fn trial_balance_snapshot(id: TrialBalanceSnapshotId) -> Self {
+        CoreAccountingObject::TrialBalanceSnapshot(AllOrOne::ById(id))
+    }
 }
 
 impl Display for CoreAccountingObject {
@@ -414,6 +474,11 @@ impl Display for CoreAccountingObject {
             BalanceSheetConfiguration(obj_ref) => write!(f, "{}/{}", discriminant, obj_ref),
             AccountingCsv(obj_ref) => write!(f, "{}/{}", discriminant, obj_ref),
             TrialBalance(obj_ref) => write!(f, "{}/{}", discriminant, obj_ref),
+            AccountingPeriod(obj_ref) => write!(f, "{}/{}", discriminant, obj_ref),
+            RecurringTransaction(obj_ref) => write!(f, "{}/{}", discriminant, obj_ref),
+            CashFlowStatement(obj_ref) => write!(f, "{}/{}", discriminant, obj_ref),
+            CashFlowStatementConfiguration(obj_ref) => write!(f, "{}/{}", discriminant, obj_ref),
+            TrialBalanceSnapshot(obj_ref) => write!(f, "{}/{}", discriminant, obj_ref),
         }
     }
 }
@@ -483,6 +548,34 @@ impl FromStr for CoreAccountingObject {
                 let obj_ref = id.parse().map_err(|_| "could not parse TrialBalance")?;
                 CoreAccountingObject::TrialBalance(obj_ref)
             }
+            AccountingPeriod => {
+                let obj_ref = id.parse().map_err(|_| "could not parse AccountingPeriod")?;
+                CoreAccountingObject::AccountingPeriod(obj_ref)
+            }
+            RecurringTransaction => {
+                let obj_ref = id
+                    .parse()
+                    .map_err(|_| "could not parse RecurringTransaction")?;
+                CoreAccountingObject::RecurringTransaction(obj_ref)
+            }
+            CashFlowStatement => {
+                let obj_ref = id
+                    .parse()
+                    .map_err(|_| "could not parse CashFlowStatement")?;
+                CoreAccountingObject::CashFlowStatement(obj_ref)
+            }
+            CashFlowStatementConfiguration => {
+                let obj_ref = id
+                    .parse()
+                    .map_err(|_| "could not parse CashFlowStatementConfiguration")?;
+                CoreAccountingObject::CashFlowStatementConfiguration(obj_ref)
+            }
+            TrialBalanceSnapshot => {
+                let obj_ref = id
+                    .parse()
+                    .map_err(|_| "could not parse TrialBalanceSnapshot")?;
+                CoreAccountingObject::TrialBalanceSnapshot(obj_ref)
+            }
         };
         Ok(res)
     }
@@ -493,6 +586,8 @@ impl CoreAccountingAction {
     pub const CHART_LIST: Self = CoreAccountingAction::ChartAction(ChartAction::List);
     pub const CHART_IMPORT_ACCOUNTS: Self =
         CoreAccountingAction::ChartAction(ChartAction::ImportAccounts);
+    pub const CHART_UPDATE_NODES: Self =
+        CoreAccountingAction::ChartAction(ChartAction::UpdateNodes);
 
     pub const JOURNAL_READ_ENTRIES: Self =
         CoreAccountingAction::JournalAction(JournalAction::ReadEntries);
@@ -518,6 +613,12 @@ impl CoreAccountingAction {
         CoreAccountingAction::ManualTransactionAction(ManualTransactionAction::Create);
     pub const MANUAL_TRANSACTION_LIST: Self =
         CoreAccountingAction::ManualTransactionAction(ManualTransactionAction::List);
+    pub const MANUAL_TRANSACTION_CONCLUDE_APPROVAL_PROCESS: Self =
+        CoreAccountingAction::ManualTransactionAction(
+            ManualTransactionAction::ConcludeApprovalProcess,
+        );
+    pub const MANUAL_TRANSACTION_REVERSE: Self =
+        CoreAccountingAction::ManualTransactionAction(ManualTransactionAction::Reverse);
     pub const PROFIT_AND_LOSS_READ: Self =
         CoreAccountingAction::ProfitAndLossAction(ProfitAndLossAction::Read);
     pub const PROFIT_AND_LOSS_CREATE: Self =
@@ -563,6 +664,55 @@ impl CoreAccountingAction {
         CoreAccountingAction::TrialBalanceAction(TrialBalanceAction::Create);
     pub const TRIAL_BALANCE_UPDATE: Self =
         CoreAccountingAction::TrialBalanceAction(TrialBalanceAction::Update);
+
+    pub const ACCOUNTING_PERIOD_CREATE: Self =
+        CoreAccountingAction::AccountingPeriodAction(AccountingPeriodAction::Create);
+    pub const ACCOUNTING_PERIOD_READ: Self =
+        CoreAccountingAction::AccountingPeriodAction(AccountingPeriodAction::Read);
+    pub const ACCOUNTING_PERIOD_LIST: Self =
+        CoreAccountingAction::AccountingPeriodAction(AccountingPeriodAction::List);
+    pub const ACCOUNTING_PERIOD_SOFT_CLOSE: Self =
+        CoreAccountingAction::AccountingPeriodAction(AccountingPeriodAction::SoftClose);
+    pub const ACCOUNTING_PERIOD_REOPEN: Self =
+        CoreAccountingAction::AccountingPeriodAction(AccountingPeriodAction::Reopen);
+    pub const ACCOUNTING_PERIOD_CLOSE: Self =
+        CoreAccountingAction::AccountingPeriodAction(AccountingPeriodAction::Close);
+    pub const ACCOUNTING_PERIOD_CLOSE_YEAR_END: Self =
+        CoreAccountingAction::AccountingPeriodAction(AccountingPeriodAction::CloseYearEnd);
+
+    pub const RECURRING_TRANSACTION_CREATE: Self =
+        CoreAccountingAction::RecurringTransactionAction(RecurringTransactionAction::Create);
+    pub const RECURRING_TRANSACTION_READ: Self =
+        CoreAccountingAction::RecurringTransactionAction(RecurringTransactionAction::Read);
+    pub const RECURRING_TRANSACTION_LIST: Self =
+        CoreAccountingAction::RecurringTransactionAction(RecurringTransactionAction::List);
+    pub const RECURRING_TRANSACTION_PAUSE: Self =
+        CoreAccountingAction::RecurringTransactionAction(RecurringTransactionAction::Pause);
+    pub const RECURRING_TRANSACTION_RESUME: Self =
+        CoreAccountingAction::RecurringTransactionAction(RecurringTransactionAction::Resume);
+    pub const RECURRING_TRANSACTION_POST: Self =
+        CoreAccountingAction::RecurringTransactionAction(RecurringTransactionAction::Post);
+
+    pub const CASH_FLOW_STATEMENT_READ: Self =
+        CoreAccountingAction::CashFlowStatementAction(CashFlowStatementAction::Read);
+    pub const CASH_FLOW_STATEMENT_CREATE: Self =
+        CoreAccountingAction::CashFlowStatementAction(CashFlowStatementAction::Create);
+
+    pub const CASH_FLOW_STATEMENT_CONFIGURATION_READ: Self =
+        CoreAccountingAction::CashFlowStatementConfigurationAction(
+            CashFlowStatementConfigurationAction::Read,
+        );
+    pub const CASH_FLOW_STATEMENT_CONFIGURATION_UPDATE: Self =
+        CoreAccountingAction::CashFlowStatementConfigurationAction(
+            CashFlowStatementConfigurationAction::Update,
+        );
+
+    pub const TRIAL_BALANCE_SNAPSHOT_CREATE: Self =
+        CoreAccountingAction::TrialBalanceSnapshotAction(TrialBalanceSnapshotAction::Create);
+    pub const TRIAL_BALANCE_SNAPSHOT_READ: Self =
+        CoreAccountingAction::TrialBalanceSnapshotAction(TrialBalanceSnapshotAction::Read);
+    pub const TRIAL_BALANCE_SNAPSHOT_LIST: Self =
+        CoreAccountingAction::TrialBalanceSnapshotAction(TrialBalanceSnapshotAction::List);
 }
 
 impl Display for CoreAccountingAction {
@@ -582,6 +732,11 @@ impl Display for CoreAccountingAction {
             BalanceSheetConfigurationAction(action) => action.fmt(f),
             AccountingCsv(action) => action.fmt(f),
             TrialBalanceAction(action) => action.fmt(f),
+            AccountingPeriodAction(action) => action.fmt(f),
+            RecurringTransactionAction(action) => action.fmt(f),
+            CashFlowStatementAction(action) => action.fmt(f),
+            CashFlowStatementConfigurationAction(action) => action.fmt(f),
+            TrialBalanceSnapshotAction(action) => action.fmt(f),
         }
     }
 }
@@ -628,6 +783,21 @@ impl FromStr for CoreAccountingAction {
             CoreAccountingActionDiscriminants::TrialBalanceAction => {
                 CoreAccountingAction::from(action.parse::<TrialBalanceAction>()?)
             }
+            CoreAccountingActionDiscriminants::AccountingPeriodAction => {
+                CoreAccountingAction::from(action.parse::<AccountingPeriodAction>()?)
+            }
+            CoreAccountingActionDiscriminants::RecurringTransactionAction => {
+                CoreAccountingAction::from(action.parse::<RecurringTransactionAction>()?)
+            }
+            CoreAccountingActionDiscriminants::CashFlowStatementAction => {
+                CoreAccountingAction::from(action.parse::<CashFlowStatementAction>()?)
+            }
+            CoreAccountingActionDiscriminants::CashFlowStatementConfigurationAction => {
+                CoreAccountingAction::from(action.parse::<CashFlowStatementConfigurationAction>()?)
+            }
+            CoreAccountingActionDiscriminants::TrialBalanceSnapshotAction => {
+                CoreAccountingAction::from(action.parse::<TrialBalanceSnapshotAction>()?)
+            }
         };
         Ok(res)
     }
@@ -639,6 +809,7 @@ pub enum ChartAction {
     Create,
     List,
     ImportAccounts,
+    UpdateNodes,
 }
 
 impl From<ChartAction> for CoreAccountingAction {
@@ -705,6 +876,8 @@ pub enum ManualTransactionAction {
     Read,
     Create,
     List,
+    ConcludeApprovalProcess,
+    Reverse,
 }
 
 impl From<ManualTransactionAction> for CoreAccountingAction {
@@ -796,6 +969,81 @@ impl From<TrialBalanceAction> for CoreAccountingAction {
     }
 }
 
+#[derive(PartialEq, Clone, Copy, Debug, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum AccountingPeriodAction {
+    Create,
+    Read,
+    List,
+    SoftClose,
+    Reopen,
+    Close,
+    CloseYearEnd,
+}
+
+impl From<AccountingPeriodAction> for CoreAccountingAction {
+    fn from(action: AccountingPeriodAction) -> Self {
+        CoreAccountingAction::AccountingPeriodAction(action)
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum RecurringTransactionAction {
+    Create,
+    Read,
+    List,
+    Pause,
+    Resume,
+    Post,
+}
+
+impl From<RecurringTransactionAction> for CoreAccountingAction {
+    fn from(action: RecurringTransactionAction) -> Self {
+        CoreAccountingAction::RecurringTransactionAction(action)
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CashFlowStatementAction {
+    Read,
+    Create,
+}
+
+impl From<CashFlowStatementAction> for CoreAccountingAction {
+    fn from(action: CashFlowStatementAction) -> Self {
+        CoreAccountingAction::CashFlowStatementAction(action)
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum CashFlowStatementConfigurationAction {
+    Read,
+    Update,
+}
+
+impl From<CashFlowStatementConfigurationAction> for CoreAccountingAction {
+    fn from(action: CashFlowStatementConfigurationAction) -> Self {
+        CoreAccountingAction::CashFlowStatementConfigurationAction(action)
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum TrialBalanceSnapshotAction {
+    Create,
+    Read,
+    List,
+}
+
+impl From<TrialBalanceSnapshotAction> for CoreAccountingAction {
+    fn from(action: TrialBalanceSnapshotAction) -> Self {
+        CoreAccountingAction::TrialBalanceSnapshotAction(action)
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct BalanceRange {
     pub start: Option<CalaAccountBalance>,
@@ -14,6 +14,8 @@ pub enum ProfitAndLossStatementError {
     ),
     #[error("ProfitAndLossStatementError - ChartOfAccountsError: {0}")]
     ChartOfAccountsError(#[from] crate::chart_of_accounts::error::ChartOfAccountsError),
+    #[error("ProfitAndLossStatementError - ComparativeStatementError: {0}")]
+    ComparativeStatementError(#[from] crate::comparative::error::ComparativeStatementError),
     #[error("ProfitAndLossStatementError - ChartConfigAlreadyExists")]
     ChartConfigAlreadyExists,
     #[error("ProfitAndLossStatementError - ChartIdMismatch")]
@@ -11,6 +11,9 @@ use cala_ledger::CalaLedger;
 use crate::{
     LedgerAccountId,
     chart_of_accounts::Chart,
+    comparative::{
+        ComparativeMeasure, ComparativeStatement, ComparativeStatementLedger, StatementPeriod,
+    },
     primitives::{BalanceRange, CalaAccountSetId, CoreAccountingAction, CoreAccountingObject},
 };
 
@@ -54,6 +57,7 @@ where
     pool: sqlx::PgPool,
     authz: Perms,
     pl_statement_ledger: ProfitAndLossStatementLedger,
+    comparative_ledger: ComparativeStatementLedger,
 }
 
 impl<Perms> ProfitAndLossStatements<Perms>
@@ -69,10 +73,12 @@ where
         journal_id: cala_ledger::JournalId,
     ) -> Self {
         let pl_statement_ledger = ProfitAndLossStatementLedger::new(cala, journal_id);
+        let comparative_ledger = ComparativeStatementLedger::new(cala, journal_id);
 
         Self {
             pool: pool.clone(),
             pl_statement_ledger,
+            comparative_ledger,
             authz: authz.clone(),
         }
     }
@@ -189,6 +195,35 @@ where
             .get_pl_statement(reference, from, until)
             .await?)
     }
+
+    /// Activity within each period, one column per period.
+    pub async fn comparative_pl_statement(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        reference: String,
+        periods: Vec<StatementPeriod>,
+    ) -> Result<ComparativeStatement, ProfitAndLossStatementError> {
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_profit_and_loss(),
+                CoreAccountingAction::PROFIT_AND_LOSS_READ,
+            )
+            .await?;
+
+        self.find_comparative_pl_statement(reference, periods).await
+    }
+
+    pub(crate) async fn find_comparative_pl_statement(
+        &self,
+        reference: String,
+        periods: Vec<StatementPeriod>,
+    ) -> Result<ComparativeStatement, ProfitAndLossStatementError> {
+        Ok(self
+            .comparative_ledger
+            .get_comparative_statement(reference, periods, ComparativeMeasure::PeriodActivity)
+            .await?)
+    }
 }
 
 #[derive(Clone)]
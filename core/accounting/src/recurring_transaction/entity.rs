@@ -0,0 +1,390 @@
+use chrono::NaiveDate;
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use audit::AuditInfo;
+use es_entity::*;
+
+use crate::{
+    manual_transaction::ManualEntry,
+    primitives::{ManualTransactionId, RecurringTransactionId},
+};
+
+use super::primitives::*;
+
+#[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[es_event(id = "RecurringTransactionId")]
+pub enum RecurringTransactionEvent {
+    Initialized {
+        id: RecurringTransactionId,
+        reference: String,
+        description: String,
+        entries: Vec<ManualEntry>,
+        schedule: RecurrenceSchedule,
+        start_date: NaiveDate,
+        end_date: Option<NaiveDate>,
+        audit_info: AuditInfo,
+    },
+    Posted {
+        manual_transaction_id: ManualTransactionId,
+        effective: NaiveDate,
+        audit_info: AuditInfo,
+    },
+    PostingFailed {
+        effective: NaiveDate,
+        error: String,
+        audit_info: AuditInfo,
+    },
+    Paused {
+        audit_info: AuditInfo,
+    },
+    Resumed {
+        audit_info: AuditInfo,
+    },
+}
+
+#[derive(EsEntity, Builder)]
+#[builder(pattern = "owned", build_fn(error = "EsEntityError"))]
+pub struct RecurringTransaction {
+    pub id: RecurringTransactionId,
+    pub reference: String,
+    pub description: String,
+    pub entries: Vec<ManualEntry>,
+    pub schedule: RecurrenceSchedule,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+    events: EntityEvents<RecurringTransactionEvent>,
+}
+
+impl RecurringTransaction {
+    pub fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.events
+            .entity_first_persisted_at()
+            .expect("No events for recurring transaction")
+    }
+
+    pub fn status(&self) -> RecurringTransactionStatus {
+        let mut status = RecurringTransactionStatus::Active;
+        for event in self.events.iter_all() {
+            match event {
+                RecurringTransactionEvent::Paused { .. } => {
+                    status = RecurringTransactionStatus::Paused
+                }
+                RecurringTransactionEvent::Resumed { .. } => {
+                    status = RecurringTransactionStatus::Active
+                }
+                RecurringTransactionEvent::PostingFailed { .. } => {
+                    status = RecurringTransactionStatus::Failed
+                }
+                RecurringTransactionEvent::Initialized { .. }
+                | RecurringTransactionEvent::Posted { .. } => (),
+            }
+        }
+        if status == RecurringTransactionStatus::Active && self.next_occurrence().is_none() {
+            return RecurringTransactionStatus::Completed;
+        }
+        status
+    }
+
+    /// The manual transactions posted so far with their effective dates.
+    pub fn postings(&self) -> impl Iterator<Item = (ManualTransactionId, NaiveDate)> + '_ {
+        self.events.iter_all().filter_map(|e| match e {
+            RecurringTransactionEvent::Posted {
+                manual_transaction_id,
+                effective,
+                ..
+            } => Some((*manual_transaction_id, *effective)),
+            _ => None,
+        })
+    }
+
+    /// The effective date and error of the failed posting holding the
+    /// recurring transaction in `Failed`.
+    pub fn last_failure(&self) -> Option<(NaiveDate, &str)> {
+        if self.status() != RecurringTransactionStatus::Failed {
+            return None;
+        }
+        self.events.iter_all().rev().find_map(|e| match e {
+            RecurringTransactionEvent::PostingFailed {
+                effective, error, ..
+            } => Some((*effective, error.as_str())),
+            _ => None,
+        })
+    }
+
+    /// The next date a posting is due on, while the recurring transaction is active.
+    pub fn next_due_on(&self) -> Option<NaiveDate> {
+        match self.status() {
+            RecurringTransactionStatus::Active => self.next_occurrence(),
+            _ => None,
+        }
+    }
+
+    fn next_occurrence(&self) -> Option<NaiveDate> {
+        let from = match self.postings().map(|(_, effective)| effective).max() {
+            Some(last) => last.succ_opt()?,
+            None => self.start_date,
+        };
+        next_occurrence(&self.schedule, from, self.end_date)
+    }
+
+    pub fn record_posting(
+        &mut self,
+        manual_transaction_id: ManualTransactionId,
+        effective: NaiveDate,
+        audit_info: AuditInfo,
+    ) -> Idempotent<()> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            RecurringTransactionEvent::Posted { effective: e, .. } if *e == effective
+        );
+        self.events.push(RecurringTransactionEvent::Posted {
+            manual_transaction_id,
+            effective,
+            audit_info,
+        });
+        Idempotent::Executed(())
+    }
+
+    /// Stops further postings until the recurring transaction is resumed.
+    pub fn record_posting_failure(
+        &mut self,
+        effective: NaiveDate,
+        error: String,
+        audit_info: AuditInfo,
+    ) {
+        self.events.push(RecurringTransactionEvent::PostingFailed {
+            effective,
+            error,
+            audit_info,
+        });
+    }
+
+    pub fn pause(&mut self, audit_info: AuditInfo) -> Idempotent<()> {
+        match self.status() {
+            RecurringTransactionStatus::Paused | RecurringTransactionStatus::Completed => {
+                Idempotent::Ignored
+            }
+            RecurringTransactionStatus::Active | RecurringTransactionStatus::Failed => {
+                self.events
+                    .push(RecurringTransactionEvent::Paused { audit_info });
+                Idempotent::Executed(())
+            }
+        }
+    }
+
+    /// Resuming a failed recurring transaction retries the failed posting.
+    pub fn resume(&mut self, audit_info: AuditInfo) -> Idempotent<()> {
+        match self.status() {
+            RecurringTransactionStatus::Active | RecurringTransactionStatus::Completed => {
+                Idempotent::Ignored
+            }
+            RecurringTransactionStatus::Paused | RecurringTransactionStatus::Failed => {
+                self.events
+                    .push(RecurringTransactionEvent::Resumed { audit_info });
+                Idempotent::Executed(())
+            }
+        }
+    }
+}
+
+impl TryFromEvents<RecurringTransactionEvent> for RecurringTransaction {
+    fn try_from_events(
+        events: EntityEvents<RecurringTransactionEvent>,
+    ) -> Result<Self, EsEntityError> {
+        let mut builder = RecurringTransactionBuilder::default();
+        for event in events.iter_all() {
+            match event {
+                RecurringTransactionEvent::Initialized {
+                    id,
+                    reference,
+                    description,
+                    entries,
+                    schedule,
+                    start_date,
+                    end_date,
+                    ..
+                } => {
+                    builder = builder
+                        .id(*id)
+                        .reference(reference.clone())
+                        .description(description.clone())
+                        .entries(entries.clone())
+                        .schedule(*schedule)
+                        .start_date(*start_date)
+                        .end_date(*end_date)
+                }
+                RecurringTransactionEvent::Posted { .. }
+                | RecurringTransactionEvent::PostingFailed { .. }
+                | RecurringTransactionEvent::Paused { .. }
+                | RecurringTransactionEvent::Resumed { .. } => (),
+            }
+        }
+        builder.events(events).build()
+    }
+}
+
+#[derive(Debug, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct NewRecurringTransaction {
+    #[builder(setter(into))]
+    pub(super) id: RecurringTransactionId,
+    #[builder(setter(into))]
+    pub(super) reference: String,
+    #[builder(setter(into))]
+    description: String,
+    entries: Vec<ManualEntry>,
+    schedule: RecurrenceSchedule,
+    start_date: NaiveDate,
+    #[builder(default)]
+    end_date: Option<NaiveDate>,
+    #[builder(setter(into))]
+    pub audit_info: AuditInfo,
+}
+
+impl NewRecurringTransaction {
+    pub fn builder() -> NewRecurringTransactionBuilder {
+        NewRecurringTransactionBuilder::default()
+    }
+
+    pub(super) fn next_due_on(&self) -> Option<NaiveDate> {
+        next_occurrence(&self.schedule, self.start_date, self.end_date)
+    }
+}
+
+impl NewRecurringTransactionBuilder {
+    fn validate(&self) -> Result<(), String> {
+        if let Some(schedule) = &self.schedule
+            && !schedule.is_valid()
+        {
+            return Err("Day of month must be between 1 and 31".to_string());
+        }
+        if let Some(entries) = &self.entries
+            && entries.is_empty()
+        {
+            return Err("Recurring transaction needs at least one entry".to_string());
+        }
+        match (self.start_date, self.end_date.flatten()) {
+            (Some(start), Some(end)) if end < start => {
+                Err("Recurring transaction cannot end before it starts".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl IntoEvents<RecurringTransactionEvent> for NewRecurringTransaction {
+    fn into_events(self) -> EntityEvents<RecurringTransactionEvent> {
+        EntityEvents::init(
+            self.id,
+            [RecurringTransactionEvent::Initialized {
+                id: self.id,
+                reference: self.reference,
+                description: self.description,
+                entries: self.entries,
+                schedule: self.schedule,
+                start_date: self.start_date,
+                end_date: self.end_date,
+                audit_info: self.audit_info,
+            }],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use audit::{AuditEntryId, AuditInfo};
+    use cala_ledger::{AccountId as CalaAccountId, Currency, DebitOrCredit};
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn dummy_audit_info() -> AuditInfo {
+        AuditInfo {
+            audit_entry_id: AuditEntryId::from(1),
+            sub: "sub".to_string(),
+        }
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    fn recurring(end_date: Option<NaiveDate>) -> RecurringTransaction {
+        let new = NewRecurringTransaction::builder()
+            .id(RecurringTransactionId::new())
+            .reference("rent")
+            .description("Office rent")
+            .entries(vec![ManualEntry {
+                account_id: CalaAccountId::new(),
+                amount: dec!(1000),
+                currency: Currency::USD,
+                description: "rent".to_string(),
+                direction: DebitOrCredit::Debit,
+            }])
+            .schedule(RecurrenceSchedule::MonthlyOnDay { day: 1 })
+            .start_date(date("2025-01-01"))
+            .end_date(end_date)
+            .audit_info(dummy_audit_info())
+            .build()
+            .unwrap();
+        RecurringTransaction::try_from_events(new.into_events()).unwrap()
+    }
+
+    #[test]
+    fn postings_advance_until_end_date() {
+        let mut recurring = recurring(Some(date("2025-02-15")));
+        assert_eq!(recurring.next_due_on(), Some(date("2025-01-01")));
+
+        assert!(
+            recurring
+                .record_posting(
+                    ManualTransactionId::new(),
+                    date("2025-01-01"),
+                    dummy_audit_info()
+                )
+                .did_execute()
+        );
+        assert_eq!(recurring.next_due_on(), Some(date("2025-02-01")));
+
+        let _ = recurring.record_posting(
+            ManualTransactionId::new(),
+            date("2025-02-01"),
+            dummy_audit_info(),
+        );
+        assert_eq!(recurring.next_due_on(), None);
+        assert_eq!(recurring.status(), RecurringTransactionStatus::Completed);
+        assert_eq!(recurring.postings().count(), 2);
+    }
+
+    #[test]
+    fn failure_stops_postings_until_resumed() {
+        let mut recurring = recurring(None);
+        recurring.record_posting_failure(
+            date("2025-01-01"),
+            "period closed".to_string(),
+            dummy_audit_info(),
+        );
+        assert_eq!(recurring.status(), RecurringTransactionStatus::Failed);
+        assert_eq!(recurring.next_due_on(), None);
+        assert_eq!(
+            recurring.last_failure(),
+            Some((date("2025-01-01"), "period closed"))
+        );
+
+        assert!(recurring.resume(dummy_audit_info()).did_execute());
+        assert_eq!(recurring.next_due_on(), Some(date("2025-01-01")));
+        assert_eq!(recurring.last_failure(), None);
+    }
+
+    #[test]
+    fn paused_recurring_transaction_is_not_due() {
+        let mut recurring = recurring(None);
+        assert!(recurring.pause(dummy_audit_info()).did_execute());
+        assert!(recurring.pause(dummy_audit_info()).was_ignored());
+        assert_eq!(recurring.next_due_on(), None);
+        assert!(recurring.resume(dummy_audit_info()).did_execute());
+        assert_eq!(recurring.status(), RecurringTransactionStatus::Active);
+    }
+}
@@ -0,0 +1,23 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum RecurringTransactionError {
+    #[error("RecurringTransactionError - Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("RecurringTransactionError - EsEntityError: {0}")]
+    EsEntityError(es_entity::EsEntityError),
+    #[error("RecurringTransactionError - CursorDestructureError: {0}")]
+    CursorDestructureError(#[from] es_entity::CursorDestructureError),
+    #[error("RecurringTransactionError - AuthorizationError: {0}")]
+    AuthorizationError(#[from] authz::error::AuthorizationError),
+    #[error("RecurringTransactionError - AuditError: {0}")]
+    AuditError(#[from] audit::error::AuditError),
+    #[error("RecurringTransactionError - JobError: {0}")]
+    JobError(#[from] job::error::JobError),
+    #[error("RecurringTransactionError - ManualTransactionError: {0}")]
+    ManualTransactionError(#[from] crate::manual_transaction::error::ManualTransactionError),
+    #[error("RecurringTransactionError - NewRecurringTransactionBuilderError: {0}")]
+    NewRecurringTransactionBuilderError(#[from] super::entity::NewRecurringTransactionBuilderError),
+}
+
+es_entity::from_es_entity_error!(RecurringTransactionError);
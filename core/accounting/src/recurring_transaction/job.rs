@@ -0,0 +1,188 @@
+use async_trait::async_trait;
+
+use audit::{AuditSvc, SystemSubject};
+use authz::PermissionCheck;
+use governance::{GovernanceAction, GovernanceEvent, GovernanceObject};
+use job::*;
+use outbox::OutboxEventMarker;
+
+use crate::{
+    manual_transaction::{ManualTransactions, NewManualTransaction},
+    primitives::{CalaTxId, CoreAccountingAction, CoreAccountingObject, ManualTransactionId},
+};
+
+use super::repo::{RecurringTransactionRepo, recurring_transaction_cursor::*};
+
+#[derive(serde::Serialize)]
+pub struct PostRecurringTransactionsJobConfig<Perms, E> {
+    _phantom: std::marker::PhantomData<(Perms, E)>,
+}
+impl<Perms, E> PostRecurringTransactionsJobConfig<Perms, E> {
+    pub fn new() -> Self {
+        Self {
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+impl<Perms, E> JobConfig for PostRecurringTransactionsJobConfig<Perms, E>
+where
+    E: OutboxEventMarker<GovernanceEvent>,
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreAccountingAction> + From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreAccountingObject> + From<GovernanceObject>,
+    governance::CommitteeMemberId:
+        for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+{
+    type Initializer = PostRecurringTransactionsJobInitializer<Perms, E>;
+}
+
+pub struct PostRecurringTransactionsJobInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    repo: RecurringTransactionRepo,
+    manual_transactions: ManualTransactions<Perms, E>,
+    audit: Perms::Audit,
+}
+
+impl<Perms, E> PostRecurringTransactionsJobInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    pub fn new(
+        repo: &RecurringTransactionRepo,
+        manual_transactions: &ManualTransactions<Perms, E>,
+        audit: &Perms::Audit,
+    ) -> Self {
+        Self {
+            repo: repo.clone(),
+            manual_transactions: manual_transactions.clone(),
+            audit: audit.clone(),
+        }
+    }
+}
+
+const POST_RECURRING_TRANSACTIONS_JOB: JobType = JobType::new("post-recurring-transactions");
+impl<Perms, E> JobInitializer for PostRecurringTransactionsJobInitializer<Perms, E>
+where
+    E: OutboxEventMarker<GovernanceEvent>,
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreAccountingAction> + From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreAccountingObject> + From<GovernanceObject>,
+    governance::CommitteeMemberId:
+        for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        POST_RECURRING_TRANSACTIONS_JOB
+    }
+
+    fn init(&self, _: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(PostRecurringTransactionsJobRunner::<Perms, E> {
+            repo: self.repo.clone(),
+            manual_transactions: self.manual_transactions.clone(),
+            audit: self.audit.clone(),
+        }))
+    }
+}
+
+pub struct PostRecurringTransactionsJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    repo: RecurringTransactionRepo,
+    manual_transactions: ManualTransactions<Perms, E>,
+    audit: Perms::Audit,
+}
+
+#[async_trait]
+impl<Perms, E> JobRunner for PostRecurringTransactionsJobRunner<Perms, E>
+where
+    E: OutboxEventMarker<GovernanceEvent>,
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreAccountingAction> + From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreAccountingObject> + From<GovernanceObject>,
+    governance::CommitteeMemberId:
+        for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+{
+    async fn run(
+        &self,
+        _current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let now = chrono::Utc::now();
+        let today = now.date_naive();
+        let system = <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject::system();
+
+        let mut after = None;
+        'pages: loop {
+            let ret = self
+                .repo
+                .list_by_next_due_on(
+                    es_entity::PaginatedQueryArgs::<RecurringTransactionsByNextDueOnCursor> {
+                        first: 100,
+                        after,
+                    },
+                    es_entity::ListDirection::Ascending,
+                )
+                .await?;
+
+            for mut recurring in ret.entities.into_iter() {
+                if !matches!(recurring.next_due_on(), Some(due) if due <= today) {
+                    break 'pages;
+                }
+
+                // Catch up on every occurrence missed since the last run. A
+                // failure is recorded on the recurring transaction, which
+                // stops posting until it is resumed.
+                while let Some(due) = recurring.next_due_on().filter(|due| *due <= today) {
+                    let audit_info = self
+                        .audit
+                        .record_system_entry(
+                            CoreAccountingObject::recurring_transaction(recurring.id),
+                            CoreAccountingAction::RECURRING_TRANSACTION_POST,
+                        )
+                        .await?;
+                    let manual_tx_id = ManualTransactionId::new();
+                    let new_tx = NewManualTransaction::builder()
+                        .id(manual_tx_id)
+                        .ledger_transaction_id(CalaTxId::new())
+                        .approval_process_id(manual_tx_id)
+                        .description(recurring.description.clone())
+                        .reference(Some(format!("{}-{}", recurring.reference, due)))
+                        .effective(due)
+                        .entries(recurring.entries.clone())
+                        .recurring_transaction_id(Some(recurring.id))
+                        .audit_info(audit_info.clone())
+                        .build()
+                        .expect("Couldn't build new manual transaction");
+
+                    match self.manual_transactions.submit(&system, new_tx).await {
+                        Ok(manual_tx) => {
+                            let _ = recurring.record_posting(manual_tx.id, due, audit_info);
+                        }
+                        Err(e) => recurring.record_posting_failure(due, e.to_string(), audit_info),
+                    }
+                    self.repo.update(&mut recurring).await?;
+                }
+            }
+
+            if !ret.has_next_page {
+                break;
+            }
+            after = ret.end_cursor;
+        }
+
+        Ok(JobCompletion::RescheduleAt(now + chrono::Duration::days(1)))
+    }
+}
@@ -0,0 +1,219 @@
+mod entity;
+pub mod error;
+mod job;
+mod primitives;
+mod repo;
+
+use std::collections::HashMap;
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use chrono::NaiveDate;
+use governance::{GovernanceAction, GovernanceEvent, GovernanceObject};
+use outbox::OutboxEventMarker;
+
+use crate::{
+    Chart, Jobs,
+    manual_transaction::{ManualEntryInput, ManualTransactions},
+    primitives::{CoreAccountingAction, CoreAccountingObject, RecurringTransactionId},
+};
+
+pub use entity::RecurringTransaction;
+pub(super) use entity::*;
+use error::*;
+use job::*;
+pub use primitives::{RecurrenceSchedule, RecurringTransactionStatus};
+pub use repo::recurring_transaction_cursor::RecurringTransactionsByCreatedAtCursor;
+use repo::*;
+
+pub struct RecurringTransactions<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    authz: Perms,
+    repo: RecurringTransactionRepo,
+    manual_transactions: ManualTransactions<Perms, E>,
+}
+
+impl<Perms, E> Clone for RecurringTransactions<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            authz: self.authz.clone(),
+            repo: self.repo.clone(),
+            manual_transactions: self.manual_transactions.clone(),
+        }
+    }
+}
+
+impl<Perms, E> RecurringTransactions<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreAccountingAction> + From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreAccountingObject> + From<GovernanceObject>,
+    E: OutboxEventMarker<GovernanceEvent>,
+    governance::CommitteeMemberId:
+        for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+{
+    pub async fn init(
+        pool: &sqlx::PgPool,
+        authz: &Perms,
+        manual_transactions: &ManualTransactions<Perms, E>,
+        jobs: &Jobs,
+    ) -> Result<Self, RecurringTransactionError> {
+        let repo = RecurringTransactionRepo::new(pool);
+
+        jobs.add_initializer_and_spawn_unique(
+            PostRecurringTransactionsJobInitializer::new(&repo, manual_transactions, authz.audit()),
+            PostRecurringTransactionsJobConfig::<Perms, E>::new(),
+        )
+        .await?;
+
+        Ok(Self {
+            authz: authz.clone(),
+            repo,
+            manual_transactions: manual_transactions.clone(),
+        })
+    }
+
+    /// Creates a recurring transaction. Each occurrence is submitted as a
+    /// manual transaction on its effective date by a daily job.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn create(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        chart: &Chart,
+        reference: String,
+        description: String,
+        entries: Vec<ManualEntryInput>,
+        schedule: RecurrenceSchedule,
+        start_date: NaiveDate,
+        end_date: Option<NaiveDate>,
+    ) -> Result<RecurringTransaction, RecurringTransactionError> {
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_recurring_transactions(),
+                CoreAccountingAction::RECURRING_TRANSACTION_CREATE,
+            )
+            .await?;
+
+        let entries = self
+            .manual_transactions
+            .resolve_entries(chart, entries)
+            .await?;
+        let new_recurring = NewRecurringTransaction::builder()
+            .id(RecurringTransactionId::new())
+            .reference(reference)
+            .description(description)
+            .entries(entries)
+            .schedule(schedule)
+            .start_date(start_date)
+            .end_date(end_date)
+            .audit_info(audit_info)
+            .build()?;
+        self.repo.create(new_recurring).await
+    }
+
+    pub async fn find_by_id(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<RecurringTransactionId> + std::fmt::Debug,
+    ) -> Result<Option<RecurringTransaction>, RecurringTransactionError> {
+        let id = id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::recurring_transaction(id),
+                CoreAccountingAction::RECURRING_TRANSACTION_READ,
+            )
+            .await?;
+
+        match self.repo.find_by_id(id).await {
+            Ok(recurring) => Ok(Some(recurring)),
+            Err(e) if e.was_not_found() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub async fn list(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        query: es_entity::PaginatedQueryArgs<RecurringTransactionsByCreatedAtCursor>,
+    ) -> Result<
+        es_entity::PaginatedQueryRet<RecurringTransaction, RecurringTransactionsByCreatedAtCursor>,
+        RecurringTransactionError,
+    > {
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_recurring_transactions(),
+                CoreAccountingAction::RECURRING_TRANSACTION_LIST,
+            )
+            .await?;
+
+        self.repo
+            .list_by_created_at(query, es_entity::ListDirection::Descending)
+            .await
+    }
+
+    pub async fn find_all<T: From<RecurringTransaction>>(
+        &self,
+        ids: &[RecurringTransactionId],
+    ) -> Result<HashMap<RecurringTransactionId, T>, RecurringTransactionError> {
+        self.repo.find_all(ids).await
+    }
+
+    pub async fn pause(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<RecurringTransactionId> + std::fmt::Debug,
+    ) -> Result<RecurringTransaction, RecurringTransactionError> {
+        let id = id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::recurring_transaction(id),
+                CoreAccountingAction::RECURRING_TRANSACTION_PAUSE,
+            )
+            .await?;
+
+        let mut recurring = self.repo.find_by_id(id).await?;
+        if recurring.pause(audit_info).did_execute() {
+            self.repo.update(&mut recurring).await?;
+        }
+        Ok(recurring)
+    }
+
+    /// Resumes a paused recurring transaction, or retries the posting of one
+    /// that failed. Occurrences missed in the meantime are posted on the next run.
+    pub async fn resume(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<RecurringTransactionId> + std::fmt::Debug,
+    ) -> Result<RecurringTransaction, RecurringTransactionError> {
+        let id = id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::recurring_transaction(id),
+                CoreAccountingAction::RECURRING_TRANSACTION_RESUME,
+            )
+            .await?;
+
+        let mut recurring = self.repo.find_by_id(id).await?;
+        if recurring.resume(audit_info).did_execute() {
+            self.repo.update(&mut recurring).await?;
+        }
+        Ok(recurring)
+    }
+}
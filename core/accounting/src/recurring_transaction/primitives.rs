@@ -0,0 +1,125 @@
+use chrono::{Datelike, Months, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+/// When a recurring transaction is posted.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum RecurrenceSchedule {
+    /// Monthly on the given day, or on the last day of shorter months.
+    MonthlyOnDay {
+        day: u32,
+    },
+    EndOfMonth,
+}
+
+impl RecurrenceSchedule {
+    /// The first occurrence on or after `date`.
+    pub fn next_on_or_after(&self, date: NaiveDate) -> NaiveDate {
+        let occurrence = self.occurrence_in(date);
+        if occurrence >= date {
+            return occurrence;
+        }
+        let next_month = first_of_month(date)
+            .checked_add_months(Months::new(1))
+            .expect("date out of range");
+        self.occurrence_in(next_month)
+    }
+
+    fn occurrence_in(&self, date: NaiveDate) -> NaiveDate {
+        let last_day = first_of_month(date)
+            .checked_add_months(Months::new(1))
+            .and_then(|d| d.pred_opt())
+            .expect("date out of range");
+        match self {
+            RecurrenceSchedule::MonthlyOnDay { day } => last_day
+                .with_day((*day).min(last_day.day()))
+                .expect("day is within month"),
+            RecurrenceSchedule::EndOfMonth => last_day,
+        }
+    }
+
+    pub(super) fn is_valid(&self) -> bool {
+        match self {
+            RecurrenceSchedule::MonthlyOnDay { day } => (1..=31).contains(day),
+            RecurrenceSchedule::EndOfMonth => true,
+        }
+    }
+}
+
+fn first_of_month(date: NaiveDate) -> NaiveDate {
+    date.with_day(1).expect("first of month")
+}
+
+/// The first occurrence of `schedule` on or after `from`, unless it falls
+/// after `end_date`.
+pub(super) fn next_occurrence(
+    schedule: &RecurrenceSchedule,
+    from: NaiveDate,
+    end_date: Option<NaiveDate>,
+) -> Option<NaiveDate> {
+    let next = schedule.next_on_or_after(from);
+    match end_date {
+        Some(end_date) if next > end_date => None,
+        _ => Some(next),
+    }
+}
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+pub enum RecurringTransactionStatus {
+    Active,
+    Paused,
+    /// The last posting failed. Nothing is posted until it is resumed.
+    Failed,
+    /// Every occurrence up to the end date has been posted.
+    Completed,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(s: &str) -> NaiveDate {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn monthly_on_day_clamps_to_short_months() {
+        let schedule = RecurrenceSchedule::MonthlyOnDay { day: 31 };
+        assert_eq!(
+            schedule.next_on_or_after(date("2025-02-01")),
+            date("2025-02-28")
+        );
+        assert_eq!(
+            schedule.next_on_or_after(date("2025-03-01")),
+            date("2025-03-31")
+        );
+
+        let schedule = RecurrenceSchedule::MonthlyOnDay { day: 15 };
+        assert_eq!(
+            schedule.next_on_or_after(date("2025-01-15")),
+            date("2025-01-15")
+        );
+        assert_eq!(
+            schedule.next_on_or_after(date("2025-01-16")),
+            date("2025-02-15")
+        );
+    }
+
+    #[test]
+    fn end_of_month_and_end_date() {
+        let schedule = RecurrenceSchedule::EndOfMonth;
+        assert_eq!(
+            schedule.next_on_or_after(date("2024-02-10")),
+            date("2024-02-29")
+        );
+        assert_eq!(
+            next_occurrence(&schedule, date("2025-01-01"), Some(date("2025-01-30"))),
+            None
+        );
+        assert_eq!(
+            next_occurrence(&schedule, date("2025-01-01"), Some(date("2025-01-31"))),
+            Some(date("2025-01-31"))
+        );
+    }
+}
@@ -0,0 +1,41 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+
+use es_entity::*;
+
+use crate::primitives::RecurringTransactionId;
+
+use super::{entity::*, error::*};
+
+#[derive(EsRepo)]
+#[es_repo(
+    entity = "RecurringTransaction",
+    err = "RecurringTransactionError",
+    columns(
+        reference(ty = "String"),
+        next_due_on(
+            ty = "Option<NaiveDate>",
+            list_by,
+            create(accessor = "next_due_on()"),
+            update(accessor = "next_due_on()")
+        )
+    ),
+    tbl_prefix = "core"
+)]
+pub struct RecurringTransactionRepo {
+    pool: PgPool,
+}
+
+impl Clone for RecurringTransactionRepo {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+impl RecurringTransactionRepo {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
@@ -4,10 +4,20 @@ use thiserror::Error;
 pub enum TrialBalanceError {
     #[error("TrialBalanceError - Sqlx: {0}")]
     Sqlx(#[from] sqlx::Error),
+    #[error("TrialBalanceError - EsEntityError: {0}")]
+    EsEntityError(es_entity::EsEntityError),
+    #[error("TrialBalanceError - CursorDestructureError: {0}")]
+    CursorDestructureError(#[from] es_entity::CursorDestructureError),
     #[error("TrialBalanceError - AuditError: {0}")]
     AuditError(#[from] audit::error::AuditError),
     #[error("TrialBalanceError - AuthorizationError: {0}")]
     AuthorizationError(#[from] authz::error::AuthorizationError),
+    #[error("TrialBalanceError - JobError: {0}")]
+    JobError(#[from] job::error::JobError),
     #[error("TrialBalanceError - TrialBalanceLedgerError: {0}")]
     TrialBalanceLedgerError(#[from] super::ledger::error::TrialBalanceLedgerError),
+    #[error("TrialBalanceError - SnapshotDateInFuture: {0}")]
+    SnapshotDateInFuture(chrono::NaiveDate),
 }
+
+es_entity::from_es_entity_error!(TrialBalanceError);
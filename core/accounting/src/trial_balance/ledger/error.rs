@@ -12,6 +12,8 @@ pub enum TrialBalanceLedgerError {
     CalaBalance(#[from] cala_ledger::balance::error::BalanceError),
     #[error("TrialBalanceLedgerError - CalaEntry: {0}")]
     CalaEntry(#[from] cala_ledger::entry::error::EntryError),
+    #[error("TrialBalanceLedgerError - CalaTransaction: {0}")]
+    CalaTransaction(#[from] cala_ledger::transaction::error::TransactionError),
     #[error("TrialBalanceLedgerError - NonAccountSetMemberTypeFound")]
     NonAccountSetMemberTypeFound,
     #[error("TrialBalanceLedgerError - AccountCodeParseError: {0}")]
@@ -1,13 +1,19 @@
 pub mod error;
 
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
 
 use cala_ledger::{
     AccountSetId, BalanceId, CalaLedger, Currency, DebitOrCredit, JournalId, LedgerOperation,
-    account_set::{AccountSet, NewAccountSet},
+    account_set::{
+        AccountSet, AccountSetMemberId, AccountSetMembersByCreatedAtCursor, NewAccountSet,
+    },
+    entry::EntriesByCreatedAtCursor,
+    transaction::Transaction,
 };
 
-use crate::primitives::{BalanceRange, CalaBalanceRange};
+use crate::primitives::{BalanceRange, CalaBalanceRange, LedgerTransactionId};
+
+use super::snapshot::{SnapshotBalance, TrialBalanceSnapshotLine};
 
 use error::*;
 
@@ -211,6 +217,133 @@ impl TrialBalanceLedger {
 
         Ok(TrialBalanceRoot::from((account, balances, from, until)))
     }
+
+    /// Settled balances of every member of the trial balance at the end of `as_of`.
+    pub async fn get_member_lines(
+        &self,
+        reference: String,
+        as_of: NaiveDate,
+    ) -> Result<Vec<TrialBalanceSnapshotLine>, TrialBalanceLedgerError> {
+        let statement_id = self.get_id_from_reference(reference).await?;
+
+        let mut member_ids = Vec::new();
+        let mut query = es_entity::PaginatedQueryArgs::<AccountSetMembersByCreatedAtCursor> {
+            first: 100,
+            after: None,
+        };
+        loop {
+            let res = self
+                .cala
+                .account_sets()
+                .list_members_by_created_at(statement_id, query)
+                .await?;
+            for member in res.entities {
+                match member.id {
+                    AccountSetMemberId::AccountSet(id) => member_ids.push(id),
+                    AccountSetMemberId::Account(_) => {
+                        return Err(TrialBalanceLedgerError::NonAccountSetMemberTypeFound);
+                    }
+                }
+            }
+            match res.end_cursor {
+                Some(after) if res.has_next_page => {
+                    query = es_entity::PaginatedQueryArgs {
+                        first: 100,
+                        after: Some(after),
+                    }
+                }
+                _ => break,
+            }
+        }
+
+        let mut account_sets = self
+            .cala
+            .account_sets()
+            .find_all::<AccountSet>(&member_ids)
+            .await?;
+        let balances = self
+            .get_balances_by_id(member_ids.clone(), as_of, Some(as_of))
+            .await?;
+        let settled = |id: AccountSetId, currency| {
+            balances
+                .get(&(self.journal_id, id.into(), currency))
+                .map(|range| SnapshotBalance {
+                    debit: range.close.details.settled.dr_balance,
+                    credit: range.close.details.settled.cr_balance,
+                })
+                .unwrap_or_default()
+        };
+
+        Ok(member_ids
+            .into_iter()
+            .filter_map(|id| {
+                account_sets
+                    .remove(&id)
+                    .map(|account_set| TrialBalanceSnapshotLine {
+                        ledger_account_id: id.into(),
+                        name: account_set.into_values().name,
+                        usd: settled(id, Currency::USD),
+                        btc: settled(id, Currency::BTC),
+                    })
+            })
+            .collect())
+    }
+
+    /// Transactions recorded after `since` that are effective on or before
+    /// `as_of` and touch one of the given account sets, oldest first.
+    pub async fn find_back_dated_transaction_ids(
+        &self,
+        account_set_ids: Vec<AccountSetId>,
+        since: DateTime<Utc>,
+        as_of: NaiveDate,
+    ) -> Result<Vec<LedgerTransactionId>, TrialBalanceLedgerError> {
+        let mut transaction_ids = Vec::new();
+        for account_set_id in account_set_ids {
+            let mut query = es_entity::PaginatedQueryArgs::<EntriesByCreatedAtCursor> {
+                first: 100,
+                after: None,
+            };
+            'pages: loop {
+                let res = self
+                    .cala
+                    .entries()
+                    .list_for_account_set_id(
+                        account_set_id,
+                        query,
+                        es_entity::ListDirection::Descending,
+                    )
+                    .await?;
+                for entry in res.entities {
+                    if entry.created_at() <= since {
+                        break 'pages;
+                    }
+                    let transaction_id = entry.values().transaction_id;
+                    if !transaction_ids.contains(&transaction_id) {
+                        transaction_ids.push(transaction_id);
+                    }
+                }
+                match res.end_cursor {
+                    Some(after) if res.has_next_page => {
+                        query = es_entity::PaginatedQueryArgs {
+                            first: 100,
+                            after: Some(after),
+                        }
+                    }
+                    _ => break,
+                }
+            }
+        }
+
+        let transactions: std::collections::HashMap<_, Transaction> =
+            self.cala.transactions().find_all(&transaction_ids).await?;
+        let mut back_dated = transactions
+            .into_values()
+            .filter(|tx| tx.values().effective <= as_of)
+            .collect::<Vec<_>>();
+        back_dated.sort_by_key(|tx| tx.created_at());
+
+        Ok(back_dated.into_iter().map(|tx| tx.id).collect())
+    }
 }
 
 impl
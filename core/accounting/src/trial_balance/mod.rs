@@ -1,5 +1,6 @@
 pub mod error;
 pub mod ledger;
+mod snapshot;
 
 use chrono::NaiveDate;
 
@@ -8,13 +9,19 @@ use authz::PermissionCheck;
 use cala_ledger::CalaLedger;
 
 use crate::{
-    Chart,
-    primitives::{CoreAccountingAction, CoreAccountingObject},
+    Chart, Jobs,
+    primitives::{CoreAccountingAction, CoreAccountingObject, TrialBalanceSnapshotId},
 };
 
 use error::*;
 pub use ledger::TrialBalanceRoot;
 use ledger::*;
+use snapshot::*;
+pub use snapshot::{
+    SnapshotBalance, TrialBalanceLineDifference, TrialBalanceSnapshot,
+    TrialBalanceSnapshotComparison, TrialBalanceSnapshotKind, TrialBalanceSnapshotLine,
+    TrialBalanceSnapshotsByCreatedAtCursor,
+};
 
 #[derive(Clone)]
 pub struct TrialBalances<Perms>
@@ -23,7 +30,9 @@ where
 {
     pool: sqlx::PgPool,
     authz: Perms,
+    jobs: Jobs,
     trial_balance_ledger: TrialBalanceLedger,
+    snapshots: TrialBalanceSnapshotRepo,
 }
 
 impl<Perms> TrialBalances<Perms>
@@ -37,12 +46,22 @@ where
         authz: &Perms,
         cala: &CalaLedger,
         journal_id: cala_ledger::JournalId,
+        jobs: &Jobs,
     ) -> Self {
         let trial_balance_ledger = TrialBalanceLedger::new(cala, journal_id);
+        let snapshots = TrialBalanceSnapshotRepo::new(pool);
+
+        jobs.add_initializer(CaptureTrialBalanceSnapshotsJobInitializer::<Perms>::new(
+            &snapshots,
+            &trial_balance_ledger,
+            authz.audit(),
+        ));
 
         Self {
             pool: pool.clone(),
             trial_balance_ledger,
+            snapshots,
+            jobs: jobs.clone(),
             authz: authz.clone(),
         }
     }
@@ -62,6 +81,19 @@ where
             )
             .await?;
 
+        // Rolled back together with the statement when it already exists, so
+        // each trial balance gets a single capture job.
+        self.jobs
+            .create_and_spawn_in_op(
+                &mut op,
+                job::JobId::new(),
+                CaptureTrialBalanceSnapshotsJobConfig::<Perms> {
+                    reference: reference.clone(),
+                    _phantom: std::marker::PhantomData,
+                },
+            )
+            .await?;
+
         match self.trial_balance_ledger.create(op, &reference).await {
             Ok(_) => Ok(()),
             Err(e) if e.account_set_exists() => Ok(()),
@@ -121,4 +153,136 @@ where
             .get_trial_balance(name, from, Some(until))
             .await?)
     }
+
+    /// Captures the balances of the trial balance at the end of `as_of`.
+    pub async fn create_snapshot(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        name: String,
+        as_of: NaiveDate,
+    ) -> Result<TrialBalanceSnapshot, TrialBalanceError> {
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_trial_balance_snapshots(),
+                CoreAccountingAction::TRIAL_BALANCE_SNAPSHOT_CREATE,
+            )
+            .await?;
+
+        if as_of > chrono::Utc::now().date_naive() {
+            return Err(TrialBalanceError::SnapshotDateInFuture(as_of));
+        }
+
+        let lines = self
+            .trial_balance_ledger
+            .get_member_lines(name.clone(), as_of)
+            .await?;
+        let new_snapshot = NewTrialBalanceSnapshot::builder()
+            .id(TrialBalanceSnapshotId::new())
+            .reference(name)
+            .as_of(as_of)
+            .kind(TrialBalanceSnapshotKind::OnDemand)
+            .lines(lines)
+            .audit_info(audit_info)
+            .build()
+            .expect("Could not build new trial balance snapshot");
+
+        self.snapshots.create(new_snapshot).await
+    }
+
+    pub async fn find_snapshot_by_id(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<TrialBalanceSnapshotId> + std::fmt::Debug,
+    ) -> Result<Option<TrialBalanceSnapshot>, TrialBalanceError> {
+        let id = id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::trial_balance_snapshot(id),
+                CoreAccountingAction::TRIAL_BALANCE_SNAPSHOT_READ,
+            )
+            .await?;
+
+        match self.snapshots.find_by_id(id).await {
+            Ok(snapshot) => Ok(Some(snapshot)),
+            Err(e) if e.was_not_found() => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub async fn find_all_snapshots<T: From<TrialBalanceSnapshot>>(
+        &self,
+        ids: &[TrialBalanceSnapshotId],
+    ) -> Result<std::collections::HashMap<TrialBalanceSnapshotId, T>, TrialBalanceError> {
+        self.snapshots.find_all(ids).await
+    }
+
+    pub async fn list_snapshots(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        query: es_entity::PaginatedQueryArgs<TrialBalanceSnapshotsByCreatedAtCursor>,
+    ) -> Result<
+        es_entity::PaginatedQueryRet<TrialBalanceSnapshot, TrialBalanceSnapshotsByCreatedAtCursor>,
+        TrialBalanceError,
+    > {
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::all_trial_balance_snapshots(),
+                CoreAccountingAction::TRIAL_BALANCE_SNAPSHOT_LIST,
+            )
+            .await?;
+
+        self.snapshots
+            .list_by_created_at(query, es_entity::ListDirection::Descending)
+            .await
+    }
+
+    /// Recomputes the snapshot from the ledger and lists the accounts that
+    /// changed since it was captured, along with the back-dated transactions
+    /// responsible.
+    pub async fn compare_snapshot(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<TrialBalanceSnapshotId> + std::fmt::Debug,
+    ) -> Result<TrialBalanceSnapshotComparison, TrialBalanceError> {
+        let id = id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreAccountingObject::trial_balance_snapshot(id),
+                CoreAccountingAction::TRIAL_BALANCE_SNAPSHOT_READ,
+            )
+            .await?;
+
+        let snapshot = self.snapshots.find_by_id(id).await?;
+        let live = self
+            .trial_balance_ledger
+            .get_member_lines(snapshot.reference.clone(), snapshot.as_of)
+            .await?;
+        let differences = differences(&snapshot.lines, &live);
+
+        let transaction_ids = if differences.is_empty() {
+            Vec::new()
+        } else {
+            self.trial_balance_ledger
+                .find_back_dated_transaction_ids(
+                    differences
+                        .iter()
+                        .map(|d| d.ledger_account_id.into())
+                        .collect(),
+                    snapshot.created_at(),
+                    snapshot.as_of,
+                )
+                .await?
+        };
+
+        Ok(TrialBalanceSnapshotComparison {
+            snapshot,
+            differences,
+            transaction_ids,
+        })
+    }
 }
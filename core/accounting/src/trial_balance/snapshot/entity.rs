@@ -0,0 +1,180 @@
+use chrono::NaiveDate;
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use audit::AuditInfo;
+use es_entity::*;
+
+use crate::primitives::TrialBalanceSnapshotId;
+
+use super::primitives::*;
+
+#[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[es_event(id = "TrialBalanceSnapshotId")]
+pub enum TrialBalanceSnapshotEvent {
+    Initialized {
+        id: TrialBalanceSnapshotId,
+        reference: String,
+        as_of: NaiveDate,
+        kind: TrialBalanceSnapshotKind,
+        lines: Vec<TrialBalanceSnapshotLine>,
+        checksum: String,
+        audit_info: AuditInfo,
+    },
+}
+
+#[derive(EsEntity, Builder)]
+#[builder(pattern = "owned", build_fn(error = "EsEntityError"))]
+pub struct TrialBalanceSnapshot {
+    pub id: TrialBalanceSnapshotId,
+    pub reference: String,
+    pub as_of: NaiveDate,
+    pub kind: TrialBalanceSnapshotKind,
+    pub lines: Vec<TrialBalanceSnapshotLine>,
+    pub checksum: String,
+    events: EntityEvents<TrialBalanceSnapshotEvent>,
+}
+
+impl TrialBalanceSnapshot {
+    pub fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.events
+            .entity_first_persisted_at()
+            .expect("No events for trial balance snapshot")
+    }
+
+    /// Whether the stored lines still hash to the checksum taken at capture.
+    pub fn is_intact(&self) -> bool {
+        checksum(&self.reference, self.as_of, &self.lines) == self.checksum
+    }
+}
+
+impl TryFromEvents<TrialBalanceSnapshotEvent> for TrialBalanceSnapshot {
+    fn try_from_events(
+        events: EntityEvents<TrialBalanceSnapshotEvent>,
+    ) -> Result<Self, EsEntityError> {
+        let mut builder = TrialBalanceSnapshotBuilder::default();
+        for event in events.iter_all() {
+            match event {
+                TrialBalanceSnapshotEvent::Initialized {
+                    id,
+                    reference,
+                    as_of,
+                    kind,
+                    lines,
+                    checksum,
+                    ..
+                } => {
+                    builder = builder
+                        .id(*id)
+                        .reference(reference.clone())
+                        .as_of(*as_of)
+                        .kind(*kind)
+                        .lines(lines.clone())
+                        .checksum(checksum.clone())
+                }
+            }
+        }
+        builder.events(events).build()
+    }
+}
+
+#[derive(Debug, Builder)]
+pub struct NewTrialBalanceSnapshot {
+    #[builder(setter(into))]
+    pub(super) id: TrialBalanceSnapshotId,
+    #[builder(setter(into))]
+    pub(super) reference: String,
+    pub(super) as_of: NaiveDate,
+    kind: TrialBalanceSnapshotKind,
+    lines: Vec<TrialBalanceSnapshotLine>,
+    #[builder(setter(into))]
+    pub audit_info: AuditInfo,
+}
+
+impl NewTrialBalanceSnapshot {
+    pub fn builder() -> NewTrialBalanceSnapshotBuilder {
+        NewTrialBalanceSnapshotBuilder::default()
+    }
+}
+
+impl IntoEvents<TrialBalanceSnapshotEvent> for NewTrialBalanceSnapshot {
+    fn into_events(self) -> EntityEvents<TrialBalanceSnapshotEvent> {
+        let checksum = checksum(&self.reference, self.as_of, &self.lines);
+        EntityEvents::init(
+            self.id,
+            [TrialBalanceSnapshotEvent::Initialized {
+                id: self.id,
+                reference: self.reference,
+                as_of: self.as_of,
+                kind: self.kind,
+                lines: self.lines,
+                checksum,
+                audit_info: self.audit_info,
+            }],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use audit::{AuditEntryId, AuditInfo};
+    use rust_decimal_macros::dec;
+
+    use crate::primitives::LedgerAccountId;
+
+    use super::*;
+
+    fn dummy_audit_info() -> AuditInfo {
+        AuditInfo {
+            audit_entry_id: AuditEntryId::from(1),
+            sub: "sub".to_string(),
+        }
+    }
+
+    fn snapshot(lines: Vec<TrialBalanceSnapshotLine>) -> TrialBalanceSnapshot {
+        let new = NewTrialBalanceSnapshotBuilder::default()
+            .id(TrialBalanceSnapshotId::new())
+            .reference("Trial Balance")
+            .as_of("2025-01-31".parse().unwrap())
+            .kind(TrialBalanceSnapshotKind::MonthEnd)
+            .lines(lines)
+            .audit_info(dummy_audit_info())
+            .build()
+            .unwrap();
+        TrialBalanceSnapshot::try_from_events(new.into_events()).unwrap()
+    }
+
+    fn line(usd_debit: rust_decimal::Decimal) -> TrialBalanceSnapshotLine {
+        TrialBalanceSnapshotLine {
+            ledger_account_id: LedgerAccountId::new(),
+            name: "Assets".to_string(),
+            usd: SnapshotBalance {
+                debit: usd_debit,
+                credit: dec!(0),
+            },
+            btc: SnapshotBalance::default(),
+        }
+    }
+
+    #[test]
+    fn checksum_detects_tampered_lines() {
+        let mut snapshot = snapshot(vec![line(dec!(100))]);
+        assert!(snapshot.is_intact());
+
+        snapshot.lines[0].usd.debit = dec!(101);
+        assert!(!snapshot.is_intact());
+    }
+
+    #[test]
+    fn checksum_ignores_decimal_scale() {
+        let a = snapshot(vec![line(dec!(100))]);
+        let mut b = snapshot(a.lines.clone());
+        b.lines[0].usd.debit = dec!(100.00);
+
+        assert_eq!(
+            checksum(&a.reference, a.as_of, &a.lines),
+            checksum(&b.reference, b.as_of, &b.lines)
+        );
+    }
+}
@@ -0,0 +1,156 @@
+use async_trait::async_trait;
+use chrono::{Duration, Utc};
+use serde::{Deserialize, Serialize};
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use job::*;
+
+use crate::primitives::{CoreAccountingAction, CoreAccountingObject, TrialBalanceSnapshotId};
+
+use super::{
+    super::ledger::TrialBalanceLedger,
+    entity::NewTrialBalanceSnapshot,
+    primitives::TrialBalanceSnapshotKind,
+    repo::{TrialBalanceSnapshotRepo, trial_balance_snapshot_cursor::*},
+};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct CaptureTrialBalanceSnapshotsJobConfig<Perms> {
+    pub reference: String,
+    pub _phantom: std::marker::PhantomData<Perms>,
+}
+
+impl<Perms> JobConfig for CaptureTrialBalanceSnapshotsJobConfig<Perms>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreAccountingAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreAccountingObject>,
+{
+    type Initializer = CaptureTrialBalanceSnapshotsJobInitializer<Perms>;
+}
+
+pub struct CaptureTrialBalanceSnapshotsJobInitializer<Perms>
+where
+    Perms: PermissionCheck,
+{
+    repo: TrialBalanceSnapshotRepo,
+    ledger: TrialBalanceLedger,
+    audit: Perms::Audit,
+}
+
+impl<Perms> CaptureTrialBalanceSnapshotsJobInitializer<Perms>
+where
+    Perms: PermissionCheck,
+{
+    pub fn new(
+        repo: &TrialBalanceSnapshotRepo,
+        ledger: &TrialBalanceLedger,
+        audit: &Perms::Audit,
+    ) -> Self {
+        Self {
+            repo: repo.clone(),
+            ledger: ledger.clone(),
+            audit: audit.clone(),
+        }
+    }
+}
+
+const CAPTURE_TRIAL_BALANCE_SNAPSHOTS_JOB: JobType =
+    JobType::new("capture-trial-balance-snapshots");
+impl<Perms> JobInitializer for CaptureTrialBalanceSnapshotsJobInitializer<Perms>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreAccountingAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreAccountingObject>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        CAPTURE_TRIAL_BALANCE_SNAPSHOTS_JOB
+    }
+
+    fn init(&self, job: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(CaptureTrialBalanceSnapshotsJobRunner::<Perms> {
+            config: job.config()?,
+            repo: self.repo.clone(),
+            ledger: self.ledger.clone(),
+            audit: self.audit.clone(),
+        }))
+    }
+}
+
+pub struct CaptureTrialBalanceSnapshotsJobRunner<Perms>
+where
+    Perms: PermissionCheck,
+{
+    config: CaptureTrialBalanceSnapshotsJobConfig<Perms>,
+    repo: TrialBalanceSnapshotRepo,
+    ledger: TrialBalanceLedger,
+    audit: Perms::Audit,
+}
+
+#[async_trait]
+impl<Perms> JobRunner for CaptureTrialBalanceSnapshotsJobRunner<Perms>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreAccountingAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreAccountingObject>,
+{
+    async fn run(
+        &self,
+        _current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let today = Utc::now().date_naive();
+        let as_of = today.pred_opt().expect("date out of range");
+
+        // The job is rescheduled daily, so only the latest snapshots need to
+        // be checked to skip a day that was already captured.
+        let latest = self
+            .repo
+            .list_by_as_of(
+                es_entity::PaginatedQueryArgs::<TrialBalanceSnapshotsByAsOfCursor> {
+                    first: 10,
+                    after: None,
+                },
+                es_entity::ListDirection::Descending,
+            )
+            .await?;
+        let already_captured = latest.entities.iter().any(|snapshot| {
+            snapshot.as_of == as_of
+                && snapshot.reference == self.config.reference
+                && snapshot.kind != TrialBalanceSnapshotKind::OnDemand
+        });
+
+        if !already_captured {
+            let lines = self
+                .ledger
+                .get_member_lines(self.config.reference.clone(), as_of)
+                .await?;
+            let audit_info = self
+                .audit
+                .record_system_entry(
+                    CoreAccountingObject::all_trial_balance_snapshots(),
+                    CoreAccountingAction::TRIAL_BALANCE_SNAPSHOT_CREATE,
+                )
+                .await?;
+            let new_snapshot = NewTrialBalanceSnapshot::builder()
+                .id(TrialBalanceSnapshotId::new())
+                .reference(self.config.reference.clone())
+                .as_of(as_of)
+                .kind(TrialBalanceSnapshotKind::scheduled_for(as_of))
+                .lines(lines)
+                .audit_info(audit_info)
+                .build()
+                .expect("Could not build new trial balance snapshot");
+            self.repo.create(new_snapshot).await?;
+        }
+
+        let next_run = (today + Duration::days(1))
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is a valid time")
+            .and_utc();
+        Ok(JobCompletion::RescheduleAt(next_run))
+    }
+}
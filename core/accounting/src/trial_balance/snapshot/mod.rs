@@ -0,0 +1,31 @@
+mod entity;
+mod job;
+mod primitives;
+mod repo;
+
+use crate::primitives::LedgerTransactionId;
+
+pub use entity::TrialBalanceSnapshot;
+pub(super) use entity::*;
+pub(super) use job::*;
+pub(super) use primitives::differences;
+pub use primitives::{
+    SnapshotBalance, TrialBalanceLineDifference, TrialBalanceSnapshotKind, TrialBalanceSnapshotLine,
+};
+pub use repo::trial_balance_snapshot_cursor::TrialBalanceSnapshotsByCreatedAtCursor;
+pub(super) use repo::*;
+
+/// A snapshot set against the balances recomputed from the ledger today.
+pub struct TrialBalanceSnapshotComparison {
+    pub snapshot: TrialBalanceSnapshot,
+    pub differences: Vec<TrialBalanceLineDifference>,
+    /// Back-dated transactions recorded after the snapshot that explain the
+    /// differences.
+    pub transaction_ids: Vec<LedgerTransactionId>,
+}
+
+impl TrialBalanceSnapshotComparison {
+    pub fn is_unchanged(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
@@ -0,0 +1,182 @@
+use chrono::{Datelike, NaiveDate};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::primitives::LedgerAccountId;
+
+/// What triggered the capture of a snapshot.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[serde(rename_all = "snake_case")]
+pub enum TrialBalanceSnapshotKind {
+    EndOfDay,
+    MonthEnd,
+    OnDemand,
+}
+
+impl TrialBalanceSnapshotKind {
+    /// The kind the scheduled capture uses for a day.
+    pub fn scheduled_for(as_of: NaiveDate) -> Self {
+        match as_of.succ_opt() {
+            Some(next) if next.month0() != as_of.month0() => Self::MonthEnd,
+            _ => Self::EndOfDay,
+        }
+    }
+}
+
+/// Settled debit and credit totals of an account.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct SnapshotBalance {
+    pub debit: Decimal,
+    pub credit: Decimal,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrialBalanceSnapshotLine {
+    pub ledger_account_id: LedgerAccountId,
+    pub name: String,
+    pub usd: SnapshotBalance,
+    pub btc: SnapshotBalance,
+}
+
+impl TrialBalanceSnapshotLine {
+    fn same_balances(&self, other: &Self) -> bool {
+        self.usd == other.usd && self.btc == other.btc
+    }
+}
+
+/// An account whose live balance no longer matches the snapshot.
+#[derive(Debug, Clone)]
+pub struct TrialBalanceLineDifference {
+    pub ledger_account_id: LedgerAccountId,
+    pub name: String,
+    pub captured_usd: SnapshotBalance,
+    pub captured_btc: SnapshotBalance,
+    pub live_usd: SnapshotBalance,
+    pub live_btc: SnapshotBalance,
+}
+
+impl TrialBalanceLineDifference {
+    fn new(captured: &TrialBalanceSnapshotLine, live: &TrialBalanceSnapshotLine) -> Self {
+        Self {
+            ledger_account_id: live.ledger_account_id,
+            name: live.name.clone(),
+            captured_usd: captured.usd,
+            captured_btc: captured.btc,
+            live_usd: live.usd,
+            live_btc: live.btc,
+        }
+    }
+}
+
+/// Hex encoded SHA-256 of the balances in a snapshot. Account names are left
+/// out so renaming an account does not invalidate earlier snapshots.
+pub(super) fn checksum(
+    reference: &str,
+    as_of: NaiveDate,
+    lines: &[TrialBalanceSnapshotLine],
+) -> String {
+    let mut lines = lines.iter().collect::<Vec<_>>();
+    lines.sort_by_key(|line| line.ledger_account_id.to_string());
+
+    let mut hasher = Sha256::new();
+    hasher.update(format!("{reference}\n{as_of}\n"));
+    for line in lines {
+        hasher.update(format!(
+            "{}|{}|{}|{}|{}\n",
+            line.ledger_account_id,
+            line.usd.debit.normalize(),
+            line.usd.credit.normalize(),
+            line.btc.debit.normalize(),
+            line.btc.credit.normalize(),
+        ));
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Accounts whose balances differ between the captured and the live lines.
+/// Accounts missing on either side are compared against a zero balance.
+pub(crate) fn differences(
+    captured: &[TrialBalanceSnapshotLine],
+    live: &[TrialBalanceSnapshotLine],
+) -> Vec<TrialBalanceLineDifference> {
+    let zero = |line: &TrialBalanceSnapshotLine| TrialBalanceSnapshotLine {
+        usd: SnapshotBalance::default(),
+        btc: SnapshotBalance::default(),
+        ..line.clone()
+    };
+
+    let mut res = Vec::new();
+    for captured_line in captured {
+        let live_line = live
+            .iter()
+            .find(|l| l.ledger_account_id == captured_line.ledger_account_id)
+            .cloned()
+            .unwrap_or_else(|| zero(captured_line));
+        if !captured_line.same_balances(&live_line) {
+            res.push(TrialBalanceLineDifference::new(captured_line, &live_line));
+        }
+    }
+    for live_line in live {
+        if captured
+            .iter()
+            .all(|l| l.ledger_account_id != live_line.ledger_account_id)
+            && !live_line.same_balances(&zero(live_line))
+        {
+            res.push(TrialBalanceLineDifference::new(&zero(live_line), live_line));
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use rust_decimal_macros::dec;
+
+    use super::*;
+
+    fn line(id: LedgerAccountId, usd_debit: Decimal) -> TrialBalanceSnapshotLine {
+        TrialBalanceSnapshotLine {
+            ledger_account_id: id,
+            name: "Assets".to_string(),
+            usd: SnapshotBalance {
+                debit: usd_debit,
+                credit: Decimal::ZERO,
+            },
+            btc: SnapshotBalance::default(),
+        }
+    }
+
+    #[test]
+    fn differences_include_changed_and_new_accounts() {
+        let (unchanged, changed, added) = (
+            LedgerAccountId::new(),
+            LedgerAccountId::new(),
+            LedgerAccountId::new(),
+        );
+        let captured = vec![line(unchanged, dec!(10)), line(changed, dec!(20))];
+        let live = vec![
+            line(unchanged, dec!(10)),
+            line(changed, dec!(25)),
+            line(added, dec!(5)),
+        ];
+
+        let res = differences(&captured, &live);
+
+        assert_eq!(res.len(), 2);
+        assert_eq!(res[0].ledger_account_id, changed);
+        assert_eq!(res[0].captured_usd.debit, dec!(20));
+        assert_eq!(res[0].live_usd.debit, dec!(25));
+        assert_eq!(res[1].ledger_account_id, added);
+        assert_eq!(res[1].captured_usd.debit, Decimal::ZERO);
+    }
+
+    #[test]
+    fn scheduled_kind_marks_last_day_of_month() {
+        let kind = |s: &str| TrialBalanceSnapshotKind::scheduled_for(s.parse().unwrap());
+        assert_eq!(kind("2024-02-29"), TrialBalanceSnapshotKind::MonthEnd);
+        assert_eq!(kind("2025-02-27"), TrialBalanceSnapshotKind::EndOfDay);
+        assert_eq!(kind("2025-12-31"), TrialBalanceSnapshotKind::MonthEnd);
+    }
+}
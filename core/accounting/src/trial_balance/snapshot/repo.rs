@@ -0,0 +1,33 @@
+use chrono::NaiveDate;
+use sqlx::PgPool;
+
+use es_entity::*;
+
+use crate::primitives::TrialBalanceSnapshotId;
+
+use super::{super::error::*, entity::*};
+
+#[derive(EsRepo)]
+#[es_repo(
+    entity = "TrialBalanceSnapshot",
+    err = "TrialBalanceError",
+    columns(as_of(ty = "NaiveDate", list_by)),
+    tbl_prefix = "core"
+)]
+pub struct TrialBalanceSnapshotRepo {
+    pool: PgPool,
+}
+
+impl Clone for TrialBalanceSnapshotRepo {
+    fn clone(&self) -> Self {
+        Self {
+            pool: self.pool.clone(),
+        }
+    }
+}
+
+impl TrialBalanceSnapshotRepo {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
@@ -0,0 +1,152 @@
+mod helpers;
+
+use authz::dummy::{DummyPerms, DummySubject};
+use cloud_storage::{Storage, config::StorageConfig};
+use job::{JobExecutorConfig, Jobs};
+
+use cala_ledger::{CalaLedger, CalaLedgerConfig};
+use core_accounting::{
+    CoreAccounting,
+    csv::{AccountingCsv, AccountingCsvStatus},
+};
+use helpers::{action, event, object};
+use rust_decimal_macros::dec;
+
+const HEADER: &str = "reference,effective_date,account_code,direction,amount,currency,description";
+
+#[tokio::test]
+async fn import_posts_every_transaction() -> anyhow::Result<()> {
+    let (accounting, chart_ref) = prepare_test().await?;
+    let today = chrono::Utc::now().date_naive();
+
+    let data = format!(
+        "{HEADER}\n\
+         {chart_ref}-1,{today},1,debit,100,USD,Accrual\n\
+         {chart_ref}-1,{today},2,credit,100,USD,Accrual\n\
+         {chart_ref}-2,{today},1,debit,20,USD,Fee\n\
+         {chart_ref}-2,{today},2,credit,20,USD,Fee\n"
+    );
+    let import = import_csv(&accounting, &chart_ref, data).await?;
+
+    assert!(import.import_errors().is_empty());
+    assert_eq!(import.imported_manual_transaction_ids().len(), 2);
+    let account = accounting
+        .find_ledger_account_by_code(&DummySubject, &chart_ref, "2".to_string())
+        .await?
+        .unwrap();
+    assert_eq!(
+        account
+            .usd_balance_range
+            .expect("should have balance")
+            .end
+            .expect("balance missing")
+            .settled(),
+        dec!(120)
+    );
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn import_posts_nothing_when_a_line_is_invalid() -> anyhow::Result<()> {
+    let (accounting, chart_ref) = prepare_test().await?;
+    let today = chrono::Utc::now().date_naive();
+
+    let data = format!(
+        "{HEADER}\n\
+         {chart_ref}-1,{today},1,debit,100,USD,Accrual\n\
+         {chart_ref}-1,{today},2,credit,100,USD,Accrual\n\
+         {chart_ref}-2,{today},1,debit,20,USD,Fee\n\
+         {chart_ref}-2,{today},9,credit,20,USD,Fee\n"
+    );
+    let import = import_csv(&accounting, &chart_ref, data).await?;
+
+    assert!(import.imported_manual_transaction_ids().is_empty());
+    let errors = import.import_errors();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].line, 5);
+    assert_eq!(errors[0].reference, Some(format!("{chart_ref}-2")));
+    let account = accounting
+        .find_ledger_account_by_code(&DummySubject, &chart_ref, "2".to_string())
+        .await?
+        .unwrap();
+    assert!(account.usd_balance_range.is_none());
+
+    Ok(())
+}
+
+async fn import_csv(
+    accounting: &CoreAccounting<
+        DummyPerms<action::DummyAction, object::DummyObject>,
+        event::DummyEvent,
+    >,
+    chart_ref: &str,
+    data: String,
+) -> anyhow::Result<AccountingCsv> {
+    let import = accounting
+        .import_manual_transactions_csv(&DummySubject, chart_ref, "import.csv".to_string(), data)
+        .await?;
+
+    for _ in 0..100 {
+        let mut csvs = accounting
+            .csvs()
+            .find_all::<AccountingCsv>(&[import.id])
+            .await?;
+        let import = csvs.remove(&import.id).expect("import not found");
+        if import.status() != AccountingCsvStatus::Pending {
+            return Ok(import);
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    anyhow::bail!("import did not conclude")
+}
+
+async fn prepare_test() -> anyhow::Result<(
+    CoreAccounting<DummyPerms<action::DummyAction, object::DummyObject>, event::DummyEvent>,
+    String,
+)> {
+    use rand::Rng;
+    let pool = helpers::init_pool().await?;
+    let cala_config = CalaLedgerConfig::builder()
+        .pool(pool.clone())
+        .exec_migrations(false)
+        .build()?;
+    let cala = CalaLedger::init(cala_config).await?;
+    let authz = authz::dummy::DummyPerms::<action::DummyAction, object::DummyObject>::new();
+    let journal_id = helpers::init_journal(&cala).await?;
+
+    let storage = Storage::new(&StorageConfig::default());
+    let mut jobs = Jobs::new(&pool, JobExecutorConfig::default());
+
+    let outbox = outbox::Outbox::<event::DummyEvent>::init(&pool).await?;
+    let governance = governance::Governance::init(&pool, &authz, &outbox, &jobs).await?;
+    let accounting = CoreAccounting::init(
+        &pool,
+        &authz,
+        &cala,
+        journal_id,
+        &storage,
+        &jobs,
+        &governance,
+        &outbox,
+    )
+    .await?;
+    jobs.start_poll().await?;
+
+    let chart_ref = format!("ref-{:08}", rand::rng().random_range(0..10000));
+    let chart = accounting
+        .chart_of_accounts()
+        .create_chart(&DummySubject, "Test chart".to_string(), chart_ref.clone())
+        .await?;
+    let import = r#"
+        1,,Assets
+        2,,Liabilities
+        "#;
+    let chart_id = chart.id;
+    let _ = accounting
+        .chart_of_accounts()
+        .import_from_csv(&DummySubject, chart_id, import)
+        .await?;
+
+    Ok((accounting, chart_ref))
+}
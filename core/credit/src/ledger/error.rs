@@ -20,6 +20,8 @@ pub enum CreditLedgerError {
     CalaVelocity(#[from] cala_ledger::velocity::error::VelocityError),
     #[error("CreditLedgerError - ChartOfAccountsError: {0}")]
     ChartOfAccountsError(#[from] core_accounting::chart_of_accounts::error::ChartOfAccountsError),
+    #[error("CreditLedgerError - AccountingPeriodError: {0}")]
+    AccountingPeriodError(#[from] core_accounting::accounting_period::error::AccountingPeriodError),
     #[error("CreditLedgerError - NonAccountMemberFoundInAccountSet: Found non-Account typed member in account set {0}")]
     NonAccountMemberFoundInAccountSet(String),
     #[error("CreditLedgerError - JournalIdMismatch: Account sets have wrong JournalId")]
@@ -18,6 +18,8 @@ use cala_ledger::{
     CalaLedger, Currency, DebitOrCredit, JournalId, LedgerOperation, TransactionId,
 };
 
+use core_accounting::{AccountingPeriodCheck, PostingKind};
+
 use crate::{
     payment_allocation::PaymentAllocation,
     primitives::{
@@ -171,10 +173,15 @@ pub struct CreditLedger {
     credit_facility_control_id: VelocityControlId,
     usd: Currency,
     btc: Currency,
+    periods: AccountingPeriodCheck,
 }
 
 impl CreditLedger {
-    pub async fn init(cala: &CalaLedger, journal_id: JournalId) -> Result<Self, CreditLedgerError> {
+    pub async fn init(
+        cala: &CalaLedger,
+        journal_id: JournalId,
+        periods: &AccountingPeriodCheck,
+    ) -> Result<Self, CreditLedgerError> {
         templates::AddCollateral::init(cala).await?;
         templates::CreateCreditFacility::init(cala).await?;
         templates::ActivateCreditFacility::init(cala).await?;
@@ -787,6 +794,7 @@ impl CreditLedger {
             credit_facility_control_id,
             usd: Currency::USD,
             btc: Currency::BTC,
+            periods: periods.clone(),
         })
     }
 
@@ -1066,9 +1074,20 @@ impl CreditLedger {
         })
     }
 
+    async fn ensure_period_open(
+        &self,
+        op: &mut es_entity::DbOp<'_>,
+        effective: chrono::NaiveDate,
+    ) -> Result<(), CreditLedgerError> {
+        self.periods
+            .ensure_accepts_posting_in_op(op, effective, PostingKind::System)
+            .await?;
+        Ok(())
+    }
+
     pub async fn update_credit_facility_collateral(
         &self,
-        op: es_entity::DbOp<'_>,
+        mut op: es_entity::DbOp<'_>,
         CollateralUpdate {
             tx_id,
             abs_diff,
@@ -1076,6 +1095,8 @@ impl CreditLedger {
         }: CollateralUpdate,
         credit_facility_account_ids: CreditFacilityAccountIds,
     ) -> Result<(), CreditLedgerError> {
+        self.ensure_period_open(&mut op, crate::time::now().date_naive())
+            .await?;
         let mut op = self.cala.ledger_operation_from_db_op(op);
         match action {
             CollateralAction::Add => {
@@ -1155,9 +1176,11 @@ impl CreditLedger {
 
     pub async fn record_obligation_repayments(
         &self,
-        op: es_entity::DbOp<'_>,
+        mut op: es_entity::DbOp<'_>,
         payments: Vec<PaymentAllocation>,
     ) -> Result<(), CreditLedgerError> {
+        self.ensure_period_open(&mut op, crate::time::now().date_naive())
+            .await?;
         let mut op = self.cala.ledger_operation_from_db_op(op);
 
         for payment in payments {
@@ -1171,7 +1194,7 @@ impl CreditLedger {
 
     pub async fn record_obligation_due(
         &self,
-        op: es_entity::DbOp<'_>,
+        mut op: es_entity::DbOp<'_>,
         ObligationDueReallocationData {
             tx_id,
             amount: outstanding_amount,
@@ -1180,6 +1203,8 @@ impl CreditLedger {
             ..
         }: ObligationDueReallocationData,
     ) -> Result<(), CreditLedgerError> {
+        self.ensure_period_open(&mut op, crate::time::now().date_naive())
+            .await?;
         let mut op = self.cala.ledger_operation_from_db_op(op);
         self.cala
             .post_transaction_in_op(
@@ -1200,7 +1225,7 @@ impl CreditLedger {
 
     pub async fn record_obligation_overdue(
         &self,
-        op: es_entity::DbOp<'_>,
+        mut op: es_entity::DbOp<'_>,
         ObligationOverdueReallocationData {
             tx_id,
             amount: outstanding_amount,
@@ -1209,6 +1234,8 @@ impl CreditLedger {
             ..
         }: ObligationOverdueReallocationData,
     ) -> Result<(), CreditLedgerError> {
+        self.ensure_period_open(&mut op, crate::time::now().date_naive())
+            .await?;
         let mut op = self.cala.ledger_operation_from_db_op(op);
         self.cala
             .post_transaction_in_op(
@@ -1229,7 +1256,7 @@ impl CreditLedger {
 
     pub async fn record_obligation_defaulted(
         &self,
-        op: es_entity::DbOp<'_>,
+        mut op: es_entity::DbOp<'_>,
         ObligationDefaultedReallocationData {
             tx_id,
             amount: outstanding_amount,
@@ -1238,6 +1265,8 @@ impl CreditLedger {
             ..
         }: ObligationDefaultedReallocationData,
     ) -> Result<(), CreditLedgerError> {
+        self.ensure_period_open(&mut op, crate::time::now().date_naive())
+            .await?;
         let mut op = self.cala.ledger_operation_from_db_op(op);
         self.cala
             .post_transaction_in_op(
@@ -1258,13 +1287,15 @@ impl CreditLedger {
 
     pub async fn complete_credit_facility(
         &self,
-        op: es_entity::DbOp<'_>,
+        mut op: es_entity::DbOp<'_>,
         CreditFacilityCompletion {
             tx_id,
             collateral,
             credit_facility_account_ids,
         }: CreditFacilityCompletion,
     ) -> Result<(), CreditLedgerError> {
+        self.ensure_period_open(&mut op, crate::time::now().date_naive())
+            .await?;
         let mut op = self.cala.ledger_operation_from_db_op(op);
         self.cala
             .post_transaction_in_op(
@@ -1294,6 +1325,8 @@ impl CreditLedger {
             facility_amount,
         }: CreditFacilityCreation,
     ) -> Result<(), CreditLedgerError> {
+        self.ensure_period_open(op.op(), crate::time::now().date_naive())
+            .await?;
         self.cala
             .post_transaction_in_op(
                 &mut op,
@@ -1315,7 +1348,7 @@ impl CreditLedger {
 
     pub async fn activate_credit_facility(
         &self,
-        op: es_entity::DbOp<'_>,
+        mut op: es_entity::DbOp<'_>,
         CreditFacilityActivation {
             tx_id,
             tx_ref,
@@ -1325,6 +1358,8 @@ impl CreditLedger {
             structuring_fee_amount,
         }: CreditFacilityActivation,
     ) -> Result<(), CreditLedgerError> {
+        self.ensure_period_open(&mut op, crate::time::now().date_naive())
+            .await?;
         let mut op = self.cala.ledger_operation_from_db_op(op);
         self.cala
             .post_transaction_in_op(
@@ -1352,7 +1387,7 @@ impl CreditLedger {
 
     pub async fn record_interest_accrual(
         &self,
-        op: es_entity::DbOp<'_>,
+        mut op: es_entity::DbOp<'_>,
         CreditFacilityInterestAccrual {
             tx_id,
             tx_ref,
@@ -1361,6 +1396,8 @@ impl CreditLedger {
             credit_facility_account_ids,
         }: CreditFacilityInterestAccrual,
     ) -> Result<(), CreditLedgerError> {
+        self.ensure_period_open(&mut op, period.end.date_naive())
+            .await?;
         let mut op = self.cala.ledger_operation_from_db_op(op);
         self.cala
             .post_transaction_in_op(
@@ -1386,7 +1423,7 @@ impl CreditLedger {
 
     pub async fn record_interest_accrual_cycle(
         &self,
-        op: es_entity::DbOp<'_>,
+        mut op: es_entity::DbOp<'_>,
         obligation: Obligation,
     ) -> Result<(), CreditLedgerError> {
         let interest_receivable_account_id =
@@ -1401,6 +1438,8 @@ impl CreditLedger {
             ..
         } = obligation;
 
+        self.ensure_period_open(&mut op, posted_at.date_naive())
+            .await?;
         let mut op = self.cala.ledger_operation_from_db_op(op);
         self.cala
             .post_transaction_in_op(
@@ -1424,11 +1463,13 @@ impl CreditLedger {
 
     pub async fn initiate_disbursal(
         &self,
-        op: es_entity::DbOp<'_>,
+        mut op: es_entity::DbOp<'_>,
         tx_id: impl Into<TransactionId>,
         amount: UsdCents,
         facility_account_id: CalaAccountId,
     ) -> Result<(), CreditLedgerError> {
+        self.ensure_period_open(&mut op, crate::time::now().date_naive())
+            .await?;
         let mut op = self.cala.ledger_operation_from_db_op(op);
         self.cala
             .post_transaction_in_op(
@@ -1449,11 +1490,13 @@ impl CreditLedger {
 
     pub async fn cancel_disbursal(
         &self,
-        op: es_entity::DbOp<'_>,
+        mut op: es_entity::DbOp<'_>,
         tx_id: LedgerTxId,
         amount: UsdCents,
         facility_account_id: CalaAccountId,
     ) -> Result<(), CreditLedgerError> {
+        self.ensure_period_open(&mut op, crate::time::now().date_naive())
+            .await?;
         let mut op = self.cala.ledger_operation_from_db_op(op);
         self.cala
             .post_transaction_in_op(
@@ -1474,7 +1517,7 @@ impl CreditLedger {
 
     pub async fn settle_disbursal(
         &self,
-        op: es_entity::DbOp<'_>,
+        mut op: es_entity::DbOp<'_>,
         obligation: Obligation,
         facility_account_id: CalaAccountId,
     ) -> Result<(), CreditLedgerError> {
@@ -1488,6 +1531,8 @@ impl CreditLedger {
             ..
         } = obligation;
 
+        self.ensure_period_open(&mut op, crate::time::now().date_naive())
+            .await?;
         let mut op = self.cala.ledger_operation_from_db_op(op);
         self.cala
             .post_transaction_in_op(
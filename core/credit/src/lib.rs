@@ -25,7 +25,7 @@ use std::collections::HashMap;
 use audit::{AuditInfo, AuditSvc};
 use authz::PermissionCheck;
 use cala_ledger::CalaLedger;
-use core_accounting::Chart;
+use core_accounting::{AccountingPeriodCheck, Chart};
 use core_customer::{CoreCustomerAction, CoreCustomerEvent, CustomerObject, Customers};
 use core_price::Price;
 use es_entity::Idempotent;
@@ -147,7 +147,8 @@ where
         let history_repo = HistoryRepo::new(pool);
         let repayment_plan_repo = RepaymentPlanRepo::new(pool);
         let payment_allocation_repo = PaymentAllocationRepo::new(pool, &publisher);
-        let ledger = CreditLedger::init(cala, journal_id).await?;
+        let ledger =
+            CreditLedger::init(cala, journal_id, &AccountingPeriodCheck::new(pool)).await?;
         let approve_disbursal = ApproveDisbursal::new(
             &disbursal_repo,
             &obligations,
@@ -339,7 +340,11 @@ where
         disbursal_credit_account_id: impl Into<CalaAccountId> + std::fmt::Debug,
         amount: UsdCents,
         terms: TermValues,
-    ) -> Result<CreditFacility, CoreCreditError> {
+    ) -> Result<CreditFacility, CoreCreditError>
+    where
+        governance::CommitteeMemberId:
+            for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+    {
         let audit_info = self
             .subject_can_create(sub, true)
             .await?
@@ -374,7 +379,14 @@ where
 
         let mut db = self.credit_facility_repo.begin_op().await?;
         self.governance
-            .start_process(&mut db, id, id.to_string(), APPROVE_CREDIT_FACILITY_PROCESS)
+            .start_process(
+                &mut db,
+                id,
+                id.to_string(),
+                APPROVE_CREDIT_FACILITY_PROCESS,
+                Some(amount),
+                sub,
+            )
             .await?;
 
         let new_collateral = NewCollateral::builder()
@@ -524,7 +536,11 @@ where
         sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
         credit_facility_id: CreditFacilityId,
         amount: UsdCents,
-    ) -> Result<Disbursal, CoreCreditError> {
+    ) -> Result<Disbursal, CoreCreditError>
+    where
+        governance::CommitteeMemberId:
+            for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+    {
         let audit_info = self
             .subject_can_initiate_disbursal(sub, true)
             .await?
@@ -582,6 +598,8 @@ where
                 new_disbursal.approval_process_id,
                 new_disbursal.approval_process_id.to_string(),
                 APPROVE_DISBURSAL_PROCESS,
+                Some(amount),
+                sub,
             )
             .await?;
         let disbursal = self
@@ -1083,6 +1101,49 @@ where
         Ok(disbursals)
     }
 
+    /// Whether the customer still has a facility that is not closed and that
+    /// disburses into the given account.
+    #[instrument(
+        name = "credit_facility.has_open_facility_for_disbursal_account",
+        skip(self, db),
+        err
+    )]
+    pub async fn has_open_facility_for_disbursal_account(
+        &self,
+        db: &mut es_entity::DbOp<'_>,
+        customer_id: impl Into<CustomerId> + std::fmt::Debug,
+        disbursal_credit_account_id: impl Into<CalaAccountId> + std::fmt::Debug,
+    ) -> Result<bool, CoreCreditError> {
+        let customer_id = customer_id.into();
+        let disbursal_credit_account_id = disbursal_credit_account_id.into();
+
+        let mut after = None;
+        loop {
+            let ret = self
+                .credit_facility_repo
+                .list_for_customer_id_by_created_at_in_tx(
+                    db.tx(),
+                    customer_id,
+                    es_entity::PaginatedQueryArgs::<CreditFacilitiesByCreatedAtCursor> {
+                        first: 100,
+                        after,
+                    },
+                    es_entity::ListDirection::Descending,
+                )
+                .await?;
+            if ret.entities.iter().any(|facility| {
+                facility.disbursal_credit_account_id == disbursal_credit_account_id
+                    && facility.status() != CreditFacilityStatus::Closed
+            }) {
+                return Ok(true);
+            }
+            if !ret.has_next_page {
+                return Ok(false);
+            }
+            after = ret.end_cursor;
+        }
+    }
+
     pub async fn find_all<T: From<CreditFacility>>(
         &self,
         ids: &[CreditFacilityId],
@@ -17,7 +17,6 @@ async fn chart_of_accounts_integration() -> anyhow::Result<()> {
     let outbox = outbox::Outbox::<event::DummyEvent>::init(&pool).await?;
     let authz = authz::dummy::DummyPerms::<action::DummyAction, object::DummyObject>::new();
 
-    let governance = governance::Governance::new(&pool, &authz, &outbox);
     let customers = core_customer::Customers::new(&pool, &authz, &outbox);
     let price = core_price::Price::new();
 
@@ -27,6 +26,7 @@ async fn chart_of_accounts_integration() -> anyhow::Result<()> {
         .build()?;
     let cala = CalaLedger::init(cala_config).await?;
     let jobs = job::Jobs::new(&pool, job::JobExecutorConfig::default());
+    let governance = governance::Governance::init(&pool, &authz, &outbox, &jobs).await?;
 
     let journal_id = helpers::init_journal(&cala).await?;
 
@@ -45,7 +45,17 @@ async fn chart_of_accounts_integration() -> anyhow::Result<()> {
     .await?;
 
     let storage = Storage::new(&StorageConfig::default());
-    let accounting = CoreAccounting::new(&pool, &authz, &cala, journal_id, &storage, &jobs);
+    let accounting = CoreAccounting::init(
+        &pool,
+        &authz,
+        &cala,
+        journal_id,
+        &storage,
+        &jobs,
+        &governance,
+        &outbox,
+    )
+    .await?;
     let chart_ref = format!("ref-{:08}", rand::rng().random_range(0..10000));
     let chart = accounting
         .chart_of_accounts()
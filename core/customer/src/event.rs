@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 
-use crate::primitives::{AccountStatus, CustomerId, CustomerType};
+use crate::primitives::{AccountStatus, CustomerId, CustomerType, KycLevel};
 
 #[derive(Debug, Serialize, Deserialize)]
 #[serde(tag = "type")]
@@ -18,4 +18,8 @@ pub enum CoreCustomerEvent {
         id: CustomerId,
         email: String,
     },
+    CustomerKycLevelUpdated {
+        id: CustomerId,
+        level: KycLevel,
+    },
 }
@@ -50,6 +50,12 @@ where
                         status: *status,
                     })
                 }
+                KycApproved { .. } | KycDeclined { .. } => {
+                    Some(CoreCustomerEvent::CustomerKycLevelUpdated {
+                        id: entity.id,
+                        level: entity.level,
+                    })
+                }
                 EmailUpdated { email, .. } => Some(CoreCustomerEvent::CustomerEmailUpdated {
                     id: entity.id,
                     email: email.clone(),
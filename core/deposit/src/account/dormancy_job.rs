@@ -0,0 +1,169 @@
+use async_trait::async_trait;
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use job::*;
+use outbox::OutboxEventMarker;
+
+use crate::{
+    event::CoreDepositEvent, ledger::DepositLedger, primitives::DepositAccountStatus,
+    CoreDepositAction, CoreDepositObject,
+};
+
+use super::{deposit_account_cursor::DepositAccountsByCreatedAtCursor, repo::DepositAccountRepo};
+
+#[derive(serde::Serialize)]
+pub struct DormantDepositAccountsJobConfig<Perms, E> {
+    _phantom: std::marker::PhantomData<(Perms, E)>,
+}
+impl<Perms, E> DormantDepositAccountsJobConfig<Perms, E> {
+    pub fn new() -> Self {
+        Self {
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+impl<Perms, E> JobConfig for DormantDepositAccountsJobConfig<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreDepositAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreDepositObject>,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    type Initializer = DormantDepositAccountsJobInitializer<Perms, E>;
+}
+
+pub struct DormantDepositAccountsJobInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    accounts: DepositAccountRepo<E>,
+    ledger: DepositLedger,
+    dormant_after: chrono::Duration,
+    audit: Perms::Audit,
+}
+
+impl<Perms, E> DormantDepositAccountsJobInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreDepositAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreDepositObject>,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    pub fn new(
+        accounts: &DepositAccountRepo<E>,
+        ledger: &DepositLedger,
+        dormant_after: chrono::Duration,
+        audit: &Perms::Audit,
+    ) -> Self {
+        Self {
+            accounts: accounts.clone(),
+            ledger: ledger.clone(),
+            dormant_after,
+            audit: audit.clone(),
+        }
+    }
+}
+
+const DORMANT_DEPOSIT_ACCOUNTS_JOB: JobType = JobType::new("dormant-deposit-accounts");
+impl<Perms, E> JobInitializer for DormantDepositAccountsJobInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreDepositAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreDepositObject>,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        DORMANT_DEPOSIT_ACCOUNTS_JOB
+    }
+
+    fn init(&self, _: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(DormantDepositAccountsJobRunner::<Perms, E> {
+            accounts: self.accounts.clone(),
+            ledger: self.ledger.clone(),
+            dormant_after: self.dormant_after,
+            audit: self.audit.clone(),
+        }))
+    }
+}
+
+pub struct DormantDepositAccountsJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    accounts: DepositAccountRepo<E>,
+    ledger: DepositLedger,
+    dormant_after: chrono::Duration,
+    audit: Perms::Audit,
+}
+
+#[async_trait]
+impl<Perms, E> JobRunner for DormantDepositAccountsJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreDepositAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreDepositObject>,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    async fn run(
+        &self,
+        _current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let now = chrono::Utc::now();
+
+        let mut after = None;
+        loop {
+            let ret = self
+                .accounts
+                .list_for_status_by_created_at(
+                    DepositAccountStatus::Active,
+                    es_entity::PaginatedQueryArgs::<DepositAccountsByCreatedAtCursor> {
+                        first: 100,
+                        after,
+                    },
+                    es_entity::ListDirection::Ascending,
+                )
+                .await?;
+
+            for mut account in ret.entities.into_iter() {
+                let last_activity_at = self
+                    .ledger
+                    .last_activity_at(account.id)
+                    .await?
+                    .unwrap_or_else(|| account.created_at());
+                if !account.dormancy_needs_update(last_activity_at, self.dormant_after, now) {
+                    continue;
+                }
+
+                let mut db = self.accounts.begin_op().await?;
+                let audit_info = self
+                    .audit
+                    .record_system_entry_in_tx(
+                        db.tx(),
+                        CoreDepositObject::deposit_account(account.id),
+                        CoreDepositAction::DEPOSIT_ACCOUNT_UPDATE_DORMANCY,
+                    )
+                    .await?;
+                if account
+                    .update_dormancy(last_activity_at, self.dormant_after, now, audit_info)
+                    .did_execute()
+                {
+                    self.accounts.update_in_op(&mut db, &mut account).await?;
+                }
+                db.commit().await?;
+            }
+
+            if !ret.has_next_page {
+                break;
+            }
+            after = ret.end_cursor;
+        }
+
+        Ok(JobCompletion::RescheduleAt(now + chrono::Duration::days(1)))
+    }
+}
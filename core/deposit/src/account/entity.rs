@@ -5,7 +5,12 @@ use es_entity::*;
 
 use audit::AuditInfo;
 
-use crate::primitives::*;
+use crate::{
+    account::error::DepositAccountError,
+    config::{WithdrawalLimits, WithdrawalLimitsConfig},
+    ledger::WithdrawalLimitAccountIds,
+    primitives::*,
+};
 
 #[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -15,16 +20,41 @@ pub enum DepositAccountEvent {
         id: DepositAccountId,
         account_holder_id: DepositAccountHolderId,
         ledger_account_id: CalaAccountId,
+        withdrawal_limit_account_ids: WithdrawalLimitAccountIds,
         reference: String,
         name: String,
         description: String,
-        status: AccountStatus,
+        status: DepositAccountStatus,
+        deposit_account_type: DepositAccountType,
         audit_info: AuditInfo,
     },
     AccountStatusUpdated {
         status: AccountStatus,
         audit_info: AuditInfo,
     },
+    KycLevelUpdated {
+        kyc_level: KycLevel,
+        audit_info: AuditInfo,
+    },
+    WithdrawalLimitOverrideApplied {
+        withdrawal_limit_override_id: WithdrawalLimitOverrideId,
+        limits: WithdrawalLimits,
+        audit_info: AuditInfo,
+    },
+    MarkedDormant {
+        last_activity_at: chrono::DateTime<chrono::Utc>,
+        audit_info: AuditInfo,
+    },
+    DormancyCleared {
+        last_activity_at: chrono::DateTime<chrono::Utc>,
+        audit_info: AuditInfo,
+    },
+    Closed {
+        settlement: DepositAccountClosureSettlement,
+        settled_amount: UsdCents,
+        settlement_tx_id: Option<CalaTransactionId>,
+        audit_info: AuditInfo,
+    },
 }
 
 #[derive(EsEntity, Builder)]
@@ -35,7 +65,15 @@ pub struct DepositAccount {
     pub reference: String,
     pub name: String,
     pub description: String,
-    pub status: AccountStatus,
+    pub status: DepositAccountStatus,
+    pub deposit_account_type: DepositAccountType,
+    #[builder(default = "KycLevel::NotKyced")]
+    pub kyc_level: KycLevel,
+    withdrawal_limit_account_ids: WithdrawalLimitAccountIds,
+    #[builder(default)]
+    withdrawal_limit_override: Option<(WithdrawalLimitOverrideId, WithdrawalLimits)>,
+    #[builder(default)]
+    dormant_since_last_activity_at: Option<chrono::DateTime<chrono::Utc>>,
 
     events: EntityEvents<DepositAccountEvent>,
 }
@@ -52,15 +90,149 @@ impl DepositAccount {
         status: AccountStatus,
         audit_info: AuditInfo,
     ) -> Idempotent<()> {
+        if self.status.is_closed() {
+            return Idempotent::Ignored;
+        }
         idempotency_guard!(
             self.events.iter_all().rev(),
             DepositAccountEvent::AccountStatusUpdated { status: existing_status, .. } if existing_status == &status
         );
         self.events
             .push(DepositAccountEvent::AccountStatusUpdated { status, audit_info });
-        self.status = status;
+        self.status = status.into();
+        Idempotent::Executed(())
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.status.is_closed()
+    }
+
+    /// Closing is terminal: once closed the account never becomes active again.
+    pub fn close(
+        &mut self,
+        settlement: DepositAccountClosureSettlement,
+        settled_amount: UsdCents,
+        settlement_tx_id: Option<CalaTransactionId>,
+        audit_info: AuditInfo,
+    ) -> Result<Idempotent<()>, DepositAccountError> {
+        idempotency_guard!(self.events.iter_all(), DepositAccountEvent::Closed { .. });
+        if settlement == DepositAccountClosureSettlement::TransferTo(self.id) {
+            return Err(DepositAccountError::CannotTransferClosingBalanceToSelf);
+        }
+        self.events.push(DepositAccountEvent::Closed {
+            settlement,
+            settled_amount,
+            settlement_tx_id,
+            audit_info,
+        });
+        self.status = DepositAccountStatus::Closed;
+        Ok(Idempotent::Executed(()))
+    }
+
+    pub fn is_dormant(&self) -> bool {
+        self.dormant_since_last_activity_at.is_some()
+    }
+
+    /// Flags or clears dormancy depending on how long ago the last ledger
+    /// activity on the account happened.
+    pub fn update_dormancy(
+        &mut self,
+        last_activity_at: chrono::DateTime<chrono::Utc>,
+        dormant_after: chrono::Duration,
+        now: chrono::DateTime<chrono::Utc>,
+        audit_info: AuditInfo,
+    ) -> Idempotent<()> {
+        if !self.dormancy_needs_update(last_activity_at, dormant_after, now) {
+            return Idempotent::Ignored;
+        }
+        match self.dormant_since_last_activity_at {
+            None => {
+                self.events.push(DepositAccountEvent::MarkedDormant {
+                    last_activity_at,
+                    audit_info,
+                });
+                self.dormant_since_last_activity_at = Some(last_activity_at);
+                Idempotent::Executed(())
+            }
+            Some(_) => {
+                self.events.push(DepositAccountEvent::DormancyCleared {
+                    last_activity_at,
+                    audit_info,
+                });
+                self.dormant_since_last_activity_at = None;
+                Idempotent::Executed(())
+            }
+        }
+    }
+
+    pub(super) fn dormancy_needs_update(
+        &self,
+        last_activity_at: chrono::DateTime<chrono::Utc>,
+        dormant_after: chrono::Duration,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> bool {
+        if self.status.is_closed() {
+            return false;
+        }
+        match self.dormant_since_last_activity_at {
+            None => now - last_activity_at >= dormant_after,
+            Some(marked_at) => last_activity_at > marked_at,
+        }
+    }
+
+    pub fn update_kyc_level(
+        &mut self,
+        kyc_level: KycLevel,
+        audit_info: AuditInfo,
+    ) -> Idempotent<()> {
+        if self.kyc_level == kyc_level {
+            return Idempotent::Ignored;
+        }
+        self.events.push(DepositAccountEvent::KycLevelUpdated {
+            kyc_level,
+            audit_info,
+        });
+        self.kyc_level = kyc_level;
+        Idempotent::Executed(())
+    }
+
+    pub fn apply_withdrawal_limit_override(
+        &mut self,
+        withdrawal_limit_override_id: WithdrawalLimitOverrideId,
+        limits: WithdrawalLimits,
+        audit_info: AuditInfo,
+    ) -> Idempotent<()> {
+        idempotency_guard!(
+            self.events.iter_all().rev(),
+            DepositAccountEvent::WithdrawalLimitOverrideApplied { withdrawal_limit_override_id: id, .. }
+                if id == &withdrawal_limit_override_id
+        );
+        self.events
+            .push(DepositAccountEvent::WithdrawalLimitOverrideApplied {
+                withdrawal_limit_override_id,
+                limits,
+                audit_info,
+            });
+        self.withdrawal_limit_override = Some((withdrawal_limit_override_id, limits));
         Idempotent::Executed(())
     }
+
+    /// The ledger account new withdrawals are held on for the withdrawal limits
+    /// in effect.
+    pub(crate) fn withdrawal_limit_account_id(&self) -> CalaAccountId {
+        match (self.withdrawal_limit_override, self.kyc_level) {
+            (Some((id, _)), _) => id.into(),
+            (None, KycLevel::Advanced) => self.withdrawal_limit_account_ids.advanced,
+            (None, _) => self.withdrawal_limit_account_ids.basic,
+        }
+    }
+
+    pub fn withdrawal_limits(&self, config: &WithdrawalLimitsConfig) -> WithdrawalLimits {
+        match self.withdrawal_limit_override {
+            Some((_, limits)) => limits,
+            None => config.limits_for(self.kyc_level, self.deposit_account_type),
+        }
+    }
 }
 
 impl TryFromEvents<DepositAccountEvent> for DepositAccount {
@@ -71,22 +243,48 @@ impl TryFromEvents<DepositAccountEvent> for DepositAccount {
                 DepositAccountEvent::Initialized {
                     id,
                     account_holder_id,
+                    withdrawal_limit_account_ids,
                     reference,
                     name,
                     description,
                     status,
+                    deposit_account_type,
                     ..
                 } => {
                     builder = builder
                         .id(*id)
                         .account_holder_id(*account_holder_id)
+                        .withdrawal_limit_account_ids(*withdrawal_limit_account_ids)
                         .reference(reference.to_string())
                         .name(name.to_string())
                         .description(description.to_string())
                         .status(*status)
+                        .deposit_account_type(*deposit_account_type)
                 }
                 DepositAccountEvent::AccountStatusUpdated { status, .. } => {
-                    builder = builder.status(*status);
+                    builder = builder.status((*status).into());
+                }
+                DepositAccountEvent::KycLevelUpdated { kyc_level, .. } => {
+                    builder = builder.kyc_level(*kyc_level);
+                }
+                DepositAccountEvent::WithdrawalLimitOverrideApplied {
+                    withdrawal_limit_override_id,
+                    limits,
+                    ..
+                } => {
+                    builder = builder
+                        .withdrawal_limit_override(Some((*withdrawal_limit_override_id, *limits)));
+                }
+                DepositAccountEvent::MarkedDormant {
+                    last_activity_at, ..
+                } => {
+                    builder = builder.dormant_since_last_activity_at(Some(*last_activity_at));
+                }
+                DepositAccountEvent::DormancyCleared { .. } => {
+                    builder = builder.dormant_since_last_activity_at(None);
+                }
+                DepositAccountEvent::Closed { .. } => {
+                    builder = builder.status(DepositAccountStatus::Closed);
                 }
             }
         }
@@ -104,6 +302,8 @@ pub struct NewDepositAccount {
     pub(super) name: String,
     pub(super) description: String,
     pub(super) active: bool,
+    pub(super) deposit_account_type: DepositAccountType,
+    pub(super) withdrawal_limit_account_ids: WithdrawalLimitAccountIds,
     #[builder(setter(into))]
     pub audit_info: AuditInfo,
 }
@@ -112,26 +312,124 @@ impl NewDepositAccount {
     pub fn builder() -> NewDepositAccountBuilder {
         NewDepositAccountBuilder::default()
     }
+
+    pub(super) fn status(&self) -> DepositAccountStatus {
+        if self.active {
+            DepositAccountStatus::Active
+        } else {
+            DepositAccountStatus::Inactive
+        }
+    }
 }
 
 impl IntoEvents<DepositAccountEvent> for NewDepositAccount {
     fn into_events(self) -> EntityEvents<DepositAccountEvent> {
+        let status = self.status();
         EntityEvents::init(
             self.id,
             [DepositAccountEvent::Initialized {
                 id: self.id,
                 account_holder_id: self.account_holder_id,
                 ledger_account_id: self.id.into(),
+                withdrawal_limit_account_ids: self.withdrawal_limit_account_ids,
                 reference: self.reference,
                 name: self.name,
                 description: self.description,
-                status: if self.active {
-                    AccountStatus::Active
-                } else {
-                    AccountStatus::Inactive
-                },
+                status,
+                deposit_account_type: self.deposit_account_type,
                 audit_info: self.audit_info,
             }],
         )
     }
 }
+
+#[cfg(test)]
+mod test {
+    use audit::AuditEntryId;
+
+    use super::*;
+
+    fn dummy_audit_info() -> AuditInfo {
+        AuditInfo {
+            audit_entry_id: AuditEntryId::from(1),
+            sub: "sub".to_string(),
+        }
+    }
+
+    fn account() -> DepositAccount {
+        let new_account = NewDepositAccount::builder()
+            .id(DepositAccountId::new())
+            .account_holder_id(DepositAccountHolderId::new())
+            .reference("ref".to_string())
+            .name("name".to_string())
+            .description("description".to_string())
+            .active(true)
+            .deposit_account_type(DepositAccountType::Individual)
+            .withdrawal_limit_account_ids(WithdrawalLimitAccountIds::new())
+            .audit_info(dummy_audit_info())
+            .build()
+            .unwrap();
+        DepositAccount::try_from_events(new_account.into_events()).unwrap()
+    }
+
+    #[test]
+    fn dormancy_follows_last_activity() {
+        let mut account = account();
+        let now = chrono::Utc::now();
+        let dormant_after = chrono::Duration::days(30);
+
+        let recent = now - chrono::Duration::days(10);
+        assert!(account
+            .update_dormancy(recent, dormant_after, now, dummy_audit_info())
+            .was_ignored());
+
+        let old = now - chrono::Duration::days(40);
+        assert!(account
+            .update_dormancy(old, dormant_after, now, dummy_audit_info())
+            .did_execute());
+        assert!(account.is_dormant());
+        assert!(account
+            .update_dormancy(old, dormant_after, now, dummy_audit_info())
+            .was_ignored());
+
+        assert!(account
+            .update_dormancy(now, dormant_after, now, dummy_audit_info())
+            .did_execute());
+        assert!(!account.is_dormant());
+    }
+
+    #[test]
+    fn closed_account_stays_closed() {
+        let mut account = account();
+        assert!(account
+            .close(
+                DepositAccountClosureSettlement::PayOut,
+                UsdCents::ZERO,
+                None,
+                dummy_audit_info()
+            )
+            .unwrap()
+            .did_execute());
+        assert!(account.is_closed());
+
+        assert!(account
+            .update_account_status(AccountStatus::Active, dummy_audit_info())
+            .was_ignored());
+        assert!(account.is_closed());
+    }
+
+    #[test]
+    fn cannot_transfer_closing_balance_to_itself() {
+        let mut account = account();
+        let res = account.close(
+            DepositAccountClosureSettlement::TransferTo(account.id),
+            UsdCents::ZERO,
+            None,
+            dummy_audit_info(),
+        );
+        assert!(matches!(
+            res,
+            Err(DepositAccountError::CannotTransferClosingBalanceToSelf)
+        ));
+    }
+}
@@ -1,7 +1,9 @@
+mod dormancy_job;
 mod entity;
 pub mod error;
 mod repo;
 
+pub(crate) use dormancy_job::*;
 pub use entity::DepositAccount;
 pub(crate) use entity::*;
 pub(crate) use repo::*;
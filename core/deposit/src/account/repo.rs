@@ -5,7 +5,7 @@ use outbox::OutboxEventMarker;
 
 use crate::{
     event::CoreDepositEvent,
-    primitives::{DepositAccountHolderId, DepositAccountId},
+    primitives::{DepositAccountHolderId, DepositAccountId, DepositAccountStatus},
     publisher::DepositPublisher,
 };
 
@@ -15,7 +15,10 @@ use super::{entity::*, error::*};
 #[es_repo(
     entity = "DepositAccount",
     err = "DepositAccountError",
-    columns(account_holder_id(ty = "DepositAccountHolderId", list_for, update(persist = false))),
+    columns(
+        account_holder_id(ty = "DepositAccountHolderId", list_for, update(persist = false)),
+        status(ty = "DepositAccountStatus", list_for, create(accessor = "status()"))
+    ),
     tbl_prefix = "core",
     post_persist_hook = "publish"
 )]
@@ -51,6 +54,88 @@ where
         }
     }
 
+    /// Holds a lock on the account until the operation ends so that closing
+    /// the account and initiating a withdrawal from it don't interleave.
+    pub async fn lock_in_op(
+        &self,
+        op: &mut es_entity::DbOp<'_>,
+        id: DepositAccountId,
+    ) -> Result<(), DepositAccountError> {
+        sqlx::query!(
+            "SELECT id FROM core_deposit_accounts WHERE id = $1 FOR UPDATE",
+            id as DepositAccountId,
+        )
+        .fetch_one(&mut **op.tx())
+        .await?;
+        Ok(())
+    }
+
+    pub async fn list_open_for_account_holder_id_by_created_at(
+        &self,
+        account_holder_id: DepositAccountHolderId,
+        query: es_entity::PaginatedQueryArgs<
+            deposit_account_cursor::DepositAccountsByCreatedAtCursor,
+        >,
+        direction: es_entity::ListDirection,
+    ) -> Result<
+        es_entity::PaginatedQueryRet<
+            DepositAccount,
+            deposit_account_cursor::DepositAccountsByCreatedAtCursor,
+        >,
+        DepositAccountError,
+    > {
+        let es_entity::PaginatedQueryArgs { first, after } = query;
+        let (id, created_at) = match after {
+            Some(after) => (Some(after.id), Some(after.created_at)),
+            None => (None, None),
+        };
+        let (entities, has_next_page) = match direction {
+            es_entity::ListDirection::Ascending => {
+                es_query!(
+                    "core",
+                    self.pool(),
+                    r#"SELECT created_at, id FROM core_deposit_accounts
+                    WHERE account_holder_id = $1 AND status != $5
+                    AND COALESCE((created_at, id) > ($4, $3), $3 IS NULL)
+                    ORDER BY created_at ASC, id ASC LIMIT $2"#,
+                    account_holder_id as DepositAccountHolderId,
+                    (first + 1) as i64,
+                    id as Option<DepositAccountId>,
+                    created_at as Option<chrono::DateTime<chrono::Utc>>,
+                    DepositAccountStatus::Closed as DepositAccountStatus,
+                )
+                .fetch_n(first)
+                .await?
+            }
+            es_entity::ListDirection::Descending => {
+                es_query!(
+                    "core",
+                    self.pool(),
+                    r#"SELECT created_at, id FROM core_deposit_accounts
+                    WHERE account_holder_id = $1 AND status != $5
+                    AND COALESCE((created_at, id) < ($4, $3), $3 IS NULL)
+                    ORDER BY created_at DESC, id DESC LIMIT $2"#,
+                    account_holder_id as DepositAccountHolderId,
+                    (first + 1) as i64,
+                    id as Option<DepositAccountId>,
+                    created_at as Option<chrono::DateTime<chrono::Utc>>,
+                    DepositAccountStatus::Closed as DepositAccountStatus,
+                )
+                .fetch_n(first)
+                .await?
+            }
+        };
+
+        let end_cursor = entities
+            .last()
+            .map(deposit_account_cursor::DepositAccountsByCreatedAtCursor::from);
+        Ok(es_entity::PaginatedQueryRet {
+            entities,
+            has_next_page,
+            end_cursor,
+        })
+    }
+
     async fn publish(
         &self,
         db: &mut es_entity::DbOp<'_>,
@@ -62,3 +147,41 @@ where
             .await
     }
 }
+
+mod deposit_account_status_sqlx {
+    use sqlx::{postgres::*, Type};
+
+    use crate::primitives::DepositAccountStatus;
+
+    impl Type<Postgres> for DepositAccountStatus {
+        fn type_info() -> PgTypeInfo {
+            <String as Type<Postgres>>::type_info()
+        }
+
+        fn compatible(ty: &PgTypeInfo) -> bool {
+            <String as Type<Postgres>>::compatible(ty)
+        }
+    }
+
+    impl sqlx::Encode<'_, Postgres> for DepositAccountStatus {
+        fn encode_by_ref(
+            &self,
+            buf: &mut PgArgumentBuffer,
+        ) -> Result<sqlx::encode::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+            <String as sqlx::Encode<'_, Postgres>>::encode(self.to_string(), buf)
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for DepositAccountStatus {
+        fn decode(value: PgValueRef<'r>) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+            let s = <String as sqlx::Decode<Postgres>>::decode(value)?;
+            Ok(s.parse().map_err(|e: strum::ParseError| Box::new(e))?)
+        }
+    }
+
+    impl PgHasArrayType for DepositAccountStatus {
+        fn array_type_info() -> PgTypeInfo {
+            <String as sqlx::postgres::PgHasArrayType>::array_type_info()
+        }
+    }
+}
@@ -0,0 +1,290 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use es_entity::*;
+
+use audit::AuditInfo;
+
+use crate::{bank_statement_import::BankStatementLine, primitives::*};
+
+use super::error::BankStatementExceptionError;
+
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, strum::Display, strum::EnumString, Serialize, Deserialize,
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum BankStatementExceptionStatus {
+    Open,
+    Resolved,
+    Dismissed,
+}
+
+/// Why a statement line could not be matched to a deposit account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[serde(rename_all = "snake_case")]
+pub enum BankStatementExceptionReason {
+    NoMatchingAccount,
+    AmbiguousAccount,
+    AccountNotActive,
+}
+
+#[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[es_event(id = "BankStatementExceptionId")]
+pub enum BankStatementExceptionEvent {
+    Initialized {
+        id: BankStatementExceptionId,
+        bank_statement_import_id: BankStatementImportId,
+        reference: String,
+        line: BankStatementLine,
+        reason: BankStatementExceptionReason,
+        audit_info: AuditInfo,
+    },
+    Resolved {
+        deposit_account_id: DepositAccountId,
+        deposit_id: DepositId,
+        audit_info: AuditInfo,
+    },
+    Dismissed {
+        note: String,
+        audit_info: AuditInfo,
+    },
+}
+
+#[derive(EsEntity, Builder)]
+#[builder(pattern = "owned", build_fn(error = "EsEntityError"))]
+pub struct BankStatementException {
+    pub id: BankStatementExceptionId,
+    pub bank_statement_import_id: BankStatementImportId,
+    pub reference: String,
+    pub line: BankStatementLine,
+    pub reason: BankStatementExceptionReason,
+    #[builder(default)]
+    pub deposit_id: Option<DepositId>,
+
+    events: EntityEvents<BankStatementExceptionEvent>,
+}
+
+impl BankStatementException {
+    pub fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.events
+            .entity_first_persisted_at()
+            .expect("No events for bank statement exception")
+    }
+
+    pub fn status(&self) -> BankStatementExceptionStatus {
+        self.events
+            .iter_all()
+            .rev()
+            .find_map(|event| match event {
+                BankStatementExceptionEvent::Resolved { .. } => {
+                    Some(BankStatementExceptionStatus::Resolved)
+                }
+                BankStatementExceptionEvent::Dismissed { .. } => {
+                    Some(BankStatementExceptionStatus::Dismissed)
+                }
+                BankStatementExceptionEvent::Initialized { .. } => None,
+            })
+            .unwrap_or(BankStatementExceptionStatus::Open)
+    }
+
+    pub fn dismissal_note(&self) -> Option<&str> {
+        self.events.iter_all().find_map(|event| match event {
+            BankStatementExceptionEvent::Dismissed { note, .. } => Some(note.as_str()),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn resolve(
+        &mut self,
+        deposit_account_id: DepositAccountId,
+        deposit_id: DepositId,
+        audit_info: AuditInfo,
+    ) -> Result<Idempotent<()>, BankStatementExceptionError> {
+        match self.status() {
+            BankStatementExceptionStatus::Resolved => return Ok(Idempotent::Ignored),
+            BankStatementExceptionStatus::Dismissed => {
+                return Err(BankStatementExceptionError::AlreadyDismissed)
+            }
+            BankStatementExceptionStatus::Open => (),
+        }
+        self.events.push(BankStatementExceptionEvent::Resolved {
+            deposit_account_id,
+            deposit_id,
+            audit_info,
+        });
+        self.deposit_id = Some(deposit_id);
+        Ok(Idempotent::Executed(()))
+    }
+
+    pub(crate) fn dismiss(
+        &mut self,
+        note: String,
+        audit_info: AuditInfo,
+    ) -> Result<Idempotent<()>, BankStatementExceptionError> {
+        match self.status() {
+            BankStatementExceptionStatus::Dismissed => return Ok(Idempotent::Ignored),
+            BankStatementExceptionStatus::Resolved => {
+                return Err(BankStatementExceptionError::AlreadyResolved)
+            }
+            BankStatementExceptionStatus::Open => (),
+        }
+        self.events
+            .push(BankStatementExceptionEvent::Dismissed { note, audit_info });
+        Ok(Idempotent::Executed(()))
+    }
+}
+
+impl TryFromEvents<BankStatementExceptionEvent> for BankStatementException {
+    fn try_from_events(
+        events: EntityEvents<BankStatementExceptionEvent>,
+    ) -> Result<Self, EsEntityError> {
+        let mut builder = BankStatementExceptionBuilder::default();
+        for event in events.iter_all() {
+            match event {
+                BankStatementExceptionEvent::Initialized {
+                    id,
+                    bank_statement_import_id,
+                    reference,
+                    line,
+                    reason,
+                    ..
+                } => {
+                    builder = builder
+                        .id(*id)
+                        .bank_statement_import_id(*bank_statement_import_id)
+                        .reference(reference.clone())
+                        .line(line.clone())
+                        .reason(*reason)
+                }
+                BankStatementExceptionEvent::Resolved { deposit_id, .. } => {
+                    builder = builder.deposit_id(Some(*deposit_id))
+                }
+                BankStatementExceptionEvent::Dismissed { .. } => (),
+            }
+        }
+        builder.events(events).build()
+    }
+}
+
+#[derive(Debug, Builder)]
+pub struct NewBankStatementException {
+    #[builder(setter(into))]
+    pub(super) id: BankStatementExceptionId,
+    #[builder(setter(into))]
+    pub(super) bank_statement_import_id: BankStatementImportId,
+    #[builder(setter(into))]
+    pub(super) reference: String,
+    pub(super) line: BankStatementLine,
+    pub(super) reason: BankStatementExceptionReason,
+    #[builder(setter(into))]
+    pub audit_info: AuditInfo,
+}
+
+impl NewBankStatementException {
+    pub fn builder() -> NewBankStatementExceptionBuilder {
+        NewBankStatementExceptionBuilder::default()
+    }
+
+    pub(super) fn status(&self) -> BankStatementExceptionStatus {
+        BankStatementExceptionStatus::Open
+    }
+}
+
+impl IntoEvents<BankStatementExceptionEvent> for NewBankStatementException {
+    fn into_events(self) -> EntityEvents<BankStatementExceptionEvent> {
+        EntityEvents::init(
+            self.id,
+            [BankStatementExceptionEvent::Initialized {
+                id: self.id,
+                bank_statement_import_id: self.bank_statement_import_id,
+                reference: self.reference,
+                line: self.line,
+                reason: self.reason,
+                audit_info: self.audit_info,
+            }],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use audit::AuditEntryId;
+
+    use super::*;
+
+    fn dummy_audit_info() -> AuditInfo {
+        AuditInfo {
+            audit_entry_id: AuditEntryId::from(1),
+            sub: "sub".to_string(),
+        }
+    }
+
+    fn exception() -> BankStatementException {
+        let new_exception = NewBankStatementException::builder()
+            .id(BankStatementExceptionId::new())
+            .bank_statement_import_id(BankStatementImportId::new())
+            .reference("bank-statement:987654321:BK000111")
+            .line(BankStatementLine {
+                bank_reference: "BK000111".to_string(),
+                value_date: chrono::NaiveDate::from_ymd_opt(2025, 1, 2).unwrap(),
+                amount: UsdCents::from(100),
+                customer_reference: None,
+                account_number: None,
+                remittance_information: None,
+            })
+            .reason(BankStatementExceptionReason::NoMatchingAccount)
+            .audit_info(dummy_audit_info())
+            .build()
+            .unwrap();
+        BankStatementException::try_from_events(new_exception.into_events()).unwrap()
+    }
+
+    #[test]
+    fn resolve_is_idempotent_and_final() {
+        let mut exception = exception();
+        assert_eq!(exception.status(), BankStatementExceptionStatus::Open);
+
+        let deposit_id = DepositId::new();
+        assert!(exception
+            .resolve(DepositAccountId::new(), deposit_id, dummy_audit_info())
+            .unwrap()
+            .did_execute());
+        assert_eq!(exception.status(), BankStatementExceptionStatus::Resolved);
+        assert_eq!(exception.deposit_id, Some(deposit_id));
+        assert!(exception
+            .resolve(
+                DepositAccountId::new(),
+                DepositId::new(),
+                dummy_audit_info()
+            )
+            .unwrap()
+            .was_ignored());
+        assert!(matches!(
+            exception.dismiss("duplicate".to_string(), dummy_audit_info()),
+            Err(BankStatementExceptionError::AlreadyResolved)
+        ));
+    }
+
+    #[test]
+    fn dismissed_exceptions_cannot_be_resolved() {
+        let mut exception = exception();
+        assert!(exception
+            .dismiss("returned to sender".to_string(), dummy_audit_info())
+            .unwrap()
+            .did_execute());
+        assert_eq!(exception.status(), BankStatementExceptionStatus::Dismissed);
+        assert_eq!(exception.dismissal_note(), Some("returned to sender"));
+        assert!(matches!(
+            exception.resolve(
+                DepositAccountId::new(),
+                DepositId::new(),
+                dummy_audit_info()
+            ),
+            Err(BankStatementExceptionError::AlreadyDismissed)
+        ));
+    }
+}
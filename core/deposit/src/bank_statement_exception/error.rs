@@ -0,0 +1,17 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum BankStatementExceptionError {
+    #[error("BankStatementExceptionError - Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("BankStatementExceptionError - EsEntityError: {0}")]
+    EsEntityError(es_entity::EsEntityError),
+    #[error("BankStatementExceptionError - CursorDestructureError: {0}")]
+    CursorDestructureError(#[from] es_entity::CursorDestructureError),
+    #[error("BankStatementExceptionError - AlreadyResolved")]
+    AlreadyResolved,
+    #[error("BankStatementExceptionError - AlreadyDismissed")]
+    AlreadyDismissed,
+}
+
+es_entity::from_es_entity_error!(BankStatementExceptionError);
@@ -0,0 +1,10 @@
+mod entity;
+pub mod error;
+mod repo;
+
+pub(crate) use entity::*;
+pub use entity::{
+    BankStatementException, BankStatementExceptionReason, BankStatementExceptionStatus,
+};
+pub use repo::bank_statement_exception_cursor::BankStatementExceptionsByCreatedAtCursor;
+pub(crate) use repo::*;
@@ -0,0 +1,71 @@
+use sqlx::PgPool;
+
+use es_entity::*;
+
+use crate::primitives::{BankStatementExceptionId, BankStatementImportId};
+
+use super::{entity::*, error::*};
+
+#[derive(EsRepo, Clone)]
+#[es_repo(
+    entity = "BankStatementException",
+    err = "BankStatementExceptionError",
+    columns(
+        bank_statement_import_id(ty = "BankStatementImportId", list_for, update(persist = false)),
+        reference(ty = "String", update(persist = false)),
+        status(
+            ty = "BankStatementExceptionStatus",
+            list_for,
+            create(accessor = "status()"),
+            update(accessor = "status()")
+        ),
+    ),
+    tbl_prefix = "core"
+)]
+pub struct BankStatementExceptionRepo {
+    pool: PgPool,
+}
+
+impl BankStatementExceptionRepo {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
+
+mod bank_statement_exception_status_sqlx {
+    use sqlx::{postgres::*, Type};
+
+    use crate::bank_statement_exception::BankStatementExceptionStatus;
+
+    impl Type<Postgres> for BankStatementExceptionStatus {
+        fn type_info() -> PgTypeInfo {
+            <String as Type<Postgres>>::type_info()
+        }
+
+        fn compatible(ty: &PgTypeInfo) -> bool {
+            <String as Type<Postgres>>::compatible(ty)
+        }
+    }
+
+    impl sqlx::Encode<'_, Postgres> for BankStatementExceptionStatus {
+        fn encode_by_ref(
+            &self,
+            buf: &mut PgArgumentBuffer,
+        ) -> Result<sqlx::encode::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+            <String as sqlx::Encode<'_, Postgres>>::encode(self.to_string(), buf)
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for BankStatementExceptionStatus {
+        fn decode(value: PgValueRef<'r>) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+            let s = <String as sqlx::Decode<Postgres>>::decode(value)?;
+            Ok(s.parse().map_err(|e: strum::ParseError| Box::new(e))?)
+        }
+    }
+
+    impl PgHasArrayType for BankStatementExceptionStatus {
+        fn array_type_info() -> PgTypeInfo {
+            <String as sqlx::postgres::PgHasArrayType>::array_type_info()
+        }
+    }
+}
@@ -0,0 +1,277 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use es_entity::*;
+
+use audit::AuditInfo;
+
+use crate::primitives::*;
+
+use super::parser::{BankStatementCsvMapping, BankStatementFormat};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BankStatementImportSummary {
+    pub matched_lines: usize,
+    pub matched_amount: UsdCents,
+    pub exception_lines: usize,
+    pub duplicate_lines: usize,
+}
+
+#[allow(clippy::large_enum_variant)]
+#[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[es_event(id = "BankStatementImportId")]
+pub enum BankStatementImportEvent {
+    Initialized {
+        id: BankStatementImportId,
+        file_name: String,
+        format: BankStatementFormat,
+        csv_mapping: Option<BankStatementCsvMapping>,
+        statement_account: String,
+        audit_info: AuditInfo,
+    },
+    LineMatched {
+        reference: String,
+        deposit_account_id: DepositAccountId,
+        deposit_id: DepositId,
+        amount: UsdCents,
+    },
+    LineSentToExceptions {
+        reference: String,
+        bank_statement_exception_id: BankStatementExceptionId,
+    },
+    LineAlreadyImported {
+        reference: String,
+    },
+}
+
+#[derive(EsEntity, Builder)]
+#[builder(pattern = "owned", build_fn(error = "EsEntityError"))]
+pub struct BankStatementImport {
+    pub id: BankStatementImportId,
+    pub file_name: String,
+    pub format: BankStatementFormat,
+    pub statement_account: String,
+
+    events: EntityEvents<BankStatementImportEvent>,
+}
+
+impl BankStatementImport {
+    pub fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.events
+            .entity_first_persisted_at()
+            .expect("No events for bank statement import")
+    }
+
+    pub fn summary(&self) -> BankStatementImportSummary {
+        let mut summary = BankStatementImportSummary::default();
+        for event in self.events.iter_all() {
+            match event {
+                BankStatementImportEvent::LineMatched { amount, .. } => {
+                    summary.matched_lines += 1;
+                    summary.matched_amount += *amount;
+                }
+                BankStatementImportEvent::LineSentToExceptions { .. } => {
+                    summary.exception_lines += 1;
+                }
+                BankStatementImportEvent::LineAlreadyImported { .. } => {
+                    summary.duplicate_lines += 1;
+                }
+                BankStatementImportEvent::Initialized { .. } => (),
+            }
+        }
+        summary
+    }
+
+    pub fn exception_ids(&self) -> Vec<BankStatementExceptionId> {
+        self.events
+            .iter_all()
+            .filter_map(|event| match event {
+                BankStatementImportEvent::LineSentToExceptions {
+                    bank_statement_exception_id,
+                    ..
+                } => Some(*bank_statement_exception_id),
+                _ => None,
+            })
+            .collect()
+    }
+
+    fn line_recorded(&self, reference: &str) -> bool {
+        self.events.iter_all().any(|event| match event {
+            BankStatementImportEvent::LineMatched { reference: r, .. }
+            | BankStatementImportEvent::LineSentToExceptions { reference: r, .. }
+            | BankStatementImportEvent::LineAlreadyImported { reference: r } => r == reference,
+            BankStatementImportEvent::Initialized { .. } => false,
+        })
+    }
+
+    pub(crate) fn line_matched(
+        &mut self,
+        reference: String,
+        deposit_account_id: DepositAccountId,
+        deposit_id: DepositId,
+        amount: UsdCents,
+    ) -> Idempotent<()> {
+        if self.line_recorded(&reference) {
+            return Idempotent::Ignored;
+        }
+        self.events.push(BankStatementImportEvent::LineMatched {
+            reference,
+            deposit_account_id,
+            deposit_id,
+            amount,
+        });
+        Idempotent::Executed(())
+    }
+
+    pub(crate) fn line_sent_to_exceptions(
+        &mut self,
+        reference: String,
+        bank_statement_exception_id: BankStatementExceptionId,
+    ) -> Idempotent<()> {
+        if self.line_recorded(&reference) {
+            return Idempotent::Ignored;
+        }
+        self.events
+            .push(BankStatementImportEvent::LineSentToExceptions {
+                reference,
+                bank_statement_exception_id,
+            });
+        Idempotent::Executed(())
+    }
+
+    pub(crate) fn line_already_imported(&mut self, reference: String) -> Idempotent<()> {
+        if self.line_recorded(&reference) {
+            return Idempotent::Ignored;
+        }
+        self.events
+            .push(BankStatementImportEvent::LineAlreadyImported { reference });
+        Idempotent::Executed(())
+    }
+}
+
+impl TryFromEvents<BankStatementImportEvent> for BankStatementImport {
+    fn try_from_events(
+        events: EntityEvents<BankStatementImportEvent>,
+    ) -> Result<Self, EsEntityError> {
+        let mut builder = BankStatementImportBuilder::default();
+        for event in events.iter_all() {
+            match event {
+                BankStatementImportEvent::Initialized {
+                    id,
+                    file_name,
+                    format,
+                    statement_account,
+                    ..
+                } => {
+                    builder = builder
+                        .id(*id)
+                        .file_name(file_name.clone())
+                        .format(*format)
+                        .statement_account(statement_account.clone())
+                }
+                BankStatementImportEvent::LineMatched { .. } => (),
+                BankStatementImportEvent::LineSentToExceptions { .. } => (),
+                BankStatementImportEvent::LineAlreadyImported { .. } => (),
+            }
+        }
+        builder.events(events).build()
+    }
+}
+
+#[derive(Debug, Builder)]
+pub struct NewBankStatementImport {
+    #[builder(setter(into))]
+    pub(super) id: BankStatementImportId,
+    #[builder(setter(into))]
+    pub(super) file_name: String,
+    pub(super) format: BankStatementFormat,
+    #[builder(default)]
+    pub(super) csv_mapping: Option<BankStatementCsvMapping>,
+    #[builder(setter(into))]
+    pub(super) statement_account: String,
+    #[builder(setter(into))]
+    pub audit_info: AuditInfo,
+}
+
+impl NewBankStatementImport {
+    pub fn builder() -> NewBankStatementImportBuilder {
+        NewBankStatementImportBuilder::default()
+    }
+}
+
+impl IntoEvents<BankStatementImportEvent> for NewBankStatementImport {
+    fn into_events(self) -> EntityEvents<BankStatementImportEvent> {
+        EntityEvents::init(
+            self.id,
+            [BankStatementImportEvent::Initialized {
+                id: self.id,
+                file_name: self.file_name,
+                format: self.format,
+                csv_mapping: self.csv_mapping,
+                statement_account: self.statement_account,
+                audit_info: self.audit_info,
+            }],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use audit::AuditEntryId;
+
+    use super::*;
+
+    fn dummy_audit_info() -> AuditInfo {
+        AuditInfo {
+            audit_entry_id: AuditEntryId::from(1),
+            sub: "sub".to_string(),
+        }
+    }
+
+    fn import() -> BankStatementImport {
+        let new_import = NewBankStatementImport::builder()
+            .id(BankStatementImportId::new())
+            .file_name("statement.sta")
+            .format(BankStatementFormat::Mt940)
+            .statement_account("987654321")
+            .audit_info(dummy_audit_info())
+            .build()
+            .unwrap();
+        BankStatementImport::try_from_events(new_import.into_events()).unwrap()
+    }
+
+    #[test]
+    fn each_line_is_recorded_once() {
+        let mut import = import();
+        let account_id = DepositAccountId::new();
+        assert!(import
+            .line_matched(
+                "ref-1".to_string(),
+                account_id,
+                DepositId::new(),
+                UsdCents::from(100)
+            )
+            .did_execute());
+        assert!(import
+            .line_sent_to_exceptions("ref-1".to_string(), BankStatementExceptionId::new())
+            .was_ignored());
+        assert!(import
+            .line_sent_to_exceptions("ref-2".to_string(), BankStatementExceptionId::new())
+            .did_execute());
+        assert!(import
+            .line_already_imported("ref-3".to_string())
+            .did_execute());
+
+        assert_eq!(
+            import.summary(),
+            BankStatementImportSummary {
+                matched_lines: 1,
+                matched_amount: UsdCents::from(100),
+                exception_lines: 1,
+                duplicate_lines: 1,
+            }
+        );
+        assert_eq!(import.exception_ids().len(), 1);
+    }
+}
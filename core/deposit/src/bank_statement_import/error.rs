@@ -0,0 +1,37 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum BankStatementParseError {
+    #[error("BankStatementParseError - Csv: {0}")]
+    Csv(#[from] csv::Error),
+    #[error("BankStatementParseError - MissingCsvMapping")]
+    MissingCsvMapping,
+    #[error("BankStatementParseError - MissingColumn: {0}")]
+    MissingColumn(String),
+    #[error("BankStatementParseError - MissingField: {0}")]
+    MissingField(&'static str),
+    #[error("BankStatementParseError - MalformedLine: {0}")]
+    MalformedLine(String),
+    #[error("BankStatementParseError - InvalidAmount: {0}")]
+    InvalidAmount(String),
+    #[error("BankStatementParseError - InvalidDate: {0}")]
+    InvalidDate(String),
+    #[error("BankStatementParseError - UnsupportedCurrency: {0}")]
+    UnsupportedCurrency(String),
+    #[error("BankStatementParseError - MultipleStatementAccounts")]
+    MultipleStatementAccounts,
+}
+
+#[derive(Error, Debug)]
+pub enum BankStatementImportError {
+    #[error("BankStatementImportError - Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("BankStatementImportError - EsEntityError: {0}")]
+    EsEntityError(es_entity::EsEntityError),
+    #[error("BankStatementImportError - CursorDestructureError: {0}")]
+    CursorDestructureError(#[from] es_entity::CursorDestructureError),
+    #[error("BankStatementImportError - ParseError: {0}")]
+    ParseError(#[from] BankStatementParseError),
+}
+
+es_entity::from_es_entity_error!(BankStatementImportError);
@@ -0,0 +1,11 @@
+mod entity;
+pub mod error;
+mod parser;
+mod repo;
+
+pub(crate) use entity::*;
+pub use entity::{BankStatementImport, BankStatementImportSummary};
+pub(crate) use parser::*;
+pub use parser::{BankStatementCsvMapping, BankStatementFormat, BankStatementLine};
+pub use repo::bank_statement_import_cursor::BankStatementImportsByCreatedAtCursor;
+pub(crate) use repo::*;
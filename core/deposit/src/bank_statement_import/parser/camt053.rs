@@ -0,0 +1,216 @@
+use chrono::NaiveDate;
+
+use super::{
+    check_currency, parse_amount, to_usd_cents, xml, BankStatementLine, BankStatementParseError,
+    ParsedBankStatement,
+};
+
+const BOOKED: &str = "BOOK";
+const CREDIT: &str = "CRDT";
+const DEBIT: &str = "DBIT";
+const NOT_PROVIDED: &str = "NOTPROVIDED";
+
+/// Parses an ISO 20022 camt.053 bank-to-customer statement. Every `Stmt` in
+/// the document must belong to the same account. Only booked entries are
+/// considered and the first transaction detail of an entry provides its
+/// references.
+pub(super) fn parse(data: &str) -> Result<ParsedBankStatement, BankStatementParseError> {
+    let statements = xml::elements(data, "Stmt");
+    if statements.is_empty() {
+        return Err(BankStatementParseError::MissingField("Stmt"));
+    }
+
+    let mut statement_account: Option<String> = None;
+    let mut lines = Vec::new();
+    for statement in statements {
+        let statement = statement.content;
+        let account = xml::text(statement, &["Acct", "Id", "IBAN"])
+            .or_else(|| xml::text(statement, &["Acct", "Id", "Othr", "Id"]))
+            .ok_or(BankStatementParseError::MissingField("Acct/Id"))?;
+        match &statement_account {
+            Some(existing) if existing != &account => {
+                return Err(BankStatementParseError::MultipleStatementAccounts)
+            }
+            Some(_) => (),
+            None => statement_account = Some(account),
+        }
+        if let Some(currency) = xml::text(statement, &["Acct", "Ccy"]) {
+            check_currency(&currency)?;
+        }
+
+        let statement_id = xml::text(statement, &["Id"]).unwrap_or_default();
+        for (index, entry) in xml::elements(statement, "Ntry").into_iter().enumerate() {
+            let fallback_reference = format!("{statement_id}:{index}");
+            if let Some(line) = parse_entry(entry.content, fallback_reference)? {
+                lines.push(line);
+            }
+        }
+    }
+
+    Ok(ParsedBankStatement {
+        statement_account: statement_account.unwrap_or_default(),
+        lines,
+    })
+}
+
+fn parse_entry(
+    entry: &str,
+    fallback_reference: String,
+) -> Result<Option<BankStatementLine>, BankStatementParseError> {
+    let status = xml::text(entry, &["Sts", "Cd"]).or_else(|| xml::text(entry, &["Sts"]));
+    if status.as_deref() != Some(BOOKED) {
+        return Ok(None);
+    }
+
+    let reversal = xml::text(entry, &["RvslInd"]).as_deref() == Some("true");
+    let indicator = xml::text(entry, &["CdtDbtInd"])
+        .ok_or(BankStatementParseError::MissingField("CdtDbtInd"))?;
+    let credit = match indicator.as_str() {
+        CREDIT => !reversal,
+        DEBIT => reversal,
+        other => return Err(BankStatementParseError::MalformedLine(other.to_string())),
+    };
+    if !credit {
+        return Ok(None);
+    }
+
+    let amount = xml::first(entry, &["Amt"]).ok_or(BankStatementParseError::MissingField("Amt"))?;
+    if let Some(currency) = amount.attribute("Ccy") {
+        check_currency(currency)?;
+    }
+    let amount = to_usd_cents(parse_amount(&amount.text())?)?;
+
+    let value_date = ["ValDt", "BookgDt"]
+        .into_iter()
+        .find_map(|date| {
+            xml::text(entry, &[date, "Dt"]).or_else(|| xml::text(entry, &[date, "DtTm"]))
+        })
+        .ok_or(BankStatementParseError::MissingField("ValDt"))?;
+    let value_date = value_date
+        .get(0..10)
+        .and_then(|date| NaiveDate::parse_from_str(date, "%Y-%m-%d").ok())
+        .ok_or_else(|| BankStatementParseError::InvalidDate(value_date.clone()))?;
+
+    let details = xml::first(entry, &["NtryDtls", "TxDtls"]).map(|details| details.content);
+    let bank_reference = xml::text(entry, &["AcctSvcrRef"])
+        .or_else(|| details.and_then(|d| xml::text(d, &["Refs", "AcctSvcrRef"])))
+        .or_else(|| xml::text(entry, &["NtryRef"]))
+        .unwrap_or(fallback_reference);
+    let customer_reference = details
+        .and_then(|d| xml::text(d, &["Refs", "EndToEndId"]))
+        .filter(|reference| reference != NOT_PROVIDED);
+    let account_number = details.and_then(|d| {
+        xml::text(d, &["RltdPties", "CdtrAcct", "Id", "IBAN"])
+            .or_else(|| xml::text(d, &["RltdPties", "CdtrAcct", "Id", "Othr", "Id"]))
+    });
+    let remittance_information = details
+        .and_then(|d| xml::first(d, &["RmtInf"]))
+        .map(|remittance| {
+            xml::elements(remittance.content, "Ustrd")
+                .iter()
+                .map(|line| line.text())
+                .collect::<Vec<_>>()
+                .join(" ")
+        })
+        .filter(|text| !text.is_empty())
+        .or_else(|| xml::text(entry, &["AddtlNtryInf"]));
+
+    Ok(Some(BankStatementLine {
+        bank_reference,
+        value_date,
+        amount,
+        customer_reference,
+        account_number,
+        remittance_information,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::primitives::UsdCents;
+
+    use super::*;
+
+    const STATEMENT: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<Document xmlns="urn:iso:std:iso:20022:tech:xsd:camt.053.001.02">
+  <BkToCstmrStmt>
+    <Stmt>
+      <Id>STMT-2025-01-02</Id>
+      <Acct><Id><Othr><Id>987654321</Id></Othr></Id><Ccy>USD</Ccy></Acct>
+      <Ntry>
+        <Amt Ccy="USD">1500.00</Amt>
+        <CdtDbtInd>CRDT</CdtDbtInd>
+        <Sts><Cd>BOOK</Cd></Sts>
+        <ValDt><Dt>2025-01-02</Dt></ValDt>
+        <AcctSvcrRef>BK000111</AcctSvcrRef>
+        <NtryDtls><TxDtls>
+          <Refs><EndToEndId>E2E-1</EndToEndId></Refs>
+          <RltdPties><CdtrAcct><Id><Othr><Id>6a3e1b12-5b7e-4bd0-9b0a-6b3e3f0c9b11</Id></Othr></Id></CdtrAcct></RltdPties>
+          <RmtInf><Ustrd>Savings</Ustrd><Ustrd>top up</Ustrd></RmtInf>
+        </TxDtls></NtryDtls>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="USD">20.00</Amt>
+        <CdtDbtInd>DBIT</CdtDbtInd>
+        <Sts>BOOK</Sts>
+        <ValDt><Dt>2025-01-02</Dt></ValDt>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="USD">75.25</Amt>
+        <CdtDbtInd>CRDT</CdtDbtInd>
+        <Sts>PDNG</Sts>
+        <ValDt><Dt>2025-01-03</Dt></ValDt>
+      </Ntry>
+      <Ntry>
+        <Amt Ccy="USD">10.00</Amt>
+        <CdtDbtInd>CRDT</CdtDbtInd>
+        <Sts>BOOK</Sts>
+        <BookgDt><DtTm>2025-01-03T10:00:00</DtTm></BookgDt>
+        <NtryDtls><TxDtls><Refs><EndToEndId>NOTPROVIDED</EndToEndId></Refs></TxDtls></NtryDtls>
+        <AddtlNtryInf>Cash deposit</AddtlNtryInf>
+      </Ntry>
+    </Stmt>
+  </BkToCstmrStmt>
+</Document>"#;
+
+    #[test]
+    fn parses_booked_credits_only() {
+        let statement = parse(STATEMENT).unwrap();
+        assert_eq!(statement.statement_account, "987654321");
+        assert_eq!(statement.lines.len(), 2);
+
+        let first = &statement.lines[0];
+        assert_eq!(first.bank_reference, "BK000111");
+        assert_eq!(first.amount, UsdCents::from(150_000));
+        assert_eq!(first.customer_reference.as_deref(), Some("E2E-1"));
+        assert_eq!(
+            first.account_number.as_deref(),
+            Some("6a3e1b12-5b7e-4bd0-9b0a-6b3e3f0c9b11")
+        );
+        assert_eq!(
+            first.remittance_information.as_deref(),
+            Some("Savings top up")
+        );
+
+        let second = &statement.lines[1];
+        assert_eq!(second.bank_reference, "STMT-2025-01-02:3");
+        assert_eq!(second.customer_reference, None);
+        assert_eq!(
+            second.value_date,
+            NaiveDate::from_ymd_opt(2025, 1, 3).unwrap()
+        );
+        assert_eq!(
+            second.remittance_information.as_deref(),
+            Some("Cash deposit")
+        );
+    }
+
+    #[test]
+    fn rejects_other_currencies() {
+        let statement = STATEMENT.replace(r#"Ccy="USD">1500.00"#, r#"Ccy="EUR">1500.00"#);
+        assert!(matches!(
+            parse(&statement),
+            Err(BankStatementParseError::UnsupportedCurrency(c)) if c == "EUR"
+        ));
+    }
+}
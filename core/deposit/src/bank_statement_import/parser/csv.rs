@@ -0,0 +1,112 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+
+use std::str::FromStr;
+
+use super::{
+    non_empty, to_usd_cents, BankStatementCsvMapping, BankStatementLine, BankStatementParseError,
+    ParsedBankStatement,
+};
+
+pub(super) fn parse(
+    mapping: &BankStatementCsvMapping,
+    data: &str,
+) -> Result<ParsedBankStatement, BankStatementParseError> {
+    let mut reader = ::csv::ReaderBuilder::new()
+        .trim(::csv::Trim::All)
+        .from_reader(data.as_bytes());
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| {
+        headers
+            .iter()
+            .position(|header| header == name)
+            .ok_or_else(|| BankStatementParseError::MissingColumn(name.to_string()))
+    };
+    let optional_column = |name: &Option<String>| name.as_deref().map(column).transpose();
+
+    let bank_reference = column(&mapping.bank_reference_column)?;
+    let amount = column(&mapping.amount_column)?;
+    let value_date = column(&mapping.value_date_column)?;
+    let customer_reference = optional_column(&mapping.customer_reference_column)?;
+    let account_number = optional_column(&mapping.account_number_column)?;
+    let remittance_information = optional_column(&mapping.remittance_information_column)?;
+
+    let mut lines = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let field = |index: usize| record.get(index).unwrap_or_default();
+        let optional_field = |index: Option<usize>| index.and_then(|i| non_empty(field(i)));
+
+        let line_amount = Decimal::from_str(&field(amount).replace(',', ""))
+            .map_err(|_| BankStatementParseError::InvalidAmount(field(amount).to_string()))?;
+        if line_amount <= Decimal::ZERO {
+            continue;
+        }
+        let bank_reference = non_empty(field(bank_reference))
+            .ok_or(BankStatementParseError::MissingField("bank reference"))?;
+        let line_value_date = NaiveDate::parse_from_str(field(value_date), &mapping.date_format)
+            .map_err(|_| BankStatementParseError::InvalidDate(field(value_date).to_string()))?;
+
+        lines.push(BankStatementLine {
+            bank_reference,
+            value_date: line_value_date,
+            amount: to_usd_cents(line_amount)?,
+            customer_reference: optional_field(customer_reference),
+            account_number: optional_field(account_number),
+            remittance_information: optional_field(remittance_information),
+        });
+    }
+
+    Ok(ParsedBankStatement {
+        statement_account: mapping.statement_account.clone(),
+        lines,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::primitives::UsdCents;
+
+    use super::*;
+
+    fn mapping() -> BankStatementCsvMapping {
+        BankStatementCsvMapping {
+            statement_account: "987654321".to_string(),
+            bank_reference_column: "Transaction ID".to_string(),
+            amount_column: "Amount".to_string(),
+            value_date_column: "Date".to_string(),
+            date_format: "%m/%d/%Y".to_string(),
+            customer_reference_column: None,
+            account_number_column: None,
+            remittance_information_column: Some("Memo".to_string()),
+        }
+    }
+
+    #[test]
+    fn parses_positive_amounts_as_credits() {
+        let data = "Date,Transaction ID,Amount,Memo\n\
+                    01/02/2025,TX-1,1500.00,Deposit 6a3e1b12-5b7e-4bd0-9b0a-6b3e3f0c9b11\n\
+                    01/02/2025,TX-2,-20.00,Fee\n\
+                    01/03/2025,TX-3,\"1,000.5\",\n";
+        let statement = parse(&mapping(), data).unwrap();
+        assert_eq!(statement.statement_account, "987654321");
+        assert_eq!(statement.lines.len(), 2);
+        assert_eq!(statement.lines[0].bank_reference, "TX-1");
+        assert_eq!(statement.lines[0].amount, UsdCents::from(150_000));
+        assert_eq!(
+            statement.lines[0].value_date,
+            NaiveDate::from_ymd_opt(2025, 1, 2).unwrap()
+        );
+        assert_eq!(statement.lines[1].amount, UsdCents::from(100_050));
+        assert_eq!(statement.lines[1].remittance_information, None);
+    }
+
+    #[test]
+    fn reports_missing_columns() {
+        let data = "Date,Amount\n01/02/2025,1.00\n";
+        assert!(matches!(
+            parse(&mapping(), data),
+            Err(BankStatementParseError::MissingColumn(c)) if c == "Transaction ID"
+        ));
+    }
+}
@@ -0,0 +1,178 @@
+mod camt053;
+mod csv;
+mod mt940;
+mod xml;
+
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+
+use std::str::FromStr;
+
+use crate::primitives::UsdCents;
+
+use super::error::BankStatementParseError;
+
+const SUPPORTED_CURRENCY: &str = "USD";
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+pub enum BankStatementFormat {
+    Mt940,
+    Camt053,
+    Csv,
+}
+
+/// Describes which columns of a bank-specific CSV export hold the fields of a
+/// statement line. Positive amounts are credits, anything else is ignored.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BankStatementCsvMapping {
+    pub statement_account: String,
+    pub bank_reference_column: String,
+    pub amount_column: String,
+    pub value_date_column: String,
+    pub date_format: String,
+    pub customer_reference_column: Option<String>,
+    pub account_number_column: Option<String>,
+    pub remittance_information_column: Option<String>,
+}
+
+/// A credit booked on one of the bank's own accounts.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct BankStatementLine {
+    pub bank_reference: String,
+    pub value_date: NaiveDate,
+    pub amount: UsdCents,
+    pub customer_reference: Option<String>,
+    pub account_number: Option<String>,
+    pub remittance_information: Option<String>,
+}
+
+impl BankStatementLine {
+    /// Reference of the deposit recorded for this line. Deposit references are
+    /// unique so importing the same line twice never credits an account twice.
+    pub(crate) fn deposit_reference(&self, statement_account: &str) -> String {
+        format!(
+            "bank-statement:{}:{}",
+            statement_account, self.bank_reference
+        )
+    }
+
+    /// Ids quoted on the line, in order of precedence: the credited account
+    /// number, the customer reference and finally the free-text remittance
+    /// information. An id may identify a deposit account or its holder.
+    pub(crate) fn referenced_ids(&self) -> Vec<uuid::Uuid> {
+        let mut ids = Vec::new();
+        let fields = [
+            self.account_number.as_deref(),
+            self.customer_reference.as_deref(),
+            self.remittance_information.as_deref(),
+        ];
+        for token in fields
+            .into_iter()
+            .flatten()
+            .flat_map(|field| field.split(|c: char| !(c.is_ascii_alphanumeric() || c == '-')))
+        {
+            if let Ok(id) = token.parse::<uuid::Uuid>() {
+                if !ids.contains(&id) {
+                    ids.push(id);
+                }
+            }
+        }
+        ids
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ParsedBankStatement {
+    pub statement_account: String,
+    pub lines: Vec<BankStatementLine>,
+}
+
+pub fn parse_bank_statement(
+    format: BankStatementFormat,
+    csv_mapping: Option<&BankStatementCsvMapping>,
+    data: &str,
+) -> Result<ParsedBankStatement, BankStatementParseError> {
+    let statement = match format {
+        BankStatementFormat::Mt940 => mt940::parse(data)?,
+        BankStatementFormat::Camt053 => camt053::parse(data)?,
+        BankStatementFormat::Csv => {
+            let mapping = csv_mapping.ok_or(BankStatementParseError::MissingCsvMapping)?;
+            csv::parse(mapping, data)?
+        }
+    };
+    if statement.statement_account.is_empty() {
+        return Err(BankStatementParseError::MissingField("statement account"));
+    }
+    Ok(statement)
+}
+
+fn check_currency(currency: &str) -> Result<(), BankStatementParseError> {
+    if currency.eq_ignore_ascii_case(SUPPORTED_CURRENCY) {
+        Ok(())
+    } else {
+        Err(BankStatementParseError::UnsupportedCurrency(
+            currency.to_string(),
+        ))
+    }
+}
+
+fn parse_amount(amount: &str) -> Result<Decimal, BankStatementParseError> {
+    Decimal::from_str(&amount.trim().replace(',', "."))
+        .map_err(|_| BankStatementParseError::InvalidAmount(amount.to_string()))
+}
+
+fn to_usd_cents(amount: Decimal) -> Result<UsdCents, BankStatementParseError> {
+    UsdCents::try_from_usd(amount)
+        .map_err(|_| BankStatementParseError::InvalidAmount(amount.to_string()))
+}
+
+fn non_empty(value: &str) -> Option<String> {
+    let value = value.trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(
+        customer_reference: Option<&str>,
+        remittance_information: Option<&str>,
+    ) -> BankStatementLine {
+        BankStatementLine {
+            bank_reference: "REF1".to_string(),
+            value_date: NaiveDate::from_ymd_opt(2025, 1, 2).unwrap(),
+            amount: UsdCents::from(100),
+            customer_reference: customer_reference.map(str::to_string),
+            account_number: None,
+            remittance_information: remittance_information.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn referenced_ids_are_extracted_in_order() {
+        let first = uuid::Uuid::new_v4();
+        let second = uuid::Uuid::new_v4();
+        let line = line(
+            Some(&first.to_string()),
+            Some(&format!("Top up /{}/ and again {}", second, first)),
+        );
+        assert_eq!(line.referenced_ids(), vec![first, second]);
+    }
+
+    #[test]
+    fn lines_without_ids_reference_nothing() {
+        let line = line(Some("NONREF"), Some("salary january"));
+        assert!(line.referenced_ids().is_empty());
+    }
+
+    #[test]
+    fn csv_requires_a_mapping() {
+        assert!(matches!(
+            parse_bank_statement(BankStatementFormat::Csv, None, "a,b"),
+            Err(BankStatementParseError::MissingCsvMapping)
+        ));
+    }
+}
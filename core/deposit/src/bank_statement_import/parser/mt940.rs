@@ -0,0 +1,241 @@
+use chrono::NaiveDate;
+
+use super::{
+    check_currency, non_empty, parse_amount, to_usd_cents, BankStatementLine,
+    BankStatementParseError, ParsedBankStatement,
+};
+
+/// Parses a SWIFT MT940 customer statement. Only the tags needed for
+/// reconciliation are interpreted: `:20:`, `:25:`, `:28C:`, `:60F:`/`:60M:`,
+/// `:61:` and `:86:`.
+pub(super) fn parse(data: &str) -> Result<ParsedBankStatement, BankStatementParseError> {
+    let mut transaction_reference = String::new();
+    let mut statement_number = String::new();
+    let mut statement_account = String::new();
+    let mut lines = Vec::new();
+    let mut entry_count = 0;
+    let mut pending: Option<PendingLine> = None;
+
+    for (tag, value) in fields(data) {
+        match tag.as_str() {
+            "20" => transaction_reference = value.trim().to_string(),
+            "25" => statement_account = value.trim().to_string(),
+            "28C" => statement_number = value.trim().to_string(),
+            "60F" | "60M" => {
+                let currency = value
+                    .get(7..10)
+                    .ok_or_else(|| BankStatementParseError::MalformedLine(value.clone()))?;
+                check_currency(currency)?;
+            }
+            "61" => {
+                if let Some(line) = pending.take() {
+                    lines.extend(line.into_credit());
+                }
+                let fallback_reference =
+                    format!("{transaction_reference}:{statement_number}:{entry_count}");
+                entry_count += 1;
+                pending = Some(parse_statement_line(&value, fallback_reference)?);
+            }
+            "86" => {
+                if let Some(pending) = pending.as_mut() {
+                    pending.line.remittance_information = non_empty(&value.replace('\n', " "));
+                }
+            }
+            _ => {
+                if let Some(line) = pending.take() {
+                    lines.extend(line.into_credit());
+                }
+            }
+        }
+    }
+    if let Some(line) = pending.take() {
+        lines.extend(line.into_credit());
+    }
+
+    Ok(ParsedBankStatement {
+        statement_account,
+        lines,
+    })
+}
+
+struct PendingLine {
+    credit: bool,
+    line: BankStatementLine,
+}
+
+impl PendingLine {
+    fn into_credit(self) -> Option<BankStatementLine> {
+        self.credit.then_some(self.line)
+    }
+}
+
+/// Splits the message into `(tag, value)` pairs, joining continuation lines.
+fn fields(data: &str) -> Vec<(String, String)> {
+    let mut fields: Vec<(String, String)> = Vec::new();
+    for raw in data.lines() {
+        let raw = raw.trim_end_matches('\r');
+        if raw.starts_with('{') || raw.starts_with("-}") || raw == "-" {
+            continue;
+        }
+        if let Some(rest) = raw.strip_prefix(':') {
+            if let Some((tag, value)) = rest.split_once(':') {
+                fields.push((tag.to_string(), value.to_string()));
+                continue;
+            }
+        }
+        if let Some((_, value)) = fields.last_mut() {
+            value.push('\n');
+            value.push_str(raw);
+        }
+    }
+    fields
+}
+
+/// `:61:` layout: value date (YYMMDD), optional entry date (MMDD), debit/credit
+/// mark, optional funds code, amount, transaction type (4 chars), customer
+/// reference, optional `//` bank reference and supplementary details.
+fn parse_statement_line(
+    value: &str,
+    fallback_reference: String,
+) -> Result<PendingLine, BankStatementParseError> {
+    let malformed = || BankStatementParseError::MalformedLine(value.to_string());
+    let (first_line, _) = value.split_once('\n').unwrap_or((value, ""));
+
+    let value_date = first_line.get(0..6).ok_or_else(malformed)?;
+    let value_date = NaiveDate::parse_from_str(&format!("20{value_date}"), "%Y%m%d")
+        .map_err(|_| BankStatementParseError::InvalidDate(value_date.to_string()))?;
+
+    let mut rest = &first_line[6..];
+    if rest
+        .get(..4)
+        .is_some_and(|date| date.chars().all(|c| c.is_ascii_digit()))
+    {
+        rest = &rest[4..];
+    }
+
+    let (credit, mark_len) = if rest.starts_with("RC") {
+        (false, 2)
+    } else if rest.starts_with("RD") {
+        (true, 2)
+    } else if rest.starts_with('C') {
+        (true, 1)
+    } else if rest.starts_with('D') {
+        (false, 1)
+    } else {
+        return Err(malformed());
+    };
+    rest = &rest[mark_len..];
+    if rest.starts_with(|c: char| c.is_ascii_alphabetic()) {
+        rest = &rest[1..];
+    }
+
+    let amount_len = rest
+        .find(|c: char| !(c.is_ascii_digit() || c == ','))
+        .ok_or_else(malformed)?;
+    let amount = to_usd_cents(parse_amount(&rest[..amount_len])?)?;
+    rest = &rest[amount_len..];
+
+    let references = rest.get(4..).ok_or_else(malformed)?;
+    let (customer_reference, bank_reference) = match references.split_once("//") {
+        Some((customer, bank)) => (customer, non_empty(bank)),
+        None => (references, None),
+    };
+    let customer_reference = non_empty(customer_reference).filter(|r| r != "NONREF");
+    // The customer reference is chosen by the sender and may repeat, so it
+    // can't identify the line.
+    let bank_reference = bank_reference.unwrap_or(fallback_reference);
+
+    Ok(PendingLine {
+        credit,
+        line: BankStatementLine {
+            bank_reference,
+            value_date,
+            amount,
+            customer_reference,
+            account_number: None,
+            remittance_information: None,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::primitives::UsdCents;
+
+    use super::*;
+
+    const STATEMENT: &str = "{1:F01BANKUS33AXXX0000000000}{2:I940BANKUS33XXXXN}{4:
+:20:STMT20250102
+:25:021000021/987654321
+:28C:00001/001
+:60F:C250101USD1000,00
+:61:2501020102C1500,00NTRFINV-7781//BK000111
+:86:Deposit for 6a3e1b12-5b7e-4bd0-9b0a-6b3e3f0c9b11
+ thank you
+:61:2501020102D200,00NTRFNONREF//BK000112
+:86:Wire fee
+:61:250103C25,5NMSCNONREF
+:62F:C250103USD2325,50
+-}";
+
+    #[test]
+    fn parses_credits_only() {
+        let statement = parse(STATEMENT).unwrap();
+        assert_eq!(statement.statement_account, "021000021/987654321");
+        assert_eq!(statement.lines.len(), 2);
+
+        let first = &statement.lines[0];
+        assert_eq!(first.bank_reference, "BK000111");
+        assert_eq!(first.customer_reference.as_deref(), Some("INV-7781"));
+        assert_eq!(first.amount, UsdCents::from(150_000));
+        assert_eq!(
+            first.value_date,
+            NaiveDate::from_ymd_opt(2025, 1, 2).unwrap()
+        );
+        assert_eq!(
+            first.remittance_information.as_deref(),
+            Some("Deposit for 6a3e1b12-5b7e-4bd0-9b0a-6b3e3f0c9b11  thank you")
+        );
+
+        let second = &statement.lines[1];
+        assert_eq!(second.amount, UsdCents::from(2_550));
+        assert_eq!(second.customer_reference, None);
+        assert_eq!(second.bank_reference, "STMT20250102:00001/001:2");
+    }
+
+    #[test]
+    fn lines_without_bank_reference_are_identified_by_position() {
+        let statement = parse(
+            ":20:STMT20250102
+:28C:00001/001
+:60F:C250101USD0,00
+:61:2501020102C100,00NTRFINV-7781
+:61:2501020102C100,00NTRFINV-7781
+:62F:C250102USD200,00",
+        )
+        .unwrap();
+
+        assert_eq!(statement.lines.len(), 2);
+        assert_eq!(
+            statement.lines[0].bank_reference,
+            "STMT20250102:00001/001:0"
+        );
+        assert_eq!(
+            statement.lines[1].bank_reference,
+            "STMT20250102:00001/001:1"
+        );
+        assert!(statement
+            .lines
+            .iter()
+            .all(|l| l.customer_reference.as_deref() == Some("INV-7781")));
+    }
+
+    #[test]
+    fn rejects_other_currencies() {
+        let statement = STATEMENT.replace("C250101USD", "C250101EUR");
+        assert!(matches!(
+            parse(&statement),
+            Err(BankStatementParseError::UnsupportedCurrency(c)) if c == "EUR"
+        ));
+    }
+}
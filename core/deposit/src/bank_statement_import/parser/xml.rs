@@ -0,0 +1,179 @@
+//! Just enough XML reading for ISO 20022 statements: elements are looked up
+//! by local name (namespace prefixes are ignored) and nested elements with the
+//! same name are handled.
+
+#[derive(Debug, Clone, Copy)]
+pub(super) struct Element<'a> {
+    attributes: &'a str,
+    pub content: &'a str,
+}
+
+impl<'a> Element<'a> {
+    pub fn attribute(&self, name: &str) -> Option<&'a str> {
+        let mut rest = self.attributes;
+        while let Some(eq) = rest.find('=') {
+            let key = rest[..eq].trim();
+            let value = rest[eq + 1..].trim_start();
+            let quote = value.chars().next()?;
+            let value = &value[1..];
+            let end = value.find(quote)?;
+            if local_name(key) == name {
+                return Some(&value[..end]);
+            }
+            rest = &value[end + 1..];
+        }
+        None
+    }
+
+    pub fn text(&self) -> String {
+        unescape(self.content.trim())
+    }
+}
+
+#[derive(PartialEq)]
+enum TagKind {
+    Open,
+    Close,
+    Empty,
+}
+
+struct Tag<'a> {
+    kind: TagKind,
+    name: &'a str,
+    attributes: &'a str,
+    start: usize,
+    end: usize,
+}
+
+fn local_name(name: &str) -> &str {
+    name.rsplit(':').next().unwrap_or(name)
+}
+
+fn tags(xml: &str) -> impl Iterator<Item = Tag<'_>> {
+    let mut pos = 0;
+    std::iter::from_fn(move || loop {
+        let start = pos + xml[pos..].find('<')?;
+        let rest = &xml[start..];
+        let skip_to = |terminator: &str| rest.find(terminator).map(|i| i + terminator.len());
+        if rest.starts_with("<!--") {
+            pos = start + skip_to("-->")?;
+            continue;
+        }
+        if rest.starts_with("<![CDATA[") {
+            pos = start + skip_to("]]>")?;
+            continue;
+        }
+        if rest.starts_with("<?") || rest.starts_with("<!") {
+            pos = start + skip_to(">")?;
+            continue;
+        }
+        let end = start + skip_to(">")?;
+        pos = end;
+        let inner = &xml[start + 1..end - 1];
+        let (kind, inner) = if let Some(inner) = inner.strip_prefix('/') {
+            (TagKind::Close, inner)
+        } else if let Some(inner) = inner.strip_suffix('/') {
+            (TagKind::Empty, inner)
+        } else {
+            (TagKind::Open, inner)
+        };
+        let name_end = inner
+            .find(|c: char| c.is_whitespace())
+            .unwrap_or(inner.len());
+        return Some(Tag {
+            kind,
+            name: local_name(&inner[..name_end]),
+            attributes: &inner[name_end..],
+            start,
+            end,
+        });
+    })
+}
+
+/// All outermost elements called `name`, in document order.
+pub(super) fn elements<'a>(xml: &'a str, name: &str) -> Vec<Element<'a>> {
+    let mut found = Vec::new();
+    let mut depth = 0;
+    let mut current: Option<(&str, usize)> = None;
+    for tag in tags(xml).filter(|tag| tag.name == name) {
+        match tag.kind {
+            TagKind::Empty if depth == 0 => found.push(Element {
+                attributes: tag.attributes,
+                content: "",
+            }),
+            TagKind::Empty => (),
+            TagKind::Open => {
+                if depth == 0 {
+                    current = Some((tag.attributes, tag.end));
+                }
+                depth += 1;
+            }
+            TagKind::Close => {
+                if depth == 0 {
+                    continue;
+                }
+                depth -= 1;
+                if depth == 0 {
+                    if let Some((attributes, content_start)) = current.take() {
+                        found.push(Element {
+                            attributes,
+                            content: &xml[content_start..tag.start],
+                        });
+                    }
+                }
+            }
+        }
+    }
+    found
+}
+
+/// The first element reached by following `path` from `xml`.
+pub(super) fn first<'a>(xml: &'a str, path: &[&str]) -> Option<Element<'a>> {
+    let (name, rest) = path.split_first()?;
+    let element = elements(xml, name).into_iter().next()?;
+    if rest.is_empty() {
+        Some(element)
+    } else {
+        first(element.content, rest)
+    }
+}
+
+/// Trimmed, unescaped text of the element at `path`, if it is not empty.
+pub(super) fn text(xml: &str, path: &[&str]) -> Option<String> {
+    first(xml, path)
+        .map(|element| element.text())
+        .filter(|text| !text.is_empty())
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn nested_elements_with_the_same_name() {
+        let xml = r#"<Acct><Id><Othr><Id>123</Id></Othr></Id><Ccy>USD</Ccy></Acct>"#;
+        assert_eq!(
+            text(xml, &["Acct", "Id", "Othr", "Id"]).as_deref(),
+            Some("123")
+        );
+        assert_eq!(text(xml, &["Acct", "Ccy"]).as_deref(), Some("USD"));
+    }
+
+    #[test]
+    fn attributes_prefixes_and_entities() {
+        let xml = r#"<?xml version="1.0"?><!-- note --><ns:Amt Ccy="USD">10.00</ns:Amt><Ustrd>A &amp; B</Ustrd><Empty/>"#;
+        let amount = first(xml, &["Amt"]).unwrap();
+        assert_eq!(amount.attribute("Ccy"), Some("USD"));
+        assert_eq!(amount.text(), "10.00");
+        assert_eq!(text(xml, &["Ustrd"]).as_deref(), Some("A & B"));
+        assert_eq!(text(xml, &["Empty"]), None);
+    }
+}
@@ -0,0 +1,24 @@
+use sqlx::PgPool;
+
+use es_entity::*;
+
+use crate::primitives::BankStatementImportId;
+
+use super::{entity::*, error::*};
+
+#[derive(EsRepo, Clone)]
+#[es_repo(
+    entity = "BankStatementImport",
+    err = "BankStatementImportError",
+    columns(statement_account(ty = "String", update(persist = false))),
+    tbl_prefix = "core"
+)]
+pub struct BankStatementImportRepo {
+    pool: PgPool,
+}
+
+impl BankStatementImportRepo {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
@@ -0,0 +1,300 @@
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use es_entity::*;
+
+use audit::AuditInfo;
+
+use crate::primitives::*;
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+pub enum BeneficiaryStatus {
+    PendingApproval,
+    /// Approved, but the cooling-off period has not elapsed yet.
+    CoolingOff,
+    Verified,
+    Denied,
+    Revoked,
+}
+
+#[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[es_event(id = "BeneficiaryId")]
+pub enum BeneficiaryEvent {
+    Initialized {
+        id: BeneficiaryId,
+        deposit_account_holder_id: DepositAccountHolderId,
+        label: String,
+        details: BeneficiaryDetails,
+        approval_process_id: ApprovalProcessId,
+        cooling_off_until: DateTime<Utc>,
+        audit_info: AuditInfo,
+    },
+    ApprovalProcessConcluded {
+        approval_process_id: ApprovalProcessId,
+        approved: bool,
+        audit_info: AuditInfo,
+    },
+    Revoked {
+        audit_info: AuditInfo,
+    },
+}
+
+#[derive(EsEntity, Builder)]
+#[builder(pattern = "owned", build_fn(error = "EsEntityError"))]
+pub struct Beneficiary {
+    pub id: BeneficiaryId,
+    pub deposit_account_holder_id: DepositAccountHolderId,
+    pub label: String,
+    pub details: BeneficiaryDetails,
+    pub approval_process_id: ApprovalProcessId,
+    pub cooling_off_until: DateTime<Utc>,
+
+    events: EntityEvents<BeneficiaryEvent>,
+}
+
+impl Beneficiary {
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.events
+            .entity_first_persisted_at()
+            .expect("No events for beneficiary")
+    }
+
+    pub fn is_approved_or_denied(&self) -> Option<bool> {
+        self.events.iter_all().find_map(|e| {
+            if let BeneficiaryEvent::ApprovalProcessConcluded { approved, .. } = e {
+                Some(*approved)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn is_revoked(&self) -> bool {
+        self.events
+            .iter_all()
+            .any(|e| matches!(e, BeneficiaryEvent::Revoked { .. }))
+    }
+
+    pub fn status(&self, now: DateTime<Utc>) -> BeneficiaryStatus {
+        if self.is_revoked() {
+            return BeneficiaryStatus::Revoked;
+        }
+        match self.is_approved_or_denied() {
+            Some(true) if now < self.cooling_off_until => BeneficiaryStatus::CoolingOff,
+            Some(true) => BeneficiaryStatus::Verified,
+            Some(false) => BeneficiaryStatus::Denied,
+            None => BeneficiaryStatus::PendingApproval,
+        }
+    }
+
+    pub fn is_verified(&self, now: DateTime<Utc>) -> bool {
+        self.status(now) == BeneficiaryStatus::Verified
+    }
+
+    pub fn approval_process_concluded(
+        &mut self,
+        approved: bool,
+        audit_info: AuditInfo,
+    ) -> Idempotent<()> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            BeneficiaryEvent::ApprovalProcessConcluded { .. }
+        );
+        self.events
+            .push(BeneficiaryEvent::ApprovalProcessConcluded {
+                approval_process_id: self.approval_process_id,
+                approved,
+                audit_info,
+            });
+        Idempotent::Executed(())
+    }
+
+    pub fn revoke(&mut self, audit_info: AuditInfo) -> Idempotent<()> {
+        idempotency_guard!(self.events.iter_all(), BeneficiaryEvent::Revoked { .. });
+        self.events.push(BeneficiaryEvent::Revoked { audit_info });
+        Idempotent::Executed(())
+    }
+}
+
+impl TryFromEvents<BeneficiaryEvent> for Beneficiary {
+    fn try_from_events(events: EntityEvents<BeneficiaryEvent>) -> Result<Self, EsEntityError> {
+        let mut builder = BeneficiaryBuilder::default();
+        for event in events.iter_all() {
+            match event {
+                BeneficiaryEvent::Initialized {
+                    id,
+                    deposit_account_holder_id,
+                    label,
+                    details,
+                    approval_process_id,
+                    cooling_off_until,
+                    ..
+                } => {
+                    builder = builder
+                        .id(*id)
+                        .deposit_account_holder_id(*deposit_account_holder_id)
+                        .label(label.clone())
+                        .details(details.clone())
+                        .approval_process_id(*approval_process_id)
+                        .cooling_off_until(*cooling_off_until)
+                }
+                BeneficiaryEvent::ApprovalProcessConcluded { .. } => (),
+                BeneficiaryEvent::Revoked { .. } => (),
+            }
+        }
+        builder.events(events).build()
+    }
+}
+
+#[derive(Debug, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct NewBeneficiary {
+    #[builder(setter(into))]
+    pub(super) id: BeneficiaryId,
+    #[builder(setter(into))]
+    pub(super) deposit_account_holder_id: DepositAccountHolderId,
+    #[builder(setter(into))]
+    pub(super) label: String,
+    pub(super) details: BeneficiaryDetails,
+    #[builder(setter(into))]
+    pub(super) approval_process_id: ApprovalProcessId,
+    pub(super) cooling_off_until: DateTime<Utc>,
+    #[builder(setter(into))]
+    pub audit_info: AuditInfo,
+}
+
+impl NewBeneficiary {
+    pub fn builder() -> NewBeneficiaryBuilder {
+        NewBeneficiaryBuilder::default()
+    }
+}
+
+impl NewBeneficiaryBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match &self.label {
+            Some(label) if label.trim().is_empty() => {
+                return Err("Beneficiary label cannot be empty".to_string())
+            }
+            _ => (),
+        }
+        match &self.details {
+            Some(details) if !details.is_valid() => {
+                Err("Beneficiary details are incomplete or invalid".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl IntoEvents<BeneficiaryEvent> for NewBeneficiary {
+    fn into_events(self) -> EntityEvents<BeneficiaryEvent> {
+        EntityEvents::init(
+            self.id,
+            [BeneficiaryEvent::Initialized {
+                id: self.id,
+                deposit_account_holder_id: self.deposit_account_holder_id,
+                label: self.label,
+                details: self.details,
+                approval_process_id: self.approval_process_id,
+                cooling_off_until: self.cooling_off_until,
+                audit_info: self.audit_info,
+            }],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use audit::AuditEntryId;
+
+    use super::*;
+
+    fn dummy_audit_info() -> AuditInfo {
+        AuditInfo {
+            audit_entry_id: AuditEntryId::from(1),
+            sub: "sub".to_string(),
+        }
+    }
+
+    fn bank_account(routing_number: &str) -> BeneficiaryDetails {
+        BeneficiaryDetails::BankAccount(PayoutDestination {
+            account_holder_name: "Jane Doe".to_string(),
+            routing_number: routing_number.to_string(),
+            account_number: "000123456789".to_string(),
+            account_type: PayoutAccountType::Checking,
+        })
+    }
+
+    fn new_beneficiary(
+        details: BeneficiaryDetails,
+        cooling_off_until: DateTime<Utc>,
+    ) -> Result<NewBeneficiary, String> {
+        let id = BeneficiaryId::new();
+        NewBeneficiary::builder()
+            .id(id)
+            .deposit_account_holder_id(DepositAccountHolderId::new())
+            .label("savings at other bank")
+            .details(details)
+            .approval_process_id(id)
+            .cooling_off_until(cooling_off_until)
+            .audit_info(dummy_audit_info())
+            .build()
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn details_must_be_valid() {
+        let now = Utc::now();
+        assert!(new_beneficiary(bank_account("123456789"), now).is_err());
+        assert!(new_beneficiary(bank_account("021000021"), now).is_ok());
+        assert!(new_beneficiary(
+            BeneficiaryDetails::CryptoAddress {
+                network: "bitcoin".to_string(),
+                address: " ".to_string(),
+            },
+            now
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn verified_only_after_approval_and_cooling_off() {
+        let now = Utc::now();
+        let cooling_off_until = now + chrono::Duration::hours(24);
+        let new_beneficiary =
+            new_beneficiary(bank_account("021000021"), cooling_off_until).unwrap();
+        let mut beneficiary = Beneficiary::try_from_events(new_beneficiary.into_events()).unwrap();
+        assert_eq!(beneficiary.status(now), BeneficiaryStatus::PendingApproval);
+
+        assert!(beneficiary
+            .approval_process_concluded(true, dummy_audit_info())
+            .did_execute());
+        assert_eq!(beneficiary.status(now), BeneficiaryStatus::CoolingOff);
+        assert!(!beneficiary.is_verified(now));
+        assert!(beneficiary.is_verified(cooling_off_until));
+
+        assert!(beneficiary.revoke(dummy_audit_info()).did_execute());
+        assert_eq!(
+            beneficiary.status(cooling_off_until),
+            BeneficiaryStatus::Revoked
+        );
+        assert!(beneficiary.revoke(dummy_audit_info()).was_ignored());
+    }
+
+    #[test]
+    fn denied_beneficiary_is_never_verified() {
+        let now = Utc::now();
+        let new_beneficiary = new_beneficiary(bank_account("021000021"), now).unwrap();
+        let mut beneficiary = Beneficiary::try_from_events(new_beneficiary.into_events()).unwrap();
+        assert!(beneficiary
+            .approval_process_concluded(false, dummy_audit_info())
+            .did_execute());
+        assert_eq!(beneficiary.status(now), BeneficiaryStatus::Denied);
+        assert!(beneficiary
+            .approval_process_concluded(true, dummy_audit_info())
+            .was_ignored());
+    }
+}
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+use crate::primitives::BeneficiaryId;
+
+#[derive(Error, Debug)]
+pub enum BeneficiaryError {
+    #[error("BeneficiaryError - Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("BeneficiaryError - EsEntityError: {0}")]
+    EsEntityError(es_entity::EsEntityError),
+    #[error("BeneficiaryError - CursorDestructureError: {0}")]
+    CursorDestructureError(#[from] es_entity::CursorDestructureError),
+    #[error("BeneficiaryError - AuditError: {0}")]
+    AuditError(#[from] audit::error::AuditError),
+    #[error("BeneficiaryError - NotVerified: {0}")]
+    NotVerified(BeneficiaryId),
+    #[error("BeneficiaryError - HolderMismatch: {0}")]
+    HolderMismatch(BeneficiaryId),
+}
+
+es_entity::from_es_entity_error!(BeneficiaryError);
@@ -0,0 +1,7 @@
+mod entity;
+pub mod error;
+mod repo;
+
+pub(crate) use entity::*;
+pub use entity::{Beneficiary, BeneficiaryStatus};
+pub(crate) use repo::*;
@@ -0,0 +1,31 @@
+use sqlx::PgPool;
+
+use es_entity::*;
+
+use crate::primitives::{ApprovalProcessId, BeneficiaryId, DepositAccountHolderId};
+
+use super::{entity::*, error::*};
+
+#[derive(EsRepo, Clone)]
+#[es_repo(
+    entity = "Beneficiary",
+    err = "BeneficiaryError",
+    columns(
+        deposit_account_holder_id(
+            ty = "DepositAccountHolderId",
+            list_for,
+            update(persist = false)
+        ),
+        approval_process_id(ty = "ApprovalProcessId", update(persist = false)),
+    ),
+    tbl_prefix = "core"
+)]
+pub struct BeneficiaryRepo {
+    pool: PgPool,
+}
+
+impl BeneficiaryRepo {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
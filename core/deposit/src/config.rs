@@ -0,0 +1,185 @@
+use serde::{Deserialize, Serialize};
+
+use crate::primitives::{DepositAccountType, KycLevel, UsdCents};
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct DepositConfig {
+    #[serde(default)]
+    pub withdrawal_limits: WithdrawalLimitsConfig,
+    #[serde(default = "default_dormant_account_after_days")]
+    pub dormant_account_after_days: u32,
+    #[serde(default)]
+    pub payouts: PayoutConfig,
+    /// How long a newly approved beneficiary has to wait before withdrawals
+    /// can be sent to it.
+    #[serde(default = "default_beneficiary_cooling_off_hours")]
+    pub beneficiary_cooling_off_hours: u32,
+}
+
+impl Default for DepositConfig {
+    fn default() -> Self {
+        Self {
+            withdrawal_limits: WithdrawalLimitsConfig::default(),
+            dormant_account_after_days: default_dormant_account_after_days(),
+            payouts: PayoutConfig::default(),
+            beneficiary_cooling_off_hours: default_beneficiary_cooling_off_hours(),
+        }
+    }
+}
+
+impl DepositConfig {
+    pub fn dormant_account_after(&self) -> chrono::Duration {
+        chrono::Duration::days(self.dormant_account_after_days.into())
+    }
+
+    pub fn beneficiary_cooling_off(&self) -> chrono::Duration {
+        chrono::Duration::hours(self.beneficiary_cooling_off_hours.into())
+    }
+}
+
+/// Identifies the bank as originator of the payment files sent to its
+/// settlement bank.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct PayoutConfig {
+    #[serde(default = "default_payout_originator_name")]
+    pub originator_name: String,
+    /// Company identification used in NACHA batch headers.
+    #[serde(default = "default_payout_originator_id")]
+    pub originator_id: String,
+    #[serde(default = "default_payout_routing_number")]
+    pub originator_routing_number: String,
+    /// Account debited at the settlement bank, used in pain.001 files.
+    #[serde(default)]
+    pub originator_account_number: String,
+    #[serde(default = "default_payout_routing_number")]
+    pub destination_routing_number: String,
+    #[serde(default)]
+    pub destination_name: String,
+    #[serde(default = "default_payout_entry_description")]
+    pub entry_description: String,
+}
+
+impl Default for PayoutConfig {
+    fn default() -> Self {
+        Self {
+            originator_name: default_payout_originator_name(),
+            originator_id: default_payout_originator_id(),
+            originator_routing_number: default_payout_routing_number(),
+            originator_account_number: String::new(),
+            destination_routing_number: default_payout_routing_number(),
+            destination_name: String::new(),
+            entry_description: default_payout_entry_description(),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WithdrawalLimitsConfig {
+    #[serde(default = "default_basic_withdrawal_limits")]
+    pub basic: WithdrawalLimitsByAccountType,
+    #[serde(default = "default_advanced_withdrawal_limits")]
+    pub advanced: WithdrawalLimitsByAccountType,
+}
+
+impl Default for WithdrawalLimitsConfig {
+    fn default() -> Self {
+        Self {
+            basic: default_basic_withdrawal_limits(),
+            advanced: default_advanced_withdrawal_limits(),
+        }
+    }
+}
+
+impl WithdrawalLimitsConfig {
+    /// Accounts whose holder has not (yet) completed KYC fall back to the basic limits.
+    pub fn limits_for(
+        &self,
+        kyc_level: KycLevel,
+        deposit_account_type: DepositAccountType,
+    ) -> WithdrawalLimits {
+        match kyc_level {
+            KycLevel::NotKyced | KycLevel::Basic => self.basic.for_type(deposit_account_type),
+            KycLevel::Advanced => self.advanced.for_type(deposit_account_type),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WithdrawalLimitsByAccountType {
+    pub individual: WithdrawalLimits,
+    pub government_entity: WithdrawalLimits,
+    pub private_company: WithdrawalLimits,
+    pub bank: WithdrawalLimits,
+    pub financial_institution: WithdrawalLimits,
+    pub non_domiciled_company: WithdrawalLimits,
+}
+
+impl WithdrawalLimitsByAccountType {
+    pub fn uniform(limits: WithdrawalLimits) -> Self {
+        Self {
+            individual: limits,
+            government_entity: limits,
+            private_company: limits,
+            bank: limits,
+            financial_institution: limits,
+            non_domiciled_company: limits,
+        }
+    }
+
+    pub fn for_type(&self, deposit_account_type: DepositAccountType) -> WithdrawalLimits {
+        match deposit_account_type {
+            DepositAccountType::Individual => self.individual,
+            DepositAccountType::GovernmentEntity => self.government_entity,
+            DepositAccountType::PrivateCompany => self.private_company,
+            DepositAccountType::Bank => self.bank,
+            DepositAccountType::FinancialInstitution => self.financial_institution,
+            DepositAccountType::NonDomiciledCompany => self.non_domiciled_company,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+pub struct WithdrawalLimits {
+    /// Cap on withdrawals initiated in the last 24 hours.
+    pub daily: UsdCents,
+    /// Cap on withdrawals initiated in the last 30 days.
+    pub monthly: UsdCents,
+}
+
+fn default_dormant_account_after_days() -> u32 {
+    365
+}
+
+fn default_beneficiary_cooling_off_hours() -> u32 {
+    24
+}
+
+fn default_payout_originator_name() -> String {
+    "LANA BANK".to_string()
+}
+
+fn default_payout_originator_id() -> String {
+    "0000000000".to_string()
+}
+
+fn default_payout_routing_number() -> String {
+    "000000000".to_string()
+}
+
+fn default_payout_entry_description() -> String {
+    "WITHDRAWAL".to_string()
+}
+
+fn default_basic_withdrawal_limits() -> WithdrawalLimitsByAccountType {
+    WithdrawalLimitsByAccountType::uniform(WithdrawalLimits {
+        daily: UsdCents::from(1_000_000),
+        monthly: UsdCents::from(10_000_000),
+    })
+}
+
+fn default_advanced_withdrawal_limits() -> WithdrawalLimitsByAccountType {
+    WithdrawalLimitsByAccountType::uniform(WithdrawalLimits {
+        daily: UsdCents::from(10_000_000),
+        monthly: UsdCents::from(100_000_000),
+    })
+}
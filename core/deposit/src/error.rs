@@ -14,6 +14,8 @@ pub enum CoreDepositError {
     DepositError(#[from] crate::deposit::error::DepositError),
     #[error("CoreDepositError - WithdrawalError: {0}")]
     WithdrawalError(#[from] crate::withdrawal::error::WithdrawalError),
+    #[error("CoreDepositError - DepositAccountStatementError: {0}")]
+    DepositAccountStatementError(#[from] crate::statement::error::DepositAccountStatementError),
     #[error("CoreDepositError - DepositLedgerError: {0}")]
     DepositLedgerError(#[from] crate::ledger::error::DepositLedgerError),
     #[error("CoreDepositError - GovernanceError: {0}")]
@@ -22,8 +24,24 @@ pub enum CoreDepositError {
     CoreChartOfAccountsError(
         #[from] core_accounting::chart_of_accounts::error::ChartOfAccountsError,
     ),
+    #[error("CoreDepositError - StorageError: {0}")]
+    StorageError(#[from] cloud_storage::error::StorageError),
     #[error("CoreDepositError - JobError: {0}")]
     JobError(#[from] job::error::JobError),
+    #[error("CoreDepositError - WithdrawalLimitOverrideError: {0}")]
+    WithdrawalLimitOverrideError(
+        #[from] crate::withdrawal_limit_override::error::WithdrawalLimitOverrideError,
+    ),
+    #[error("CoreDepositError - BankStatementImportError: {0}")]
+    BankStatementImportError(#[from] crate::bank_statement_import::error::BankStatementImportError),
+    #[error("CoreDepositError - BankStatementExceptionError: {0}")]
+    BankStatementExceptionError(
+        #[from] crate::bank_statement_exception::error::BankStatementExceptionError,
+    ),
+    #[error("CoreDepositError - PayoutBatchError: {0}")]
+    PayoutBatchError(#[from] crate::payout_batch::error::PayoutBatchError),
+    #[error("CoreDepositError - BeneficiaryError: {0}")]
+    BeneficiaryError(#[from] crate::beneficiary::error::BeneficiaryError),
     #[error("CoreDepositError - ProcessError: {0}")]
     ProcessError(#[from] crate::processes::error::ProcessError),
     #[error("CoreDepositError - SubjectIsNotDepositAccountHolder")]
@@ -36,10 +54,29 @@ pub enum CoreDepositError {
     DepositConfigAlreadyExists,
     #[error("CoreDepositError - DepositAccountNotActive")]
     DepositAccountNotActive,
+    #[error("CoreDepositError - DepositAccountHasPendingWithdrawals")]
+    DepositAccountHasPendingWithdrawals,
+    #[error("CoreDepositError - DepositAccountHasHolds")]
+    DepositAccountHasHolds,
+    #[error("CoreDepositError - WithdrawalLimitExceeded: remaining headroom is {remaining} for the {window} limit")]
+    WithdrawalLimitExceeded {
+        window: crate::primitives::WithdrawalLimitWindow,
+        remaining: core_money::UsdCents,
+    },
     #[error("CoreDepositError - WithdrawalBuilderError: {0}")]
     WithdrawalBuilderError(#[from] super::NewWithdrawalBuilderError),
     #[error("CoreDepositError - DepositBuilderError: {0}")]
     DepositBuilderError(#[from] super::NewDepositBuilderError),
+    #[error("CoreDepositError - WithdrawalLimitOverrideBuilderError: {0}")]
+    WithdrawalLimitOverrideBuilderError(#[from] super::NewWithdrawalLimitOverrideBuilderError),
+    #[error("CoreDepositError - BankStatementImportBuilderError: {0}")]
+    BankStatementImportBuilderError(#[from] super::NewBankStatementImportBuilderError),
+    #[error("CoreDepositError - BankStatementExceptionBuilderError: {0}")]
+    BankStatementExceptionBuilderError(#[from] super::NewBankStatementExceptionBuilderError),
+    #[error("CoreDepositError - PayoutBatchBuilderError: {0}")]
+    PayoutBatchBuilderError(#[from] super::NewPayoutBatchBuilderError),
+    #[error("CoreDepositError - BeneficiaryBuilderError: {0}")]
+    BeneficiaryBuilderError(#[from] super::NewBeneficiaryBuilderError),
 }
 
 impl CoreDepositError {
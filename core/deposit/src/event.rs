@@ -10,6 +10,16 @@ pub enum CoreDepositEvent {
         id: DepositAccountId,
         account_holder_id: DepositAccountHolderId,
     },
+    DepositAccountClosed {
+        id: DepositAccountId,
+        account_holder_id: DepositAccountHolderId,
+        settled_amount: UsdCents,
+    },
+    DepositAccountDormant {
+        id: DepositAccountId,
+        account_holder_id: DepositAccountHolderId,
+        last_activity_at: chrono::DateTime<chrono::Utc>,
+    },
     DepositInitialized {
         id: DepositId,
         deposit_account_id: DepositAccountId,
@@ -20,4 +30,9 @@ pub enum CoreDepositEvent {
         deposit_account_id: DepositAccountId,
         amount: UsdCents,
     },
+    WithdrawalPayoutReturned {
+        id: WithdrawalId,
+        deposit_account_id: DepositAccountId,
+        amount: UsdCents,
+    },
 }
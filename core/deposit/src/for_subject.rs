@@ -1,4 +1,4 @@
-use audit::AuditSvc;
+use audit::{AuditInfo, AuditSvc};
 use authz::PermissionCheck;
 use outbox::OutboxEventMarker;
 use tracing::instrument;
@@ -13,6 +13,7 @@ use crate::{
     history::{DepositAccountHistoryCursor, DepositAccountHistoryEntry},
     ledger::*,
     primitives::*,
+    statement::*,
     withdrawal::*,
 };
 
@@ -26,7 +27,9 @@ where
     accounts: &'a DepositAccountRepo<E>,
     deposits: &'a DepositRepo<E>,
     withdrawals: &'a WithdrawalRepo<E>,
+    statements: &'a DepositAccountStatementRepo,
     ledger: &'a DepositLedger,
+    storage: &'a cloud_storage::Storage,
     authz: &'a Perms,
 }
 
@@ -39,13 +42,16 @@ where
         From<CoreDepositObject> + From<GovernanceObject>,
     E: OutboxEventMarker<CoreDepositEvent>,
 {
+    #[allow(clippy::too_many_arguments)]
     pub(super) fn new(
         subject: &'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
         account_holder_id: DepositAccountHolderId,
         accounts: &'a DepositAccountRepo<E>,
         deposits: &'a DepositRepo<E>,
         withdrawals: &'a WithdrawalRepo<E>,
+        statements: &'a DepositAccountStatementRepo,
         ledger: &'a DepositLedger,
+        storage: &'a cloud_storage::Storage,
         authz: &'a Perms,
     ) -> Self {
         Self {
@@ -54,7 +60,9 @@ where
             accounts,
             deposits,
             withdrawals,
+            statements,
             ledger,
+            storage,
             authz,
         }
     }
@@ -79,7 +87,7 @@ where
 
         Ok(self
             .accounts
-            .list_for_account_holder_id_by_created_at(
+            .list_open_for_account_holder_id_by_created_at(
                 self.account_holder_id,
                 query,
                 direction.into(),
@@ -234,12 +242,85 @@ where
         Ok(withdrawal)
     }
 
+    pub async fn find_withdrawal_by_returned_tx_id(
+        &self,
+        returned_tx_id: impl Into<CalaTransactionId> + std::fmt::Debug,
+    ) -> Result<Withdrawal, CoreDepositError> {
+        let returned_tx_id = returned_tx_id.into();
+        let withdrawal = self
+            .withdrawals
+            .find_by_returned_tx_id(Some(returned_tx_id))
+            .await?;
+
+        self.ensure_account_access(
+            withdrawal.deposit_account_id,
+            CoreDepositObject::withdrawal(withdrawal.id),
+            CoreDepositAction::WITHDRAWAL_READ,
+        )
+        .await?;
+
+        Ok(withdrawal)
+    }
+
+    pub async fn list_statements_for_account(
+        &self,
+        account_id: impl Into<DepositAccountId> + std::fmt::Debug,
+    ) -> Result<Vec<DepositAccountStatement>, CoreDepositError> {
+        let account_id = account_id.into();
+
+        self.ensure_account_access(
+            account_id,
+            CoreDepositObject::all_deposit_account_statements(),
+            CoreDepositAction::DEPOSIT_ACCOUNT_STATEMENT_LIST,
+        )
+        .await?;
+
+        Ok(self
+            .statements
+            .list_for_deposit_account_id_by_created_at(
+                account_id,
+                Default::default(),
+                es_entity::ListDirection::Descending,
+            )
+            .await?
+            .entities)
+    }
+
+    pub async fn generate_statement_download_link(
+        &self,
+        statement_id: impl Into<DepositAccountStatementId> + std::fmt::Debug,
+        format: DepositAccountStatementFormat,
+    ) -> Result<DepositAccountStatementDownloadLink, CoreDepositError> {
+        let statement_id = statement_id.into();
+        let mut statement = self.statements.find_by_id(statement_id).await?;
+
+        let audit_info = self
+            .ensure_account_access(
+                statement.deposit_account_id,
+                CoreDepositObject::deposit_account_statement(statement_id),
+                CoreDepositAction::DEPOSIT_ACCOUNT_STATEMENT_GENERATE_DOWNLOAD_LINK,
+            )
+            .await?;
+
+        let url = {
+            let location = statement.download_link_generated(format, audit_info)?;
+            self.storage.generate_download_link(&location).await?
+        };
+        self.statements.update(&mut statement).await?;
+
+        Ok(DepositAccountStatementDownloadLink {
+            deposit_account_statement_id: statement_id,
+            format,
+            url,
+        })
+    }
+
     async fn ensure_account_access(
         &self,
         account_id: DepositAccountId,
         object: CoreDepositObject,
         action: CoreDepositAction,
-    ) -> Result<(), CoreDepositError> {
+    ) -> Result<AuditInfo, CoreDepositError> {
         let account = self.accounts.find_by_id(account_id).await?;
 
         if account.account_holder_id != self.account_holder_id {
@@ -249,11 +330,12 @@ where
                 .await?;
             return Err(CoreDepositError::DepositAccountNotFound);
         }
-        self.authz
+        let audit_info = self
+            .authz
             .audit()
             .record_entry(self.sub, object, action, true)
             .await?;
 
-        Ok(())
+        Ok(audit_info)
     }
 }
@@ -7,6 +7,7 @@ pub enum DepositAccountHistoryEntry {
     Deposit(DepositEntry),
     Withdrawal(WithdrawalEntry),
     CancelledWithdrawal(WithdrawalEntry),
+    ReturnedWithdrawal(WithdrawalEntry),
     Disbursal(DisbursalEntry),
     Payment(PaymentEntry),
     Unknown(UnknownEntry),
@@ -42,11 +43,12 @@ pub struct UnknownEntry {
     pub recorded_at: DateTime<Utc>,
 }
 
-const RECORD_DEPOSIT: &str = "RECORD_DEPOSIT_CR";
-const INITIATE_WITHDRAW: &str = "INITIATE_WITHDRAW_SETTLED_DR";
-const CANCEL_WITHDRAW: &str = "CANCEL_WITHDRAW_SETTLED_CR";
-const CONFIRM_DISBURSAL: &str = "CONFIRM_DISBURSAL_SETTLED_CR";
-const RECORD_PAYMENT_ALLOCATION: &str = "RECORD_PAYMENT_ALLOCATION_DR";
+pub(crate) const RECORD_DEPOSIT: &str = "RECORD_DEPOSIT_CR";
+pub(crate) const INITIATE_WITHDRAW: &str = "INITIATE_WITHDRAW_SETTLED_DR";
+pub(crate) const CANCEL_WITHDRAW: &str = "CANCEL_WITHDRAW_SETTLED_CR";
+pub(crate) const RETURN_WITHDRAW: &str = "RETURN_WITHDRAW_CR";
+pub(crate) const CONFIRM_DISBURSAL: &str = "CONFIRM_DISBURSAL_SETTLED_CR";
+pub(crate) const RECORD_PAYMENT_ALLOCATION: &str = "RECORD_PAYMENT_ALLOCATION_DR";
 
 const IGNORE_INITIATE_WITHDRAW_PENDING: &str = "INITIATE_WITHDRAW_PENDING_CR";
 const IGNORE_CONFIRM_WITHDRAWAL_PENDING: &str = "CONFIRM_WITHDRAW_PENDING_DR";
@@ -70,6 +72,11 @@ impl From<cala_ledger::entry::Entry> for DepositAccountHistoryEntry {
                 entry_id: entry.id,
                 recorded_at: entry.created_at(),
             }),
+            RETURN_WITHDRAW => DepositAccountHistoryEntry::ReturnedWithdrawal(WithdrawalEntry {
+                tx_id: entry.values().transaction_id,
+                entry_id: entry.id,
+                recorded_at: entry.created_at(),
+            }),
             CONFIRM_DISBURSAL => DepositAccountHistoryEntry::Disbursal(DisbursalEntry {
                 tx_id: entry.values().transaction_id,
                 entry_id: entry.id,
@@ -115,6 +122,10 @@ impl From<&DepositAccountHistoryEntry> for DepositAccountHistoryCursor {
                 entry_id: entry.entry_id,
                 created_at: entry.recorded_at,
             },
+            DepositAccountHistoryEntry::ReturnedWithdrawal(entry) => Self {
+                entry_id: entry.entry_id,
+                created_at: entry.recorded_at,
+            },
             DepositAccountHistoryEntry::Disbursal(entry) => Self {
                 entry_id: entry.entry_id,
                 created_at: entry.recorded_at,
@@ -28,10 +28,19 @@ pub enum DepositLedgerError {
     MissingTxMetadata,
     #[error("DepositLedgerError - ChartOfAccountsError: {0}")]
     ChartOfAccountsError(#[from] core_accounting::chart_of_accounts::error::ChartOfAccountsError),
+    #[error("DepositLedgerError - AccountingPeriodError: {0}")]
+    AccountingPeriodError(#[from] core_accounting::accounting_period::error::AccountingPeriodError),
     #[error("DepositLedgerError - MismatchedTxMetadata: {0}")]
     MismatchedTxMetadata(serde_json::Error),
     #[error("DepositLedgerError - NonAccountMemberFoundInAccountSet: Found non-Account typed member in account set {0}")]
     NonAccountMemberFoundInAccountSet(String),
     #[error("DepositLedgerError - JournalIdMismatch: Account sets have wrong JournalId")]
     JournalIdMismatch,
+    #[error(
+        "DepositLedgerError - WithdrawalLimitExceeded: {remaining} left in the {window} window"
+    )]
+    WithdrawalLimitExceeded {
+        window: crate::primitives::WithdrawalLimitWindow,
+        remaining: core_money::UsdCents,
+    },
 }
@@ -12,16 +12,20 @@ use cala_ledger::{
     account_set::{AccountSet, AccountSetMemberId, AccountSetUpdate, NewAccountSet},
     tx_template::Params,
     velocity::{NewVelocityControl, VelocityControlId},
-    CalaLedger, Currency, DebitOrCredit, JournalId, LedgerOperation, TransactionId,
+    CalaLedger, Currency, DebitOrCredit, JournalId, Layer, LedgerOperation, TransactionId,
 };
 
+use core_accounting::{AccountingPeriodCheck, PostingKind};
+
 use crate::{
     chart_of_accounts_integration::ChartOfAccountsIntegrationConfig,
+    config::WithdrawalLimits,
     primitives::{CalaAccountId, CalaAccountSetId, DepositAccountType, UsdCents},
     DepositAccountBalance, LedgerOmnibusAccountIds,
 };
 
 use error::*;
+pub use velocity::{WithdrawalLimitAccountIds, WithdrawalLimitMeta};
 
 pub const DEPOSIT_INDIVIDUAL_ACCOUNT_SET_NAME: &str = "Deposit Individual Account Set";
 pub const DEPOSIT_INDIVIDUAL_ACCOUNT_SET_REF: &str = "deposit-individual-account-set";
@@ -45,6 +49,12 @@ pub const DEPOSIT_OMNIBUS_ACCOUNT_SET_NAME: &str = "Deposit Omnibus Account Set"
 pub const DEPOSIT_OMNIBUS_ACCOUNT_SET_REF: &str = "deposit-omnibus-account-set";
 pub const DEPOSIT_OMNIBUS_ACCOUNT_REF: &str = "deposit-omnibus-account";
 
+pub const WITHDRAWAL_LIMIT_ACCOUNT_SET_NAME: &str = "Withdrawal Limit Account Set";
+pub const WITHDRAWAL_LIMIT_ACCOUNT_SET_REF: &str = "withdrawal-limit-account-set";
+pub const WITHDRAWAL_LIMIT_OMNIBUS_ACCOUNT_SET_NAME: &str = "Withdrawal Limit Omnibus Account Set";
+pub const WITHDRAWAL_LIMIT_OMNIBUS_ACCOUNT_SET_REF: &str = "withdrawal-limit-omnibus-account-set";
+pub const WITHDRAWAL_LIMIT_OMNIBUS_ACCOUNT_REF: &str = "withdrawal-limit-omnibus-account";
+
 pub const DEPOSITS_VELOCITY_CONTROL_ID: uuid::Uuid =
     uuid::uuid!("00000000-0000-0000-0000-000000000001");
 
@@ -89,17 +99,25 @@ pub struct DepositLedger {
     deposit_omnibus_account_ids: LedgerOmnibusAccountIds,
     usd: Currency,
     deposit_control_id: VelocityControlId,
+    withdrawal_limit_account_set: InternalAccountSetDetails,
+    withdrawal_limit_omnibus_account_ids: LedgerOmnibusAccountIds,
+    withdrawal_limit_control_id: VelocityControlId,
+    periods: AccountingPeriodCheck,
 }
 
 impl DepositLedger {
     pub async fn init(
         cala: &CalaLedger,
         journal_id: JournalId,
+        periods: &AccountingPeriodCheck,
     ) -> Result<Self, DepositLedgerError> {
         templates::RecordDeposit::init(cala).await?;
         templates::InitiateWithdraw::init(cala).await?;
         templates::CancelWithdraw::init(cala).await?;
         templates::ConfirmWithdraw::init(cala).await?;
+        templates::ReturnWithdraw::init(cala).await?;
+        templates::SettleClosingBalance::init(cala).await?;
+        templates::ReleaseWithdrawalLimit::init(cala).await?;
 
         let deposits_normal_balance_type = DebitOrCredit::Credit;
 
@@ -181,6 +199,27 @@ impl DepositLedger {
             Err(e) => return Err(e.into()),
         }
 
+        let withdrawal_limit_account_set_id = Self::find_or_create_account_set(
+            cala,
+            journal_id,
+            format!("{journal_id}:{WITHDRAWAL_LIMIT_ACCOUNT_SET_REF}"),
+            WITHDRAWAL_LIMIT_ACCOUNT_SET_NAME.to_string(),
+            DebitOrCredit::Debit,
+        )
+        .await?;
+
+        let withdrawal_limit_omnibus_account_ids = Self::find_or_create_omnibus_account(
+            cala,
+            journal_id,
+            format!("{journal_id}:{WITHDRAWAL_LIMIT_OMNIBUS_ACCOUNT_SET_REF}"),
+            format!("{journal_id}:{WITHDRAWAL_LIMIT_OMNIBUS_ACCOUNT_REF}"),
+            WITHDRAWAL_LIMIT_OMNIBUS_ACCOUNT_SET_NAME.to_string(),
+            DebitOrCredit::Credit,
+        )
+        .await?;
+
+        let withdrawal_limit_control_id = velocity::WithdrawalLimit::init(cala).await?;
+
         Ok(Self {
             cala: cala.clone(),
             journal_id,
@@ -212,7 +251,14 @@ impl DepositLedger {
             },
             deposit_omnibus_account_ids,
             deposit_control_id,
+            withdrawal_limit_account_set: InternalAccountSetDetails {
+                id: withdrawal_limit_account_set_id,
+                normal_balance_type: DebitOrCredit::Debit,
+            },
+            withdrawal_limit_omnibus_account_ids,
+            withdrawal_limit_control_id,
             usd: Currency::USD,
+            periods: periods.clone(),
         })
     }
 
@@ -364,14 +410,82 @@ impl DepositLedger {
         })
     }
 
+    pub async fn settled_entries_since(
+        &self,
+        id: impl Into<AccountId>,
+        since: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Vec<cala_ledger::entry::Entry>, DepositLedgerError> {
+        let id = id.into();
+
+        let mut entries = Vec::new();
+        let mut after = None;
+        loop {
+            let ret = self
+                .cala
+                .entries()
+                .list_for_account_id(
+                    id,
+                    es_entity::PaginatedQueryArgs { first: 100, after },
+                    es_entity::ListDirection::Descending,
+                )
+                .await?;
+            let mut reached_since = false;
+            for entry in ret.entities {
+                if entry.created_at() < since {
+                    reached_since = true;
+                    break;
+                }
+                if entry.values().layer == Layer::Settled && entry.values().currency == self.usd {
+                    entries.push(entry);
+                }
+            }
+            if reached_since || !ret.has_next_page {
+                break;
+            }
+            after = ret.end_cursor;
+        }
+
+        Ok(entries)
+    }
+
+    pub async fn effective_dates(
+        &self,
+        ids: &[TransactionId],
+    ) -> Result<HashMap<TransactionId, chrono::NaiveDate>, DepositLedgerError> {
+        let transactions = self
+            .cala
+            .transactions()
+            .find_all::<cala_ledger::transaction::Transaction>(ids)
+            .await?;
+        Ok(transactions
+            .into_iter()
+            .map(|(id, tx)| (id, tx.effective()))
+            .collect())
+    }
+
+    /// Deposit templates post effective on the day of the operation that
+    /// posts them.
+    async fn ensure_period_open(
+        &self,
+        op: &mut es_entity::DbOp<'_>,
+        effective: chrono::NaiveDate,
+    ) -> Result<(), DepositLedgerError> {
+        self.periods
+            .ensure_accepts_posting_in_op(op, effective, PostingKind::System)
+            .await?;
+        Ok(())
+    }
+
     pub async fn record_deposit(
         &self,
-        op: es_entity::DbOp<'_>,
+        mut op: es_entity::DbOp<'_>,
         tx_id: impl Into<TransactionId>,
         amount: UsdCents,
         credit_account_id: impl Into<AccountId>,
     ) -> Result<(), DepositLedgerError> {
         let tx_id = tx_id.into();
+        let effective = op.now().date_naive();
+        self.ensure_period_open(&mut op, effective).await?;
         let mut op = self.cala.ledger_operation_from_db_op(op);
 
         let params = templates::RecordDepositParams {
@@ -380,6 +494,7 @@ impl DepositLedger {
             amount: amount.to_usd(),
             deposit_omnibus_account_id: self.deposit_omnibus_account_ids.account_id,
             credit_account_id: credit_account_id.into(),
+            effective,
         };
         self.cala
             .post_transaction_in_op(&mut op, tx_id, templates::RECORD_DEPOSIT_CODE, params)
@@ -391,25 +506,52 @@ impl DepositLedger {
 
     pub async fn initiate_withdrawal(
         &self,
-        op: es_entity::DbOp<'_>,
+        mut op: es_entity::DbOp<'_>,
         tx_id: impl Into<TransactionId>,
         amount: UsdCents,
         credit_account_id: impl Into<AccountId>,
+        withdrawal_limit_account_id: impl Into<AccountId>,
+        withdrawal_limit: WithdrawalLimitMeta,
     ) -> Result<(), DepositLedgerError> {
         let tx_id = tx_id.into();
+        let effective = op.now().date_naive();
+        self.ensure_period_open(&mut op, effective).await?;
         let mut op = self.cala.ledger_operation_from_db_op(op);
 
         let params = templates::InitiateWithdrawParams {
             journal_id: self.journal_id,
             deposit_omnibus_account_id: self.deposit_omnibus_account_ids.account_id,
             credit_account_id: credit_account_id.into(),
+            withdrawal_limit_account_id: withdrawal_limit_account_id.into(),
+            withdrawal_limit_omnibus_account_id: self
+                .withdrawal_limit_omnibus_account_ids
+                .account_id,
             amount: amount.to_usd(),
             currency: self.usd,
+            withdrawal_limit,
+            effective,
         };
 
-        self.cala
+        match self
+            .cala
             .post_transaction_in_op(&mut op, tx_id, templates::INITIATE_WITHDRAW_CODE, params)
-            .await?;
+            .await
+        {
+            Err(cala_ledger::error::LedgerError::VelocityError(
+                cala_ledger::velocity::error::VelocityError::Enforcement(e),
+            )) if e.limit_id != velocity::OVERDRAFT_PREVENTION_ID.into() => {
+                let Some(window) = velocity::WithdrawalLimit::window_of(e.limit_id) else {
+                    return Err(cala_ledger::velocity::error::VelocityError::Enforcement(e).into());
+                };
+                // `requested` is what the window would hold with this withdrawal.
+                let used = e.requested - amount.to_usd();
+                let remaining =
+                    UsdCents::try_from_usd((e.limit - used).max(rust_decimal::Decimal::ZERO))?;
+                return Err(DepositLedgerError::WithdrawalLimitExceeded { window, remaining });
+            }
+            Err(e) => return Err(e.into()),
+            Ok(_) => (),
+        }
 
         op.commit().await?;
         Ok(())
@@ -417,7 +559,7 @@ impl DepositLedger {
 
     pub async fn confirm_withdrawal(
         &self,
-        op: es_entity::DbOp<'_>,
+        mut op: es_entity::DbOp<'_>,
         tx_id: impl Into<TransactionId>,
         correlation_id: String,
         amount: UsdCents,
@@ -425,6 +567,8 @@ impl DepositLedger {
         external_id: String,
     ) -> Result<(), DepositLedgerError> {
         let tx_id = tx_id.into();
+        let effective = op.now().date_naive();
+        self.ensure_period_open(&mut op, effective).await?;
         let mut op = self.cala.ledger_operation_from_db_op(op);
 
         let params = templates::ConfirmWithdrawParams {
@@ -435,6 +579,7 @@ impl DepositLedger {
             credit_account_id: credit_account_id.into(),
             correlation_id,
             external_id,
+            effective,
         };
 
         self.cala
@@ -446,20 +591,33 @@ impl DepositLedger {
 
     pub async fn cancel_withdrawal(
         &self,
-        op: es_entity::DbOp<'_>,
+        mut op: es_entity::DbOp<'_>,
         tx_id: impl Into<TransactionId>,
         amount: UsdCents,
         credit_account_id: impl Into<AccountId>,
+        withdrawal_limit_account_id: Option<CalaAccountId>,
+        withdrawal_limit: WithdrawalLimitMeta,
     ) -> Result<(), DepositLedgerError> {
         let tx_id = tx_id.into();
+        let effective = op.now().date_naive();
+        self.ensure_period_open(&mut op, effective).await?;
         let mut op = self.cala.ledger_operation_from_db_op(op);
 
+        // A withdrawal that holds no withdrawal limits nets out on the omnibus account.
+        let withdrawal_limit_account_id = withdrawal_limit_account_id
+            .unwrap_or(self.withdrawal_limit_omnibus_account_ids.account_id);
         let params = templates::CancelWithdrawParams {
             journal_id: self.journal_id,
             currency: self.usd,
             amount: amount.to_usd(),
             credit_account_id: credit_account_id.into(),
             deposit_omnibus_account_id: self.deposit_omnibus_account_ids.account_id,
+            withdrawal_limit_account_id,
+            withdrawal_limit_omnibus_account_id: self
+                .withdrawal_limit_omnibus_account_ids
+                .account_id,
+            withdrawal_limit,
+            effective,
         };
 
         self.cala
@@ -469,6 +627,130 @@ impl DepositLedger {
         Ok(())
     }
 
+    /// Stops a withdrawal from counting towards the given withdrawal limit
+    /// windows.
+    pub async fn release_withdrawal_limit(
+        &self,
+        mut op: es_entity::DbOp<'_>,
+        tx_id: impl Into<TransactionId>,
+        amount: UsdCents,
+        withdrawal_limit_account_id: impl Into<AccountId>,
+        withdrawal_limit: WithdrawalLimitMeta,
+    ) -> Result<(), DepositLedgerError> {
+        let tx_id = tx_id.into();
+        let effective = op.now().date_naive();
+        self.ensure_period_open(&mut op, effective).await?;
+        let mut op = self.cala.ledger_operation_from_db_op(op);
+
+        let params = templates::ReleaseWithdrawalLimitParams {
+            journal_id: self.journal_id,
+            currency: self.usd,
+            amount: amount.to_usd(),
+            withdrawal_limit_account_id: withdrawal_limit_account_id.into(),
+            withdrawal_limit_omnibus_account_id: self
+                .withdrawal_limit_omnibus_account_ids
+                .account_id,
+            withdrawal_limit,
+            effective,
+        };
+
+        self.cala
+            .post_transaction_in_op(
+                &mut op,
+                tx_id,
+                templates::RELEASE_WITHDRAWAL_LIMIT_CODE,
+                params,
+            )
+            .await?;
+        op.commit().await?;
+        Ok(())
+    }
+
+    pub async fn return_withdrawal(
+        &self,
+        mut op: es_entity::DbOp<'_>,
+        tx_id: impl Into<TransactionId>,
+        amount: UsdCents,
+        credit_account_id: impl Into<AccountId>,
+    ) -> Result<(), DepositLedgerError> {
+        let tx_id = tx_id.into();
+        let effective = op.now().date_naive();
+        self.ensure_period_open(&mut op, effective).await?;
+        let mut op = self.cala.ledger_operation_from_db_op(op);
+
+        let params = templates::ReturnWithdrawParams {
+            journal_id: self.journal_id,
+            currency: self.usd,
+            amount: amount.to_usd(),
+            deposit_omnibus_account_id: self.deposit_omnibus_account_ids.account_id,
+            credit_account_id: credit_account_id.into(),
+            effective,
+        };
+
+        self.cala
+            .post_transaction_in_op(&mut op, tx_id, templates::RETURN_WITHDRAW_CODE, params)
+            .await?;
+        op.commit().await?;
+        Ok(())
+    }
+
+    /// Moves the settled balance of a closing account either to another
+    /// deposit account or, when no target is given, out through the omnibus
+    /// account.
+    pub async fn settle_closing_balance(
+        &self,
+        mut op: es_entity::DbOp<'_>,
+        tx_id: impl Into<TransactionId>,
+        amount: UsdCents,
+        closing_account_id: impl Into<AccountId>,
+        settlement_account_id: Option<AccountId>,
+    ) -> Result<(), DepositLedgerError> {
+        let tx_id = tx_id.into();
+        let effective = op.now().date_naive();
+        self.ensure_period_open(&mut op, effective).await?;
+        let mut op = self.cala.ledger_operation_from_db_op(op);
+
+        let params = templates::SettleClosingBalanceParams {
+            journal_id: self.journal_id,
+            currency: self.usd,
+            amount: amount.to_usd(),
+            closing_account_id: closing_account_id.into(),
+            settlement_account_id: settlement_account_id
+                .unwrap_or(self.deposit_omnibus_account_ids.account_id),
+            effective,
+        };
+
+        self.cala
+            .post_transaction_in_op(
+                &mut op,
+                tx_id,
+                templates::SETTLE_CLOSING_BALANCE_CODE,
+                params,
+            )
+            .await?;
+        op.commit().await?;
+        Ok(())
+    }
+
+    pub async fn last_activity_at(
+        &self,
+        account_id: impl Into<AccountId>,
+    ) -> Result<Option<chrono::DateTime<chrono::Utc>>, DepositLedgerError> {
+        let ret = self
+            .cala
+            .entries()
+            .list_for_account_id(
+                account_id.into(),
+                es_entity::PaginatedQueryArgs {
+                    first: 1,
+                    after: None,
+                },
+                es_entity::ListDirection::Descending,
+            )
+            .await?;
+        Ok(ret.entities.first().map(|entry| entry.created_at()))
+    }
+
     pub async fn balance(
         &self,
         account_id: impl Into<AccountId>,
@@ -490,6 +772,7 @@ impl DepositLedger {
         }
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub async fn create_deposit_account(
         &self,
         op: es_entity::DbOp<'_>,
@@ -497,6 +780,9 @@ impl DepositLedger {
         deposit_account_reference: String,
         deposit_account_name: String,
         deposit_account_type: impl Into<DepositAccountType>,
+        withdrawal_limit_account_ids: WithdrawalLimitAccountIds,
+        basic_withdrawal_limits: WithdrawalLimits,
+        advanced_withdrawal_limits: WithdrawalLimits,
     ) -> Result<(), DepositLedgerError> {
         let id = id.into();
 
@@ -513,6 +799,20 @@ impl DepositLedger {
         .await?;
 
         self.add_deposit_control_to_account(&mut op, id).await?;
+        self.create_withdrawal_limit_account_in_op(
+            &mut op,
+            withdrawal_limit_account_ids.basic,
+            &format!("{deposit_account_name} (basic withdrawal limits)"),
+            basic_withdrawal_limits,
+        )
+        .await?;
+        self.create_withdrawal_limit_account_in_op(
+            &mut op,
+            withdrawal_limit_account_ids.advanced,
+            &format!("{deposit_account_name} (advanced withdrawal limits)"),
+            advanced_withdrawal_limits,
+        )
+        .await?;
 
         op.commit().await?;
 
@@ -607,6 +907,62 @@ impl DepositLedger {
         Ok(())
     }
 
+    /// Creates an account that withdrawals are tracked on for the withdrawal
+    /// limits and attaches the limits to it.
+    async fn create_withdrawal_limit_account_in_op(
+        &self,
+        op: &mut LedgerOperation<'_>,
+        id: impl Into<CalaAccountId>,
+        name: &str,
+        limits: WithdrawalLimits,
+    ) -> Result<(), DepositLedgerError> {
+        let id = id.into();
+
+        self.create_account_in_op(
+            op,
+            id,
+            self.withdrawal_limit_account_set,
+            &id.to_string(),
+            name,
+            name,
+        )
+        .await?;
+        self.cala
+            .velocities()
+            .attach_control_to_account_in_op(
+                op,
+                self.withdrawal_limit_control_id,
+                id,
+                Params::from(velocity::WithdrawalLimitParams::from(limits)),
+            )
+            .await?;
+
+        Ok(())
+    }
+
+    /// Overrides are tracked on an account of their own, so withdrawals held
+    /// under the limits they replace don't count towards them.
+    pub async fn apply_withdrawal_limit_override(
+        &self,
+        op: es_entity::DbOp<'_>,
+        withdrawal_limit_account_id: impl Into<CalaAccountId>,
+        deposit_account_name: &str,
+        limits: WithdrawalLimits,
+    ) -> Result<(), DepositLedgerError> {
+        let mut op = self.cala.ledger_operation_from_db_op(op);
+
+        self.create_withdrawal_limit_account_in_op(
+            &mut op,
+            withdrawal_limit_account_id,
+            &format!("{deposit_account_name} (withdrawal limit override)"),
+            limits,
+        )
+        .await?;
+
+        op.commit().await?;
+        Ok(())
+    }
+
     pub async fn get_chart_of_accounts_integration_config(
         &self,
     ) -> Result<Option<ChartOfAccountsIntegrationConfig>, DepositLedgerError> {
@@ -6,7 +6,7 @@ use cala_ledger::{
     *,
 };
 
-use crate::ledger::error::*;
+use crate::ledger::{error::*, velocity::WithdrawalLimitMeta};
 
 pub const CANCEL_WITHDRAW_CODE: &str = "CANCEL_WITHDRAW";
 
@@ -17,6 +17,10 @@ pub struct CancelWithdrawParams {
     pub amount: Decimal,
     pub deposit_omnibus_account_id: AccountId,
     pub credit_account_id: AccountId,
+    pub withdrawal_limit_account_id: AccountId,
+    pub withdrawal_limit_omnibus_account_id: AccountId,
+    pub withdrawal_limit: WithdrawalLimitMeta,
+    pub effective: chrono::NaiveDate,
 }
 
 impl CancelWithdrawParams {
@@ -47,11 +51,26 @@ impl CancelWithdrawParams {
                 .r#type(ParamDataType::Uuid)
                 .build()
                 .unwrap(),
+            NewParamDefinition::builder()
+                .name("withdrawal_limit_account_id")
+                .r#type(ParamDataType::Uuid)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("withdrawal_limit_omnibus_account_id")
+                .r#type(ParamDataType::Uuid)
+                .build()
+                .unwrap(),
             NewParamDefinition::builder()
                 .name("effective")
                 .r#type(ParamDataType::Date)
                 .build()
                 .unwrap(),
+            NewParamDefinition::builder()
+                .name("meta")
+                .r#type(ParamDataType::Json)
+                .build()
+                .unwrap(),
         ]
     }
 }
@@ -64,15 +83,27 @@ impl From<CancelWithdrawParams> for Params {
             amount,
             deposit_omnibus_account_id,
             credit_account_id,
+            withdrawal_limit_account_id,
+            withdrawal_limit_omnibus_account_id,
+            withdrawal_limit,
+            effective,
         }: CancelWithdrawParams,
     ) -> Self {
+        let meta = serde_json::to_value(withdrawal_limit).expect("Couldn't serialize meta");
+
         let mut params = Self::default();
         params.insert("journal_id", journal_id);
         params.insert("currency", currency);
         params.insert("amount", amount);
         params.insert("deposit_omnibus_account_id", deposit_omnibus_account_id);
         params.insert("credit_account_id", credit_account_id);
-        params.insert("effective", chrono::Utc::now().date_naive());
+        params.insert("withdrawal_limit_account_id", withdrawal_limit_account_id);
+        params.insert(
+            "withdrawal_limit_omnibus_account_id",
+            withdrawal_limit_omnibus_account_id,
+        );
+        params.insert("effective", effective);
+        params.insert("meta", meta);
 
         params
     }
@@ -86,6 +117,7 @@ impl CancelWithdraw {
         let tx_input = NewTxTemplateTransaction::builder()
             .journal_id("params.journal_id")
             .effective("params.effective")
+            .metadata("params.meta")
             .description("'Cancel a Withdraw'")
             .build()
             .expect("Couldn't build TxInput");
@@ -126,6 +158,24 @@ impl CancelWithdraw {
                 .units("params.amount")
                 .build()
                 .expect("Couldn't build entry"),
+            NewTxTemplateEntry::builder()
+                .entry_type("'CANCEL_WITHDRAW_LIMIT_CR'")
+                .currency("params.currency")
+                .account_id("params.withdrawal_limit_account_id")
+                .direction("CREDIT")
+                .layer("SETTLED")
+                .units("params.amount")
+                .build()
+                .expect("Couldn't build entry"),
+            NewTxTemplateEntry::builder()
+                .entry_type("'CANCEL_WITHDRAW_LIMIT_DR'")
+                .currency("params.currency")
+                .account_id("params.withdrawal_limit_omnibus_account_id")
+                .direction("DEBIT")
+                .layer("SETTLED")
+                .units("params.amount")
+                .build()
+                .expect("Couldn't build entry"),
         ];
 
         let params = CancelWithdrawParams::defs();
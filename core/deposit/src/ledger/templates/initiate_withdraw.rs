@@ -6,7 +6,10 @@ use cala_ledger::{
     *,
 };
 
-use crate::{ledger::error::*, primitives::CalaAccountId};
+use crate::{
+    ledger::{error::*, velocity::WithdrawalLimitMeta},
+    primitives::CalaAccountId,
+};
 
 pub const INITIATE_WITHDRAW_CODE: &str = "INITIATE_WITHDRAW";
 
@@ -15,8 +18,12 @@ pub struct InitiateWithdrawParams {
     pub journal_id: JournalId,
     pub deposit_omnibus_account_id: CalaAccountId,
     pub credit_account_id: CalaAccountId,
+    pub withdrawal_limit_account_id: CalaAccountId,
+    pub withdrawal_limit_omnibus_account_id: CalaAccountId,
     pub amount: Decimal,
     pub currency: Currency,
+    pub withdrawal_limit: WithdrawalLimitMeta,
+    pub effective: chrono::NaiveDate,
 }
 
 impl InitiateWithdrawParams {
@@ -47,11 +54,26 @@ impl InitiateWithdrawParams {
                 .r#type(ParamDataType::Uuid)
                 .build()
                 .unwrap(),
+            NewParamDefinition::builder()
+                .name("withdrawal_limit_account_id")
+                .r#type(ParamDataType::Uuid)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("withdrawal_limit_omnibus_account_id")
+                .r#type(ParamDataType::Uuid)
+                .build()
+                .unwrap(),
             NewParamDefinition::builder()
                 .name("effective")
                 .r#type(ParamDataType::Date)
                 .build()
                 .unwrap(),
+            NewParamDefinition::builder()
+                .name("meta")
+                .r#type(ParamDataType::Json)
+                .build()
+                .unwrap(),
         ]
     }
 }
@@ -62,10 +84,16 @@ impl From<InitiateWithdrawParams> for Params {
             journal_id,
             deposit_omnibus_account_id,
             credit_account_id,
+            withdrawal_limit_account_id,
+            withdrawal_limit_omnibus_account_id,
             amount,
             currency,
+            withdrawal_limit,
+            effective,
         }: InitiateWithdrawParams,
     ) -> Self {
+        let meta = serde_json::to_value(withdrawal_limit).expect("Couldn't serialize meta");
+
         let mut params = Self::default();
 
         params.insert("journal_id", journal_id);
@@ -73,7 +101,13 @@ impl From<InitiateWithdrawParams> for Params {
         params.insert("amount", amount);
         params.insert("deposit_omnibus_account_id", deposit_omnibus_account_id);
         params.insert("credit_account_id", credit_account_id);
-        params.insert("effective", chrono::Utc::now().date_naive());
+        params.insert("withdrawal_limit_account_id", withdrawal_limit_account_id);
+        params.insert(
+            "withdrawal_limit_omnibus_account_id",
+            withdrawal_limit_omnibus_account_id,
+        );
+        params.insert("effective", effective);
+        params.insert("meta", meta);
 
         params
     }
@@ -87,6 +121,7 @@ impl InitiateWithdraw {
         let tx_input = NewTxTemplateTransaction::builder()
             .journal_id("params.journal_id")
             .effective("params.effective")
+            .metadata("params.meta")
             .description("'Initiate a withdraw'")
             .build()
             .expect("Couldn't build TxInput");
@@ -127,6 +162,24 @@ impl InitiateWithdraw {
                 .units("params.amount")
                 .build()
                 .expect("Couldn't build entry"),
+            NewTxTemplateEntry::builder()
+                .entry_type("'INITIATE_WITHDRAW_LIMIT_DR'")
+                .currency("params.currency")
+                .account_id("params.withdrawal_limit_account_id")
+                .direction("DEBIT")
+                .layer("SETTLED")
+                .units("params.amount")
+                .build()
+                .expect("Couldn't build entry"),
+            NewTxTemplateEntry::builder()
+                .entry_type("'INITIATE_WITHDRAW_LIMIT_CR'")
+                .currency("params.currency")
+                .account_id("params.withdrawal_limit_omnibus_account_id")
+                .direction("CREDIT")
+                .layer("SETTLED")
+                .units("params.amount")
+                .build()
+                .expect("Couldn't build entry"),
         ];
 
         let params = InitiateWithdrawParams::defs();
@@ -2,8 +2,14 @@ mod cancel_withdraw;
 mod confirm_withdraw;
 mod initiate_withdraw;
 mod record_deposit;
+mod release_withdrawal_limit;
+mod return_withdraw;
+mod settle_closing_balance;
 
 pub use cancel_withdraw::*;
 pub use confirm_withdraw::*;
 pub use initiate_withdraw::*;
 pub use record_deposit::*;
+pub use release_withdrawal_limit::*;
+pub use return_withdraw::*;
+pub use settle_closing_balance::*;
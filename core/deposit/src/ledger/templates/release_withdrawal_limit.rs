@@ -0,0 +1,149 @@
+use rust_decimal::Decimal;
+use tracing::instrument;
+
+use cala_ledger::{
+    tx_template::{error::TxTemplateError, Params, *},
+    *,
+};
+
+use crate::{
+    ledger::{error::*, velocity::WithdrawalLimitMeta},
+    primitives::CalaAccountId,
+};
+
+pub const RELEASE_WITHDRAWAL_LIMIT_CODE: &str = "RELEASE_WITHDRAWAL_LIMIT";
+
+/// Credits a withdrawal back to its withdrawal limit account for the windows
+/// it no longer counts towards.
+#[derive(Debug)]
+pub struct ReleaseWithdrawalLimitParams {
+    pub journal_id: JournalId,
+    pub currency: Currency,
+    pub amount: Decimal,
+    pub withdrawal_limit_account_id: CalaAccountId,
+    pub withdrawal_limit_omnibus_account_id: CalaAccountId,
+    pub withdrawal_limit: WithdrawalLimitMeta,
+    pub effective: chrono::NaiveDate,
+}
+
+impl ReleaseWithdrawalLimitParams {
+    pub fn defs() -> Vec<NewParamDefinition> {
+        vec![
+            NewParamDefinition::builder()
+                .name("journal_id")
+                .r#type(ParamDataType::Uuid)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("currency")
+                .r#type(ParamDataType::String)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("amount")
+                .r#type(ParamDataType::Decimal)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("withdrawal_limit_account_id")
+                .r#type(ParamDataType::Uuid)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("withdrawal_limit_omnibus_account_id")
+                .r#type(ParamDataType::Uuid)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("effective")
+                .r#type(ParamDataType::Date)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("meta")
+                .r#type(ParamDataType::Json)
+                .build()
+                .unwrap(),
+        ]
+    }
+}
+
+impl From<ReleaseWithdrawalLimitParams> for Params {
+    fn from(
+        ReleaseWithdrawalLimitParams {
+            journal_id,
+            currency,
+            amount,
+            withdrawal_limit_account_id,
+            withdrawal_limit_omnibus_account_id,
+            withdrawal_limit,
+            effective,
+        }: ReleaseWithdrawalLimitParams,
+    ) -> Self {
+        let meta = serde_json::to_value(withdrawal_limit).expect("Couldn't serialize meta");
+
+        let mut params = Self::default();
+        params.insert("journal_id", journal_id);
+        params.insert("currency", currency);
+        params.insert("amount", amount);
+        params.insert("withdrawal_limit_account_id", withdrawal_limit_account_id);
+        params.insert(
+            "withdrawal_limit_omnibus_account_id",
+            withdrawal_limit_omnibus_account_id,
+        );
+        params.insert("effective", effective);
+        params.insert("meta", meta);
+
+        params
+    }
+}
+
+pub struct ReleaseWithdrawalLimit;
+
+impl ReleaseWithdrawalLimit {
+    #[instrument(name = "ledger.release_withdrawal_limit.init", skip_all)]
+    pub async fn init(ledger: &CalaLedger) -> Result<(), DepositLedgerError> {
+        let tx_input = NewTxTemplateTransaction::builder()
+            .journal_id("params.journal_id")
+            .effective("params.effective")
+            .metadata("params.meta")
+            .description("'Release a withdrawal from its withdrawal limits'")
+            .build()
+            .expect("Couldn't build TxInput");
+        let entries = vec![
+            NewTxTemplateEntry::builder()
+                .entry_type("'RELEASE_WITHDRAWAL_LIMIT_CR'")
+                .currency("params.currency")
+                .account_id("params.withdrawal_limit_account_id")
+                .direction("CREDIT")
+                .layer("SETTLED")
+                .units("params.amount")
+                .build()
+                .expect("Couldn't build entry"),
+            NewTxTemplateEntry::builder()
+                .entry_type("'RELEASE_WITHDRAWAL_LIMIT_DR'")
+                .currency("params.currency")
+                .account_id("params.withdrawal_limit_omnibus_account_id")
+                .direction("DEBIT")
+                .layer("SETTLED")
+                .units("params.amount")
+                .build()
+                .expect("Couldn't build entry"),
+        ];
+
+        let params = ReleaseWithdrawalLimitParams::defs();
+        let template = NewTxTemplate::builder()
+            .id(TxTemplateId::new())
+            .code(RELEASE_WITHDRAWAL_LIMIT_CODE)
+            .transaction(tx_input)
+            .entries(entries)
+            .params(params)
+            .build()
+            .expect("Couldn't build template");
+        match ledger.tx_templates().create(template).await {
+            Err(TxTemplateError::DuplicateCode) => Ok(()),
+            Err(e) => Err(e.into()),
+            Ok(_) => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,130 @@
+use rust_decimal::Decimal;
+use tracing::instrument;
+
+use cala_ledger::{
+    tx_template::{error::TxTemplateError, Params, *},
+    *,
+};
+
+use crate::{ledger::error::*, primitives::CalaAccountId};
+
+pub const SETTLE_CLOSING_BALANCE_CODE: &str = "SETTLE_CLOSING_BALANCE";
+
+#[derive(Debug)]
+pub struct SettleClosingBalanceParams {
+    pub journal_id: JournalId,
+    pub currency: Currency,
+    pub amount: Decimal,
+    pub closing_account_id: CalaAccountId,
+    pub settlement_account_id: CalaAccountId,
+    pub effective: chrono::NaiveDate,
+}
+
+impl SettleClosingBalanceParams {
+    pub fn defs() -> Vec<NewParamDefinition> {
+        vec![
+            NewParamDefinition::builder()
+                .name("journal_id")
+                .r#type(ParamDataType::Uuid)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("currency")
+                .r#type(ParamDataType::String)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("amount")
+                .r#type(ParamDataType::Decimal)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("closing_account_id")
+                .r#type(ParamDataType::Uuid)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("settlement_account_id")
+                .r#type(ParamDataType::Uuid)
+                .build()
+                .unwrap(),
+            NewParamDefinition::builder()
+                .name("effective")
+                .r#type(ParamDataType::Date)
+                .build()
+                .unwrap(),
+        ]
+    }
+}
+
+impl From<SettleClosingBalanceParams> for Params {
+    fn from(
+        SettleClosingBalanceParams {
+            journal_id,
+            currency,
+            amount,
+            closing_account_id,
+            settlement_account_id,
+            effective,
+        }: SettleClosingBalanceParams,
+    ) -> Self {
+        let mut params = Self::default();
+        params.insert("journal_id", journal_id);
+        params.insert("currency", currency);
+        params.insert("amount", amount);
+        params.insert("closing_account_id", closing_account_id);
+        params.insert("settlement_account_id", settlement_account_id);
+        params.insert("effective", effective);
+
+        params
+    }
+}
+
+pub struct SettleClosingBalance;
+
+impl SettleClosingBalance {
+    #[instrument(name = "ledger.settle_closing_balance.init", skip_all)]
+    pub async fn init(ledger: &CalaLedger) -> Result<(), DepositLedgerError> {
+        let tx_input = NewTxTemplateTransaction::builder()
+            .journal_id("params.journal_id")
+            .effective("params.effective")
+            .description("'Settle the remaining balance of a closing deposit account'")
+            .build()
+            .expect("Couldn't build TxInput");
+        let entries = vec![
+            NewTxTemplateEntry::builder()
+                .entry_type("'SETTLE_CLOSING_BALANCE_DR'")
+                .currency("params.currency")
+                .account_id("params.closing_account_id")
+                .direction("DEBIT")
+                .layer("SETTLED")
+                .units("params.amount")
+                .build()
+                .expect("Couldn't build entry"),
+            NewTxTemplateEntry::builder()
+                .entry_type("'SETTLE_CLOSING_BALANCE_CR'")
+                .currency("params.currency")
+                .account_id("params.settlement_account_id")
+                .direction("CREDIT")
+                .layer("SETTLED")
+                .units("params.amount")
+                .build()
+                .expect("Couldn't build entry"),
+        ];
+
+        let params = SettleClosingBalanceParams::defs();
+        let template = NewTxTemplate::builder()
+            .id(TxTemplateId::new())
+            .code(SETTLE_CLOSING_BALANCE_CODE)
+            .transaction(tx_input)
+            .entries(entries)
+            .params(params)
+            .build()
+            .expect("Couldn't build template");
+        match ledger.tx_templates().create(template).await {
+            Err(TxTemplateError::DuplicateCode) => Ok(()),
+            Err(e) => Err(e.into()),
+            Ok(_) => Ok(()),
+        }
+    }
+}
@@ -1,3 +1,5 @@
 mod overdraft_prevention;
+mod withdrawal_limit;
 
 pub use overdraft_prevention::*;
+pub use withdrawal_limit::*;
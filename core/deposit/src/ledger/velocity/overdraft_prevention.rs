@@ -6,7 +6,8 @@ use crate::ledger::error::*;
 
 pub struct OverdraftPrevention;
 
-const OVERDRAFT_PREVENTION_ID: uuid::Uuid = uuid::uuid!("00000000-0000-0000-0000-000000000001");
+pub(crate) const OVERDRAFT_PREVENTION_ID: uuid::Uuid =
+    uuid::uuid!("00000000-0000-0000-0000-000000000001");
 
 impl OverdraftPrevention {
     #[instrument(name = "ledger.overdraft_prevention.init", skip_all)]
@@ -0,0 +1,191 @@
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use tracing::instrument;
+
+use cala_ledger::{
+    tx_template::{NewParamDefinition, ParamDataType, Params},
+    velocity::*,
+    *,
+};
+
+use crate::{
+    config::WithdrawalLimits,
+    ledger::error::*,
+    primitives::{CalaAccountId, WithdrawalLimitWindow},
+};
+
+const WITHDRAWAL_LIMITS_CONTROL_ID: uuid::Uuid =
+    uuid::uuid!("00000000-0000-0000-0000-000000000003");
+
+const DAILY_WITHDRAWAL_LIMIT_ID: uuid::Uuid = uuid::uuid!("00000000-0000-0000-0000-000000000007");
+const MONTHLY_WITHDRAWAL_LIMIT_ID: uuid::Uuid =
+    uuid::uuid!("00000000-0000-0000-0000-000000000008");
+
+const DAILY_LIMIT_PARAM: &str = "daily_limit";
+const MONTHLY_LIMIT_PARAM: &str = "monthly_limit";
+
+/// The accounts a deposit account's withdrawals are tracked on, one per KYC
+/// tier. An override gets its own account, keyed by the override id.
+#[derive(Debug, Copy, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WithdrawalLimitAccountIds {
+    pub basic: CalaAccountId,
+    pub advanced: CalaAccountId,
+}
+
+impl WithdrawalLimitAccountIds {
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> Self {
+        Self {
+            basic: CalaAccountId::new(),
+            advanced: CalaAccountId::new(),
+        }
+    }
+}
+
+/// Read by the withdrawal velocity limits to select the windows an entry
+/// counts towards.
+#[derive(Debug, Clone, Serialize)]
+pub struct WithdrawalLimitMeta {
+    withdrawal_limit_daily: bool,
+    withdrawal_limit_monthly: bool,
+}
+
+impl WithdrawalLimitMeta {
+    pub fn new(windows: &[WithdrawalLimitWindow]) -> Self {
+        Self {
+            withdrawal_limit_daily: windows.contains(&WithdrawalLimitWindow::Daily),
+            withdrawal_limit_monthly: windows.contains(&WithdrawalLimitWindow::Monthly),
+        }
+    }
+}
+
+/// Rolling daily and monthly caps on the amount leaving a deposit account
+/// through withdrawals.
+///
+/// The limits are not enforced on the deposit account itself but on a
+/// tracking account per tier that only the withdrawal templates post to, so
+/// the tier in effect is picked by the account a withdrawal is held on and
+/// the limits never see entries lacking their metadata.
+///
+/// Cala has no notion of a rolling window, so the limits accumulate without
+/// one: a withdrawal debits the tracking account for every window when it is
+/// initiated and credits a window back when it stops counting towards it,
+/// either because it was cancelled or denied or because the window has
+/// elapsed.
+pub struct WithdrawalLimit;
+
+impl WithdrawalLimit {
+    #[instrument(name = "ledger.withdrawal_limit.init", skip_all)]
+    pub async fn init(ledger: &CalaLedger) -> Result<VelocityControlId, DepositLedgerError> {
+        let control = NewVelocityControl::builder()
+            .id(WITHDRAWAL_LIMITS_CONTROL_ID)
+            .name("Withdrawal Limits")
+            .description("Velocity Control for withdrawal limits")
+            .build()
+            .expect("build control");
+        let control_id = match ledger.velocities().create_control(control).await {
+            Err(cala_ledger::velocity::error::VelocityError::ControlIdAlreadyExists) => {
+                WITHDRAWAL_LIMITS_CONTROL_ID.into()
+            }
+            Err(e) => return Err(e.into()),
+            Ok(control) => control.id(),
+        };
+
+        for window in WithdrawalLimitWindow::ALL {
+            let limit_id = match ledger
+                .velocities()
+                .create_limit(Self::new_limit(window))
+                .await
+            {
+                Err(cala_ledger::velocity::error::VelocityError::LimitIdAlreadyExists) => {
+                    Self::limit_id(window)
+                }
+                Err(e) => return Err(e.into()),
+                Ok(limit) => limit.id(),
+            };
+            match ledger
+                .velocities()
+                .add_limit_to_control(control_id, limit_id)
+                .await
+            {
+                Ok(_)
+                | Err(cala_ledger::velocity::error::VelocityError::LimitAlreadyAddedToControl) => {}
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(control_id)
+    }
+
+    /// The window a limit enforces, for reporting which one a withdrawal ran
+    /// into.
+    pub fn window_of(limit_id: VelocityLimitId) -> Option<WithdrawalLimitWindow> {
+        WithdrawalLimitWindow::ALL
+            .into_iter()
+            .find(|window| Self::limit_id(*window) == limit_id)
+    }
+
+    fn limit_id(window: WithdrawalLimitWindow) -> VelocityLimitId {
+        match window {
+            WithdrawalLimitWindow::Daily => DAILY_WITHDRAWAL_LIMIT_ID.into(),
+            WithdrawalLimitWindow::Monthly => MONTHLY_WITHDRAWAL_LIMIT_ID.into(),
+        }
+    }
+
+    fn new_limit(window: WithdrawalLimitWindow) -> NewVelocityLimit {
+        let param = match window {
+            WithdrawalLimitWindow::Daily => DAILY_LIMIT_PARAM,
+            WithdrawalLimitWindow::Monthly => MONTHLY_LIMIT_PARAM,
+        };
+        NewVelocityLimit::builder()
+            .id(Self::limit_id(window))
+            .name(format!("Withdrawal Limit ({window})"))
+            .description(format!("Limit {window} withdrawals"))
+            .window(vec![])
+            .condition(format!(
+                "context.vars.transaction.metadata.withdrawal_limit_{window}"
+            ))
+            .params(vec![NewParamDefinition::builder()
+                .name(param)
+                .r#type(ParamDataType::Decimal)
+                .build()
+                .expect("param definition")])
+            .limit(
+                NewLimit::builder()
+                    .balance(vec![NewBalanceLimit::builder()
+                        .layer("SETTLED")
+                        .amount(format!("params.{param}"))
+                        .enforcement_direction("DEBIT")
+                        .build()
+                        .expect("balance limit")])
+                    .build()
+                    .expect("limit"),
+            )
+            .build()
+            .expect("velocity limit")
+    }
+}
+
+#[derive(Debug)]
+pub struct WithdrawalLimitParams {
+    pub daily: Decimal,
+    pub monthly: Decimal,
+}
+
+impl From<WithdrawalLimits> for WithdrawalLimitParams {
+    fn from(limits: WithdrawalLimits) -> Self {
+        Self {
+            daily: limits.daily.to_usd(),
+            monthly: limits.monthly.to_usd(),
+        }
+    }
+}
+
+impl From<WithdrawalLimitParams> for Params {
+    fn from(WithdrawalLimitParams { daily, monthly }: WithdrawalLimitParams) -> Self {
+        let mut params = Self::default();
+        params.insert(DAILY_LIMIT_PARAM, daily);
+        params.insert(MONTHLY_LIMIT_PARAM, monthly);
+        params
+    }
+}
@@ -2,7 +2,11 @@
 #![cfg_attr(feature = "fail-on-warnings", deny(clippy::all))]
 
 mod account;
+mod bank_statement_exception;
+mod bank_statement_import;
+mod beneficiary;
 mod chart_of_accounts_integration;
+mod config;
 mod deposit;
 mod deposit_account_balance;
 pub mod error;
@@ -10,25 +14,42 @@ mod event;
 mod for_subject;
 mod history;
 mod ledger;
+mod payout_batch;
 mod primitives;
 mod processes;
 mod publisher;
+mod statement;
 mod withdrawal;
+mod withdrawal_limit_override;
 
 use deposit_account_cursor::DepositAccountsByCreatedAtCursor;
 use tracing::instrument;
 
-use audit::AuditSvc;
+use audit::{AuditInfo, AuditSvc};
 use authz::PermissionCheck;
 use cala_ledger::CalaLedger;
-use core_accounting::Chart;
+use cloud_storage::Storage;
+use core_accounting::{AccountingPeriodCheck, Chart};
 use governance::{Governance, GovernanceEvent};
 use job::Jobs;
 use outbox::{Outbox, OutboxEventMarker};
 
 pub use account::DepositAccount;
 use account::*;
+use bank_statement_exception::*;
+pub use bank_statement_exception::{
+    BankStatementException, BankStatementExceptionReason, BankStatementExceptionStatus,
+    BankStatementExceptionsByCreatedAtCursor,
+};
+use bank_statement_import::*;
+pub use bank_statement_import::{
+    BankStatementCsvMapping, BankStatementFormat, BankStatementImport, BankStatementImportSummary,
+    BankStatementImportsByCreatedAtCursor, BankStatementLine,
+};
+use beneficiary::*;
+pub use beneficiary::{Beneficiary, BeneficiaryStatus};
 pub use chart_of_accounts_integration::ChartOfAccountsIntegrationConfig;
+pub use config::*;
 use deposit::*;
 pub use deposit::{Deposit, DepositsByCreatedAtCursor};
 pub use deposit_account_balance::DepositAccountBalance;
@@ -37,14 +58,38 @@ pub use event::*;
 pub use for_subject::DepositsForSubject;
 pub use history::{DepositAccountHistoryCursor, DepositAccountHistoryEntry};
 use ledger::*;
+use payout_batch::*;
+pub use payout_batch::{
+    PayoutBatch, PayoutBatchDownloadLink, PayoutBatchItem, PayoutBatchStatus,
+    PayoutBatchesByCreatedAtCursor, PayoutFileFormat,
+};
 pub use primitives::*;
 pub use processes::approval::APPROVE_WITHDRAWAL_PROCESS;
 use processes::approval::{
     ApproveWithdrawal, WithdrawApprovalJobConfig, WithdrawApprovalJobInitializer,
 };
+pub use processes::beneficiary_approval::APPROVE_BENEFICIARY_PROCESS;
+use processes::beneficiary_approval::{
+    ApproveBeneficiary, BeneficiaryApprovalJobConfig, BeneficiaryApprovalJobInitializer,
+};
+pub use processes::withdrawal_limit_override_approval::APPROVE_WITHDRAWAL_LIMIT_OVERRIDE_PROCESS;
+use processes::withdrawal_limit_override_approval::{
+    ApproveWithdrawalLimitOverride, WithdrawalLimitOverrideApprovalJobConfig,
+    WithdrawalLimitOverrideApprovalJobInitializer,
+};
 use publisher::DepositPublisher;
+use statement::*;
+pub use statement::{
+    DepositAccountStatement, DepositAccountStatementDownloadLink, DepositAccountStatementFormat,
+    DepositAccountStatementStatus, DepositAccountStatementSummary,
+};
 use withdrawal::*;
-pub use withdrawal::{Withdrawal, WithdrawalStatus, WithdrawalsByCreatedAtCursor};
+pub use withdrawal::{
+    Withdrawal, WithdrawalBeneficiary, WithdrawalPayoutStatus, WithdrawalStatus,
+    WithdrawalsByCreatedAtCursor,
+};
+use withdrawal_limit_override::*;
+pub use withdrawal_limit_override::{WithdrawalLimitOverride, WithdrawalLimitOverrideStatus};
 
 pub struct CoreDeposit<Perms, E>
 where
@@ -54,12 +99,23 @@ where
     accounts: DepositAccountRepo<E>,
     deposits: DepositRepo<E>,
     withdrawals: WithdrawalRepo<E>,
+    statements: DepositAccountStatementRepo,
+    withdrawal_limit_overrides: WithdrawalLimitOverrideRepo,
+    bank_statement_imports: BankStatementImportRepo,
+    bank_statement_exceptions: BankStatementExceptionRepo,
+    payout_batches: PayoutBatchRepo,
+    beneficiaries: BeneficiaryRepo,
     approve_withdrawal: ApproveWithdrawal<Perms, E>,
+    approve_withdrawal_limit_override: ApproveWithdrawalLimitOverride<Perms, E>,
+    approve_beneficiary: ApproveBeneficiary<Perms, E>,
     ledger: DepositLedger,
+    storage: Storage,
     cala: CalaLedger,
     authz: Perms,
     governance: Governance<Perms, E>,
     outbox: Outbox<E>,
+    jobs: Jobs,
+    config: DepositConfig,
 }
 
 impl<Perms, E> Clone for CoreDeposit<Perms, E>
@@ -72,12 +128,23 @@ where
             accounts: self.accounts.clone(),
             deposits: self.deposits.clone(),
             withdrawals: self.withdrawals.clone(),
+            statements: self.statements.clone(),
+            withdrawal_limit_overrides: self.withdrawal_limit_overrides.clone(),
+            bank_statement_imports: self.bank_statement_imports.clone(),
+            bank_statement_exceptions: self.bank_statement_exceptions.clone(),
+            payout_batches: self.payout_batches.clone(),
+            beneficiaries: self.beneficiaries.clone(),
             ledger: self.ledger.clone(),
+            storage: self.storage.clone(),
             cala: self.cala.clone(),
             authz: self.authz.clone(),
             governance: self.governance.clone(),
             approve_withdrawal: self.approve_withdrawal.clone(),
+            approve_withdrawal_limit_override: self.approve_withdrawal_limit_override.clone(),
+            approve_beneficiary: self.approve_beneficiary.clone(),
             outbox: self.outbox.clone(),
+            jobs: self.jobs.clone(),
+            config: self.config.clone(),
         }
     }
 }
@@ -94,10 +161,12 @@ where
     #[allow(clippy::too_many_arguments)]
     pub async fn init(
         pool: &sqlx::PgPool,
+        config: DepositConfig,
         authz: &Perms,
         outbox: &Outbox<E>,
         governance: &Governance<Perms, E>,
         jobs: &Jobs,
+        storage: &Storage,
         cala: &CalaLedger,
         journal_id: CalaJournalId,
     ) -> Result<Self, CoreDepositError> {
@@ -105,15 +174,78 @@ where
         let accounts = DepositAccountRepo::new(pool, &publisher);
         let deposits = DepositRepo::new(pool, &publisher);
         let withdrawals = WithdrawalRepo::new(pool, &publisher);
-        let ledger = DepositLedger::init(cala, journal_id).await?;
+        let statements = DepositAccountStatementRepo::new(pool);
+        let withdrawal_limit_overrides = WithdrawalLimitOverrideRepo::new(pool);
+        let bank_statement_imports = BankStatementImportRepo::new(pool);
+        let bank_statement_exceptions = BankStatementExceptionRepo::new(pool);
+        let payout_batches = PayoutBatchRepo::new(pool);
+        let beneficiaries = BeneficiaryRepo::new(pool);
+        let ledger =
+            DepositLedger::init(cala, journal_id, &AccountingPeriodCheck::new(pool)).await?;
 
-        let approve_withdrawal = ApproveWithdrawal::new(&withdrawals, authz.audit(), governance);
+        let approve_withdrawal =
+            ApproveWithdrawal::new(&withdrawals, &ledger, authz.audit(), governance);
+        let approve_withdrawal_limit_override = ApproveWithdrawalLimitOverride::new(
+            &withdrawal_limit_overrides,
+            &accounts,
+            &ledger,
+            authz.audit(),
+        );
+        let approve_beneficiary =
+            ApproveBeneficiary::new(&beneficiaries, authz.audit(), governance);
 
         jobs.add_initializer_and_spawn_unique(
             WithdrawApprovalJobInitializer::new(outbox, &approve_withdrawal),
             WithdrawApprovalJobConfig::<Perms, E>::new(),
         )
         .await?;
+        jobs.add_initializer_and_spawn_unique(
+            WithdrawalLimitOverrideApprovalJobInitializer::new(
+                outbox,
+                &approve_withdrawal_limit_override,
+            ),
+            WithdrawalLimitOverrideApprovalJobConfig::<Perms, E>::new(),
+        )
+        .await?;
+        jobs.add_initializer_and_spawn_unique(
+            BeneficiaryApprovalJobInitializer::new(outbox, &approve_beneficiary),
+            BeneficiaryApprovalJobConfig::<Perms, E>::new(),
+        )
+        .await?;
+
+        jobs.add_initializer(WithdrawalLimitReleaseJobInitializer::<Perms, E>::new(
+            &withdrawals,
+            &ledger,
+            authz.audit(),
+        ));
+        jobs.add_initializer(GenerateDepositAccountStatementInitializer::<Perms, E>::new(
+            &statements,
+            &accounts,
+            &ledger,
+            storage,
+            authz.audit(),
+        ));
+        jobs.add_initializer_and_spawn_unique(
+            MonthEndDepositAccountStatementsJobInitializer::<Perms, E>::new(
+                &statements,
+                &accounts,
+                jobs,
+                authz.audit(),
+            ),
+            MonthEndDepositAccountStatementsJobConfig::<Perms, E>::new(),
+        )
+        .await?;
+
+        jobs.add_initializer_and_spawn_unique(
+            DormantDepositAccountsJobInitializer::<Perms, E>::new(
+                &accounts,
+                &ledger,
+                config.dormant_account_after(),
+                authz.audit(),
+            ),
+            DormantDepositAccountsJobConfig::<Perms, E>::new(),
+        )
+        .await?;
 
         match governance.init_policy(APPROVE_WITHDRAWAL_PROCESS).await {
             Err(governance::error::GovernanceError::PolicyError(
@@ -123,16 +255,46 @@ where
             _ => (),
         }
 
+        match governance
+            .init_policy(APPROVE_WITHDRAWAL_LIMIT_OVERRIDE_PROCESS)
+            .await
+        {
+            Err(governance::error::GovernanceError::PolicyError(
+                governance::policy_error::PolicyError::DuplicateApprovalProcessType,
+            )) => (),
+            Err(e) => return Err(e.into()),
+            _ => (),
+        }
+
+        match governance.init_policy(APPROVE_BENEFICIARY_PROCESS).await {
+            Err(governance::error::GovernanceError::PolicyError(
+                governance::policy_error::PolicyError::DuplicateApprovalProcessType,
+            )) => (),
+            Err(e) => return Err(e.into()),
+            _ => (),
+        }
+
         let res = Self {
             accounts,
             deposits,
             withdrawals,
+            statements,
+            withdrawal_limit_overrides,
+            bank_statement_imports,
+            bank_statement_exceptions,
+            payout_batches,
+            beneficiaries,
+            storage: storage.clone(),
             authz: authz.clone(),
             outbox: outbox.clone(),
+            jobs: jobs.clone(),
             governance: governance.clone(),
             cala: cala.clone(),
             approve_withdrawal,
+            approve_withdrawal_limit_override,
+            approve_beneficiary,
             ledger,
+            config,
         };
         Ok(res)
     }
@@ -153,7 +315,9 @@ where
             &self.accounts,
             &self.deposits,
             &self.withdrawals,
+            &self.statements,
             &self.ledger,
+            &self.storage,
             &self.authz,
         ))
     }
@@ -179,7 +343,9 @@ where
             )
             .await?;
 
+        let deposit_account_type = deposit_account_type.into();
         let account_id = DepositAccountId::new();
+        let withdrawal_limit_account_ids = WithdrawalLimitAccountIds::new();
         let new_account = NewDepositAccount::builder()
             .id(account_id)
             .account_holder_id(holder_id)
@@ -187,6 +353,8 @@ where
             .name(name.to_string())
             .description(name.to_string())
             .active(active)
+            .deposit_account_type(deposit_account_type)
+            .withdrawal_limit_account_ids(withdrawal_limit_account_ids)
             .audit_info(audit_info.clone())
             .build()
             .expect("Could not build new account");
@@ -201,6 +369,13 @@ where
                 account.reference.to_string(),
                 account.name.to_string(),
                 deposit_account_type,
+                withdrawal_limit_account_ids,
+                self.config
+                    .withdrawal_limits
+                    .limits_for(KycLevel::Basic, deposit_account_type),
+                self.config
+                    .withdrawal_limits
+                    .limits_for(KycLevel::Advanced, deposit_account_type),
             )
             .await?;
 
@@ -263,6 +438,151 @@ where
         Ok(())
     }
 
+    #[instrument(name = "deposit.update_account_kyc_level_for_holder", skip(self), err)]
+    pub async fn update_account_kyc_level_for_holder(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        holder_id: impl Into<DepositAccountHolderId> + std::fmt::Debug,
+        kyc_level: KycLevel,
+    ) -> Result<(), CoreDepositError> {
+        let holder_id = holder_id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::all_deposit_accounts(),
+                CoreDepositAction::DEPOSIT_ACCOUNT_UPDATE_KYC_LEVEL,
+            )
+            .await?;
+
+        let mut op = self.accounts.begin_op().await?;
+        let mut after = None;
+        loop {
+            let ret = self
+                .accounts
+                .list_for_account_holder_id_by_id(
+                    holder_id,
+                    es_entity::PaginatedQueryArgs { first: 100, after },
+                    Default::default(),
+                )
+                .await?;
+            for mut account in ret.entities.into_iter() {
+                if account
+                    .update_kyc_level(kyc_level, audit_info.clone())
+                    .did_execute()
+                {
+                    self.accounts.update_in_op(&mut op, &mut account).await?;
+                }
+            }
+            if !ret.has_next_page {
+                break;
+            }
+            after = ret.end_cursor;
+        }
+        op.commit().await?;
+        Ok(())
+    }
+
+    pub async fn begin_op(&self) -> Result<es_entity::DbOp<'_>, CoreDepositError> {
+        Ok(self.accounts.begin_op().await?)
+    }
+
+    /// Closes the account for good. The account must not have pending
+    /// withdrawals or funds on hold; whatever is left on the settled balance is
+    /// paid out or transferred as part of the same operation.
+    #[instrument(name = "deposit.close_account", skip(self), err)]
+    pub async fn close_account(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        account_id: impl Into<DepositAccountId> + std::fmt::Debug,
+        settlement: DepositAccountClosureSettlement,
+    ) -> Result<DepositAccount, CoreDepositError> {
+        let op = self.accounts.begin_op().await?;
+        self.close_account_in_op(op, sub, account_id, settlement)
+            .await
+    }
+
+    /// Closes the account in an operation opened by the caller, so that any
+    /// checks the caller made in it are committed together with the closure.
+    /// The account is locked before its withdrawals and balance are checked.
+    #[instrument(name = "deposit.close_account_in_op", skip(self, op), err)]
+    pub async fn close_account_in_op(
+        &self,
+        mut op: es_entity::DbOp<'_>,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        account_id: impl Into<DepositAccountId> + std::fmt::Debug,
+        settlement: DepositAccountClosureSettlement,
+    ) -> Result<DepositAccount, CoreDepositError> {
+        let account_id = account_id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::deposit_account(account_id),
+                CoreDepositAction::DEPOSIT_ACCOUNT_CLOSE,
+            )
+            .await?;
+
+        self.accounts.lock_in_op(&mut op, account_id).await?;
+        let mut account = self.accounts.find_by_id_in_tx(op.tx(), account_id).await?;
+        if account.is_closed() {
+            return Ok(account);
+        }
+
+        let settlement_account_id = match settlement {
+            DepositAccountClosureSettlement::PayOut => None,
+            DepositAccountClosureSettlement::TransferTo(target_id) => {
+                if target_id == account_id {
+                    return Err(
+                        account::error::DepositAccountError::CannotTransferClosingBalanceToSelf
+                            .into(),
+                    );
+                }
+                self.check_account_active(target_id).await?;
+                Some(target_id.into())
+            }
+        };
+
+        if self.has_pending_withdrawals(&mut op, account_id).await? {
+            return Err(CoreDepositError::DepositAccountHasPendingWithdrawals);
+        }
+        let balance = self.ledger.balance(account_id).await?;
+        if balance.pending != UsdCents::ZERO {
+            return Err(CoreDepositError::DepositAccountHasHolds);
+        }
+
+        let settlement_tx_id = if balance.settled > UsdCents::ZERO {
+            Some(CalaTransactionId::new())
+        } else {
+            None
+        };
+
+        if account
+            .close(settlement, balance.settled, settlement_tx_id, audit_info)?
+            .was_ignored()
+        {
+            return Ok(account);
+        }
+        self.accounts.update_in_op(&mut op, &mut account).await?;
+
+        match settlement_tx_id {
+            Some(tx_id) => {
+                self.ledger
+                    .settle_closing_balance(
+                        op,
+                        tx_id,
+                        balance.settled,
+                        account_id,
+                        settlement_account_id,
+                    )
+                    .await?
+            }
+            None => op.commit().await?,
+        }
+
+        Ok(account)
+    }
+
     #[instrument(name = "deposit.account_history", skip(self), err)]
     pub async fn account_history(
         &self,
@@ -291,6 +611,62 @@ where
         Ok(history)
     }
 
+    #[instrument(name = "deposit.list_statements_for_account", skip(self), err)]
+    pub async fn list_statements_for_account(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        account_id: impl Into<DepositAccountId> + std::fmt::Debug,
+    ) -> Result<Vec<DepositAccountStatement>, CoreDepositError> {
+        let account_id = account_id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::all_deposit_account_statements(),
+                CoreDepositAction::DEPOSIT_ACCOUNT_STATEMENT_LIST,
+            )
+            .await?;
+        Ok(self
+            .statements
+            .list_for_deposit_account_id_by_created_at(
+                account_id,
+                Default::default(),
+                es_entity::ListDirection::Descending,
+            )
+            .await?
+            .entities)
+    }
+
+    #[instrument(name = "deposit.generate_statement_download_link", skip(self), err)]
+    pub async fn generate_statement_download_link(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        statement_id: impl Into<DepositAccountStatementId> + std::fmt::Debug,
+        format: DepositAccountStatementFormat,
+    ) -> Result<DepositAccountStatementDownloadLink, CoreDepositError> {
+        let statement_id = statement_id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::deposit_account_statement(statement_id),
+                CoreDepositAction::DEPOSIT_ACCOUNT_STATEMENT_GENERATE_DOWNLOAD_LINK,
+            )
+            .await?;
+
+        let mut statement = self.statements.find_by_id(statement_id).await?;
+        let url = {
+            let location = statement.download_link_generated(format, audit_info)?;
+            self.storage.generate_download_link(&location).await?
+        };
+        self.statements.update(&mut statement).await?;
+
+        Ok(DepositAccountStatementDownloadLink {
+            deposit_account_statement_id: statement_id,
+            format,
+            url,
+        })
+    }
+
     #[instrument(name = "deposit.record_deposit", skip(self), err)]
     pub async fn record_deposit(
         &self,
@@ -309,7 +685,27 @@ where
             )
             .await?;
         self.check_account_active(deposit_account_id).await?;
-        let deposit_id = DepositId::new();
+        let op = self.deposits.begin_op().await?;
+        self.record_deposit_in_op(
+            op,
+            DepositId::new(),
+            deposit_account_id,
+            amount,
+            reference,
+            audit_info,
+        )
+        .await
+    }
+
+    async fn record_deposit_in_op(
+        &self,
+        mut op: es_entity::DbOp<'_>,
+        deposit_id: DepositId,
+        deposit_account_id: DepositAccountId,
+        amount: UsdCents,
+        reference: Option<String>,
+        audit_info: AuditInfo,
+    ) -> Result<Deposit, CoreDepositError> {
         let new_deposit = NewDeposit::builder()
             .id(deposit_id)
             .ledger_transaction_id(deposit_id)
@@ -319,7 +715,6 @@ where
             .audit_info(audit_info)
             .build()?;
 
-        let mut op = self.deposits.begin_op().await?;
         let deposit = self.deposits.create_in_op(&mut op, new_deposit).await?;
         self.ledger
             .record_deposit(op, deposit_id, amount, deposit_account_id)
@@ -327,15 +722,462 @@ where
         Ok(deposit)
     }
 
+    /// Records a deposit for every credit on the statement that references
+    /// exactly one active deposit account, either by its id or by the id of
+    /// its holder. Lines that cannot be matched are queued as exceptions.
+    /// Re-importing a statement never credits the same line twice.
+    #[instrument(name = "deposit.import_bank_statement", skip(self, data), err)]
+    pub async fn import_bank_statement(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        file_name: impl Into<String> + std::fmt::Debug,
+        format: BankStatementFormat,
+        csv_mapping: Option<BankStatementCsvMapping>,
+        data: &str,
+    ) -> Result<BankStatementImport, CoreDepositError> {
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::all_bank_statement_imports(),
+                CoreDepositAction::BANK_STATEMENT_IMPORT_CREATE,
+            )
+            .await?;
+
+        let statement = parse_bank_statement(format, csv_mapping.as_ref(), data)
+            .map_err(bank_statement_import::error::BankStatementImportError::from)?;
+        let new_import = NewBankStatementImport::builder()
+            .id(BankStatementImportId::new())
+            .file_name(file_name)
+            .format(format)
+            .csv_mapping(csv_mapping)
+            .statement_account(statement.statement_account.clone())
+            .audit_info(audit_info.clone())
+            .build()?;
+        let mut import = self.bank_statement_imports.create(new_import).await?;
+
+        for line in statement.lines {
+            let reference = line.deposit_reference(&import.statement_account);
+            if self
+                .bank_statement_line_already_imported(&reference)
+                .await?
+            {
+                let _ = import.line_already_imported(reference);
+                continue;
+            }
+
+            // Each line's outcome is recorded in the same operation as its
+            // deposit or exception so an interrupted import can be re-run.
+            let mut op = self.deposits.begin_op().await?;
+            match self.match_bank_statement_line(&line).await? {
+                Ok(deposit_account_id) => {
+                    let deposit_id = DepositId::new();
+                    let _ = import.line_matched(
+                        reference.clone(),
+                        deposit_account_id,
+                        deposit_id,
+                        line.amount,
+                    );
+                    self.bank_statement_imports
+                        .update_in_op(&mut op, &mut import)
+                        .await?;
+                    self.record_deposit_in_op(
+                        op,
+                        deposit_id,
+                        deposit_account_id,
+                        line.amount,
+                        Some(reference),
+                        audit_info.clone(),
+                    )
+                    .await?;
+                }
+                Err(reason) => {
+                    let new_exception = NewBankStatementException::builder()
+                        .id(BankStatementExceptionId::new())
+                        .bank_statement_import_id(import.id)
+                        .reference(reference.clone())
+                        .line(line)
+                        .reason(reason)
+                        .audit_info(audit_info.clone())
+                        .build()?;
+                    let exception = self
+                        .bank_statement_exceptions
+                        .create_in_op(&mut op, new_exception)
+                        .await?;
+                    let _ = import.line_sent_to_exceptions(reference, exception.id);
+                    self.bank_statement_imports
+                        .update_in_op(&mut op, &mut import)
+                        .await?;
+                    op.commit().await?;
+                }
+            }
+        }
+        self.bank_statement_imports.update(&mut import).await?;
+
+        Ok(import)
+    }
+
+    async fn bank_statement_line_already_imported(
+        &self,
+        reference: &str,
+    ) -> Result<bool, CoreDepositError> {
+        match self.deposits.find_by_reference(reference.to_string()).await {
+            Ok(_) => return Ok(true),
+            Err(e) if e.was_not_found() => (),
+            Err(e) => return Err(e.into()),
+        }
+        match self
+            .bank_statement_exceptions
+            .find_by_reference(reference.to_string())
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(e) if e.was_not_found() => Ok(false),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    async fn match_bank_statement_line(
+        &self,
+        line: &BankStatementLine,
+    ) -> Result<Result<DepositAccountId, BankStatementExceptionReason>, CoreDepositError> {
+        let ids = line.referenced_ids();
+        let account_ids: Vec<DepositAccountId> =
+            ids.iter().map(|id| DepositAccountId::from(*id)).collect();
+        let mut accounts: Vec<DepositAccount> = self
+            .accounts
+            .find_all(&account_ids)
+            .await?
+            .into_values()
+            .collect();
+        if accounts.is_empty() {
+            for id in ids {
+                accounts.extend(
+                    self.accounts
+                        .list_for_account_holder_id_by_created_at(
+                            DepositAccountHolderId::from(id),
+                            Default::default(),
+                            es_entity::ListDirection::Ascending,
+                        )
+                        .await?
+                        .entities
+                        .into_iter()
+                        .filter(|account| !account.is_closed()),
+                );
+            }
+        }
+
+        let account = match accounts.as_slice() {
+            [] => return Ok(Err(BankStatementExceptionReason::NoMatchingAccount)),
+            [account] => account,
+            _ => return Ok(Err(BankStatementExceptionReason::AmbiguousAccount)),
+        };
+        if !account.status.is_active() {
+            return Ok(Err(BankStatementExceptionReason::AccountNotActive));
+        }
+        Ok(Ok(account.id))
+    }
+
+    #[instrument(name = "deposit.find_bank_statement_import_by_id", skip(self), err)]
+    pub async fn find_bank_statement_import_by_id(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<BankStatementImportId> + std::fmt::Debug,
+    ) -> Result<Option<BankStatementImport>, CoreDepositError> {
+        let id = id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::bank_statement_import(id),
+                CoreDepositAction::BANK_STATEMENT_IMPORT_READ,
+            )
+            .await?;
+
+        match self.bank_statement_imports.find_by_id(id).await {
+            Ok(import) => Ok(Some(import)),
+            Err(e) if e.was_not_found() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "deposit.list_bank_statement_imports", skip(self), err)]
+    pub async fn list_bank_statement_imports(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        query: es_entity::PaginatedQueryArgs<BankStatementImportsByCreatedAtCursor>,
+    ) -> Result<
+        es_entity::PaginatedQueryRet<BankStatementImport, BankStatementImportsByCreatedAtCursor>,
+        CoreDepositError,
+    > {
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::all_bank_statement_imports(),
+                CoreDepositAction::BANK_STATEMENT_IMPORT_LIST,
+            )
+            .await?;
+        Ok(self
+            .bank_statement_imports
+            .list_by_created_at(query, es_entity::ListDirection::Descending)
+            .await?)
+    }
+
+    /// Exceptions are listed oldest first so the queue is worked in order.
+    #[instrument(name = "deposit.list_bank_statement_exceptions", skip(self), err)]
+    pub async fn list_bank_statement_exceptions(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        status: BankStatementExceptionStatus,
+        query: es_entity::PaginatedQueryArgs<BankStatementExceptionsByCreatedAtCursor>,
+    ) -> Result<
+        es_entity::PaginatedQueryRet<
+            BankStatementException,
+            BankStatementExceptionsByCreatedAtCursor,
+        >,
+        CoreDepositError,
+    > {
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::all_bank_statement_imports(),
+                CoreDepositAction::BANK_STATEMENT_IMPORT_LIST,
+            )
+            .await?;
+        Ok(self
+            .bank_statement_exceptions
+            .list_for_status_by_created_at(status, query, es_entity::ListDirection::Ascending)
+            .await?)
+    }
+
+    /// Credits the exception's amount to the chosen account using the same
+    /// deposit reference an automatic match would have used.
+    #[instrument(name = "deposit.resolve_bank_statement_exception", skip(self), err)]
+    pub async fn resolve_bank_statement_exception(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        exception_id: impl Into<BankStatementExceptionId> + std::fmt::Debug,
+        deposit_account_id: impl Into<DepositAccountId> + std::fmt::Debug,
+    ) -> Result<BankStatementException, CoreDepositError> {
+        let exception_id = exception_id.into();
+        let deposit_account_id = deposit_account_id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::all_bank_statement_imports(),
+                CoreDepositAction::BANK_STATEMENT_IMPORT_RESOLVE_EXCEPTION,
+            )
+            .await?;
+
+        let mut exception = self
+            .bank_statement_exceptions
+            .find_by_id(exception_id)
+            .await?;
+        let deposit_id = DepositId::new();
+        if exception
+            .resolve(deposit_account_id, deposit_id, audit_info.clone())?
+            .was_ignored()
+        {
+            return Ok(exception);
+        }
+        self.check_account_active(deposit_account_id).await?;
+
+        let mut op = self.bank_statement_exceptions.begin_op().await?;
+        self.bank_statement_exceptions
+            .update_in_op(&mut op, &mut exception)
+            .await?;
+        self.record_deposit_in_op(
+            op,
+            deposit_id,
+            deposit_account_id,
+            exception.line.amount,
+            Some(exception.reference.clone()),
+            audit_info,
+        )
+        .await?;
+
+        Ok(exception)
+    }
+
+    #[instrument(name = "deposit.dismiss_bank_statement_exception", skip(self), err)]
+    pub async fn dismiss_bank_statement_exception(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        exception_id: impl Into<BankStatementExceptionId> + std::fmt::Debug,
+        note: String,
+    ) -> Result<BankStatementException, CoreDepositError> {
+        let exception_id = exception_id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::all_bank_statement_imports(),
+                CoreDepositAction::BANK_STATEMENT_IMPORT_RESOLVE_EXCEPTION,
+            )
+            .await?;
+
+        let mut exception = self
+            .bank_statement_exceptions
+            .find_by_id(exception_id)
+            .await?;
+        if exception.dismiss(note, audit_info)?.did_execute() {
+            self.bank_statement_exceptions
+                .update(&mut exception)
+                .await?;
+        }
+
+        Ok(exception)
+    }
+
+    #[instrument(name = "deposit.register_beneficiary", skip(self), err)]
+    pub async fn register_beneficiary(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        holder_id: impl Into<DepositAccountHolderId> + std::fmt::Debug,
+        label: String,
+        details: BeneficiaryDetails,
+    ) -> Result<Beneficiary, CoreDepositError>
+    where
+        governance::CommitteeMemberId:
+            for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+    {
+        let holder_id = holder_id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::all_beneficiaries(),
+                CoreDepositAction::BENEFICIARY_REGISTER,
+            )
+            .await?;
+
+        let id = BeneficiaryId::new();
+        let new_beneficiary = NewBeneficiary::builder()
+            .id(id)
+            .deposit_account_holder_id(holder_id)
+            .label(label)
+            .details(details)
+            .approval_process_id(id)
+            .cooling_off_until(chrono::Utc::now() + self.config.beneficiary_cooling_off())
+            .audit_info(audit_info)
+            .build()?;
+
+        let mut op = self.beneficiaries.begin_op().await?;
+        self.governance
+            .start_process(
+                &mut op,
+                id,
+                id.to_string(),
+                APPROVE_BENEFICIARY_PROCESS,
+                None,
+                sub,
+            )
+            .await?;
+        let beneficiary = self
+            .beneficiaries
+            .create_in_op(&mut op, new_beneficiary)
+            .await?;
+        op.commit().await?;
+
+        Ok(self
+            .approve_beneficiary
+            .execute_from_svc(&beneficiary)
+            .await?
+            .unwrap_or(beneficiary))
+    }
+
+    #[instrument(name = "deposit.revoke_beneficiary", skip(self), err)]
+    pub async fn revoke_beneficiary(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        beneficiary_id: impl Into<BeneficiaryId> + std::fmt::Debug,
+    ) -> Result<Beneficiary, CoreDepositError> {
+        let beneficiary_id = beneficiary_id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::beneficiary(beneficiary_id),
+                CoreDepositAction::BENEFICIARY_REVOKE,
+            )
+            .await?;
+
+        let mut beneficiary = self.beneficiaries.find_by_id(beneficiary_id).await?;
+        if beneficiary.revoke(audit_info).did_execute() {
+            self.beneficiaries.update(&mut beneficiary).await?;
+        }
+
+        Ok(beneficiary)
+    }
+
+    #[instrument(name = "deposit.find_beneficiary_by_id", skip(self), err)]
+    pub async fn find_beneficiary_by_id(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<BeneficiaryId> + std::fmt::Debug,
+    ) -> Result<Option<Beneficiary>, CoreDepositError> {
+        let id = id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::beneficiary(id),
+                CoreDepositAction::BENEFICIARY_READ,
+            )
+            .await?;
+
+        match self.beneficiaries.find_by_id(id).await {
+            Ok(beneficiary) => Ok(Some(beneficiary)),
+            Err(e) if e.was_not_found() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(
+        name = "deposit.list_beneficiaries_for_account_holder",
+        skip(self),
+        err
+    )]
+    pub async fn list_beneficiaries_for_account_holder(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        holder_id: impl Into<DepositAccountHolderId> + std::fmt::Debug,
+    ) -> Result<Vec<Beneficiary>, CoreDepositError> {
+        let holder_id = holder_id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::all_beneficiaries(),
+                CoreDepositAction::BENEFICIARY_LIST,
+            )
+            .await?;
+        Ok(self
+            .beneficiaries
+            .list_for_deposit_account_holder_id_by_created_at(
+                holder_id,
+                Default::default(),
+                es_entity::ListDirection::Descending,
+            )
+            .await?
+            .entities)
+    }
+
+    /// Every withdrawal is sent to a beneficiary of the account holder that
+    /// has been approved and is past its cooling-off period.
     #[instrument(name = "deposit.initiate_withdrawal", skip(self), err)]
     pub async fn initiate_withdrawal(
         &self,
         sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
         deposit_account_id: impl Into<DepositAccountId> + std::fmt::Debug,
+        beneficiary_id: impl Into<BeneficiaryId> + std::fmt::Debug,
         amount: UsdCents,
         reference: Option<String>,
-    ) -> Result<Withdrawal, CoreDepositError> {
+    ) -> Result<Withdrawal, CoreDepositError>
+    where
+        governance::CommitteeMemberId:
+            for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+    {
         let deposit_account_id = deposit_account_id.into();
+        let beneficiary_id = beneficiary_id.into();
         let audit_info = self
             .authz
             .enforce_permission(
@@ -344,7 +1186,24 @@ where
                 CoreDepositAction::WITHDRAWAL_INITIATE,
             )
             .await?;
-        self.check_account_active(deposit_account_id).await?;
+        let account = self.check_account_active(deposit_account_id).await?;
+        let beneficiary = self
+            .verified_beneficiary_for(&account, beneficiary_id)
+            .await?;
+
+        let mut op = self.withdrawals.begin_op().await?;
+        self.accounts
+            .lock_in_op(&mut op, deposit_account_id)
+            .await?;
+        let account = self
+            .accounts
+            .find_by_id_in_tx(op.tx(), deposit_account_id)
+            .await?;
+        if !account.status.is_active() {
+            return Err(CoreDepositError::DepositAccountNotActive);
+        }
+        let withdrawal_limit_account_id = account.withdrawal_limit_account_id();
+
         let withdrawal_id = WithdrawalId::new();
         let new_withdrawal = NewWithdrawal::builder()
             .id(withdrawal_id)
@@ -352,29 +1211,173 @@ where
             .amount(amount)
             .approval_process_id(withdrawal_id)
             .reference(reference)
+            .beneficiary(WithdrawalBeneficiary {
+                id: beneficiary.id,
+                label: beneficiary.label,
+                details: beneficiary.details,
+            })
+            .withdrawal_limit_account_id(withdrawal_limit_account_id)
             .audit_info(audit_info)
             .build()?;
 
-        let mut op = self.withdrawals.begin_op().await?;
         self.governance
             .start_process(
                 &mut op,
                 withdrawal_id,
                 withdrawal_id.to_string(),
                 APPROVE_WITHDRAWAL_PROCESS,
+                Some(amount),
+                sub,
             )
             .await?;
         let withdrawal = self
             .withdrawals
             .create_in_op(&mut op, new_withdrawal)
             .await?;
+        let now = chrono::Utc::now();
+        for window in WithdrawalLimitWindow::ALL {
+            self.jobs
+                .create_and_spawn_at_in_op(
+                    &mut op,
+                    job::JobId::new(),
+                    WithdrawalLimitReleaseJobConfig::<Perms, E>::new(withdrawal_id, window),
+                    now + window.length(),
+                )
+                .await?;
+        }
 
-        self.ledger
-            .initiate_withdrawal(op, withdrawal_id, amount, deposit_account_id)
-            .await?;
+        match self
+            .ledger
+            .initiate_withdrawal(
+                op,
+                withdrawal_id,
+                amount,
+                deposit_account_id,
+                withdrawal_limit_account_id,
+                WithdrawalLimitMeta::new(&WithdrawalLimitWindow::ALL),
+            )
+            .await
+        {
+            Err(ledger::error::DepositLedgerError::WithdrawalLimitExceeded {
+                window,
+                remaining,
+            }) => {
+                return Err(CoreDepositError::WithdrawalLimitExceeded { window, remaining });
+            }
+            Err(e) => return Err(e.into()),
+            Ok(_) => (),
+        }
         Ok(withdrawal)
     }
 
+    #[instrument(name = "deposit.request_withdrawal_limit_override", skip(self), err)]
+    pub async fn request_withdrawal_limit_override(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        deposit_account_id: impl Into<DepositAccountId> + std::fmt::Debug,
+        limits: WithdrawalLimits,
+        reason: String,
+    ) -> Result<WithdrawalLimitOverride, CoreDepositError>
+    where
+        governance::CommitteeMemberId:
+            for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+    {
+        let deposit_account_id = deposit_account_id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::all_withdrawal_limit_overrides(),
+                CoreDepositAction::WITHDRAWAL_LIMIT_OVERRIDE_REQUEST,
+            )
+            .await?;
+        self.accounts.find_by_id(deposit_account_id).await?;
+
+        let id = WithdrawalLimitOverrideId::new();
+        let new_override = NewWithdrawalLimitOverride::builder()
+            .id(id)
+            .deposit_account_id(deposit_account_id)
+            .limits(limits)
+            .reason(reason)
+            .approval_process_id(id)
+            .audit_info(audit_info)
+            .build()?;
+
+        let mut op = self.withdrawal_limit_overrides.begin_op().await?;
+        self.governance
+            .start_process(
+                &mut op,
+                id,
+                id.to_string(),
+                APPROVE_WITHDRAWAL_LIMIT_OVERRIDE_PROCESS,
+                None,
+                sub,
+            )
+            .await?;
+        let limit_override = self
+            .withdrawal_limit_overrides
+            .create_in_op(&mut op, new_override)
+            .await?;
+        op.commit().await?;
+
+        Ok(limit_override)
+    }
+
+    #[instrument(name = "deposit.find_withdrawal_limit_override_by_id", skip(self), err)]
+    pub async fn find_withdrawal_limit_override_by_id(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<WithdrawalLimitOverrideId> + std::fmt::Debug,
+    ) -> Result<Option<WithdrawalLimitOverride>, CoreDepositError> {
+        let id = id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::withdrawal_limit_override(id),
+                CoreDepositAction::WITHDRAWAL_LIMIT_OVERRIDE_READ,
+            )
+            .await?;
+
+        match self.withdrawal_limit_overrides.find_by_id(id).await {
+            Ok(limit_override) => Ok(Some(limit_override)),
+            Err(e) if e.was_not_found() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(
+        name = "deposit.list_withdrawal_limit_overrides_for_account",
+        skip(self),
+        err
+    )]
+    pub async fn list_withdrawal_limit_overrides_for_account(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        account_id: impl Into<DepositAccountId> + std::fmt::Debug,
+    ) -> Result<Vec<WithdrawalLimitOverride>, CoreDepositError> {
+        let account_id = account_id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::all_withdrawal_limit_overrides(),
+                CoreDepositAction::WITHDRAWAL_LIMIT_OVERRIDE_LIST,
+            )
+            .await?;
+        Ok(self
+            .withdrawal_limit_overrides
+            .list_for_deposit_account_id_by_created_at(
+                account_id,
+                Default::default(),
+                es_entity::ListDirection::Descending,
+            )
+            .await?
+            .entities)
+    }
+
+    pub fn withdrawal_limits_for_account(&self, account: &DepositAccount) -> WithdrawalLimits {
+        account.withdrawal_limits(&self.config.withdrawal_limits)
+    }
+
     #[instrument(name = "deposit.confirm_withdrawal", skip(self), err)]
     pub async fn confirm_withdrawal(
         &self,
@@ -431,17 +1434,262 @@ where
         let mut withdrawal = self.withdrawals.find_by_id(id).await?;
         self.check_account_active(withdrawal.deposit_account_id)
             .await?;
+        let (withdrawal_limit_account_id, held_windows) =
+            withdrawal.withdrawal_limits_held().unzip();
         let mut op = self.withdrawals.begin_op().await?;
         let tx_id = withdrawal.cancel(audit_info)?;
         self.withdrawals
             .update_in_op(&mut op, &mut withdrawal)
             .await?;
         self.ledger
-            .cancel_withdrawal(op, tx_id, withdrawal.amount, withdrawal.deposit_account_id)
+            .cancel_withdrawal(
+                op,
+                tx_id,
+                withdrawal.amount,
+                withdrawal.deposit_account_id,
+                withdrawal_limit_account_id,
+                WithdrawalLimitMeta::new(&held_windows.unwrap_or_default()),
+            )
+            .await?;
+        Ok(withdrawal)
+    }
+
+    /// Collects every confirmed withdrawal that has a payout destination and
+    /// has not been paid out yet into a single payment file.
+    #[instrument(name = "deposit.create_payout_batch", skip(self), err)]
+    pub async fn create_payout_batch(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        format: PayoutFileFormat,
+    ) -> Result<PayoutBatch, CoreDepositError> {
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::all_payout_batches(),
+                CoreDepositAction::PAYOUT_BATCH_CREATE,
+            )
+            .await?;
+
+        // Withdrawals are claimed before the file is uploaded so that a
+        // concurrent batch can't pay them out a second time.
+        let mut op = self.payout_batches.begin_op().await?;
+        let withdrawal_ids = self.withdrawals.lock_awaiting_batch_in_op(&mut op).await?;
+        if withdrawal_ids.is_empty() {
+            return Err(payout_batch::error::PayoutBatchError::NoWithdrawalsAwaitingPayout.into());
+        }
+
+        let payout_batch_id = PayoutBatchId::new();
+        let mut items = Vec::with_capacity(withdrawal_ids.len());
+        for id in withdrawal_ids {
+            let mut withdrawal = self.withdrawals.find_by_id_in_tx(op.tx(), id).await?;
+            let destination = withdrawal
+                .payout_destination
+                .clone()
+                .ok_or(withdrawal::error::WithdrawalError::NotAwaitingPayout(withdrawal.id))?;
+            if withdrawal
+                .payout_batched(payout_batch_id, audit_info.clone())?
+                .did_execute()
+            {
+                self.withdrawals
+                    .update_in_op(&mut op, &mut withdrawal)
+                    .await?;
+            }
+            items.push(PayoutBatchItem {
+                withdrawal_id: withdrawal.id,
+                deposit_account_id: withdrawal.deposit_account_id,
+                amount: withdrawal.amount,
+                reference: withdrawal.reference.clone(),
+                beneficiary_id: withdrawal.beneficiary.as_ref().map(|b| b.id),
+                destination,
+            });
+        }
+
+        let file = PayoutFile {
+            payout_batch_id,
+            created_at: chrono::Utc::now(),
+            items: &items,
+            config: &self.config.payouts,
+        }
+        .render(format)?;
+        let path_in_bucket = format.path_in_bucket(payout_batch_id);
+
+        let new_batch = NewPayoutBatch::builder()
+            .id(payout_batch_id)
+            .format(format)
+            .items(items)
+            .bucket(self.storage.bucket_name())
+            .path_in_bucket(path_in_bucket.clone())
+            .audit_info(audit_info)
+            .build()?;
+        let batch = self.payout_batches.create_in_op(&mut op, new_batch).await?;
+
+        self.storage
+            .upload(file, &path_in_bucket, format.content_type())
+            .await?;
+        op.commit().await?;
+
+        Ok(batch)
+    }
+
+    /// Records that the payment file was handed to the settlement bank.
+    #[instrument(name = "deposit.mark_payout_batch_sent", skip(self), err)]
+    pub async fn mark_payout_batch_sent(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        payout_batch_id: impl Into<PayoutBatchId> + std::fmt::Debug,
+    ) -> Result<PayoutBatch, CoreDepositError> {
+        let payout_batch_id = payout_batch_id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::payout_batch(payout_batch_id),
+                CoreDepositAction::PAYOUT_BATCH_MARK_SENT,
+            )
+            .await?;
+
+        let mut batch = self.payout_batches.find_by_id(payout_batch_id).await?;
+        if batch.mark_sent(audit_info.clone()).was_ignored() {
+            return Ok(batch);
+        }
+
+        let withdrawal_ids = batch
+            .items
+            .iter()
+            .map(|item| item.withdrawal_id)
+            .collect::<Vec<_>>();
+        let withdrawals: std::collections::HashMap<WithdrawalId, Withdrawal> =
+            self.withdrawals.find_all(&withdrawal_ids).await?;
+
+        let mut op = self.payout_batches.begin_op().await?;
+        self.payout_batches
+            .update_in_op(&mut op, &mut batch)
+            .await?;
+        for mut withdrawal in withdrawals.into_values() {
+            if withdrawal
+                .payout_sent(batch.id, audit_info.clone())
+                .did_execute()
+            {
+                self.withdrawals
+                    .update_in_op(&mut op, &mut withdrawal)
+                    .await?;
+            }
+        }
+        op.commit().await?;
+
+        Ok(batch)
+    }
+
+    #[instrument(name = "deposit.generate_payout_batch_download_link", skip(self), err)]
+    pub async fn generate_payout_batch_download_link(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        payout_batch_id: impl Into<PayoutBatchId> + std::fmt::Debug,
+    ) -> Result<PayoutBatchDownloadLink, CoreDepositError> {
+        let payout_batch_id = payout_batch_id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::payout_batch(payout_batch_id),
+                CoreDepositAction::PAYOUT_BATCH_GENERATE_DOWNLOAD_LINK,
+            )
+            .await?;
+
+        let mut batch = self.payout_batches.find_by_id(payout_batch_id).await?;
+        let url = {
+            let location = batch.download_link_generated(audit_info);
+            self.storage.generate_download_link(&location).await?
+        };
+        self.payout_batches.update(&mut batch).await?;
+
+        Ok(PayoutBatchDownloadLink {
+            payout_batch_id,
+            url,
+        })
+    }
+
+    /// Credits the amount of a payout that the receiving bank sent back to
+    /// the deposit account it was withdrawn from.
+    #[instrument(name = "deposit.record_withdrawal_payout_return", skip(self), err)]
+    pub async fn record_withdrawal_payout_return(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        withdrawal_id: impl Into<WithdrawalId> + std::fmt::Debug,
+        reason: String,
+    ) -> Result<Withdrawal, CoreDepositError> {
+        let id = withdrawal_id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::withdrawal(id),
+                CoreDepositAction::WITHDRAWAL_RECORD_PAYOUT_RETURN,
+            )
+            .await?;
+
+        let mut withdrawal = self.withdrawals.find_by_id(id).await?;
+        let tx_id = match withdrawal.payout_returned(reason, audit_info)? {
+            es_entity::Idempotent::Executed(tx_id) => tx_id,
+            es_entity::Idempotent::Ignored => return Ok(withdrawal),
+        };
+
+        let mut op = self.withdrawals.begin_op().await?;
+        self.withdrawals
+            .update_in_op(&mut op, &mut withdrawal)
+            .await?;
+        self.ledger
+            .return_withdrawal(op, tx_id, withdrawal.amount, withdrawal.deposit_account_id)
             .await?;
+
         Ok(withdrawal)
     }
 
+    #[instrument(name = "deposit.find_payout_batch_by_id", skip(self), err)]
+    pub async fn find_payout_batch_by_id(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        id: impl Into<PayoutBatchId> + std::fmt::Debug,
+    ) -> Result<Option<PayoutBatch>, CoreDepositError> {
+        let id = id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::payout_batch(id),
+                CoreDepositAction::PAYOUT_BATCH_READ,
+            )
+            .await?;
+
+        match self.payout_batches.find_by_id(id).await {
+            Ok(batch) => Ok(Some(batch)),
+            Err(e) if e.was_not_found() => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    #[instrument(name = "deposit.list_payout_batches", skip(self), err)]
+    pub async fn list_payout_batches(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        query: es_entity::PaginatedQueryArgs<PayoutBatchesByCreatedAtCursor>,
+    ) -> Result<
+        es_entity::PaginatedQueryRet<PayoutBatch, PayoutBatchesByCreatedAtCursor>,
+        CoreDepositError,
+    > {
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::all_payout_batches(),
+                CoreDepositAction::PAYOUT_BATCH_LIST,
+            )
+            .await?;
+        Ok(self
+            .payout_batches
+            .list_by_created_at(query, es_entity::ListDirection::Descending)
+            .await?)
+    }
+
     #[instrument(name = "deposit.account_balance", skip(self), err)]
     pub async fn account_balance(
         &self,
@@ -527,6 +1775,27 @@ where
         Ok(withdrawal)
     }
 
+    pub async fn find_withdrawal_by_returned_tx_id(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        returned_tx_id: impl Into<CalaTransactionId> + std::fmt::Debug,
+    ) -> Result<Withdrawal, CoreDepositError> {
+        let returned_tx_id = returned_tx_id.into();
+        let withdrawal = self
+            .withdrawals
+            .find_by_returned_tx_id(Some(returned_tx_id))
+            .await?;
+        self.authz
+            .enforce_permission(
+                sub,
+                CoreDepositObject::withdrawal(withdrawal.id),
+                CoreDepositAction::WITHDRAWAL_READ,
+            )
+            .await?;
+
+        Ok(withdrawal)
+    }
+
     #[instrument(name = "deposit.find_all_withdrawals", skip(self), err)]
     pub async fn find_all_withdrawals<T: From<Withdrawal>>(
         &self,
@@ -551,6 +1820,46 @@ where
         Ok(self.accounts.find_all(ids).await?)
     }
 
+    #[instrument(name = "deposit.find_all_withdrawal_limit_overrides", skip(self), err)]
+    pub async fn find_all_withdrawal_limit_overrides<T: From<WithdrawalLimitOverride>>(
+        &self,
+        ids: &[WithdrawalLimitOverrideId],
+    ) -> Result<std::collections::HashMap<WithdrawalLimitOverrideId, T>, CoreDepositError> {
+        Ok(self.withdrawal_limit_overrides.find_all(ids).await?)
+    }
+
+    #[instrument(name = "deposit.find_all_bank_statement_imports", skip(self), err)]
+    pub async fn find_all_bank_statement_imports<T: From<BankStatementImport>>(
+        &self,
+        ids: &[BankStatementImportId],
+    ) -> Result<std::collections::HashMap<BankStatementImportId, T>, CoreDepositError> {
+        Ok(self.bank_statement_imports.find_all(ids).await?)
+    }
+
+    #[instrument(name = "deposit.find_all_beneficiaries", skip(self), err)]
+    pub async fn find_all_beneficiaries<T: From<Beneficiary>>(
+        &self,
+        ids: &[BeneficiaryId],
+    ) -> Result<std::collections::HashMap<BeneficiaryId, T>, CoreDepositError> {
+        Ok(self.beneficiaries.find_all(ids).await?)
+    }
+
+    #[instrument(name = "deposit.find_all_payout_batches", skip(self), err)]
+    pub async fn find_all_payout_batches<T: From<PayoutBatch>>(
+        &self,
+        ids: &[PayoutBatchId],
+    ) -> Result<std::collections::HashMap<PayoutBatchId, T>, CoreDepositError> {
+        Ok(self.payout_batches.find_all(ids).await?)
+    }
+
+    #[instrument(name = "deposit.find_all_bank_statement_exceptions", skip(self), err)]
+    pub async fn find_all_bank_statement_exceptions<T: From<BankStatementException>>(
+        &self,
+        ids: &[BankStatementExceptionId],
+    ) -> Result<std::collections::HashMap<BankStatementExceptionId, T>, CoreDepositError> {
+        Ok(self.bank_statement_exceptions.find_all(ids).await?)
+    }
+
     #[instrument(name = "deposit.list_withdrawals", skip(self), err)]
     pub async fn list_withdrawals(
         &self,
@@ -773,11 +2082,67 @@ where
     async fn check_account_active(
         &self,
         deposit_account_id: DepositAccountId,
-    ) -> Result<(), CoreDepositError> {
+    ) -> Result<DepositAccount, CoreDepositError> {
         let account = self.accounts.find_by_id(deposit_account_id).await?;
-        if account.status.is_inactive() {
+        if !account.status.is_active() {
             return Err(CoreDepositError::DepositAccountNotActive);
         }
-        Ok(())
+        Ok(account)
+    }
+
+    async fn verified_beneficiary_for(
+        &self,
+        account: &DepositAccount,
+        beneficiary_id: BeneficiaryId,
+    ) -> Result<Beneficiary, CoreDepositError> {
+        let beneficiary = self.beneficiaries.find_by_id(beneficiary_id).await?;
+        let beneficiary = self
+            .approve_beneficiary
+            .execute_from_svc(&beneficiary)
+            .await?
+            .unwrap_or(beneficiary);
+        if beneficiary.deposit_account_holder_id != account.account_holder_id {
+            return Err(
+                beneficiary::error::BeneficiaryError::HolderMismatch(beneficiary_id).into(),
+            );
+        }
+        if !beneficiary.is_verified(chrono::Utc::now()) {
+            return Err(beneficiary::error::BeneficiaryError::NotVerified(beneficiary_id).into());
+        }
+        Ok(beneficiary)
+    }
+
+    async fn has_pending_withdrawals(
+        &self,
+        op: &mut es_entity::DbOp<'_>,
+        deposit_account_id: DepositAccountId,
+    ) -> Result<bool, CoreDepositError> {
+        let mut after = None;
+        loop {
+            let ret = self
+                .withdrawals
+                .list_for_deposit_account_id_by_created_at_in_tx(
+                    op.tx(),
+                    deposit_account_id,
+                    es_entity::PaginatedQueryArgs::<WithdrawalsByCreatedAtCursor> {
+                        first: 100,
+                        after,
+                    },
+                    es_entity::ListDirection::Descending,
+                )
+                .await?;
+            if ret.entities.iter().any(|withdrawal| {
+                matches!(
+                    withdrawal.status(),
+                    WithdrawalStatus::PendingApproval | WithdrawalStatus::PendingConfirmation
+                )
+            }) {
+                return Ok(true);
+            }
+            if !ret.has_next_page {
+                return Ok(false);
+            }
+            after = ret.end_cursor;
+        }
     }
 }
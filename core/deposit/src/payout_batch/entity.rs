@@ -0,0 +1,254 @@
+use chrono::{DateTime, Utc};
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use audit::AuditInfo;
+use es_entity::*;
+
+use crate::primitives::*;
+
+use super::file::PayoutFileFormat;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+pub enum PayoutBatchStatus {
+    Generated,
+    Sent,
+}
+
+/// A single credit transfer in a payout file.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct PayoutBatchItem {
+    pub withdrawal_id: WithdrawalId,
+    pub deposit_account_id: DepositAccountId,
+    pub amount: UsdCents,
+    pub reference: String,
+    /// Missing on items batched before withdrawals targeted beneficiaries.
+    #[serde(default)]
+    pub beneficiary_id: Option<BeneficiaryId>,
+    pub destination: PayoutDestination,
+}
+
+#[derive(Debug)]
+pub struct PayoutBatchLocationInCloud<'a> {
+    pub bucket: &'a str,
+    pub path_in_bucket: &'a str,
+}
+
+impl<'a> From<&PayoutBatchLocationInCloud<'a>> for cloud_storage::LocationInCloud<'a> {
+    fn from(meta: &PayoutBatchLocationInCloud<'a>) -> Self {
+        cloud_storage::LocationInCloud {
+            bucket: meta.bucket,
+            path_in_bucket: meta.path_in_bucket,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct PayoutBatchDownloadLink {
+    pub payout_batch_id: PayoutBatchId,
+    pub url: String,
+}
+
+#[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[es_event(id = "PayoutBatchId")]
+pub enum PayoutBatchEvent {
+    Initialized {
+        id: PayoutBatchId,
+        format: PayoutFileFormat,
+        items: Vec<PayoutBatchItem>,
+        bucket: String,
+        path_in_bucket: String,
+        audit_info: AuditInfo,
+    },
+    Sent {
+        audit_info: AuditInfo,
+        recorded_at: DateTime<Utc>,
+    },
+    DownloadLinkGenerated {
+        audit_info: AuditInfo,
+        recorded_at: DateTime<Utc>,
+    },
+}
+
+#[derive(EsEntity, Builder)]
+#[builder(pattern = "owned", build_fn(error = "EsEntityError"))]
+pub struct PayoutBatch {
+    pub id: PayoutBatchId,
+    pub format: PayoutFileFormat,
+    pub items: Vec<PayoutBatchItem>,
+    bucket: String,
+    path_in_bucket: String,
+    events: EntityEvents<PayoutBatchEvent>,
+}
+
+impl PayoutBatch {
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.events
+            .entity_first_persisted_at()
+            .expect("entity_first_persisted_at not found")
+    }
+
+    pub fn status(&self) -> PayoutBatchStatus {
+        if self.sent_at().is_some() {
+            PayoutBatchStatus::Sent
+        } else {
+            PayoutBatchStatus::Generated
+        }
+    }
+
+    pub fn sent_at(&self) -> Option<DateTime<Utc>> {
+        self.events.iter_all().find_map(|e| match e {
+            PayoutBatchEvent::Sent { recorded_at, .. } => Some(*recorded_at),
+            _ => None,
+        })
+    }
+
+    pub fn total_amount(&self) -> UsdCents {
+        self.items
+            .iter()
+            .fold(UsdCents::ZERO, |total, item| total + item.amount)
+    }
+
+    pub(crate) fn mark_sent(&mut self, audit_info: AuditInfo) -> Idempotent<()> {
+        idempotency_guard!(self.events.iter_all(), PayoutBatchEvent::Sent { .. });
+        self.events.push(PayoutBatchEvent::Sent {
+            audit_info,
+            recorded_at: Utc::now(),
+        });
+        Idempotent::Executed(())
+    }
+
+    pub(crate) fn download_link_generated(
+        &mut self,
+        audit_info: AuditInfo,
+    ) -> PayoutBatchLocationInCloud<'_> {
+        self.events.push(PayoutBatchEvent::DownloadLinkGenerated {
+            audit_info,
+            recorded_at: Utc::now(),
+        });
+        PayoutBatchLocationInCloud {
+            bucket: &self.bucket,
+            path_in_bucket: &self.path_in_bucket,
+        }
+    }
+}
+
+impl TryFromEvents<PayoutBatchEvent> for PayoutBatch {
+    fn try_from_events(events: EntityEvents<PayoutBatchEvent>) -> Result<Self, EsEntityError> {
+        let mut builder = PayoutBatchBuilder::default();
+        for event in events.iter_all() {
+            if let PayoutBatchEvent::Initialized {
+                id,
+                format,
+                items,
+                bucket,
+                path_in_bucket,
+                ..
+            } = event
+            {
+                builder = builder
+                    .id(*id)
+                    .format(*format)
+                    .items(items.clone())
+                    .bucket(bucket.clone())
+                    .path_in_bucket(path_in_bucket.clone());
+            }
+        }
+        builder.events(events).build()
+    }
+}
+
+#[derive(Debug, Builder)]
+pub struct NewPayoutBatch {
+    #[builder(setter(into))]
+    pub(super) id: PayoutBatchId,
+    pub(super) format: PayoutFileFormat,
+    pub(super) items: Vec<PayoutBatchItem>,
+    #[builder(setter(into))]
+    pub(super) bucket: String,
+    #[builder(setter(into))]
+    pub(super) path_in_bucket: String,
+    #[builder(setter(into))]
+    pub(super) audit_info: AuditInfo,
+}
+
+impl NewPayoutBatch {
+    pub fn builder() -> NewPayoutBatchBuilder {
+        NewPayoutBatchBuilder::default()
+    }
+}
+
+impl IntoEvents<PayoutBatchEvent> for NewPayoutBatch {
+    fn into_events(self) -> EntityEvents<PayoutBatchEvent> {
+        EntityEvents::init(
+            self.id,
+            [PayoutBatchEvent::Initialized {
+                id: self.id,
+                format: self.format,
+                items: self.items,
+                bucket: self.bucket,
+                path_in_bucket: self.path_in_bucket,
+                audit_info: self.audit_info,
+            }],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use audit::AuditEntryId;
+
+    use super::*;
+
+    fn dummy_audit_info() -> AuditInfo {
+        AuditInfo {
+            audit_entry_id: AuditEntryId::from(1),
+            sub: "sub".to_string(),
+        }
+    }
+
+    fn item(amount: u64) -> PayoutBatchItem {
+        PayoutBatchItem {
+            withdrawal_id: WithdrawalId::new(),
+            deposit_account_id: DepositAccountId::new(),
+            amount: UsdCents::from(amount),
+            reference: "withdrawal".to_string(),
+            beneficiary_id: Some(BeneficiaryId::new()),
+            destination: PayoutDestination {
+                account_holder_name: "Jane Doe".to_string(),
+                routing_number: "021000021".to_string(),
+                account_number: "000123456789".to_string(),
+                account_type: PayoutAccountType::Checking,
+            },
+        }
+    }
+
+    fn batch() -> PayoutBatch {
+        let new_batch = NewPayoutBatch::builder()
+            .id(PayoutBatchId::new())
+            .format(PayoutFileFormat::Nacha)
+            .items(vec![item(1_000), item(2_500)])
+            .bucket("bucket")
+            .path_in_bucket("payout_batches/batch.ach")
+            .audit_info(dummy_audit_info())
+            .build()
+            .unwrap();
+        PayoutBatch::try_from_events(new_batch.into_events()).unwrap()
+    }
+
+    #[test]
+    fn total_amount_sums_items() {
+        assert_eq!(batch().total_amount(), UsdCents::from(3_500));
+    }
+
+    #[test]
+    fn mark_sent_is_idempotent() {
+        let mut batch = batch();
+        assert_eq!(batch.status(), PayoutBatchStatus::Generated);
+        assert!(batch.mark_sent(dummy_audit_info()).did_execute());
+        assert_eq!(batch.status(), PayoutBatchStatus::Sent);
+        assert!(batch.mark_sent(dummy_audit_info()).was_ignored());
+    }
+}
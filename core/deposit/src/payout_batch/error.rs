@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+use crate::primitives::WithdrawalId;
+
+#[derive(Error, Debug)]
+pub enum PayoutBatchError {
+    #[error("PayoutBatchError - Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("PayoutBatchError - EsEntityError: {0}")]
+    EsEntityError(es_entity::EsEntityError),
+    #[error("PayoutBatchError - CursorDestructureError: {0}")]
+    CursorDestructureError(#[from] es_entity::CursorDestructureError),
+    #[error("PayoutBatchError - NoWithdrawalsAwaitingPayout")]
+    NoWithdrawalsAwaitingPayout,
+    #[error("PayoutBatchError - AmountTooLarge: {0}")]
+    AmountTooLarge(WithdrawalId),
+    #[error("PayoutBatchError - AccountNumberTooLong: {0}")]
+    AccountNumberTooLong(WithdrawalId),
+}
+
+es_entity::from_es_entity_error!(PayoutBatchError);
@@ -0,0 +1,92 @@
+mod nacha;
+mod pain001;
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::{config::PayoutConfig, primitives::PayoutBatchId};
+
+use super::{entity::PayoutBatchItem, error::PayoutBatchError};
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+pub enum PayoutFileFormat {
+    /// NACHA ACH file with a single PPD credit batch.
+    Nacha,
+    /// ISO 20022 customer credit transfer initiation (pain.001.001.03).
+    Pain001,
+}
+
+impl PayoutFileFormat {
+    pub(crate) fn path_in_bucket(&self, payout_batch_id: PayoutBatchId) -> String {
+        match self {
+            Self::Nacha => format!("payout_batches/{}.ach", payout_batch_id),
+            Self::Pain001 => format!("payout_batches/{}.xml", payout_batch_id),
+        }
+    }
+
+    pub(crate) fn content_type(&self) -> &'static str {
+        match self {
+            Self::Nacha => "text/plain",
+            Self::Pain001 => "application/xml",
+        }
+    }
+}
+
+pub(crate) struct PayoutFile<'a> {
+    pub payout_batch_id: PayoutBatchId,
+    pub created_at: DateTime<Utc>,
+    pub items: &'a [PayoutBatchItem],
+    pub config: &'a PayoutConfig,
+}
+
+impl PayoutFile<'_> {
+    pub fn render(&self, format: PayoutFileFormat) -> Result<Vec<u8>, PayoutBatchError> {
+        let file = match format {
+            PayoutFileFormat::Nacha => nacha::render(self)?,
+            PayoutFileFormat::Pain001 => pain001::render(self),
+        };
+        Ok(file.into_bytes())
+    }
+}
+
+#[cfg(test)]
+pub(super) mod test_helpers {
+    use chrono::TimeZone;
+
+    use crate::primitives::*;
+
+    use super::*;
+
+    pub fn config() -> PayoutConfig {
+        PayoutConfig {
+            originator_name: "Lana Bank".to_string(),
+            originator_id: "1234567890".to_string(),
+            originator_routing_number: "011000015".to_string(),
+            originator_account_number: "9876543210".to_string(),
+            destination_routing_number: "011000015".to_string(),
+            destination_name: "Federal Reserve Bank".to_string(),
+            entry_description: "WITHDRAWAL".to_string(),
+        }
+    }
+
+    pub fn item(amount: u64, account_type: PayoutAccountType) -> PayoutBatchItem {
+        PayoutBatchItem {
+            withdrawal_id: WithdrawalId::new(),
+            deposit_account_id: DepositAccountId::new(),
+            amount: UsdCents::from(amount),
+            reference: "rent & utilities".to_string(),
+            beneficiary_id: Some(BeneficiaryId::new()),
+            destination: PayoutDestination {
+                account_holder_name: "Jane Doe".to_string(),
+                routing_number: "021000021".to_string(),
+                account_number: "000123456789".to_string(),
+                account_type,
+            },
+        }
+    }
+
+    pub fn created_at() -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2025, 3, 14, 9, 26, 53).unwrap()
+    }
+}
@@ -0,0 +1,186 @@
+//! Fixed-width NACHA ACH file: one file header, a single PPD batch of
+//! credits, the controls and `9` filler records up to a full block of ten.
+
+use crate::primitives::PayoutAccountType;
+
+use super::{PayoutBatchError, PayoutFile};
+
+const RECORD_SIZE: usize = 94;
+const BLOCKING_FACTOR: usize = 10;
+const CREDITS_ONLY: &str = "220";
+const MAX_AMOUNT_IN_CENTS: u64 = 9_999_999_999;
+const BATCH_NUMBER: u64 = 1;
+
+pub(super) fn render(file: &PayoutFile) -> Result<String, PayoutBatchError> {
+    let config = file.config;
+    let date = file.created_at.format("%y%m%d").to_string();
+    let odfi = leading_digits(&config.originator_routing_number, 8);
+
+    let mut records = vec![format!(
+        "101 {} {}{}{}A094101{}{}{}",
+        numeric_str(&config.destination_routing_number, 9),
+        numeric_str(&config.originator_routing_number, 9),
+        date,
+        file.created_at.format("%H%M"),
+        alpha(&config.destination_name, 23),
+        alpha(&config.originator_name, 23),
+        alpha(&file.payout_batch_id.to_string(), 8),
+    )];
+    records.push(format!(
+        "5{}{}{}{}PPD{}{}{}   1{}{}",
+        CREDITS_ONLY,
+        alpha(&config.originator_name, 16),
+        alpha("", 20),
+        alpha(&config.originator_id, 10),
+        alpha(&config.entry_description, 10),
+        date,
+        date,
+        odfi,
+        numeric(BATCH_NUMBER, 7),
+    ));
+
+    let mut entry_hash = 0u64;
+    let mut total_credit = 0u64;
+    for (sequence, item) in file.items.iter().enumerate() {
+        let destination = &item.destination;
+        let amount = item.amount.into_inner();
+        if amount > MAX_AMOUNT_IN_CENTS {
+            return Err(PayoutBatchError::AmountTooLarge(item.withdrawal_id));
+        }
+        if destination.account_number.len() > 17 {
+            return Err(PayoutBatchError::AccountNumberTooLong(item.withdrawal_id));
+        }
+        let rdfi = leading_digits(&destination.routing_number, 8);
+        entry_hash += rdfi.parse::<u64>().expect("routing number is numeric");
+        total_credit += amount;
+
+        let transaction_code = match destination.account_type {
+            PayoutAccountType::Checking => "22",
+            PayoutAccountType::Savings => "32",
+        };
+        records.push(format!(
+            "6{}{}{}{:<17}{}{}{}  0{}{}",
+            transaction_code,
+            rdfi,
+            &numeric_str(&destination.routing_number, 9)[8..],
+            destination.account_number,
+            numeric(amount, 10),
+            alpha(&item.withdrawal_id.to_string().replace('-', ""), 15),
+            alpha(&destination.account_holder_name, 22),
+            odfi,
+            numeric(sequence as u64 + 1, 7),
+        ));
+    }
+
+    let entry_count = file.items.len() as u64;
+    let entry_hash = entry_hash % 10_000_000_000;
+    records.push(format!(
+        "8{}{}{}{}{}{}{:25}{}{}",
+        CREDITS_ONLY,
+        numeric(entry_count, 6),
+        numeric(entry_hash, 10),
+        numeric(0, 12),
+        numeric(total_credit, 12),
+        alpha(&config.originator_id, 10),
+        "",
+        odfi,
+        numeric(BATCH_NUMBER, 7),
+    ));
+
+    let record_count = records.len() + 1;
+    let block_count = record_count.div_ceil(BLOCKING_FACTOR);
+    records.push(format!(
+        "9{}{}{}{}{}{}{:39}",
+        numeric(1, 6),
+        numeric(block_count as u64, 6),
+        numeric(entry_count, 8),
+        numeric(entry_hash, 10),
+        numeric(0, 12),
+        numeric(total_credit, 12),
+        "",
+    ));
+    while records.len() < block_count * BLOCKING_FACTOR {
+        records.push("9".repeat(RECORD_SIZE));
+    }
+
+    debug_assert!(records.iter().all(|record| record.len() == RECORD_SIZE));
+    let mut rendered = records.join("\n");
+    rendered.push('\n');
+    Ok(rendered)
+}
+
+/// Upper-case, left-justified and space-padded, keeping only characters
+/// that are safe in every ACH processor.
+fn alpha(value: &str, width: usize) -> String {
+    let cleaned: String = value
+        .chars()
+        .map(|c| c.to_ascii_uppercase())
+        .filter(|c| c.is_ascii_alphanumeric() || *c == ' ')
+        .take(width)
+        .collect();
+    format!("{:<width$}", cleaned, width = width)
+}
+
+fn numeric(value: u64, width: usize) -> String {
+    format!("{:0>width$}", value, width = width)
+}
+
+fn numeric_str(value: &str, width: usize) -> String {
+    let digits: String = value.chars().filter(|c| c.is_ascii_digit()).collect();
+    format!("{:0>width$}", digits, width = width)
+}
+
+fn leading_digits(routing_number: &str, count: usize) -> String {
+    numeric_str(routing_number, 9)[..count].to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::primitives::{PayoutAccountType, PayoutBatchId};
+
+    use super::{super::test_helpers::*, *};
+
+    #[test]
+    fn records_are_fixed_width_and_blocked() {
+        let config = config();
+        let items = vec![
+            item(12_345, PayoutAccountType::Checking),
+            item(500, PayoutAccountType::Savings),
+        ];
+        let file = PayoutFile {
+            payout_batch_id: PayoutBatchId::new(),
+            created_at: created_at(),
+            items: &items,
+            config: &config,
+        };
+        let rendered = render(&file).unwrap();
+        let records: Vec<&str> = rendered.lines().collect();
+
+        assert_eq!(records.len(), 10);
+        assert!(records.iter().all(|record| record.len() == RECORD_SIZE));
+        assert!(records[0].starts_with("101 011000015 011000015250314"));
+        assert!(records[1].starts_with("5220LANA BANK"));
+        assert!(records[2].starts_with("622021000021000123456789     0000012345"));
+        assert!(records[3].starts_with("632021000021000123456789     0000000500"));
+        assert!(records[3].ends_with("011000010000002"));
+        assert!(records[4].starts_with("82200000020004200004000000000000000000012845"));
+        assert!(records[5].starts_with("9000001000001000000020004200004000000000000000000012845"));
+        assert_eq!(records[9], "9".repeat(RECORD_SIZE));
+    }
+
+    #[test]
+    fn rejects_amounts_that_do_not_fit() {
+        let config = config();
+        let items = vec![item(MAX_AMOUNT_IN_CENTS + 1, PayoutAccountType::Checking)];
+        let file = PayoutFile {
+            payout_batch_id: PayoutBatchId::new(),
+            created_at: created_at(),
+            items: &items,
+            config: &config,
+        };
+        assert!(matches!(
+            render(&file),
+            Err(PayoutBatchError::AmountTooLarge(_))
+        ));
+    }
+}
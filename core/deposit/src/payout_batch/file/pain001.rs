@@ -0,0 +1,174 @@
+//! ISO 20022 `pain.001.001.03` with one payment information block. US
+//! accounts are identified by account number and ABA routing number.
+
+use std::fmt::Write;
+
+use crate::primitives::UsdCents;
+
+use super::PayoutFile;
+
+const NAMESPACE: &str = "urn:iso:std:iso:20022:tech:xsd:pain.001.001.03";
+
+pub(super) fn render(file: &PayoutFile) -> String {
+    let config = file.config;
+    let message_id = file.payout_batch_id.to_string().replace('-', "");
+    let total = file
+        .items
+        .iter()
+        .fold(UsdCents::ZERO, |total, item| total + item.amount);
+    let number_of_transactions = file.items.len();
+
+    let mut xml = String::new();
+    let _ = writeln!(xml, r#"<?xml version="1.0" encoding="UTF-8"?>"#);
+    let _ = writeln!(xml, r#"<Document xmlns="{}">"#, NAMESPACE);
+    let _ = writeln!(xml, "  <CstmrCdtTrfInitn>");
+    let _ = writeln!(xml, "    <GrpHdr>");
+    let _ = writeln!(xml, "      <MsgId>{}</MsgId>", message_id);
+    let _ = writeln!(
+        xml,
+        "      <CreDtTm>{}</CreDtTm>",
+        file.created_at.format("%Y-%m-%dT%H:%M:%S")
+    );
+    let _ = writeln!(xml, "      <NbOfTxs>{}</NbOfTxs>", number_of_transactions);
+    let _ = writeln!(xml, "      <CtrlSum>{}</CtrlSum>", decimal(total));
+    let _ = writeln!(
+        xml,
+        "      <InitgPty><Nm>{}</Nm></InitgPty>",
+        escape(&config.originator_name)
+    );
+    let _ = writeln!(xml, "    </GrpHdr>");
+    let _ = writeln!(xml, "    <PmtInf>");
+    let _ = writeln!(xml, "      <PmtInfId>{}</PmtInfId>", message_id);
+    let _ = writeln!(xml, "      <PmtMtd>TRF</PmtMtd>");
+    let _ = writeln!(xml, "      <NbOfTxs>{}</NbOfTxs>", number_of_transactions);
+    let _ = writeln!(xml, "      <CtrlSum>{}</CtrlSum>", decimal(total));
+    let _ = writeln!(
+        xml,
+        "      <ReqdExctnDt>{}</ReqdExctnDt>",
+        file.created_at.format("%Y-%m-%d")
+    );
+    let _ = writeln!(
+        xml,
+        "      <Dbtr><Nm>{}</Nm></Dbtr>",
+        escape(&config.originator_name)
+    );
+    let _ = writeln!(
+        xml,
+        "      <DbtrAcct><Id><Othr><Id>{}</Id></Othr></Id><Ccy>USD</Ccy></DbtrAcct>",
+        escape(&config.originator_account_number)
+    );
+    let _ = writeln!(
+        xml,
+        "      <DbtrAgt>{}</DbtrAgt>",
+        financial_institution(&config.originator_routing_number)
+    );
+    for item in file.items {
+        let destination = &item.destination;
+        let _ = writeln!(xml, "      <CdtTrfTxInf>");
+        let _ = writeln!(
+            xml,
+            "        <PmtId><EndToEndId>{}</EndToEndId></PmtId>",
+            item.withdrawal_id.to_string().replace('-', "")
+        );
+        let _ = writeln!(
+            xml,
+            r#"        <Amt><InstdAmt Ccy="USD">{}</InstdAmt></Amt>"#,
+            decimal(item.amount)
+        );
+        let _ = writeln!(
+            xml,
+            "        <CdtrAgt>{}</CdtrAgt>",
+            financial_institution(&destination.routing_number)
+        );
+        let _ = writeln!(
+            xml,
+            "        <Cdtr><Nm>{}</Nm></Cdtr>",
+            escape(&destination.account_holder_name)
+        );
+        let _ = writeln!(
+            xml,
+            "        <CdtrAcct><Id><Othr><Id>{}</Id></Othr></Id></CdtrAcct>",
+            escape(&destination.account_number)
+        );
+        let _ = writeln!(
+            xml,
+            "        <RmtInf><Ustrd>{}</Ustrd></RmtInf>",
+            escape(&item.reference)
+        );
+        let _ = writeln!(xml, "      </CdtTrfTxInf>");
+    }
+    let _ = writeln!(xml, "    </PmtInf>");
+    let _ = writeln!(xml, "  </CstmrCdtTrfInitn>");
+    let _ = writeln!(xml, "</Document>");
+    xml
+}
+
+fn financial_institution(routing_number: &str) -> String {
+    format!(
+        "<FinInstnId><ClrSysMmbId><ClrSysId><Cd>USABA</Cd></ClrSysId><MmbId>{}</MmbId></ClrSysMmbId></FinInstnId>",
+        escape(routing_number)
+    )
+}
+
+fn decimal(amount: UsdCents) -> String {
+    let cents = amount.into_inner();
+    format!("{}.{:02}", cents / 100, cents % 100)
+}
+
+fn escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\'', "&apos;")
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::primitives::{PayoutAccountType, PayoutBatchId};
+
+    use super::{super::test_helpers::*, *};
+
+    #[test]
+    fn renders_group_header_and_transactions() {
+        let config = config();
+        let items = vec![
+            item(12_345, PayoutAccountType::Checking),
+            item(500, PayoutAccountType::Savings),
+        ];
+        let file = PayoutFile {
+            payout_batch_id: PayoutBatchId::new(),
+            created_at: created_at(),
+            items: &items,
+            config: &config,
+        };
+        let xml = render(&file);
+
+        assert!(xml.contains(NAMESPACE));
+        assert!(xml.contains("<CreDtTm>2025-03-14T09:26:53</CreDtTm>"));
+        assert_eq!(xml.matches("<NbOfTxs>2</NbOfTxs>").count(), 2);
+        assert_eq!(xml.matches("<CtrlSum>128.45</CtrlSum>").count(), 2);
+        assert_eq!(xml.matches("<CdtTrfTxInf>").count(), 2);
+        assert!(xml.contains(r#"<InstdAmt Ccy="USD">123.45</InstdAmt>"#));
+        assert!(xml.contains(r#"<InstdAmt Ccy="USD">5.00</InstdAmt>"#));
+        assert!(xml.contains("<MmbId>021000021</MmbId>"));
+        assert!(xml.contains("<Ustrd>rent &amp; utilities</Ustrd>"));
+    }
+
+    #[test]
+    fn end_to_end_ids_fit_the_schema() {
+        let config = config();
+        let items = vec![item(100, PayoutAccountType::Checking)];
+        let file = PayoutFile {
+            payout_batch_id: PayoutBatchId::new(),
+            created_at: created_at(),
+            items: &items,
+            config: &config,
+        };
+        let xml = render(&file);
+        let start = xml.find("<EndToEndId>").unwrap() + "<EndToEndId>".len();
+        let end = xml.find("</EndToEndId>").unwrap();
+        assert!(end - start <= 35);
+    }
+}
@@ -0,0 +1,11 @@
+mod entity;
+pub mod error;
+mod file;
+mod repo;
+
+pub(crate) use entity::*;
+pub use entity::{PayoutBatch, PayoutBatchDownloadLink, PayoutBatchItem, PayoutBatchStatus};
+pub use file::PayoutFileFormat;
+pub(crate) use file::*;
+pub use repo::payout_batch_cursor::PayoutBatchesByCreatedAtCursor;
+pub(crate) use repo::*;
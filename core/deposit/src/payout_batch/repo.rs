@@ -0,0 +1,19 @@
+use sqlx::PgPool;
+
+use es_entity::*;
+
+use crate::primitives::PayoutBatchId;
+
+use super::{entity::*, error::*};
+
+#[derive(EsRepo, Clone)]
+#[es_repo(entity = "PayoutBatch", err = "PayoutBatchError", tbl_prefix = "core")]
+pub struct PayoutBatchRepo {
+    pool: PgPool,
+}
+
+impl PayoutBatchRepo {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
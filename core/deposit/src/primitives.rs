@@ -1,9 +1,10 @@
+use serde::{Deserialize, Serialize};
 use std::{fmt::Display, str::FromStr};
 
 use authz::AllOrOne;
 
 pub use core_accounting::ChartId;
-pub use core_customer::CustomerType;
+pub use core_customer::{CustomerType, KycLevel};
 pub use governance::{ApprovalProcessId, GovernanceAction, GovernanceObject};
 
 pub use cala_ledger::primitives::{
@@ -16,23 +17,181 @@ es_entity::entity_id! {
     DepositAccountId,
     WithdrawalId,
     ChartOfAccountsIntegrationConfigId,
+    DepositAccountStatementId,
+    WithdrawalLimitOverrideId,
+    BankStatementImportId,
+    BankStatementExceptionId,
+    PayoutBatchId,
+    BeneficiaryId,
     DepositId;
 
     DepositAccountHolderId => core_customer::CustomerId,
     DepositAccountId => CalaAccountId,
     DepositId => CalaTransactionId,
     WithdrawalId => CalaTransactionId,
-    WithdrawalId => ApprovalProcessId
+    WithdrawalId => ApprovalProcessId,
+    DepositAccountStatementId => job::JobId,
+    WithdrawalLimitOverrideId => ApprovalProcessId,
+    WithdrawalLimitOverrideId => CalaAccountId,
+    BeneficiaryId => ApprovalProcessId
 }
 
 pub use core_customer::AccountStatus;
 pub use core_money::UsdCents;
 
+#[derive(
+    Debug,
+    Default,
+    Clone,
+    Copy,
+    PartialEq,
+    Eq,
+    strum::Display,
+    strum::EnumString,
+    Serialize,
+    Deserialize,
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum DepositAccountStatus {
+    #[default]
+    Inactive,
+    Active,
+    Closed,
+}
+
+impl DepositAccountStatus {
+    pub fn is_active(&self) -> bool {
+        matches!(self, DepositAccountStatus::Active)
+    }
+
+    pub fn is_closed(&self) -> bool {
+        matches!(self, DepositAccountStatus::Closed)
+    }
+}
+
+impl From<AccountStatus> for DepositAccountStatus {
+    fn from(status: AccountStatus) -> Self {
+        match status {
+            AccountStatus::Inactive => DepositAccountStatus::Inactive,
+            AccountStatus::Active => DepositAccountStatus::Active,
+        }
+    }
+}
+
+/// The rolling windows withdrawal limits are enforced over.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, strum::Display)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum WithdrawalLimitWindow {
+    /// The last 24 hours.
+    Daily,
+    /// The last 30 days.
+    Monthly,
+}
+
+impl WithdrawalLimitWindow {
+    pub const ALL: [Self; 2] = [Self::Daily, Self::Monthly];
+
+    pub fn length(&self) -> chrono::Duration {
+        match self {
+            Self::Daily => chrono::Duration::days(1),
+            Self::Monthly => chrono::Duration::days(30),
+        }
+    }
+}
+
+/// Where the remaining balance of an account goes when it is closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DepositAccountClosureSettlement {
+    /// Paid out to the holder through the deposit omnibus account.
+    PayOut,
+    /// Moved to another active deposit account.
+    TransferTo(DepositAccountId),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[serde(rename_all = "snake_case")]
+pub enum PayoutAccountType {
+    Checking,
+    Savings,
+}
+
+/// The external bank account a withdrawal is paid out to.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PayoutDestination {
+    pub account_holder_name: String,
+    pub routing_number: String,
+    pub account_number: String,
+    pub account_type: PayoutAccountType,
+}
+
+impl PayoutDestination {
+    /// ABA routing numbers are nine digits whose weighted sum (3, 7, 1, ...)
+    /// is a multiple of ten.
+    pub fn has_valid_routing_number(&self) -> bool {
+        let digits: Vec<u32> = self
+            .routing_number
+            .chars()
+            .filter_map(|c| c.to_digit(10))
+            .collect();
+        if digits.len() != 9 || self.routing_number.len() != 9 {
+            return false;
+        }
+        let checksum: u32 = digits
+            .iter()
+            .zip([3, 7, 1].iter().cycle())
+            .map(|(digit, weight)| digit * weight)
+            .sum();
+        checksum.is_multiple_of(10)
+    }
+}
+
+/// Where a beneficiary receives the money withdrawn to it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum BeneficiaryDetails {
+    BankAccount(PayoutDestination),
+    CryptoAddress { network: String, address: String },
+}
+
+impl BeneficiaryDetails {
+    /// Only bank accounts can be paid out through payout files; crypto
+    /// withdrawals are settled outside of them.
+    pub fn payout_destination(&self) -> Option<PayoutDestination> {
+        match self {
+            BeneficiaryDetails::BankAccount(destination) => Some(destination.clone()),
+            BeneficiaryDetails::CryptoAddress { .. } => None,
+        }
+    }
+
+    pub fn is_valid(&self) -> bool {
+        match self {
+            BeneficiaryDetails::BankAccount(destination) => {
+                destination.has_valid_routing_number()
+                    && !destination.account_holder_name.trim().is_empty()
+                    && !destination.account_number.trim().is_empty()
+            }
+            BeneficiaryDetails::CryptoAddress { network, address } => {
+                !network.trim().is_empty() && !address.trim().is_empty()
+            }
+        }
+    }
+}
+
 pub type DepositAccountAllOrOne = AllOrOne<DepositAccountId>;
 pub type DepositAccountByHolderAllOrOne = AllOrOne<DepositAccountHolderId>;
 pub type DepositAllOrOne = AllOrOne<DepositId>;
 pub type ChartOfAccountsIntegrationConfigAllOrOne = AllOrOne<ChartOfAccountsIntegrationConfigId>;
 pub type WithdrawalAllOrOne = AllOrOne<WithdrawalId>;
+pub type DepositAccountStatementAllOrOne = AllOrOne<DepositAccountStatementId>;
+pub type WithdrawalLimitOverrideAllOrOne = AllOrOne<WithdrawalLimitOverrideId>;
+pub type BankStatementImportAllOrOne = AllOrOne<BankStatementImportId>;
+pub type PayoutBatchAllOrOne = AllOrOne<PayoutBatchId>;
+pub type BeneficiaryAllOrOne = AllOrOne<BeneficiaryId>;
 
 #[derive(Debug, Clone)]
 pub struct LedgerOmnibusAccountIds {
@@ -48,6 +207,11 @@ pub enum CoreDepositObject {
     Deposit(DepositAllOrOne),
     ChartOfAccountsIntegration(ChartOfAccountsIntegrationConfigAllOrOne),
     Withdrawal(WithdrawalAllOrOne),
+    DepositAccountStatement(DepositAccountStatementAllOrOne),
+    WithdrawalLimitOverride(WithdrawalLimitOverrideAllOrOne),
+    BankStatementImport(BankStatementImportAllOrOne),
+    PayoutBatch(PayoutBatchAllOrOne),
+    Beneficiary(BeneficiaryAllOrOne),
 }
 
 impl CoreDepositObject {
@@ -78,6 +242,46 @@ impl CoreDepositObject {
     pub fn chart_of_accounts_integration() -> Self {
         CoreDepositObject::ChartOfAccountsIntegration(AllOrOne::All)
     }
+
+    pub fn all_deposit_account_statements() -> Self {
+        CoreDepositObject::DepositAccountStatement(AllOrOne::All)
+    }
+
+    pub fn deposit_account_statement(id: DepositAccountStatementId) -> Self {
+        CoreDepositObject::DepositAccountStatement(AllOrOne::ById(id))
+    }
+
+    pub fn all_withdrawal_limit_overrides() -> Self {
+        CoreDepositObject::WithdrawalLimitOverride(AllOrOne::All)
+    }
+
+    pub fn withdrawal_limit_override(id: WithdrawalLimitOverrideId) -> Self {
+        CoreDepositObject::WithdrawalLimitOverride(AllOrOne::ById(id))
+    }
+
+    pub fn all_bank_statement_imports() -> Self {
+        CoreDepositObject::BankStatementImport(AllOrOne::All)
+    }
+
+    pub fn bank_statement_import(id: BankStatementImportId) -> Self {
+        CoreDepositObject::BankStatementImport(AllOrOne::ById(id))
+    }
+
+    pub fn all_payout_batches() -> Self {
+        CoreDepositObject::PayoutBatch(AllOrOne::All)
+    }
+
+    pub fn payout_batch(id: PayoutBatchId) -> Self {
+        CoreDepositObject::PayoutBatch(AllOrOne::ById(id))
+    }
+
+    pub fn all_beneficiaries() -> Self {
+        CoreDepositObject::Beneficiary(AllOrOne::All)
+    }
+
+    pub fn beneficiary(id: BeneficiaryId) -> Self {
+        CoreDepositObject::Beneficiary(AllOrOne::ById(id))
+    }
 }
 
 impl Display for CoreDepositObject {
@@ -89,6 +293,11 @@ impl Display for CoreDepositObject {
             Deposit(obj_ref) => write!(f, "{}/{}", discriminant, obj_ref),
             Withdrawal(obj_ref) => write!(f, "{}/{}", discriminant, obj_ref),
             ChartOfAccountsIntegration(obj_ref) => write!(f, "{}/{}", discriminant, obj_ref),
+            DepositAccountStatement(obj_ref) => write!(f, "{}/{}", discriminant, obj_ref),
+            WithdrawalLimitOverride(obj_ref) => write!(f, "{}/{}", discriminant, obj_ref),
+            BankStatementImport(obj_ref) => write!(f, "{}/{}", discriminant, obj_ref),
+            PayoutBatch(obj_ref) => write!(f, "{}/{}", discriminant, obj_ref),
+            Beneficiary(obj_ref) => write!(f, "{}/{}", discriminant, obj_ref),
         }
     }
 }
@@ -124,6 +333,36 @@ impl FromStr for CoreDepositObject {
                     .map_err(|_| "could not parse CoreDepositObject")?;
                 CoreDepositObject::ChartOfAccountsIntegration(obj_ref)
             }
+            DepositAccountStatement => {
+                let obj_ref = id
+                    .parse()
+                    .map_err(|_| "could not parse CoreDepositObject")?;
+                CoreDepositObject::DepositAccountStatement(obj_ref)
+            }
+            WithdrawalLimitOverride => {
+                let obj_ref = id
+                    .parse()
+                    .map_err(|_| "could not parse CoreDepositObject")?;
+                CoreDepositObject::WithdrawalLimitOverride(obj_ref)
+            }
+            BankStatementImport => {
+                let obj_ref = id
+                    .parse()
+                    .map_err(|_| "could not parse CoreDepositObject")?;
+                CoreDepositObject::BankStatementImport(obj_ref)
+            }
+            PayoutBatch => {
+                let obj_ref = id
+                    .parse()
+                    .map_err(|_| "could not parse CoreDepositObject")?;
+                CoreDepositObject::PayoutBatch(obj_ref)
+            }
+            Beneficiary => {
+                let obj_ref = id
+                    .parse()
+                    .map_err(|_| "could not parse CoreDepositObject")?;
+                CoreDepositObject::Beneficiary(obj_ref)
+            }
         };
         Ok(res)
     }
@@ -137,6 +376,11 @@ pub enum CoreDepositAction {
     Deposit(DepositAction),
     ChartOfAccountsIntegrationConfig(ChartOfAccountsIntegrationConfigAction),
     Withdrawal(WithdrawalAction),
+    DepositAccountStatement(DepositAccountStatementAction),
+    WithdrawalLimitOverride(WithdrawalLimitOverrideAction),
+    BankStatementImport(BankStatementImportAction),
+    PayoutBatch(PayoutBatchAction),
+    Beneficiary(BeneficiaryAction),
 }
 
 impl CoreDepositAction {
@@ -144,6 +388,12 @@ impl CoreDepositAction {
         CoreDepositAction::DepositAccount(DepositAccountAction::Create);
     pub const DEPOSIT_ACCOUNT_UPDATE_STATUS: Self =
         CoreDepositAction::DepositAccount(DepositAccountAction::UpdateStatus);
+    pub const DEPOSIT_ACCOUNT_UPDATE_KYC_LEVEL: Self =
+        CoreDepositAction::DepositAccount(DepositAccountAction::UpdateKycLevel);
+    pub const DEPOSIT_ACCOUNT_CLOSE: Self =
+        CoreDepositAction::DepositAccount(DepositAccountAction::Close);
+    pub const DEPOSIT_ACCOUNT_UPDATE_DORMANCY: Self =
+        CoreDepositAction::DepositAccount(DepositAccountAction::UpdateDormancy);
     pub const DEPOSIT_ACCOUNT_READ_BALANCE: Self =
         CoreDepositAction::DepositAccount(DepositAccountAction::ReadBalance);
     pub const DEPOSIT_ACCOUNT_READ: Self =
@@ -171,6 +421,55 @@ impl CoreDepositAction {
     pub const WITHDRAWAL_CONFIRM: Self = CoreDepositAction::Withdrawal(WithdrawalAction::Confirm);
     pub const WITHDRAWAL_READ: Self = CoreDepositAction::Withdrawal(WithdrawalAction::Read);
     pub const WITHDRAWAL_LIST: Self = CoreDepositAction::Withdrawal(WithdrawalAction::List);
+    pub const WITHDRAWAL_RECORD_PAYOUT_RETURN: Self =
+        CoreDepositAction::Withdrawal(WithdrawalAction::RecordPayoutReturn);
+    pub const WITHDRAWAL_RELEASE_LIMIT: Self =
+        CoreDepositAction::Withdrawal(WithdrawalAction::ReleaseLimit);
+
+    pub const DEPOSIT_ACCOUNT_STATEMENT_GENERATE: Self =
+        CoreDepositAction::DepositAccountStatement(DepositAccountStatementAction::Generate);
+    pub const DEPOSIT_ACCOUNT_STATEMENT_LIST: Self =
+        CoreDepositAction::DepositAccountStatement(DepositAccountStatementAction::List);
+    pub const DEPOSIT_ACCOUNT_STATEMENT_GENERATE_DOWNLOAD_LINK: Self =
+        CoreDepositAction::DepositAccountStatement(
+            DepositAccountStatementAction::GenerateDownloadLink,
+        );
+
+    pub const WITHDRAWAL_LIMIT_OVERRIDE_REQUEST: Self =
+        CoreDepositAction::WithdrawalLimitOverride(WithdrawalLimitOverrideAction::Request);
+    pub const WITHDRAWAL_LIMIT_OVERRIDE_CONCLUDE_APPROVAL_PROCESS: Self =
+        CoreDepositAction::WithdrawalLimitOverride(
+            WithdrawalLimitOverrideAction::ConcludeApprovalProcess,
+        );
+    pub const WITHDRAWAL_LIMIT_OVERRIDE_READ: Self =
+        CoreDepositAction::WithdrawalLimitOverride(WithdrawalLimitOverrideAction::Read);
+    pub const WITHDRAWAL_LIMIT_OVERRIDE_LIST: Self =
+        CoreDepositAction::WithdrawalLimitOverride(WithdrawalLimitOverrideAction::List);
+
+    pub const BANK_STATEMENT_IMPORT_CREATE: Self =
+        CoreDepositAction::BankStatementImport(BankStatementImportAction::Create);
+    pub const BANK_STATEMENT_IMPORT_RESOLVE_EXCEPTION: Self =
+        CoreDepositAction::BankStatementImport(BankStatementImportAction::ResolveException);
+    pub const BANK_STATEMENT_IMPORT_READ: Self =
+        CoreDepositAction::BankStatementImport(BankStatementImportAction::Read);
+    pub const BANK_STATEMENT_IMPORT_LIST: Self =
+        CoreDepositAction::BankStatementImport(BankStatementImportAction::List);
+
+    pub const PAYOUT_BATCH_CREATE: Self = CoreDepositAction::PayoutBatch(PayoutBatchAction::Create);
+    pub const PAYOUT_BATCH_MARK_SENT: Self =
+        CoreDepositAction::PayoutBatch(PayoutBatchAction::MarkSent);
+    pub const PAYOUT_BATCH_GENERATE_DOWNLOAD_LINK: Self =
+        CoreDepositAction::PayoutBatch(PayoutBatchAction::GenerateDownloadLink);
+    pub const PAYOUT_BATCH_READ: Self = CoreDepositAction::PayoutBatch(PayoutBatchAction::Read);
+    pub const PAYOUT_BATCH_LIST: Self = CoreDepositAction::PayoutBatch(PayoutBatchAction::List);
+
+    pub const BENEFICIARY_REGISTER: Self =
+        CoreDepositAction::Beneficiary(BeneficiaryAction::Register);
+    pub const BENEFICIARY_CONCLUDE_APPROVAL_PROCESS: Self =
+        CoreDepositAction::Beneficiary(BeneficiaryAction::ConcludeApprovalProcess);
+    pub const BENEFICIARY_REVOKE: Self = CoreDepositAction::Beneficiary(BeneficiaryAction::Revoke);
+    pub const BENEFICIARY_READ: Self = CoreDepositAction::Beneficiary(BeneficiaryAction::Read);
+    pub const BENEFICIARY_LIST: Self = CoreDepositAction::Beneficiary(BeneficiaryAction::List);
 }
 
 impl Display for CoreDepositAction {
@@ -182,6 +481,11 @@ impl Display for CoreDepositAction {
             Deposit(action) => action.fmt(f),
             ChartOfAccountsIntegrationConfig(action) => action.fmt(f),
             Withdrawal(action) => action.fmt(f),
+            DepositAccountStatement(action) => action.fmt(f),
+            WithdrawalLimitOverride(action) => action.fmt(f),
+            BankStatementImport(action) => action.fmt(f),
+            PayoutBatch(action) => action.fmt(f),
+            Beneficiary(action) => action.fmt(f),
         }
     }
 }
@@ -199,6 +503,17 @@ impl FromStr for CoreDepositAction {
                 CoreDepositAction::from(action.parse::<ChartOfAccountsIntegrationConfigAction>()?)
             }
             Withdrawal => CoreDepositAction::from(action.parse::<WithdrawalAction>()?),
+            DepositAccountStatement => {
+                CoreDepositAction::from(action.parse::<DepositAccountStatementAction>()?)
+            }
+            WithdrawalLimitOverride => {
+                CoreDepositAction::from(action.parse::<WithdrawalLimitOverrideAction>()?)
+            }
+            BankStatementImport => {
+                CoreDepositAction::from(action.parse::<BankStatementImportAction>()?)
+            }
+            PayoutBatch => CoreDepositAction::from(action.parse::<PayoutBatchAction>()?),
+            Beneficiary => CoreDepositAction::from(action.parse::<BeneficiaryAction>()?),
         };
 
         Ok(res)
@@ -210,6 +525,9 @@ impl FromStr for CoreDepositAction {
 pub enum DepositAccountAction {
     Create,
     UpdateStatus,
+    UpdateKycLevel,
+    Close,
+    UpdateDormancy,
     ReadBalance,
     ReadTxHistory,
     Read,
@@ -243,6 +561,8 @@ pub enum WithdrawalAction {
     Cancel,
     Confirm,
     ConcludeApprovalProcess,
+    RecordPayoutReturn,
+    ReleaseLimit,
     Read,
     List,
 }
@@ -266,6 +586,84 @@ impl From<ChartOfAccountsIntegrationConfigAction> for CoreDepositAction {
     }
 }
 
+#[derive(PartialEq, Clone, Copy, Debug, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum DepositAccountStatementAction {
+    Generate,
+    List,
+    GenerateDownloadLink,
+}
+
+impl From<DepositAccountStatementAction> for CoreDepositAction {
+    fn from(action: DepositAccountStatementAction) -> Self {
+        CoreDepositAction::DepositAccountStatement(action)
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum WithdrawalLimitOverrideAction {
+    Request,
+    ConcludeApprovalProcess,
+    Read,
+    List,
+}
+
+impl From<WithdrawalLimitOverrideAction> for CoreDepositAction {
+    fn from(action: WithdrawalLimitOverrideAction) -> Self {
+        CoreDepositAction::WithdrawalLimitOverride(action)
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum BankStatementImportAction {
+    Create,
+    ResolveException,
+    Read,
+    List,
+}
+
+impl From<BankStatementImportAction> for CoreDepositAction {
+    fn from(action: BankStatementImportAction) -> Self {
+        CoreDepositAction::BankStatementImport(action)
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum PayoutBatchAction {
+    Create,
+    MarkSent,
+    GenerateDownloadLink,
+    Read,
+    List,
+}
+
+impl From<PayoutBatchAction> for CoreDepositAction {
+    fn from(action: PayoutBatchAction) -> Self {
+        CoreDepositAction::PayoutBatch(action)
+    }
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum BeneficiaryAction {
+    Register,
+    ConcludeApprovalProcess,
+    Revoke,
+    Read,
+    List,
+}
+
+impl From<BeneficiaryAction> for CoreDepositAction {
+    fn from(action: BeneficiaryAction) -> Self {
+        CoreDepositAction::Beneficiary(action)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
 pub enum DepositAccountType {
     Individual,
     GovernmentEntity,
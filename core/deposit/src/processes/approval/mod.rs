@@ -12,7 +12,8 @@ use outbox::OutboxEventMarker;
 
 use crate::{
     event::CoreDepositEvent,
-    primitives::WithdrawalId,
+    ledger::{DepositLedger, WithdrawalLimitMeta},
+    primitives::{WithdrawalId, WithdrawalLimitWindow},
     withdrawal::{error::WithdrawalError, repo::WithdrawalRepo, Withdrawal},
     CoreDepositAction, CoreDepositObject, WithdrawalAction,
 };
@@ -29,6 +30,7 @@ where
     E: OutboxEventMarker<GovernanceEvent> + OutboxEventMarker<CoreDepositEvent>,
 {
     repo: WithdrawalRepo<E>,
+    ledger: DepositLedger,
     audit: Perms::Audit,
     governance: Governance<Perms, E>,
 }
@@ -40,6 +42,7 @@ where
     fn clone(&self) -> Self {
         Self {
             repo: self.repo.clone(),
+            ledger: self.ledger.clone(),
             audit: self.audit.clone(),
             governance: self.governance.clone(),
         }
@@ -57,11 +60,13 @@ where
 {
     pub fn new(
         repo: &WithdrawalRepo<E>,
+        ledger: &DepositLedger,
         audit: &Perms::Audit,
         governance: &Governance<Perms, E>,
     ) -> Self {
         Self {
             repo: repo.clone(),
+            ledger: ledger.clone(),
             audit: audit.clone(),
             governance: governance.clone(),
         }
@@ -111,11 +116,32 @@ where
             )
             .await?;
         if withdraw
-            .approval_process_concluded(approved, audit_info)
-            .did_execute()
+            .approval_process_concluded(approved, audit_info.clone())
+            .was_ignored()
         {
-            self.repo.update_in_op(&mut db, &mut withdraw).await?;
-            db.commit().await?;
+            return Ok(withdraw);
+        }
+        // A denied withdrawal no longer counts towards the withdrawal limits.
+        let release = if approved {
+            es_entity::Idempotent::Ignored
+        } else {
+            withdraw.release_withdrawal_limits(&WithdrawalLimitWindow::ALL, audit_info)
+        };
+        self.repo.update_in_op(&mut db, &mut withdraw).await?;
+
+        match release {
+            es_entity::Idempotent::Executed(release) => {
+                self.ledger
+                    .release_withdrawal_limit(
+                        db,
+                        release.ledger_tx_id,
+                        withdraw.amount,
+                        release.withdrawal_limit_account_id,
+                        WithdrawalLimitMeta::new(&release.windows),
+                    )
+                    .await?
+            }
+            es_entity::Idempotent::Ignored => db.commit().await?,
         }
         Ok(withdraw)
     }
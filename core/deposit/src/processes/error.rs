@@ -10,6 +10,12 @@ pub enum ProcessError {
     Sqlx(#[from] sqlx::Error),
     #[error("ProcessError - WithdrawalError: {0}")]
     WithdrawalError(#[from] crate::withdrawal::error::WithdrawalError),
+    #[error("ProcessError - WithdrawalLimitOverrideError: {0}")]
+    WithdrawalLimitOverrideError(
+        #[from] crate::withdrawal_limit_override::error::WithdrawalLimitOverrideError,
+    ),
+    #[error("ProcessError - BeneficiaryError: {0}")]
+    BeneficiaryError(#[from] crate::beneficiary::error::BeneficiaryError),
     #[error("ProcessError - AuditError: {0}")]
     AuditError(#[from] audit::error::AuditError),
 }
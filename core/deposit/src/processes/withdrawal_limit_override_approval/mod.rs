@@ -0,0 +1,125 @@
+mod job;
+
+use authz::PermissionCheck;
+use governance::{ApprovalProcessType, GovernanceAction, GovernanceEvent, GovernanceObject};
+
+use audit::AuditSvc;
+use outbox::OutboxEventMarker;
+
+use crate::{
+    account::DepositAccountRepo,
+    event::CoreDepositEvent,
+    ledger::DepositLedger,
+    primitives::WithdrawalLimitOverrideId,
+    withdrawal_limit_override::{
+        error::WithdrawalLimitOverrideError, WithdrawalLimitOverride, WithdrawalLimitOverrideRepo,
+    },
+    CoreDepositAction, CoreDepositObject,
+};
+
+pub use job::*;
+
+pub const APPROVE_WITHDRAWAL_LIMIT_OVERRIDE_PROCESS: ApprovalProcessType =
+    ApprovalProcessType::new("withdrawal-limit-override");
+
+pub struct ApproveWithdrawalLimitOverride<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent> + OutboxEventMarker<CoreDepositEvent>,
+{
+    repo: WithdrawalLimitOverrideRepo,
+    accounts: DepositAccountRepo<E>,
+    ledger: DepositLedger,
+    audit: Perms::Audit,
+}
+
+impl<Perms, E> Clone for ApproveWithdrawalLimitOverride<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<GovernanceEvent> + OutboxEventMarker<CoreDepositEvent>,
+{
+    fn clone(&self) -> Self {
+        Self {
+            repo: self.repo.clone(),
+            accounts: self.accounts.clone(),
+            ledger: self.ledger.clone(),
+            audit: self.audit.clone(),
+        }
+    }
+}
+
+impl<Perms, E> ApproveWithdrawalLimitOverride<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreDepositAction> + From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CoreDepositObject> + From<GovernanceObject>,
+    E: OutboxEventMarker<GovernanceEvent> + OutboxEventMarker<CoreDepositEvent>,
+{
+    pub fn new(
+        repo: &WithdrawalLimitOverrideRepo,
+        accounts: &DepositAccountRepo<E>,
+        ledger: &DepositLedger,
+        audit: &Perms::Audit,
+    ) -> Self {
+        Self {
+            repo: repo.clone(),
+            accounts: accounts.clone(),
+            ledger: ledger.clone(),
+            audit: audit.clone(),
+        }
+    }
+
+    #[es_entity::retry_on_concurrent_modification]
+    pub async fn execute(
+        &self,
+        id: impl es_entity::RetryableInto<WithdrawalLimitOverrideId>,
+        approved: bool,
+    ) -> Result<WithdrawalLimitOverride, WithdrawalLimitOverrideError> {
+        let id = id.into();
+        let mut limit_override = self.repo.find_by_id(id).await?;
+        if limit_override.is_approved_or_denied().is_some() {
+            return Ok(limit_override);
+        }
+        let mut db = self.repo.begin_op().await?;
+        let audit_info = self
+            .audit
+            .record_system_entry_in_tx(
+                db.tx(),
+                CoreDepositObject::withdrawal_limit_override(id),
+                CoreDepositAction::WITHDRAWAL_LIMIT_OVERRIDE_CONCLUDE_APPROVAL_PROCESS,
+            )
+            .await?;
+        if limit_override
+            .approval_process_concluded(approved, audit_info.clone())
+            .was_ignored()
+        {
+            return Ok(limit_override);
+        }
+        self.repo.update_in_op(&mut db, &mut limit_override).await?;
+
+        if !approved {
+            db.commit().await?;
+            return Ok(limit_override);
+        }
+
+        let mut account = self
+            .accounts
+            .find_by_id(limit_override.deposit_account_id)
+            .await?;
+        if account
+            .apply_withdrawal_limit_override(id, limit_override.limits, audit_info)
+            .was_ignored()
+        {
+            db.commit().await?;
+            return Ok(limit_override);
+        }
+        self.accounts.update_in_op(&mut db, &mut account).await?;
+        self.ledger
+            .apply_withdrawal_limit_override(db, id, &account.name, limit_override.limits)
+            .await?;
+
+        Ok(limit_override)
+    }
+}
@@ -48,6 +48,18 @@ where
                     id: entity.id,
                     account_holder_id: entity.account_holder_id,
                 }),
+                Closed { settled_amount, .. } => Some(CoreDepositEvent::DepositAccountClosed {
+                    id: entity.id,
+                    account_holder_id: entity.account_holder_id,
+                    settled_amount: *settled_amount,
+                }),
+                MarkedDormant {
+                    last_activity_at, ..
+                } => Some(CoreDepositEvent::DepositAccountDormant {
+                    id: entity.id,
+                    account_holder_id: entity.account_holder_id,
+                    last_activity_at: *last_activity_at,
+                }),
                 _ => None,
             })
             .collect::<Vec<_>>();
@@ -71,6 +83,11 @@ where
                     deposit_account_id: entity.deposit_account_id,
                     amount: entity.amount,
                 }),
+                PayoutReturned { .. } => Some(CoreDepositEvent::WithdrawalPayoutReturned {
+                    id: entity.id,
+                    deposit_account_id: entity.deposit_account_id,
+                    amount: entity.amount,
+                }),
                 _ => None,
             })
             .collect::<Vec<_>>();
@@ -0,0 +1,387 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use audit::AuditInfo;
+use es_entity::*;
+
+use crate::primitives::*;
+
+use super::error::DepositAccountStatementError;
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+pub enum DepositAccountStatementStatus {
+    Pending,
+    Generated,
+    Failed,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+pub enum DepositAccountStatementFormat {
+    Pdf,
+    Csv,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+pub struct DepositAccountStatementSummary {
+    pub opening_balance: UsdCents,
+    pub closing_balance: UsdCents,
+    pub total_credits: UsdCents,
+    pub total_debits: UsdCents,
+    pub interest: UsdCents,
+    pub fees: UsdCents,
+}
+
+#[derive(Debug)]
+pub struct DepositAccountStatementLocationInCloud<'a> {
+    pub format: DepositAccountStatementFormat,
+    pub bucket: &'a str,
+    pub path_in_bucket: &'a str,
+}
+
+impl<'a> From<&DepositAccountStatementLocationInCloud<'a>> for cloud_storage::LocationInCloud<'a> {
+    fn from(meta: &DepositAccountStatementLocationInCloud<'a>) -> Self {
+        cloud_storage::LocationInCloud {
+            bucket: meta.bucket,
+            path_in_bucket: meta.path_in_bucket,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DepositAccountStatementDownloadLink {
+    pub deposit_account_statement_id: DepositAccountStatementId,
+    pub format: DepositAccountStatementFormat,
+    pub url: String,
+}
+
+#[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[es_event(id = "DepositAccountStatementId")]
+pub enum DepositAccountStatementEvent {
+    Initialized {
+        id: DepositAccountStatementId,
+        deposit_account_id: DepositAccountId,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+        audit_info: AuditInfo,
+    },
+    Generated {
+        summary: DepositAccountStatementSummary,
+        bucket: String,
+        csv_path_in_bucket: String,
+        pdf_path_in_bucket: String,
+        audit_info: AuditInfo,
+        recorded_at: DateTime<Utc>,
+    },
+    GenerationFailed {
+        error: String,
+        audit_info: AuditInfo,
+        recorded_at: DateTime<Utc>,
+    },
+    DownloadLinkGenerated {
+        format: DepositAccountStatementFormat,
+        audit_info: AuditInfo,
+        recorded_at: DateTime<Utc>,
+    },
+}
+
+#[derive(EsEntity, Builder)]
+#[builder(pattern = "owned", build_fn(error = "EsEntityError"))]
+pub struct DepositAccountStatement {
+    pub id: DepositAccountStatementId,
+    pub deposit_account_id: DepositAccountId,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub reference: String,
+    events: EntityEvents<DepositAccountStatementEvent>,
+}
+
+impl DepositAccountStatement {
+    pub fn created_at(&self) -> DateTime<Utc> {
+        self.events
+            .entity_first_persisted_at()
+            .expect("entity_first_persisted_at not found")
+    }
+
+    pub fn status(&self) -> DepositAccountStatementStatus {
+        for e in self.events.iter_all().rev() {
+            match e {
+                DepositAccountStatementEvent::Generated { .. } => {
+                    return DepositAccountStatementStatus::Generated
+                }
+                DepositAccountStatementEvent::GenerationFailed { .. } => {
+                    return DepositAccountStatementStatus::Failed
+                }
+                _ => {}
+            }
+        }
+        DepositAccountStatementStatus::Pending
+    }
+
+    pub fn summary(&self) -> Option<DepositAccountStatementSummary> {
+        self.events.iter_all().rev().find_map(|e| match e {
+            DepositAccountStatementEvent::Generated { summary, .. } => Some(*summary),
+            _ => None,
+        })
+    }
+
+    pub fn last_error(&self) -> Option<&str> {
+        self.events.iter_all().rev().find_map(|e| match e {
+            DepositAccountStatementEvent::GenerationFailed { error, .. } => Some(error.as_str()),
+            _ => None,
+        })
+    }
+
+    pub(super) fn generated(
+        &mut self,
+        summary: DepositAccountStatementSummary,
+        bucket: String,
+        csv_path_in_bucket: String,
+        pdf_path_in_bucket: String,
+        audit_info: AuditInfo,
+    ) -> Idempotent<()> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            DepositAccountStatementEvent::Generated { .. }
+        );
+
+        self.events.push(DepositAccountStatementEvent::Generated {
+            summary,
+            bucket,
+            csv_path_in_bucket,
+            pdf_path_in_bucket,
+            audit_info,
+            recorded_at: Utc::now(),
+        });
+        Idempotent::Executed(())
+    }
+
+    pub(super) fn generation_failed(&mut self, error: String, audit_info: AuditInfo) {
+        self.events
+            .push(DepositAccountStatementEvent::GenerationFailed {
+                error,
+                audit_info,
+                recorded_at: Utc::now(),
+            });
+    }
+
+    pub(crate) fn download_link_generated(
+        &mut self,
+        format: DepositAccountStatementFormat,
+        audit_info: AuditInfo,
+    ) -> Result<DepositAccountStatementLocationInCloud<'_>, DepositAccountStatementError> {
+        if self.status() != DepositAccountStatementStatus::Generated {
+            return Err(DepositAccountStatementError::StatementNotReady);
+        }
+
+        self.events
+            .push(DepositAccountStatementEvent::DownloadLinkGenerated {
+                format,
+                audit_info,
+                recorded_at: Utc::now(),
+            });
+
+        let (bucket, path_in_bucket) = self
+            .events
+            .iter_all()
+            .rev()
+            .find_map(|e| match e {
+                DepositAccountStatementEvent::Generated {
+                    bucket,
+                    csv_path_in_bucket,
+                    pdf_path_in_bucket,
+                    ..
+                } => match format {
+                    DepositAccountStatementFormat::Csv => Some((bucket, csv_path_in_bucket)),
+                    DepositAccountStatementFormat::Pdf => Some((bucket, pdf_path_in_bucket)),
+                },
+                _ => None,
+            })
+            .expect("Generated event exists");
+
+        Ok(DepositAccountStatementLocationInCloud {
+            format,
+            bucket,
+            path_in_bucket,
+        })
+    }
+}
+
+impl TryFromEvents<DepositAccountStatementEvent> for DepositAccountStatement {
+    fn try_from_events(
+        events: EntityEvents<DepositAccountStatementEvent>,
+    ) -> Result<Self, EsEntityError> {
+        let mut builder = DepositAccountStatementBuilder::default();
+        for event in events.iter_all() {
+            if let DepositAccountStatementEvent::Initialized {
+                id,
+                deposit_account_id,
+                period_start,
+                period_end,
+                ..
+            } = event
+            {
+                builder = builder
+                    .id(*id)
+                    .deposit_account_id(*deposit_account_id)
+                    .period_start(*period_start)
+                    .period_end(*period_end)
+                    .reference(statement_reference(*deposit_account_id, *period_start));
+            }
+        }
+        builder.events(events).build()
+    }
+}
+
+#[derive(Debug, Builder)]
+pub struct NewDepositAccountStatement {
+    #[builder(setter(into))]
+    pub(super) id: DepositAccountStatementId,
+    #[builder(setter(into))]
+    pub(super) deposit_account_id: DepositAccountId,
+    pub(super) period_start: NaiveDate,
+    pub(super) period_end: NaiveDate,
+    #[builder(setter(into))]
+    pub(super) audit_info: AuditInfo,
+}
+
+impl NewDepositAccountStatement {
+    pub fn builder() -> NewDepositAccountStatementBuilder {
+        NewDepositAccountStatementBuilder::default()
+    }
+
+    pub(super) fn reference(&self) -> String {
+        statement_reference(self.deposit_account_id, self.period_start)
+    }
+}
+
+impl IntoEvents<DepositAccountStatementEvent> for NewDepositAccountStatement {
+    fn into_events(self) -> EntityEvents<DepositAccountStatementEvent> {
+        EntityEvents::init(
+            self.id,
+            [DepositAccountStatementEvent::Initialized {
+                id: self.id,
+                deposit_account_id: self.deposit_account_id,
+                period_start: self.period_start,
+                period_end: self.period_end,
+                audit_info: self.audit_info,
+            }],
+        )
+    }
+}
+
+pub(crate) fn statement_reference(
+    deposit_account_id: DepositAccountId,
+    period_start: NaiveDate,
+) -> String {
+    format!("{}:{}", deposit_account_id, period_start.format("%Y-%m"))
+}
+
+#[cfg(test)]
+mod test {
+    use audit::AuditEntryId;
+
+    use super::*;
+
+    fn dummy_audit_info() -> AuditInfo {
+        AuditInfo {
+            audit_entry_id: AuditEntryId::from(1),
+            sub: "sub".to_string(),
+        }
+    }
+
+    fn statement_from(new_statement: NewDepositAccountStatement) -> DepositAccountStatement {
+        DepositAccountStatement::try_from_events(new_statement.into_events()).unwrap()
+    }
+
+    fn new_statement() -> NewDepositAccountStatement {
+        NewDepositAccountStatement::builder()
+            .id(DepositAccountStatementId::new())
+            .deposit_account_id(DepositAccountId::new())
+            .period_start(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap())
+            .period_end(NaiveDate::from_ymd_opt(2025, 1, 31).unwrap())
+            .audit_info(dummy_audit_info())
+            .build()
+            .unwrap()
+    }
+
+    fn summary() -> DepositAccountStatementSummary {
+        DepositAccountStatementSummary {
+            opening_balance: UsdCents::from(1_000),
+            closing_balance: UsdCents::from(1_500),
+            total_credits: UsdCents::from(700),
+            total_debits: UsdCents::from(200),
+            interest: UsdCents::ZERO,
+            fees: UsdCents::ZERO,
+        }
+    }
+
+    #[test]
+    fn reference_is_unique_per_account_and_month() {
+        let new_statement = new_statement();
+        let reference = new_statement.reference();
+        let statement = statement_from(new_statement);
+        assert_eq!(statement.reference, reference);
+        assert!(reference.ends_with(":2025-01"));
+    }
+
+    #[test]
+    fn download_link_requires_generated_statement() {
+        let mut statement = statement_from(new_statement());
+        assert_eq!(statement.status(), DepositAccountStatementStatus::Pending);
+        assert!(matches!(
+            statement
+                .download_link_generated(DepositAccountStatementFormat::Pdf, dummy_audit_info()),
+            Err(DepositAccountStatementError::StatementNotReady)
+        ));
+    }
+
+    #[test]
+    fn download_link_points_to_requested_format() {
+        let mut statement = statement_from(new_statement());
+        assert!(statement
+            .generated(
+                summary(),
+                "bucket".to_string(),
+                "statement.csv".to_string(),
+                "statement.pdf".to_string(),
+                dummy_audit_info(),
+            )
+            .did_execute());
+        assert_eq!(statement.status(), DepositAccountStatementStatus::Generated);
+        assert_eq!(statement.summary(), Some(summary()));
+
+        let location = statement
+            .download_link_generated(DepositAccountStatementFormat::Pdf, dummy_audit_info())
+            .unwrap();
+        assert_eq!(location.path_in_bucket, "statement.pdf");
+        let location = statement
+            .download_link_generated(DepositAccountStatementFormat::Csv, dummy_audit_info())
+            .unwrap();
+        assert_eq!(location.path_in_bucket, "statement.csv");
+    }
+
+    #[test]
+    fn generated_is_idempotent() {
+        let mut statement = statement_from(new_statement());
+        let _ = statement.generated(
+            summary(),
+            "bucket".to_string(),
+            "statement.csv".to_string(),
+            "statement.pdf".to_string(),
+            dummy_audit_info(),
+        );
+        assert!(statement
+            .generated(
+                summary(),
+                "bucket".to_string(),
+                "statement.csv".to_string(),
+                "statement.pdf".to_string(),
+                dummy_audit_info(),
+            )
+            .was_ignored());
+    }
+}
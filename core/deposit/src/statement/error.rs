@@ -0,0 +1,25 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum DepositAccountStatementError {
+    #[error("DepositAccountStatementError - Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("DepositAccountStatementError - EsEntityError: {0}")]
+    EsEntityError(es_entity::EsEntityError),
+    #[error("DepositAccountStatementError - CursorDestructureError: {0}")]
+    CursorDestructureError(#[from] es_entity::CursorDestructureError),
+    #[error("DepositAccountStatementError - StorageError: {0}")]
+    StorageError(#[from] cloud_storage::error::StorageError),
+    #[error("DepositAccountStatementError - DepositLedgerError: {0}")]
+    DepositLedgerError(#[from] crate::ledger::error::DepositLedgerError),
+    #[error("DepositAccountStatementError - DepositAccountError: {0}")]
+    DepositAccountError(#[from] crate::account::error::DepositAccountError),
+    #[error("DepositAccountStatementError - ConversionError: {0}")]
+    ConversionError(#[from] core_money::ConversionError),
+    #[error("DepositAccountStatementError - CsvError: {0}")]
+    CsvError(String),
+    #[error("DepositAccountStatementError - StatementNotReady")]
+    StatementNotReady,
+}
+
+es_entity::from_es_entity_error!(DepositAccountStatementError);
@@ -0,0 +1,437 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use csv::Writer;
+use rust_decimal::Decimal;
+
+use cala_ledger::DebitOrCredit;
+
+use crate::{
+    history::{
+        CANCEL_WITHDRAW, CONFIRM_DISBURSAL, INITIATE_WITHDRAW, RECORD_DEPOSIT,
+        RECORD_PAYMENT_ALLOCATION, RETURN_WITHDRAW,
+    },
+    primitives::{CalaTransactionId, DepositAccountId, UsdCents},
+};
+
+use super::{entity::DepositAccountStatementSummary, error::DepositAccountStatementError, pdf};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum StatementLineKind {
+    Deposit,
+    Withdrawal,
+    CancelledWithdrawal,
+    ReturnedWithdrawal,
+    Disbursal,
+    Payment,
+    Interest,
+    Fee,
+    Other,
+}
+
+impl StatementLineKind {
+    fn from_entry_type(entry_type: &str) -> Self {
+        match entry_type {
+            RECORD_DEPOSIT => Self::Deposit,
+            INITIATE_WITHDRAW => Self::Withdrawal,
+            CANCEL_WITHDRAW => Self::CancelledWithdrawal,
+            RETURN_WITHDRAW => Self::ReturnedWithdrawal,
+            CONFIRM_DISBURSAL => Self::Disbursal,
+            RECORD_PAYMENT_ALLOCATION => Self::Payment,
+            t if t.contains("INTEREST") => Self::Interest,
+            t if t.contains("FEE") => Self::Fee,
+            _ => Self::Other,
+        }
+    }
+
+    fn label(&self) -> &'static str {
+        match self {
+            Self::Deposit => "Deposit",
+            Self::Withdrawal => "Withdrawal",
+            Self::CancelledWithdrawal => "Cancelled Withdrawal",
+            Self::ReturnedWithdrawal => "Returned Withdrawal",
+            Self::Disbursal => "Disbursal",
+            Self::Payment => "Payment",
+            Self::Interest => "Interest",
+            Self::Fee => "Fee",
+            Self::Other => "Other",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct StatementEntry {
+    pub effective: NaiveDate,
+    pub recorded_at: DateTime<Utc>,
+    pub transaction_id: CalaTransactionId,
+    pub kind: StatementLineKind,
+    pub description: String,
+    pub direction: DebitOrCredit,
+    pub amount: UsdCents,
+}
+
+impl StatementEntry {
+    pub fn try_new(
+        entry: cala_ledger::entry::Entry,
+        effective: NaiveDate,
+    ) -> Result<Self, DepositAccountStatementError> {
+        let values = entry.values();
+        Ok(Self {
+            effective,
+            recorded_at: entry.created_at(),
+            transaction_id: values.transaction_id,
+            kind: StatementLineKind::from_entry_type(&values.entry_type),
+            description: values.description.clone().unwrap_or_default(),
+            direction: values.direction,
+            amount: UsdCents::try_from_usd(values.units)?,
+        })
+    }
+}
+
+#[derive(Debug, Clone)]
+pub(super) struct StatementLine {
+    pub entry: StatementEntry,
+    pub running_balance: Decimal,
+}
+
+pub(super) struct StatementData {
+    pub deposit_account_id: DepositAccountId,
+    pub account_reference: String,
+    pub period_start: NaiveDate,
+    pub period_end: NaiveDate,
+    pub lines: Vec<StatementLine>,
+    pub summary: DepositAccountStatementSummary,
+}
+
+impl StatementData {
+    /// Builds the statement from the current settled balance and every settled
+    /// entry recorded since the start of the period (in any order). Entries are
+    /// placed by the effective date of their transaction: those effective
+    /// before the period are part of the opening balance and those effective
+    /// after it are only used to roll the balance back to the opening figure.
+    pub fn new(
+        deposit_account_id: DepositAccountId,
+        account_reference: String,
+        period_start: NaiveDate,
+        period_end: NaiveDate,
+        current_balance: UsdCents,
+        mut entries: Vec<StatementEntry>,
+    ) -> Result<Self, DepositAccountStatementError> {
+        entries.sort_by_key(|e| (e.effective, e.recorded_at));
+
+        let net_since_start: Decimal = entries
+            .iter()
+            .filter(|e| e.effective >= period_start)
+            .map(signed_amount)
+            .sum();
+        let opening_balance = current_balance.to_usd() - net_since_start;
+
+        let mut running_balance = opening_balance;
+        let mut total_credits = UsdCents::ZERO;
+        let mut total_debits = UsdCents::ZERO;
+        let mut interest = UsdCents::ZERO;
+        let mut fees = UsdCents::ZERO;
+        let mut lines = Vec::new();
+        for entry in entries
+            .into_iter()
+            .filter(|e| e.effective >= period_start && e.effective <= period_end)
+        {
+            running_balance += signed_amount(&entry);
+            match entry.direction {
+                DebitOrCredit::Credit => total_credits += entry.amount,
+                DebitOrCredit::Debit => total_debits += entry.amount,
+            }
+            match entry.kind {
+                StatementLineKind::Interest => interest += entry.amount,
+                StatementLineKind::Fee => fees += entry.amount,
+                _ => {}
+            }
+            lines.push(StatementLine {
+                entry,
+                running_balance,
+            });
+        }
+
+        Ok(Self {
+            deposit_account_id,
+            account_reference,
+            period_start,
+            period_end,
+            lines,
+            summary: DepositAccountStatementSummary {
+                opening_balance: UsdCents::try_from_usd(opening_balance)?,
+                closing_balance: UsdCents::try_from_usd(running_balance)?,
+                total_credits,
+                total_debits,
+                interest,
+                fees,
+            },
+        })
+    }
+
+    pub fn to_csv(&self) -> Result<Vec<u8>, DepositAccountStatementError> {
+        let mut wtr = Writer::from_writer(vec![]);
+        wtr.write_record([
+            "Date",
+            "Transaction Id",
+            "Type",
+            "Description",
+            "Debit",
+            "Credit",
+            "Running Balance",
+        ])
+        .map_err(|e| DepositAccountStatementError::CsvError(e.to_string()))?;
+
+        wtr.write_record([
+            self.period_start.to_string(),
+            String::new(),
+            "Opening Balance".to_string(),
+            String::new(),
+            String::new(),
+            String::new(),
+            self.summary.opening_balance.formatted_usd(),
+        ])
+        .map_err(|e| DepositAccountStatementError::CsvError(e.to_string()))?;
+
+        for line in self.lines.iter() {
+            let (debit, credit) = debit_and_credit(&line.entry);
+            wtr.write_record([
+                line.entry.effective.to_string(),
+                line.entry.transaction_id.to_string(),
+                line.entry.kind.label().to_string(),
+                line.entry.description.clone(),
+                debit,
+                credit,
+                format!("{:.2}", line.running_balance),
+            ])
+            .map_err(|e| DepositAccountStatementError::CsvError(e.to_string()))?;
+        }
+
+        wtr.write_record([
+            self.period_end.to_string(),
+            String::new(),
+            "Closing Balance".to_string(),
+            String::new(),
+            self.summary.total_debits.formatted_usd(),
+            self.summary.total_credits.formatted_usd(),
+            self.summary.closing_balance.formatted_usd(),
+        ])
+        .map_err(|e| DepositAccountStatementError::CsvError(e.to_string()))?;
+
+        let csv_data = wtr
+            .into_inner()
+            .map_err(|e| DepositAccountStatementError::CsvError(e.to_string()))?;
+
+        Ok(csv_data)
+    }
+
+    pub fn to_pdf(&self) -> Vec<u8> {
+        let mut lines = vec![
+            "DEPOSIT ACCOUNT STATEMENT".to_string(),
+            String::new(),
+            format!("Account:   {}", self.account_reference),
+            format!("Account Id: {}", self.deposit_account_id),
+            format!("Period:    {} to {}", self.period_start, self.period_end),
+            String::new(),
+            format!(
+                "Opening balance: {:>14}",
+                self.summary.opening_balance.formatted_usd()
+            ),
+            format!(
+                "Total credits:   {:>14}",
+                self.summary.total_credits.formatted_usd()
+            ),
+            format!(
+                "Total debits:    {:>14}",
+                self.summary.total_debits.formatted_usd()
+            ),
+            format!(
+                "Interest:        {:>14}",
+                self.summary.interest.formatted_usd()
+            ),
+            format!("Fees:            {:>14}", self.summary.fees.formatted_usd()),
+            format!(
+                "Closing balance: {:>14}",
+                self.summary.closing_balance.formatted_usd()
+            ),
+            String::new(),
+            format!(
+                "{:<10} {:<22} {:>13} {:>13} {:>14}",
+                "Date", "Type", "Debit", "Credit", "Balance"
+            ),
+        ];
+        for line in self.lines.iter() {
+            let (debit, credit) = debit_and_credit(&line.entry);
+            lines.push(format!(
+                "{:<10} {:<22} {:>13} {:>13} {:>14.2}",
+                line.entry.effective,
+                line.entry.kind.label(),
+                debit,
+                credit,
+                line.running_balance
+            ));
+            if !line.entry.description.is_empty() {
+                lines.push(format!("           {}", line.entry.description));
+            }
+        }
+        if self.lines.is_empty() {
+            lines.push("No transactions in this period.".to_string());
+        }
+
+        pdf::render(&lines)
+    }
+}
+
+fn signed_amount(entry: &StatementEntry) -> Decimal {
+    match entry.direction {
+        DebitOrCredit::Credit => entry.amount.to_usd(),
+        DebitOrCredit::Debit => -entry.amount.to_usd(),
+    }
+}
+
+fn debit_and_credit(entry: &StatementEntry) -> (String, String) {
+    match entry.direction {
+        DebitOrCredit::Debit => (entry.amount.formatted_usd(), String::new()),
+        DebitOrCredit::Credit => (String::new(), entry.amount.formatted_usd()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn at(day: u32) -> DateTime<Utc> {
+        NaiveDate::from_ymd_opt(2025, 1, 1)
+            .unwrap()
+            .checked_add_days(chrono::Days::new(u64::from(day) - 1))
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap()
+            .and_utc()
+    }
+
+    fn entry(
+        day: u32,
+        kind: StatementLineKind,
+        direction: DebitOrCredit,
+        amount: u64,
+    ) -> StatementEntry {
+        StatementEntry {
+            effective: at(day).date_naive(),
+            recorded_at: at(day),
+            transaction_id: CalaTransactionId::new(),
+            kind,
+            description: String::new(),
+            direction,
+            amount: UsdCents::from(amount),
+        }
+    }
+
+    fn statement(current_balance: u64, entries: Vec<StatementEntry>) -> StatementData {
+        StatementData::new(
+            DepositAccountId::new(),
+            "ref".to_string(),
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            UsdCents::from(current_balance),
+            entries,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn entry_kind_from_entry_type() {
+        assert_eq!(
+            StatementLineKind::from_entry_type(RECORD_DEPOSIT),
+            StatementLineKind::Deposit
+        );
+        assert_eq!(
+            StatementLineKind::from_entry_type("ACCRUE_INTEREST_CR"),
+            StatementLineKind::Interest
+        );
+        assert_eq!(
+            StatementLineKind::from_entry_type("CHARGE_FEE_DR"),
+            StatementLineKind::Fee
+        );
+    }
+
+    #[test]
+    fn running_balance_and_summary() {
+        let data = statement(
+            1_300,
+            vec![
+                entry(10, StatementLineKind::Withdrawal, DebitOrCredit::Debit, 200),
+                entry(2, StatementLineKind::Deposit, DebitOrCredit::Credit, 500),
+                entry(31, StatementLineKind::Interest, DebitOrCredit::Credit, 10),
+                entry(31, StatementLineKind::Fee, DebitOrCredit::Debit, 10),
+            ],
+        );
+
+        assert_eq!(data.summary.opening_balance, UsdCents::from(1_000));
+        assert_eq!(data.summary.closing_balance, UsdCents::from(1_300));
+        assert_eq!(data.summary.total_credits, UsdCents::from(510));
+        assert_eq!(data.summary.total_debits, UsdCents::from(210));
+        assert_eq!(data.summary.interest, UsdCents::from(10));
+        assert_eq!(data.summary.fees, UsdCents::from(10));
+        let balances: Vec<_> = data.lines.iter().map(|l| l.running_balance).collect();
+        assert_eq!(
+            balances,
+            vec![
+                Decimal::new(1500, 2),
+                Decimal::new(1300, 2),
+                Decimal::new(1310, 2),
+                Decimal::new(1300, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn entries_after_period_only_affect_opening_balance() {
+        let data = statement(
+            1_500,
+            vec![
+                entry(5, StatementLineKind::Deposit, DebitOrCredit::Credit, 500),
+                entry(40, StatementLineKind::Deposit, DebitOrCredit::Credit, 300),
+            ],
+        );
+
+        assert_eq!(data.lines.len(), 1);
+        assert_eq!(data.summary.opening_balance, UsdCents::from(700));
+        assert_eq!(data.summary.closing_balance, UsdCents::from(1_200));
+    }
+
+    #[test]
+    fn entries_are_placed_by_effective_date() {
+        let mut backdated = entry(5, StatementLineKind::Deposit, DebitOrCredit::Credit, 500);
+        backdated.effective = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+        let data = statement(
+            700,
+            vec![
+                backdated,
+                entry(6, StatementLineKind::Deposit, DebitOrCredit::Credit, 200),
+            ],
+        );
+
+        assert_eq!(data.lines.len(), 1);
+        assert_eq!(data.summary.opening_balance, UsdCents::from(500));
+        assert_eq!(data.summary.closing_balance, UsdCents::from(700));
+    }
+
+    #[test]
+    fn csv_has_opening_and_closing_rows() {
+        let data = statement(
+            500,
+            vec![entry(
+                5,
+                StatementLineKind::Deposit,
+                DebitOrCredit::Credit,
+                500,
+            )],
+        );
+        let csv = String::from_utf8(data.to_csv().unwrap()).unwrap();
+        let rows: Vec<_> = csv.lines().collect();
+        assert_eq!(rows.len(), 4);
+        assert!(rows[1].contains("Opening Balance"));
+        assert!(rows[1].ends_with("0.00"));
+        assert!(rows[3].contains("Closing Balance"));
+        assert!(rows[3].ends_with("5.00"));
+    }
+}
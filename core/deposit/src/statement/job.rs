@@ -0,0 +1,254 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use cloud_storage::Storage;
+use job::*;
+use outbox::OutboxEventMarker;
+
+use crate::{
+    account::DepositAccountRepo, event::CoreDepositEvent, ledger::DepositLedger,
+    primitives::DepositAccountStatementId, CoreDepositAction, CoreDepositObject,
+};
+
+use super::{
+    entity::{
+        DepositAccountStatement, DepositAccountStatementStatus, DepositAccountStatementSummary,
+    },
+    error::DepositAccountStatementError,
+    generate::{StatementData, StatementEntry},
+    repo::DepositAccountStatementRepo,
+};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct GenerateDepositAccountStatementConfig<Perms, E> {
+    pub deposit_account_statement_id: DepositAccountStatementId,
+    pub _phantom: std::marker::PhantomData<(Perms, E)>,
+}
+
+impl<Perms, E> GenerateDepositAccountStatementConfig<Perms, E> {
+    pub fn new(deposit_account_statement_id: DepositAccountStatementId) -> Self {
+        Self {
+            deposit_account_statement_id,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<Perms, E> JobConfig for GenerateDepositAccountStatementConfig<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreDepositAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreDepositObject>,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    type Initializer = GenerateDepositAccountStatementInitializer<Perms, E>;
+}
+
+pub struct GenerateDepositAccountStatementInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    statements: DepositAccountStatementRepo,
+    accounts: DepositAccountRepo<E>,
+    ledger: DepositLedger,
+    storage: Storage,
+    audit: Perms::Audit,
+}
+
+impl<Perms, E> GenerateDepositAccountStatementInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreDepositAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreDepositObject>,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    pub fn new(
+        statements: &DepositAccountStatementRepo,
+        accounts: &DepositAccountRepo<E>,
+        ledger: &DepositLedger,
+        storage: &Storage,
+        audit: &Perms::Audit,
+    ) -> Self {
+        Self {
+            statements: statements.clone(),
+            accounts: accounts.clone(),
+            ledger: ledger.clone(),
+            storage: storage.clone(),
+            audit: audit.clone(),
+        }
+    }
+}
+
+pub const GENERATE_DEPOSIT_ACCOUNT_STATEMENT_JOB: JobType =
+    JobType::new("generate-deposit-account-statement");
+
+impl<Perms, E> JobInitializer for GenerateDepositAccountStatementInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreDepositAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreDepositObject>,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        GENERATE_DEPOSIT_ACCOUNT_STATEMENT_JOB
+    }
+
+    fn init(&self, job: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(GenerateDepositAccountStatementJobRunner::<
+            Perms,
+            E,
+        > {
+            config: job.config()?,
+            statements: self.statements.clone(),
+            accounts: self.accounts.clone(),
+            ledger: self.ledger.clone(),
+            storage: self.storage.clone(),
+            audit: self.audit.clone(),
+        }))
+    }
+}
+
+pub struct GenerateDepositAccountStatementJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    config: GenerateDepositAccountStatementConfig<Perms, E>,
+    statements: DepositAccountStatementRepo,
+    accounts: DepositAccountRepo<E>,
+    ledger: DepositLedger,
+    storage: Storage,
+    audit: Perms::Audit,
+}
+
+impl<Perms, E> GenerateDepositAccountStatementJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    async fn statement_data(
+        &self,
+        statement: &DepositAccountStatement,
+    ) -> Result<StatementData, DepositAccountStatementError> {
+        let account = self
+            .accounts
+            .find_by_id(statement.deposit_account_id)
+            .await?;
+        let balance = self.ledger.balance(account.id).await?;
+        let since = statement
+            .period_start
+            .and_hms_opt(0, 0, 0)
+            .expect("midnight is valid")
+            .and_utc();
+        let entries = self.ledger.settled_entries_since(account.id, since).await?;
+        let effective_dates = self
+            .ledger
+            .effective_dates(
+                &entries
+                    .iter()
+                    .map(|entry| entry.values().transaction_id)
+                    .collect::<Vec<_>>(),
+            )
+            .await?;
+        let entries = entries
+            .into_iter()
+            .map(|entry| {
+                let effective = effective_dates[&entry.values().transaction_id];
+                StatementEntry::try_new(entry, effective)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        StatementData::new(
+            account.id,
+            account.reference,
+            statement.period_start,
+            statement.period_end,
+            balance.settled,
+            entries,
+        )
+    }
+
+    async fn generate(
+        &self,
+        statement: &DepositAccountStatement,
+    ) -> Result<(DepositAccountStatementSummary, String, String), DepositAccountStatementError>
+    {
+        let data = self.statement_data(statement).await?;
+        let csv_path_in_bucket = format!("deposit_account_statements/{}.csv", statement.id);
+        let pdf_path_in_bucket = format!("deposit_account_statements/{}.pdf", statement.id);
+        self.storage
+            .upload(data.to_csv()?, &csv_path_in_bucket, "text/csv")
+            .await?;
+        self.storage
+            .upload(data.to_pdf(), &pdf_path_in_bucket, "application/pdf")
+            .await?;
+        Ok((data.summary, csv_path_in_bucket, pdf_path_in_bucket))
+    }
+}
+
+#[async_trait]
+impl<Perms, E> JobRunner for GenerateDepositAccountStatementJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreDepositAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreDepositObject>,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    async fn run(
+        &self,
+        _current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let mut statement = self
+            .statements
+            .find_by_id(self.config.deposit_account_statement_id)
+            .await?;
+        if statement.status() == DepositAccountStatementStatus::Generated {
+            return Ok(JobCompletion::Complete);
+        }
+
+        let mut db = self.statements.begin_op().await?;
+        let audit_info = self
+            .audit
+            .record_system_entry_in_tx(
+                db.tx(),
+                CoreDepositObject::all_deposit_account_statements(),
+                CoreDepositAction::DEPOSIT_ACCOUNT_STATEMENT_GENERATE,
+            )
+            .await?;
+
+        match self.generate(&statement).await {
+            Ok((summary, csv_path_in_bucket, pdf_path_in_bucket)) => {
+                let _ = statement.generated(
+                    summary,
+                    self.storage.bucket_name().to_string(),
+                    csv_path_in_bucket,
+                    pdf_path_in_bucket,
+                    audit_info,
+                );
+            }
+            Err(e) => {
+                // The failure is recorded for visibility and the job is
+                // retried, as the month-end job won't enqueue it again.
+                statement.generation_failed(e.to_string(), audit_info);
+                self.statements
+                    .update_in_op(&mut db, &mut statement)
+                    .await?;
+                db.commit().await?;
+                return Err(e.into());
+            }
+        }
+
+        self.statements
+            .update_in_op(&mut db, &mut statement)
+            .await?;
+        let (now, tx) = (db.now(), db.into_tx());
+        let db_static = es_entity::DbOp::new(tx, now);
+        Ok(JobCompletion::CompleteWithOp(db_static))
+    }
+}
@@ -0,0 +1,15 @@
+mod entity;
+pub mod error;
+mod generate;
+mod job;
+mod month_end_job;
+mod pdf;
+mod repo;
+
+pub use entity::{
+    DepositAccountStatement, DepositAccountStatementDownloadLink, DepositAccountStatementFormat,
+    DepositAccountStatementStatus, DepositAccountStatementSummary,
+};
+pub(crate) use job::*;
+pub(crate) use month_end_job::*;
+pub(crate) use repo::*;
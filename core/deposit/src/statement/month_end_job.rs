@@ -0,0 +1,246 @@
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use job::*;
+use outbox::OutboxEventMarker;
+
+use crate::{
+    account::DepositAccountRepo,
+    event::CoreDepositEvent,
+    primitives::{DepositAccountStatementId, DepositAccountStatus},
+    CoreDepositAction, CoreDepositObject,
+};
+
+use super::{
+    entity::{statement_reference, NewDepositAccountStatement},
+    job::GenerateDepositAccountStatementConfig,
+    repo::DepositAccountStatementRepo,
+};
+
+#[derive(serde::Serialize)]
+pub struct MonthEndDepositAccountStatementsJobConfig<Perms, E> {
+    _phantom: std::marker::PhantomData<(Perms, E)>,
+}
+impl<Perms, E> MonthEndDepositAccountStatementsJobConfig<Perms, E> {
+    pub fn new() -> Self {
+        Self {
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+impl<Perms, E> JobConfig for MonthEndDepositAccountStatementsJobConfig<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreDepositAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreDepositObject>,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    type Initializer = MonthEndDepositAccountStatementsJobInitializer<Perms, E>;
+}
+
+pub struct MonthEndDepositAccountStatementsJobInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    statements: DepositAccountStatementRepo,
+    accounts: DepositAccountRepo<E>,
+    jobs: Jobs,
+    audit: Perms::Audit,
+}
+
+impl<Perms, E> MonthEndDepositAccountStatementsJobInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreDepositAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreDepositObject>,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    pub fn new(
+        statements: &DepositAccountStatementRepo,
+        accounts: &DepositAccountRepo<E>,
+        jobs: &Jobs,
+        audit: &Perms::Audit,
+    ) -> Self {
+        Self {
+            statements: statements.clone(),
+            accounts: accounts.clone(),
+            jobs: jobs.clone(),
+            audit: audit.clone(),
+        }
+    }
+}
+
+const MONTH_END_DEPOSIT_ACCOUNT_STATEMENTS_JOB: JobType =
+    JobType::new("month-end-deposit-account-statements");
+impl<Perms, E> JobInitializer for MonthEndDepositAccountStatementsJobInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreDepositAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreDepositObject>,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        MONTH_END_DEPOSIT_ACCOUNT_STATEMENTS_JOB
+    }
+
+    fn init(&self, _: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(MonthEndDepositAccountStatementsJobRunner::<
+            Perms,
+            E,
+        > {
+            statements: self.statements.clone(),
+            accounts: self.accounts.clone(),
+            jobs: self.jobs.clone(),
+            audit: self.audit.clone(),
+        }))
+    }
+}
+
+pub struct MonthEndDepositAccountStatementsJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    statements: DepositAccountStatementRepo,
+    accounts: DepositAccountRepo<E>,
+    jobs: Jobs,
+    audit: Perms::Audit,
+}
+
+#[async_trait]
+impl<Perms, E> JobRunner for MonthEndDepositAccountStatementsJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreDepositAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreDepositObject>,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    async fn run(
+        &self,
+        _current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let now = chrono::Utc::now();
+        let (period_start, period_end) = previous_month(now);
+
+        let mut after = None;
+        loop {
+            let ret = self
+                .accounts
+                .list_for_status_by_created_at(
+                    DepositAccountStatus::Active,
+                    es_entity::PaginatedQueryArgs::<
+                        crate::account::deposit_account_cursor::DepositAccountsByCreatedAtCursor,
+                    > {
+                        first: 100,
+                        after,
+                    },
+                    es_entity::ListDirection::Ascending,
+                )
+                .await?;
+
+            for account in ret.entities.iter() {
+                if account.created_at().date_naive() > period_end {
+                    continue;
+                }
+
+                let reference = statement_reference(account.id, period_start);
+                match self.statements.find_by_reference(&reference).await {
+                    Ok(_) => continue,
+                    Err(e) if e.was_not_found() => (),
+                    Err(e) => return Err(e.into()),
+                }
+
+                let mut db = self.statements.begin_op().await?;
+                let audit_info = self
+                    .audit
+                    .record_system_entry_in_tx(
+                        db.tx(),
+                        CoreDepositObject::all_deposit_account_statements(),
+                        CoreDepositAction::DEPOSIT_ACCOUNT_STATEMENT_GENERATE,
+                    )
+                    .await?;
+                let id = DepositAccountStatementId::new();
+                let new_statement = NewDepositAccountStatement::builder()
+                    .id(id)
+                    .deposit_account_id(account.id)
+                    .period_start(period_start)
+                    .period_end(period_end)
+                    .audit_info(audit_info)
+                    .build()
+                    .expect("Could not build new deposit account statement");
+                self.statements.create_in_op(&mut db, new_statement).await?;
+                self.jobs
+                    .create_and_spawn_in_op(
+                        &mut db,
+                        id,
+                        GenerateDepositAccountStatementConfig::<Perms, E>::new(id),
+                    )
+                    .await?;
+                db.commit().await?;
+            }
+
+            if !ret.has_next_page {
+                break;
+            }
+            after = ret.end_cursor;
+        }
+
+        Ok(JobCompletion::RescheduleAt(next_month_start(now)))
+    }
+}
+
+fn previous_month(now: DateTime<Utc>) -> (NaiveDate, NaiveDate) {
+    let current_month_start =
+        NaiveDate::from_ymd_opt(now.year(), now.month(), 1).expect("first day of month is valid");
+    let period_end = current_month_start.pred_opt().expect("date out of range");
+    let period_start = NaiveDate::from_ymd_opt(period_end.year(), period_end.month(), 1)
+        .expect("first day of month is valid");
+    (period_start, period_end)
+}
+
+fn next_month_start(now: DateTime<Utc>) -> DateTime<Utc> {
+    let (year, month) = if now.month() == 12 {
+        (now.year() + 1, 1)
+    } else {
+        (now.year(), now.month() + 1)
+    };
+    NaiveDate::from_ymd_opt(year, month, 1)
+        .expect("first day of month is valid")
+        .and_hms_opt(0, 0, 0)
+        .expect("midnight is valid")
+        .and_utc()
+}
+
+#[cfg(test)]
+mod test {
+    use chrono::TimeZone;
+
+    use super::*;
+
+    #[test]
+    fn previous_month_of_january_is_december() {
+        let now = Utc.with_ymd_and_hms(2025, 1, 1, 0, 5, 0).unwrap();
+        assert_eq!(
+            previous_month(now),
+            (
+                NaiveDate::from_ymd_opt(2024, 12, 1).unwrap(),
+                NaiveDate::from_ymd_opt(2024, 12, 31).unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn next_month_start_rolls_over_year() {
+        let now = Utc.with_ymd_and_hms(2024, 12, 15, 10, 0, 0).unwrap();
+        assert_eq!(
+            next_month_start(now),
+            Utc.with_ymd_and_hms(2025, 1, 1, 0, 0, 0).unwrap()
+        );
+    }
+}
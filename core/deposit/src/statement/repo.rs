@@ -0,0 +1,31 @@
+use sqlx::PgPool;
+
+use es_entity::*;
+
+use crate::primitives::{DepositAccountId, DepositAccountStatementId};
+
+use super::{entity::*, error::*};
+
+#[derive(EsRepo, Clone)]
+#[es_repo(
+    entity = "DepositAccountStatement",
+    err = "DepositAccountStatementError",
+    columns(
+        deposit_account_id(ty = "DepositAccountId", list_for, update(persist = false)),
+        reference(
+            ty = "String",
+            create(accessor = "reference()"),
+            update(persist = false)
+        ),
+    ),
+    tbl_prefix = "core"
+)]
+pub struct DepositAccountStatementRepo {
+    pool: PgPool,
+}
+
+impl DepositAccountStatementRepo {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
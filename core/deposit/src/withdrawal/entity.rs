@@ -4,7 +4,9 @@ use serde::{Deserialize, Serialize};
 use es_entity::*;
 
 use crate::primitives::{
-    ApprovalProcessId, CalaTransactionId, DepositAccountId, UsdCents, WithdrawalId,
+    ApprovalProcessId, BeneficiaryDetails, BeneficiaryId, CalaAccountId, CalaTransactionId,
+    DepositAccountId,
+    PayoutBatchId, PayoutDestination, UsdCents, WithdrawalId, WithdrawalLimitWindow,
 };
 use audit::AuditInfo;
 
@@ -20,6 +22,32 @@ pub enum WithdrawalStatus {
     Cancelled,
 }
 
+/// Where the money of a withdrawal is on its way out of the bank.
+#[derive(
+    Debug, Copy, Clone, Serialize, Deserialize, Eq, PartialEq, strum::Display, strum::EnumString,
+)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum WithdrawalPayoutStatus {
+    /// Not confirmed yet, cancelled, or without a payout destination.
+    NotPayable,
+    AwaitingBatch,
+    Batched,
+    Sent,
+    Returned,
+}
+
+/// The beneficiary a withdrawal is sent to, as registered when the
+/// withdrawal was initiated.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WithdrawalBeneficiary {
+    pub id: BeneficiaryId,
+    pub label: String,
+    pub details: BeneficiaryDetails,
+}
+
+#[allow(clippy::large_enum_variant)]
 #[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 #[es_event(id = "WithdrawalId")]
@@ -30,6 +58,11 @@ pub enum WithdrawalEvent {
         amount: UsdCents,
         reference: String,
         approval_process_id: ApprovalProcessId,
+        payout_destination: Option<PayoutDestination>,
+        #[serde(default)]
+        beneficiary: Option<WithdrawalBeneficiary>,
+        #[serde(default)]
+        withdrawal_limit_account_id: Option<CalaAccountId>,
         audit_info: AuditInfo,
     },
     ApprovalProcessConcluded {
@@ -45,6 +78,32 @@ pub enum WithdrawalEvent {
         ledger_tx_id: CalaTransactionId,
         audit_info: AuditInfo,
     },
+    PayoutBatched {
+        payout_batch_id: PayoutBatchId,
+        audit_info: AuditInfo,
+    },
+    PayoutSent {
+        payout_batch_id: PayoutBatchId,
+        audit_info: AuditInfo,
+    },
+    PayoutReturned {
+        reason: String,
+        ledger_tx_id: CalaTransactionId,
+        audit_info: AuditInfo,
+    },
+    WithdrawalLimitReleased {
+        windows: Vec<WithdrawalLimitWindow>,
+        ledger_tx_id: CalaTransactionId,
+        audit_info: AuditInfo,
+    },
+}
+
+/// Withdrawal limit windows a withdrawal stops counting towards.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct WithdrawalLimitRelease {
+    pub ledger_tx_id: CalaTransactionId,
+    pub withdrawal_limit_account_id: CalaAccountId,
+    pub windows: Vec<WithdrawalLimitWindow>,
 }
 
 #[derive(EsEntity, Builder)]
@@ -55,8 +114,16 @@ pub struct Withdrawal {
     pub reference: String,
     pub amount: UsdCents,
     pub approval_process_id: ApprovalProcessId,
+    #[builder(default)]
+    pub payout_destination: Option<PayoutDestination>,
+    #[builder(default)]
+    pub beneficiary: Option<WithdrawalBeneficiary>,
     #[builder(setter(strip_option), default)]
     pub cancelled_tx_id: Option<CalaTransactionId>,
+    #[builder(setter(strip_option), default)]
+    pub returned_tx_id: Option<CalaTransactionId>,
+    #[builder(default)]
+    withdrawal_limit_account_id: Option<CalaAccountId>,
 
     events: EntityEvents<WithdrawalEvent>,
 }
@@ -147,6 +214,154 @@ impl Withdrawal {
         }
     }
 
+    pub fn payout_status(&self) -> WithdrawalPayoutStatus {
+        let latest = self.events.iter_all().rev().find_map(|e| match e {
+            WithdrawalEvent::PayoutBatched { .. } => Some(WithdrawalPayoutStatus::Batched),
+            WithdrawalEvent::PayoutSent { .. } => Some(WithdrawalPayoutStatus::Sent),
+            WithdrawalEvent::PayoutReturned { .. } => Some(WithdrawalPayoutStatus::Returned),
+            _ => None,
+        });
+        match latest {
+            Some(status) => status,
+            None if self.is_confirmed() && self.payout_destination.is_some() => {
+                WithdrawalPayoutStatus::AwaitingBatch
+            }
+            None => WithdrawalPayoutStatus::NotPayable,
+        }
+    }
+
+    pub fn payout_batch_id(&self) -> Option<PayoutBatchId> {
+        self.events.iter_all().rev().find_map(|e| match e {
+            WithdrawalEvent::PayoutBatched {
+                payout_batch_id, ..
+            } => Some(*payout_batch_id),
+            _ => None,
+        })
+    }
+
+    pub fn payout_return_reason(&self) -> Option<&str> {
+        self.events.iter_all().rev().find_map(|e| match e {
+            WithdrawalEvent::PayoutReturned { reason, .. } => Some(reason.as_str()),
+            _ => None,
+        })
+    }
+
+    pub(crate) fn payout_batched(
+        &mut self,
+        payout_batch_id: PayoutBatchId,
+        audit_info: AuditInfo,
+    ) -> Result<Idempotent<()>, WithdrawalError> {
+        match self.payout_status() {
+            WithdrawalPayoutStatus::AwaitingBatch => (),
+            WithdrawalPayoutStatus::Batched if self.payout_batch_id() == Some(payout_batch_id) => {
+                return Ok(Idempotent::Ignored)
+            }
+            _ => return Err(WithdrawalError::NotAwaitingPayout(self.id)),
+        }
+        self.events.push(WithdrawalEvent::PayoutBatched {
+            payout_batch_id,
+            audit_info,
+        });
+        Ok(Idempotent::Executed(()))
+    }
+
+    pub(crate) fn payout_sent(
+        &mut self,
+        payout_batch_id: PayoutBatchId,
+        audit_info: AuditInfo,
+    ) -> Idempotent<()> {
+        if self.payout_status() != WithdrawalPayoutStatus::Batched
+            || self.payout_batch_id() != Some(payout_batch_id)
+        {
+            return Idempotent::Ignored;
+        }
+        self.events.push(WithdrawalEvent::PayoutSent {
+            payout_batch_id,
+            audit_info,
+        });
+        Idempotent::Executed(())
+    }
+
+    /// Records that the receiving bank sent the payment back. The returned
+    /// transaction id is used to credit the amount back to the account.
+    pub(crate) fn payout_returned(
+        &mut self,
+        reason: String,
+        audit_info: AuditInfo,
+    ) -> Result<Idempotent<CalaTransactionId>, WithdrawalError> {
+        match self.payout_status() {
+            WithdrawalPayoutStatus::Sent => (),
+            WithdrawalPayoutStatus::Returned => return Ok(Idempotent::Ignored),
+            _ => return Err(WithdrawalError::PayoutNotSent(self.id)),
+        }
+        let ledger_tx_id = CalaTransactionId::new();
+        self.events.push(WithdrawalEvent::PayoutReturned {
+            reason,
+            ledger_tx_id,
+            audit_info,
+        });
+        self.returned_tx_id = Some(ledger_tx_id);
+        Ok(Idempotent::Executed(ledger_tx_id))
+    }
+
+    /// The ledger account the withdrawal is held on for the withdrawal limits
+    /// and the windows it still counts towards. Cancelling a withdrawal takes
+    /// it out of every window.
+    pub(crate) fn withdrawal_limits_held(
+        &self,
+    ) -> Option<(CalaAccountId, Vec<WithdrawalLimitWindow>)> {
+        let withdrawal_limit_account_id = self.withdrawal_limit_account_id?;
+        if self.is_cancelled() {
+            return None;
+        }
+        let released: Vec<_> = self
+            .events
+            .iter_all()
+            .filter_map(|e| match e {
+                WithdrawalEvent::WithdrawalLimitReleased { windows, .. } => Some(windows),
+                _ => None,
+            })
+            .flatten()
+            .collect();
+        let held: Vec<_> = WithdrawalLimitWindow::ALL
+            .into_iter()
+            .filter(|window| !released.contains(&window))
+            .collect();
+        if held.is_empty() {
+            None
+        } else {
+            Some((withdrawal_limit_account_id, held))
+        }
+    }
+
+    pub(crate) fn release_withdrawal_limits(
+        &mut self,
+        windows: &[WithdrawalLimitWindow],
+        audit_info: AuditInfo,
+    ) -> Idempotent<WithdrawalLimitRelease> {
+        let Some((withdrawal_limit_account_id, held)) = self.withdrawal_limits_held() else {
+            return Idempotent::Ignored;
+        };
+        let windows: Vec<_> = held
+            .into_iter()
+            .filter(|window| windows.contains(window))
+            .collect();
+        if windows.is_empty() {
+            return Idempotent::Ignored;
+        }
+        let ledger_tx_id = CalaTransactionId::new();
+        self.events.push(WithdrawalEvent::WithdrawalLimitReleased {
+            windows: windows.clone(),
+            ledger_tx_id,
+            audit_info,
+        });
+        Idempotent::Executed(WithdrawalLimitRelease {
+            ledger_tx_id,
+            withdrawal_limit_account_id,
+            windows,
+        })
+    }
+
     pub fn approval_process_concluded(
         &mut self,
         approved: bool,
@@ -176,6 +391,9 @@ impl TryFromEvents<WithdrawalEvent> for Withdrawal {
                     deposit_account_id,
                     amount,
                     approval_process_id,
+                    payout_destination,
+                    beneficiary,
+                    withdrawal_limit_account_id,
                     ..
                 } => {
                     builder = builder
@@ -184,10 +402,16 @@ impl TryFromEvents<WithdrawalEvent> for Withdrawal {
                         .amount(*amount)
                         .reference(reference.clone())
                         .approval_process_id(*approval_process_id)
+                        .payout_destination(payout_destination.clone())
+                        .beneficiary(beneficiary.clone())
+                        .withdrawal_limit_account_id(*withdrawal_limit_account_id)
                 }
                 WithdrawalEvent::Cancelled { ledger_tx_id, .. } => {
                     builder = builder.cancelled_tx_id(*ledger_tx_id)
                 }
+                WithdrawalEvent::PayoutReturned { ledger_tx_id, .. } => {
+                    builder = builder.returned_tx_id(*ledger_tx_id)
+                }
                 _ => (),
             }
         }
@@ -207,6 +431,10 @@ pub struct NewWithdrawal {
     #[builder(setter(into))]
     pub(super) approval_process_id: ApprovalProcessId,
     reference: Option<String>,
+    #[builder(setter(strip_option), default)]
+    pub(super) beneficiary: Option<WithdrawalBeneficiary>,
+    #[builder(setter(into, strip_option), default)]
+    pub(super) withdrawal_limit_account_id: Option<CalaAccountId>,
     #[builder(setter(into))]
     pub audit_info: AuditInfo,
 }
@@ -223,12 +451,33 @@ impl NewWithdrawal {
             Some(reference) => reference.to_string(),
         }
     }
+
+    pub(super) fn payout_status(&self) -> WithdrawalPayoutStatus {
+        WithdrawalPayoutStatus::NotPayable
+    }
+
+    fn payout_destination(&self) -> Option<PayoutDestination> {
+        self.beneficiary
+            .as_ref()
+            .and_then(|beneficiary| beneficiary.details.payout_destination())
+    }
 }
 
 impl NewWithdrawalBuilder {
     fn validate(&self) -> Result<(), String> {
         match self.amount {
-            Some(amount) if amount.is_zero() => Err("Withdrawal amount cannot be zero".to_string()),
+            Some(amount) if amount.is_zero() => {
+                return Err("Withdrawal amount cannot be zero".to_string())
+            }
+            _ => (),
+        }
+        match &self.beneficiary {
+            Some(Some(WithdrawalBeneficiary {
+                details: BeneficiaryDetails::BankAccount(destination),
+                ..
+            })) if !destination.has_valid_routing_number() => {
+                Err("Payout destination has an invalid routing number".to_string())
+            }
             _ => Ok(()),
         }
     }
@@ -240,10 +489,13 @@ impl IntoEvents<WithdrawalEvent> for NewWithdrawal {
             self.id,
             [WithdrawalEvent::Initialized {
                 reference: self.reference(),
+                payout_destination: self.payout_destination(),
                 id: self.id,
                 deposit_account_id: self.deposit_account_id,
                 amount: self.amount,
                 approval_process_id: self.approval_process_id,
+                beneficiary: self.beneficiary,
+                withdrawal_limit_account_id: self.withdrawal_limit_account_id,
                 audit_info: self.audit_info,
             }],
         )
@@ -309,4 +561,193 @@ mod test {
 
         assert!(withdrawal.is_ok());
     }
+
+    fn destination(routing_number: &str) -> PayoutDestination {
+        PayoutDestination {
+            account_holder_name: "Jane Doe".to_string(),
+            routing_number: routing_number.to_string(),
+            account_number: "000123456789".to_string(),
+            account_type: crate::primitives::PayoutAccountType::Checking,
+        }
+    }
+
+    fn beneficiary(details: BeneficiaryDetails) -> WithdrawalBeneficiary {
+        WithdrawalBeneficiary {
+            id: BeneficiaryId::new(),
+            label: "own account".to_string(),
+            details,
+        }
+    }
+
+    fn confirmed_withdrawal(details: BeneficiaryDetails) -> Withdrawal {
+        let new_withdrawal = NewWithdrawal::builder()
+            .id(WithdrawalId::new())
+            .deposit_account_id(DepositAccountId::new())
+            .amount(UsdCents::from(10_000))
+            .reference(None)
+            .approval_process_id(ApprovalProcessId::new())
+            .beneficiary(beneficiary(details))
+            .audit_info(dummy_audit_info())
+            .build()
+            .unwrap();
+        let mut withdrawal = Withdrawal::try_from_events(new_withdrawal.into_events()).unwrap();
+        let _ = withdrawal.approval_process_concluded(true, dummy_audit_info());
+        withdrawal.confirm(dummy_audit_info()).unwrap();
+        withdrawal
+    }
+
+    #[test]
+    fn errors_when_routing_number_is_invalid() {
+        let withdrawal = NewWithdrawal::builder()
+            .id(WithdrawalId::new())
+            .deposit_account_id(DepositAccountId::new())
+            .amount(UsdCents::ONE)
+            .reference(None)
+            .approval_process_id(ApprovalProcessId::new())
+            .beneficiary(beneficiary(BeneficiaryDetails::BankAccount(destination(
+                "123456789",
+            ))))
+            .audit_info(dummy_audit_info())
+            .build();
+
+        assert!(matches!(
+            withdrawal,
+            Err(NewWithdrawalBuilderError::ValidationError(_))
+        ));
+    }
+
+    #[test]
+    fn confirmed_withdrawals_to_crypto_addresses_are_not_payable() {
+        let withdrawal = confirmed_withdrawal(BeneficiaryDetails::CryptoAddress {
+            network: "bitcoin".to_string(),
+            address: "bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq".to_string(),
+        });
+        assert!(withdrawal.payout_destination.is_none());
+        assert_eq!(
+            withdrawal.payout_status(),
+            WithdrawalPayoutStatus::NotPayable
+        );
+    }
+
+    #[test]
+    fn payout_lifecycle() {
+        let mut withdrawal =
+            confirmed_withdrawal(BeneficiaryDetails::BankAccount(destination("021000021")));
+        assert_eq!(
+            withdrawal.payout_destination,
+            Some(destination("021000021"))
+        );
+        assert_eq!(
+            withdrawal.payout_status(),
+            WithdrawalPayoutStatus::AwaitingBatch
+        );
+        assert!(matches!(
+            withdrawal.payout_returned("R01".to_string(), dummy_audit_info()),
+            Err(WithdrawalError::PayoutNotSent(_))
+        ));
+
+        let batch_id = PayoutBatchId::new();
+        assert!(withdrawal
+            .payout_batched(batch_id, dummy_audit_info())
+            .unwrap()
+            .did_execute());
+        assert!(withdrawal
+            .payout_batched(batch_id, dummy_audit_info())
+            .unwrap()
+            .was_ignored());
+        assert!(matches!(
+            withdrawal.payout_batched(PayoutBatchId::new(), dummy_audit_info()),
+            Err(WithdrawalError::NotAwaitingPayout(_))
+        ));
+        assert_eq!(withdrawal.payout_batch_id(), Some(batch_id));
+
+        assert!(withdrawal
+            .payout_sent(PayoutBatchId::new(), dummy_audit_info())
+            .was_ignored());
+        assert!(withdrawal
+            .payout_sent(batch_id, dummy_audit_info())
+            .did_execute());
+        assert_eq!(withdrawal.payout_status(), WithdrawalPayoutStatus::Sent);
+
+        let Idempotent::Executed(tx_id) = withdrawal
+            .payout_returned("R01".to_string(), dummy_audit_info())
+            .unwrap()
+        else {
+            panic!("return should be recorded");
+        };
+        assert_eq!(withdrawal.returned_tx_id, Some(tx_id));
+        assert_eq!(withdrawal.payout_status(), WithdrawalPayoutStatus::Returned);
+        assert_eq!(withdrawal.payout_return_reason(), Some("R01"));
+        assert!(withdrawal
+            .payout_returned("R01".to_string(), dummy_audit_info())
+            .unwrap()
+            .was_ignored());
+    }
+
+    fn withdrawal_held_on(withdrawal_limit_account_id: CalaAccountId) -> Withdrawal {
+        let new_withdrawal = NewWithdrawal::builder()
+            .id(WithdrawalId::new())
+            .deposit_account_id(DepositAccountId::new())
+            .amount(UsdCents::from(10_000))
+            .reference(None)
+            .approval_process_id(ApprovalProcessId::new())
+            .withdrawal_limit_account_id(withdrawal_limit_account_id)
+            .audit_info(dummy_audit_info())
+            .build()
+            .unwrap();
+        Withdrawal::try_from_events(new_withdrawal.into_events()).unwrap()
+    }
+
+    #[test]
+    fn withdrawal_limit_windows_are_released_once() {
+        let withdrawal_limit_account_id = CalaAccountId::new();
+        let mut withdrawal = withdrawal_held_on(withdrawal_limit_account_id);
+        assert_eq!(
+            withdrawal.withdrawal_limits_held(),
+            Some((
+                withdrawal_limit_account_id,
+                WithdrawalLimitWindow::ALL.to_vec()
+            ))
+        );
+
+        let Idempotent::Executed(release) = withdrawal
+            .release_withdrawal_limits(&[WithdrawalLimitWindow::Daily], dummy_audit_info())
+        else {
+            panic!("daily window should be released");
+        };
+        assert_eq!(
+            release.withdrawal_limit_account_id,
+            withdrawal_limit_account_id
+        );
+        assert_eq!(release.windows, vec![WithdrawalLimitWindow::Daily]);
+        assert!(withdrawal
+            .release_withdrawal_limits(&[WithdrawalLimitWindow::Daily], dummy_audit_info())
+            .was_ignored());
+        assert_eq!(
+            withdrawal.withdrawal_limits_held(),
+            Some((
+                withdrawal_limit_account_id,
+                vec![WithdrawalLimitWindow::Monthly]
+            ))
+        );
+
+        let Idempotent::Executed(release) =
+            withdrawal.release_withdrawal_limits(&WithdrawalLimitWindow::ALL, dummy_audit_info())
+        else {
+            panic!("monthly window should be released");
+        };
+        assert_eq!(release.windows, vec![WithdrawalLimitWindow::Monthly]);
+        assert_eq!(withdrawal.withdrawal_limits_held(), None);
+    }
+
+    #[test]
+    fn cancelled_withdrawals_hold_no_withdrawal_limit_windows() {
+        let mut withdrawal = withdrawal_held_on(CalaAccountId::new());
+        withdrawal.cancel(dummy_audit_info()).unwrap();
+
+        assert_eq!(withdrawal.withdrawal_limits_held(), None);
+        assert!(withdrawal
+            .release_withdrawal_limits(&WithdrawalLimitWindow::ALL, dummy_audit_info())
+            .was_ignored());
+    }
 }
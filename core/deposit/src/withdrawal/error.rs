@@ -16,8 +16,14 @@ pub enum WithdrawalError {
     AlreadyCancelled(WithdrawalId),
     #[error("WithdrawalError - NotApproved: {0}")]
     NotApproved(WithdrawalId),
+    #[error("WithdrawalError - NotAwaitingPayout: {0}")]
+    NotAwaitingPayout(WithdrawalId),
+    #[error("WithdrawalError - PayoutNotSent: {0}")]
+    PayoutNotSent(WithdrawalId),
     #[error("WithdrawalError - AuditError: {0}")]
     AuditError(#[from] audit::error::AuditError),
+    #[error("WithdrawalError - DepositLedgerError: {0}")]
+    DepositLedgerError(#[from] crate::ledger::error::DepositLedgerError),
 }
 
 es_entity::from_es_entity_error!(WithdrawalError);
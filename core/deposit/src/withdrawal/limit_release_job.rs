@@ -0,0 +1,159 @@
+use async_trait::async_trait;
+use serde::{Deserialize, Serialize};
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use job::*;
+use outbox::OutboxEventMarker;
+
+use crate::{
+    event::CoreDepositEvent,
+    ledger::{DepositLedger, WithdrawalLimitMeta},
+    primitives::{WithdrawalId, WithdrawalLimitWindow},
+    CoreDepositAction, CoreDepositObject,
+};
+
+use super::repo::WithdrawalRepo;
+
+/// Scheduled when a withdrawal is initiated, once per window, to take it out
+/// of the window when the window has rolled past it.
+#[derive(Clone, Serialize, Deserialize)]
+pub struct WithdrawalLimitReleaseJobConfig<Perms, E> {
+    pub withdrawal_id: WithdrawalId,
+    pub window: WithdrawalLimitWindow,
+    pub _phantom: std::marker::PhantomData<(Perms, E)>,
+}
+
+impl<Perms, E> WithdrawalLimitReleaseJobConfig<Perms, E> {
+    pub fn new(withdrawal_id: WithdrawalId, window: WithdrawalLimitWindow) -> Self {
+        Self {
+            withdrawal_id,
+            window,
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+
+impl<Perms, E> JobConfig for WithdrawalLimitReleaseJobConfig<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreDepositAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreDepositObject>,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    type Initializer = WithdrawalLimitReleaseJobInitializer<Perms, E>;
+}
+
+pub struct WithdrawalLimitReleaseJobInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    withdrawals: WithdrawalRepo<E>,
+    ledger: DepositLedger,
+    audit: Perms::Audit,
+}
+
+impl<Perms, E> WithdrawalLimitReleaseJobInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreDepositAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreDepositObject>,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    pub fn new(
+        withdrawals: &WithdrawalRepo<E>,
+        ledger: &DepositLedger,
+        audit: &Perms::Audit,
+    ) -> Self {
+        Self {
+            withdrawals: withdrawals.clone(),
+            ledger: ledger.clone(),
+            audit: audit.clone(),
+        }
+    }
+}
+
+const WITHDRAWAL_LIMIT_RELEASE_JOB: JobType = JobType::new("withdrawal-limit-release");
+impl<Perms, E> JobInitializer for WithdrawalLimitReleaseJobInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreDepositAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreDepositObject>,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        WITHDRAWAL_LIMIT_RELEASE_JOB
+    }
+
+    fn init(&self, job: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(WithdrawalLimitReleaseJobRunner::<Perms, E> {
+            config: job.config()?,
+            withdrawals: self.withdrawals.clone(),
+            ledger: self.ledger.clone(),
+            audit: self.audit.clone(),
+        }))
+    }
+}
+
+pub struct WithdrawalLimitReleaseJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    config: WithdrawalLimitReleaseJobConfig<Perms, E>,
+    withdrawals: WithdrawalRepo<E>,
+    ledger: DepositLedger,
+    audit: Perms::Audit,
+}
+
+#[async_trait]
+impl<Perms, E> JobRunner for WithdrawalLimitReleaseJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<CoreDepositAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<CoreDepositObject>,
+    E: OutboxEventMarker<CoreDepositEvent>,
+{
+    async fn run(
+        &self,
+        _current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let mut withdrawal = self
+            .withdrawals
+            .find_by_id(self.config.withdrawal_id)
+            .await?;
+
+        let mut db = self.withdrawals.begin_op().await?;
+        let audit_info = self
+            .audit
+            .record_system_entry_in_tx(
+                db.tx(),
+                CoreDepositObject::withdrawal(withdrawal.id),
+                CoreDepositAction::WITHDRAWAL_RELEASE_LIMIT,
+            )
+            .await?;
+        let es_entity::Idempotent::Executed(release) =
+            withdrawal.release_withdrawal_limits(&[self.config.window], audit_info)
+        else {
+            return Ok(JobCompletion::Complete);
+        };
+        self.withdrawals
+            .update_in_op(&mut db, &mut withdrawal)
+            .await?;
+        self.ledger
+            .release_withdrawal_limit(
+                db,
+                release.ledger_tx_id,
+                withdrawal.amount,
+                release.withdrawal_limit_account_id,
+                WithdrawalLimitMeta::new(&release.windows),
+            )
+            .await?;
+
+        Ok(JobCompletion::Complete)
+    }
+}
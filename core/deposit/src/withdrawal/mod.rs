@@ -1,8 +1,10 @@
 mod entity;
 pub mod error;
+mod limit_release_job;
 pub mod repo;
 
 pub(super) use entity::*;
-pub use entity::{Withdrawal, WithdrawalStatus};
+pub use entity::{Withdrawal, WithdrawalBeneficiary, WithdrawalPayoutStatus, WithdrawalStatus};
+pub(super) use limit_release_job::*;
 pub use repo::withdrawal_cursor::WithdrawalsByCreatedAtCursor;
 pub(super) use repo::*;
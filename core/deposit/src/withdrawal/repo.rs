@@ -19,7 +19,14 @@ use super::{entity::*, error::*};
         deposit_account_id(ty = "DepositAccountId", list_for, update(persist = false)),
         approval_process_id(ty = "ApprovalProcessId", update(persist = false)),
         cancelled_tx_id(ty = "Option<CalaTransactionId>", create(persist = false)),
-        reference(ty = "String", create(accessor = "reference()"))
+        returned_tx_id(ty = "Option<CalaTransactionId>", create(persist = false)),
+        reference(ty = "String", create(accessor = "reference()")),
+        payout_status(
+            ty = "WithdrawalPayoutStatus",
+            list_for,
+            create(accessor = "payout_status()"),
+            update(accessor = "payout_status()")
+        )
     ),
     tbl_prefix = "core",
     post_persist_hook = "publish"
@@ -56,6 +63,24 @@ where
         }
     }
 
+    /// Locks the withdrawals awaiting a payout batch, skipping those that a
+    /// concurrent batch has already claimed.
+    pub async fn lock_awaiting_batch_in_op(
+        &self,
+        op: &mut es_entity::DbOp<'_>,
+    ) -> Result<Vec<WithdrawalId>, WithdrawalError> {
+        let rows = sqlx::query!(
+            r#"SELECT id as "id: WithdrawalId" FROM core_withdrawals
+               WHERE payout_status = $1
+               ORDER BY created_at
+               FOR UPDATE SKIP LOCKED"#,
+            WithdrawalPayoutStatus::AwaitingBatch as WithdrawalPayoutStatus,
+        )
+        .fetch_all(&mut **op.tx())
+        .await?;
+        Ok(rows.into_iter().map(|row| row.id).collect())
+    }
+
     async fn publish(
         &self,
         db: &mut es_entity::DbOp<'_>,
@@ -67,3 +92,41 @@ where
             .await
     }
 }
+
+mod withdrawal_payout_status_sqlx {
+    use sqlx::{postgres::*, Type};
+
+    use crate::withdrawal::WithdrawalPayoutStatus;
+
+    impl Type<Postgres> for WithdrawalPayoutStatus {
+        fn type_info() -> PgTypeInfo {
+            <String as Type<Postgres>>::type_info()
+        }
+
+        fn compatible(ty: &PgTypeInfo) -> bool {
+            <String as Type<Postgres>>::compatible(ty)
+        }
+    }
+
+    impl sqlx::Encode<'_, Postgres> for WithdrawalPayoutStatus {
+        fn encode_by_ref(
+            &self,
+            buf: &mut PgArgumentBuffer,
+        ) -> Result<sqlx::encode::IsNull, Box<dyn std::error::Error + Sync + Send>> {
+            <String as sqlx::Encode<'_, Postgres>>::encode(self.to_string(), buf)
+        }
+    }
+
+    impl<'r> sqlx::Decode<'r, Postgres> for WithdrawalPayoutStatus {
+        fn decode(value: PgValueRef<'r>) -> Result<Self, Box<dyn std::error::Error + Sync + Send>> {
+            let s = <String as sqlx::Decode<Postgres>>::decode(value)?;
+            Ok(s.parse().map_err(|e: strum::ParseError| Box::new(e))?)
+        }
+    }
+
+    impl PgHasArrayType for WithdrawalPayoutStatus {
+        fn array_type_info() -> PgTypeInfo {
+            <String as sqlx::postgres::PgHasArrayType>::array_type_info()
+        }
+    }
+}
@@ -0,0 +1,231 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use es_entity::*;
+
+use audit::AuditInfo;
+
+use crate::{config::WithdrawalLimits, primitives::*};
+
+#[derive(Debug, Copy, Clone, Serialize, Deserialize, Eq, PartialEq)]
+#[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
+pub enum WithdrawalLimitOverrideStatus {
+    PendingApproval,
+    Approved,
+    Denied,
+}
+
+#[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[es_event(id = "WithdrawalLimitOverrideId")]
+pub enum WithdrawalLimitOverrideEvent {
+    Initialized {
+        id: WithdrawalLimitOverrideId,
+        deposit_account_id: DepositAccountId,
+        limits: WithdrawalLimits,
+        reason: String,
+        approval_process_id: ApprovalProcessId,
+        audit_info: AuditInfo,
+    },
+    ApprovalProcessConcluded {
+        approval_process_id: ApprovalProcessId,
+        approved: bool,
+        audit_info: AuditInfo,
+    },
+}
+
+#[derive(EsEntity, Builder)]
+#[builder(pattern = "owned", build_fn(error = "EsEntityError"))]
+pub struct WithdrawalLimitOverride {
+    pub id: WithdrawalLimitOverrideId,
+    pub deposit_account_id: DepositAccountId,
+    pub limits: WithdrawalLimits,
+    pub reason: String,
+    pub approval_process_id: ApprovalProcessId,
+
+    events: EntityEvents<WithdrawalLimitOverrideEvent>,
+}
+
+impl WithdrawalLimitOverride {
+    pub fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.events
+            .entity_first_persisted_at()
+            .expect("No events for withdrawal limit override")
+    }
+
+    pub fn is_approved_or_denied(&self) -> Option<bool> {
+        self.events.iter_all().find_map(|e| {
+            if let WithdrawalLimitOverrideEvent::ApprovalProcessConcluded { approved, .. } = e {
+                Some(*approved)
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn status(&self) -> WithdrawalLimitOverrideStatus {
+        match self.is_approved_or_denied() {
+            Some(true) => WithdrawalLimitOverrideStatus::Approved,
+            Some(false) => WithdrawalLimitOverrideStatus::Denied,
+            None => WithdrawalLimitOverrideStatus::PendingApproval,
+        }
+    }
+
+    pub fn approval_process_concluded(
+        &mut self,
+        approved: bool,
+        audit_info: AuditInfo,
+    ) -> Idempotent<()> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            WithdrawalLimitOverrideEvent::ApprovalProcessConcluded { .. }
+        );
+        self.events
+            .push(WithdrawalLimitOverrideEvent::ApprovalProcessConcluded {
+                approval_process_id: self.approval_process_id,
+                approved,
+                audit_info,
+            });
+        Idempotent::Executed(())
+    }
+}
+
+impl TryFromEvents<WithdrawalLimitOverrideEvent> for WithdrawalLimitOverride {
+    fn try_from_events(
+        events: EntityEvents<WithdrawalLimitOverrideEvent>,
+    ) -> Result<Self, EsEntityError> {
+        let mut builder = WithdrawalLimitOverrideBuilder::default();
+        for event in events.iter_all() {
+            match event {
+                WithdrawalLimitOverrideEvent::Initialized {
+                    id,
+                    deposit_account_id,
+                    limits,
+                    reason,
+                    approval_process_id,
+                    ..
+                } => {
+                    builder = builder
+                        .id(*id)
+                        .deposit_account_id(*deposit_account_id)
+                        .limits(*limits)
+                        .reason(reason.clone())
+                        .approval_process_id(*approval_process_id)
+                }
+                WithdrawalLimitOverrideEvent::ApprovalProcessConcluded { .. } => (),
+            }
+        }
+        builder.events(events).build()
+    }
+}
+
+#[derive(Debug, Builder)]
+#[builder(build_fn(validate = "Self::validate"))]
+pub struct NewWithdrawalLimitOverride {
+    #[builder(setter(into))]
+    pub(super) id: WithdrawalLimitOverrideId,
+    #[builder(setter(into))]
+    pub(super) deposit_account_id: DepositAccountId,
+    pub(super) limits: WithdrawalLimits,
+    #[builder(setter(into))]
+    pub(super) reason: String,
+    #[builder(setter(into))]
+    pub(super) approval_process_id: ApprovalProcessId,
+    #[builder(setter(into))]
+    pub audit_info: AuditInfo,
+}
+
+impl NewWithdrawalLimitOverride {
+    pub fn builder() -> NewWithdrawalLimitOverrideBuilder {
+        NewWithdrawalLimitOverrideBuilder::default()
+    }
+}
+
+impl NewWithdrawalLimitOverrideBuilder {
+    fn validate(&self) -> Result<(), String> {
+        match self.limits {
+            Some(limits) if limits.daily > limits.monthly => {
+                Err("Daily withdrawal limit cannot exceed the monthly limit".to_string())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+impl IntoEvents<WithdrawalLimitOverrideEvent> for NewWithdrawalLimitOverride {
+    fn into_events(self) -> EntityEvents<WithdrawalLimitOverrideEvent> {
+        EntityEvents::init(
+            self.id,
+            [WithdrawalLimitOverrideEvent::Initialized {
+                id: self.id,
+                deposit_account_id: self.deposit_account_id,
+                limits: self.limits,
+                reason: self.reason,
+                approval_process_id: self.approval_process_id,
+                audit_info: self.audit_info,
+            }],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use audit::AuditEntryId;
+
+    use super::*;
+
+    fn dummy_audit_info() -> AuditInfo {
+        AuditInfo {
+            audit_entry_id: AuditEntryId::from(1),
+            sub: "sub".to_string(),
+        }
+    }
+
+    fn limits(daily: u64, monthly: u64) -> WithdrawalLimits {
+        WithdrawalLimits {
+            daily: UsdCents::from(daily),
+            monthly: UsdCents::from(monthly),
+        }
+    }
+
+    fn new_override(limits: WithdrawalLimits) -> Result<NewWithdrawalLimitOverride, String> {
+        let id = WithdrawalLimitOverrideId::new();
+        NewWithdrawalLimitOverride::builder()
+            .id(id)
+            .deposit_account_id(DepositAccountId::new())
+            .limits(limits)
+            .reason("treasury sweep")
+            .approval_process_id(id)
+            .audit_info(dummy_audit_info())
+            .build()
+            .map_err(|e| e.to_string())
+    }
+
+    #[test]
+    fn daily_limit_cannot_exceed_monthly_limit() {
+        assert!(new_override(limits(200, 100)).is_err());
+        assert!(new_override(limits(100, 200)).is_ok());
+    }
+
+    #[test]
+    fn status_follows_approval_process() {
+        let new_override = new_override(limits(100, 200)).unwrap();
+        let mut limit_override =
+            WithdrawalLimitOverride::try_from_events(new_override.into_events()).unwrap();
+        assert_eq!(
+            limit_override.status(),
+            WithdrawalLimitOverrideStatus::PendingApproval
+        );
+
+        assert!(limit_override
+            .approval_process_concluded(false, dummy_audit_info())
+            .did_execute());
+        assert_eq!(
+            limit_override.status(),
+            WithdrawalLimitOverrideStatus::Denied
+        );
+        assert!(limit_override
+            .approval_process_concluded(true, dummy_audit_info())
+            .was_ignored());
+    }
+}
@@ -0,0 +1,19 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum WithdrawalLimitOverrideError {
+    #[error("WithdrawalLimitOverrideError - Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("WithdrawalLimitOverrideError - EsEntityError: {0}")]
+    EsEntityError(es_entity::EsEntityError),
+    #[error("WithdrawalLimitOverrideError - CursorDestructureError: {0}")]
+    CursorDestructureError(#[from] es_entity::CursorDestructureError),
+    #[error("WithdrawalLimitOverrideError - AuditError: {0}")]
+    AuditError(#[from] audit::error::AuditError),
+    #[error("WithdrawalLimitOverrideError - DepositAccountError: {0}")]
+    DepositAccountError(#[from] crate::account::error::DepositAccountError),
+    #[error("WithdrawalLimitOverrideError - DepositLedgerError: {0}")]
+    DepositLedgerError(#[from] crate::ledger::error::DepositLedgerError),
+}
+
+es_entity::from_es_entity_error!(WithdrawalLimitOverrideError);
@@ -0,0 +1,7 @@
+mod entity;
+pub mod error;
+mod repo;
+
+pub(crate) use entity::*;
+pub use entity::{WithdrawalLimitOverride, WithdrawalLimitOverrideStatus};
+pub(crate) use repo::*;
@@ -0,0 +1,27 @@
+use sqlx::PgPool;
+
+use es_entity::*;
+
+use crate::primitives::{ApprovalProcessId, DepositAccountId, WithdrawalLimitOverrideId};
+
+use super::{entity::*, error::*};
+
+#[derive(EsRepo, Clone)]
+#[es_repo(
+    entity = "WithdrawalLimitOverride",
+    err = "WithdrawalLimitOverrideError",
+    columns(
+        deposit_account_id(ty = "DepositAccountId", list_for, update(persist = false)),
+        approval_process_id(ty = "ApprovalProcessId", update(persist = false)),
+    ),
+    tbl_prefix = "core"
+)]
+pub struct WithdrawalLimitOverrideRepo {
+    pool: PgPool,
+}
+
+impl WithdrawalLimitOverrideRepo {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
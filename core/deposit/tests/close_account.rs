@@ -0,0 +1,133 @@
+mod helpers;
+
+use rust_decimal_macros::dec;
+
+use authz::dummy::DummySubject;
+use cala_ledger::{CalaLedger, CalaLedgerConfig};
+use cloud_storage::{config::StorageConfig, Storage};
+use deposit::*;
+use helpers::{action, event, object};
+
+#[tokio::test]
+async fn close_account() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+
+    let outbox = outbox::Outbox::<event::DummyEvent>::init(&pool).await?;
+    let authz = authz::dummy::DummyPerms::<action::DummyAction, object::DummyObject>::new();
+
+    let cala_config = CalaLedgerConfig::builder()
+        .pool(pool.clone())
+        .exec_migrations(false)
+        .build()?;
+    let cala = CalaLedger::init(cala_config).await?;
+    let jobs = job::Jobs::new(&pool, job::JobExecutorConfig::default());
+    let governance = governance::Governance::init(&pool, &authz, &outbox, &jobs).await?;
+    let storage = Storage::new(&StorageConfig::default());
+
+    let journal_id = helpers::init_journal(&cala).await?;
+
+    let deposit = CoreDeposit::init(
+        &pool,
+        DepositConfig {
+            beneficiary_cooling_off_hours: 0,
+            ..Default::default()
+        },
+        &authz,
+        &outbox,
+        &governance,
+        &jobs,
+        &storage,
+        &cala,
+        journal_id,
+    )
+    .await?;
+
+    let account_holder_id = DepositAccountHolderId::new();
+    let account = deposit
+        .create_account(
+            &DummySubject,
+            account_holder_id,
+            true,
+            DepositAccountType::Individual,
+        )
+        .await?;
+    let target = deposit
+        .create_account(
+            &DummySubject,
+            DepositAccountHolderId::new(),
+            true,
+            DepositAccountType::Individual,
+        )
+        .await?;
+
+    let beneficiary = deposit
+        .register_beneficiary(
+            &DummySubject,
+            account_holder_id,
+            "Own account".to_string(),
+            helpers::bank_account_beneficiary(),
+        )
+        .await?;
+
+    let deposit_amount = UsdCents::try_from_usd(dec!(1000)).unwrap();
+    deposit
+        .record_deposit(&DummySubject, account.id, deposit_amount, None)
+        .await?;
+
+    let withdrawal = deposit
+        .initiate_withdrawal(
+            &DummySubject,
+            account.id,
+            beneficiary.id,
+            UsdCents::try_from_usd(dec!(100)).unwrap(),
+            None,
+        )
+        .await?;
+    let res = deposit
+        .close_account(
+            &DummySubject,
+            account.id,
+            DepositAccountClosureSettlement::TransferTo(target.id),
+        )
+        .await;
+    assert!(matches!(
+        res,
+        Err(deposit::error::CoreDepositError::DepositAccountHasPendingWithdrawals)
+    ));
+
+    deposit
+        .cancel_withdrawal(&DummySubject, withdrawal.id)
+        .await?;
+    let closed = deposit
+        .close_account(
+            &DummySubject,
+            account.id,
+            DepositAccountClosureSettlement::TransferTo(target.id),
+        )
+        .await?;
+    assert_eq!(closed.status, DepositAccountStatus::Closed);
+
+    let balance = deposit.account_balance(&DummySubject, account.id).await?;
+    assert_eq!(balance.settled, UsdCents::ZERO);
+    let balance = deposit.account_balance(&DummySubject, target.id).await?;
+    assert_eq!(balance.settled, deposit_amount);
+
+    let res = deposit
+        .record_deposit(&DummySubject, account.id, deposit_amount, None)
+        .await;
+    assert!(matches!(
+        res,
+        Err(deposit::error::CoreDepositError::DepositAccountNotActive)
+    ));
+
+    deposit
+        .update_account_status_for_holder(&DummySubject, account_holder_id, AccountStatus::Active)
+        .await?;
+    let account = deposit
+        .find_account_by_id(&DummySubject, account.id)
+        .await?
+        .expect("account exists");
+    assert_eq!(account.status, DepositAccountStatus::Closed);
+
+    Ok(())
+}
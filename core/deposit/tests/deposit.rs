@@ -4,6 +4,7 @@ use rust_decimal_macros::dec;
 
 use authz::dummy::DummySubject;
 use cala_ledger::{CalaLedger, CalaLedgerConfig};
+use cloud_storage::{config::StorageConfig, Storage};
 use deposit::*;
 use helpers::{action, event, object};
 
@@ -13,7 +14,6 @@ async fn deposit() -> anyhow::Result<()> {
 
     let outbox = outbox::Outbox::<event::DummyEvent>::init(&pool).await?;
     let authz = authz::dummy::DummyPerms::<action::DummyAction, object::DummyObject>::new();
-    let governance = governance::Governance::new(&pool, &authz, &outbox);
 
     let cala_config = CalaLedgerConfig::builder()
         .pool(pool.clone())
@@ -21,15 +21,19 @@ async fn deposit() -> anyhow::Result<()> {
         .build()?;
     let cala = CalaLedger::init(cala_config).await?;
     let jobs = job::Jobs::new(&pool, job::JobExecutorConfig::default());
+    let governance = governance::Governance::init(&pool, &authz, &outbox, &jobs).await?;
+    let storage = Storage::new(&StorageConfig::default());
 
     let journal_id = helpers::init_journal(&cala).await?;
 
     let deposit = CoreDeposit::init(
         &pool,
+        DepositConfig::default(),
         &authz,
         &outbox,
         &governance,
         &jobs,
+        &storage,
         &cala,
         journal_id,
     )
@@ -20,6 +20,16 @@ pub async fn init_journal(cala: &CalaLedger) -> anyhow::Result<cala_ledger::Jour
     Ok(journal.id)
 }
 
+#[allow(dead_code)]
+pub fn bank_account_beneficiary() -> deposit::BeneficiaryDetails {
+    deposit::BeneficiaryDetails::BankAccount(deposit::PayoutDestination {
+        account_holder_name: "Jane Doe".to_string(),
+        routing_number: "021000021".to_string(),
+        account_number: "000123456789".to_string(),
+        account_type: deposit::PayoutAccountType::Checking,
+    })
+}
+
 pub mod action {
     use core_accounting::CoreAccountingAction;
     use core_customer::CoreCustomerAction;
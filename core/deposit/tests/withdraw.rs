@@ -4,17 +4,29 @@ use rust_decimal_macros::dec;
 
 use authz::dummy::DummySubject;
 use cala_ledger::{CalaLedger, CalaLedgerConfig};
+use cloud_storage::{config::StorageConfig, Storage};
 use deposit::*;
 
 use helpers::{action, event, object};
 
+fn config_with_limits(daily: UsdCents, monthly: UsdCents) -> DepositConfig {
+    let limits = WithdrawalLimitsByAccountType::uniform(WithdrawalLimits { daily, monthly });
+    DepositConfig {
+        withdrawal_limits: WithdrawalLimitsConfig {
+            basic: limits.clone(),
+            advanced: limits,
+        },
+        beneficiary_cooling_off_hours: 0,
+        ..Default::default()
+    }
+}
+
 #[tokio::test]
 async fn overdraw_and_cancel_withdrawal() -> anyhow::Result<()> {
     let pool = helpers::init_pool().await?;
 
     let outbox = outbox::Outbox::<event::DummyEvent>::init(&pool).await?;
     let authz = authz::dummy::DummyPerms::<action::DummyAction, object::DummyObject>::new();
-    let governance = governance::Governance::new(&pool, &authz, &outbox);
 
     let cala_config = CalaLedgerConfig::builder()
         .pool(pool.clone())
@@ -22,15 +34,22 @@ async fn overdraw_and_cancel_withdrawal() -> anyhow::Result<()> {
         .build()?;
     let cala = CalaLedger::init(cala_config).await?;
     let jobs = job::Jobs::new(&pool, job::JobExecutorConfig::default());
+    let governance = governance::Governance::init(&pool, &authz, &outbox, &jobs).await?;
+    let storage = Storage::new(&StorageConfig::default());
 
     let journal_id = helpers::init_journal(&cala).await?;
 
     let deposit = CoreDeposit::init(
         &pool,
+        config_with_limits(
+            UsdCents::try_from_usd(dec!(10000000)).unwrap(),
+            UsdCents::try_from_usd(dec!(10000000)).unwrap(),
+        ),
         &authz,
         &outbox,
         &governance,
         &jobs,
+        &storage,
         &cala,
         journal_id,
     )
@@ -46,6 +65,15 @@ async fn overdraw_and_cancel_withdrawal() -> anyhow::Result<()> {
         )
         .await?;
 
+    let beneficiary = deposit
+        .register_beneficiary(
+            &DummySubject,
+            account_holder_id,
+            "Own account".to_string(),
+            helpers::bank_account_beneficiary(),
+        )
+        .await?;
+
     let deposit_amount = UsdCents::try_from_usd(dec!(1000000)).unwrap();
 
     deposit
@@ -55,7 +83,13 @@ async fn overdraw_and_cancel_withdrawal() -> anyhow::Result<()> {
     // overdraw
     let withdrawal_amount = UsdCents::try_from_usd(dec!(5000000)).unwrap();
     let withdrawal = deposit
-        .initiate_withdrawal(&DummySubject, account.id, withdrawal_amount, None)
+        .initiate_withdrawal(
+            &DummySubject,
+            account.id,
+            beneficiary.id,
+            withdrawal_amount,
+            None,
+        )
         .await;
     assert!(matches!(
         withdrawal,
@@ -65,8 +99,19 @@ async fn overdraw_and_cancel_withdrawal() -> anyhow::Result<()> {
     let withdrawal_amount = UsdCents::try_from_usd(dec!(500000)).unwrap();
 
     let withdrawal = deposit
-        .initiate_withdrawal(&DummySubject, account.id, withdrawal_amount, None)
+        .initiate_withdrawal(
+            &DummySubject,
+            account.id,
+            beneficiary.id,
+            withdrawal_amount,
+            None,
+        )
         .await?;
+    assert_eq!(
+        withdrawal.beneficiary.as_ref().map(|b| b.id),
+        Some(beneficiary.id)
+    );
+    assert!(withdrawal.payout_destination.is_some());
 
     let balance = deposit.account_balance(&DummySubject, account.id).await?;
     assert_eq!(balance.settled, deposit_amount - withdrawal_amount);
@@ -80,3 +125,382 @@ async fn overdraw_and_cancel_withdrawal() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[tokio::test]
+async fn withdrawal_limit_exceeded() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+
+    let outbox = outbox::Outbox::<event::DummyEvent>::init(&pool).await?;
+    let authz = authz::dummy::DummyPerms::<action::DummyAction, object::DummyObject>::new();
+
+    let cala_config = CalaLedgerConfig::builder()
+        .pool(pool.clone())
+        .exec_migrations(false)
+        .build()?;
+    let cala = CalaLedger::init(cala_config).await?;
+    let jobs = job::Jobs::new(&pool, job::JobExecutorConfig::default());
+    let governance = governance::Governance::init(&pool, &authz, &outbox, &jobs).await?;
+    let storage = Storage::new(&StorageConfig::default());
+
+    let journal_id = helpers::init_journal(&cala).await?;
+
+    let daily_limit = UsdCents::try_from_usd(dec!(1000)).unwrap();
+    let deposit = CoreDeposit::init(
+        &pool,
+        config_with_limits(daily_limit, UsdCents::try_from_usd(dec!(5000)).unwrap()),
+        &authz,
+        &outbox,
+        &governance,
+        &jobs,
+        &storage,
+        &cala,
+        journal_id,
+    )
+    .await?;
+
+    let account_holder_id = DepositAccountHolderId::new();
+    let account = deposit
+        .create_account(
+            &DummySubject,
+            account_holder_id,
+            true,
+            DepositAccountType::Individual,
+        )
+        .await?;
+    let beneficiary = deposit
+        .register_beneficiary(
+            &DummySubject,
+            account_holder_id,
+            "Own account".to_string(),
+            helpers::bank_account_beneficiary(),
+        )
+        .await?;
+    deposit
+        .record_deposit(
+            &DummySubject,
+            account.id,
+            UsdCents::try_from_usd(dec!(10000)).unwrap(),
+            None,
+        )
+        .await?;
+
+    let first_withdrawal = UsdCents::try_from_usd(dec!(600)).unwrap();
+    let first = deposit
+        .initiate_withdrawal(
+            &DummySubject,
+            account.id,
+            beneficiary.id,
+            first_withdrawal,
+            None,
+        )
+        .await?;
+
+    let second_withdrawal = UsdCents::try_from_usd(dec!(500)).unwrap();
+    let withdrawal = deposit
+        .initiate_withdrawal(
+            &DummySubject,
+            account.id,
+            beneficiary.id,
+            second_withdrawal,
+            None,
+        )
+        .await;
+    match withdrawal {
+        Err(deposit::error::CoreDepositError::WithdrawalLimitExceeded { window, remaining }) => {
+            assert_eq!(window, WithdrawalLimitWindow::Daily);
+            assert_eq!(remaining, daily_limit - first_withdrawal);
+        }
+        _ => panic!("expected withdrawal limit to be exceeded"),
+    }
+
+    deposit.cancel_withdrawal(&DummySubject, first.id).await?;
+    deposit
+        .initiate_withdrawal(
+            &DummySubject,
+            account.id,
+            beneficiary.id,
+            second_withdrawal,
+            None,
+        )
+        .await?;
+
+    Ok(())
+}
+
+/// Brings the release of `window` for the withdrawal forward to now, or runs
+/// it again when it already ran, and waits for the job executor to pick it up.
+async fn release_withdrawal_limit_window(
+    pool: &sqlx::PgPool,
+    withdrawal_id: WithdrawalId,
+    window: WithdrawalLimitWindow,
+) -> anyhow::Result<()> {
+    let job_id: uuid::Uuid = sqlx::query_scalar(
+        r#"
+        SELECT id FROM job_events
+        WHERE event->>'type' = 'initialized'
+          AND event->'config'->>'withdrawal_id' = $1
+          AND event->'config'->>'window' = $2
+        "#,
+    )
+    .bind(withdrawal_id.to_string())
+    .bind(window.to_string())
+    .fetch_one(pool)
+    .await?;
+    sqlx::query(
+        r#"
+        INSERT INTO job_executions (id, reschedule_after, created_at)
+        VALUES ($1, NOW(), NOW())
+        ON CONFLICT (id) DO UPDATE SET reschedule_after = NOW()
+        "#,
+    )
+    .bind(job_id)
+    .execute(pool)
+    .await?;
+
+    for _ in 0..100 {
+        let pending: bool =
+            sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM job_executions WHERE id = $1)")
+                .bind(job_id)
+                .fetch_one(pool)
+                .await?;
+        if !pending {
+            return Ok(());
+        }
+        tokio::time::sleep(std::time::Duration::from_millis(100)).await;
+    }
+    anyhow::bail!("withdrawal limit release did not run")
+}
+
+#[tokio::test]
+async fn withdrawal_limit_windows_are_released_separately() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+
+    let outbox = outbox::Outbox::<event::DummyEvent>::init(&pool).await?;
+    let authz = authz::dummy::DummyPerms::<action::DummyAction, object::DummyObject>::new();
+
+    let cala_config = CalaLedgerConfig::builder()
+        .pool(pool.clone())
+        .exec_migrations(false)
+        .build()?;
+    let cala = CalaLedger::init(cala_config).await?;
+    let mut jobs = job::Jobs::new(
+        &pool,
+        job::JobExecutorConfig {
+            poll_interval: std::time::Duration::from_millis(100),
+            ..Default::default()
+        },
+    );
+    let governance = governance::Governance::init(&pool, &authz, &outbox, &jobs).await?;
+    let storage = Storage::new(&StorageConfig::default());
+
+    let journal_id = helpers::init_journal(&cala).await?;
+
+    let daily_limit = UsdCents::try_from_usd(dec!(1000)).unwrap();
+    let monthly_limit = UsdCents::try_from_usd(dec!(1500)).unwrap();
+    let deposit = CoreDeposit::init(
+        &pool,
+        config_with_limits(daily_limit, monthly_limit),
+        &authz,
+        &outbox,
+        &governance,
+        &jobs,
+        &storage,
+        &cala,
+        journal_id,
+    )
+    .await?;
+    jobs.start_poll().await?;
+
+    let account_holder_id = DepositAccountHolderId::new();
+    let account = deposit
+        .create_account(
+            &DummySubject,
+            account_holder_id,
+            true,
+            DepositAccountType::Individual,
+        )
+        .await?;
+    let beneficiary = deposit
+        .register_beneficiary(
+            &DummySubject,
+            account_holder_id,
+            "Own account".to_string(),
+            helpers::bank_account_beneficiary(),
+        )
+        .await?;
+    deposit
+        .record_deposit(
+            &DummySubject,
+            account.id,
+            UsdCents::try_from_usd(dec!(10000)).unwrap(),
+            None,
+        )
+        .await?;
+
+    let first_withdrawal = UsdCents::try_from_usd(dec!(600)).unwrap();
+    let first = deposit
+        .initiate_withdrawal(
+            &DummySubject,
+            account.id,
+            beneficiary.id,
+            first_withdrawal,
+            None,
+        )
+        .await?;
+    release_withdrawal_limit_window(&pool, first.id, WithdrawalLimitWindow::Daily).await?;
+
+    // Out of the daily window, but still part of the monthly one.
+    let withdrawal = deposit
+        .initiate_withdrawal(
+            &DummySubject,
+            account.id,
+            beneficiary.id,
+            daily_limit,
+            None,
+        )
+        .await;
+    match withdrawal {
+        Err(deposit::error::CoreDepositError::WithdrawalLimitExceeded { window, remaining }) => {
+            assert_eq!(window, WithdrawalLimitWindow::Monthly);
+            assert_eq!(remaining, monthly_limit - first_withdrawal);
+        }
+        _ => panic!("expected monthly withdrawal limit to be exceeded"),
+    }
+    let second_withdrawal = monthly_limit - first_withdrawal;
+    deposit
+        .initiate_withdrawal(
+            &DummySubject,
+            account.id,
+            beneficiary.id,
+            second_withdrawal,
+            None,
+        )
+        .await?;
+
+    // Neither running the daily release again nor cancelling, which releases
+    // every window still held, takes the first withdrawal out of the daily
+    // window a second time.
+    release_withdrawal_limit_window(&pool, first.id, WithdrawalLimitWindow::Daily).await?;
+    deposit.cancel_withdrawal(&DummySubject, first.id).await?;
+    let withdrawal = deposit
+        .initiate_withdrawal(
+            &DummySubject,
+            account.id,
+            beneficiary.id,
+            daily_limit - second_withdrawal + UsdCents::ONE,
+            None,
+        )
+        .await;
+    match withdrawal {
+        Err(deposit::error::CoreDepositError::WithdrawalLimitExceeded { window, remaining }) => {
+            assert_eq!(window, WithdrawalLimitWindow::Daily);
+            assert_eq!(remaining, daily_limit - second_withdrawal);
+        }
+        _ => panic!("expected daily withdrawal limit to be exceeded"),
+    }
+
+    Ok(())
+}
+
+#[tokio::test]
+async fn withdrawal_requires_verified_beneficiary_of_holder() -> anyhow::Result<()> {
+    let pool = helpers::init_pool().await?;
+
+    let outbox = outbox::Outbox::<event::DummyEvent>::init(&pool).await?;
+    let authz = authz::dummy::DummyPerms::<action::DummyAction, object::DummyObject>::new();
+
+    let cala_config = CalaLedgerConfig::builder()
+        .pool(pool.clone())
+        .exec_migrations(false)
+        .build()?;
+    let cala = CalaLedger::init(cala_config).await?;
+    let jobs = job::Jobs::new(&pool, job::JobExecutorConfig::default());
+    let governance = governance::Governance::init(&pool, &authz, &outbox, &jobs).await?;
+    let storage = Storage::new(&StorageConfig::default());
+
+    let journal_id = helpers::init_journal(&cala).await?;
+
+    let deposit = CoreDeposit::init(
+        &pool,
+        DepositConfig {
+            beneficiary_cooling_off_hours: 24,
+            ..Default::default()
+        },
+        &authz,
+        &outbox,
+        &governance,
+        &jobs,
+        &storage,
+        &cala,
+        journal_id,
+    )
+    .await?;
+
+    let account_holder_id = DepositAccountHolderId::new();
+    let account = deposit
+        .create_account(
+            &DummySubject,
+            account_holder_id,
+            true,
+            DepositAccountType::Individual,
+        )
+        .await?;
+    deposit
+        .record_deposit(
+            &DummySubject,
+            account.id,
+            UsdCents::try_from_usd(dec!(1000)).unwrap(),
+            None,
+        )
+        .await?;
+
+    let beneficiary = deposit
+        .register_beneficiary(
+            &DummySubject,
+            account_holder_id,
+            "Own account".to_string(),
+            helpers::bank_account_beneficiary(),
+        )
+        .await?;
+    assert_eq!(
+        beneficiary.status(chrono::Utc::now()),
+        BeneficiaryStatus::CoolingOff
+    );
+    let withdrawal = deposit
+        .initiate_withdrawal(
+            &DummySubject,
+            account.id,
+            beneficiary.id,
+            UsdCents::try_from_usd(dec!(100)).unwrap(),
+            None,
+        )
+        .await;
+    assert!(matches!(
+        withdrawal,
+        Err(deposit::error::CoreDepositError::BeneficiaryError(_))
+    ));
+
+    let other_holders_beneficiary = deposit
+        .register_beneficiary(
+            &DummySubject,
+            DepositAccountHolderId::new(),
+            "Someone else".to_string(),
+            helpers::bank_account_beneficiary(),
+        )
+        .await?;
+    let withdrawal = deposit
+        .initiate_withdrawal(
+            &DummySubject,
+            account.id,
+            other_holders_beneficiary.id,
+            UsdCents::try_from_usd(dec!(100)).unwrap(),
+            None,
+        )
+        .await;
+    assert!(matches!(
+        withdrawal,
+        Err(deposit::error::CoreDepositError::BeneficiaryError(_))
+    ));
+
+    Ok(())
+}
@@ -6,7 +6,18 @@ use std::collections::HashSet;
 use audit::AuditInfo;
 use es_entity::*;
 
-use crate::{policy::ApprovalRules, primitives::*};
+use crate::{
+    approval_process::error::ApprovalProcessError,
+    policy::{
+        ApprovalExpiry, ApprovalRules, ApprovalStage, ApprovalTimeoutAction, ApprovalVotes,
+        EligibleVoters,
+    },
+    primitives::*,
+};
+
+const NOT_ENOUGH_ELIGIBLE_VOTERS: &str =
+    "Not enough eligible voters left to reach the approval threshold";
+const EXPIRED: &str = "Approval process expired before a decision was reached";
 
 #[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
@@ -18,23 +29,63 @@ pub enum ApprovalProcessEvent {
         process_type: ApprovalProcessType,
         rules: ApprovalRules,
         target_ref: String,
+        initiated_by: String,
+        initiator_id: Option<CommitteeMemberId>,
+        expiry: Option<ApprovalExpiry>,
+        expires_at: Option<chrono::DateTime<chrono::Utc>>,
         audit_info: AuditInfo,
     },
+    /// `delegate_id` is set when the vote was cast by a delegate on behalf
+    /// of `approver_id`.
     Approved {
         approver_id: CommitteeMemberId,
+        delegate_id: Option<CommitteeMemberId>,
+        stage: usize,
+        rationale: Option<String>,
         audit_info: AuditInfo,
     },
     Denied {
         denier_id: CommitteeMemberId,
+        delegate_id: Option<CommitteeMemberId>,
+        stage: usize,
         reason: String,
         audit_info: AuditInfo,
     },
+    /// `document_refs` point at documents attached to the comment.
+    Commented {
+        author_id: CommitteeMemberId,
+        body: String,
+        document_refs: Vec<String>,
+        audit_info: AuditInfo,
+    },
+    StageCompleted {
+        stage: usize,
+        audit_info: AuditInfo,
+    },
+    Escalated {
+        stage: usize,
+        fallback: ApprovalStage,
+        expires_at: chrono::DateTime<chrono::Utc>,
+        audit_info: AuditInfo,
+    },
+    Expired {
+        action: ApprovalTimeoutAction,
+        audit_info: AuditInfo,
+    },
     Concluded {
         approved: bool,
         audit_info: AuditInfo,
     },
 }
 
+#[derive(Debug, Clone)]
+pub struct ApprovalProcessComment {
+    pub author_id: CommitteeMemberId,
+    pub body: String,
+    pub document_refs: Vec<String>,
+    pub created_at: chrono::DateTime<chrono::Utc>,
+}
+
 #[derive(EsEntity, Builder)]
 #[builder(pattern = "owned", build_fn(error = "EsEntityError"))]
 pub struct ApprovalProcess {
@@ -42,6 +93,12 @@ pub struct ApprovalProcess {
     pub process_type: ApprovalProcessType,
     pub policy_id: PolicyId,
     pub rules: ApprovalRules,
+    #[builder(default)]
+    initiator_id: Option<CommitteeMemberId>,
+    #[builder(default)]
+    expiry: Option<ApprovalExpiry>,
+    #[builder(default)]
+    expires_at: Option<chrono::DateTime<chrono::Utc>>,
     events: EntityEvents<ApprovalProcessEvent>,
 }
 
@@ -57,28 +114,138 @@ impl ApprovalProcess {
             .iter_all()
             .filter_map(|event| match event {
                 ApprovalProcessEvent::Denied { reason, .. } => Some(reason.as_str()),
+                ApprovalProcessEvent::Expired {
+                    action: ApprovalTimeoutAction::Deny,
+                    ..
+                } => Some(EXPIRED),
                 _ => None,
             })
             .next()
     }
 
+    /// When the member voted in the current stage.
     pub fn member_voted_at(
         &self,
         member_id: CommitteeMemberId,
     ) -> Option<chrono::DateTime<chrono::Utc>> {
+        let current_stage = self.current_stage();
         self.events
             .iter_persisted()
             .filter_map(|event| match event.event {
-                ApprovalProcessEvent::Approved { approver_id, .. } if approver_id == member_id => {
-                    Some(event.recorded_at)
-                }
-                ApprovalProcessEvent::Denied { denier_id, .. } if denier_id == member_id => {
-                    Some(event.recorded_at)
+                ApprovalProcessEvent::Approved {
+                    approver_id, stage, ..
+                } if approver_id == member_id && stage == current_stage => Some(event.recorded_at),
+                ApprovalProcessEvent::Denied {
+                    denier_id, stage, ..
+                } if denier_id == member_id && stage == current_stage => Some(event.recorded_at),
+                _ => None,
+            })
+            .next()
+    }
+    /// The delegate that cast the member's vote in the current stage, if any.
+    pub fn member_vote_delegate(&self, member_id: CommitteeMemberId) -> Option<CommitteeMemberId> {
+        let current_stage = self.current_stage();
+        self.events
+            .iter_all()
+            .filter_map(|event| match event {
+                ApprovalProcessEvent::Approved {
+                    approver_id: id,
+                    delegate_id,
+                    stage,
+                    ..
                 }
+                | ApprovalProcessEvent::Denied {
+                    denier_id: id,
+                    delegate_id,
+                    stage,
+                    ..
+                } if *id == member_id && *stage == current_stage => *delegate_id,
+                _ => None,
+            })
+            .next()
+    }
+
+    /// The rationale the member gave when approving the current stage.
+    pub fn member_rationale(&self, member_id: CommitteeMemberId) -> Option<&str> {
+        let current_stage = self.current_stage();
+        self.events
+            .iter_all()
+            .filter_map(|event| match event {
+                ApprovalProcessEvent::Approved {
+                    approver_id,
+                    rationale,
+                    stage,
+                    ..
+                } if *approver_id == member_id && *stage == current_stage => rationale.as_deref(),
                 _ => None,
             })
             .next()
     }
+
+    /// The discussion thread, oldest comment first.
+    pub fn comments(&self) -> Vec<ApprovalProcessComment> {
+        self.events
+            .iter_persisted()
+            .filter_map(|event| match &event.event {
+                ApprovalProcessEvent::Commented {
+                    author_id,
+                    body,
+                    document_refs,
+                    ..
+                } => Some(ApprovalProcessComment {
+                    author_id: *author_id,
+                    body: body.clone(),
+                    document_refs: document_refs.clone(),
+                    created_at: event.recorded_at,
+                }),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// The initiator and every member that voted on the process in any
+    /// stage, directly or as a delegate.
+    pub fn participants(&self) -> HashSet<CommitteeMemberId> {
+        self.events
+            .iter_all()
+            .flat_map(|event| match event {
+                ApprovalProcessEvent::Approved {
+                    approver_id: id,
+                    delegate_id,
+                    ..
+                }
+                | ApprovalProcessEvent::Denied {
+                    denier_id: id,
+                    delegate_id,
+                    ..
+                } => vec![Some(*id), *delegate_id],
+                _ => vec![],
+            })
+            .flatten()
+            .chain(self.initiator_id)
+            .collect()
+    }
+
+    pub(crate) fn comment(
+        &mut self,
+        author_id: CommitteeMemberId,
+        body: String,
+        document_refs: Vec<String>,
+        audit_info: AuditInfo,
+    ) -> Result<(), ApprovalProcessError> {
+        let body = body.trim();
+        if body.is_empty() && document_refs.is_empty() {
+            return Err(ApprovalProcessError::EmptyComment);
+        }
+        self.events.push(ApprovalProcessEvent::Commented {
+            author_id,
+            body: body.to_string(),
+            document_refs,
+            audit_info,
+        });
+        Ok(())
+    }
+
     pub fn target_ref(&self) -> &str {
         if let ApprovalProcessEvent::Initialized { target_ref, .. } =
             self.events.iter_all().next().expect("No events")
@@ -89,33 +256,199 @@ impl ApprovalProcess {
         }
     }
 
+    /// The subject that started the action waiting on this process.
+    pub fn initiated_by(&self) -> &str {
+        if let ApprovalProcessEvent::Initialized { initiated_by, .. } =
+            self.events.iter_all().next().expect("No events")
+        {
+            initiated_by
+        } else {
+            panic!("No events")
+        }
+    }
+
+    /// Set when the initiator is a potential committee member, who may then
+    /// not vote on their own request.
+    pub fn initiator_id(&self) -> Option<CommitteeMemberId> {
+        self.initiator_id
+    }
+
+    /// Index of the stage currently being voted on. Stays on the final
+    /// stage once the process has concluded.
+    pub fn current_stage(&self) -> usize {
+        self.events
+            .iter_all()
+            .filter(|event| {
+                matches!(
+                    event,
+                    ApprovalProcessEvent::StageCompleted { .. }
+                        | ApprovalProcessEvent::Escalated { .. }
+                )
+            })
+            .count()
+    }
+
+    /// When the policy's timeout action is applied if no decision has been
+    /// reached by then.
+    pub fn expires_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        self.expires_at
+    }
+
+    /// The expiry of a process that is still undecided, what the expiry job
+    /// looks processes up by.
+    pub(crate) fn pending_expires_at(&self) -> Option<chrono::DateTime<chrono::Utc>> {
+        if self.status().is_concluded() {
+            None
+        } else {
+            self.expires_at
+        }
+    }
+
+    /// Whether the process is undecided on a stage that the passing of time
+    /// alone can decide, so the expiry job evaluates it again.
+    pub(crate) fn pending_time_dependent(&self) -> bool {
+        !self.status().is_concluded() && self.rules.stage_depends_on_time(self.current_stage())
+    }
+
+    pub fn is_expired(&self, now: chrono::DateTime<chrono::Utc>) -> bool {
+        self.pending_expires_at().is_some_and(|at| at <= now)
+    }
+
+    pub fn was_escalated(&self) -> bool {
+        self.events
+            .iter_all()
+            .any(|event| matches!(event, ApprovalProcessEvent::Escalated { .. }))
+    }
+
+    pub fn has_timed_out(&self) -> bool {
+        self.events
+            .iter_all()
+            .any(|event| matches!(event, ApprovalProcessEvent::Expired { .. }))
+    }
+
+    /// Applies the policy's timeout action to a process that outlived its
+    /// time to live. Escalation happens at most once, a fallback committee
+    /// that does not decide in time gets the process denied.
+    pub(crate) fn time_out(
+        &mut self,
+        now: chrono::DateTime<chrono::Utc>,
+        audit_info: AuditInfo,
+    ) -> Idempotent<ApprovalTimeoutAction> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            ApprovalProcessEvent::Concluded { .. },
+        );
+        let Some(expiry) = self.expiry else {
+            return Idempotent::Ignored;
+        };
+        if !self.is_expired(now) {
+            return Idempotent::Ignored;
+        }
+
+        let action = match expiry.action {
+            ApprovalTimeoutAction::Escalate { .. } if self.was_escalated() => {
+                ApprovalTimeoutAction::Deny
+            }
+            action => action,
+        };
+        match action {
+            ApprovalTimeoutAction::Escalate { fallback } => {
+                let stage = self.current_stage();
+                let expires_at = now + expiry.ttl();
+                self.rules = self.rules.escalate(stage, fallback);
+                self.expires_at = Some(expires_at);
+                self.events.push(ApprovalProcessEvent::Escalated {
+                    stage,
+                    fallback,
+                    expires_at,
+                    audit_info,
+                });
+            }
+            ApprovalTimeoutAction::Deny => {
+                self.expires_at = None;
+                self.events.push(ApprovalProcessEvent::Expired {
+                    action,
+                    audit_info: audit_info.clone(),
+                });
+                self.events.push(ApprovalProcessEvent::Concluded {
+                    approved: false,
+                    audit_info,
+                });
+            }
+        }
+        Idempotent::Executed(action)
+    }
+
+    pub fn stage_count(&self) -> usize {
+        self.rules.stage_count()
+    }
+
+    /// First committee deciding the current stage.
     pub fn committee_id(&self) -> Option<CommitteeId> {
-        self.rules.committee_id()
+        self.committee_ids().into_iter().next()
+    }
+
+    /// Committees whose members vote in the current stage.
+    pub fn committee_ids(&self) -> Vec<CommitteeId> {
+        self.rules.stage_committee_ids(self.current_stage())
+    }
+
+    /// Members named individually by the rules of the current stage.
+    pub fn required_member_ids(&self) -> Vec<CommitteeMemberId> {
+        self.rules.stage_member_ids(self.current_stage())
     }
 
     pub fn can_member_vote(
         &self,
         member_id: CommitteeMemberId,
-        eligible: HashSet<CommitteeMemberId>,
+        eligible: &HashSet<CommitteeMemberId>,
     ) -> bool {
         eligible.contains(&member_id)
+            && self.initiator_id != Some(member_id)
             && !self.approvers().contains(&member_id)
             && !self.deniers().contains(&member_id)
     }
 
+    /// Moves on to the next stage once the current one is approved. The
+    /// final stage is never completed this way, its approval concludes the
+    /// process instead.
+    pub(crate) fn advance_stage(
+        &mut self,
+        eligible: &EligibleVoters,
+        now: chrono::DateTime<chrono::Utc>,
+        audit_info: AuditInfo,
+    ) -> Idempotent<()> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            ApprovalProcessEvent::Concluded { .. },
+        );
+        let stage = self.current_stage();
+        if stage + 1 >= self.rules.stage_count() {
+            return Idempotent::Ignored;
+        }
+        if self.evaluate_stage(eligible, now) != Some(true) {
+            return Idempotent::Ignored;
+        }
+        self.events
+            .push(ApprovalProcessEvent::StageCompleted { stage, audit_info });
+        Idempotent::Executed(())
+    }
+
     pub(crate) fn check_concluded(
         &mut self,
-        eligible: HashSet<CommitteeMemberId>,
+        eligible: &EligibleVoters,
+        now: chrono::DateTime<chrono::Utc>,
         audit_info: AuditInfo,
     ) -> Idempotent<(bool, Option<String>)> {
         idempotency_guard!(
             self.events.iter_all(),
             ApprovalProcessEvent::Concluded { .. },
         );
-        if let Some(approved) =
-            self.rules
-                .is_approved_or_denied(&eligible, &self.approvers(), &self.deniers())
-        {
+        let stage = self.current_stage();
+        if let Some(approved) = self.evaluate_stage(eligible, now) {
+            if approved && stage + 1 < self.rules.stage_count() {
+                return Idempotent::Ignored;
+            }
             let reason = self
                 .events
                 .iter_all()
@@ -123,7 +456,8 @@ impl ApprovalProcess {
                     ApprovalProcessEvent::Denied { reason, .. } => Some(reason.clone()),
                     _ => None,
                 })
-                .next();
+                .next()
+                .or_else(|| (!approved).then(|| NOT_ENOUGH_ELIGIBLE_VOTERS.to_string()));
 
             self.events.push(ApprovalProcessEvent::Concluded {
                 approved,
@@ -134,6 +468,33 @@ impl ApprovalProcess {
         Idempotent::Ignored
     }
 
+    fn evaluate_stage(
+        &self,
+        eligible: &EligibleVoters,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Option<bool> {
+        let current_stage = self.current_stage();
+        let denying = self
+            .events
+            .iter_all()
+            .filter_map(|event| match event {
+                ApprovalProcessEvent::Denied {
+                    denier_id, stage, ..
+                } if *stage == current_stage => Some(*denier_id),
+                _ => None,
+            })
+            .collect();
+        self.rules.evaluate(
+            current_stage,
+            &ApprovalVotes {
+                eligible,
+                approving: &self.approvers(),
+                denying: &denying,
+                at: now,
+            },
+        )
+    }
+
     pub fn status(&self) -> ApprovalProcessStatus {
         for event in self.events.iter_all().rev() {
             match event {
@@ -153,13 +514,16 @@ impl ApprovalProcess {
         &mut self,
         eligible_members: &HashSet<CommitteeMemberId>,
         approver_id: CommitteeMemberId,
+        delegate_id: Option<CommitteeMemberId>,
+        rationale: Option<String>,
         audit_info: AuditInfo,
     ) -> Idempotent<()> {
         use ApprovalProcessEvent::*;
+        let stage = self.current_stage();
         idempotency_guard!(
             self.events.iter_all(),
             Concluded {..},
-            Approved {approver_id: id, ..} | Denied {denier_id: id,..} if id == &approver_id,
+            Approved {approver_id: id, stage: s, ..} | Denied {denier_id: id, stage: s, ..} if id == &approver_id && s == &stage,
         );
 
         if !eligible_members.contains(&approver_id) {
@@ -168,6 +532,9 @@ impl ApprovalProcess {
 
         self.events.push(ApprovalProcessEvent::Approved {
             approver_id,
+            delegate_id,
+            stage,
+            rationale,
             audit_info,
         });
 
@@ -178,14 +545,16 @@ impl ApprovalProcess {
         &mut self,
         eligible_members: &HashSet<CommitteeMemberId>,
         denier_id: CommitteeMemberId,
+        delegate_id: Option<CommitteeMemberId>,
         reason: String,
         audit_info: AuditInfo,
     ) -> Idempotent<()> {
         use ApprovalProcessEvent::*;
+        let stage = self.current_stage();
         idempotency_guard!(
             self.events.iter_all(),
             Concluded {..},
-            Approved {approver_id: id, ..} | Denied {denier_id: id,..} if id == &denier_id,
+            Approved {approver_id: id, stage: s, ..} | Denied {denier_id: id, stage: s, ..} if id == &denier_id && s == &stage,
         );
 
         if !eligible_members.contains(&denier_id) {
@@ -194,6 +563,8 @@ impl ApprovalProcess {
 
         self.events.push(ApprovalProcessEvent::Denied {
             denier_id,
+            delegate_id,
+            stage,
             reason,
             audit_info,
         });
@@ -201,11 +572,15 @@ impl ApprovalProcess {
         Idempotent::Executed(())
     }
 
+    /// Members that approved the current stage.
     pub fn approvers(&self) -> HashSet<CommitteeMemberId> {
+        let current_stage = self.current_stage();
         self.events
             .iter_all()
             .filter_map(|event| match event {
-                ApprovalProcessEvent::Approved { approver_id, .. } => Some(*approver_id),
+                ApprovalProcessEvent::Approved {
+                    approver_id, stage, ..
+                } if *stage == current_stage => Some(*approver_id),
                 _ => None,
             })
             .collect()
@@ -225,6 +600,7 @@ impl ApprovalProcess {
 impl TryFromEvents<ApprovalProcessEvent> for ApprovalProcess {
     fn try_from_events(events: EntityEvents<ApprovalProcessEvent>) -> Result<Self, EsEntityError> {
         let mut builder = ApprovalProcessBuilder::default();
+        let mut current_rules = None;
         for event in events.iter_all() {
             match event {
                 ApprovalProcessEvent::Initialized {
@@ -232,19 +608,40 @@ impl TryFromEvents<ApprovalProcessEvent> for ApprovalProcess {
                     process_type,
                     policy_id,
                     rules,
+                    initiator_id,
+                    expiry,
+                    expires_at,
                     ..
                 } => {
+                    current_rules = Some(rules.clone());
                     builder = builder
                         .id(*id)
                         .process_type(process_type.clone())
                         .policy_id(*policy_id)
-                        .rules(*rules);
+                        .initiator_id(*initiator_id)
+                        .expiry(*expiry)
+                        .expires_at(*expires_at);
                 }
                 ApprovalProcessEvent::Approved { .. } => {}
                 ApprovalProcessEvent::Denied { .. } => {}
+                ApprovalProcessEvent::Commented { .. } => {}
+                ApprovalProcessEvent::StageCompleted { .. } => {}
+                ApprovalProcessEvent::Escalated {
+                    stage,
+                    fallback,
+                    expires_at,
+                    ..
+                } => {
+                    current_rules = current_rules.map(|rules| rules.escalate(*stage, *fallback));
+                    builder = builder.expires_at(Some(*expires_at));
+                }
+                ApprovalProcessEvent::Expired { .. } => builder = builder.expires_at(None),
                 ApprovalProcessEvent::Concluded { .. } => {}
             }
         }
+        if let Some(rules) = current_rules {
+            builder = builder.rules(rules);
+        }
         builder.events(events).build()
     }
 }
@@ -259,6 +656,14 @@ pub struct NewApprovalProcess {
     #[builder(setter(into))]
     pub(super) target_ref: String,
     #[builder(setter(into))]
+    pub(super) initiated_by: String,
+    #[builder(default)]
+    pub(super) initiator_id: Option<CommitteeMemberId>,
+    #[builder(default)]
+    pub(super) expiry: Option<ApprovalExpiry>,
+    #[builder(default)]
+    pub(super) expires_at: Option<chrono::DateTime<chrono::Utc>>,
+    #[builder(setter(into))]
     pub audit_info: AuditInfo,
 }
 
@@ -270,6 +675,10 @@ impl NewApprovalProcess {
     pub fn committee_id(&self) -> Option<CommitteeId> {
         self.rules.committee_id()
     }
+
+    pub fn time_dependent(&self) -> bool {
+        self.rules.stage_depends_on_time(0)
+    }
 }
 
 impl IntoEvents<ApprovalProcessEvent> for NewApprovalProcess {
@@ -282,6 +691,10 @@ impl IntoEvents<ApprovalProcessEvent> for NewApprovalProcess {
                 process_type: self.process_type,
                 rules: self.rules,
                 target_ref: self.target_ref,
+                initiated_by: self.initiated_by,
+                initiator_id: self.initiator_id,
+                expiry: self.expiry,
+                expires_at: self.expires_at,
                 audit_info: self.audit_info,
             }],
         )
@@ -300,7 +713,22 @@ mod tests {
         }
     }
 
+    fn voters(members: &HashSet<CommitteeMemberId>) -> EligibleVoters {
+        let mut voters = EligibleVoters::default();
+        for member in members {
+            voters.add_member(*member);
+        }
+        voters
+    }
+
     fn init_events(rules: ApprovalRules) -> EntityEvents<ApprovalProcessEvent> {
+        init_events_with_initiator(rules, None)
+    }
+
+    fn init_events_with_initiator(
+        rules: ApprovalRules,
+        initiator_id: Option<CommitteeMemberId>,
+    ) -> EntityEvents<ApprovalProcessEvent> {
         EntityEvents::init(
             ApprovalProcessId::new(),
             [ApprovalProcessEvent::Initialized {
@@ -309,6 +737,10 @@ mod tests {
                 process_type: ApprovalProcessType::from_owned("type".to_string()),
                 rules,
                 target_ref: "target_ref".to_string(),
+                initiated_by: "sub".to_string(),
+                initiator_id,
+                expiry: None,
+                expires_at: None,
                 audit_info: dummy_audit_info(),
             }],
         )
@@ -326,7 +758,7 @@ mod tests {
         let audit_info = dummy_audit_info();
         let eligible = [approver].iter().copied().collect();
         assert!(process
-            .approve(&eligible, approver, audit_info.clone())
+            .approve(&eligible, approver, None, None, audit_info.clone())
             .did_execute());
         assert!(process.approvers().contains(&approver));
     }
@@ -342,7 +774,7 @@ mod tests {
         let approver = CommitteeMemberId::new();
         let audit_info = dummy_audit_info();
         assert!(process
-            .approve(&HashSet::new(), approver, audit_info.clone())
+            .approve(&HashSet::new(), approver, None, None, audit_info.clone())
             .was_ignored());
         assert!(process.approvers().is_empty());
     }
@@ -359,10 +791,10 @@ mod tests {
         let audit_info = dummy_audit_info();
         let eligible: HashSet<_> = [approver].iter().copied().collect();
         assert!(process
-            .approve(&eligible, approver, audit_info.clone())
+            .approve(&eligible, approver, None, None, audit_info.clone())
             .did_execute());
         assert!(process
-            .approve(&eligible, approver, audit_info.clone())
+            .approve(&eligible, approver, None, None, audit_info.clone())
             .was_ignored());
     }
 
@@ -371,12 +803,16 @@ mod tests {
         let mut process =
             ApprovalProcess::try_from_events(init_events(ApprovalRules::SystemAutoApprove))
                 .expect("Could not build approval process");
-        let _ = process.check_concluded(HashSet::new(), dummy_audit_info());
+        let _ = process.check_concluded(
+            &EligibleVoters::default(),
+            chrono::Utc::now(),
+            dummy_audit_info(),
+        );
         let approver = CommitteeMemberId::new();
         let audit_info = dummy_audit_info();
         let eligible: HashSet<_> = [approver].iter().copied().collect();
         assert!(process
-            .approve(&eligible, approver, audit_info.clone())
+            .approve(&eligible, approver, None, None, audit_info.clone())
             .was_ignored());
     }
 
@@ -393,7 +829,7 @@ mod tests {
         let reason = String::new();
         let eligible = [denier].iter().copied().collect();
         assert!(process
-            .deny(&eligible, denier, reason, audit_info.clone())
+            .deny(&eligible, denier, None, reason, audit_info.clone())
             .did_execute());
         assert!(process.deniers().contains(&denier));
     }
@@ -410,7 +846,7 @@ mod tests {
         let reason = String::new();
         let audit_info = dummy_audit_info();
         assert!(process
-            .deny(&HashSet::new(), denier, reason, audit_info.clone())
+            .deny(&HashSet::new(), denier, None, reason, audit_info.clone())
             .was_ignored());
         assert!(process.deniers().is_empty());
     }
@@ -427,10 +863,10 @@ mod tests {
         let audit_info = dummy_audit_info();
         let eligible: HashSet<_> = [denier].iter().copied().collect();
         assert!(process
-            .approve(&eligible, denier, audit_info.clone())
+            .approve(&eligible, denier, None, None, audit_info.clone())
             .did_execute());
         assert!(process
-            .deny(&eligible, denier, String::new(), audit_info.clone())
+            .deny(&eligible, denier, None, String::new(), audit_info.clone())
             .was_ignored());
     }
 
@@ -439,12 +875,317 @@ mod tests {
         let mut process =
             ApprovalProcess::try_from_events(init_events(ApprovalRules::SystemAutoApprove))
                 .expect("Could not build approval process");
-        let _ = process.check_concluded(HashSet::new(), dummy_audit_info());
+        let _ = process.check_concluded(
+            &EligibleVoters::default(),
+            chrono::Utc::now(),
+            dummy_audit_info(),
+        );
         let denier = CommitteeMemberId::new();
         let audit_info = dummy_audit_info();
         let eligible: HashSet<_> = [denier].iter().copied().collect();
         assert!(process
-            .deny(&eligible, denier, String::new(), audit_info.clone())
+            .deny(&eligible, denier, None, String::new(), audit_info.clone())
+            .was_ignored());
+    }
+
+    #[test]
+    fn initiator_cannot_vote() {
+        let initiator = CommitteeMemberId::new();
+        let process = ApprovalProcess::try_from_events(init_events_with_initiator(
+            ApprovalRules::CommitteeThreshold {
+                threshold: 1,
+                committee_id: CommitteeId::new(),
+            },
+            Some(initiator),
+        ))
+        .expect("Could not build approval process");
+        let eligible: HashSet<_> = [initiator].iter().copied().collect();
+        assert!(!process.can_member_vote(initiator, &eligible));
+    }
+
+    #[test]
+    fn denied_when_threshold_unreachable() {
+        let mut process =
+            ApprovalProcess::try_from_events(init_events(ApprovalRules::CommitteeThreshold {
+                threshold: 2,
+                committee_id: CommitteeId::new(),
+            }))
+            .expect("Could not build approval process");
+        let eligible: HashSet<_> = [CommitteeMemberId::new()].iter().copied().collect();
+        let Idempotent::Executed((approved, reason)) =
+            process.check_concluded(&voters(&eligible), chrono::Utc::now(), dummy_audit_info())
+        else {
+            panic!("process should have concluded");
+        };
+        assert!(!approved);
+        assert_eq!(reason.as_deref(), Some(NOT_ENOUGH_ELIGIBLE_VOTERS));
+    }
+
+    fn two_stage_chain() -> ApprovalRules {
+        ApprovalRules::ApprovalChain {
+            stages: vec![
+                crate::policy::ApprovalStage {
+                    committee_id: CommitteeId::new(),
+                    threshold: 1,
+                },
+                crate::policy::ApprovalStage {
+                    committee_id: CommitteeId::new(),
+                    threshold: 1,
+                },
+            ],
+        }
+    }
+
+    #[test]
+    fn chain_concludes_after_final_stage() {
+        let rules = two_stage_chain();
+        let stages = rules.stages();
+        let mut process = ApprovalProcess::try_from_events(init_events(rules))
+            .expect("Could not build approval process");
+        assert_eq!(process.committee_id(), Some(stages[0].committee_id));
+
+        let credit_member = CommitteeMemberId::new();
+        let eligible: HashSet<_> = [credit_member].iter().copied().collect();
+        assert!(process
+            .approve(&eligible, credit_member, None, None, dummy_audit_info())
+            .did_execute());
+        assert!(process
+            .check_concluded(&voters(&eligible), chrono::Utc::now(), dummy_audit_info())
+            .was_ignored());
+        assert!(process
+            .advance_stage(&voters(&eligible), chrono::Utc::now(), dummy_audit_info())
+            .did_execute());
+        assert_eq!(process.current_stage(), 1);
+        assert_eq!(process.committee_id(), Some(stages[1].committee_id));
+        assert!(process.approvers().is_empty());
+
+        let risk_member = CommitteeMemberId::new();
+        let eligible: HashSet<_> = [risk_member].iter().copied().collect();
+        assert!(process
+            .advance_stage(&voters(&eligible), chrono::Utc::now(), dummy_audit_info())
+            .was_ignored());
+        assert!(process
+            .approve(&eligible, risk_member, None, None, dummy_audit_info())
+            .did_execute());
+        assert!(process
+            .advance_stage(&voters(&eligible), chrono::Utc::now(), dummy_audit_info())
+            .was_ignored());
+        let Idempotent::Executed((approved, _)) =
+            process.check_concluded(&voters(&eligible), chrono::Utc::now(), dummy_audit_info())
+        else {
+            panic!("process should have concluded");
+        };
+        assert!(approved);
+        assert_eq!(process.status(), ApprovalProcessStatus::Approved);
+    }
+
+    #[test]
+    fn chain_denied_in_first_stage() {
+        let mut process = ApprovalProcess::try_from_events(init_events(two_stage_chain()))
+            .expect("Could not build approval process");
+        let member = CommitteeMemberId::new();
+        let eligible: HashSet<_> = [member].iter().copied().collect();
+        assert!(process
+            .deny(
+                &eligible,
+                member,
+                None,
+                "no".to_string(),
+                dummy_audit_info()
+            )
+            .did_execute());
+        assert!(process
+            .advance_stage(&voters(&eligible), chrono::Utc::now(), dummy_audit_info())
+            .was_ignored());
+        let Idempotent::Executed((approved, reason)) =
+            process.check_concluded(&voters(&eligible), chrono::Utc::now(), dummy_audit_info())
+        else {
+            panic!("process should have concluded");
+        };
+        assert!(!approved);
+        assert_eq!(reason.as_deref(), Some("no"));
+        assert_eq!(process.current_stage(), 0);
+    }
+
+    fn expiring_events(
+        action: ApprovalTimeoutAction,
+        expires_at: chrono::DateTime<chrono::Utc>,
+    ) -> EntityEvents<ApprovalProcessEvent> {
+        EntityEvents::init(
+            ApprovalProcessId::new(),
+            [ApprovalProcessEvent::Initialized {
+                id: ApprovalProcessId::new(),
+                policy_id: PolicyId::new(),
+                process_type: ApprovalProcessType::from_owned("type".to_string()),
+                rules: ApprovalRules::CommitteeThreshold {
+                    threshold: 1,
+                    committee_id: CommitteeId::new(),
+                },
+                target_ref: "target_ref".to_string(),
+                initiated_by: "sub".to_string(),
+                initiator_id: None,
+                expiry: Some(ApprovalExpiry {
+                    ttl_hours: 24,
+                    action,
+                }),
+                expires_at: Some(expires_at),
+                audit_info: dummy_audit_info(),
+            }],
+        )
+    }
+
+    #[test]
+    fn time_out_denies() {
+        let now = chrono::Utc::now();
+        let mut process =
+            ApprovalProcess::try_from_events(expiring_events(ApprovalTimeoutAction::Deny, now))
+                .expect("Could not build approval process");
+        assert!(process
+            .time_out(now - chrono::Duration::hours(1), dummy_audit_info())
+            .was_ignored());
+        assert!(process.time_out(now, dummy_audit_info()).did_execute());
+        assert_eq!(process.status(), ApprovalProcessStatus::Denied);
+        assert_eq!(process.denied_reason(), Some(EXPIRED));
+        assert!(process.time_out(now, dummy_audit_info()).was_ignored());
+    }
+
+    #[test]
+    fn time_out_escalates_once() {
+        let now = chrono::Utc::now();
+        let fallback = ApprovalStage {
+            committee_id: CommitteeId::new(),
+            threshold: 1,
+        };
+        let mut process = ApprovalProcess::try_from_events(expiring_events(
+            ApprovalTimeoutAction::Escalate { fallback },
+            now,
+        ))
+        .expect("Could not build approval process");
+
+        assert!(matches!(
+            process.time_out(now, dummy_audit_info()),
+            Idempotent::Executed(ApprovalTimeoutAction::Escalate { .. })
+        ));
+        assert_eq!(process.current_stage(), 1);
+        assert_eq!(process.committee_id(), Some(fallback.committee_id));
+        assert!(!process.is_expired(now));
+
+        let later = now + chrono::Duration::hours(24);
+        assert!(matches!(
+            process.time_out(later, dummy_audit_info()),
+            Idempotent::Executed(ApprovalTimeoutAction::Deny)
+        ));
+        assert_eq!(process.status(), ApprovalProcessStatus::Denied);
+    }
+
+    #[test]
+    fn delegate_votes_on_behalf_of_member() {
+        let mut process =
+            ApprovalProcess::try_from_events(init_events(ApprovalRules::CommitteeThreshold {
+                threshold: 1,
+                committee_id: CommitteeId::new(),
+            }))
+            .expect("Could not build approval process");
+        let member = CommitteeMemberId::new();
+        let delegate = CommitteeMemberId::new();
+        let eligible: HashSet<_> = [member].iter().copied().collect();
+        assert!(process
+            .approve(&eligible, member, Some(delegate), None, dummy_audit_info())
+            .did_execute());
+        assert!(process.approvers().contains(&member));
+        assert_eq!(process.member_vote_delegate(member), Some(delegate));
+        assert!(process
+            .approve(&eligible, member, None, None, dummy_audit_info())
+            .was_ignored());
+    }
+
+    #[test]
+    fn composite_stage_needs_every_committee() {
+        let (credit, risk) = (CommitteeId::new(), CommitteeId::new());
+        let mut process = ApprovalProcess::try_from_events(init_events(ApprovalRules::Composite {
+            stages: vec![crate::policy::ApprovalExpression::AllOf {
+                rules: vec![
+                    crate::policy::ApprovalExpression::CommitteeThreshold {
+                        committee_id: credit,
+                        threshold: 1,
+                    },
+                    crate::policy::ApprovalExpression::CommitteeThreshold {
+                        committee_id: risk,
+                        threshold: 1,
+                    },
+                ],
+            }],
+        }))
+        .expect("Could not build approval process");
+        assert_eq!(process.committee_ids(), vec![credit, risk]);
+
+        let (credit_member, risk_member) = (CommitteeMemberId::new(), CommitteeMemberId::new());
+        let mut eligible = EligibleVoters::default();
+        eligible.add_committee(credit, [credit_member].into_iter().collect());
+        eligible.add_committee(risk, [risk_member].into_iter().collect());
+
+        assert!(process
+            .approve(
+                eligible.members(),
+                credit_member,
+                None,
+                None,
+                dummy_audit_info()
+            )
+            .did_execute());
+        assert!(process
+            .check_concluded(&eligible, chrono::Utc::now(), dummy_audit_info())
             .was_ignored());
+        assert!(process
+            .approve(
+                eligible.members(),
+                risk_member,
+                None,
+                None,
+                dummy_audit_info()
+            )
+            .did_execute());
+        let Idempotent::Executed((approved, _)) =
+            process.check_concluded(&eligible, chrono::Utc::now(), dummy_audit_info())
+        else {
+            panic!("process should have concluded");
+        };
+        assert!(approved);
+    }
+
+    #[test]
+    fn comment_and_rationale() {
+        let mut process =
+            ApprovalProcess::try_from_events(init_events(ApprovalRules::CommitteeThreshold {
+                threshold: 2,
+                committee_id: CommitteeId::new(),
+            }))
+            .expect("Could not build approval process");
+        let member = CommitteeMemberId::new();
+        assert!(matches!(
+            process.comment(member, "  ".to_string(), vec![], dummy_audit_info()),
+            Err(ApprovalProcessError::EmptyComment)
+        ));
+        assert!(process
+            .comment(
+                member,
+                "Collateral looks thin".to_string(),
+                vec!["document-ref".to_string()],
+                dummy_audit_info()
+            )
+            .is_ok());
+
+        let eligible: HashSet<_> = [member].iter().copied().collect();
+        assert!(process
+            .approve(
+                &eligible,
+                member,
+                None,
+                Some("Checked with risk".to_string()),
+                dummy_audit_info()
+            )
+            .did_execute());
+        assert_eq!(process.member_rationale(member), Some("Checked with risk"));
+        assert!(process.participants().contains(&member));
     }
 }
@@ -8,6 +8,8 @@ pub enum ApprovalProcessError {
     EsEntityError(es_entity::EsEntityError),
     #[error("ApprovalProcessError - CursorDestructureError: {0}")]
     CursorDestructureError(#[from] es_entity::CursorDestructureError),
+    #[error("ApprovalProcessError - EmptyComment")]
+    EmptyComment,
 }
 
 es_entity::from_es_entity_error!(ApprovalProcessError);
@@ -0,0 +1,104 @@
+use async_trait::async_trait;
+
+use audit::AuditSvc;
+use authz::PermissionCheck;
+use job::*;
+use outbox::OutboxEventMarker;
+
+use crate::{event::GovernanceEvent, Governance, GovernanceAction, GovernanceObject};
+
+#[derive(serde::Serialize)]
+pub struct ApprovalProcessExpiryJobConfig<Perms, E> {
+    _phantom: std::marker::PhantomData<(Perms, E)>,
+}
+impl<Perms, E> ApprovalProcessExpiryJobConfig<Perms, E> {
+    pub fn new() -> Self {
+        Self {
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+impl<Perms, E> JobConfig for ApprovalProcessExpiryJobConfig<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<GovernanceObject>,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    type Initializer = ApprovalProcessExpiryJobInitializer<Perms, E>;
+}
+
+pub struct ApprovalProcessExpiryJobInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: serde::de::DeserializeOwned + serde::Serialize + Send + Sync + 'static + Unpin,
+{
+    governance: Governance<Perms, E>,
+}
+
+impl<Perms, E> ApprovalProcessExpiryJobInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<GovernanceObject>,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    pub fn new(governance: &Governance<Perms, E>) -> Self {
+        Self {
+            governance: governance.clone(),
+        }
+    }
+}
+
+const APPROVAL_PROCESS_EXPIRY_JOB: JobType = JobType::new("approval-process-expiry");
+impl<Perms, E> JobInitializer for ApprovalProcessExpiryJobInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<GovernanceObject>,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        APPROVAL_PROCESS_EXPIRY_JOB
+    }
+
+    fn init(&self, _: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(ApprovalProcessExpiryJobRunner {
+            governance: self.governance.clone(),
+        }))
+    }
+}
+
+pub struct ApprovalProcessExpiryJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: serde::de::DeserializeOwned + serde::Serialize + Send + Sync + 'static + Unpin,
+{
+    governance: Governance<Perms, E>,
+}
+
+#[async_trait]
+impl<Perms, E> JobRunner for ApprovalProcessExpiryJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action: From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<GovernanceObject>,
+    E: OutboxEventMarker<GovernanceEvent>,
+{
+    async fn run(
+        &self,
+        _current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let now = chrono::Utc::now();
+        self.governance.time_out_expired_processes(now).await?;
+        self.governance
+            .reevaluate_time_dependent_processes()
+            .await?;
+        Ok(JobCompletion::RescheduleAt(
+            now + chrono::Duration::hours(1),
+        ))
+    }
+}
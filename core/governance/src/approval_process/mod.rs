@@ -1,8 +1,10 @@
 mod entity;
 pub mod error;
+mod expiry_job;
 mod repo;
 
 pub use entity::*;
+pub(crate) use expiry_job::*;
 pub use repo::approval_process_cursor;
 
 pub(crate) use repo::ApprovalProcessRepo;
@@ -18,7 +18,18 @@ use super::{entity::*, error::*};
             create(accessor = "committee_id()"),
             update(accessor = "committee_id()")
         ),
-        policy_id(ty = "PolicyId")
+        policy_id(ty = "PolicyId"),
+        expires_at(
+            ty = "Option<chrono::DateTime<chrono::Utc>>",
+            list_by,
+            update(accessor = "pending_expires_at()")
+        ),
+        time_dependent(
+            ty = "bool",
+            list_for,
+            create(accessor = "time_dependent()"),
+            update(accessor = "pending_time_dependent()")
+        )
     )
 )]
 pub(crate) struct ApprovalProcessRepo {
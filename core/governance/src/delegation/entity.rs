@@ -0,0 +1,180 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use es_entity::*;
+
+use audit::AuditInfo;
+
+use crate::primitives::{CommitteeId, CommitteeMemberId, DelegationId};
+
+#[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[es_event(id = "DelegationId")]
+pub enum DelegationEvent {
+    Initialized {
+        id: DelegationId,
+        committee_id: CommitteeId,
+        delegator_id: CommitteeMemberId,
+        delegate_id: CommitteeMemberId,
+        starts_at: chrono::DateTime<chrono::Utc>,
+        ends_at: chrono::DateTime<chrono::Utc>,
+        audit_info: AuditInfo,
+    },
+    Revoked {
+        audit_info: AuditInfo,
+    },
+}
+
+/// Lets `delegate_id` vote on behalf of `delegator_id` in the committee's
+/// approval processes between `starts_at` and `ends_at`.
+#[derive(EsEntity, Builder)]
+#[builder(pattern = "owned", build_fn(error = "EsEntityError"))]
+pub struct Delegation {
+    pub id: DelegationId,
+    pub committee_id: CommitteeId,
+    pub delegator_id: CommitteeMemberId,
+    pub delegate_id: CommitteeMemberId,
+    pub starts_at: chrono::DateTime<chrono::Utc>,
+    pub ends_at: chrono::DateTime<chrono::Utc>,
+    events: EntityEvents<DelegationEvent>,
+}
+
+impl Delegation {
+    pub fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.events
+            .entity_first_persisted_at()
+            .expect("No events for delegation")
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.events
+            .iter_all()
+            .any(|event| matches!(event, DelegationEvent::Revoked { .. }))
+    }
+
+    pub fn is_active_at(&self, at: chrono::DateTime<chrono::Utc>) -> bool {
+        !self.is_revoked() && self.starts_at <= at && at < self.ends_at
+    }
+
+    pub(crate) fn revoke(&mut self, audit_info: AuditInfo) -> Idempotent<()> {
+        idempotency_guard!(self.events.iter_all(), DelegationEvent::Revoked { .. });
+        self.events.push(DelegationEvent::Revoked { audit_info });
+        Idempotent::Executed(())
+    }
+}
+
+impl TryFromEvents<DelegationEvent> for Delegation {
+    fn try_from_events(events: EntityEvents<DelegationEvent>) -> Result<Self, EsEntityError> {
+        let mut builder = DelegationBuilder::default();
+        for event in events.iter_all() {
+            match event {
+                DelegationEvent::Initialized {
+                    id,
+                    committee_id,
+                    delegator_id,
+                    delegate_id,
+                    starts_at,
+                    ends_at,
+                    ..
+                } => {
+                    builder = builder
+                        .id(*id)
+                        .committee_id(*committee_id)
+                        .delegator_id(*delegator_id)
+                        .delegate_id(*delegate_id)
+                        .starts_at(*starts_at)
+                        .ends_at(*ends_at)
+                }
+                DelegationEvent::Revoked { .. } => {}
+            }
+        }
+        builder.events(events).build()
+    }
+}
+
+#[derive(Debug, Builder)]
+pub struct NewDelegation {
+    #[builder(setter(into))]
+    pub(super) id: DelegationId,
+    pub(super) committee_id: CommitteeId,
+    pub(super) delegator_id: CommitteeMemberId,
+    pub(super) delegate_id: CommitteeMemberId,
+    pub(super) starts_at: chrono::DateTime<chrono::Utc>,
+    pub(super) ends_at: chrono::DateTime<chrono::Utc>,
+    #[builder(setter(into))]
+    pub audit_info: AuditInfo,
+}
+
+impl NewDelegation {
+    pub fn builder() -> NewDelegationBuilder {
+        NewDelegationBuilder::default()
+    }
+}
+
+impl IntoEvents<DelegationEvent> for NewDelegation {
+    fn into_events(self) -> EntityEvents<DelegationEvent> {
+        EntityEvents::init(
+            self.id,
+            [DelegationEvent::Initialized {
+                id: self.id,
+                committee_id: self.committee_id,
+                delegator_id: self.delegator_id,
+                delegate_id: self.delegate_id,
+                starts_at: self.starts_at,
+                ends_at: self.ends_at,
+                audit_info: self.audit_info,
+            }],
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use audit::AuditEntryId;
+
+    use super::*;
+
+    fn dummy_audit_info() -> AuditInfo {
+        AuditInfo {
+            audit_entry_id: AuditEntryId::from(1),
+            sub: "sub".to_string(),
+        }
+    }
+
+    fn delegation(
+        starts_at: chrono::DateTime<chrono::Utc>,
+        ends_at: chrono::DateTime<chrono::Utc>,
+    ) -> Delegation {
+        let events = EntityEvents::init(
+            DelegationId::new(),
+            [DelegationEvent::Initialized {
+                id: DelegationId::new(),
+                committee_id: CommitteeId::new(),
+                delegator_id: CommitteeMemberId::new(),
+                delegate_id: CommitteeMemberId::new(),
+                starts_at,
+                ends_at,
+                audit_info: dummy_audit_info(),
+            }],
+        );
+        Delegation::try_from_events(events).unwrap()
+    }
+
+    #[test]
+    fn active_only_within_period() {
+        let now = chrono::Utc::now();
+        let delegation = delegation(now, now + chrono::Duration::days(7));
+        assert!(!delegation.is_active_at(now - chrono::Duration::seconds(1)));
+        assert!(delegation.is_active_at(now));
+        assert!(!delegation.is_active_at(now + chrono::Duration::days(7)));
+    }
+
+    #[test]
+    fn revoked_delegation_is_inactive() {
+        let now = chrono::Utc::now();
+        let mut delegation = delegation(now, now + chrono::Duration::days(7));
+        assert!(delegation.revoke(dummy_audit_info()).did_execute());
+        assert!(delegation.revoke(dummy_audit_info()).was_ignored());
+        assert!(!delegation.is_active_at(now));
+    }
+}
@@ -0,0 +1,21 @@
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum DelegationError {
+    #[error("DelegationError - Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("DelegationError - EsEntityError: {0}")]
+    EsEntityError(es_entity::EsEntityError),
+    #[error("DelegationError - CursorDestructureError: {0}")]
+    CursorDestructureError(#[from] es_entity::CursorDestructureError),
+    #[error("DelegationError - DelegatorNotCommitteeMember: {0}")]
+    DelegatorNotCommitteeMember(crate::primitives::CommitteeMemberId),
+    #[error("DelegationError - CannotDelegateToSelf")]
+    CannotDelegateToSelf,
+    #[error("DelegationError - InvalidPeriod")]
+    InvalidPeriod,
+    #[error("DelegationError - NoActiveDelegation")]
+    NoActiveDelegation,
+}
+
+es_entity::from_es_entity_error!(DelegationError);
@@ -0,0 +1,9 @@
+mod entity;
+pub mod error;
+mod repo;
+
+pub use entity::Delegation;
+pub use repo::delegation_cursor;
+
+pub(super) use entity::*;
+pub(super) use repo::DelegationRepo;
@@ -0,0 +1,28 @@
+use sqlx::PgPool;
+
+use es_entity::*;
+
+use crate::primitives::*;
+
+use super::{entity::*, error::*};
+
+#[derive(EsRepo, Clone)]
+#[es_repo(
+    entity = "Delegation",
+    err = "DelegationError",
+    columns(
+        committee_id(ty = "CommitteeId", list_for),
+        delegator_id(ty = "CommitteeMemberId"),
+        delegate_id(ty = "CommitteeMemberId", list_for),
+    )
+)]
+pub(crate) struct DelegationRepo {
+    #[allow(dead_code)]
+    pool: PgPool,
+}
+
+impl DelegationRepo {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
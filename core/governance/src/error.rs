@@ -10,10 +10,18 @@ pub enum GovernanceError {
     CommitteeError(#[from] crate::committee::error::CommitteeError),
     #[error("GovernanceError - PolicyError: {0}")]
     PolicyError(#[from] crate::policy::error::PolicyError),
+    #[error("GovernanceError - DelegationError: {0}")]
+    DelegationError(#[from] crate::delegation::error::DelegationError),
     #[error("GovernanceError - ApprovalProcessError: {0}")]
     ApprovalProcessError(#[from] crate::approval_process::error::ApprovalProcessError),
+    #[error("GovernanceError - JobError: {0}")]
+    JobError(#[from] job::error::JobError),
     #[error("GovernanceError - Audit: {0}")]
     AuditError(#[from] audit::error::AuditError),
     #[error("GovernanceError - SubjectIsNotCommitteeMember")]
     SubjectIsNotCommitteeMember,
+    #[error("GovernanceError - InitiatorCannotVote")]
+    InitiatorCannotVote,
+    #[error("GovernanceError - SubjectCannotComment")]
+    SubjectCannotComment,
 }
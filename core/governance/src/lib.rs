@@ -3,6 +3,7 @@
 
 mod approval_process;
 mod committee;
+mod delegation;
 pub mod error;
 mod event;
 mod policy;
@@ -15,10 +16,13 @@ use std::collections::{HashMap, HashSet};
 
 use audit::AuditSvc;
 use authz::PermissionCheck;
+use job::Jobs;
 use outbox::{Outbox, OutboxEventMarker};
 
 pub use approval_process::{error as approval_process_error, *};
 pub use committee::{error as committee_error, *};
+pub use delegation::{error as delegation_error, *};
+use delegation_error::DelegationError;
 use error::*;
 pub use event::*;
 pub use policy::{error as policy_error, *};
@@ -32,6 +36,7 @@ where
     committee_repo: CommitteeRepo,
     policy_repo: PolicyRepo,
     process_repo: ApprovalProcessRepo,
+    delegation_repo: DelegationRepo,
     authz: Perms,
     outbox: Outbox<E>,
 }
@@ -46,6 +51,7 @@ where
             committee_repo: self.committee_repo.clone(),
             policy_repo: self.policy_repo.clone(),
             process_repo: self.process_repo.clone(),
+            delegation_repo: self.delegation_repo.clone(),
             authz: self.authz.clone(),
             outbox: self.outbox.clone(),
         }
@@ -59,18 +65,33 @@ where
     <<Perms as PermissionCheck>::Audit as AuditSvc>::Object: From<GovernanceObject>,
     E: OutboxEventMarker<GovernanceEvent>,
 {
-    pub fn new(pool: &sqlx::PgPool, authz: &Perms, outbox: &Outbox<E>) -> Self {
+    pub async fn init(
+        pool: &sqlx::PgPool,
+        authz: &Perms,
+        outbox: &Outbox<E>,
+        jobs: &Jobs,
+    ) -> Result<Self, GovernanceError> {
         let committee_repo = CommitteeRepo::new(pool);
         let policy_repo = PolicyRepo::new(pool);
         let process_repo = ApprovalProcessRepo::new(pool);
+        let delegation_repo = DelegationRepo::new(pool);
 
-        Self {
+        let governance = Self {
             committee_repo,
             policy_repo,
             process_repo,
+            delegation_repo,
             authz: authz.clone(),
             outbox: outbox.clone(),
-        }
+        };
+
+        jobs.add_initializer_and_spawn_unique(
+            ApprovalProcessExpiryJobInitializer::new(&governance),
+            ApprovalProcessExpiryJobConfig::<Perms, E>::new(),
+        )
+        .await?;
+
+        Ok(governance)
     }
 
     pub async fn init_policy(
@@ -179,6 +200,135 @@ where
         Ok(policy)
     }
 
+    /// Replaces the rules of a policy with stages that are approved one after
+    /// another. A denial in any stage denies the whole process.
+    #[instrument(name = "governance.assign_approval_chain_to_policy", skip(self), err)]
+    pub async fn assign_approval_chain_to_policy(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        policy_id: impl Into<PolicyId> + std::fmt::Debug,
+        stages: Vec<ApprovalStage>,
+    ) -> Result<Policy, GovernanceError> {
+        let policy_id = policy_id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                GovernanceObject::policy(policy_id),
+                GovernanceAction::POLICY_UPDATE_RULES,
+            )
+            .await?;
+
+        for stage in stages.iter() {
+            self.committee_repo.find_by_id(stage.committee_id).await?;
+        }
+        let mut policy = self.policy_repo.find_by_id(policy_id).await?;
+        policy.assign_approval_chain(stages, audit_info)?;
+        self.policy_repo.update(&mut policy).await?;
+
+        Ok(policy)
+    }
+
+    /// Replaces the rules of a policy with a single stage decided by
+    /// `expression`, which may combine several committees and members.
+    #[instrument(
+        name = "governance.assign_approval_expression_to_policy",
+        skip(self),
+        err
+    )]
+    pub async fn assign_approval_expression_to_policy(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        policy_id: impl Into<PolicyId> + std::fmt::Debug,
+        expression: ApprovalExpression,
+    ) -> Result<Policy, GovernanceError> {
+        let policy_id = policy_id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                GovernanceObject::policy(policy_id),
+                GovernanceAction::POLICY_UPDATE_RULES,
+            )
+            .await?;
+
+        for committee_id in expression.committee_ids() {
+            self.committee_repo.find_by_id(committee_id).await?;
+        }
+        let mut policy = self.policy_repo.find_by_id(policy_id).await?;
+        policy.assign_approval_expression(expression, audit_info);
+        self.policy_repo.update(&mut policy).await?;
+
+        Ok(policy)
+    }
+
+    /// Replaces the amount tiers of a policy. Processes started after the
+    /// update use the rules of the highest tier the amount reaches.
+    #[instrument(name = "governance.update_policy_tiers", skip(self), err)]
+    pub async fn update_policy_tiers(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        policy_id: impl Into<PolicyId> + std::fmt::Debug,
+        tiers: Vec<ApprovalTier>,
+    ) -> Result<Policy, GovernanceError> {
+        let policy_id = policy_id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                GovernanceObject::policy(policy_id),
+                GovernanceAction::POLICY_UPDATE_RULES,
+            )
+            .await?;
+
+        let tiers = ApprovalTiers::try_new(tiers)?;
+        for committee_id in tiers.iter().flat_map(|tier| tier.rules.committee_ids()) {
+            self.committee_repo.find_by_id(committee_id).await?;
+        }
+        let mut policy = self.policy_repo.find_by_id(policy_id).await?;
+        if policy.update_tiers(tiers, audit_info).did_execute() {
+            self.policy_repo.update(&mut policy).await?;
+        }
+
+        Ok(policy)
+    }
+
+    /// Sets how long processes spawned from the policy may stay undecided and
+    /// what happens to them afterwards. `None` lets them stay open forever.
+    #[instrument(name = "governance.update_policy_expiry", skip(self), err)]
+    pub async fn update_policy_expiry(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        policy_id: impl Into<PolicyId> + std::fmt::Debug,
+        expiry: Option<ApprovalExpiry>,
+    ) -> Result<Policy, GovernanceError> {
+        let policy_id = policy_id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                GovernanceObject::policy(policy_id),
+                GovernanceAction::POLICY_UPDATE_RULES,
+            )
+            .await?;
+
+        if let Some(ApprovalExpiry {
+            action: ApprovalTimeoutAction::Escalate { fallback },
+            ..
+        }) = expiry
+        {
+            self.committee_repo
+                .find_by_id(fallback.committee_id)
+                .await?;
+        }
+        let mut policy = self.policy_repo.find_by_id(policy_id).await?;
+        if policy.update_expiry(expiry, audit_info).did_execute() {
+            self.policy_repo.update(&mut policy).await?;
+        }
+
+        Ok(policy)
+    }
+
     #[instrument(name = "governance.find_all_policies", skip(self), err)]
     pub async fn find_all_policies<T: From<Policy>>(
         &self,
@@ -194,7 +344,14 @@ where
         id: impl Into<ApprovalProcessId> + std::fmt::Debug,
         target_ref: String,
         process_type: ApprovalProcessType,
-    ) -> Result<ApprovalProcess, GovernanceError> {
+        amount: Option<UsdCents>,
+        initiated_by: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+    ) -> Result<ApprovalProcess, GovernanceError>
+    where
+        CommitteeMemberId:
+            for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+    {
+        let initiator_id = CommitteeMemberId::try_from(initiated_by).ok();
         let policy = self.policy_repo.find_by_process_type(process_type).await?;
         let audit_info = self
             .authz
@@ -204,11 +361,17 @@ where
                 GovernanceAction::APPROVAL_PROCESS_CREATE,
             )
             .await?;
-        let new_process = policy.spawn_process(id.into(), target_ref, audit_info);
+        let new_process = policy.spawn_process(
+            id.into(),
+            target_ref,
+            amount,
+            initiated_by.to_string(),
+            initiator_id,
+            audit_info,
+        );
         let mut process = self.process_repo.create_in_op(db, new_process).await?;
-        let eligible = self.eligible_voters_for_process(&process).await?;
         if self
-            .maybe_fire_concluded_event(db.tx().begin().await?, eligible, &mut process)
+            .maybe_fire_concluded_event(db.tx().begin().await?, &mut process)
             .await?
         {
             self.process_repo.update_in_op(db, &mut process).await?;
@@ -216,11 +379,139 @@ where
         Ok(process)
     }
 
+    /// Applies the timeout action of every undecided process whose time to
+    /// live has run out.
+    #[instrument(name = "governance.time_out_expired_processes", skip(self), err)]
+    pub(crate) async fn time_out_expired_processes(
+        &self,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), GovernanceError> {
+        // Walking down from `now` only visits expired processes, and a time
+        // out moves a process later or out of the index, never into the part
+        // still to be visited.
+        let mut after = Some(
+            approval_process_cursor::ApprovalProcessesByExpiresAtCursor {
+                id: ApprovalProcessId::from(uuid::Uuid::nil()),
+                expires_at: Some(now + chrono::Duration::microseconds(1)),
+            },
+        );
+        loop {
+            let ret = self
+                .process_repo
+                .list_by_expires_at(
+                    es_entity::PaginatedQueryArgs { first: 100, after },
+                    es_entity::ListDirection::Descending,
+                )
+                .await?;
+
+            for mut process in ret.entities.into_iter() {
+                if process.is_expired(now) {
+                    self.time_out_process(&mut process, now).await?;
+                }
+            }
+
+            if !ret.has_next_page {
+                break;
+            }
+            after = ret.end_cursor;
+        }
+        Ok(())
+    }
+
+    async fn time_out_process(
+        &self,
+        process: &mut ApprovalProcess,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<(), GovernanceError> {
+        let mut db = self.process_repo.begin_op().await?;
+        let audit_info = self
+            .authz
+            .audit()
+            .record_system_entry_in_tx(
+                db.tx(),
+                GovernanceObject::approval_process(process.id),
+                GovernanceAction::APPROVAL_PROCESS_TIME_OUT,
+            )
+            .await?;
+
+        let es_entity::Idempotent::Executed(action) = process.time_out(now, audit_info) else {
+            return Ok(());
+        };
+        match action {
+            ApprovalTimeoutAction::Deny => {
+                self.outbox
+                    .publish_persisted(
+                        db.tx(),
+                        GovernanceEvent::ApprovalProcessConcluded {
+                            id: process.id,
+                            approved: false,
+                            denied_reason: process.denied_reason().map(str::to_string),
+                            process_type: process.process_type.clone(),
+                            target_ref: process.target_ref().to_string(),
+                        },
+                    )
+                    .await?;
+            }
+            ApprovalTimeoutAction::Escalate { .. } => {
+                self.maybe_fire_concluded_event(db.tx().begin().await?, process)
+                    .await?;
+            }
+        }
+        self.process_repo.update_in_op(&mut db, process).await?;
+        db.commit().await?;
+
+        Ok(())
+    }
+
+    /// Evaluates every undecided process again whose current stage can be
+    /// decided by the passing of time, as otherwise only a vote would.
+    #[instrument(
+        name = "governance.reevaluate_time_dependent_processes",
+        skip(self),
+        err
+    )]
+    pub(crate) async fn reevaluate_time_dependent_processes(&self) -> Result<(), GovernanceError> {
+        let mut after = None;
+        loop {
+            let ret = self
+                .process_repo
+                .list_for_time_dependent_by_created_at(
+                    true,
+                    es_entity::PaginatedQueryArgs { first: 100, after },
+                    es_entity::ListDirection::Ascending,
+                )
+                .await?;
+
+            for mut process in ret.entities.into_iter() {
+                let mut db = self.process_repo.begin_op().await?;
+                if self
+                    .maybe_fire_concluded_event(db.tx().begin().await?, &mut process)
+                    .await?
+                {
+                    self.process_repo
+                        .update_in_op(&mut db, &mut process)
+                        .await?;
+                    db.commit().await?;
+                }
+            }
+
+            if !ret.has_next_page {
+                break;
+            }
+            after = ret.end_cursor;
+        }
+        Ok(())
+    }
+
+    /// Approves the process as the subject, or as a delegate on behalf of
+    /// `on_behalf_of` when the subject holds an active delegation from them.
     #[instrument(name = "governance.approve_process", skip(self), err)]
     pub async fn approve_process(
         &self,
         sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
         process_id: impl Into<ApprovalProcessId> + std::fmt::Debug,
+        on_behalf_of: Option<CommitteeMemberId>,
+        rationale: Option<String>,
     ) -> Result<ApprovalProcess, GovernanceError>
     where
         CommitteeMemberId:
@@ -238,14 +529,23 @@ where
         let member_id = CommitteeMemberId::try_from(sub)
             .map_err(|_| GovernanceError::SubjectIsNotCommitteeMember)?;
         let mut process = self.process_repo.find_by_id(process_id).await?;
+        let (voter_id, delegate_id) = self
+            .resolve_voter(&process, member_id, on_behalf_of)
+            .await?;
         let eligible = self.eligible_voters_for_process(&process).await?;
 
         if process
-            .approve(&eligible, member_id, audit_info)
+            .approve(
+                eligible.members(),
+                voter_id,
+                delegate_id,
+                rationale,
+                audit_info,
+            )
             .did_execute()
         {
             let mut db = self.policy_repo.begin_op().await?;
-            self.maybe_fire_concluded_event(db.tx().begin().await?, eligible, &mut process)
+            self.maybe_fire_concluded_event(db.tx().begin().await?, &mut process)
                 .await?;
             self.process_repo
                 .update_in_op(&mut db, &mut process)
@@ -261,6 +561,7 @@ where
         &self,
         sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
         process_id: impl Into<ApprovalProcessId> + std::fmt::Debug,
+        on_behalf_of: Option<CommitteeMemberId>,
         reason: String,
     ) -> Result<ApprovalProcess, GovernanceError>
     where
@@ -279,20 +580,22 @@ where
         let member_id = CommitteeMemberId::try_from(sub)
             .map_err(|_| GovernanceError::SubjectIsNotCommitteeMember)?;
         let mut process = self.process_repo.find_by_id(process_id).await?;
-        let eligible = if let Some(committee_id) = process.committee_id() {
-            self.committee_repo
-                .find_by_id(committee_id)
-                .await?
-                .members()
-        } else {
-            HashSet::new()
-        };
+        let (voter_id, delegate_id) = self
+            .resolve_voter(&process, member_id, on_behalf_of)
+            .await?;
+        let eligible = self.eligible_voters_for_process(&process).await?;
         if process
-            .deny(&eligible, member_id, reason, audit_info)
+            .deny(
+                eligible.members(),
+                voter_id,
+                delegate_id,
+                reason,
+                audit_info,
+            )
             .did_execute()
         {
             let mut db = self.policy_repo.begin_op().await?;
-            self.maybe_fire_concluded_event(db.tx().begin().await?, eligible, &mut process)
+            self.maybe_fire_concluded_event(db.tx().begin().await?, &mut process)
                 .await?;
             self.process_repo
                 .update_in_op(&mut db, &mut process)
@@ -303,6 +606,233 @@ where
         Ok(process)
     }
 
+    /// Adds a comment to the discussion thread of the process. Only the
+    /// initiator, members eligible to vote and members that already voted
+    /// may take part in the discussion.
+    #[instrument(name = "governance.comment_on_process", skip(self), err)]
+    pub async fn comment_on_process(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        process_id: impl Into<ApprovalProcessId> + std::fmt::Debug,
+        body: String,
+        document_refs: Vec<String>,
+    ) -> Result<ApprovalProcess, GovernanceError>
+    where
+        CommitteeMemberId:
+            for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
+    {
+        let process_id = process_id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                GovernanceObject::approval_process(process_id),
+                GovernanceAction::APPROVAL_PROCESS_COMMENT,
+            )
+            .await?;
+        let member_id = CommitteeMemberId::try_from(sub)
+            .map_err(|_| GovernanceError::SubjectIsNotCommitteeMember)?;
+        let mut process = self.process_repo.find_by_id(process_id).await?;
+        if !process.participants().contains(&member_id)
+            && !self
+                .eligible_voters_for_process(&process)
+                .await?
+                .members()
+                .contains(&member_id)
+        {
+            return Err(GovernanceError::SubjectCannotComment);
+        }
+        process.comment(member_id, body, document_refs, audit_info)?;
+        self.process_repo.update(&mut process).await?;
+
+        Ok(process)
+    }
+
+    /// Returns whose vote is being cast and, when voting on behalf of someone
+    /// else, the delegate casting it. Neither may be the initiator.
+    async fn resolve_voter(
+        &self,
+        process: &ApprovalProcess,
+        member_id: CommitteeMemberId,
+        on_behalf_of: Option<CommitteeMemberId>,
+    ) -> Result<(CommitteeMemberId, Option<CommitteeMemberId>), GovernanceError> {
+        if process.initiator_id() == Some(member_id) {
+            return Err(GovernanceError::InitiatorCannotVote);
+        }
+        let Some(delegator_id) = on_behalf_of.filter(|id| *id != member_id) else {
+            return Ok((member_id, None));
+        };
+        if process.initiator_id() == Some(delegator_id) {
+            return Err(GovernanceError::InitiatorCannotVote);
+        }
+        let delegators = self
+            .active_delegators(&process.committee_ids(), member_id, chrono::Utc::now())
+            .await?;
+        if !delegators.contains(&delegator_id) {
+            return Err(DelegationError::NoActiveDelegation.into());
+        }
+        Ok((delegator_id, Some(member_id)))
+    }
+
+    /// Members of the committees that currently let `delegate_id` vote for
+    /// them.
+    async fn active_delegators(
+        &self,
+        committee_ids: &[CommitteeId],
+        delegate_id: CommitteeMemberId,
+        now: chrono::DateTime<chrono::Utc>,
+    ) -> Result<HashSet<CommitteeMemberId>, GovernanceError> {
+        let mut res = HashSet::new();
+        let mut after = None;
+        loop {
+            let ret = self
+                .delegation_repo
+                .list_for_delegate_id_by_created_at(
+                    delegate_id,
+                    es_entity::PaginatedQueryArgs::<
+                        delegation_cursor::DelegationsByCreatedAtCursor,
+                    > {
+                        first: 100,
+                        after,
+                    },
+                    es_entity::ListDirection::Ascending,
+                )
+                .await?;
+            res.extend(
+                ret.entities
+                    .iter()
+                    .filter(|d| committee_ids.contains(&d.committee_id) && d.is_active_at(now))
+                    .map(|d| d.delegator_id),
+            );
+            if !ret.has_next_page {
+                break;
+            }
+            after = ret.end_cursor;
+        }
+        Ok(res)
+    }
+
+    #[instrument(name = "governance.create_delegation", skip(self), err)]
+    pub async fn create_delegation(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        committee_id: impl Into<CommitteeId> + std::fmt::Debug,
+        delegator_id: impl Into<CommitteeMemberId> + std::fmt::Debug,
+        delegate_id: impl Into<CommitteeMemberId> + std::fmt::Debug,
+        starts_at: chrono::DateTime<chrono::Utc>,
+        ends_at: chrono::DateTime<chrono::Utc>,
+    ) -> Result<Delegation, GovernanceError> {
+        let committee_id = committee_id.into();
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                GovernanceObject::committee(committee_id),
+                GovernanceAction::COMMITTEE_CREATE_DELEGATION,
+            )
+            .await?;
+
+        let delegator_id = delegator_id.into();
+        let delegate_id = delegate_id.into();
+        if delegator_id == delegate_id {
+            return Err(DelegationError::CannotDelegateToSelf.into());
+        }
+        if ends_at <= starts_at {
+            return Err(DelegationError::InvalidPeriod.into());
+        }
+        let committee = self.committee_repo.find_by_id(committee_id).await?;
+        if !committee.members().contains(&delegator_id) {
+            return Err(DelegationError::DelegatorNotCommitteeMember(delegator_id).into());
+        }
+
+        let new_delegation = NewDelegation::builder()
+            .id(DelegationId::new())
+            .committee_id(committee_id)
+            .delegator_id(delegator_id)
+            .delegate_id(delegate_id)
+            .starts_at(starts_at)
+            .ends_at(ends_at)
+            .audit_info(audit_info)
+            .build()
+            .expect("Could not build new delegation");
+
+        let delegation = self.delegation_repo.create(new_delegation).await?;
+        Ok(delegation)
+    }
+
+    #[instrument(name = "governance.revoke_delegation", skip(self), err)]
+    pub async fn revoke_delegation(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        delegation_id: impl Into<DelegationId> + std::fmt::Debug,
+    ) -> Result<Delegation, GovernanceError> {
+        let mut delegation = self
+            .delegation_repo
+            .find_by_id(delegation_id.into())
+            .await?;
+        let audit_info = self
+            .authz
+            .enforce_permission(
+                sub,
+                GovernanceObject::committee(delegation.committee_id),
+                GovernanceAction::COMMITTEE_REVOKE_DELEGATION,
+            )
+            .await?;
+
+        if delegation.revoke(audit_info).did_execute() {
+            self.delegation_repo.update(&mut delegation).await?;
+        }
+        Ok(delegation)
+    }
+
+    #[instrument(name = "governance.list_delegations_for_committee", skip(self), err)]
+    pub async fn list_delegations_for_committee(
+        &self,
+        sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
+        committee_id: impl Into<CommitteeId> + std::fmt::Debug,
+    ) -> Result<Vec<Delegation>, GovernanceError> {
+        let committee_id = committee_id.into();
+        self.authz
+            .enforce_permission(
+                sub,
+                GovernanceObject::committee(committee_id),
+                GovernanceAction::COMMITTEE_LIST_DELEGATIONS,
+            )
+            .await?;
+
+        let mut delegations = Vec::new();
+        let mut after = None;
+        loop {
+            let ret = self
+                .delegation_repo
+                .list_for_committee_id_by_created_at(
+                    committee_id,
+                    es_entity::PaginatedQueryArgs::<
+                        delegation_cursor::DelegationsByCreatedAtCursor,
+                    > {
+                        first: 100,
+                        after,
+                    },
+                    es_entity::ListDirection::Descending,
+                )
+                .await?;
+            delegations.extend(ret.entities);
+            if !ret.has_next_page {
+                break;
+            }
+            after = ret.end_cursor;
+        }
+        Ok(delegations)
+    }
+
+    #[instrument(name = "governance.find_all_delegations", skip(self), err)]
+    pub async fn find_all_delegations<T: From<Delegation>>(
+        &self,
+        ids: &[DelegationId],
+    ) -> Result<HashMap<DelegationId, T>, GovernanceError> {
+        Ok(self.delegation_repo.find_all(ids).await?)
+    }
+
     #[instrument(name = "governance.create_committee", skip(self), err)]
     pub async fn create_committee(
         &self,
@@ -334,10 +864,12 @@ where
         Ok(committee)
     }
 
+    /// Completes every stage that has gathered enough approvals and
+    /// concludes the process once its final stage is decided. Returns
+    /// whether the process changed.
     async fn maybe_fire_concluded_event(
         &self,
         mut db: sqlx::Transaction<'_, sqlx::Postgres>,
-        eligible: HashSet<CommitteeMemberId>,
         process: &mut ApprovalProcess,
     ) -> Result<bool, GovernanceError> {
         let audit_info = self
@@ -350,8 +882,19 @@ where
             )
             .await?;
 
+        let now = chrono::Utc::now();
+        let mut eligible = self.eligible_voters_for_process(process).await?;
+        let mut stage_completed = false;
+        while process
+            .advance_stage(&eligible, now, audit_info.clone())
+            .did_execute()
+        {
+            stage_completed = true;
+            eligible = self.eligible_voters_for_process(process).await?;
+        }
+
         if let es_entity::Idempotent::Executed((approved, denied_reason)) =
-            process.check_concluded(eligible, audit_info)
+            process.check_concluded(&eligible, now, audit_info)
         {
             self.outbox
                 .publish_persisted(
@@ -370,7 +913,11 @@ where
             return Ok(true);
         }
 
-        Ok(false)
+        if stage_completed {
+            db.commit().await?;
+        }
+
+        Ok(stage_completed)
     }
 
     #[instrument(name = "governance.add_member_to_committee", skip(self), err)]
@@ -543,33 +1090,51 @@ where
         &self,
         sub: &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject,
         process: &ApprovalProcess,
-        committee: Option<&Committee>,
     ) -> Result<bool, GovernanceError>
     where
         CommitteeMemberId:
             for<'a> TryFrom<&'a <<Perms as PermissionCheck>::Audit as AuditSvc>::Subject>,
     {
-        if let Some(committee) = committee {
-            let member_id = CommitteeMemberId::try_from(sub)
-                .map_err(|_| GovernanceError::SubjectIsNotCommitteeMember)?;
-            Ok(process.can_member_vote(member_id, committee.members()))
-        } else {
-            Ok(false)
+        let member_id = CommitteeMemberId::try_from(sub)
+            .map_err(|_| GovernanceError::SubjectIsNotCommitteeMember)?;
+        if process.initiator_id() == Some(member_id) {
+            return Ok(false);
+        }
+        let eligible = self.eligible_voters_for_process(process).await?;
+        if process.can_member_vote(member_id, eligible.members()) {
+            return Ok(true);
         }
+        let delegators = self
+            .active_delegators(&process.committee_ids(), member_id, chrono::Utc::now())
+            .await?;
+        Ok(delegators
+            .into_iter()
+            .any(|delegator_id| process.can_member_vote(delegator_id, eligible.members())))
     }
 
+    /// Members of the current stage's committees, and the members its rules
+    /// name, that may vote on the process. The initiator is left out, so a
+    /// committee too small to reach the threshold without them denies the
+    /// process.
     async fn eligible_voters_for_process(
         &self,
         process: &ApprovalProcess,
-    ) -> Result<HashSet<CommitteeMemberId>, GovernanceError> {
-        let res = if let Some(committee_id) = process.committee_id() {
-            self.committee_repo
-                .find_by_id(committee_id)
-                .await?
-                .members()
-        } else {
-            HashSet::new()
-        };
+    ) -> Result<EligibleVoters, GovernanceError> {
+        let mut res = EligibleVoters::default();
+        let committee_ids = process.committee_ids();
+        let committees: HashMap<CommitteeId, Committee> =
+            self.committee_repo.find_all(&committee_ids).await?;
+        for committee_id in committee_ids {
+            if let Some(committee) = committees.get(&committee_id) {
+                res.add_committee(committee_id, committee.members());
+            }
+        }
+        for member_id in process.required_member_ids() {
+            res.add_member(member_id);
+        }
+        if let Some(initiator_id) = process.initiator_id() {
+            res.remove_member(initiator_id);
+        }
         Ok(res)
     }
 }
@@ -4,7 +4,12 @@ use serde::{Deserialize, Serialize};
 use audit::AuditInfo;
 use es_entity::*;
 
-use super::rules::ApprovalRules;
+use super::{
+    error::PolicyError,
+    expiry::ApprovalExpiry,
+    rules::{ApprovalExpression, ApprovalRules, ApprovalStage},
+    tier::ApprovalTiers,
+};
 use crate::{approval_process::NewApprovalProcess, primitives::*};
 
 #[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
@@ -21,6 +26,14 @@ pub enum PolicyEvent {
         rules: ApprovalRules,
         audit_info: AuditInfo,
     },
+    ApprovalTiersUpdated {
+        tiers: ApprovalTiers,
+        audit_info: AuditInfo,
+    },
+    ExpiryUpdated {
+        expiry: Option<ApprovalExpiry>,
+        audit_info: AuditInfo,
+    },
 }
 
 #[derive(EsEntity, Builder)]
@@ -29,6 +42,10 @@ pub struct Policy {
     pub id: PolicyId,
     pub process_type: ApprovalProcessType,
     pub rules: ApprovalRules,
+    #[builder(default)]
+    pub tiers: ApprovalTiers,
+    #[builder(default)]
+    pub expiry: Option<ApprovalExpiry>,
     events: EntityEvents<PolicyEvent>,
 }
 
@@ -47,14 +64,21 @@ impl Policy {
         &self,
         id: ApprovalProcessId,
         target_ref: String,
+        amount: Option<UsdCents>,
+        initiated_by: String,
+        initiator_id: Option<CommitteeMemberId>,
         audit_info: AuditInfo,
     ) -> NewApprovalProcess {
         NewApprovalProcess::builder()
             .id(id)
             .target_ref(target_ref)
+            .initiated_by(initiated_by)
+            .initiator_id(initiator_id)
             .policy_id(self.id)
             .process_type(self.process_type.clone())
-            .rules(self.rules)
+            .rules(self.tiers.rules_for(&self.rules, amount))
+            .expiry(self.expiry)
+            .expires_at(self.expiry.map(|expiry| chrono::Utc::now() + expiry.ttl()))
             .audit_info(audit_info)
             .build()
             .expect("failed to build new approval process")
@@ -71,10 +95,65 @@ impl Policy {
             committee_id,
         };
         self.events.push(PolicyEvent::ApprovalRulesUpdated {
-            rules: self.rules,
+            rules: self.rules.clone(),
             audit_info,
         });
     }
+
+    pub fn assign_approval_chain(
+        &mut self,
+        stages: Vec<ApprovalStage>,
+        audit_info: AuditInfo,
+    ) -> Result<(), PolicyError> {
+        if stages.is_empty() {
+            return Err(PolicyError::EmptyApprovalChain);
+        }
+        self.rules = ApprovalRules::ApprovalChain { stages };
+        self.events.push(PolicyEvent::ApprovalRulesUpdated {
+            rules: self.rules.clone(),
+            audit_info,
+        });
+        Ok(())
+    }
+
+    /// Replaces the rules with a single stage decided by `expression`.
+    pub fn assign_approval_expression(
+        &mut self,
+        expression: ApprovalExpression,
+        audit_info: AuditInfo,
+    ) {
+        self.rules = ApprovalRules::Composite {
+            stages: vec![expression],
+        };
+        self.events.push(PolicyEvent::ApprovalRulesUpdated {
+            rules: self.rules.clone(),
+            audit_info,
+        });
+    }
+
+    pub fn update_expiry(
+        &mut self,
+        expiry: Option<ApprovalExpiry>,
+        audit_info: AuditInfo,
+    ) -> Idempotent<()> {
+        if self.expiry == expiry {
+            return Idempotent::Ignored;
+        }
+        self.expiry = expiry;
+        self.events
+            .push(PolicyEvent::ExpiryUpdated { expiry, audit_info });
+        Idempotent::Executed(())
+    }
+
+    pub fn update_tiers(&mut self, tiers: ApprovalTiers, audit_info: AuditInfo) -> Idempotent<()> {
+        if self.tiers == tiers {
+            return Idempotent::Ignored;
+        }
+        self.tiers = tiers.clone();
+        self.events
+            .push(PolicyEvent::ApprovalTiersUpdated { tiers, audit_info });
+        Idempotent::Executed(())
+    }
 }
 
 impl TryFromEvents<PolicyEvent> for Policy {
@@ -91,9 +170,15 @@ impl TryFromEvents<PolicyEvent> for Policy {
                     builder = builder
                         .id(*id)
                         .process_type(process_type.clone())
-                        .rules(*rules)
+                        .rules(rules.clone())
                 }
-                PolicyEvent::ApprovalRulesUpdated { rules, .. } => builder = builder.rules(*rules),
+                PolicyEvent::ApprovalRulesUpdated { rules, .. } => {
+                    builder = builder.rules(rules.clone())
+                }
+                PolicyEvent::ApprovalTiersUpdated { tiers, .. } => {
+                    builder = builder.tiers(tiers.clone())
+                }
+                PolicyEvent::ExpiryUpdated { expiry, .. } => builder = builder.expiry(*expiry),
             }
         }
         builder.events(events).build()
@@ -175,4 +260,72 @@ mod test {
             }
         );
     }
+
+    #[test]
+    fn spawn_process_uses_tier_for_amount() {
+        let mut policy = Policy::try_from_events(init_events()).unwrap();
+        let committee_id = CommitteeId::new();
+        let tier_rules = ApprovalRules::CommitteeThreshold {
+            threshold: 2,
+            committee_id,
+        };
+        let tiers = ApprovalTiers::try_new(vec![crate::ApprovalTier {
+            min_amount: UsdCents::from(100_000),
+            rules: tier_rules,
+        }])
+        .unwrap();
+        assert!(policy
+            .update_tiers(tiers.clone(), dummy_audit_info())
+            .did_execute());
+        assert!(policy.update_tiers(tiers, dummy_audit_info()).was_ignored());
+
+        let small = policy.spawn_process(
+            ApprovalProcessId::new(),
+            "small".to_string(),
+            Some(UsdCents::from(99_999)),
+            "sub".to_string(),
+            None,
+            dummy_audit_info(),
+        );
+        assert_eq!(small.committee_id(), None);
+
+        let large = policy.spawn_process(
+            ApprovalProcessId::new(),
+            "large".to_string(),
+            Some(UsdCents::from(100_000)),
+            "sub".to_string(),
+            None,
+            dummy_audit_info(),
+        );
+        assert_eq!(large.committee_id(), Some(committee_id));
+    }
+
+    #[test]
+    fn assign_approval_chain() {
+        let mut policy = Policy::try_from_events(init_events()).unwrap();
+        assert!(matches!(
+            policy.assign_approval_chain(vec![], dummy_audit_info()),
+            Err(PolicyError::EmptyApprovalChain)
+        ));
+
+        let credit = CommitteeId::new();
+        let risk = CommitteeId::new();
+        policy
+            .assign_approval_chain(
+                vec![
+                    ApprovalStage {
+                        committee_id: credit,
+                        threshold: 2,
+                    },
+                    ApprovalStage {
+                        committee_id: risk,
+                        threshold: 1,
+                    },
+                ],
+                dummy_audit_info(),
+            )
+            .unwrap();
+        assert_eq!(policy.committee_id(), Some(credit));
+        assert_eq!(policy.rules.committee_ids(), vec![credit, risk]);
+    }
 }
@@ -10,6 +10,10 @@ pub enum PolicyError {
     CursorDestructureError(#[from] es_entity::CursorDestructureError),
     #[error("PolicyError - DuplicateApprovalProcessType")]
     DuplicateApprovalProcessType,
+    #[error("PolicyError - ApprovalTiersNotAscending")]
+    ApprovalTiersNotAscending,
+    #[error("PolicyError - EmptyApprovalChain")]
+    EmptyApprovalChain,
 }
 
 es_entity::from_es_entity_error!(PolicyError);
@@ -0,0 +1,28 @@
+use serde::{Deserialize, Serialize};
+
+use super::rules::ApprovalStage;
+
+/// What happens to a process that is still undecided when its time to live
+/// runs out.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ApprovalTimeoutAction {
+    Deny,
+    /// Hands the undecided stage to a fallback committee, once. If the
+    /// fallback committee does not decide in time the process is denied.
+    Escalate {
+        fallback: ApprovalStage,
+    },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalExpiry {
+    pub ttl_hours: u32,
+    pub action: ApprovalTimeoutAction,
+}
+
+impl ApprovalExpiry {
+    pub fn ttl(&self) -> chrono::Duration {
+        chrono::Duration::hours(i64::from(self.ttl_hours))
+    }
+}
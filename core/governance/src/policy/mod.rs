@@ -1,9 +1,13 @@
 mod entity;
 pub mod error;
+mod expiry;
 mod repo;
 mod rules;
+mod tier;
 
 pub use entity::*;
+pub use expiry::*;
 pub use repo::policy_cursor;
 pub(crate) use repo::PolicyRepo;
 pub use rules::*;
+pub use tier::*;
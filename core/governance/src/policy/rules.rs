@@ -1,29 +1,344 @@
+use chrono::Datelike;
 use serde::{Deserialize, Serialize};
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 
-use crate::primitives::CommitteeId;
+use crate::primitives::{CommitteeId, CommitteeMemberId};
 
+/// A single step of approval, decided by one committee.
 #[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalStage {
+    pub committee_id: CommitteeId,
+    pub threshold: usize,
+}
+
+impl ApprovalStage {
+    pub fn is_approved_or_denied<Id: Eq + std::hash::Hash>(
+        &self,
+        eligible_members: &HashSet<Id>,
+        approving_members: &HashSet<Id>,
+        denying_members: &HashSet<Id>,
+    ) -> Option<bool> {
+        if !denying_members.is_empty() {
+            return Some(false);
+        }
+        if eligible_members.intersection(approving_members).count() >= self.threshold {
+            Some(true)
+        } else if eligible_members.len() < self.threshold {
+            Some(false)
+        } else {
+            None
+        }
+    }
+}
+
+/// Who may vote in a stage, per committee. Members named directly by an
+/// expression are eligible without belonging to any of the committees.
+#[derive(Debug, Clone, Default)]
+pub struct EligibleVoters {
+    committees: HashMap<CommitteeId, HashSet<CommitteeMemberId>>,
+    members: HashSet<CommitteeMemberId>,
+}
+
+impl EligibleVoters {
+    pub fn add_committee(
+        &mut self,
+        committee_id: CommitteeId,
+        members: HashSet<CommitteeMemberId>,
+    ) {
+        self.members.extend(members.iter().copied());
+        self.committees
+            .entry(committee_id)
+            .or_default()
+            .extend(members);
+    }
+
+    pub fn add_member(&mut self, member_id: CommitteeMemberId) {
+        self.members.insert(member_id);
+    }
+
+    pub fn remove_member(&mut self, member_id: CommitteeMemberId) {
+        self.members.remove(&member_id);
+        for members in self.committees.values_mut() {
+            members.remove(&member_id);
+        }
+    }
+
+    /// Everyone eligible, regardless of committee.
+    pub fn members(&self) -> &HashSet<CommitteeMemberId> {
+        &self.members
+    }
+
+    pub fn committee_members(&self, committee_id: CommitteeId) -> HashSet<CommitteeMemberId> {
+        self.committees
+            .get(&committee_id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// The votes an expression is evaluated against.
+#[derive(Debug, Clone, Copy)]
+pub struct ApprovalVotes<'a> {
+    pub eligible: &'a EligibleVoters,
+    pub approving: &'a HashSet<CommitteeMemberId>,
+    pub denying: &'a HashSet<CommitteeMemberId>,
+    pub at: chrono::DateTime<chrono::Utc>,
+}
+
+/// A composable approval rule. Evaluating it yields `Some(true)` once it is
+/// satisfied, `Some(false)` once it can no longer be satisfied and `None`
+/// while it is still undecided.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ApprovalExpression {
+    AllOf {
+        rules: Vec<ApprovalExpression>,
+    },
+    AnyOf {
+        rules: Vec<ApprovalExpression>,
+    },
+    CommitteeThreshold {
+        committee_id: CommitteeId,
+        threshold: usize,
+    },
+    MemberRequired {
+        member_id: CommitteeMemberId,
+    },
+    /// Satisfied Monday to Friday (UTC). It never denies, on weekends it
+    /// stays undecided so another branch of an `any_of` has to decide.
+    Weekday,
+}
+
+impl ApprovalExpression {
+    pub fn evaluate(&self, votes: &ApprovalVotes) -> Option<bool> {
+        match self {
+            ApprovalExpression::AllOf { rules } => {
+                let mut res = Some(true);
+                for rule in rules {
+                    match rule.evaluate(votes) {
+                        Some(false) => return Some(false),
+                        None => res = None,
+                        Some(true) => {}
+                    }
+                }
+                res
+            }
+            ApprovalExpression::AnyOf { rules } => {
+                let mut res = Some(false);
+                for rule in rules {
+                    match rule.evaluate(votes) {
+                        Some(true) => return Some(true),
+                        None => res = None,
+                        Some(false) => {}
+                    }
+                }
+                res
+            }
+            ApprovalExpression::CommitteeThreshold {
+                committee_id,
+                threshold,
+            } => {
+                let members = votes.eligible.committee_members(*committee_id);
+                let denying = members.intersection(votes.denying).copied().collect();
+                ApprovalStage {
+                    committee_id: *committee_id,
+                    threshold: *threshold,
+                }
+                .is_approved_or_denied(&members, votes.approving, &denying)
+            }
+            ApprovalExpression::MemberRequired { member_id } => {
+                if votes.denying.contains(member_id)
+                    || !votes.eligible.members().contains(member_id)
+                {
+                    Some(false)
+                } else if votes.approving.contains(member_id) {
+                    Some(true)
+                } else {
+                    None
+                }
+            }
+            ApprovalExpression::Weekday => match votes.at.weekday() {
+                chrono::Weekday::Sat | chrono::Weekday::Sun => None,
+                _ => Some(true),
+            },
+        }
+    }
+
+    /// Whether the outcome can change with time alone, without anyone
+    /// voting.
+    pub fn depends_on_time(&self) -> bool {
+        match self {
+            ApprovalExpression::AllOf { rules } | ApprovalExpression::AnyOf { rules } => {
+                rules.iter().any(ApprovalExpression::depends_on_time)
+            }
+            ApprovalExpression::CommitteeThreshold { .. }
+            | ApprovalExpression::MemberRequired { .. } => false,
+            ApprovalExpression::Weekday => true,
+        }
+    }
+
+    pub fn committee_ids(&self) -> Vec<CommitteeId> {
+        let mut res = Vec::new();
+        self.collect_ids(&mut res, &mut Vec::new());
+        res
+    }
+
+    /// Members named by `member_required` rules.
+    pub fn member_ids(&self) -> Vec<CommitteeMemberId> {
+        let mut res = Vec::new();
+        self.collect_ids(&mut Vec::new(), &mut res);
+        res
+    }
+
+    fn collect_ids(&self, committees: &mut Vec<CommitteeId>, members: &mut Vec<CommitteeMemberId>) {
+        match self {
+            ApprovalExpression::AllOf { rules } | ApprovalExpression::AnyOf { rules } => {
+                for rule in rules {
+                    rule.collect_ids(committees, members);
+                }
+            }
+            ApprovalExpression::CommitteeThreshold { committee_id, .. } => {
+                if !committees.contains(committee_id) {
+                    committees.push(*committee_id);
+                }
+            }
+            ApprovalExpression::MemberRequired { member_id } => {
+                if !members.contains(member_id) {
+                    members.push(*member_id);
+                }
+            }
+            ApprovalExpression::Weekday => {}
+        }
+    }
+}
+
+impl From<ApprovalStage> for ApprovalExpression {
+    fn from(stage: ApprovalStage) -> Self {
+        ApprovalExpression::CommitteeThreshold {
+            committee_id: stage.committee_id,
+            threshold: stage.threshold,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 #[serde(tag = "type", rename_all = "snake_case")]
 pub enum ApprovalRules {
     CommitteeThreshold {
         committee_id: CommitteeId,
         threshold: usize,
     },
+    /// Stages are decided one after another, each by its own committee.
+    ApprovalChain {
+        stages: Vec<ApprovalStage>,
+    },
     SystemAutoApprove,
+    /// Like a chain, but every stage is decided by an expression that may
+    /// involve several committees.
+    Composite {
+        stages: Vec<ApprovalExpression>,
+    },
 }
 
 impl ApprovalRules {
+    /// First committee deciding the first stage.
     pub fn committee_id(&self) -> Option<CommitteeId> {
+        self.stage_committee_ids(0).into_iter().next()
+    }
+
+    pub fn committee_ids(&self) -> Vec<CommitteeId> {
+        let mut res = Vec::new();
+        for idx in 0..self.stage_count() {
+            for committee_id in self.stage_committee_ids(idx) {
+                if !res.contains(&committee_id) {
+                    res.push(committee_id);
+                }
+            }
+        }
+        res
+    }
+
+    /// Committees whose members vote in stage `stage`.
+    pub fn stage_committee_ids(&self, stage: usize) -> Vec<CommitteeId> {
+        match self {
+            ApprovalRules::Composite { stages } => stages
+                .get(stage)
+                .map(ApprovalExpression::committee_ids)
+                .unwrap_or_default(),
+            _ => self
+                .stage(stage)
+                .map(|stage| vec![stage.committee_id])
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Members named individually in stage `stage`.
+    pub fn stage_member_ids(&self, stage: usize) -> Vec<CommitteeMemberId> {
+        match self {
+            ApprovalRules::Composite { stages } => stages
+                .get(stage)
+                .map(ApprovalExpression::member_ids)
+                .unwrap_or_default(),
+            _ => vec![],
+        }
+    }
+
+    /// Whether stage `stage` can be decided by the passing of time, so it
+    /// has to be evaluated again even when nobody votes.
+    pub fn stage_depends_on_time(&self, stage: usize) -> bool {
+        match self {
+            ApprovalRules::Composite { stages } => stages
+                .get(stage)
+                .is_some_and(ApprovalExpression::depends_on_time),
+            _ => false,
+        }
+    }
+
+    pub fn stages(&self) -> Vec<ApprovalStage> {
+        match self {
+            ApprovalRules::CommitteeThreshold {
+                committee_id,
+                threshold,
+            } => vec![ApprovalStage {
+                committee_id: *committee_id,
+                threshold: *threshold,
+            }],
+            ApprovalRules::ApprovalChain { stages } => stages.clone(),
+            ApprovalRules::SystemAutoApprove | ApprovalRules::Composite { .. } => vec![],
+        }
+    }
+
+    pub fn stage(&self, idx: usize) -> Option<ApprovalStage> {
+        self.stages().get(idx).copied()
+    }
+
+    pub fn stage_count(&self) -> usize {
         match self {
-            ApprovalRules::CommitteeThreshold { committee_id, .. } => Some(*committee_id),
-            ApprovalRules::SystemAutoApprove => None,
+            ApprovalRules::Composite { stages } => stages.len(),
+            _ => self.stages().len(),
+        }
+    }
+
+    /// Rules in which `fallback` takes over from the stage following
+    /// `stage`. The stages after `stage` are dropped, so the fallback
+    /// committee makes the final decision.
+    pub fn escalate(&self, stage: usize, fallback: ApprovalStage) -> ApprovalRules {
+        if let ApprovalRules::Composite { stages } = self {
+            let mut stages: Vec<_> = stages.iter().take(stage + 1).cloned().collect();
+            stages.push(fallback.into());
+            return ApprovalRules::Composite { stages };
         }
+        let mut stages: Vec<_> = self.stages().into_iter().take(stage + 1).collect();
+        stages.push(fallback);
+        ApprovalRules::ApprovalChain { stages }
     }
 
+    /// Evaluates the votes cast in stage `stage`. A stage past the last one
+    /// has nothing left to approve.
     pub fn is_approved_or_denied<Id: Eq + std::hash::Hash>(
         &self,
+        stage: usize,
         eligible_members: &HashSet<Id>,
         approving_members: &HashSet<Id>,
         denying_members: &HashSet<Id>,
@@ -31,19 +346,29 @@ impl ApprovalRules {
         if !denying_members.is_empty() {
             return Some(false);
         }
-        match self {
-            ApprovalRules::SystemAutoApprove => Some(true),
-            ApprovalRules::CommitteeThreshold { threshold, .. }
-                if eligible_members.intersection(approving_members).count() >= *threshold =>
-            {
-                Some(true)
-            }
-            ApprovalRules::CommitteeThreshold { threshold, .. }
-                if eligible_members.len() < *threshold =>
-            {
-                Some(false)
+        match self.stage(stage) {
+            Some(stage) => {
+                stage.is_approved_or_denied(eligible_members, approving_members, denying_members)
             }
-            _ => None,
+            None => Some(true),
+        }
+    }
+
+    /// Evaluates stage `stage` against `votes`. Unlike the other rules a
+    /// composite stage is not denied by any single denial, only once its
+    /// expression can no longer be satisfied.
+    pub fn evaluate(&self, stage: usize, votes: &ApprovalVotes) -> Option<bool> {
+        match self {
+            ApprovalRules::Composite { stages } => match stages.get(stage) {
+                Some(expression) => expression.evaluate(votes),
+                None => Some(true),
+            },
+            _ => self.is_approved_or_denied(
+                stage,
+                votes.eligible.members(),
+                votes.approving,
+                votes.denying,
+            ),
         }
     }
 }
@@ -67,7 +392,7 @@ mod tests {
         let approving = make_set(&[1, 2, 3]);
         let denying = HashSet::new();
 
-        let result = rules.is_approved_or_denied(&eligible, &approving, &denying);
+        let result = rules.is_approved_or_denied(0, &eligible, &approving, &denying);
 
         assert_eq!(
             result,
@@ -87,7 +412,7 @@ mod tests {
         let approving = make_set(&[2, 3, 4]);
         let denying = make_set(&[1]);
 
-        let result = rules.is_approved_or_denied(&eligible, &approving, &denying);
+        let result = rules.is_approved_or_denied(0, &eligible, &approving, &denying);
 
         assert_eq!(
             result,
@@ -107,7 +432,7 @@ mod tests {
         let approving = make_set(&[1, 2]);
         let denying = HashSet::new();
 
-        let result = rules.is_approved_or_denied(&eligible, &approving, &denying);
+        let result = rules.is_approved_or_denied(0, &eligible, &approving, &denying);
 
         assert_eq!(
             result, None,
@@ -128,7 +453,7 @@ mod tests {
         let rules = ApprovalRules::SystemAutoApprove;
 
         assert_eq!(
-            rules.is_approved_or_denied(&make_set(&[1, 2, 3]), &HashSet::new(), &HashSet::new()),
+            rules.is_approved_or_denied(0, &make_set(&[1, 2, 3]), &HashSet::new(), &HashSet::new()),
             Some(true),
             "Automatic rules should always approve regardless of inputs"
         );
@@ -144,7 +469,7 @@ mod tests {
         // Empty sets
         let empty = HashSet::new();
         assert_eq!(
-            rules.is_approved_or_denied(&empty, &empty, &empty),
+            rules.is_approved_or_denied(0, &empty, &empty, &empty),
             Some(false),
             "Empty eligible set should result in denial"
         );
@@ -152,9 +477,287 @@ mod tests {
         // Threshold larger than eligible set
         let small_eligible = make_set(&[1, 2]);
         assert_eq!(
-            rules.is_approved_or_denied(&small_eligible, &empty, &empty),
+            rules.is_approved_or_denied(0, &small_eligible, &empty, &empty),
             Some(false),
             "Should be denied when threshold exceeds eligible set size"
         );
     }
+
+    #[test]
+    fn test_approval_chain_stages() {
+        let rules = ApprovalRules::ApprovalChain {
+            stages: vec![
+                ApprovalStage {
+                    committee_id: CommitteeId::new(),
+                    threshold: 1,
+                },
+                ApprovalStage {
+                    committee_id: CommitteeId::new(),
+                    threshold: 2,
+                },
+            ],
+        };
+
+        let eligible = make_set(&[1, 2, 3]);
+        let empty = HashSet::new();
+        assert_eq!(
+            rules.is_approved_or_denied(0, &eligible, &make_set(&[1]), &empty),
+            Some(true),
+            "First stage should be approved with 1 approval"
+        );
+        assert_eq!(
+            rules.is_approved_or_denied(1, &eligible, &make_set(&[1]), &empty),
+            None,
+            "Second stage should still be pending with 1 approval"
+        );
+        assert_eq!(
+            rules.is_approved_or_denied(1, &eligible, &empty, &make_set(&[2])),
+            Some(false),
+            "A denial in a later stage should deny"
+        );
+        assert_eq!(rules.stage_count(), 2);
+        assert_eq!(rules.committee_ids().len(), 2);
+    }
+
+    fn members(n: usize) -> Vec<CommitteeMemberId> {
+        (0..n).map(|_| CommitteeMemberId::new()).collect()
+    }
+
+    fn monday() -> chrono::DateTime<chrono::Utc> {
+        "2024-06-03T12:00:00Z".parse().unwrap()
+    }
+
+    fn saturday() -> chrono::DateTime<chrono::Utc> {
+        "2024-06-08T12:00:00Z".parse().unwrap()
+    }
+
+    fn evaluate(
+        expression: &ApprovalExpression,
+        eligible: &EligibleVoters,
+        approving: &[CommitteeMemberId],
+        denying: &[CommitteeMemberId],
+        at: chrono::DateTime<chrono::Utc>,
+    ) -> Option<bool> {
+        expression.evaluate(&ApprovalVotes {
+            eligible,
+            approving: &approving.iter().copied().collect(),
+            denying: &denying.iter().copied().collect(),
+            at,
+        })
+    }
+
+    #[test]
+    fn all_of_needs_every_committee() {
+        let (credit, risk) = (CommitteeId::new(), CommitteeId::new());
+        let credit_members = members(3);
+        let risk_members = members(2);
+        let mut eligible = EligibleVoters::default();
+        eligible.add_committee(credit, credit_members.iter().copied().collect());
+        eligible.add_committee(risk, risk_members.iter().copied().collect());
+        let expression = ApprovalExpression::AllOf {
+            rules: vec![
+                ApprovalExpression::CommitteeThreshold {
+                    committee_id: credit,
+                    threshold: 2,
+                },
+                ApprovalExpression::CommitteeThreshold {
+                    committee_id: risk,
+                    threshold: 1,
+                },
+            ],
+        };
+
+        assert_eq!(
+            evaluate(&expression, &eligible, &credit_members[..2], &[], monday()),
+            None,
+            "Risk has not approved yet"
+        );
+        let approving = [&credit_members[..2], &risk_members[..1]].concat();
+        assert_eq!(
+            evaluate(&expression, &eligible, &approving, &[], monday()),
+            Some(true)
+        );
+        assert_eq!(
+            evaluate(
+                &expression,
+                &eligible,
+                &credit_members[..2],
+                &risk_members[..1],
+                monday()
+            ),
+            Some(false),
+            "A denial in risk fails the risk rule"
+        );
+    }
+
+    #[test]
+    fn any_of_needs_one_branch() {
+        let (executive, credit) = (CommitteeId::new(), CommitteeId::new());
+        let executive_members = members(2);
+        let credit_members = members(4);
+        let mut eligible = EligibleVoters::default();
+        eligible.add_committee(executive, executive_members.iter().copied().collect());
+        eligible.add_committee(credit, credit_members.iter().copied().collect());
+        let expression = ApprovalExpression::AnyOf {
+            rules: vec![
+                ApprovalExpression::CommitteeThreshold {
+                    committee_id: executive,
+                    threshold: 1,
+                },
+                ApprovalExpression::CommitteeThreshold {
+                    committee_id: credit,
+                    threshold: 3,
+                },
+            ],
+        };
+
+        assert_eq!(
+            evaluate(
+                &expression,
+                &eligible,
+                &executive_members[..1],
+                &credit_members[..1],
+                monday()
+            ),
+            Some(true),
+            "A credit denial does not block the executive branch"
+        );
+        assert_eq!(
+            evaluate(&expression, &eligible, &credit_members[..3], &[], monday()),
+            Some(true)
+        );
+        assert_eq!(
+            evaluate(
+                &expression,
+                &eligible,
+                &[],
+                &[executive_members[0], credit_members[0]],
+                monday()
+            ),
+            Some(false),
+            "Denied once every branch is denied"
+        );
+    }
+
+    #[test]
+    fn member_required() {
+        let member = CommitteeMemberId::new();
+        let mut eligible = EligibleVoters::default();
+        eligible.add_member(member);
+        let expression = ApprovalExpression::MemberRequired { member_id: member };
+
+        assert_eq!(evaluate(&expression, &eligible, &[], &[], monday()), None);
+        assert_eq!(
+            evaluate(&expression, &eligible, &[member], &[], monday()),
+            Some(true)
+        );
+        assert_eq!(
+            evaluate(&expression, &eligible, &[], &[member], monday()),
+            Some(false)
+        );
+        eligible.remove_member(member);
+        assert_eq!(
+            evaluate(&expression, &eligible, &[], &[], monday()),
+            Some(false),
+            "A member that may not vote can never approve"
+        );
+    }
+
+    #[test]
+    fn no_auto_approve_on_weekends() {
+        let ops = CommitteeId::new();
+        let ops_members = members(1);
+        let mut eligible = EligibleVoters::default();
+        eligible.add_committee(ops, ops_members.iter().copied().collect());
+        let expression = ApprovalExpression::AnyOf {
+            rules: vec![
+                ApprovalExpression::Weekday,
+                ApprovalExpression::CommitteeThreshold {
+                    committee_id: ops,
+                    threshold: 1,
+                },
+            ],
+        };
+
+        assert_eq!(
+            evaluate(&expression, &eligible, &[], &[], monday()),
+            Some(true)
+        );
+        assert_eq!(evaluate(&expression, &eligible, &[], &[], saturday()), None);
+        assert_eq!(
+            evaluate(&expression, &eligible, &ops_members, &[], saturday()),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn weekday_holds_back_an_approval_over_the_weekend() {
+        let credit = CommitteeId::new();
+        let credit_members = members(2);
+        let mut eligible = EligibleVoters::default();
+        eligible.add_committee(credit, credit_members.iter().copied().collect());
+        let rules = ApprovalRules::Composite {
+            stages: vec![ApprovalExpression::AllOf {
+                rules: vec![
+                    ApprovalExpression::CommitteeThreshold {
+                        committee_id: credit,
+                        threshold: 2,
+                    },
+                    ApprovalExpression::Weekday,
+                ],
+            }],
+        };
+        let approving = credit_members.iter().copied().collect();
+        let denying = HashSet::new();
+        let votes = |at| ApprovalVotes {
+            eligible: &eligible,
+            approving: &approving,
+            denying: &denying,
+            at,
+        };
+
+        assert_eq!(
+            rules.evaluate(0, &votes(saturday())),
+            None,
+            "The threshold is met but it is the weekend"
+        );
+        assert!(
+            rules.stage_depends_on_time(0),
+            "Nobody is left to vote, so the stage has to be evaluated again on Monday"
+        );
+        assert_eq!(rules.evaluate(0, &votes(monday())), Some(true));
+    }
+
+    #[test]
+    fn composite_rules_roundtrip_and_escalate() {
+        let (credit, risk) = (CommitteeId::new(), CommitteeId::new());
+        let member = CommitteeMemberId::new();
+        let rules = ApprovalRules::Composite {
+            stages: vec![ApprovalExpression::AllOf {
+                rules: vec![
+                    ApprovalExpression::CommitteeThreshold {
+                        committee_id: credit,
+                        threshold: 2,
+                    },
+                    ApprovalExpression::MemberRequired { member_id: member },
+                    ApprovalExpression::Weekday,
+                ],
+            }],
+        };
+
+        let json = serde_json::to_string(&rules).unwrap();
+        assert_eq!(serde_json::from_str::<ApprovalRules>(&json).unwrap(), rules);
+        assert_eq!(rules.committee_ids(), vec![credit]);
+        assert_eq!(rules.stage_member_ids(0), vec![member]);
+
+        let escalated = rules.escalate(
+            0,
+            ApprovalStage {
+                committee_id: risk,
+                threshold: 1,
+            },
+        );
+        assert_eq!(escalated.stage_count(), 2);
+        assert_eq!(escalated.stage_committee_ids(1), vec![risk]);
+    }
 }
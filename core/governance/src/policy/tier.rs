@@ -0,0 +1,125 @@
+use serde::{Deserialize, Serialize};
+
+use crate::primitives::UsdCents;
+
+use super::{error::PolicyError, rules::ApprovalRules};
+
+/// Rules that replace the policy's default rules once the amount being
+/// approved reaches `min_amount`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ApprovalTier {
+    pub min_amount: UsdCents,
+    pub rules: ApprovalRules,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ApprovalTiers(Vec<ApprovalTier>);
+
+impl ApprovalTiers {
+    /// Tiers must be given in strictly ascending `min_amount` order so that
+    /// every amount maps to exactly one tier.
+    pub fn try_new(tiers: Vec<ApprovalTier>) -> Result<Self, PolicyError> {
+        if tiers.windows(2).any(|w| w[0].min_amount >= w[1].min_amount) {
+            return Err(PolicyError::ApprovalTiersNotAscending);
+        }
+        Ok(Self(tiers))
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &ApprovalTier> {
+        self.0.iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub(crate) fn rules_for(
+        &self,
+        default_rules: &ApprovalRules,
+        amount: Option<UsdCents>,
+    ) -> ApprovalRules {
+        let Some(amount) = amount else {
+            return default_rules.clone();
+        };
+        self.0
+            .iter()
+            .rev()
+            .find(|tier| tier.min_amount <= amount)
+            .map(|tier| tier.rules.clone())
+            .unwrap_or_else(|| default_rules.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::primitives::CommitteeId;
+
+    use super::*;
+
+    fn tiers(officers: CommitteeId, board: CommitteeId) -> ApprovalTiers {
+        ApprovalTiers::try_new(vec![
+            ApprovalTier {
+                min_amount: UsdCents::from(100_000),
+                rules: ApprovalRules::CommitteeThreshold {
+                    committee_id: officers,
+                    threshold: 1,
+                },
+            },
+            ApprovalTier {
+                min_amount: UsdCents::from(10_000_000),
+                rules: ApprovalRules::CommitteeThreshold {
+                    committee_id: board,
+                    threshold: 3,
+                },
+            },
+        ])
+        .unwrap()
+    }
+
+    #[test]
+    fn picks_highest_tier_reached() {
+        let officers = CommitteeId::new();
+        let board = CommitteeId::new();
+        let tiers = tiers(officers, board);
+        let default = ApprovalRules::SystemAutoApprove;
+
+        assert_eq!(
+            tiers.rules_for(&default, Some(UsdCents::from(50_000))),
+            ApprovalRules::SystemAutoApprove
+        );
+        assert_eq!(
+            tiers
+                .rules_for(&default, Some(UsdCents::from(100_000)))
+                .committee_id(),
+            Some(officers)
+        );
+        assert_eq!(
+            tiers
+                .rules_for(&default, Some(UsdCents::from(20_000_000)))
+                .committee_id(),
+            Some(board)
+        );
+    }
+
+    #[test]
+    fn no_amount_uses_default_rules() {
+        let tiers = tiers(CommitteeId::new(), CommitteeId::new());
+        assert_eq!(
+            tiers.rules_for(&ApprovalRules::SystemAutoApprove, None),
+            ApprovalRules::SystemAutoApprove
+        );
+    }
+
+    #[test]
+    fn rejects_unordered_tiers() {
+        let tier = ApprovalTier {
+            min_amount: UsdCents::from(100),
+            rules: ApprovalRules::SystemAutoApprove,
+        };
+        assert!(matches!(
+            ApprovalTiers::try_new(vec![tier.clone(), tier]),
+            Err(PolicyError::ApprovalTiersNotAscending)
+        ));
+    }
+}
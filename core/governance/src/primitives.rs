@@ -2,7 +2,18 @@ use serde::{Deserialize, Serialize};
 use std::{borrow::Cow, fmt::Display, str::FromStr};
 
 use authz::AllOrOne;
-es_entity::entity_id! { ApprovalProcessId, CommitteeId, PolicyId, CommitteeMemberId }
+pub use core_money::UsdCents;
+
+es_entity::entity_id! { ApprovalProcessId, CommitteeId, PolicyId, CommitteeMemberId, DelegationId }
+
+#[cfg(feature = "test-dummy")]
+impl TryFrom<&authz::dummy::DummySubject> for CommitteeMemberId {
+    type Error = &'static str;
+
+    fn try_from(_: &authz::dummy::DummySubject) -> Result<Self, Self::Error> {
+        Err("DummySubject is not a committee member")
+    }
+}
 
 #[cfg_attr(feature = "graphql", derive(async_graphql::Enum))]
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -58,6 +69,12 @@ impl GovernanceAction {
     pub const COMMITTEE_ADD_MEMBER: Self = GovernanceAction::Committee(CommitteeAction::AddMember);
     pub const COMMITTEE_REMOVE_MEMBER: Self =
         GovernanceAction::Committee(CommitteeAction::RemoveMember);
+    pub const COMMITTEE_CREATE_DELEGATION: Self =
+        GovernanceAction::Committee(CommitteeAction::CreateDelegation);
+    pub const COMMITTEE_REVOKE_DELEGATION: Self =
+        GovernanceAction::Committee(CommitteeAction::RevokeDelegation);
+    pub const COMMITTEE_LIST_DELEGATIONS: Self =
+        GovernanceAction::Committee(CommitteeAction::ListDelegations);
 
     pub const POLICY_CREATE: Self = GovernanceAction::Policy(PolicyAction::Create);
     pub const POLICY_READ: Self = GovernanceAction::Policy(PolicyAction::Read);
@@ -76,6 +93,10 @@ impl GovernanceAction {
         GovernanceAction::ApprovalProcess(ApprovalProcessAction::Deny);
     pub const APPROVAL_PROCESS_CONCLUDE: Self =
         GovernanceAction::ApprovalProcess(ApprovalProcessAction::Conclude);
+    pub const APPROVAL_PROCESS_TIME_OUT: Self =
+        GovernanceAction::ApprovalProcess(ApprovalProcessAction::TimeOut);
+    pub const APPROVAL_PROCESS_COMMENT: Self =
+        GovernanceAction::ApprovalProcess(ApprovalProcessAction::Comment);
 }
 
 impl Display for GovernanceAction {
@@ -113,6 +134,9 @@ pub enum CommitteeAction {
     RemoveMember,
     Read,
     List,
+    CreateDelegation,
+    RevokeDelegation,
+    ListDelegations,
 }
 
 #[derive(PartialEq, Clone, Copy, Debug, strum::Display, strum::EnumString)]
@@ -133,6 +157,8 @@ pub enum ApprovalProcessAction {
     Approve,
     Deny,
     Conclude,
+    TimeOut,
+    Comment,
 }
 
 pub type CommitteeAllOrOne = AllOrOne<CommitteeId>;
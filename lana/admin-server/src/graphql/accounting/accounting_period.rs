@@ -0,0 +1,87 @@
+use async_graphql::*;
+
+pub use lana_app::accounting::{
+    AccountingPeriod as DomainAccountingPeriod, AccountingPeriodStatus,
+};
+
+use crate::{graphql::loader::LanaDataLoader, primitives::*};
+
+use super::ledger_transaction::LedgerTransaction;
+
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct AccountingPeriod {
+    id: ID,
+    accounting_period_id: UUID,
+    period_start: Date,
+    period_end: Date,
+    created_at: Timestamp,
+
+    #[graphql(skip)]
+    pub(crate) entity: Arc<DomainAccountingPeriod>,
+}
+
+impl From<DomainAccountingPeriod> for AccountingPeriod {
+    fn from(period: DomainAccountingPeriod) -> Self {
+        Self {
+            id: period.id.to_global_id(),
+            accounting_period_id: UUID::from(period.id),
+            period_start: period.period_start.into(),
+            period_end: period.period_end.into(),
+            created_at: period.created_at().into(),
+            entity: Arc::new(period),
+        }
+    }
+}
+
+#[ComplexObject]
+impl AccountingPeriod {
+    async fn status(&self) -> AccountingPeriodStatus {
+        self.entity.status()
+    }
+
+    /// The ledger transaction posting the year-end closing entries. Only
+    /// present when the period was closed as a year end with non-zero
+    /// income statement balances.
+    async fn closing_transaction(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<LedgerTransaction>> {
+        let Some(tx_id) = self.entity.closing_transaction_id else {
+            return Ok(None);
+        };
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        Ok(loader.load_one(tx_id).await?)
+    }
+}
+
+#[derive(InputObject)]
+pub struct AccountingPeriodCreateInput {
+    pub period_start: Date,
+    pub period_end: Date,
+}
+crate::mutation_payload! { AccountingPeriodCreatePayload, accounting_period: AccountingPeriod }
+
+#[derive(InputObject)]
+pub struct AccountingPeriodSoftCloseInput {
+    pub accounting_period_id: UUID,
+}
+crate::mutation_payload! { AccountingPeriodSoftClosePayload, accounting_period: AccountingPeriod }
+
+#[derive(InputObject)]
+pub struct AccountingPeriodReopenInput {
+    pub accounting_period_id: UUID,
+}
+crate::mutation_payload! { AccountingPeriodReopenPayload, accounting_period: AccountingPeriod }
+
+#[derive(InputObject)]
+pub struct AccountingPeriodCloseInput {
+    pub accounting_period_id: UUID,
+}
+crate::mutation_payload! { AccountingPeriodClosePayload, accounting_period: AccountingPeriod }
+
+#[derive(InputObject)]
+pub struct AccountingPeriodCloseYearEndInput {
+    pub accounting_period_id: UUID,
+}
+crate::mutation_payload! { AccountingPeriodCloseYearEndPayload, accounting_period: AccountingPeriod }
@@ -0,0 +1,104 @@
+use async_graphql::*;
+
+use lana_app::cash_flow_statement::{
+    CashFlowAmount as DomainCashFlowAmount, CashFlowLine as DomainCashFlowLine,
+    CashFlowSection as DomainCashFlowSection, CashFlowStatement as DomainCashFlowStatement,
+};
+
+use crate::primitives::*;
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct CashFlowStatement {
+    pub name: String,
+    pub net_income: CashFlowLine,
+    pub operating_activities: CashFlowSection,
+    pub investing_activities: CashFlowSection,
+    pub financing_activities: CashFlowSection,
+    pub opening_cash: CashFlowAmount,
+    pub closing_cash: CashFlowAmount,
+
+    #[graphql(skip)]
+    pub entity: Arc<DomainCashFlowStatement>,
+}
+
+impl From<DomainCashFlowStatement> for CashFlowStatement {
+    fn from(statement: DomainCashFlowStatement) -> Self {
+        CashFlowStatement {
+            name: statement.name.to_string(),
+            net_income: CashFlowLine::from(&statement.net_income),
+            operating_activities: CashFlowSection::from(&statement.operating_activities),
+            investing_activities: CashFlowSection::from(&statement.investing_activities),
+            financing_activities: CashFlowSection::from(&statement.financing_activities),
+            opening_cash: statement.opening_cash.into(),
+            closing_cash: statement.closing_cash.into(),
+            entity: Arc::new(statement),
+        }
+    }
+}
+
+#[ComplexObject]
+impl CashFlowStatement {
+    async fn net_change_in_cash(&self) -> CashFlowAmount {
+        self.entity.net_change_in_cash().into()
+    }
+
+    async fn unexplained_change_in_cash(&self) -> CashFlowAmount {
+        self.entity.unexplained_change_in_cash().into()
+    }
+
+    async fn reconciled(&self) -> bool {
+        self.entity.is_reconciled()
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct CashFlowSection {
+    pub ledger_account_id: UUID,
+    pub name: String,
+    pub total: CashFlowAmount,
+    pub lines: Vec<CashFlowLine>,
+}
+
+impl From<&DomainCashFlowSection> for CashFlowSection {
+    fn from(section: &DomainCashFlowSection) -> Self {
+        CashFlowSection {
+            ledger_account_id: section.id.into(),
+            name: section.name.to_string(),
+            total: section.total.into(),
+            lines: section.lines.iter().map(CashFlowLine::from).collect(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct CashFlowLine {
+    pub ledger_account_id: UUID,
+    pub name: String,
+    pub amount: CashFlowAmount,
+}
+
+impl From<&DomainCashFlowLine> for CashFlowLine {
+    fn from(line: &DomainCashFlowLine) -> Self {
+        CashFlowLine {
+            ledger_account_id: line.id.into(),
+            name: line.name.to_string(),
+            amount: line.amount.into(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct CashFlowAmount {
+    pub usd: SignedUsdCents,
+    pub btc: SignedSatoshis,
+}
+
+impl From<DomainCashFlowAmount> for CashFlowAmount {
+    fn from(amount: DomainCashFlowAmount) -> Self {
+        CashFlowAmount {
+            usd: SignedUsdCents::from_usd(amount.usd),
+            btc: SignedSatoshis::from_btc(amount.btc),
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use async_graphql::*;
+
+pub use lana_app::accounting::comparative::ComparativePeriodPreset;
+use lana_app::accounting::comparative::{
+    ComparativeAmount as DomainComparativeAmount, ComparativeLine as DomainComparativeLine,
+    ComparativeStatement as DomainComparativeStatement, ComparativeValue as DomainComparativeValue,
+    ComparativeVariance as DomainComparativeVariance, StatementPeriod as DomainStatementPeriod,
+};
+
+use crate::{graphql::primitives::Decimal, primitives::*};
+
+#[derive(SimpleObject)]
+pub struct StatementPeriod {
+    pub from: Date,
+    pub until: Date,
+}
+
+impl From<DomainStatementPeriod> for StatementPeriod {
+    fn from(period: DomainStatementPeriod) -> Self {
+        StatementPeriod {
+            from: period.from.into(),
+            until: period.until.into(),
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct StatementPeriodInput {
+    pub from: Date,
+    pub until: Date,
+}
+
+impl From<StatementPeriodInput> for DomainStatementPeriod {
+    fn from(input: StatementPeriodInput) -> Self {
+        DomainStatementPeriod::new(input.from.into_inner(), input.until.into_inner())
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ComparativeStatement {
+    pub name: String,
+    pub periods: Vec<StatementPeriod>,
+    pub lines: Vec<ComparativeLine>,
+}
+
+impl From<DomainComparativeStatement> for ComparativeStatement {
+    fn from(statement: DomainComparativeStatement) -> Self {
+        ComparativeStatement {
+            name: statement.name,
+            periods: statement
+                .periods
+                .into_iter()
+                .map(StatementPeriod::from)
+                .collect(),
+            lines: statement
+                .lines
+                .into_iter()
+                .map(ComparativeLine::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ComparativeLine {
+    pub ledger_account_id: UUID,
+    pub name: String,
+    pub depth: i32,
+    pub values: Vec<ComparativeValue>,
+}
+
+impl From<DomainComparativeLine> for ComparativeLine {
+    fn from(line: DomainComparativeLine) -> Self {
+        ComparativeLine {
+            ledger_account_id: line.id.into(),
+            name: line.name,
+            depth: line.depth as i32,
+            values: line
+                .values
+                .into_iter()
+                .map(ComparativeValue::from)
+                .collect(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ComparativeValue {
+    pub amount: ComparativeAmount,
+    pub variance: Option<ComparativeVariance>,
+}
+
+impl From<DomainComparativeValue> for ComparativeValue {
+    fn from(value: DomainComparativeValue) -> Self {
+        ComparativeValue {
+            amount: value.amount.into(),
+            variance: value.variance.map(ComparativeVariance::from),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ComparativeVariance {
+    pub absolute: ComparativeAmount,
+    pub usd_percentage: Option<Decimal>,
+    pub btc_percentage: Option<Decimal>,
+}
+
+impl From<DomainComparativeVariance> for ComparativeVariance {
+    fn from(variance: DomainComparativeVariance) -> Self {
+        ComparativeVariance {
+            absolute: variance.absolute.into(),
+            usd_percentage: variance.usd_percentage.map(Decimal::from),
+            btc_percentage: variance.btc_percentage.map(Decimal::from),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ComparativeAmount {
+    pub usd: SignedUsdCents,
+    pub btc: SignedSatoshis,
+}
+
+impl From<DomainComparativeAmount> for ComparativeAmount {
+    fn from(amount: DomainComparativeAmount) -> Self {
+        ComparativeAmount {
+            usd: SignedUsdCents::from_usd(amount.usd),
+            btc: SignedSatoshis::from_btc(amount.btc),
+        }
+    }
+}
@@ -1,10 +1,15 @@
 use async_graphql::*;
 
-use crate::primitives::*;
-pub use lana_app::accounting::csv::{AccountingCsv as DomainAccountingCsv, AccountingCsvStatus};
+use super::{JournalEntryFilterInput, ManualTransaction, StatementPeriodInput};
+use crate::{graphql::loader::LanaDataLoader, primitives::*};
+pub use lana_app::accounting::{
+    csv::{AccountingCsv as DomainAccountingCsv, AccountingCsvStatus},
+    ManualTransactionImportError as DomainManualTransactionImportError,
+};
 use std::sync::Arc;
 
 #[derive(SimpleObject, Clone)]
+#[graphql(complex)]
 pub struct AccountingCsv {
     id: ID,
     csv_id: UUID,
@@ -27,6 +32,49 @@ impl From<DomainAccountingCsv> for AccountingCsv {
     }
 }
 
+#[ComplexObject]
+impl AccountingCsv {
+    /// The invalid lines of a rejected manual transaction import.
+    async fn import_errors(&self) -> Vec<ManualTransactionImportError> {
+        self.entity
+            .import_errors()
+            .iter()
+            .map(ManualTransactionImportError::from)
+            .collect()
+    }
+
+    /// The transactions created by a manual transaction import.
+    async fn imported_manual_transactions(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<ManualTransaction>> {
+        let ids = self.entity.imported_manual_transaction_ids();
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let mut manual_transactions = loader.load_many(ids.iter().copied()).await?;
+        Ok(ids
+            .iter()
+            .filter_map(|id| manual_transactions.remove(id))
+            .collect())
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ManualTransactionImportError {
+    line: u64,
+    reference: Option<String>,
+    message: String,
+}
+
+impl From<&DomainManualTransactionImportError> for ManualTransactionImportError {
+    fn from(error: &DomainManualTransactionImportError) -> Self {
+        Self {
+            line: error.line,
+            reference: error.reference.clone(),
+            message: error.message.clone(),
+        }
+    }
+}
+
 #[derive(SimpleObject)]
 pub struct AccountingCsvDownloadLink {
     pub url: String,
@@ -50,6 +98,38 @@ pub struct LedgerAccountCsvCreateInput {
 }
 crate::mutation_payload! { LedgerAccountCsvCreatePayload, accounting_csv: AccountingCsv }
 
+#[derive(InputObject)]
+pub struct ChartOfAccountsCsvCreateInput {
+    pub chart_id: UUID,
+}
+crate::mutation_payload! { ChartOfAccountsCsvCreatePayload, accounting_csv: AccountingCsv }
+
+#[derive(InputObject)]
+pub struct CashFlowStatementCsvCreateInput {
+    pub from: Date,
+    pub until: Option<Date>,
+}
+crate::mutation_payload! { CashFlowStatementCsvCreatePayload, accounting_csv: AccountingCsv }
+
+#[derive(InputObject)]
+pub struct ComparativeStatementCsvCreateInput {
+    pub periods: Vec<StatementPeriodInput>,
+}
+crate::mutation_payload! { ComparativeBalanceSheetCsvCreatePayload, accounting_csv: AccountingCsv }
+crate::mutation_payload! { ComparativeProfitAndLossStatementCsvCreatePayload, accounting_csv: AccountingCsv }
+
+#[derive(InputObject)]
+pub struct JournalEntrySearchCsvCreateInput {
+    pub filter: JournalEntryFilterInput,
+}
+crate::mutation_payload! { JournalEntrySearchCsvCreatePayload, accounting_csv: AccountingCsv }
+
+#[derive(InputObject)]
+pub struct ManualTransactionsCsvImportInput {
+    pub file: Upload,
+}
+crate::mutation_payload! { ManualTransactionsCsvImportPayload, accounting_csv: AccountingCsv }
+
 #[derive(InputObject)]
 pub struct AccountingCsvDownloadLinkGenerateInput {
     pub accounting_csv_id: UUID,
@@ -1,14 +1,21 @@
 use async_graphql::*;
 
 use cala_ledger::{DebitOrCredit, Layer};
-pub use lana_app::accounting::journal::JournalEntryCursor;
 use lana_app::accounting::journal::{
     JournalEntry as DomainJournalEntry, JournalEntryAmount as DomainJournalEntryAmount,
+    JournalEntryFilter,
 };
+pub use lana_app::accounting::journal::{JournalEntryCursor, JournalEntryOrigin};
 
 use super::{ledger_account::LedgerAccount, ledger_transaction::LedgerTransaction};
 
-use crate::{graphql::loader::LanaDataLoader, primitives::*};
+use crate::{
+    graphql::{
+        loader::{LanaDataLoader, CHART_REF},
+        primitives::Decimal,
+    },
+    primitives::*,
+};
 
 #[derive(SimpleObject)]
 #[graphql(complex)]
@@ -96,3 +103,44 @@ impl From<DomainJournalEntryAmount> for JournalEntryAmount {
         }
     }
 }
+
+#[derive(InputObject)]
+pub struct JournalEntryFilterInput {
+    /// Id or chart code of an account. A code also matches the accounts below it.
+    pub account_ref: Option<String>,
+    /// In the major unit of the entry currency.
+    pub min_amount: Option<Decimal>,
+    pub max_amount: Option<Decimal>,
+    pub effective_from: Option<Date>,
+    pub effective_until: Option<Date>,
+    pub template_code: Option<String>,
+    pub origin: Option<JournalEntryOrigin>,
+    pub description: Option<String>,
+}
+
+impl JournalEntryFilterInput {
+    pub async fn into_filter(
+        self,
+        app: &lana_app::app::LanaApp,
+    ) -> async_graphql::Result<JournalEntryFilter> {
+        let ledger_account_id = match self.account_ref {
+            Some(account_ref) => Some(
+                app.accounting()
+                    .resolve_ledger_account_id(CHART_REF.0, &account_ref.parse()?)
+                    .await?,
+            ),
+            None => None,
+        };
+
+        Ok(JournalEntryFilter {
+            ledger_account_id,
+            min_amount: self.min_amount.map(Into::into),
+            max_amount: self.max_amount.map(Into::into),
+            effective_from: self.effective_from.map(|d| d.into_inner()),
+            effective_until: self.effective_until.map(|d| d.into_inner()),
+            template_code: self.template_code,
+            origin: self.origin,
+            description: self.description,
+        })
+    }
+}
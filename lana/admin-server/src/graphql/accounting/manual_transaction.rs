@@ -1,22 +1,147 @@
 use async_graphql::*;
 
-pub use lana_app::accounting::manual_transaction::ManualEntryInput;
+pub use lana_app::accounting::{
+    manual_transaction::{ManualEntry as DomainManualEntry, ManualEntryInput},
+    LedgerAccountId, ManualTransaction as DomainManualTransaction, ManualTransactionStatus,
+};
 
-use crate::graphql::primitives::*;
-use crate::primitives::Date;
+use crate::graphql::{approval_process::ApprovalProcess, loader::LanaDataLoader, primitives::*};
+use crate::primitives::*;
 
 use cala_ledger::DebitOrCredit;
 
 use super::ledger_transaction::LedgerTransaction;
 
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct ManualTransaction {
+    id: ID,
+    manual_transaction_id: UUID,
+    approval_process_id: UUID,
+    reference: String,
+    description: String,
+    effective: Date,
+    auto_reverse_on: Option<Date>,
+    recurring_transaction_id: Option<UUID>,
+    created_at: Timestamp,
+
+    #[graphql(skip)]
+    pub(crate) entity: Arc<DomainManualTransaction>,
+}
+
+impl From<DomainManualTransaction> for ManualTransaction {
+    fn from(tx: DomainManualTransaction) -> Self {
+        Self {
+            id: tx.id.to_global_id(),
+            manual_transaction_id: UUID::from(tx.id),
+            approval_process_id: UUID::from(tx.approval_process_id),
+            reference: tx.reference.clone(),
+            description: tx.description.clone(),
+            effective: tx.effective.into(),
+            auto_reverse_on: tx.auto_reverse_on.map(Into::into),
+            recurring_transaction_id: tx.recurring_transaction_id.map(UUID::from),
+            created_at: tx.created_at().into(),
+            entity: Arc::new(tx),
+        }
+    }
+}
+
+#[ComplexObject]
+impl ManualTransaction {
+    async fn status(&self) -> ManualTransactionStatus {
+        self.entity.status()
+    }
+
+    async fn entries(&self) -> Vec<ManualTransactionEntry> {
+        self.entity
+            .entries
+            .iter()
+            .cloned()
+            .map(ManualTransactionEntry::from)
+            .collect()
+    }
+
+    async fn approval_process(&self, ctx: &Context<'_>) -> async_graphql::Result<ApprovalProcess> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let process = loader
+            .load_one(self.entity.approval_process_id)
+            .await?
+            .expect("process not found");
+        Ok(process)
+    }
+
+    /// The posted ledger transaction. Only present once the manual
+    /// transaction has been approved.
+    async fn ledger_transaction(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<LedgerTransaction>> {
+        if self.entity.is_approved_or_denied() != Some(true) {
+            return Ok(None);
+        }
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        Ok(loader.load_one(self.entity.ledger_transaction_id).await?)
+    }
+
+    /// The ledger transaction that reversed this one, if it has been reversed.
+    async fn reversal_ledger_transaction(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<LedgerTransaction>> {
+        let Some(tx_id) = self.entity.reversal_ledger_transaction_id else {
+            return Ok(None);
+        };
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        Ok(loader.load_one(tx_id).await?)
+    }
+
+    /// Why the accrual could not be reversed automatically and needs to be
+    /// reversed by hand.
+    async fn auto_reversal_error(&self) -> Option<&str> {
+        self.entity.auto_reversal_error()
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ManualTransactionEntry {
+    ledger_account_id: UUID,
+    amount: Decimal,
+    currency: String,
+    direction: DebitOrCredit,
+    description: String,
+}
+
+impl From<DomainManualEntry> for ManualTransactionEntry {
+    fn from(entry: DomainManualEntry) -> Self {
+        Self {
+            ledger_account_id: UUID::from(LedgerAccountId::from(entry.account_id)),
+            amount: entry.amount.into(),
+            currency: entry.currency.to_string(),
+            direction: entry.direction,
+            description: entry.description,
+        }
+    }
+}
+
 #[derive(InputObject)]
 pub struct ManualTransactionExecuteInput {
     pub description: String,
     pub reference: Option<String>,
     pub effective: Option<Date>,
     pub entries: Vec<ManualTransactionEntryInput>,
+    /// Reverse the transaction on the first day of the next accounting period.
+    #[graphql(default)]
+    pub auto_reverse: bool,
+}
+crate::mutation_payload! { ManualTransactionExecutePayload, manual_transaction: ManualTransaction }
+
+#[derive(InputObject)]
+pub struct ManualTransactionReverseInput {
+    pub manual_transaction_id: UUID,
+    pub effective: Date,
+    pub reason: String,
 }
-crate::mutation_payload! { ManualTransactionExecutePayload, transaction: LedgerTransaction }
+crate::mutation_payload! { ManualTransactionReversePayload, manual_transaction: ManualTransaction }
 
 #[derive(InputObject)]
 pub struct ManualTransactionEntryInput {
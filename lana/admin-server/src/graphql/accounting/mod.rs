@@ -1,19 +1,29 @@
+mod accounting_period;
 mod balance_sheet;
+mod cash_flow_statement;
+mod comparative_statement;
 mod csv;
 mod journal_entry;
 mod ledger_account;
 mod ledger_transaction;
 mod manual_transaction;
 mod profit_and_loss;
+mod recurring_transaction;
 mod transaction_templates;
 mod trial_balance;
+mod trial_balance_snapshot;
 
+pub use accounting_period::*;
 pub use balance_sheet::*;
+pub use cash_flow_statement::*;
+pub use comparative_statement::*;
 pub use csv::*;
 pub use journal_entry::*;
 pub use ledger_account::*;
 pub use ledger_transaction::*;
 pub use manual_transaction::*;
 pub use profit_and_loss::*;
+pub use recurring_transaction::*;
 pub use transaction_templates::*;
 pub use trial_balance::*;
+pub use trial_balance_snapshot::*;
@@ -0,0 +1,146 @@
+use async_graphql::*;
+
+pub use lana_app::accounting::{
+    recurring_transaction::RecurringTransactionsByCreatedAtCursor, RecurrenceSchedule,
+    RecurringTransaction as DomainRecurringTransaction, RecurringTransactionStatus,
+};
+
+use crate::{graphql::loader::LanaDataLoader, primitives::*};
+
+use super::manual_transaction::{
+    ManualTransaction, ManualTransactionEntry, ManualTransactionEntryInput,
+};
+
+#[derive(Enum, Copy, Clone, Eq, PartialEq)]
+pub enum RecurrenceScheduleKind {
+    MonthlyOnDay,
+    EndOfMonth,
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct RecurringTransaction {
+    id: ID,
+    recurring_transaction_id: UUID,
+    reference: String,
+    description: String,
+    schedule: RecurrenceScheduleKind,
+    day_of_month: Option<u32>,
+    start_date: Date,
+    end_date: Option<Date>,
+    created_at: Timestamp,
+
+    #[graphql(skip)]
+    pub(crate) entity: Arc<DomainRecurringTransaction>,
+}
+
+impl From<DomainRecurringTransaction> for RecurringTransaction {
+    fn from(recurring: DomainRecurringTransaction) -> Self {
+        let (schedule, day_of_month) = match recurring.schedule {
+            RecurrenceSchedule::MonthlyOnDay { day } => {
+                (RecurrenceScheduleKind::MonthlyOnDay, Some(day))
+            }
+            RecurrenceSchedule::EndOfMonth => (RecurrenceScheduleKind::EndOfMonth, None),
+        };
+        Self {
+            id: recurring.id.to_global_id(),
+            recurring_transaction_id: UUID::from(recurring.id),
+            reference: recurring.reference.clone(),
+            description: recurring.description.clone(),
+            schedule,
+            day_of_month,
+            start_date: recurring.start_date.into(),
+            end_date: recurring.end_date.map(Into::into),
+            created_at: recurring.created_at().into(),
+            entity: Arc::new(recurring),
+        }
+    }
+}
+
+#[ComplexObject]
+impl RecurringTransaction {
+    async fn status(&self) -> RecurringTransactionStatus {
+        self.entity.status()
+    }
+
+    async fn next_due_on(&self) -> Option<Date> {
+        self.entity.next_due_on().map(Into::into)
+    }
+
+    /// The posting that failed, while the recurring transaction is held in `FAILED`.
+    async fn last_failure(&self) -> Option<RecurringTransactionFailure> {
+        self.entity
+            .last_failure()
+            .map(|(effective, error)| RecurringTransactionFailure {
+                effective: effective.into(),
+                error: error.to_string(),
+            })
+    }
+
+    async fn entries(&self) -> Vec<ManualTransactionEntry> {
+        self.entity
+            .entries
+            .iter()
+            .cloned()
+            .map(ManualTransactionEntry::from)
+            .collect()
+    }
+
+    async fn manual_transactions(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<ManualTransaction>> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let ids: Vec<ManualTransactionId> = self.entity.postings().map(|(id, _)| id).collect();
+        let mut transactions = loader.load_many(ids.iter().copied()).await?;
+        Ok(ids
+            .iter()
+            .filter_map(|id| transactions.remove(id))
+            .collect())
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct RecurringTransactionFailure {
+    effective: Date,
+    error: String,
+}
+
+#[derive(InputObject)]
+pub struct RecurringTransactionCreateInput {
+    pub reference: String,
+    pub description: String,
+    pub entries: Vec<ManualTransactionEntryInput>,
+    pub schedule: RecurrenceScheduleKind,
+    /// Required for `MONTHLY_ON_DAY`.
+    pub day_of_month: Option<u32>,
+    pub start_date: Date,
+    pub end_date: Option<Date>,
+}
+crate::mutation_payload! { RecurringTransactionCreatePayload, recurring_transaction: RecurringTransaction }
+
+impl RecurringTransactionCreateInput {
+    pub fn recurrence_schedule(&self) -> async_graphql::Result<RecurrenceSchedule> {
+        match (self.schedule, self.day_of_month) {
+            (RecurrenceScheduleKind::MonthlyOnDay, Some(day)) => {
+                Ok(RecurrenceSchedule::MonthlyOnDay { day })
+            }
+            (RecurrenceScheduleKind::MonthlyOnDay, None) => {
+                Err(Error::new("dayOfMonth is required for MONTHLY_ON_DAY"))
+            }
+            (RecurrenceScheduleKind::EndOfMonth, _) => Ok(RecurrenceSchedule::EndOfMonth),
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct RecurringTransactionPauseInput {
+    pub recurring_transaction_id: UUID,
+}
+crate::mutation_payload! { RecurringTransactionPausePayload, recurring_transaction: RecurringTransaction }
+
+#[derive(InputObject)]
+pub struct RecurringTransactionResumeInput {
+    pub recurring_transaction_id: UUID,
+}
+crate::mutation_payload! { RecurringTransactionResumePayload, recurring_transaction: RecurringTransaction }
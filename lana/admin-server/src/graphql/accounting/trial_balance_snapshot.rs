@@ -0,0 +1,180 @@
+use async_graphql::*;
+
+pub use lana_app::trial_balance::{
+    SnapshotBalance as DomainSnapshotBalance,
+    TrialBalanceLineDifference as DomainTrialBalanceLineDifference,
+    TrialBalanceSnapshot as DomainTrialBalanceSnapshot, TrialBalanceSnapshotComparison,
+    TrialBalanceSnapshotKind, TrialBalanceSnapshotLine as DomainTrialBalanceSnapshotLine,
+    TrialBalanceSnapshotsByCreatedAtCursor,
+};
+
+use crate::{
+    graphql::{loader::LanaDataLoader, primitives::Decimal},
+    primitives::*,
+};
+
+use super::LedgerTransaction;
+
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct TrialBalanceSnapshot {
+    id: ID,
+    trial_balance_snapshot_id: UUID,
+    as_of: Date,
+    kind: TrialBalanceSnapshotKind,
+    checksum: String,
+    created_at: Timestamp,
+
+    #[graphql(skip)]
+    pub(crate) entity: Arc<DomainTrialBalanceSnapshot>,
+}
+
+impl From<DomainTrialBalanceSnapshot> for TrialBalanceSnapshot {
+    fn from(snapshot: DomainTrialBalanceSnapshot) -> Self {
+        Self {
+            id: snapshot.id.to_global_id(),
+            trial_balance_snapshot_id: UUID::from(snapshot.id),
+            as_of: snapshot.as_of.into(),
+            kind: snapshot.kind,
+            checksum: snapshot.checksum.clone(),
+            created_at: snapshot.created_at().into(),
+            entity: Arc::new(snapshot),
+        }
+    }
+}
+
+#[ComplexObject]
+impl TrialBalanceSnapshot {
+    /// Whether the stored balances still match the checksum taken at capture.
+    async fn intact(&self) -> bool {
+        self.entity.is_intact()
+    }
+
+    async fn lines(&self) -> Vec<TrialBalanceSnapshotLine> {
+        self.entity
+            .lines
+            .iter()
+            .map(TrialBalanceSnapshotLine::from)
+            .collect()
+    }
+
+    /// The snapshot set against the balances recomputed from the ledger now.
+    async fn comparison(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<TrialBalanceSnapshotDrift> {
+        let (app, sub) = crate::app_and_sub_from_ctx!(ctx);
+        let comparison = app
+            .accounting()
+            .trial_balances()
+            .compare_snapshot(sub, self.entity.id)
+            .await?;
+        Ok(TrialBalanceSnapshotDrift::from(comparison))
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct SnapshotBalance {
+    debit: Decimal,
+    credit: Decimal,
+}
+
+impl From<DomainSnapshotBalance> for SnapshotBalance {
+    fn from(balance: DomainSnapshotBalance) -> Self {
+        Self {
+            debit: balance.debit.into(),
+            credit: balance.credit.into(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct TrialBalanceSnapshotLine {
+    ledger_account_id: UUID,
+    name: String,
+    usd: SnapshotBalance,
+    btc: SnapshotBalance,
+}
+
+impl From<&DomainTrialBalanceSnapshotLine> for TrialBalanceSnapshotLine {
+    fn from(line: &DomainTrialBalanceSnapshotLine) -> Self {
+        Self {
+            ledger_account_id: line.ledger_account_id.into(),
+            name: line.name.clone(),
+            usd: line.usd.into(),
+            btc: line.btc.into(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct TrialBalanceLineDifference {
+    ledger_account_id: UUID,
+    name: String,
+    captured_usd: SnapshotBalance,
+    captured_btc: SnapshotBalance,
+    live_usd: SnapshotBalance,
+    live_btc: SnapshotBalance,
+}
+
+impl From<DomainTrialBalanceLineDifference> for TrialBalanceLineDifference {
+    fn from(difference: DomainTrialBalanceLineDifference) -> Self {
+        Self {
+            ledger_account_id: difference.ledger_account_id.into(),
+            name: difference.name,
+            captured_usd: difference.captured_usd.into(),
+            captured_btc: difference.captured_btc.into(),
+            live_usd: difference.live_usd.into(),
+            live_btc: difference.live_btc.into(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct TrialBalanceSnapshotDrift {
+    unchanged: bool,
+    differences: Vec<TrialBalanceLineDifference>,
+
+    #[graphql(skip)]
+    transaction_ids: Vec<LedgerTransactionId>,
+}
+
+impl From<TrialBalanceSnapshotComparison> for TrialBalanceSnapshotDrift {
+    fn from(comparison: TrialBalanceSnapshotComparison) -> Self {
+        Self {
+            unchanged: comparison.is_unchanged(),
+            differences: comparison
+                .differences
+                .into_iter()
+                .map(TrialBalanceLineDifference::from)
+                .collect(),
+            transaction_ids: comparison.transaction_ids,
+        }
+    }
+}
+
+#[ComplexObject]
+impl TrialBalanceSnapshotDrift {
+    /// Back-dated transactions recorded after the snapshot that explain the differences.
+    async fn transactions(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<LedgerTransaction>> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let mut transactions = loader
+            .load_many(self.transaction_ids.iter().copied())
+            .await?;
+        Ok(self
+            .transaction_ids
+            .iter()
+            .filter_map(|id| transactions.remove(id))
+            .collect())
+    }
+}
+
+#[derive(InputObject)]
+pub struct TrialBalanceSnapshotCreateInput {
+    pub as_of: Date,
+}
+crate::mutation_payload! { TrialBalanceSnapshotCreatePayload, trial_balance_snapshot: TrialBalanceSnapshot }
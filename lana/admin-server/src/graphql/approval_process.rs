@@ -1,10 +1,13 @@
 use async_graphql::*;
 
+use std::collections::HashSet;
+
 use crate::primitives::*;
 
 use super::{
-    approval_rules::*, credit_facility::*, loader::LanaDataLoader, policy::*, user::User,
-    withdrawal::*,
+    accounting::ManualTransaction, approval_rules::*, beneficiary::Beneficiary,
+    config_change_set::ConfigChangeSet, credit_facility::*, document::Document,
+    loader::LanaDataLoader, policy::*, user::User, withdrawal::*, withdrawal_limit_override::*,
 };
 
 pub use governance::{
@@ -41,7 +44,28 @@ impl From<DomainApprovalProcess> for ApprovalProcess {
 #[ComplexObject]
 impl ApprovalProcess {
     async fn rules(&self) -> ApprovalRules {
-        ApprovalRules::from(self.entity.rules)
+        ApprovalRules::from(self.entity.rules.clone())
+    }
+
+    /// Stage the process is waiting on, counting from 1.
+    async fn current_stage(&self) -> usize {
+        (self.entity.current_stage() + 1).min(self.entity.stage_count())
+    }
+
+    async fn stage_count(&self) -> usize {
+        self.entity.stage_count()
+    }
+
+    async fn expires_at(&self) -> Option<Timestamp> {
+        self.entity.expires_at().map(Into::into)
+    }
+
+    async fn escalated(&self) -> bool {
+        self.entity.was_escalated()
+    }
+
+    async fn timed_out(&self) -> bool {
+        self.entity.has_timed_out()
     }
 
     async fn denied_reason(&self) -> Option<&str> {
@@ -57,72 +81,81 @@ impl ApprovalProcess {
         Ok(policy)
     }
 
+    /// The discussion thread, oldest comment first.
+    async fn comments(&self) -> Vec<ApprovalProcessComment> {
+        self.entity
+            .comments()
+            .into_iter()
+            .map(ApprovalProcessComment::from)
+            .collect()
+    }
+
     async fn subject_can_submit_decision(&self, ctx: &Context<'_>) -> async_graphql::Result<bool> {
         let (app, sub) = crate::app_and_sub_from_ctx!(ctx);
-
-        let committee = if let Some(committee_id) = self.entity.committee_id() {
-            let loader = ctx.data_unchecked::<LanaDataLoader>();
-            let committee = loader
-                .load_one(committee_id)
-                .await?
-                .expect("committee not found");
-            Some(committee.entity)
-        } else {
-            None
-        };
-
         Ok(app
             .governance()
-            .subject_can_submit_decision(sub, &self.entity, committee.as_ref().map(AsRef::as_ref))
+            .subject_can_submit_decision(sub, &self.entity)
             .await?)
     }
 
     async fn voters(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<ApprovalProcessVoter>> {
-        if let Some(committee_id) = self.entity.committee_id() {
-            let loader = ctx.data_unchecked::<LanaDataLoader>();
-            let committee = loader
-                .load_one(committee_id)
-                .await?
-                .expect("committee not found");
-            let mut approvers = self.entity.approvers();
-            let mut deniers = self.entity.deniers();
-            let mut voters: Vec<_> = committee
-                .entity
-                .members()
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let committees = loader.load_many(self.entity.committee_ids()).await?;
+        let mut members: HashSet<_> = self.entity.required_member_ids().into_iter().collect();
+        for committee in committees.values() {
+            members.extend(committee.entity.members());
+        }
+        let mut approvers = self.entity.approvers();
+        let mut deniers = self.entity.deniers();
+        let mut voters: Vec<_> = members
+            .into_iter()
+            .map(|member_id| ApprovalProcessVoter {
+                delegate_id: self
+                    .entity
+                    .member_vote_delegate(member_id)
+                    .map(UserId::from),
+                still_eligible: self.entity.initiator_id() != Some(member_id),
+                did_vote: approvers.contains(&member_id) || deniers.contains(&member_id),
+                did_approve: approvers.remove(&member_id),
+                did_deny: deniers.remove(&member_id),
+                rationale: self.entity.member_rationale(member_id).map(str::to_string),
+                user_id: UserId::from(member_id),
+                voted_at: self.entity.member_voted_at(member_id).map(Into::into),
+            })
+            .collect();
+        voters.extend(
+            approvers
                 .into_iter()
                 .map(|member_id| ApprovalProcessVoter {
-                    still_eligible: true,
-                    did_vote: approvers.contains(&member_id) || deniers.contains(&member_id),
-                    did_approve: approvers.remove(&member_id),
-                    did_deny: deniers.remove(&member_id),
                     user_id: UserId::from(member_id),
+                    delegate_id: self
+                        .entity
+                        .member_vote_delegate(member_id)
+                        .map(UserId::from),
+                    still_eligible: false,
+                    did_vote: true,
+                    did_approve: true,
+                    did_deny: false,
+                    rationale: self.entity.member_rationale(member_id).map(str::to_string),
                     voted_at: self.entity.member_voted_at(member_id).map(Into::into),
                 })
-                .collect();
-            voters.extend(
-                approvers
-                    .into_iter()
-                    .map(|member_id| ApprovalProcessVoter {
-                        user_id: UserId::from(member_id),
-                        still_eligible: false,
-                        did_vote: true,
-                        did_approve: true,
-                        did_deny: false,
-                        voted_at: self.entity.member_voted_at(member_id).map(Into::into),
-                    })
-                    .chain(deniers.into_iter().map(|member_id| ApprovalProcessVoter {
+                .chain(deniers.into_iter().map(|member_id| {
+                    ApprovalProcessVoter {
                         user_id: UserId::from(member_id),
+                        delegate_id: self
+                            .entity
+                            .member_vote_delegate(member_id)
+                            .map(UserId::from),
                         still_eligible: false,
                         did_vote: true,
                         did_approve: false,
                         did_deny: true,
+                        rationale: None,
                         voted_at: self.entity.member_voted_at(member_id).map(Into::into),
-                    })),
-            );
-            Ok(voters)
-        } else {
-            Ok(vec![])
-        }
+                    }
+                })),
+        );
+        Ok(voters)
     }
 
     async fn target(&self, ctx: &Context<'_>) -> async_graphql::Result<ApprovalProcessTarget> {
@@ -164,6 +197,56 @@ impl ApprovalProcess {
                     .expect("disbursal not found");
                 Ok(ApprovalProcessTarget::CreditFacilityDisbursal(disbursal))
             }
+            ApprovalProcessType::WithdrawalLimitOverrideApproval => {
+                let limit_override = loader
+                    .load_one(
+                        self.entity
+                            .target_ref()
+                            .parse::<WithdrawalLimitOverrideId>()
+                            .expect("invalid target ref"),
+                    )
+                    .await?
+                    .expect("withdrawal limit override not found");
+                Ok(ApprovalProcessTarget::WithdrawalLimitOverride(
+                    limit_override,
+                ))
+            }
+            ApprovalProcessType::ManualTransactionApproval => {
+                let manual_transaction = loader
+                    .load_one(
+                        self.entity
+                            .target_ref()
+                            .parse::<ManualTransactionId>()
+                            .expect("invalid target ref"),
+                    )
+                    .await?
+                    .expect("manual transaction not found");
+                Ok(ApprovalProcessTarget::ManualTransaction(manual_transaction))
+            }
+            ApprovalProcessType::ConfigChangeApproval => {
+                let change_set = loader
+                    .load_one(
+                        self.entity
+                            .target_ref()
+                            .parse::<ConfigChangeSetId>()
+                            .expect("invalid target ref"),
+                    )
+                    .await?
+                    .expect("config change set not found");
+                Ok(ApprovalProcessTarget::ConfigChangeSet(change_set))
+            }
+            ApprovalProcessType::BeneficiaryApproval => {
+                let beneficiary = loader
+                    .load_one(
+                        self.entity
+                            .target_ref()
+                            .parse::<BeneficiaryId>()
+                            .expect("invalid target ref"),
+                    )
+                    .await?
+                    .expect("beneficiary not found");
+                Ok(ApprovalProcessTarget::Beneficiary(beneficiary))
+            }
         }
     }
 }
@@ -174,6 +257,10 @@ pub enum ApprovalProcessType {
     WithdrawalApproval,
     CreditFacilityApproval,
     DisbursalApproval,
+    WithdrawalLimitOverrideApproval,
+    ManualTransactionApproval,
+    ConfigChangeApproval,
+    BeneficiaryApproval,
 }
 
 impl From<&governance::ApprovalProcessType> for ApprovalProcessType {
@@ -184,6 +271,14 @@ impl From<&governance::ApprovalProcessType> for ApprovalProcessType {
             Self::CreditFacilityApproval
         } else if process_type == &lana_app::governance::APPROVE_DISBURSAL_PROCESS {
             Self::DisbursalApproval
+        } else if process_type == &lana_app::governance::APPROVE_WITHDRAWAL_LIMIT_OVERRIDE_PROCESS {
+            Self::WithdrawalLimitOverrideApproval
+        } else if process_type == &lana_app::governance::APPROVE_MANUAL_TRANSACTION_PROCESS {
+            Self::ManualTransactionApproval
+        } else if process_type == &lana_app::governance::APPROVE_CONFIG_CHANGE_SET_PROCESS {
+            Self::ConfigChangeApproval
+        } else if process_type == &lana_app::governance::APPROVE_BENEFICIARY_PROCESS {
+            Self::BeneficiaryApproval
         } else {
             panic!("Unknown approval process type: {:?}", process_type);
         }
@@ -195,10 +290,13 @@ impl From<&governance::ApprovalProcessType> for ApprovalProcessType {
 pub struct ApprovalProcessVoter {
     #[graphql(skip)]
     user_id: UserId,
+    #[graphql(skip)]
+    delegate_id: Option<UserId>,
     still_eligible: bool,
     did_vote: bool,
     did_approve: bool,
     did_deny: bool,
+    rationale: Option<String>,
     voted_at: Option<Timestamp>,
 }
 
@@ -213,6 +311,63 @@ impl ApprovalProcessVoter {
 
         Ok(users)
     }
+
+    /// The delegate that cast this member's vote, if any.
+    async fn delegate(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<User>> {
+        let Some(delegate_id) = self.delegate_id else {
+            return Ok(None);
+        };
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        Ok(loader.load_one(delegate_id).await?)
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct ApprovalProcessComment {
+    body: String,
+    created_at: Timestamp,
+    #[graphql(skip)]
+    author_id: UserId,
+    #[graphql(skip)]
+    document_ids: Vec<DocumentId>,
+}
+
+impl From<governance::ApprovalProcessComment> for ApprovalProcessComment {
+    fn from(comment: governance::ApprovalProcessComment) -> Self {
+        Self {
+            body: comment.body,
+            created_at: comment.created_at.into(),
+            author_id: UserId::from(comment.author_id),
+            document_ids: comment
+                .document_refs
+                .iter()
+                .map(|document_ref| document_ref.parse().expect("invalid document ref"))
+                .collect(),
+        }
+    }
+}
+
+#[ComplexObject]
+impl ApprovalProcessComment {
+    async fn author(&self, ctx: &Context<'_>) -> async_graphql::Result<User> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let author = loader
+            .load_one(self.author_id)
+            .await?
+            .expect("user not found");
+        Ok(author)
+    }
+
+    async fn documents(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Document>> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let mut documents = loader.load_many(self.document_ids.iter().copied()).await?;
+        Ok(self
+            .document_ids
+            .iter()
+            .filter_map(|id| documents.remove(id))
+            .collect())
+    }
 }
 
 #[derive(async_graphql::Union)]
@@ -220,16 +375,33 @@ pub(super) enum ApprovalProcessTarget {
     Withdrawal(Withdrawal),
     CreditFacility(CreditFacility),
     CreditFacilityDisbursal(CreditFacilityDisbursal),
+    WithdrawalLimitOverride(WithdrawalLimitOverride),
+    ManualTransaction(ManualTransaction),
+    ConfigChangeSet(ConfigChangeSet),
+    Beneficiary(Beneficiary),
 }
 
 #[derive(InputObject)]
 pub struct ApprovalProcessApproveInput {
     pub process_id: UUID,
+    /// Committee member to vote for, when voting as their delegate.
+    pub on_behalf_of: Option<UUID>,
+    pub rationale: Option<String>,
 }
 crate::mutation_payload! { ApprovalProcessApprovePayload, approval_process: ApprovalProcess }
 
 #[derive(InputObject)]
 pub struct ApprovalProcessDenyInput {
     pub process_id: UUID,
+    /// Committee member to vote for, when voting as their delegate.
+    pub on_behalf_of: Option<UUID>,
 }
 crate::mutation_payload! { ApprovalProcessDenyPayload, approval_process: ApprovalProcess }
+
+#[derive(InputObject)]
+pub struct ApprovalProcessCommentInput {
+    pub process_id: UUID,
+    pub body: String,
+    pub document_ids: Option<Vec<UUID>>,
+}
+crate::mutation_payload! { ApprovalProcessCommentPayload, approval_process: ApprovalProcess }
@@ -1,11 +1,15 @@
 use async_graphql::*;
 
-use super::{committee::Committee, loader::LanaDataLoader};
+use crate::primitives::*;
+
+use super::{committee::Committee, loader::LanaDataLoader, user::User};
 
 #[derive(async_graphql::Union)]
 pub(super) enum ApprovalRules {
     System(SystemApproval),
     CommitteeThreshold(CommitteeThreshold),
+    ApprovalChain(ApprovalChain),
+    Composite(CompositeApproval),
 }
 
 impl From<governance::ApprovalRules> for ApprovalRules {
@@ -18,9 +22,73 @@ impl From<governance::ApprovalRules> for ApprovalRules {
                 threshold,
                 committee_id,
             }),
+            governance::ApprovalRules::ApprovalChain { stages } => {
+                ApprovalRules::ApprovalChain(ApprovalChain {
+                    stages: stages
+                        .into_iter()
+                        .map(|stage| CommitteeThreshold {
+                            threshold: stage.threshold,
+                            committee_id: stage.committee_id,
+                        })
+                        .collect(),
+                })
+            }
             governance::ApprovalRules::SystemAutoApprove => {
                 ApprovalRules::System(SystemApproval { auto_approve: true })
             }
+            governance::ApprovalRules::Composite { stages } => {
+                ApprovalRules::Composite(CompositeApproval {
+                    stages: stages.into_iter().map(ApprovalExpression::from).collect(),
+                })
+            }
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub(super) struct ApprovalTier {
+    min_amount: UsdCents,
+    rules: ApprovalRules,
+}
+
+impl From<&governance::ApprovalTier> for ApprovalTier {
+    fn from(tier: &governance::ApprovalTier) -> Self {
+        Self {
+            min_amount: tier.min_amount,
+            rules: ApprovalRules::from(tier.rules.clone()),
+        }
+    }
+}
+
+#[derive(async_graphql::Enum, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalTimeoutActionType {
+    Deny,
+    Escalate,
+}
+
+#[derive(SimpleObject)]
+pub(super) struct ApprovalExpiry {
+    ttl_hours: u32,
+    action: ApprovalTimeoutActionType,
+    fallback: Option<CommitteeThreshold>,
+}
+
+impl From<governance::ApprovalExpiry> for ApprovalExpiry {
+    fn from(expiry: governance::ApprovalExpiry) -> Self {
+        let (action, fallback) = match expiry.action {
+            governance::ApprovalTimeoutAction::Deny => (ApprovalTimeoutActionType::Deny, None),
+            governance::ApprovalTimeoutAction::Escalate { fallback } => (
+                ApprovalTimeoutActionType::Escalate,
+                Some(CommitteeThreshold {
+                    threshold: fallback.threshold,
+                    committee_id: fallback.committee_id,
+                }),
+            ),
+        };
+        Self {
+            ttl_hours: expiry.ttl_hours,
+            action,
+            fallback,
         }
     }
 }
@@ -30,6 +98,11 @@ pub(super) struct SystemApproval {
     auto_approve: bool,
 }
 
+#[derive(SimpleObject)]
+pub(super) struct ApprovalChain {
+    stages: Vec<CommitteeThreshold>,
+}
+
 #[derive(SimpleObject)]
 #[graphql(complex)]
 pub(super) struct CommitteeThreshold {
@@ -49,3 +122,87 @@ impl CommitteeThreshold {
         Ok(committee)
     }
 }
+
+#[derive(SimpleObject)]
+pub(super) struct CompositeApproval {
+    stages: Vec<ApprovalExpression>,
+}
+
+#[derive(async_graphql::Enum, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalExpressionKind {
+    AllOf,
+    AnyOf,
+    CommitteeThreshold,
+    MemberRequired,
+    Weekday,
+}
+
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub(super) struct ApprovalExpression {
+    kind: ApprovalExpressionKind,
+    /// Sub-rules of `ALL_OF` and `ANY_OF`.
+    rules: Vec<ApprovalExpression>,
+    threshold: Option<usize>,
+    #[graphql(skip)]
+    committee_id: Option<governance::CommitteeId>,
+    #[graphql(skip)]
+    member_id: Option<governance::CommitteeMemberId>,
+}
+
+impl From<governance::ApprovalExpression> for ApprovalExpression {
+    fn from(expression: governance::ApprovalExpression) -> Self {
+        let (kind, rules) = match &expression {
+            governance::ApprovalExpression::AllOf { rules } => {
+                (ApprovalExpressionKind::AllOf, rules.clone())
+            }
+            governance::ApprovalExpression::AnyOf { rules } => {
+                (ApprovalExpressionKind::AnyOf, rules.clone())
+            }
+            governance::ApprovalExpression::CommitteeThreshold { .. } => {
+                (ApprovalExpressionKind::CommitteeThreshold, vec![])
+            }
+            governance::ApprovalExpression::MemberRequired { .. } => {
+                (ApprovalExpressionKind::MemberRequired, vec![])
+            }
+            governance::ApprovalExpression::Weekday => (ApprovalExpressionKind::Weekday, vec![]),
+        };
+        let (committee_id, threshold) = match expression {
+            governance::ApprovalExpression::CommitteeThreshold {
+                committee_id,
+                threshold,
+            } => (Some(committee_id), Some(threshold)),
+            _ => (None, None),
+        };
+        let member_id = match expression {
+            governance::ApprovalExpression::MemberRequired { member_id } => Some(member_id),
+            _ => None,
+        };
+        Self {
+            kind,
+            rules: rules.into_iter().map(ApprovalExpression::from).collect(),
+            threshold,
+            committee_id,
+            member_id,
+        }
+    }
+}
+
+#[ComplexObject]
+impl ApprovalExpression {
+    async fn committee(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Committee>> {
+        let Some(committee_id) = self.committee_id else {
+            return Ok(None);
+        };
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        Ok(loader.load_one(committee_id).await?)
+    }
+
+    async fn member(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<User>> {
+        let Some(member_id) = self.member_id else {
+            return Ok(None);
+        };
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        Ok(loader.load_one(UserId::from(member_id)).await?)
+    }
+}
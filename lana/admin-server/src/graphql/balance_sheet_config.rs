@@ -2,6 +2,8 @@ use async_graphql::*;
 
 use crate::primitives::*;
 
+use super::config_change_set::ConfigChangeSet;
+
 pub use lana_app::balance_sheet::ChartOfAccountsIntegrationConfig as DomainChartOfAccountsIntegrationConfig;
 
 #[derive(SimpleObject, Clone)]
@@ -49,4 +51,22 @@ pub struct BalanceSheetModuleConfigureInput {
     pub chart_of_accounts_cost_of_revenue_code: String,
     pub chart_of_accounts_expenses_code: String,
 }
-crate::mutation_payload! { BalanceSheetModuleConfigurePayload, balance_sheet_config: BalanceSheetModuleConfig }
+#[derive(SimpleObject)]
+pub struct BalanceSheetModuleConfigurePayload {
+    config_change_set: ConfigChangeSet,
+    /// The configuration in place once the change-set has been processed. Empty
+    /// while the change is still pending approval.
+    balance_sheet_config: Option<BalanceSheetModuleConfig>,
+}
+
+impl BalanceSheetModuleConfigurePayload {
+    pub fn new(
+        config_change_set: ConfigChangeSet,
+        balance_sheet_config: Option<BalanceSheetModuleConfig>,
+    ) -> Self {
+        Self {
+            config_change_set,
+            balance_sheet_config,
+        }
+    }
+}
@@ -0,0 +1,189 @@
+use async_graphql::*;
+
+use crate::primitives::*;
+
+use super::{deposit::Deposit, loader::LanaDataLoader};
+
+pub use lana_app::deposit::{
+    BankStatementCsvMapping as DomainBankStatementCsvMapping,
+    BankStatementException as DomainBankStatementException, BankStatementExceptionReason,
+    BankStatementExceptionStatus, BankStatementExceptionsByCreatedAtCursor, BankStatementFormat,
+    BankStatementImport as DomainBankStatementImport, BankStatementImportsByCreatedAtCursor,
+};
+
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct BankStatementImport {
+    id: ID,
+    bank_statement_import_id: UUID,
+    file_name: String,
+    format: BankStatementFormat,
+    statement_account: String,
+    created_at: Timestamp,
+
+    #[graphql(skip)]
+    pub(super) entity: Arc<DomainBankStatementImport>,
+}
+
+impl From<DomainBankStatementImport> for BankStatementImport {
+    fn from(import: DomainBankStatementImport) -> Self {
+        BankStatementImport {
+            id: import.id.to_global_id(),
+            bank_statement_import_id: UUID::from(import.id),
+            file_name: import.file_name.clone(),
+            format: import.format,
+            statement_account: import.statement_account.clone(),
+            created_at: import.created_at().into(),
+
+            entity: Arc::new(import),
+        }
+    }
+}
+
+#[ComplexObject]
+impl BankStatementImport {
+    async fn matched_lines(&self) -> usize {
+        self.entity.summary().matched_lines
+    }
+
+    async fn matched_amount(&self) -> UsdCents {
+        self.entity.summary().matched_amount
+    }
+
+    async fn exception_lines(&self) -> usize {
+        self.entity.summary().exception_lines
+    }
+
+    async fn duplicate_lines(&self) -> usize {
+        self.entity.summary().duplicate_lines
+    }
+
+    async fn exceptions(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<BankStatementException>> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let ids = self.entity.exception_ids();
+        let mut exceptions = loader.load_many(ids.iter().copied()).await?;
+        Ok(ids.iter().filter_map(|id| exceptions.remove(id)).collect())
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct BankStatementException {
+    id: ID,
+    bank_statement_exception_id: UUID,
+    bank_statement_import_id: UUID,
+    reason: BankStatementExceptionReason,
+    bank_reference: String,
+    value_date: Date,
+    amount: UsdCents,
+    customer_reference: Option<String>,
+    account_number: Option<String>,
+    remittance_information: Option<String>,
+    created_at: Timestamp,
+
+    #[graphql(skip)]
+    pub(super) entity: Arc<DomainBankStatementException>,
+}
+
+impl From<DomainBankStatementException> for BankStatementException {
+    fn from(exception: DomainBankStatementException) -> Self {
+        BankStatementException {
+            id: exception.id.to_global_id(),
+            bank_statement_exception_id: UUID::from(exception.id),
+            bank_statement_import_id: UUID::from(exception.bank_statement_import_id),
+            reason: exception.reason,
+            bank_reference: exception.line.bank_reference.clone(),
+            value_date: exception.line.value_date.into(),
+            amount: exception.line.amount,
+            customer_reference: exception.line.customer_reference.clone(),
+            account_number: exception.line.account_number.clone(),
+            remittance_information: exception.line.remittance_information.clone(),
+            created_at: exception.created_at().into(),
+
+            entity: Arc::new(exception),
+        }
+    }
+}
+
+#[ComplexObject]
+impl BankStatementException {
+    async fn status(&self) -> BankStatementExceptionStatus {
+        self.entity.status()
+    }
+
+    async fn dismissal_note(&self) -> Option<&str> {
+        self.entity.dismissal_note()
+    }
+
+    async fn deposit(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Deposit>> {
+        let Some(deposit_id) = self.entity.deposit_id else {
+            return Ok(None);
+        };
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        Ok(loader.load_one(deposit_id).await?)
+    }
+
+    async fn bank_statement_import(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<BankStatementImport> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let import = loader
+            .load_one(self.entity.bank_statement_import_id)
+            .await?
+            .expect("bank statement import not found");
+        Ok(import)
+    }
+}
+
+#[derive(InputObject)]
+pub struct BankStatementCsvMappingInput {
+    pub statement_account: String,
+    pub bank_reference_column: String,
+    pub amount_column: String,
+    pub value_date_column: String,
+    pub date_format: String,
+    pub customer_reference_column: Option<String>,
+    pub account_number_column: Option<String>,
+    pub remittance_information_column: Option<String>,
+}
+
+impl From<BankStatementCsvMappingInput> for DomainBankStatementCsvMapping {
+    fn from(input: BankStatementCsvMappingInput) -> Self {
+        Self {
+            statement_account: input.statement_account,
+            bank_reference_column: input.bank_reference_column,
+            amount_column: input.amount_column,
+            value_date_column: input.value_date_column,
+            date_format: input.date_format,
+            customer_reference_column: input.customer_reference_column,
+            account_number_column: input.account_number_column,
+            remittance_information_column: input.remittance_information_column,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct BankStatementUploadInput {
+    pub format: BankStatementFormat,
+    pub csv_mapping: Option<BankStatementCsvMappingInput>,
+    pub file: Upload,
+}
+crate::mutation_payload! { BankStatementUploadPayload, bank_statement_import: BankStatementImport }
+
+#[derive(InputObject)]
+pub struct BankStatementExceptionResolveInput {
+    pub bank_statement_exception_id: UUID,
+    pub deposit_account_id: UUID,
+}
+crate::mutation_payload! { BankStatementExceptionResolvePayload, bank_statement_exception: BankStatementException }
+
+#[derive(InputObject)]
+pub struct BankStatementExceptionDismissInput {
+    pub bank_statement_exception_id: UUID,
+    pub note: String,
+}
+crate::mutation_payload! { BankStatementExceptionDismissPayload, bank_statement_exception: BankStatementException }
@@ -0,0 +1,145 @@
+use async_graphql::*;
+
+use crate::primitives::*;
+
+use super::{
+    approval_process::ApprovalProcess, customer::Customer, loader::LanaDataLoader,
+    payout_batch::PayoutDestinationInput,
+};
+
+pub use lana_app::deposit::{
+    Beneficiary as DomainBeneficiary, BeneficiaryDetails as DomainBeneficiaryDetails,
+    BeneficiaryStatus, PayoutAccountType,
+};
+
+#[derive(SimpleObject, Clone)]
+pub struct BeneficiaryBankAccount {
+    account_holder_name: String,
+    routing_number: String,
+    account_number: String,
+    account_type: PayoutAccountType,
+}
+
+#[derive(SimpleObject, Clone)]
+pub struct BeneficiaryCryptoAddress {
+    network: String,
+    address: String,
+}
+
+#[derive(async_graphql::Union, Clone)]
+pub enum BeneficiaryDetails {
+    BankAccount(BeneficiaryBankAccount),
+    CryptoAddress(BeneficiaryCryptoAddress),
+}
+
+impl From<DomainBeneficiaryDetails> for BeneficiaryDetails {
+    fn from(details: DomainBeneficiaryDetails) -> Self {
+        match details {
+            DomainBeneficiaryDetails::BankAccount(destination) => {
+                BeneficiaryDetails::BankAccount(BeneficiaryBankAccount {
+                    account_holder_name: destination.account_holder_name,
+                    routing_number: destination.routing_number,
+                    account_number: destination.account_number,
+                    account_type: destination.account_type,
+                })
+            }
+            DomainBeneficiaryDetails::CryptoAddress { network, address } => {
+                BeneficiaryDetails::CryptoAddress(BeneficiaryCryptoAddress { network, address })
+            }
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct Beneficiary {
+    id: ID,
+    beneficiary_id: UUID,
+    customer_id: UUID,
+    approval_process_id: UUID,
+    label: String,
+    details: BeneficiaryDetails,
+    cooling_off_until: Timestamp,
+    created_at: Timestamp,
+
+    #[graphql(skip)]
+    pub(super) entity: Arc<DomainBeneficiary>,
+}
+
+impl From<DomainBeneficiary> for Beneficiary {
+    fn from(beneficiary: DomainBeneficiary) -> Self {
+        Beneficiary {
+            id: beneficiary.id.to_global_id(),
+            beneficiary_id: UUID::from(beneficiary.id),
+            customer_id: UUID::from(beneficiary.deposit_account_holder_id),
+            approval_process_id: UUID::from(beneficiary.approval_process_id),
+            label: beneficiary.label.clone(),
+            details: BeneficiaryDetails::from(beneficiary.details.clone()),
+            cooling_off_until: beneficiary.cooling_off_until.into(),
+            created_at: beneficiary.created_at().into(),
+            entity: Arc::new(beneficiary),
+        }
+    }
+}
+
+#[ComplexObject]
+impl Beneficiary {
+    async fn status(&self) -> BeneficiaryStatus {
+        self.entity.status(chrono::Utc::now())
+    }
+
+    async fn approval_process(&self, ctx: &Context<'_>) -> async_graphql::Result<ApprovalProcess> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let process = loader
+            .load_one(self.entity.approval_process_id)
+            .await?
+            .expect("process not found");
+        Ok(process)
+    }
+
+    async fn customer(&self, ctx: &Context<'_>) -> async_graphql::Result<Customer> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let customer = loader
+            .load_one(CustomerId::from(self.entity.deposit_account_holder_id))
+            .await?
+            .expect("customer not found");
+        Ok(customer)
+    }
+}
+
+#[derive(InputObject)]
+pub struct BeneficiaryCryptoAddressInput {
+    pub network: String,
+    pub address: String,
+}
+
+/// Exactly one of `bankAccount` and `cryptoAddress` must be provided.
+#[derive(InputObject)]
+pub struct BeneficiaryRegisterInput {
+    pub customer_id: UUID,
+    pub label: String,
+    pub bank_account: Option<PayoutDestinationInput>,
+    pub crypto_address: Option<BeneficiaryCryptoAddressInput>,
+}
+
+impl BeneficiaryRegisterInput {
+    pub fn details(&self) -> async_graphql::Result<DomainBeneficiaryDetails> {
+        match (&self.bank_account, &self.crypto_address) {
+            (Some(bank_account), None) => Ok(DomainBeneficiaryDetails::BankAccount(
+                bank_account.clone().into(),
+            )),
+            (None, Some(crypto_address)) => Ok(DomainBeneficiaryDetails::CryptoAddress {
+                network: crypto_address.network.clone(),
+                address: crypto_address.address.clone(),
+            }),
+            _ => Err("Provide either a bank account or a crypto address".into()),
+        }
+    }
+}
+crate::mutation_payload! { BeneficiaryRegisterPayload, beneficiary: Beneficiary }
+
+#[derive(InputObject)]
+pub struct BeneficiaryRevokeInput {
+    pub beneficiary_id: UUID,
+}
+crate::mutation_payload! { BeneficiaryRevokePayload, beneficiary: Beneficiary }
@@ -0,0 +1,65 @@
+use async_graphql::*;
+
+use crate::primitives::*;
+
+pub use lana_app::cash_flow_statement::ChartOfAccountsIntegrationConfig as DomainChartOfAccountsIntegrationConfig;
+
+#[derive(SimpleObject, Clone)]
+pub struct CashFlowStatementModuleConfig {
+    chart_of_accounts_id: Option<UUID>,
+    chart_of_accounts_cash_code: Option<String>,
+    chart_of_accounts_revenue_code: Option<String>,
+    chart_of_accounts_cost_of_revenue_code: Option<String>,
+    chart_of_accounts_expenses_code: Option<String>,
+    chart_of_accounts_operating_codes: Vec<String>,
+    chart_of_accounts_investing_codes: Vec<String>,
+    chart_of_accounts_financing_codes: Vec<String>,
+
+    #[graphql(skip)]
+    pub(super) _entity: Arc<DomainChartOfAccountsIntegrationConfig>,
+}
+
+impl From<DomainChartOfAccountsIntegrationConfig> for CashFlowStatementModuleConfig {
+    fn from(value: DomainChartOfAccountsIntegrationConfig) -> Self {
+        Self {
+            chart_of_accounts_id: Some(value.chart_of_accounts_id.into()),
+            chart_of_accounts_cash_code: Some(value.chart_of_accounts_cash_code.to_string()),
+            chart_of_accounts_revenue_code: Some(value.chart_of_accounts_revenue_code.to_string()),
+            chart_of_accounts_cost_of_revenue_code: Some(
+                value.chart_of_accounts_cost_of_revenue_code.to_string(),
+            ),
+            chart_of_accounts_expenses_code: Some(
+                value.chart_of_accounts_expenses_code.to_string(),
+            ),
+            chart_of_accounts_operating_codes: value
+                .chart_of_accounts_operating_codes
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            chart_of_accounts_investing_codes: value
+                .chart_of_accounts_investing_codes
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            chart_of_accounts_financing_codes: value
+                .chart_of_accounts_financing_codes
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+            _entity: Arc::new(value),
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct CashFlowStatementModuleConfigureInput {
+    pub chart_of_accounts_cash_code: String,
+    pub chart_of_accounts_revenue_code: String,
+    pub chart_of_accounts_cost_of_revenue_code: String,
+    pub chart_of_accounts_expenses_code: String,
+    pub chart_of_accounts_operating_codes: Vec<String>,
+    pub chart_of_accounts_investing_codes: Vec<String>,
+    pub chart_of_accounts_financing_codes: Vec<String>,
+}
+
+crate::mutation_payload! { CashFlowStatementModuleConfigurePayload, cash_flow_statement_config: CashFlowStatementModuleConfig }
@@ -2,7 +2,13 @@ use async_graphql::*;
 
 use crate::{graphql::accounting::AccountCode, primitives::*};
 
-use lana_app::accounting::Chart as DomainChart;
+use lana_app::accounting::{
+    chart_of_accounts::{
+        ChartImportDiff as DomainChartImportDiff, NodeParentChange as DomainNodeParentChange,
+        NodeRename as DomainNodeRename,
+    },
+    Chart as DomainChart,
+};
 
 #[derive(SimpleObject, Clone)]
 #[graphql(complex)]
@@ -43,6 +49,7 @@ impl ChartOfAccounts {
 pub struct ChartNode {
     name: String,
     account_code: AccountCode,
+    deactivated: bool,
     children: Vec<ChartNode>,
 }
 
@@ -51,6 +58,7 @@ impl From<lana_app::accounting::tree::TreeNode> for ChartNode {
         Self {
             name: node.name.to_string(),
             account_code: AccountCode::from(&node.code),
+            deactivated: node.deactivated,
             children: node.children.into_iter().map(ChartNode::from).collect(),
         }
     }
@@ -66,3 +74,110 @@ pub struct ChartOfAccountsCsvImportInput {
 pub struct ChartOfAccountsCsvImportPayload {
     pub success: bool,
 }
+
+#[derive(SimpleObject)]
+pub struct ChartOfAccountsCsvImportPreviewPayload {
+    pub diff: ChartOfAccountsImportDiff,
+}
+
+/// The changes a CSV import would make to the chart of accounts.
+#[derive(SimpleObject)]
+pub struct ChartOfAccountsImportDiff {
+    added: Vec<ChartOfAccountsAddedNode>,
+    renamed: Vec<ChartOfAccountsRenamedNode>,
+    moved: Vec<ChartOfAccountsMovedNode>,
+    deactivated: Vec<AccountCode>,
+}
+
+impl From<DomainChartImportDiff> for ChartOfAccountsImportDiff {
+    fn from(diff: DomainChartImportDiff) -> Self {
+        Self {
+            added: diff
+                .added
+                .into_iter()
+                .map(|spec| ChartOfAccountsAddedNode {
+                    account_code: AccountCode::from(&spec.code),
+                    name: spec.name.to_string(),
+                    parent_code: spec.parent.as_ref().map(AccountCode::from),
+                })
+                .collect(),
+            renamed: diff
+                .renamed
+                .into_iter()
+                .map(ChartOfAccountsRenamedNode::from)
+                .collect(),
+            moved: diff
+                .moved
+                .into_iter()
+                .map(ChartOfAccountsMovedNode::from)
+                .collect(),
+            deactivated: diff.deactivated.iter().map(AccountCode::from).collect(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ChartOfAccountsAddedNode {
+    account_code: AccountCode,
+    name: String,
+    parent_code: Option<AccountCode>,
+}
+
+#[derive(SimpleObject)]
+pub struct ChartOfAccountsRenamedNode {
+    account_code: AccountCode,
+    from: String,
+    to: String,
+}
+
+impl From<DomainNodeRename> for ChartOfAccountsRenamedNode {
+    fn from(rename: DomainNodeRename) -> Self {
+        Self {
+            account_code: AccountCode::from(&rename.code),
+            from: rename.from.to_string(),
+            to: rename.to.to_string(),
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ChartOfAccountsMovedNode {
+    account_code: AccountCode,
+    from_parent_code: Option<AccountCode>,
+    to_parent_code: Option<AccountCode>,
+}
+
+impl From<DomainNodeParentChange> for ChartOfAccountsMovedNode {
+    fn from(change: DomainNodeParentChange) -> Self {
+        Self {
+            account_code: AccountCode::from(&change.code),
+            from_parent_code: change.from.as_ref().map(AccountCode::from),
+            to_parent_code: change.to.as_ref().map(AccountCode::from),
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct ChartOfAccountsNodeRenameInput {
+    pub chart_id: UUID,
+    pub code: String,
+    pub name: String,
+}
+
+#[derive(InputObject)]
+pub struct ChartOfAccountsNodeMoveInput {
+    pub chart_id: UUID,
+    pub code: String,
+    /// Moves the node to the top level of the chart when not set.
+    pub parent_code: Option<String>,
+}
+
+#[derive(InputObject)]
+pub struct ChartOfAccountsNodeDeactivateInput {
+    pub chart_id: UUID,
+    pub code: String,
+}
+
+mutation_payload! { ChartOfAccountsNodeRenamePayload, chart_of_accounts: ChartOfAccounts }
+mutation_payload! { ChartOfAccountsNodeMovePayload, chart_of_accounts: ChartOfAccounts }
+mutation_payload! { ChartOfAccountsNodeDeactivatePayload, chart_of_accounts: ChartOfAccounts }
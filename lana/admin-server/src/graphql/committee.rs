@@ -2,7 +2,7 @@ use async_graphql::*;
 
 use crate::primitives::*;
 
-use super::{loader::LanaDataLoader, user::User};
+use super::{delegation::Delegation, loader::LanaDataLoader, user::User};
 
 pub use governance::{committee_cursor::CommitteesByCreatedAtCursor, Committee as DomainCommittee};
 
@@ -43,6 +43,24 @@ impl Committee {
 
         Ok(users)
     }
+
+    async fn delegations(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Delegation>> {
+        let (app, sub) = crate::app_and_sub_from_ctx!(ctx);
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let mut res = Vec::new();
+        for delegation in app
+            .governance()
+            .list_delegations_for_committee(sub, self.entity.id)
+            .await?
+        {
+            let delegation = Delegation::from(delegation);
+            loader
+                .feed_one(delegation.entity.id, delegation.clone())
+                .await;
+            res.push(delegation);
+        }
+        Ok(res)
+    }
 }
 
 #[derive(InputObject)]
@@ -0,0 +1,90 @@
+use async_graphql::*;
+
+pub use lana_app::config_change::{
+    ConfigChangeKind, ConfigChangeSet as DomainConfigChangeSet, ConfigChangeSetStatus,
+    ConfigDiffEntry as DomainConfigDiffEntry,
+};
+
+use crate::graphql::{approval_process::ApprovalProcess, loader::LanaDataLoader};
+use crate::primitives::*;
+
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct ConfigChangeSet {
+    id: ID,
+    config_change_set_id: UUID,
+    approval_process_id: UUID,
+    kind: ConfigChangeKind,
+    created_at: Timestamp,
+
+    #[graphql(skip)]
+    pub(super) entity: Arc<DomainConfigChangeSet>,
+}
+
+impl From<DomainConfigChangeSet> for ConfigChangeSet {
+    fn from(change_set: DomainConfigChangeSet) -> Self {
+        Self {
+            id: change_set.id.to_global_id(),
+            config_change_set_id: UUID::from(change_set.id),
+            approval_process_id: UUID::from(change_set.approval_process_id),
+            kind: change_set.kind(),
+            created_at: change_set.created_at().into(),
+            entity: Arc::new(change_set),
+        }
+    }
+}
+
+#[ComplexObject]
+impl ConfigChangeSet {
+    async fn status(&self) -> ConfigChangeSetStatus {
+        self.entity.status()
+    }
+
+    /// Why applying the approved change failed, if it did.
+    async fn failure_reason(&self) -> Option<&str> {
+        self.entity.failure_reason()
+    }
+
+    /// Values that differ between the configuration in place when the change
+    /// was proposed and the proposed configuration.
+    async fn diff(&self) -> Vec<ConfigChangeDiffEntry> {
+        self.entity
+            .diff()
+            .into_iter()
+            .map(ConfigChangeDiffEntry::from)
+            .collect()
+    }
+
+    async fn approval_process(&self, ctx: &Context<'_>) -> async_graphql::Result<ApprovalProcess> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let process = loader
+            .load_one(self.entity.approval_process_id)
+            .await?
+            .expect("process not found");
+        Ok(process)
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct ConfigChangeDiffEntry {
+    path: String,
+    old_value: Option<String>,
+    new_value: Option<String>,
+}
+
+impl From<DomainConfigDiffEntry> for ConfigChangeDiffEntry {
+    fn from(entry: DomainConfigDiffEntry) -> Self {
+        Self {
+            path: entry.path,
+            old_value: entry.old_value.map(display_value),
+            new_value: entry.new_value.map(display_value),
+        }
+    }
+}
+
+fn display_value(value: serde_json::Value) -> String {
+    match value {
+        serde_json::Value::String(s) => s,
+        other => other.to_string(),
+    }
+}
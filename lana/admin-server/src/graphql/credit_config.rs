@@ -2,6 +2,8 @@ use async_graphql::*;
 
 use crate::primitives::*;
 
+use super::config_change_set::ConfigChangeSet;
+
 pub use lana_app::credit::ChartOfAccountsIntegrationConfig as DomainChartOfAccountsIntegrationConfig;
 
 #[derive(SimpleObject, Clone)]
@@ -339,4 +341,22 @@ pub struct CreditModuleConfigureInput {
         String,
     pub chart_of_account_overdue_non_domiciled_company_disbursed_receivable_parent_code: String,
 }
-crate::mutation_payload! { CreditModuleConfigurePayload, credit_config: CreditModuleConfig }
+#[derive(SimpleObject)]
+pub struct CreditModuleConfigurePayload {
+    config_change_set: ConfigChangeSet,
+    /// The configuration in place once the change-set has been processed. Empty
+    /// while the change is still pending approval.
+    credit_config: Option<CreditModuleConfig>,
+}
+
+impl CreditModuleConfigurePayload {
+    pub fn new(
+        config_change_set: ConfigChangeSet,
+        credit_config: Option<CreditModuleConfig>,
+    ) -> Self {
+        Self {
+            config_change_set,
+            credit_config,
+        }
+    }
+}
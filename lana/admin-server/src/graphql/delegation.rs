@@ -0,0 +1,87 @@
+use async_graphql::*;
+
+use crate::primitives::*;
+
+use super::{committee::Committee, loader::LanaDataLoader, user::User};
+
+pub use governance::Delegation as DomainDelegation;
+
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct Delegation {
+    id: ID,
+    delegation_id: UUID,
+    starts_at: Timestamp,
+    ends_at: Timestamp,
+    created_at: Timestamp,
+
+    #[graphql(skip)]
+    pub(super) entity: Arc<DomainDelegation>,
+}
+
+impl From<DomainDelegation> for Delegation {
+    fn from(delegation: DomainDelegation) -> Self {
+        Self {
+            id: delegation.id.to_global_id(),
+            delegation_id: delegation.id.into(),
+            starts_at: delegation.starts_at.into(),
+            ends_at: delegation.ends_at.into(),
+            created_at: delegation.created_at().into(),
+            entity: Arc::new(delegation),
+        }
+    }
+}
+
+#[ComplexObject]
+impl Delegation {
+    async fn revoked(&self) -> bool {
+        self.entity.is_revoked()
+    }
+
+    async fn active(&self) -> bool {
+        self.entity.is_active_at(chrono::Utc::now())
+    }
+
+    async fn committee(&self, ctx: &Context<'_>) -> async_graphql::Result<Committee> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let committee = loader
+            .load_one(self.entity.committee_id)
+            .await?
+            .expect("committee not found");
+        Ok(committee)
+    }
+
+    async fn delegator(&self, ctx: &Context<'_>) -> async_graphql::Result<User> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let user = loader
+            .load_one(UserId::from(self.entity.delegator_id))
+            .await?
+            .expect("user not found");
+        Ok(user)
+    }
+
+    async fn delegate(&self, ctx: &Context<'_>) -> async_graphql::Result<User> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let user = loader
+            .load_one(UserId::from(self.entity.delegate_id))
+            .await?
+            .expect("user not found");
+        Ok(user)
+    }
+}
+
+#[derive(InputObject)]
+pub struct DelegationCreateInput {
+    pub committee_id: UUID,
+    pub delegator_id: UUID,
+    pub delegate_id: UUID,
+    pub starts_at: Timestamp,
+    pub ends_at: Timestamp,
+}
+crate::mutation_payload! { DelegationCreatePayload, delegation: Delegation }
+
+#[derive(InputObject)]
+pub struct DelegationRevokeInput {
+    pub delegation_id: UUID,
+}
+crate::mutation_payload! { DelegationRevokePayload, delegation: Delegation }
@@ -4,10 +4,13 @@ use crate::primitives::*;
 
 pub use lana_app::deposit::{
     DepositAccount as DomainDepositAccount, DepositAccountHistoryCursor,
-    DepositAccountHistoryEntry as DomainDepositAccountHistoryEntry,
+    DepositAccountHistoryEntry as DomainDepositAccountHistoryEntry, DepositAccountStatus,
 };
 
-use super::{customer::Customer, deposit::*, deposit_account_history::*, withdrawal::*};
+use super::{
+    beneficiary::Beneficiary, customer::Customer, deposit::*, deposit_account_history::*,
+    deposit_account_statement::*, withdrawal::*, withdrawal_limit_override::*,
+};
 
 #[derive(SimpleObject, Clone)]
 #[graphql(complex)]
@@ -15,6 +18,7 @@ pub struct DepositAccount {
     id: ID,
     deposit_account_id: UUID,
     customer_id: UUID,
+    status: DepositAccountStatus,
     created_at: Timestamp,
 
     #[graphql(skip)]
@@ -27,6 +31,7 @@ impl From<DomainDepositAccount> for DepositAccount {
             id: account.id.to_global_id(),
             deposit_account_id: account.id.into(),
             customer_id: account.account_holder_id.into(),
+            status: account.status,
             created_at: account.created_at().into(),
 
             entity: Arc::new(account),
@@ -51,6 +56,10 @@ impl From<lana_app::deposit::DepositAccountBalance> for DepositAccountBalance {
 
 #[ComplexObject]
 impl DepositAccount {
+    async fn dormant(&self) -> bool {
+        self.entity.is_dormant()
+    }
+
     async fn deposits(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Deposit>> {
         let (app, sub) = crate::app_and_sub_from_ctx!(ctx);
         let deposits = app
@@ -69,6 +78,21 @@ impl DepositAccount {
         Ok(withdrawals.into_iter().map(Withdrawal::from).collect())
     }
 
+    async fn statements(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<DepositAccountStatement>> {
+        let (app, sub) = crate::app_and_sub_from_ctx!(ctx);
+        let statements = app
+            .deposits()
+            .list_statements_for_account(sub, self.entity.id)
+            .await?;
+        Ok(statements
+            .into_iter()
+            .map(DepositAccountStatement::from)
+            .collect())
+    }
+
     async fn history(
         &self,
         ctx: &Context<'_>,
@@ -113,6 +137,41 @@ impl DepositAccount {
         .await
     }
 
+    async fn withdrawal_limits(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<WithdrawalLimits> {
+        let (app, _) = crate::app_and_sub_from_ctx!(ctx);
+        Ok(WithdrawalLimits::from(
+            app.deposits().withdrawal_limits_for_account(&self.entity),
+        ))
+    }
+
+    async fn withdrawal_limit_overrides(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<WithdrawalLimitOverride>> {
+        let (app, sub) = crate::app_and_sub_from_ctx!(ctx);
+        let overrides = app
+            .deposits()
+            .list_withdrawal_limit_overrides_for_account(sub, self.entity.id)
+            .await?;
+        Ok(overrides
+            .into_iter()
+            .map(WithdrawalLimitOverride::from)
+            .collect())
+    }
+
+    /// Beneficiaries of the account holder that withdrawals can be sent to.
+    async fn beneficiaries(&self, ctx: &Context<'_>) -> async_graphql::Result<Vec<Beneficiary>> {
+        let (app, sub) = crate::app_and_sub_from_ctx!(ctx);
+        let beneficiaries = app
+            .deposits()
+            .list_beneficiaries_for_account_holder(sub, self.entity.account_holder_id)
+            .await?;
+        Ok(beneficiaries.into_iter().map(Beneficiary::from).collect())
+    }
+
     async fn balance(&self, ctx: &Context<'_>) -> async_graphql::Result<DepositAccountBalance> {
         let (app, sub) = crate::app_and_sub_from_ctx!(ctx);
         let balance = app.deposits().account_balance(sub, self.entity.id).await?;
@@ -130,3 +189,11 @@ impl DepositAccount {
         Ok(Customer::from(customer))
     }
 }
+
+#[derive(InputObject)]
+pub struct DepositAccountCloseInput {
+    pub deposit_account_id: UUID,
+    /// Account receiving the remaining balance. The balance is paid out when omitted.
+    pub transfer_to_deposit_account_id: Option<UUID>,
+}
+crate::mutation_payload! { DepositAccountClosePayload, account: DepositAccount }
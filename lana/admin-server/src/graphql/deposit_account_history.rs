@@ -12,6 +12,7 @@ pub enum DepositAccountHistoryEntry {
     Deposit(DepositEntry),
     Withdrawal(WithdrawalEntry),
     CancelledWithdrawal(CancelledWithdrawalEntry),
+    ReturnedWithdrawal(ReturnedWithdrawalEntry),
     Disbursal(DisbursalEntry),
     Payment(PaymentEntry),
     Unknown(UnknownEntry),
@@ -40,6 +41,14 @@ pub struct CancelledWithdrawalEntry {
     pub recorded_at: Timestamp,
 }
 
+#[derive(SimpleObject)]
+#[graphql(complex)]
+pub struct ReturnedWithdrawalEntry {
+    #[graphql(skip)]
+    pub tx_id: UUID,
+    pub recorded_at: Timestamp,
+}
+
 #[derive(SimpleObject)]
 #[graphql(complex)]
 pub struct DisbursalEntry {
@@ -106,6 +115,20 @@ impl CancelledWithdrawalEntry {
     }
 }
 
+#[ComplexObject]
+impl ReturnedWithdrawalEntry {
+    async fn withdrawal(&self, ctx: &Context<'_>) -> async_graphql::Result<Withdrawal> {
+        let (app, sub) = crate::app_and_sub_from_ctx!(ctx);
+
+        let withdrawal = app
+            .deposits()
+            .find_withdrawal_by_returned_tx_id(sub, self.tx_id)
+            .await?;
+
+        Ok(Withdrawal::from(withdrawal))
+    }
+}
+
 #[ComplexObject]
 impl DisbursalEntry {
     async fn disbursal(&self, ctx: &Context<'_>) -> async_graphql::Result<CreditFacilityDisbursal> {
@@ -152,6 +175,12 @@ impl From<lana_app::deposit::DepositAccountHistoryEntry> for DepositAccountHisto
                     recorded_at: entry.recorded_at.into(),
                 })
             }
+            lana_app::deposit::DepositAccountHistoryEntry::ReturnedWithdrawal(entry) => {
+                Self::ReturnedWithdrawal(ReturnedWithdrawalEntry {
+                    tx_id: UUID::from(entry.tx_id),
+                    recorded_at: entry.recorded_at.into(),
+                })
+            }
             lana_app::deposit::DepositAccountHistoryEntry::Disbursal(entry) => {
                 Self::Disbursal(DisbursalEntry {
                     tx_id: UUID::from(entry.tx_id),
@@ -0,0 +1,97 @@
+use async_graphql::*;
+
+use crate::primitives::*;
+
+pub use lana_app::deposit::{
+    DepositAccountStatement as DomainDepositAccountStatement, DepositAccountStatementFormat,
+    DepositAccountStatementStatus,
+};
+
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct DepositAccountStatement {
+    id: ID,
+    deposit_account_statement_id: UUID,
+    deposit_account_id: UUID,
+    period_start: Date,
+    period_end: Date,
+    status: DepositAccountStatementStatus,
+    created_at: Timestamp,
+
+    #[graphql(skip)]
+    pub(super) entity: Arc<DomainDepositAccountStatement>,
+}
+
+impl From<DomainDepositAccountStatement> for DepositAccountStatement {
+    fn from(statement: DomainDepositAccountStatement) -> Self {
+        Self {
+            id: statement.id.to_global_id(),
+            deposit_account_statement_id: UUID::from(statement.id),
+            deposit_account_id: UUID::from(statement.deposit_account_id),
+            period_start: statement.period_start.into(),
+            period_end: statement.period_end.into(),
+            status: statement.status(),
+            created_at: statement.created_at().into(),
+
+            entity: Arc::new(statement),
+        }
+    }
+}
+
+#[ComplexObject]
+impl DepositAccountStatement {
+    async fn summary(&self) -> Option<DepositAccountStatementSummary> {
+        self.entity
+            .summary()
+            .map(DepositAccountStatementSummary::from)
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct DepositAccountStatementSummary {
+    opening_balance: UsdCents,
+    closing_balance: UsdCents,
+    total_credits: UsdCents,
+    total_debits: UsdCents,
+    interest: UsdCents,
+    fees: UsdCents,
+}
+
+impl From<lana_app::deposit::DepositAccountStatementSummary> for DepositAccountStatementSummary {
+    fn from(summary: lana_app::deposit::DepositAccountStatementSummary) -> Self {
+        Self {
+            opening_balance: summary.opening_balance,
+            closing_balance: summary.closing_balance,
+            total_credits: summary.total_credits,
+            total_debits: summary.total_debits,
+            interest: summary.interest,
+            fees: summary.fees,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct DepositAccountStatementDownloadLink {
+    pub deposit_account_statement_id: UUID,
+    pub format: DepositAccountStatementFormat,
+    pub url: String,
+}
+
+impl From<lana_app::deposit::DepositAccountStatementDownloadLink>
+    for DepositAccountStatementDownloadLink
+{
+    fn from(link: lana_app::deposit::DepositAccountStatementDownloadLink) -> Self {
+        Self {
+            deposit_account_statement_id: UUID::from(link.deposit_account_statement_id),
+            format: link.format,
+            url: link.url,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct DepositAccountStatementDownloadLinkGenerateInput {
+    pub deposit_account_statement_id: UUID,
+    pub format: DepositAccountStatementFormat,
+}
+crate::mutation_payload! { DepositAccountStatementDownloadLinkGeneratePayload, link: DepositAccountStatementDownloadLink }
@@ -2,6 +2,8 @@ use async_graphql::*;
 
 use crate::primitives::*;
 
+use super::config_change_set::ConfigChangeSet;
+
 pub use lana_app::deposit::ChartOfAccountsIntegrationConfig as DomainChartOfAccountsIntegrationConfig;
 
 #[derive(SimpleObject, Clone)]
@@ -72,4 +74,22 @@ pub struct DepositModuleConfigureInput {
     pub chart_of_account_financial_institution_deposit_accounts_parent_code: String,
     pub chart_of_account_non_domiciled_individual_deposit_accounts_parent_code: String,
 }
-crate::mutation_payload! { DepositModuleConfigurePayload, deposit_config: DepositModuleConfig }
+#[derive(SimpleObject)]
+pub struct DepositModuleConfigurePayload {
+    config_change_set: ConfigChangeSet,
+    /// The configuration in place once the change-set has been processed. Empty
+    /// while the change is still pending approval.
+    deposit_config: Option<DepositModuleConfig>,
+}
+
+impl DepositModuleConfigurePayload {
+    pub fn new(
+        config_change_set: ConfigChangeSet,
+        deposit_config: Option<DepositModuleConfig>,
+    ) -> Self {
+        Self {
+            config_change_set,
+            deposit_config,
+        }
+    }
+}
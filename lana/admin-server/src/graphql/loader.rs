@@ -4,22 +4,28 @@ use std::collections::HashMap;
 
 use lana_app::{
     accounting::{
+        accounting_period::error::AccountingPeriodError,
         chart_of_accounts::error::ChartOfAccountsError, csv::error::AccountingCsvError,
         ledger_transaction::error::LedgerTransactionError,
+        manual_transaction::error::ManualTransactionError,
+        recurring_transaction::error::RecurringTransactionError,
         transaction_templates::error::TransactionTemplateError, AccountingCsvId, Chart,
         LedgerAccountId, TransactionTemplateId,
     },
     app::LanaApp,
+    config_change::error::ConfigChangeSetError,
     deposit::error::CoreDepositError,
+    trial_balance::error::TrialBalanceError,
     user::error::UserError,
 };
 
 use crate::primitives::*;
 
 use super::{
-    accounting::*, approval_process::*, chart_of_accounts::*, committee::*, credit_facility::*,
-    customer::*, deposit::*, deposit_account::*, document::*, policy::*, terms_template::*,
-    user::*, withdrawal::*,
+    accounting::*, approval_process::*, bank_statement_import::*, beneficiary::*,
+    chart_of_accounts::*, committee::*, config_change_set::*, credit_facility::*, customer::*,
+    delegation::*, deposit::*, deposit_account::*, document::*, payout_batch::*, policy::*,
+    terms_template::*, user::*, withdrawal::*, withdrawal_limit_override::*,
 };
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -63,6 +69,22 @@ impl Loader<governance::CommitteeId> for LanaLoader {
     }
 }
 
+impl Loader<governance::DelegationId> for LanaLoader {
+    type Value = Delegation;
+    type Error = Arc<governance::error::GovernanceError>;
+
+    async fn load(
+        &self,
+        keys: &[DelegationId],
+    ) -> Result<HashMap<DelegationId, Delegation>, Self::Error> {
+        self.app
+            .governance()
+            .find_all_delegations(keys)
+            .await
+            .map_err(Arc::new)
+    }
+}
+
 impl Loader<governance::PolicyId> for LanaLoader {
     type Value = Policy;
     type Error = Arc<governance::error::GovernanceError>;
@@ -184,6 +206,86 @@ impl Loader<DepositId> for LanaLoader {
     }
 }
 
+impl Loader<WithdrawalLimitOverrideId> for LanaLoader {
+    type Value = WithdrawalLimitOverride;
+    type Error = Arc<CoreDepositError>;
+
+    async fn load(
+        &self,
+        keys: &[WithdrawalLimitOverrideId],
+    ) -> Result<HashMap<WithdrawalLimitOverrideId, WithdrawalLimitOverride>, Self::Error> {
+        self.app
+            .deposits()
+            .find_all_withdrawal_limit_overrides(keys)
+            .await
+            .map_err(Arc::new)
+    }
+}
+
+impl Loader<BeneficiaryId> for LanaLoader {
+    type Value = Beneficiary;
+    type Error = Arc<CoreDepositError>;
+
+    async fn load(
+        &self,
+        keys: &[BeneficiaryId],
+    ) -> Result<HashMap<BeneficiaryId, Beneficiary>, Self::Error> {
+        self.app
+            .deposits()
+            .find_all_beneficiaries(keys)
+            .await
+            .map_err(Arc::new)
+    }
+}
+
+impl Loader<BankStatementImportId> for LanaLoader {
+    type Value = BankStatementImport;
+    type Error = Arc<CoreDepositError>;
+
+    async fn load(
+        &self,
+        keys: &[BankStatementImportId],
+    ) -> Result<HashMap<BankStatementImportId, BankStatementImport>, Self::Error> {
+        self.app
+            .deposits()
+            .find_all_bank_statement_imports(keys)
+            .await
+            .map_err(Arc::new)
+    }
+}
+
+impl Loader<BankStatementExceptionId> for LanaLoader {
+    type Value = BankStatementException;
+    type Error = Arc<CoreDepositError>;
+
+    async fn load(
+        &self,
+        keys: &[BankStatementExceptionId],
+    ) -> Result<HashMap<BankStatementExceptionId, BankStatementException>, Self::Error> {
+        self.app
+            .deposits()
+            .find_all_bank_statement_exceptions(keys)
+            .await
+            .map_err(Arc::new)
+    }
+}
+
+impl Loader<PayoutBatchId> for LanaLoader {
+    type Value = PayoutBatch;
+    type Error = Arc<CoreDepositError>;
+
+    async fn load(
+        &self,
+        keys: &[PayoutBatchId],
+    ) -> Result<HashMap<PayoutBatchId, PayoutBatch>, Self::Error> {
+        self.app
+            .deposits()
+            .find_all_payout_batches(keys)
+            .await
+            .map_err(Arc::new)
+    }
+}
+
 impl Loader<DepositAccountId> for LanaLoader {
     type Value = DepositAccount;
     type Error = Arc<CoreDepositError>;
@@ -217,6 +319,90 @@ impl Loader<LedgerTransactionId> for LanaLoader {
     }
 }
 
+impl Loader<ManualTransactionId> for LanaLoader {
+    type Value = ManualTransaction;
+    type Error = Arc<ManualTransactionError>;
+
+    async fn load(
+        &self,
+        keys: &[ManualTransactionId],
+    ) -> Result<HashMap<ManualTransactionId, Self::Value>, Self::Error> {
+        self.app
+            .accounting()
+            .manual_transactions()
+            .find_all(keys)
+            .await
+            .map_err(Arc::new)
+    }
+}
+
+impl Loader<AccountingPeriodId> for LanaLoader {
+    type Value = AccountingPeriod;
+    type Error = Arc<AccountingPeriodError>;
+
+    async fn load(
+        &self,
+        keys: &[AccountingPeriodId],
+    ) -> Result<HashMap<AccountingPeriodId, Self::Value>, Self::Error> {
+        self.app
+            .accounting()
+            .accounting_periods()
+            .find_all(keys)
+            .await
+            .map_err(Arc::new)
+    }
+}
+
+impl Loader<RecurringTransactionId> for LanaLoader {
+    type Value = RecurringTransaction;
+    type Error = Arc<RecurringTransactionError>;
+
+    async fn load(
+        &self,
+        keys: &[RecurringTransactionId],
+    ) -> Result<HashMap<RecurringTransactionId, Self::Value>, Self::Error> {
+        self.app
+            .accounting()
+            .recurring_transactions()
+            .find_all(keys)
+            .await
+            .map_err(Arc::new)
+    }
+}
+
+impl Loader<TrialBalanceSnapshotId> for LanaLoader {
+    type Value = TrialBalanceSnapshot;
+    type Error = Arc<TrialBalanceError>;
+
+    async fn load(
+        &self,
+        keys: &[TrialBalanceSnapshotId],
+    ) -> Result<HashMap<TrialBalanceSnapshotId, Self::Value>, Self::Error> {
+        self.app
+            .accounting()
+            .trial_balances()
+            .find_all_snapshots(keys)
+            .await
+            .map_err(Arc::new)
+    }
+}
+
+impl Loader<ConfigChangeSetId> for LanaLoader {
+    type Value = ConfigChangeSet;
+    type Error = Arc<ConfigChangeSetError>;
+
+    async fn load(
+        &self,
+        keys: &[ConfigChangeSetId],
+    ) -> Result<HashMap<ConfigChangeSetId, Self::Value>, Self::Error> {
+        self.app
+            .config_change_sets()
+            .find_all(keys)
+            .await
+            .map_err(Arc::new)
+    }
+}
+
 impl Loader<TransactionTemplateId> for LanaLoader {
     type Value = TransactionTemplate;
     type Error = Arc<TransactionTemplateError>;
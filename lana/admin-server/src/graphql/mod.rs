@@ -4,19 +4,26 @@ mod approval_rules;
 mod audit;
 mod authenticated_subject;
 mod balance_sheet_config;
+mod bank_statement_import;
+mod beneficiary;
+mod cash_flow_statement_config;
 mod chart_of_accounts;
 mod committee;
+mod config_change_set;
 mod credit_config;
 mod credit_facility;
 mod customer;
 mod dashboard;
+mod delegation;
 mod deposit;
 mod deposit_account;
 mod deposit_account_history;
+mod deposit_account_statement;
 mod deposit_config;
 mod document;
 mod general_ledger;
 mod loader;
+mod payout_batch;
 mod price;
 mod primitives;
 mod profit_and_loss_config;
@@ -25,6 +32,7 @@ mod sumsub;
 mod terms;
 mod terms_template;
 mod withdrawal;
+mod withdrawal_limit_override;
 #[macro_use]
 pub mod macros;
 mod policy;
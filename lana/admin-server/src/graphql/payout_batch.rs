@@ -0,0 +1,155 @@
+use async_graphql::*;
+
+use crate::primitives::*;
+
+use super::{loader::LanaDataLoader, withdrawal::Withdrawal};
+
+pub use lana_app::deposit::{
+    PayoutAccountType, PayoutBatch as DomainPayoutBatch, PayoutBatchStatus,
+    PayoutBatchesByCreatedAtCursor, PayoutFileFormat,
+};
+
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct PayoutBatch {
+    id: ID,
+    payout_batch_id: UUID,
+    format: PayoutFileFormat,
+    created_at: Timestamp,
+
+    #[graphql(skip)]
+    pub(super) entity: Arc<DomainPayoutBatch>,
+}
+
+impl From<DomainPayoutBatch> for PayoutBatch {
+    fn from(batch: DomainPayoutBatch) -> Self {
+        PayoutBatch {
+            id: batch.id.to_global_id(),
+            payout_batch_id: UUID::from(batch.id),
+            format: batch.format,
+            created_at: batch.created_at().into(),
+
+            entity: Arc::new(batch),
+        }
+    }
+}
+
+#[ComplexObject]
+impl PayoutBatch {
+    async fn status(&self) -> PayoutBatchStatus {
+        self.entity.status()
+    }
+
+    async fn sent_at(&self) -> Option<Timestamp> {
+        self.entity.sent_at().map(Into::into)
+    }
+
+    async fn total_amount(&self) -> UsdCents {
+        self.entity.total_amount()
+    }
+
+    async fn items(&self) -> Vec<PayoutBatchItem> {
+        self.entity
+            .items
+            .iter()
+            .cloned()
+            .map(PayoutBatchItem::from)
+            .collect()
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct PayoutBatchItem {
+    withdrawal_id: UUID,
+    deposit_account_id: UUID,
+    amount: UsdCents,
+    reference: String,
+    account_holder_name: String,
+    routing_number: String,
+    account_number: String,
+    account_type: PayoutAccountType,
+
+    #[graphql(skip)]
+    domain_withdrawal_id: WithdrawalId,
+}
+
+impl From<lana_app::deposit::PayoutBatchItem> for PayoutBatchItem {
+    fn from(item: lana_app::deposit::PayoutBatchItem) -> Self {
+        PayoutBatchItem {
+            withdrawal_id: UUID::from(item.withdrawal_id),
+            deposit_account_id: UUID::from(item.deposit_account_id),
+            amount: item.amount,
+            reference: item.reference,
+            account_holder_name: item.destination.account_holder_name,
+            routing_number: item.destination.routing_number,
+            account_number: item.destination.account_number,
+            account_type: item.destination.account_type,
+            domain_withdrawal_id: item.withdrawal_id,
+        }
+    }
+}
+
+#[ComplexObject]
+impl PayoutBatchItem {
+    async fn withdrawal(&self, ctx: &Context<'_>) -> async_graphql::Result<Withdrawal> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let withdrawal = loader
+            .load_one(self.domain_withdrawal_id)
+            .await?
+            .expect("withdrawal not found");
+        Ok(withdrawal)
+    }
+}
+
+#[derive(InputObject, Clone)]
+pub struct PayoutDestinationInput {
+    pub account_holder_name: String,
+    pub routing_number: String,
+    pub account_number: String,
+    pub account_type: PayoutAccountType,
+}
+
+impl From<PayoutDestinationInput> for lana_app::deposit::PayoutDestination {
+    fn from(input: PayoutDestinationInput) -> Self {
+        Self {
+            account_holder_name: input.account_holder_name,
+            routing_number: input.routing_number,
+            account_number: input.account_number,
+            account_type: input.account_type,
+        }
+    }
+}
+
+#[derive(SimpleObject)]
+pub struct PayoutBatchDownloadLink {
+    pub payout_batch_id: UUID,
+    pub url: String,
+}
+
+impl From<lana_app::deposit::PayoutBatchDownloadLink> for PayoutBatchDownloadLink {
+    fn from(link: lana_app::deposit::PayoutBatchDownloadLink) -> Self {
+        Self {
+            payout_batch_id: UUID::from(link.payout_batch_id),
+            url: link.url,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct PayoutBatchCreateInput {
+    pub format: PayoutFileFormat,
+}
+crate::mutation_payload! { PayoutBatchCreatePayload, payout_batch: PayoutBatch }
+
+#[derive(InputObject)]
+pub struct PayoutBatchMarkSentInput {
+    pub payout_batch_id: UUID,
+}
+crate::mutation_payload! { PayoutBatchMarkSentPayload, payout_batch: PayoutBatch }
+
+#[derive(InputObject)]
+pub struct PayoutBatchDownloadLinkGenerateInput {
+    pub payout_batch_id: UUID,
+}
+crate::mutation_payload! { PayoutBatchDownloadLinkGeneratePayload, link: PayoutBatchDownloadLink }
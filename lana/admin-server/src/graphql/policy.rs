@@ -2,7 +2,7 @@ use async_graphql::*;
 
 use crate::primitives::*;
 
-use super::{approval_process::*, approval_rules::*};
+use super::{approval_process::*, approval_rules::*, config_change_set::ConfigChangeSet};
 
 pub use governance::{policy_cursor::PoliciesByCreatedAtCursor, Policy as DomainPolicy};
 
@@ -31,7 +31,15 @@ impl From<DomainPolicy> for Policy {
 #[ComplexObject]
 impl Policy {
     async fn rules(&self) -> ApprovalRules {
-        ApprovalRules::from(self.entity.rules)
+        ApprovalRules::from(self.entity.rules.clone())
+    }
+
+    async fn tiers(&self) -> Vec<ApprovalTier> {
+        self.entity.tiers.iter().map(ApprovalTier::from).collect()
+    }
+
+    async fn expiry(&self) -> Option<ApprovalExpiry> {
+        self.entity.expiry.map(ApprovalExpiry::from)
     }
 }
 
@@ -42,4 +50,170 @@ pub struct PolicyAssignCommitteeInput {
     pub threshold: usize,
 }
 
-mutation_payload! { PolicyAssignCommitteePayload, policy: Policy }
+#[derive(SimpleObject)]
+pub struct PolicyAssignCommitteePayload {
+    config_change_set: ConfigChangeSet,
+    /// The policy as it stands once the change-set has been processed.
+    policy: Policy,
+}
+
+impl PolicyAssignCommitteePayload {
+    pub fn new(config_change_set: ConfigChangeSet, policy: Policy) -> Self {
+        Self {
+            config_change_set,
+            policy,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct ApprovalStageInput {
+    pub committee_id: UUID,
+    pub threshold: usize,
+}
+
+impl From<ApprovalStageInput> for governance::ApprovalStage {
+    fn from(input: ApprovalStageInput) -> Self {
+        Self {
+            committee_id: input.committee_id.into(),
+            threshold: input.threshold,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct PolicyApprovalChainAssignInput {
+    pub policy_id: UUID,
+    pub stages: Vec<ApprovalStageInput>,
+}
+
+mutation_payload! { PolicyApprovalChainAssignPayload, policy: Policy }
+
+#[derive(InputObject)]
+pub struct ApprovalExpressionInput {
+    pub kind: ApprovalExpressionKind,
+    /// Required for `ALL_OF` and `ANY_OF`.
+    pub rules: Option<Vec<ApprovalExpressionInput>>,
+    /// Required for `COMMITTEE_THRESHOLD`.
+    pub committee_id: Option<UUID>,
+    pub threshold: Option<usize>,
+    /// Required for `MEMBER_REQUIRED`.
+    pub member_id: Option<UUID>,
+}
+
+impl TryFrom<ApprovalExpressionInput> for governance::ApprovalExpression {
+    type Error = Box<dyn std::error::Error + Sync + Send>;
+
+    fn try_from(input: ApprovalExpressionInput) -> Result<Self, Self::Error> {
+        let rules = || -> Result<Vec<Self>, Self::Error> {
+            input
+                .rules
+                .ok_or("rules are required to combine expressions")?
+                .into_iter()
+                .map(TryInto::try_into)
+                .collect()
+        };
+        let res = match input.kind {
+            ApprovalExpressionKind::AllOf => Self::AllOf { rules: rules()? },
+            ApprovalExpressionKind::AnyOf => Self::AnyOf { rules: rules()? },
+            ApprovalExpressionKind::CommitteeThreshold => Self::CommitteeThreshold {
+                committee_id: input
+                    .committee_id
+                    .ok_or("committeeId is required for a committee threshold")?
+                    .into(),
+                threshold: input.threshold.unwrap_or(1),
+            },
+            ApprovalExpressionKind::MemberRequired => Self::MemberRequired {
+                member_id: input
+                    .member_id
+                    .ok_or("memberId is required for a required member")?
+                    .into(),
+            },
+            ApprovalExpressionKind::Weekday => Self::Weekday,
+        };
+        Ok(res)
+    }
+}
+
+#[derive(InputObject)]
+pub struct PolicyApprovalExpressionAssignInput {
+    pub policy_id: UUID,
+    pub expression: ApprovalExpressionInput,
+}
+
+mutation_payload! { PolicyApprovalExpressionAssignPayload, policy: Policy }
+
+#[derive(InputObject)]
+pub struct ApprovalTierInput {
+    pub min_amount: UsdCents,
+    /// Leave empty to approve automatically within this tier.
+    pub committee_id: Option<UUID>,
+    pub threshold: Option<usize>,
+}
+
+impl From<ApprovalTierInput> for governance::ApprovalTier {
+    fn from(input: ApprovalTierInput) -> Self {
+        let rules = match input.committee_id {
+            Some(committee_id) => governance::ApprovalRules::CommitteeThreshold {
+                committee_id: committee_id.into(),
+                threshold: input.threshold.unwrap_or(1),
+            },
+            None => governance::ApprovalRules::SystemAutoApprove,
+        };
+        Self {
+            min_amount: input.min_amount,
+            rules,
+        }
+    }
+}
+
+#[derive(InputObject)]
+pub struct PolicyTiersUpdateInput {
+    pub policy_id: UUID,
+    pub tiers: Vec<ApprovalTierInput>,
+}
+
+mutation_payload! { PolicyTiersUpdatePayload, policy: Policy }
+
+#[derive(InputObject)]
+pub struct ApprovalExpiryInput {
+    pub ttl_hours: u32,
+    pub action: ApprovalTimeoutActionType,
+    /// Required when escalating.
+    pub fallback_committee_id: Option<UUID>,
+    pub fallback_threshold: Option<usize>,
+}
+
+impl TryFrom<ApprovalExpiryInput> for governance::ApprovalExpiry {
+    type Error = Box<dyn std::error::Error + Sync + Send>;
+
+    fn try_from(input: ApprovalExpiryInput) -> Result<Self, Self::Error> {
+        let action = match input.action {
+            ApprovalTimeoutActionType::Deny => governance::ApprovalTimeoutAction::Deny,
+            ApprovalTimeoutActionType::Escalate => {
+                let committee_id = input
+                    .fallback_committee_id
+                    .ok_or("fallbackCommitteeId is required to escalate")?;
+                governance::ApprovalTimeoutAction::Escalate {
+                    fallback: governance::ApprovalStage {
+                        committee_id: committee_id.into(),
+                        threshold: input.fallback_threshold.unwrap_or(1),
+                    },
+                }
+            }
+        };
+        Ok(Self {
+            ttl_hours: input.ttl_hours,
+            action,
+        })
+    }
+}
+
+#[derive(InputObject)]
+pub struct PolicyExpiryUpdateInput {
+    pub policy_id: UUID,
+    /// Leave empty to let processes stay open until decided.
+    pub expiry: Option<ApprovalExpiryInput>,
+}
+
+mutation_payload! { PolicyExpiryUpdatePayload, policy: Policy }
@@ -3,21 +3,25 @@ use async_graphql::{types::connection::*, Context, Object};
 use std::io::Read;
 
 use lana_app::{
-    accounting::csv::AccountingCsvsByCreatedAtCursor,
+    accounting::csv::{AccountingCsvType, AccountingCsvsByCreatedAtCursor},
     accounting_init::constants::{
-        BALANCE_SHEET_NAME, PROFIT_AND_LOSS_STATEMENT_NAME, TRIAL_BALANCE_STATEMENT_NAME,
+        BALANCE_SHEET_NAME, CASH_FLOW_STATEMENT_NAME, PROFIT_AND_LOSS_STATEMENT_NAME,
+        TRIAL_BALANCE_STATEMENT_NAME,
     },
     app::LanaApp,
+    config_change::ConfigChange,
 };
 
 use crate::primitives::*;
 
 use super::{
     accounting::*, approval_process::*, audit::*, authenticated_subject::*,
-    balance_sheet_config::*, chart_of_accounts::*, committee::*, credit_config::*,
-    credit_facility::*, customer::*, dashboard::*, deposit::*, deposit_config::*, document::*,
-    general_ledger::*, loader::*, policy::*, price::*, profit_and_loss_config::*, report::*,
-    sumsub::*, terms_template::*, user::*, withdrawal::*,
+    balance_sheet_config::*, bank_statement_import::*, beneficiary::*,
+    cash_flow_statement_config::*, chart_of_accounts::*, committee::*, config_change_set::*,
+    credit_config::*, credit_facility::*, customer::*, dashboard::*, delegation::*, deposit::*,
+    deposit_account::*, deposit_account_statement::*, deposit_config::*, document::*,
+    general_ledger::*, loader::*, payout_batch::*, policy::*, price::*, profit_and_loss_config::*,
+    report::*, sumsub::*, terms_template::*, user::*, withdrawal::*, withdrawal_limit_override::*,
 };
 
 pub struct Query;
@@ -168,6 +172,102 @@ impl Query {
         )
     }
 
+    async fn bank_statement_import(
+        &self,
+        ctx: &Context<'_>,
+        id: UUID,
+    ) -> async_graphql::Result<Option<BankStatementImport>> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        maybe_fetch_one!(
+            BankStatementImport,
+            ctx,
+            app.deposits().find_bank_statement_import_by_id(sub, id)
+        )
+    }
+
+    async fn bank_statement_imports(
+        &self,
+        ctx: &Context<'_>,
+        first: i32,
+        after: Option<String>,
+    ) -> async_graphql::Result<
+        Connection<
+            BankStatementImportsByCreatedAtCursor,
+            BankStatementImport,
+            EmptyFields,
+            EmptyFields,
+        >,
+    > {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        list_with_cursor!(
+            BankStatementImportsByCreatedAtCursor,
+            BankStatementImport,
+            ctx,
+            after,
+            first,
+            |query| app.deposits().list_bank_statement_imports(sub, query)
+        )
+    }
+
+    async fn bank_statement_exceptions(
+        &self,
+        ctx: &Context<'_>,
+        status: BankStatementExceptionStatus,
+        first: i32,
+        after: Option<String>,
+    ) -> async_graphql::Result<
+        Connection<
+            BankStatementExceptionsByCreatedAtCursor,
+            BankStatementException,
+            EmptyFields,
+            EmptyFields,
+        >,
+    > {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        list_with_cursor!(
+            BankStatementExceptionsByCreatedAtCursor,
+            BankStatementException,
+            ctx,
+            after,
+            first,
+            |query| app
+                .deposits()
+                .list_bank_statement_exceptions(sub, status, query)
+        )
+    }
+
+    async fn payout_batch(
+        &self,
+        ctx: &Context<'_>,
+        id: UUID,
+    ) -> async_graphql::Result<Option<PayoutBatch>> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        maybe_fetch_one!(
+            PayoutBatch,
+            ctx,
+            app.deposits().find_payout_batch_by_id(sub, id)
+        )
+    }
+
+    async fn payout_batches(
+        &self,
+        ctx: &Context<'_>,
+        first: i32,
+        after: Option<String>,
+    ) -> async_graphql::Result<
+        Connection<PayoutBatchesByCreatedAtCursor, PayoutBatch, EmptyFields, EmptyFields>,
+    > {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        list_with_cursor!(
+            PayoutBatchesByCreatedAtCursor,
+            PayoutBatch,
+            ctx,
+            after,
+            first,
+            |query| app.deposits().list_payout_batches(sub, query)
+        )
+    }
+
     async fn terms_template(
         &self,
         ctx: &Context<'_>,
@@ -361,6 +461,19 @@ impl Query {
         )
     }
 
+    async fn config_change_set(
+        &self,
+        ctx: &Context<'_>,
+        id: UUID,
+    ) -> async_graphql::Result<Option<ConfigChangeSet>> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        maybe_fetch_one!(
+            ConfigChangeSet,
+            ctx,
+            app.config_change_sets().find_by_id(sub, id)
+        )
+    }
+
     async fn approval_processes(
         &self,
         ctx: &Context<'_>,
@@ -465,6 +578,89 @@ impl Query {
         )
     }
 
+    async fn manual_transaction(
+        &self,
+        ctx: &Context<'_>,
+        id: UUID,
+    ) -> async_graphql::Result<Option<ManualTransaction>> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        maybe_fetch_one!(
+            ManualTransaction,
+            ctx,
+            app.accounting()
+                .manual_transactions()
+                .find_manual_transaction_by_id(sub, id)
+        )
+    }
+
+    async fn recurring_transaction(
+        &self,
+        ctx: &Context<'_>,
+        id: UUID,
+    ) -> async_graphql::Result<Option<RecurringTransaction>> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        maybe_fetch_one!(
+            RecurringTransaction,
+            ctx,
+            app.accounting()
+                .recurring_transactions()
+                .find_by_id(sub, id)
+        )
+    }
+
+    async fn recurring_transactions(
+        &self,
+        ctx: &Context<'_>,
+        first: i32,
+        after: Option<String>,
+    ) -> async_graphql::Result<
+        Connection<
+            RecurringTransactionsByCreatedAtCursor,
+            RecurringTransaction,
+            EmptyFields,
+            EmptyFields,
+        >,
+    > {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        list_with_cursor!(
+            RecurringTransactionsByCreatedAtCursor,
+            RecurringTransaction,
+            ctx,
+            after,
+            first,
+            |query| app.accounting().recurring_transactions().list(sub, query)
+        )
+    }
+
+    async fn accounting_period(
+        &self,
+        ctx: &Context<'_>,
+        id: UUID,
+    ) -> async_graphql::Result<Option<AccountingPeriod>> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        maybe_fetch_one!(
+            AccountingPeriod,
+            ctx,
+            app.accounting().accounting_periods().find_by_id(sub, id)
+        )
+    }
+
+    async fn accounting_periods(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<AccountingPeriod>> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let periods = app.accounting().accounting_periods().list(sub).await?;
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let mut res = Vec::with_capacity(periods.len());
+        for period in periods {
+            let period = AccountingPeriod::from(period);
+            loader.feed_one(period.entity.id, period.clone()).await;
+            res.push(period);
+        }
+        Ok(res)
+    }
+
     async fn ledger_transactions_for_template_code(
         &self,
         ctx: &Context<'_>,
@@ -520,6 +716,45 @@ impl Query {
         .await
     }
 
+    async fn journal_entry_search(
+        &self,
+        ctx: &Context<'_>,
+        first: i32,
+        after: Option<String>,
+        filter: JournalEntryFilterInput,
+    ) -> async_graphql::Result<Connection<JournalEntryCursor, JournalEntry, EmptyFields, EmptyFields>>
+    {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let filter = filter.into_filter(app).await?;
+        let filter = &filter;
+
+        query(
+            after,
+            None,
+            Some(first),
+            None,
+            |after, _, first, _| async move {
+                let first = first.expect("First always exists");
+                let query_args = es_entity::PaginatedQueryArgs { first, after };
+                let res = app
+                    .accounting()
+                    .journal()
+                    .search_entries(sub, filter, query_args)
+                    .await?;
+
+                let mut connection = Connection::new(false, res.has_next_page);
+                connection
+                    .edges
+                    .extend(res.entities.into_iter().map(|entry| {
+                        let cursor = JournalEntryCursor::from(&entry);
+                        Edge::new(cursor, JournalEntry::from(entry))
+                    }));
+                Ok::<_, async_graphql::Error>(connection)
+            },
+        )
+        .await
+    }
+
     async fn general_ledger_entries(
         &self,
         ctx: &Context<'_>,
@@ -573,6 +808,45 @@ impl Query {
         Ok(TrialBalance::from(account_summary))
     }
 
+    async fn trial_balance_snapshot(
+        &self,
+        ctx: &Context<'_>,
+        id: UUID,
+    ) -> async_graphql::Result<Option<TrialBalanceSnapshot>> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        maybe_fetch_one!(
+            TrialBalanceSnapshot,
+            ctx,
+            app.accounting()
+                .trial_balances()
+                .find_snapshot_by_id(sub, id)
+        )
+    }
+
+    async fn trial_balance_snapshots(
+        &self,
+        ctx: &Context<'_>,
+        first: i32,
+        after: Option<String>,
+    ) -> async_graphql::Result<
+        Connection<
+            TrialBalanceSnapshotsByCreatedAtCursor,
+            TrialBalanceSnapshot,
+            EmptyFields,
+            EmptyFields,
+        >,
+    > {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        list_with_cursor!(
+            TrialBalanceSnapshotsByCreatedAtCursor,
+            TrialBalanceSnapshot,
+            ctx,
+            after,
+            first,
+            |query| app.accounting().trial_balances().list_snapshots(sub, query)
+        )
+    }
+
     async fn chart_of_accounts(&self, ctx: &Context<'_>) -> async_graphql::Result<ChartOfAccounts> {
         let (app, sub) = app_and_sub_from_ctx!(ctx);
         let chart = app
@@ -624,6 +898,74 @@ impl Query {
         Ok(ProfitAndLossStatement::from(profit_and_loss))
     }
 
+    async fn cash_flow_statement(
+        &self,
+        ctx: &Context<'_>,
+        from: Date,
+        until: Option<Date>,
+    ) -> async_graphql::Result<CashFlowStatement> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let cash_flow_statement = app
+            .accounting()
+            .cash_flow_statements()
+            .cash_flow_statement(
+                sub,
+                CASH_FLOW_STATEMENT_NAME.to_string(),
+                from.into_inner(),
+                until.map(|t| t.into_inner()),
+            )
+            .await?;
+        Ok(CashFlowStatement::from(cash_flow_statement))
+    }
+
+    async fn comparative_periods(
+        &self,
+        preset: ComparativePeriodPreset,
+        as_of: Date,
+    ) -> Vec<StatementPeriod> {
+        preset
+            .periods(as_of.into_inner())
+            .into_iter()
+            .map(StatementPeriod::from)
+            .collect()
+    }
+
+    async fn comparative_balance_sheet(
+        &self,
+        ctx: &Context<'_>,
+        periods: Vec<StatementPeriodInput>,
+    ) -> async_graphql::Result<ComparativeStatement> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let statement = app
+            .accounting()
+            .balance_sheets()
+            .comparative_balance_sheet(
+                sub,
+                BALANCE_SHEET_NAME.to_string(),
+                periods.into_iter().map(Into::into).collect(),
+            )
+            .await?;
+        Ok(ComparativeStatement::from(statement))
+    }
+
+    async fn comparative_profit_and_loss_statement(
+        &self,
+        ctx: &Context<'_>,
+        periods: Vec<StatementPeriodInput>,
+    ) -> async_graphql::Result<ComparativeStatement> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let statement = app
+            .accounting()
+            .profit_and_loss()
+            .comparative_pl_statement(
+                sub,
+                PROFIT_AND_LOSS_STATEMENT_NAME.to_string(),
+                periods.into_iter().map(Into::into).collect(),
+            )
+            .await?;
+        Ok(ComparativeStatement::from(statement))
+    }
+
     async fn realtime_price(&self, ctx: &Context<'_>) -> async_graphql::Result<RealtimePrice> {
         let app = ctx.data_unchecked::<LanaApp>();
         let usd_cents_per_btc = app.price().usd_cents_per_btc().await?;
@@ -733,6 +1075,19 @@ impl Query {
         Ok(config.map(ProfitAndLossStatementModuleConfig::from))
     }
 
+    async fn cash_flow_statement_config(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Option<CashFlowStatementModuleConfig>> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let config = app
+            .accounting()
+            .cash_flow_statements()
+            .get_chart_of_accounts_integration_config(sub, CASH_FLOW_STATEMENT_NAME.to_string())
+            .await?;
+        Ok(config.map(CashFlowStatementModuleConfig::from))
+    }
+
     async fn accounting_csvs_for_ledger_account_id(
         &self,
         ctx: &Context<'_>,
@@ -756,32 +1111,170 @@ impl Query {
             )
         )
     }
-}
 
-pub struct Mutation;
-
-#[Object]
-impl Mutation {
-    pub async fn customer_document_attach(
+    async fn accounting_csvs_for_chart_id(
         &self,
         ctx: &Context<'_>,
-        input: DocumentCreateInput,
-    ) -> async_graphql::Result<DocumentCreatePayload> {
+        chart_id: UUID,
+        first: i32,
+        after: Option<String>,
+    ) -> async_graphql::Result<
+        Connection<AccountingCsvsByCreatedAtCursor, AccountingCsv, EmptyFields, EmptyFields>,
+    > {
         let (app, sub) = app_and_sub_from_ctx!(ctx);
-
-        let mut file = input.file.value(ctx)?;
-        let mut data = Vec::new();
-        file.content.read_to_end(&mut data)?;
-
-        exec_mutation!(
-            DocumentCreatePayload,
-            Document,
+        list_with_cursor!(
+            AccountingCsvsByCreatedAtCursor,
+            AccountingCsv,
             ctx,
-            app.documents()
-                .create(sub, data, input.customer_id, file.filename)
-        )
-    }
-
+            after,
+            first,
+            |query| app
+                .accounting()
+                .csvs()
+                .list_for_chart_id(sub, query, chart_id)
+        )
+    }
+
+    async fn accounting_csvs_for_cash_flow_statement(
+        &self,
+        ctx: &Context<'_>,
+        first: i32,
+        after: Option<String>,
+    ) -> async_graphql::Result<
+        Connection<AccountingCsvsByCreatedAtCursor, AccountingCsv, EmptyFields, EmptyFields>,
+    > {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        list_with_cursor!(
+            AccountingCsvsByCreatedAtCursor,
+            AccountingCsv,
+            ctx,
+            after,
+            first,
+            |query| app.accounting().csvs().list_for_csv_type(
+                sub,
+                query,
+                AccountingCsvType::CashFlowStatement
+            )
+        )
+    }
+
+    async fn accounting_csvs_for_comparative_balance_sheet(
+        &self,
+        ctx: &Context<'_>,
+        first: i32,
+        after: Option<String>,
+    ) -> async_graphql::Result<
+        Connection<AccountingCsvsByCreatedAtCursor, AccountingCsv, EmptyFields, EmptyFields>,
+    > {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        list_with_cursor!(
+            AccountingCsvsByCreatedAtCursor,
+            AccountingCsv,
+            ctx,
+            after,
+            first,
+            |query| app.accounting().csvs().list_for_csv_type(
+                sub,
+                query,
+                AccountingCsvType::ComparativeBalanceSheet
+            )
+        )
+    }
+
+    async fn accounting_csvs_for_comparative_profit_and_loss_statement(
+        &self,
+        ctx: &Context<'_>,
+        first: i32,
+        after: Option<String>,
+    ) -> async_graphql::Result<
+        Connection<AccountingCsvsByCreatedAtCursor, AccountingCsv, EmptyFields, EmptyFields>,
+    > {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        list_with_cursor!(
+            AccountingCsvsByCreatedAtCursor,
+            AccountingCsv,
+            ctx,
+            after,
+            first,
+            |query| app.accounting().csvs().list_for_csv_type(
+                sub,
+                query,
+                AccountingCsvType::ComparativeProfitAndLoss
+            )
+        )
+    }
+
+    async fn accounting_csvs_for_journal_entry_search(
+        &self,
+        ctx: &Context<'_>,
+        first: i32,
+        after: Option<String>,
+    ) -> async_graphql::Result<
+        Connection<AccountingCsvsByCreatedAtCursor, AccountingCsv, EmptyFields, EmptyFields>,
+    > {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        list_with_cursor!(
+            AccountingCsvsByCreatedAtCursor,
+            AccountingCsv,
+            ctx,
+            after,
+            first,
+            |query| app.accounting().csvs().list_for_csv_type(
+                sub,
+                query,
+                AccountingCsvType::JournalEntrySearch
+            )
+        )
+    }
+
+    async fn accounting_csvs_for_manual_transaction_import(
+        &self,
+        ctx: &Context<'_>,
+        first: i32,
+        after: Option<String>,
+    ) -> async_graphql::Result<
+        Connection<AccountingCsvsByCreatedAtCursor, AccountingCsv, EmptyFields, EmptyFields>,
+    > {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        list_with_cursor!(
+            AccountingCsvsByCreatedAtCursor,
+            AccountingCsv,
+            ctx,
+            after,
+            first,
+            |query| app.accounting().csvs().list_for_csv_type(
+                sub,
+                query,
+                AccountingCsvType::ManualTransactionImport
+            )
+        )
+    }
+}
+
+pub struct Mutation;
+
+#[Object]
+impl Mutation {
+    pub async fn customer_document_attach(
+        &self,
+        ctx: &Context<'_>,
+        input: DocumentCreateInput,
+    ) -> async_graphql::Result<DocumentCreatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+
+        let mut file = input.file.value(ctx)?;
+        let mut data = Vec::new();
+        file.content.read_to_end(&mut data)?;
+
+        exec_mutation!(
+            DocumentCreatePayload,
+            Document,
+            ctx,
+            app.documents()
+                .create(sub, data, input.customer_id, file.filename)
+        )
+    }
+
     pub async fn sumsub_permalink_create(
         &self,
         ctx: &Context<'_>,
@@ -933,12 +1426,22 @@ impl Mutation {
                 input.chart_of_accounts_omnibus_parent_code.parse()?,
             )
             .build()?;
+        let change_set = app
+            .config_change_sets()
+            .propose(
+                sub,
+                ConfigChange::DepositModule {
+                    config: config_values,
+                },
+            )
+            .await?;
         let config = app
             .deposits()
-            .set_chart_of_accounts_integration_config(sub, chart.as_ref(), config_values)
+            .get_chart_of_accounts_integration_config(sub)
             .await?;
-        Ok(DepositModuleConfigurePayload::from(
-            DepositModuleConfig::from(config),
+        Ok(DepositModuleConfigurePayload::new(
+            ConfigChangeSet::from(change_set),
+            config.map(DepositModuleConfig::from),
         ))
     }
 
@@ -956,7 +1459,7 @@ impl Mutation {
 
         exec_mutation!(
             ManualTransactionExecutePayload,
-            LedgerTransaction,
+            ManualTransaction,
             ctx,
             app.accounting().execute_manual_transaction(
                 sub,
@@ -964,7 +1467,192 @@ impl Mutation {
                 input.reference,
                 input.description,
                 input.effective.map(|ts| ts.into_inner()),
-                entries
+                entries,
+                input.auto_reverse
+            )
+        )
+    }
+
+    pub async fn manual_transaction_reverse(
+        &self,
+        ctx: &Context<'_>,
+        input: ManualTransactionReverseInput,
+    ) -> async_graphql::Result<ManualTransactionReversePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            ManualTransactionReversePayload,
+            ManualTransaction,
+            ctx,
+            app.accounting().manual_transactions().reverse(
+                sub,
+                input.manual_transaction_id,
+                input.effective.into_inner(),
+                input.reason
+            )
+        )
+    }
+
+    pub async fn recurring_transaction_create(
+        &self,
+        ctx: &Context<'_>,
+        input: RecurringTransactionCreateInput,
+    ) -> async_graphql::Result<RecurringTransactionCreatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let schedule = input.recurrence_schedule()?;
+
+        let mut entries = Vec::with_capacity(input.entries.len());
+        for entry in input.entries.into_iter() {
+            entries.push(entry.try_into()?);
+        }
+
+        exec_mutation!(
+            RecurringTransactionCreatePayload,
+            RecurringTransaction,
+            ctx,
+            app.accounting().create_recurring_transaction(
+                sub,
+                CHART_REF.0,
+                input.reference,
+                input.description,
+                entries,
+                schedule,
+                input.start_date.into_inner(),
+                input.end_date.map(|d| d.into_inner())
+            )
+        )
+    }
+
+    pub async fn recurring_transaction_pause(
+        &self,
+        ctx: &Context<'_>,
+        input: RecurringTransactionPauseInput,
+    ) -> async_graphql::Result<RecurringTransactionPausePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            RecurringTransactionPausePayload,
+            RecurringTransaction,
+            ctx,
+            app.accounting()
+                .recurring_transactions()
+                .pause(sub, input.recurring_transaction_id)
+        )
+    }
+
+    pub async fn recurring_transaction_resume(
+        &self,
+        ctx: &Context<'_>,
+        input: RecurringTransactionResumeInput,
+    ) -> async_graphql::Result<RecurringTransactionResumePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            RecurringTransactionResumePayload,
+            RecurringTransaction,
+            ctx,
+            app.accounting()
+                .recurring_transactions()
+                .resume(sub, input.recurring_transaction_id)
+        )
+    }
+
+    pub async fn trial_balance_snapshot_create(
+        &self,
+        ctx: &Context<'_>,
+        input: TrialBalanceSnapshotCreateInput,
+    ) -> async_graphql::Result<TrialBalanceSnapshotCreatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            TrialBalanceSnapshotCreatePayload,
+            TrialBalanceSnapshot,
+            ctx,
+            app.accounting().trial_balances().create_snapshot(
+                sub,
+                TRIAL_BALANCE_STATEMENT_NAME.to_string(),
+                input.as_of.into_inner()
+            )
+        )
+    }
+
+    pub async fn accounting_period_create(
+        &self,
+        ctx: &Context<'_>,
+        input: AccountingPeriodCreateInput,
+    ) -> async_graphql::Result<AccountingPeriodCreatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            AccountingPeriodCreatePayload,
+            AccountingPeriod,
+            ctx,
+            app.accounting().accounting_periods().create(
+                sub,
+                input.period_start.into_inner(),
+                input.period_end.into_inner()
+            )
+        )
+    }
+
+    pub async fn accounting_period_soft_close(
+        &self,
+        ctx: &Context<'_>,
+        input: AccountingPeriodSoftCloseInput,
+    ) -> async_graphql::Result<AccountingPeriodSoftClosePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            AccountingPeriodSoftClosePayload,
+            AccountingPeriod,
+            ctx,
+            app.accounting()
+                .accounting_periods()
+                .soft_close(sub, input.accounting_period_id)
+        )
+    }
+
+    pub async fn accounting_period_reopen(
+        &self,
+        ctx: &Context<'_>,
+        input: AccountingPeriodReopenInput,
+    ) -> async_graphql::Result<AccountingPeriodReopenPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            AccountingPeriodReopenPayload,
+            AccountingPeriod,
+            ctx,
+            app.accounting()
+                .accounting_periods()
+                .reopen(sub, input.accounting_period_id)
+        )
+    }
+
+    pub async fn accounting_period_close(
+        &self,
+        ctx: &Context<'_>,
+        input: AccountingPeriodCloseInput,
+    ) -> async_graphql::Result<AccountingPeriodClosePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            AccountingPeriodClosePayload,
+            AccountingPeriod,
+            ctx,
+            app.accounting()
+                .accounting_periods()
+                .close(sub, input.accounting_period_id)
+        )
+    }
+
+    pub async fn accounting_period_close_year_end(
+        &self,
+        ctx: &Context<'_>,
+        input: AccountingPeriodCloseYearEndInput,
+    ) -> async_graphql::Result<AccountingPeriodCloseYearEndPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            AccountingPeriodCloseYearEndPayload,
+            AccountingPeriod,
+            ctx,
+            app.accounting().close_accounting_period_year_end(
+                sub,
+                CHART_REF.0,
+                BALANCE_SHEET_NAME,
+                input.accounting_period_id
             )
         )
     }
@@ -989,6 +1677,125 @@ impl Mutation {
         )
     }
 
+    pub async fn deposit_account_close(
+        &self,
+        ctx: &Context<'_>,
+        input: DepositAccountCloseInput,
+    ) -> async_graphql::Result<DepositAccountClosePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let settlement = match input.transfer_to_deposit_account_id {
+            Some(id) => lana_app::deposit::DepositAccountClosureSettlement::TransferTo(id.into()),
+            None => lana_app::deposit::DepositAccountClosureSettlement::PayOut,
+        };
+        exec_mutation!(
+            DepositAccountClosePayload,
+            DepositAccount,
+            ctx,
+            app.close_deposit_account(sub, input.deposit_account_id, settlement)
+        )
+    }
+
+    pub async fn bank_statement_upload(
+        &self,
+        ctx: &Context<'_>,
+        input: BankStatementUploadInput,
+    ) -> async_graphql::Result<BankStatementUploadPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let BankStatementUploadInput {
+            format,
+            csv_mapping,
+            file,
+        } = input;
+
+        let file = file.value(ctx)?;
+        let mut content = file.content;
+        let mut data = String::new();
+        content.read_to_string(&mut data)?;
+
+        exec_mutation!(
+            BankStatementUploadPayload,
+            BankStatementImport,
+            ctx,
+            app.deposits().import_bank_statement(
+                sub,
+                file.filename,
+                format,
+                csv_mapping.map(Into::into),
+                &data
+            )
+        )
+    }
+
+    pub async fn bank_statement_exception_resolve(
+        &self,
+        ctx: &Context<'_>,
+        input: BankStatementExceptionResolveInput,
+    ) -> async_graphql::Result<BankStatementExceptionResolvePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            BankStatementExceptionResolvePayload,
+            BankStatementException,
+            ctx,
+            app.deposits().resolve_bank_statement_exception(
+                sub,
+                input.bank_statement_exception_id,
+                input.deposit_account_id
+            )
+        )
+    }
+
+    pub async fn bank_statement_exception_dismiss(
+        &self,
+        ctx: &Context<'_>,
+        input: BankStatementExceptionDismissInput,
+    ) -> async_graphql::Result<BankStatementExceptionDismissPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            BankStatementExceptionDismissPayload,
+            BankStatementException,
+            ctx,
+            app.deposits().dismiss_bank_statement_exception(
+                sub,
+                input.bank_statement_exception_id,
+                input.note
+            )
+        )
+    }
+
+    pub async fn beneficiary_register(
+        &self,
+        ctx: &Context<'_>,
+        input: BeneficiaryRegisterInput,
+    ) -> async_graphql::Result<BeneficiaryRegisterPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let details = input.details()?;
+        exec_mutation!(
+            BeneficiaryRegisterPayload,
+            Beneficiary,
+            ctx,
+            app.deposits().register_beneficiary(
+                sub,
+                CustomerId::from(input.customer_id),
+                input.label,
+                details
+            )
+        )
+    }
+
+    pub async fn beneficiary_revoke(
+        &self,
+        ctx: &Context<'_>,
+        input: BeneficiaryRevokeInput,
+    ) -> async_graphql::Result<BeneficiaryRevokePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            BeneficiaryRevokePayload,
+            Beneficiary,
+            ctx,
+            app.deposits().revoke_beneficiary(sub, input.beneficiary_id)
+        )
+    }
+
     pub async fn withdrawal_initiate(
         &self,
         ctx: &Context<'_>,
@@ -1002,12 +1809,35 @@ impl Mutation {
             app.deposits().initiate_withdrawal(
                 sub,
                 input.deposit_account_id,
+                input.beneficiary_id,
                 input.amount,
                 input.reference
             )
         )
     }
 
+    pub async fn withdrawal_limit_override_request(
+        &self,
+        ctx: &Context<'_>,
+        input: WithdrawalLimitOverrideRequestInput,
+    ) -> async_graphql::Result<WithdrawalLimitOverrideRequestPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            WithdrawalLimitOverrideRequestPayload,
+            WithdrawalLimitOverride,
+            ctx,
+            app.deposits().request_withdrawal_limit_override(
+                sub,
+                input.deposit_account_id,
+                DomainWithdrawalLimits {
+                    daily: input.daily_limit,
+                    monthly: input.monthly_limit,
+                },
+                input.reason
+            )
+        )
+    }
+
     pub async fn withdrawal_confirm(
         &self,
         ctx: &Context<'_>,
@@ -1029,12 +1859,86 @@ impl Mutation {
         input: WithdrawalCancelInput,
     ) -> async_graphql::Result<WithdrawalCancelPayload> {
         let (app, sub) = app_and_sub_from_ctx!(ctx);
-        exec_mutation!(
-            WithdrawalCancelPayload,
-            Withdrawal,
-            ctx,
-            app.deposits().cancel_withdrawal(sub, input.withdrawal_id)
-        )
+        exec_mutation!(
+            WithdrawalCancelPayload,
+            Withdrawal,
+            ctx,
+            app.deposits().cancel_withdrawal(sub, input.withdrawal_id)
+        )
+    }
+
+    pub async fn withdrawal_payout_return(
+        &self,
+        ctx: &Context<'_>,
+        input: WithdrawalPayoutReturnInput,
+    ) -> async_graphql::Result<WithdrawalPayoutReturnPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            WithdrawalPayoutReturnPayload,
+            Withdrawal,
+            ctx,
+            app.deposits()
+                .record_withdrawal_payout_return(sub, input.withdrawal_id, input.reason)
+        )
+    }
+
+    pub async fn payout_batch_create(
+        &self,
+        ctx: &Context<'_>,
+        input: PayoutBatchCreateInput,
+    ) -> async_graphql::Result<PayoutBatchCreatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            PayoutBatchCreatePayload,
+            PayoutBatch,
+            ctx,
+            app.deposits().create_payout_batch(sub, input.format)
+        )
+    }
+
+    pub async fn payout_batch_mark_sent(
+        &self,
+        ctx: &Context<'_>,
+        input: PayoutBatchMarkSentInput,
+    ) -> async_graphql::Result<PayoutBatchMarkSentPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            PayoutBatchMarkSentPayload,
+            PayoutBatch,
+            ctx,
+            app.deposits()
+                .mark_payout_batch_sent(sub, input.payout_batch_id)
+        )
+    }
+
+    async fn payout_batch_download_link_generate(
+        &self,
+        ctx: &Context<'_>,
+        input: PayoutBatchDownloadLinkGenerateInput,
+    ) -> async_graphql::Result<PayoutBatchDownloadLinkGeneratePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let link = app
+            .deposits()
+            .generate_payout_batch_download_link(sub, input.payout_batch_id)
+            .await?;
+        Ok(PayoutBatchDownloadLinkGeneratePayload::from(
+            PayoutBatchDownloadLink::from(link),
+        ))
+    }
+
+    async fn deposit_account_statement_download_link_generate(
+        &self,
+        ctx: &Context<'_>,
+        input: DepositAccountStatementDownloadLinkGenerateInput,
+    ) -> async_graphql::Result<DepositAccountStatementDownloadLinkGeneratePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let link = app
+            .deposits()
+            .generate_statement_download_link(sub, input.deposit_account_statement_id, input.format)
+            .await?;
+        Ok(DepositAccountStatementDownloadLinkGeneratePayload::from(
+            DepositAccountStatementDownloadLink::from(link),
+        ))
     }
 
     async fn terms_template_create(
@@ -1082,16 +1986,26 @@ impl Mutation {
             .margin_call_cvl(input.margin_call_cvl)
             .initial_cvl(input.initial_cvl)
             .build()?;
-        exec_mutation!(
-            TermsTemplateUpdatePayload,
-            TermsTemplate,
-            ctx,
-            app.terms_templates().update_term_values(
+        let id = TermsTemplateId::from(input.id);
+        let change_set = app
+            .config_change_sets()
+            .propose(
                 sub,
-                TermsTemplateId::from(input.id),
-                term_values
+                ConfigChange::TermsTemplate {
+                    id,
+                    values: term_values,
+                },
             )
-        )
+            .await?;
+        let terms_template = app
+            .terms_templates()
+            .find_by_id(sub, id)
+            .await?
+            .expect("terms template not found");
+        Ok(TermsTemplateUpdatePayload::new(
+            ConfigChangeSet::from(change_set),
+            TermsTemplate::from(terms_template),
+        ))
     }
 
     async fn credit_module_configure(
@@ -1217,12 +2131,22 @@ impl Mutation {
             .chart_of_account_overdue_non_domiciled_company_disbursed_receivable_parent_code(chart_of_account_overdue_non_domiciled_company_disbursed_receivable_parent_code.parse()?)
 
             .build()?;
+        let change_set = app
+            .config_change_sets()
+            .propose(
+                sub,
+                ConfigChange::CreditModule {
+                    config: config_values,
+                },
+            )
+            .await?;
         let config = app
             .credit()
-            .set_chart_of_accounts_integration_config(sub, chart.as_ref(), config_values)
+            .get_chart_of_accounts_integration_config(sub)
             .await?;
-        Ok(CreditModuleConfigurePayload::from(
-            CreditModuleConfig::from(config),
+        Ok(CreditModuleConfigurePayload::new(
+            ConfigChangeSet::from(change_set),
+            config.map(CreditModuleConfig::from),
         ))
     }
 
@@ -1373,25 +2297,138 @@ impl Mutation {
         )
     }
 
+    async fn delegation_create(
+        &self,
+        ctx: &Context<'_>,
+        input: DelegationCreateInput,
+    ) -> async_graphql::Result<DelegationCreatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            DelegationCreatePayload,
+            Delegation,
+            ctx,
+            app.governance().create_delegation(
+                sub,
+                input.committee_id,
+                input.delegator_id,
+                input.delegate_id,
+                input.starts_at.into_inner(),
+                input.ends_at.into_inner()
+            )
+        )
+    }
+
+    async fn delegation_revoke(
+        &self,
+        ctx: &Context<'_>,
+        input: DelegationRevokeInput,
+    ) -> async_graphql::Result<DelegationRevokePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            DelegationRevokePayload,
+            Delegation,
+            ctx,
+            app.governance().revoke_delegation(sub, input.delegation_id)
+        )
+    }
+
     async fn policy_assign_committee(
         &self,
         ctx: &Context<'_>,
         input: PolicyAssignCommitteeInput,
     ) -> async_graphql::Result<PolicyAssignCommitteePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let policy_id = PolicyId::from(input.policy_id);
+        let change_set = app
+            .config_change_sets()
+            .propose(
+                sub,
+                ConfigChange::PolicyCommittee {
+                    policy_id,
+                    committee_id: CommitteeId::from(input.committee_id),
+                    threshold: input.threshold,
+                },
+            )
+            .await?;
+        let policy = app
+            .governance()
+            .find_policy(sub, policy_id)
+            .await?
+            .expect("policy not found");
+        Ok(PolicyAssignCommitteePayload::new(
+            ConfigChangeSet::from(change_set),
+            Policy::from(policy),
+        ))
+    }
+
+    async fn policy_approval_chain_assign(
+        &self,
+        ctx: &Context<'_>,
+        input: PolicyApprovalChainAssignInput,
+    ) -> async_graphql::Result<PolicyApprovalChainAssignPayload> {
         let (app, sub) = app_and_sub_from_ctx!(ctx);
         exec_mutation!(
-            PolicyAssignCommitteePayload,
+            PolicyApprovalChainAssignPayload,
             Policy,
             ctx,
-            app.governance().assign_committee_to_policy(
+            app.governance().assign_approval_chain_to_policy(
                 sub,
                 input.policy_id,
-                input.committee_id,
-                input.threshold
+                input.stages.into_iter().map(Into::into).collect()
+            )
+        )
+    }
+
+    async fn policy_approval_expression_assign(
+        &self,
+        ctx: &Context<'_>,
+        input: PolicyApprovalExpressionAssignInput,
+    ) -> async_graphql::Result<PolicyApprovalExpressionAssignPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let expression = input.expression.try_into()?;
+        exec_mutation!(
+            PolicyApprovalExpressionAssignPayload,
+            Policy,
+            ctx,
+            app.governance()
+                .assign_approval_expression_to_policy(sub, input.policy_id, expression)
+        )
+    }
+
+    async fn policy_tiers_update(
+        &self,
+        ctx: &Context<'_>,
+        input: PolicyTiersUpdateInput,
+    ) -> async_graphql::Result<PolicyTiersUpdatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            PolicyTiersUpdatePayload,
+            Policy,
+            ctx,
+            app.governance().update_policy_tiers(
+                sub,
+                input.policy_id,
+                input.tiers.into_iter().map(Into::into).collect()
             )
         )
     }
 
+    async fn policy_expiry_update(
+        &self,
+        ctx: &Context<'_>,
+        input: PolicyExpiryUpdateInput,
+    ) -> async_graphql::Result<PolicyExpiryUpdatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let expiry = input.expiry.map(TryInto::try_into).transpose()?;
+        exec_mutation!(
+            PolicyExpiryUpdatePayload,
+            Policy,
+            ctx,
+            app.governance()
+                .update_policy_expiry(sub, input.policy_id, expiry)
+        )
+    }
+
     async fn approval_process_approve(
         &self,
         ctx: &Context<'_>,
@@ -1402,7 +2439,12 @@ impl Mutation {
             ApprovalProcessApprovePayload,
             ApprovalProcess,
             ctx,
-            app.governance().approve_process(sub, input.process_id)
+            app.governance().approve_process(
+                sub,
+                input.process_id,
+                input.on_behalf_of.map(CommitteeMemberId::from),
+                input.rationale
+            )
         )
     }
 
@@ -1417,7 +2459,36 @@ impl Mutation {
             ApprovalProcessDenyPayload,
             ApprovalProcess,
             ctx,
-            app.governance().deny_process(sub, input.process_id, reason)
+            app.governance().deny_process(
+                sub,
+                input.process_id,
+                input.on_behalf_of.map(CommitteeMemberId::from),
+                reason
+            )
+        )
+    }
+
+    async fn approval_process_comment(
+        &self,
+        ctx: &Context<'_>,
+        input: ApprovalProcessCommentInput,
+    ) -> async_graphql::Result<ApprovalProcessCommentPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        exec_mutation!(
+            ApprovalProcessCommentPayload,
+            ApprovalProcess,
+            ctx,
+            app.comment_on_approval_process(
+                sub,
+                input.process_id,
+                input.body,
+                input
+                    .document_ids
+                    .unwrap_or_default()
+                    .into_iter()
+                    .map(DocumentId::from)
+                    .collect()
+            )
         )
     }
 
@@ -1502,6 +2573,84 @@ impl Mutation {
         Ok(ChartOfAccountsCsvImportPayload { success: res })
     }
 
+    async fn chart_of_accounts_csv_import_preview(
+        &self,
+        ctx: &Context<'_>,
+        input: ChartOfAccountsCsvImportInput,
+    ) -> async_graphql::Result<ChartOfAccountsCsvImportPreviewPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let ChartOfAccountsCsvImportInput { chart_id, file } = input;
+
+        let mut file = file.value(ctx)?.content;
+
+        let mut data = String::new();
+        file.read_to_string(&mut data)?;
+
+        let diff = app
+            .accounting()
+            .chart_of_accounts()
+            .preview_csv_import(sub, chart_id, data)
+            .await?;
+
+        Ok(ChartOfAccountsCsvImportPreviewPayload {
+            diff: ChartOfAccountsImportDiff::from(diff),
+        })
+    }
+
+    async fn chart_of_accounts_node_rename(
+        &self,
+        ctx: &Context<'_>,
+        input: ChartOfAccountsNodeRenameInput,
+    ) -> async_graphql::Result<ChartOfAccountsNodeRenamePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let chart = app
+            .accounting()
+            .chart_of_accounts()
+            .rename_node(
+                sub,
+                input.chart_id,
+                &input.code.parse()?,
+                input.name.parse()?,
+            )
+            .await?;
+        Ok(ChartOfAccountsNodeRenamePayload::from(
+            ChartOfAccounts::from(chart),
+        ))
+    }
+
+    async fn chart_of_accounts_node_move(
+        &self,
+        ctx: &Context<'_>,
+        input: ChartOfAccountsNodeMoveInput,
+    ) -> async_graphql::Result<ChartOfAccountsNodeMovePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let parent = input.parent_code.map(|code| code.parse()).transpose()?;
+        let chart = app
+            .accounting()
+            .chart_of_accounts()
+            .move_node(sub, input.chart_id, &input.code.parse()?, parent)
+            .await?;
+        Ok(ChartOfAccountsNodeMovePayload::from(ChartOfAccounts::from(
+            chart,
+        )))
+    }
+
+    async fn chart_of_accounts_node_deactivate(
+        &self,
+        ctx: &Context<'_>,
+        input: ChartOfAccountsNodeDeactivateInput,
+    ) -> async_graphql::Result<ChartOfAccountsNodeDeactivatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let chart = app
+            .accounting()
+            .chart_of_accounts()
+            .deactivate_node(sub, input.chart_id, &input.code.parse()?)
+            .await?;
+        Ok(ChartOfAccountsNodeDeactivatePayload::from(
+            ChartOfAccounts::from(chart),
+        ))
+    }
+
     async fn balance_sheet_configure(
         &self,
         ctx: &Context<'_>,
@@ -1526,18 +2675,23 @@ impl Mutation {
             )
             .chart_of_accounts_expenses_code(input.chart_of_accounts_expenses_code.parse()?)
             .build()?;
+        let change_set = app
+            .config_change_sets()
+            .propose(
+                sub,
+                ConfigChange::BalanceSheet {
+                    config: config_values,
+                },
+            )
+            .await?;
         let config = app
             .accounting()
             .balance_sheets()
-            .set_chart_of_accounts_integration_config(
-                sub,
-                BALANCE_SHEET_NAME.to_string(),
-                chart.as_ref(),
-                config_values,
-            )
+            .get_chart_of_accounts_integration_config(sub, BALANCE_SHEET_NAME.to_string())
             .await?;
-        Ok(BalanceSheetModuleConfigurePayload::from(
-            BalanceSheetModuleConfig::from(config),
+        Ok(BalanceSheetModuleConfigurePayload::new(
+            ConfigChangeSet::from(change_set),
+            config.map(BalanceSheetModuleConfig::from),
         ))
     }
 
@@ -1577,6 +2731,59 @@ impl Mutation {
         ))
     }
 
+    async fn cash_flow_statement_configure(
+        &self,
+        ctx: &Context<'_>,
+        input: CashFlowStatementModuleConfigureInput,
+    ) -> async_graphql::Result<CashFlowStatementModuleConfigurePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let chart = loader
+            .load_one(CHART_REF)
+            .await?
+            .unwrap_or_else(|| panic!("Chart of accounts not found for ref {:?}", CHART_REF));
+
+        let parse_codes = |codes: Vec<String>| {
+            codes
+                .iter()
+                .map(|code| code.parse::<lana_app::accounting::AccountCode>())
+                .collect::<Result<Vec<_>, _>>()
+        };
+        let config_values =
+            lana_app::cash_flow_statement::ChartOfAccountsIntegrationConfig::builder()
+                .chart_of_accounts_id(chart.id)
+                .chart_of_accounts_cash_code(input.chart_of_accounts_cash_code.parse()?)
+                .chart_of_accounts_revenue_code(input.chart_of_accounts_revenue_code.parse()?)
+                .chart_of_accounts_cost_of_revenue_code(
+                    input.chart_of_accounts_cost_of_revenue_code.parse()?,
+                )
+                .chart_of_accounts_expenses_code(input.chart_of_accounts_expenses_code.parse()?)
+                .chart_of_accounts_operating_codes(parse_codes(
+                    input.chart_of_accounts_operating_codes,
+                )?)
+                .chart_of_accounts_investing_codes(parse_codes(
+                    input.chart_of_accounts_investing_codes,
+                )?)
+                .chart_of_accounts_financing_codes(parse_codes(
+                    input.chart_of_accounts_financing_codes,
+                )?)
+                .build()?;
+        let config = app
+            .accounting()
+            .cash_flow_statements()
+            .set_chart_of_accounts_integration_config(
+                sub,
+                CASH_FLOW_STATEMENT_NAME.to_string(),
+                chart.as_ref(),
+                config_values,
+            )
+            .await?;
+        Ok(CashFlowStatementModuleConfigurePayload::from(
+            CashFlowStatementModuleConfig::from(config),
+        ))
+    }
+
     pub async fn ledger_account_csv_create(
         &self,
         ctx: &Context<'_>,
@@ -1593,6 +2800,121 @@ impl Mutation {
         Ok(LedgerAccountCsvCreatePayload::from(csv))
     }
 
+    pub async fn chart_of_accounts_csv_create(
+        &self,
+        ctx: &Context<'_>,
+        input: ChartOfAccountsCsvCreateInput,
+    ) -> async_graphql::Result<ChartOfAccountsCsvCreatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let csv = app
+            .accounting()
+            .csvs()
+            .create_chart_of_accounts_csv(sub, input.chart_id)
+            .await?;
+
+        let csv = AccountingCsv::from(csv);
+        Ok(ChartOfAccountsCsvCreatePayload::from(csv))
+    }
+
+    pub async fn cash_flow_statement_csv_create(
+        &self,
+        ctx: &Context<'_>,
+        input: CashFlowStatementCsvCreateInput,
+    ) -> async_graphql::Result<CashFlowStatementCsvCreatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let csv = app
+            .accounting()
+            .csvs()
+            .create_cash_flow_statement_csv(
+                sub,
+                CASH_FLOW_STATEMENT_NAME.to_string(),
+                input.from.into_inner(),
+                input.until.map(|t| t.into_inner()),
+            )
+            .await?;
+
+        let csv = AccountingCsv::from(csv);
+        Ok(CashFlowStatementCsvCreatePayload::from(csv))
+    }
+
+    pub async fn comparative_balance_sheet_csv_create(
+        &self,
+        ctx: &Context<'_>,
+        input: ComparativeStatementCsvCreateInput,
+    ) -> async_graphql::Result<ComparativeBalanceSheetCsvCreatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let csv = app
+            .accounting()
+            .csvs()
+            .create_comparative_balance_sheet_csv(
+                sub,
+                BALANCE_SHEET_NAME.to_string(),
+                input.periods.into_iter().map(Into::into).collect(),
+            )
+            .await?;
+
+        let csv = AccountingCsv::from(csv);
+        Ok(ComparativeBalanceSheetCsvCreatePayload::from(csv))
+    }
+
+    pub async fn comparative_profit_and_loss_statement_csv_create(
+        &self,
+        ctx: &Context<'_>,
+        input: ComparativeStatementCsvCreateInput,
+    ) -> async_graphql::Result<ComparativeProfitAndLossStatementCsvCreatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let csv = app
+            .accounting()
+            .csvs()
+            .create_comparative_pl_statement_csv(
+                sub,
+                PROFIT_AND_LOSS_STATEMENT_NAME.to_string(),
+                input.periods.into_iter().map(Into::into).collect(),
+            )
+            .await?;
+
+        let csv = AccountingCsv::from(csv);
+        Ok(ComparativeProfitAndLossStatementCsvCreatePayload::from(csv))
+    }
+
+    pub async fn journal_entry_search_csv_create(
+        &self,
+        ctx: &Context<'_>,
+        input: JournalEntrySearchCsvCreateInput,
+    ) -> async_graphql::Result<JournalEntrySearchCsvCreatePayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+        let filter = input.filter.into_filter(app).await?;
+        let csv = app
+            .accounting()
+            .csvs()
+            .create_journal_entry_search_csv(sub, filter)
+            .await?;
+
+        let csv = AccountingCsv::from(csv);
+        Ok(JournalEntrySearchCsvCreatePayload::from(csv))
+    }
+
+    pub async fn manual_transactions_csv_import(
+        &self,
+        ctx: &Context<'_>,
+        input: ManualTransactionsCsvImportInput,
+    ) -> async_graphql::Result<ManualTransactionsCsvImportPayload> {
+        let (app, sub) = app_and_sub_from_ctx!(ctx);
+
+        let file = input.file.value(ctx)?;
+        let mut content = file.content;
+        let mut data = String::new();
+        content.read_to_string(&mut data)?;
+
+        let csv = app
+            .accounting()
+            .import_manual_transactions_csv(sub, CHART_REF.0, file.filename, data)
+            .await?;
+
+        let csv = AccountingCsv::from(csv);
+        Ok(ManualTransactionsCsvImportPayload::from(csv))
+    }
+
     pub async fn accounting_csv_download_link_generate(
         &self,
         ctx: &Context<'_>,
@@ -2,7 +2,7 @@ use async_graphql::*;
 
 use crate::primitives::*;
 
-use super::terms::*;
+use super::{config_change_set::ConfigChangeSet, terms::*};
 
 use lana_app::terms_template::TermsTemplate as DomainTermsTemplate;
 
@@ -77,4 +77,18 @@ pub(super) struct TermsTemplateUpdateInput {
     pub margin_call_cvl: CVLPct,
     pub initial_cvl: CVLPct,
 }
-crate::mutation_payload! { TermsTemplateUpdatePayload, terms_template: TermsTemplate }
+#[derive(SimpleObject)]
+pub struct TermsTemplateUpdatePayload {
+    config_change_set: ConfigChangeSet,
+    /// The terms template as it stands once the change-set has been processed.
+    terms_template: TermsTemplate,
+}
+
+impl TermsTemplateUpdatePayload {
+    pub fn new(config_change_set: ConfigChangeSet, terms_template: TermsTemplate) -> Self {
+        Self {
+            config_change_set,
+            terms_template,
+        }
+    }
+}
@@ -3,11 +3,13 @@ use async_graphql::*;
 use crate::primitives::*;
 
 use super::{
-    approval_process::ApprovalProcess, deposit_account::DepositAccount, loader::LanaDataLoader,
+    approval_process::ApprovalProcess, beneficiary::Beneficiary, deposit_account::DepositAccount,
+    loader::LanaDataLoader, payout_batch::PayoutBatch,
 };
 
 pub use lana_app::deposit::{
-    Withdrawal as DomainWithdrawal, WithdrawalStatus, WithdrawalsByCreatedAtCursor,
+    Withdrawal as DomainWithdrawal, WithdrawalPayoutStatus, WithdrawalStatus,
+    WithdrawalsByCreatedAtCursor,
 };
 
 #[derive(SimpleObject, Clone)]
@@ -44,6 +46,16 @@ impl Withdrawal {
         &self.entity.reference
     }
 
+    /// The beneficiary the withdrawal is sent to. Empty for withdrawals
+    /// initiated before beneficiaries were required.
+    async fn beneficiary(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<Beneficiary>> {
+        let Some(beneficiary) = self.entity.beneficiary.as_ref() else {
+            return Ok(None);
+        };
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        Ok(loader.load_one(beneficiary.id).await?)
+    }
+
     async fn status(&self, ctx: &Context<'_>) -> async_graphql::Result<WithdrawalStatus> {
         let (app, _) = crate::app_and_sub_from_ctx!(ctx);
         Ok(app
@@ -54,6 +66,22 @@ impl Withdrawal {
             .unwrap_or_else(|| self.entity.status()))
     }
 
+    async fn payout_status(&self) -> WithdrawalPayoutStatus {
+        self.entity.payout_status()
+    }
+
+    async fn payout_return_reason(&self) -> Option<&str> {
+        self.entity.payout_return_reason()
+    }
+
+    async fn payout_batch(&self, ctx: &Context<'_>) -> async_graphql::Result<Option<PayoutBatch>> {
+        let Some(payout_batch_id) = self.entity.payout_batch_id() else {
+            return Ok(None);
+        };
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        Ok(loader.load_one(payout_batch_id).await?)
+    }
+
     async fn approval_process(&self, ctx: &Context<'_>) -> async_graphql::Result<ApprovalProcess> {
         let loader = ctx.data_unchecked::<LanaDataLoader>();
         let process = loader
@@ -95,6 +123,7 @@ impl Withdrawal {
 pub struct WithdrawalInitiateInput {
     pub deposit_account_id: UUID,
     pub amount: UsdCents,
+    pub beneficiary_id: UUID,
     pub reference: Option<String>,
 }
 crate::mutation_payload! { WithdrawalInitiatePayload, withdrawal: Withdrawal }
@@ -110,3 +139,10 @@ pub struct WithdrawalCancelInput {
     pub withdrawal_id: UUID,
 }
 crate::mutation_payload! { WithdrawalCancelPayload, withdrawal: Withdrawal }
+
+#[derive(InputObject)]
+pub struct WithdrawalPayoutReturnInput {
+    pub withdrawal_id: UUID,
+    pub reason: String,
+}
+crate::mutation_payload! { WithdrawalPayoutReturnPayload, withdrawal: Withdrawal }
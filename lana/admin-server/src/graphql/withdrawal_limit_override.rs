@@ -0,0 +1,91 @@
+use async_graphql::*;
+
+use crate::primitives::*;
+
+use super::{
+    approval_process::ApprovalProcess, deposit_account::DepositAccount, loader::LanaDataLoader,
+};
+
+pub use lana_app::deposit::{
+    WithdrawalLimitOverride as DomainWithdrawalLimitOverride, WithdrawalLimitOverrideStatus,
+    WithdrawalLimits as DomainWithdrawalLimits,
+};
+
+#[derive(SimpleObject, Clone)]
+pub struct WithdrawalLimits {
+    daily: UsdCents,
+    monthly: UsdCents,
+}
+
+impl From<DomainWithdrawalLimits> for WithdrawalLimits {
+    fn from(limits: DomainWithdrawalLimits) -> Self {
+        Self {
+            daily: limits.daily,
+            monthly: limits.monthly,
+        }
+    }
+}
+
+#[derive(SimpleObject, Clone)]
+#[graphql(complex)]
+pub struct WithdrawalLimitOverride {
+    id: ID,
+    withdrawal_limit_override_id: UUID,
+    deposit_account_id: UUID,
+    approval_process_id: UUID,
+    limits: WithdrawalLimits,
+    reason: String,
+    created_at: Timestamp,
+
+    #[graphql(skip)]
+    pub(super) entity: Arc<DomainWithdrawalLimitOverride>,
+}
+
+impl From<DomainWithdrawalLimitOverride> for WithdrawalLimitOverride {
+    fn from(limit_override: DomainWithdrawalLimitOverride) -> Self {
+        WithdrawalLimitOverride {
+            id: limit_override.id.to_global_id(),
+            withdrawal_limit_override_id: UUID::from(limit_override.id),
+            deposit_account_id: UUID::from(limit_override.deposit_account_id),
+            approval_process_id: UUID::from(limit_override.approval_process_id),
+            limits: WithdrawalLimits::from(limit_override.limits),
+            reason: limit_override.reason.clone(),
+            created_at: limit_override.created_at().into(),
+            entity: Arc::new(limit_override),
+        }
+    }
+}
+
+#[ComplexObject]
+impl WithdrawalLimitOverride {
+    async fn status(&self) -> WithdrawalLimitOverrideStatus {
+        self.entity.status()
+    }
+
+    async fn approval_process(&self, ctx: &Context<'_>) -> async_graphql::Result<ApprovalProcess> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let process = loader
+            .load_one(self.entity.approval_process_id)
+            .await?
+            .expect("process not found");
+        Ok(process)
+    }
+
+    async fn account(&self, ctx: &Context<'_>) -> async_graphql::Result<DepositAccount> {
+        let loader = ctx.data_unchecked::<LanaDataLoader>();
+        let account = loader
+            .load_one(self.entity.deposit_account_id)
+            .await?
+            .expect("account not found");
+        Ok(account)
+    }
+}
+
+#[derive(InputObject)]
+pub struct WithdrawalLimitOverrideRequestInput {
+    pub deposit_account_id: UUID,
+    pub daily_limit: UsdCents,
+    pub monthly_limit: UsdCents,
+    pub reason: String,
+}
+crate::mutation_payload! { WithdrawalLimitOverrideRequestPayload, withdrawal_limit_override: WithdrawalLimitOverride }
@@ -5,10 +5,13 @@ use serde::{Deserialize, Serialize};
 
 pub use lana_app::{
     primitives::{
-        ApprovalProcessId, ChartId, CommitteeId, CreditFacilityId, CustomerId, DepositAccountId,
+        AccountingPeriodId, ApprovalProcessId, BankStatementExceptionId, BankStatementImportId,
+        BeneficiaryId, ChartId, CommitteeId, CommitteeMemberId, ConfigChangeSetId,
+        CreditFacilityId, CustomerId, DelegationId, DepositAccountId, DepositAccountStatementId,
         DepositId, DisbursalId, DisbursalStatus, DocumentId, LanaRole, LedgerTransactionId,
-        ManualTransactionId, PaymentId, PolicyId, ReportId, ReportProgress, Satoshis, Subject,
-        TermsTemplateId, UsdCents, UserId, WithdrawalId,
+        ManualTransactionId, PaymentId, PayoutBatchId, PolicyId, RecurringTransactionId, ReportId,
+        ReportProgress, Satoshis, SignedSatoshis, SignedUsdCents, Subject, TermsTemplateId,
+        TrialBalanceSnapshotId, UsdCents, UserId, WithdrawalId, WithdrawalLimitOverrideId,
     },
     terms::CollateralizationState,
 };
@@ -112,11 +115,22 @@ impl_to_global_id! {
     DocumentId,
     PolicyId,
     CommitteeId,
+    DelegationId,
     WithdrawalId,
+    WithdrawalLimitOverrideId,
+    BankStatementImportId,
+    BankStatementExceptionId,
+    PayoutBatchId,
+    BeneficiaryId,
     DepositId,
     ManualTransactionId,
+    AccountingPeriodId,
+    RecurringTransactionId,
+    TrialBalanceSnapshotId,
+    ConfigChangeSetId,
     ApprovalProcessId,
     DepositAccountId,
+    DepositAccountStatementId,
     LedgerTransactionId
 }
 
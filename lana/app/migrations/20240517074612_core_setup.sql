@@ -34,8 +34,12 @@ CREATE TABLE approval_processes (
   policy_id UUID REFERENCES policies(id),
   committee_id UUID REFERENCES committees(id),
   process_type VARCHAR NOT NULL,
+  expires_at TIMESTAMPTZ,
+  time_dependent BOOLEAN NOT NULL,
   created_at TIMESTAMPTZ NOT NULL
 );
+CREATE INDEX idx_approval_processes_expires_at_id ON approval_processes (expires_at, id);
+CREATE INDEX idx_approval_processes_time_dependent_created_at_id ON approval_processes (time_dependent, created_at, id);
 
 CREATE TABLE approval_process_events (
   id UUID NOT NULL REFERENCES approval_processes(id),
@@ -46,6 +50,24 @@ CREATE TABLE approval_process_events (
   UNIQUE(id, sequence)
 );
 
+CREATE TABLE delegations (
+  id UUID PRIMARY KEY,
+  committee_id UUID NOT NULL REFERENCES committees(id),
+  delegator_id UUID NOT NULL,
+  delegate_id UUID NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX idx_delegations_delegate_id ON delegations(delegate_id);
+
+CREATE TABLE delegation_events (
+  id UUID NOT NULL REFERENCES delegations(id),
+  sequence INT NOT NULL,
+  event_type VARCHAR NOT NULL,
+  event JSONB NOT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL,
+  UNIQUE(id, sequence)
+);
+
 CREATE TABLE core_charts (
   id UUID PRIMARY KEY,
   reference VARCHAR NOT NULL UNIQUE,
@@ -64,6 +86,7 @@ CREATE TABLE core_chart_events (
 CREATE TABLE core_deposit_accounts (
   id UUID PRIMARY KEY,
   account_holder_id UUID NOT NULL,
+  status VARCHAR NOT NULL,
   created_at TIMESTAMPTZ NOT NULL
 );
 
@@ -97,7 +120,9 @@ CREATE TABLE core_withdrawals (
   deposit_account_id UUID NOT NULL REFERENCES core_deposit_accounts(id),
   approval_process_id UUID REFERENCES approval_processes(id),
   cancelled_tx_id UUID DEFAULT NULL,
+  returned_tx_id UUID DEFAULT NULL,
   reference VARCHAR NOT NULL UNIQUE,
+  payout_status VARCHAR NOT NULL,
   created_at TIMESTAMPTZ NOT NULL
 );
 
@@ -110,6 +135,101 @@ CREATE TABLE core_withdrawal_events (
   UNIQUE(id, sequence)
 );
 
+CREATE TABLE core_payout_batches (
+  id UUID PRIMARY KEY,
+  created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE core_payout_batch_events (
+  id UUID NOT NULL REFERENCES core_payout_batches(id),
+  sequence INT NOT NULL,
+  event_type VARCHAR NOT NULL,
+  event JSONB NOT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL,
+  UNIQUE(id, sequence)
+);
+
+CREATE TABLE core_withdrawal_limit_overrides (
+  id UUID PRIMARY KEY,
+  deposit_account_id UUID NOT NULL REFERENCES core_deposit_accounts(id),
+  approval_process_id UUID NOT NULL REFERENCES approval_processes(id),
+  created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE core_withdrawal_limit_override_events (
+  id UUID NOT NULL REFERENCES core_withdrawal_limit_overrides(id),
+  sequence INT NOT NULL,
+  event_type VARCHAR NOT NULL,
+  event JSONB NOT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL,
+  UNIQUE(id, sequence)
+);
+
+CREATE TABLE core_beneficiaries (
+  id UUID PRIMARY KEY,
+  deposit_account_holder_id UUID NOT NULL,
+  approval_process_id UUID NOT NULL REFERENCES approval_processes(id),
+  created_at TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX idx_core_beneficiaries_deposit_account_holder_id ON core_beneficiaries (deposit_account_holder_id);
+
+CREATE TABLE core_beneficiary_events (
+  id UUID NOT NULL REFERENCES core_beneficiaries(id),
+  sequence INT NOT NULL,
+  event_type VARCHAR NOT NULL,
+  event JSONB NOT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL,
+  UNIQUE(id, sequence)
+);
+
+CREATE TABLE core_bank_statement_imports (
+  id UUID PRIMARY KEY,
+  statement_account VARCHAR NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE core_bank_statement_import_events (
+  id UUID NOT NULL REFERENCES core_bank_statement_imports(id),
+  sequence INT NOT NULL,
+  event_type VARCHAR NOT NULL,
+  event JSONB NOT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL,
+  UNIQUE(id, sequence)
+);
+
+CREATE TABLE core_bank_statement_exceptions (
+  id UUID PRIMARY KEY,
+  bank_statement_import_id UUID NOT NULL REFERENCES core_bank_statement_imports(id),
+  reference VARCHAR NOT NULL UNIQUE,
+  status VARCHAR NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE core_bank_statement_exception_events (
+  id UUID NOT NULL REFERENCES core_bank_statement_exceptions(id),
+  sequence INT NOT NULL,
+  event_type VARCHAR NOT NULL,
+  event JSONB NOT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL,
+  UNIQUE(id, sequence)
+);
+
+CREATE TABLE core_deposit_account_statements (
+  id UUID PRIMARY KEY,
+  deposit_account_id UUID NOT NULL REFERENCES core_deposit_accounts(id),
+  reference VARCHAR NOT NULL UNIQUE,
+  created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE core_deposit_account_statement_events (
+  id UUID NOT NULL REFERENCES core_deposit_account_statements(id),
+  sequence INT NOT NULL,
+  event_type VARCHAR NOT NULL,
+  event JSONB NOT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL,
+  UNIQUE(id, sequence)
+);
+
 CREATE TABLE customers (
   id UUID PRIMARY KEY,
   authentication_id UUID UNIQUE DEFAULT NULL,
@@ -306,10 +426,26 @@ CREATE TABLE report_events (
   UNIQUE(id, sequence)
 );
 
+CREATE TABLE config_change_sets (
+  id UUID PRIMARY KEY,
+  approval_process_id UUID NOT NULL REFERENCES approval_processes(id),
+  created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE config_change_set_events (
+  id UUID NOT NULL REFERENCES config_change_sets(id),
+  sequence INT NOT NULL,
+  event_type VARCHAR NOT NULL,
+  event JSONB NOT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL,
+  UNIQUE(id, sequence)
+);
+
 CREATE TABLE accounting_csvs (
   id UUID PRIMARY KEY,
   csv_type VARCHAR NOT NULL,
   ledger_account_id UUID,
+  chart_id UUID,
   created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
 );
 
@@ -326,6 +462,7 @@ CREATE TABLE core_manual_transactions (
   id UUID PRIMARY KEY,
   reference VARCHAR NOT NULL UNIQUE,
   ledger_transaction_id UUID NOT NULL,
+  pending_auto_reverse_on DATE,
   created_at TIMESTAMPTZ NOT NULL
 );
 
@@ -338,6 +475,76 @@ CREATE TABLE core_manual_transaction_events (
   UNIQUE(id, sequence)
 );
 
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE TABLE core_journal_entry_search (
+  entry_id UUID PRIMARY KEY,
+  journal_id UUID NOT NULL,
+  account_id UUID NOT NULL,
+  units NUMERIC NOT NULL,
+  effective DATE NOT NULL,
+  template_code VARCHAR NOT NULL,
+  manual BOOLEAN NOT NULL,
+  description TEXT NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL
+);
+CREATE INDEX idx_core_journal_entry_search_journal_id_created_at_entry_id ON core_journal_entry_search (journal_id, created_at DESC, entry_id DESC);
+CREATE INDEX idx_core_journal_entry_search_account_id_created_at_entry_id ON core_journal_entry_search (account_id, created_at DESC, entry_id DESC);
+CREATE INDEX idx_core_journal_entry_search_journal_id_effective ON core_journal_entry_search (journal_id, effective);
+CREATE INDEX idx_core_journal_entry_search_journal_id_units ON core_journal_entry_search (journal_id, units);
+CREATE INDEX idx_core_journal_entry_search_journal_id_template_code ON core_journal_entry_search (journal_id, template_code);
+CREATE INDEX idx_core_journal_entry_search_journal_id_manual_created_at ON core_journal_entry_search (journal_id, manual, created_at DESC, entry_id DESC);
+CREATE INDEX idx_core_journal_entry_search_description ON core_journal_entry_search USING GIN (description gin_trgm_ops);
+
+CREATE TABLE core_recurring_transactions (
+  id UUID PRIMARY KEY,
+  reference VARCHAR NOT NULL UNIQUE,
+  next_due_on DATE,
+  created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE core_recurring_transaction_events (
+  id UUID NOT NULL REFERENCES core_recurring_transactions(id),
+  sequence INT NOT NULL,
+  event_type VARCHAR NOT NULL,
+  event JSONB NOT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL,
+  UNIQUE(id, sequence)
+);
+
+CREATE TABLE core_trial_balance_snapshots (
+  id UUID PRIMARY KEY,
+  as_of DATE NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE TABLE core_trial_balance_snapshot_events (
+  id UUID NOT NULL REFERENCES core_trial_balance_snapshots(id),
+  sequence INT NOT NULL,
+  event_type VARCHAR NOT NULL,
+  event JSONB NOT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL,
+  UNIQUE(id, sequence)
+);
+
+CREATE TABLE core_accounting_periods (
+  id UUID PRIMARY KEY,
+  period_start DATE NOT NULL,
+  period_end DATE NOT NULL,
+  created_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX idx_core_accounting_periods_period_end_period_start ON core_accounting_periods (period_end, period_start);
+
+CREATE TABLE core_accounting_period_events (
+  id UUID NOT NULL REFERENCES core_accounting_periods(id),
+  sequence INT NOT NULL,
+  event_type VARCHAR NOT NULL,
+  event JSONB NOT NULL,
+  recorded_at TIMESTAMPTZ NOT NULL,
+  UNIQUE(id, sequence)
+);
+
 CREATE TABLE jobs (
   id UUID NOT NULL UNIQUE,
   unique_per_type BOOLEAN NOT NULL,
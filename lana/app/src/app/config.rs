@@ -2,8 +2,9 @@ use serde::{Deserialize, Serialize};
 
 use crate::{
     applicant::SumsubConfig, credit::CreditConfig, customer_sync::CustomerSyncConfig,
-    job::JobExecutorConfig, report::ReportConfig, service_account::ServiceAccountConfig,
-    storage::config::StorageConfig, user_onboarding::UserOnboardingConfig,
+    deposit::DepositConfig, job::JobExecutorConfig, report::ReportConfig,
+    service_account::ServiceAccountConfig, storage::config::StorageConfig,
+    user_onboarding::UserOnboardingConfig,
 };
 
 #[derive(Clone, Default, Debug, Deserialize, Serialize)]
@@ -17,6 +18,8 @@ pub struct AppConfig {
     #[serde(default)]
     pub credit: CreditConfig,
     #[serde(default)]
+    pub deposit: DepositConfig,
+    #[serde(default)]
     pub service_account: ServiceAccountConfig,
     #[serde(default)]
     pub report: ReportConfig,
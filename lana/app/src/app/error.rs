@@ -20,6 +20,8 @@ pub enum ApplicationError {
     ProfitAndLossStatementError(#[from] crate::profit_and_loss::error::ProfitAndLossStatementError),
     #[error("ApplicationError - BalanceSheetError: {0}")]
     BalanceSheetError(#[from] crate::balance_sheet::error::BalanceSheetError),
+    #[error("ApplicationError - CashFlowStatementError: {0}")]
+    CashFlowStatementError(#[from] crate::cash_flow_statement::error::CashFlowStatementError),
     #[error("ApplicationError - UserError: {0}")]
     UserError(#[from] crate::user::error::UserError),
     #[error("ApplicationError - UserOnboardingError: {0}")]
@@ -40,6 +42,8 @@ pub enum ApplicationError {
     DashboardError(#[from] dashboard::error::DashboardError),
     #[error("ApplicationError - CalaInit: {0}")]
     CalaError(#[from] cala_ledger::error::LedgerError),
+    #[error("ApplicationError - AccountingError: {0}")]
+    AccountingError(#[from] core_accounting::error::CoreAccountingError),
     #[error("ApplicationError - ChartOfAccountsError: {0}")]
     ChartOfAccountsError(#[from] core_accounting::chart_of_accounts::error::ChartOfAccountsError),
     #[error("ApplicationError - DepositError: {0}")]
@@ -48,4 +52,14 @@ pub enum ApplicationError {
     StorageError(#[from] crate::storage::error::StorageError),
     #[error("ApplicationError - ApplicantError: {0}")]
     ApplicantError(#[from] crate::applicant::error::ApplicantError),
+    #[error("ApplicationError - DocumentError: {0}")]
+    DocumentError(#[from] crate::document::error::DocumentError),
+    #[error("ApplicationError - ConfigChangeSetError: {0}")]
+    ConfigChangeSetError(#[from] crate::config_change::error::ConfigChangeSetError),
+    #[error("ApplicationError - DocumentNotFound")]
+    DocumentNotFound,
+    #[error("ApplicationError - DepositAccountNotFound")]
+    DepositAccountNotFound,
+    #[error("ApplicationError - DepositAccountHasOpenCreditFacility")]
+    DepositAccountHasOpenCreditFacility,
 }
@@ -12,18 +12,19 @@ use crate::{
     applicant::Applicants,
     audit::{Audit, AuditCursor, AuditEntry},
     authorization::{init as init_authz, AppAction, AppObject, AuditAction, Authorization},
+    config_change::ConfigChangeSets,
     credit::Credit,
     customer::Customers,
     customer_sync::CustomerSync,
     dashboard::Dashboard,
-    deposit::Deposits,
+    deposit::{DepositAccount, DepositAccountClosureSettlement, Deposits},
     document::Documents,
     general_ledger::GeneralLedger,
-    governance::Governance,
+    governance::{ApprovalProcess, Governance},
     job::Jobs,
     outbox::Outbox,
     price::Price,
-    primitives::Subject,
+    primitives::{ApprovalProcessId, DepositAccountId, DocumentId, Subject},
     report::Reports,
     storage::Storage,
     terms_template::TermsTemplates,
@@ -50,6 +51,7 @@ pub struct LanaApp {
     price: Price,
     report: Reports,
     terms_templates: TermsTemplates,
+    config_change_sets: ConfigChangeSets,
     documents: Documents,
     outbox: Outbox,
     governance: Governance,
@@ -67,7 +69,7 @@ impl LanaApp {
         let authz = init_authz(&pool, &audit).await?;
         let outbox = Outbox::init(&pool).await?;
         let dashboard = Dashboard::init(&pool, &authz, &jobs, &outbox).await?;
-        let governance = Governance::new(&pool, &authz, &outbox);
+        let governance = Governance::init(&pool, &authz, &outbox, &jobs).await?;
         let price = Price::new();
         let storage = Storage::new(&config.storage);
         let documents = Documents::new(&pool, &storage, &authz);
@@ -83,19 +85,23 @@ impl LanaApp {
             .expect("cala config");
         let cala = cala_ledger::CalaLedger::init(cala_config).await?;
         let journal_init = JournalInit::journal(&cala).await?;
-        let accounting = Accounting::new(
+        let accounting = Accounting::init(
             &pool,
             &authz,
             &cala,
             journal_init.journal_id,
             &storage,
             &jobs,
-        );
+            &governance,
+            &outbox,
+        )
+        .await?;
 
         StatementsInit::statements(
             accounting.trial_balances(),
             accounting.profit_and_loss(),
             accounting.balance_sheets(),
+            accounting.cash_flow_statements(),
         )
         .await?;
 
@@ -104,10 +110,12 @@ impl LanaApp {
         let customers = Customers::new(&pool, &authz, &outbox);
         let deposits = Deposits::init(
             &pool,
+            config.deposit,
             &authz,
             &outbox,
             &governance,
             &jobs,
+            &storage,
             &cala,
             journal_init.journal_id,
         )
@@ -131,6 +139,18 @@ impl LanaApp {
         )
         .await?;
         let terms_templates = TermsTemplates::new(&pool, &authz);
+        let config_change_sets = ConfigChangeSets::init(
+            &pool,
+            &authz,
+            &outbox,
+            &jobs,
+            &governance,
+            &accounting,
+            &credit,
+            &deposits,
+            &terms_templates,
+        )
+        .await?;
         jobs.start_poll().await?;
 
         Ok(Self {
@@ -148,6 +168,7 @@ impl LanaApp {
             credit,
             general_ledger,
             terms_templates,
+            config_change_sets,
             documents,
             outbox,
             governance,
@@ -208,6 +229,65 @@ impl LanaApp {
         &self.deposits
     }
 
+    /// Deposit account closure needs to know about credit facilities, which the
+    /// deposit module is not aware of, so the check lives here.
+    #[instrument(name = "lana.deposit.close_account", skip(self), err)]
+    pub async fn close_deposit_account(
+        &self,
+        sub: &Subject,
+        account_id: impl Into<DepositAccountId> + std::fmt::Debug,
+        settlement: DepositAccountClosureSettlement,
+    ) -> Result<DepositAccount, ApplicationError> {
+        let account_id = account_id.into();
+        let account = self
+            .deposits
+            .find_account_by_id(sub, account_id)
+            .await?
+            .ok_or(ApplicationError::DepositAccountNotFound)?;
+
+        let mut op = self.deposits.begin_op().await?;
+        if self
+            .credit
+            .has_open_facility_for_disbursal_account(&mut op, account.account_holder_id, account_id)
+            .await?
+        {
+            return Err(ApplicationError::DepositAccountHasOpenCreditFacility);
+        }
+
+        Ok(self
+            .deposits
+            .close_account_in_op(op, sub, account_id, settlement)
+            .await?)
+    }
+
+    /// Comments may reference documents, which the governance module is not
+    /// aware of, so they are checked here.
+    #[instrument(name = "lana.governance.comment_on_approval_process", skip(self), err)]
+    pub async fn comment_on_approval_process(
+        &self,
+        sub: &Subject,
+        process_id: impl Into<ApprovalProcessId> + std::fmt::Debug,
+        body: String,
+        document_ids: Vec<DocumentId>,
+    ) -> Result<ApprovalProcess, ApplicationError> {
+        for document_id in document_ids.iter() {
+            self.documents
+                .find_by_id(sub, *document_id)
+                .await?
+                .ok_or(ApplicationError::DocumentNotFound)?;
+        }
+
+        Ok(self
+            .governance
+            .comment_on_process(
+                sub,
+                process_id,
+                body,
+                document_ids.iter().map(ToString::to_string).collect(),
+            )
+            .await?)
+    }
+
     pub fn applicants(&self) -> &Applicants {
         &self.applicants
     }
@@ -228,6 +308,10 @@ impl LanaApp {
         &self.terms_templates
     }
 
+    pub fn config_change_sets(&self) -> &ConfigChangeSets {
+        &self.config_change_sets
+    }
+
     pub fn documents(&self) -> &Documents {
         &self.documents
     }
@@ -161,6 +161,27 @@ async fn add_permissions_for_admin(authz: &Authorization) -> Result<(), Authoriz
             GovernanceAction::COMMITTEE_REMOVE_MEMBER,
         )
         .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            GovernanceObject::all_committees(),
+            GovernanceAction::COMMITTEE_CREATE_DELEGATION,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            GovernanceObject::all_committees(),
+            GovernanceAction::COMMITTEE_REVOKE_DELEGATION,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            GovernanceObject::all_committees(),
+            GovernanceAction::COMMITTEE_LIST_DELEGATIONS,
+        )
+        .await?;
 
     authz
         .add_permission_to_role(
@@ -225,6 +246,23 @@ async fn add_permissions_for_admin(authz: &Authorization) -> Result<(), Authoriz
             GovernanceAction::APPROVAL_PROCESS_DENY,
         )
         .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            GovernanceObject::all_approval_processes(),
+            GovernanceAction::APPROVAL_PROCESS_COMMENT,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            Object::ConfigChangeSet,
+            ConfigChangeSetAction::Propose,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(&role, Object::ConfigChangeSet, ConfigChangeSetAction::Read)
+        .await?;
     Ok(())
 }
 
@@ -487,6 +525,55 @@ async fn add_permissions_for_bank_manager(authz: &Authorization) -> Result<(), A
             CoreAccountingAction::PROFIT_AND_LOSS_CONFIGURATION_UPDATE,
         )
         .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_cash_flow_statement(),
+            CoreAccountingAction::CASH_FLOW_STATEMENT_READ,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_cash_flow_statement(),
+            CoreAccountingAction::CASH_FLOW_STATEMENT_CREATE,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_cash_flow_statement_configuration(),
+            CoreAccountingAction::CASH_FLOW_STATEMENT_CONFIGURATION_READ,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_cash_flow_statement_configuration(),
+            CoreAccountingAction::CASH_FLOW_STATEMENT_CONFIGURATION_UPDATE,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_trial_balance_snapshots(),
+            CoreAccountingAction::TRIAL_BALANCE_SNAPSHOT_CREATE,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_trial_balance_snapshots(),
+            CoreAccountingAction::TRIAL_BALANCE_SNAPSHOT_READ,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_trial_balance_snapshots(),
+            CoreAccountingAction::TRIAL_BALANCE_SNAPSHOT_LIST,
+        )
+        .await?;
 
     authz
         .add_permission_to_role(
@@ -517,6 +604,13 @@ async fn add_permissions_for_bank_manager(authz: &Authorization) -> Result<(), A
             CoreAccountingAction::CHART_IMPORT_ACCOUNTS,
         )
         .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_charts(),
+            CoreAccountingAction::CHART_UPDATE_NODES,
+        )
+        .await?;
     authz
         .add_permission_to_role(
             &role,
@@ -531,6 +625,97 @@ async fn add_permissions_for_bank_manager(authz: &Authorization) -> Result<(), A
             CoreAccountingAction::MANUAL_TRANSACTION_CREATE,
         )
         .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_manual_transactions(),
+            CoreAccountingAction::MANUAL_TRANSACTION_REVERSE,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_recurring_transactions(),
+            CoreAccountingAction::RECURRING_TRANSACTION_CREATE,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_recurring_transactions(),
+            CoreAccountingAction::RECURRING_TRANSACTION_READ,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_recurring_transactions(),
+            CoreAccountingAction::RECURRING_TRANSACTION_LIST,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_recurring_transactions(),
+            CoreAccountingAction::RECURRING_TRANSACTION_PAUSE,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_recurring_transactions(),
+            CoreAccountingAction::RECURRING_TRANSACTION_RESUME,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_accounting_periods(),
+            CoreAccountingAction::ACCOUNTING_PERIOD_CREATE,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_accounting_periods(),
+            CoreAccountingAction::ACCOUNTING_PERIOD_READ,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_accounting_periods(),
+            CoreAccountingAction::ACCOUNTING_PERIOD_LIST,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_accounting_periods(),
+            CoreAccountingAction::ACCOUNTING_PERIOD_SOFT_CLOSE,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_accounting_periods(),
+            CoreAccountingAction::ACCOUNTING_PERIOD_REOPEN,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_accounting_periods(),
+            CoreAccountingAction::ACCOUNTING_PERIOD_CLOSE,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreAccountingObject::all_accounting_periods(),
+            CoreAccountingAction::ACCOUNTING_PERIOD_CLOSE_YEAR_END,
+        )
+        .await?;
     authz
         .add_permission_to_role(
             &role,
@@ -708,6 +893,153 @@ async fn add_permissions_for_bank_manager(authz: &Authorization) -> Result<(), A
             CoreDepositAction::WITHDRAWAL_LIST,
         )
         .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_deposit_account_statements(),
+            CoreDepositAction::DEPOSIT_ACCOUNT_STATEMENT_LIST,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_deposit_account_statements(),
+            CoreDepositAction::DEPOSIT_ACCOUNT_STATEMENT_GENERATE_DOWNLOAD_LINK,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_deposit_accounts(),
+            CoreDepositAction::DEPOSIT_ACCOUNT_UPDATE_KYC_LEVEL,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_deposit_accounts(),
+            CoreDepositAction::DEPOSIT_ACCOUNT_CLOSE,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_withdrawal_limit_overrides(),
+            CoreDepositAction::WITHDRAWAL_LIMIT_OVERRIDE_REQUEST,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_withdrawal_limit_overrides(),
+            CoreDepositAction::WITHDRAWAL_LIMIT_OVERRIDE_READ,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_withdrawal_limit_overrides(),
+            CoreDepositAction::WITHDRAWAL_LIMIT_OVERRIDE_LIST,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_bank_statement_imports(),
+            CoreDepositAction::BANK_STATEMENT_IMPORT_CREATE,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_bank_statement_imports(),
+            CoreDepositAction::BANK_STATEMENT_IMPORT_RESOLVE_EXCEPTION,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_bank_statement_imports(),
+            CoreDepositAction::BANK_STATEMENT_IMPORT_READ,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_bank_statement_imports(),
+            CoreDepositAction::BANK_STATEMENT_IMPORT_LIST,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_payout_batches(),
+            CoreDepositAction::PAYOUT_BATCH_CREATE,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_payout_batches(),
+            CoreDepositAction::PAYOUT_BATCH_MARK_SENT,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_payout_batches(),
+            CoreDepositAction::PAYOUT_BATCH_GENERATE_DOWNLOAD_LINK,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_payout_batches(),
+            CoreDepositAction::PAYOUT_BATCH_READ,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_payout_batches(),
+            CoreDepositAction::PAYOUT_BATCH_LIST,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_withdrawals(),
+            CoreDepositAction::WITHDRAWAL_RECORD_PAYOUT_RETURN,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_beneficiaries(),
+            CoreDepositAction::BENEFICIARY_REGISTER,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_beneficiaries(),
+            CoreDepositAction::BENEFICIARY_REVOKE,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_beneficiaries(),
+            CoreDepositAction::BENEFICIARY_READ,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_beneficiaries(),
+            CoreDepositAction::BENEFICIARY_LIST,
+        )
+        .await?;
 
     Ok(())
 }
@@ -760,6 +1092,76 @@ async fn add_permissions_for_accountant(authz: &Authorization) -> Result<(), Aut
             CoreDepositAction::WITHDRAWAL_LIST,
         )
         .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_deposit_account_statements(),
+            CoreDepositAction::DEPOSIT_ACCOUNT_STATEMENT_LIST,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_deposit_account_statements(),
+            CoreDepositAction::DEPOSIT_ACCOUNT_STATEMENT_GENERATE_DOWNLOAD_LINK,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_withdrawal_limit_overrides(),
+            CoreDepositAction::WITHDRAWAL_LIMIT_OVERRIDE_READ,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_withdrawal_limit_overrides(),
+            CoreDepositAction::WITHDRAWAL_LIMIT_OVERRIDE_LIST,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_bank_statement_imports(),
+            CoreDepositAction::BANK_STATEMENT_IMPORT_READ,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_bank_statement_imports(),
+            CoreDepositAction::BANK_STATEMENT_IMPORT_LIST,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_payout_batches(),
+            CoreDepositAction::PAYOUT_BATCH_READ,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_payout_batches(),
+            CoreDepositAction::PAYOUT_BATCH_LIST,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_beneficiaries(),
+            CoreDepositAction::BENEFICIARY_READ,
+        )
+        .await?;
+    authz
+        .add_permission_to_role(
+            &role,
+            CoreDepositObject::all_beneficiaries(),
+            CoreDepositAction::BENEFICIARY_LIST,
+        )
+        .await?;
     authz
         .add_permission_to_role(&role, Object::Document, DocumentAction::Read)
         .await?;
@@ -0,0 +1,238 @@
+use governance::{ApprovalProcess, ApprovalProcessStatus, ApprovalProcessType};
+
+use crate::{
+    accounting::Accounting,
+    accounting_init::constants::BALANCE_SHEET_NAME,
+    audit::{Audit, AuditSvc},
+    authorization::{ConfigChangeSetAction, Object},
+    credit::Credit,
+    deposit::Deposits,
+    governance::Governance,
+    primitives::{ConfigChangeSetId, Subject},
+    terms_template::TermsTemplates,
+};
+
+use super::{entity::*, error::ConfigChangeSetError, repo::ConfigChangeSetRepo};
+
+pub const APPROVE_CONFIG_CHANGE_SET_PROCESS: ApprovalProcessType =
+    ApprovalProcessType::new("config-change-set");
+
+#[derive(Clone)]
+pub struct ApplyConfigChangeSet {
+    repo: ConfigChangeSetRepo,
+    audit: Audit,
+    governance: Governance,
+    accounting: Accounting,
+    credit: Credit,
+    deposits: Deposits,
+    terms_templates: TermsTemplates,
+}
+
+impl ApplyConfigChangeSet {
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        repo: &ConfigChangeSetRepo,
+        audit: &Audit,
+        governance: &Governance,
+        accounting: &Accounting,
+        credit: &Credit,
+        deposits: &Deposits,
+        terms_templates: &TermsTemplates,
+    ) -> Self {
+        Self {
+            repo: repo.clone(),
+            audit: audit.clone(),
+            governance: governance.clone(),
+            accounting: accounting.clone(),
+            credit: credit.clone(),
+            deposits: deposits.clone(),
+            terms_templates: terms_templates.clone(),
+        }
+    }
+
+    pub async fn execute_from_svc(
+        &self,
+        change_set: &ConfigChangeSet,
+    ) -> Result<Option<ConfigChangeSet>, ConfigChangeSetError> {
+        if change_set.is_approved_or_denied().is_some() {
+            return Ok(None);
+        }
+
+        let process: ApprovalProcess = self
+            .governance
+            .find_all_approval_processes(&[change_set.approval_process_id])
+            .await?
+            .remove(&change_set.approval_process_id)
+            .expect("approval process not found");
+
+        let res = match process.status() {
+            ApprovalProcessStatus::Approved => Some(self.execute(change_set.id, true).await?),
+            ApprovalProcessStatus::Denied => Some(self.execute(change_set.id, false).await?),
+            _ => None,
+        };
+        Ok(res)
+    }
+
+    /// Records the outcome of the approval process and, once approved, applies
+    /// the change on behalf of the system. A change that can no longer be
+    /// applied is marked as failed rather than retried, while infrastructure
+    /// errors are returned so that the job tries again.
+    #[es_entity::retry_on_concurrent_modification]
+    pub async fn execute(
+        &self,
+        id: impl es_entity::RetryableInto<ConfigChangeSetId>,
+        approved: bool,
+    ) -> Result<ConfigChangeSet, ConfigChangeSetError> {
+        let id = id.into();
+        let mut change_set = self.repo.find_by_id(id).await?;
+
+        if change_set.is_approved_or_denied().is_none() {
+            let mut db = self.repo.begin_op().await?;
+            let audit_info = self
+                .audit
+                .record_system_entry_in_tx(
+                    db.tx(),
+                    Object::ConfigChangeSet,
+                    ConfigChangeSetAction::ConcludeApprovalProcess,
+                )
+                .await?;
+            if change_set
+                .approval_process_concluded(approved, audit_info)
+                .did_execute()
+            {
+                self.repo.update_in_op(&mut db, &mut change_set).await?;
+                db.commit().await?;
+            }
+        }
+
+        if change_set.status() != ConfigChangeSetStatus::PendingApproval
+            || change_set.is_approved_or_denied() != Some(true)
+        {
+            return Ok(change_set);
+        }
+
+        let result = match self.apply_change(&change_set.change).await {
+            Err(e) if e.is_transient() => return Err(e),
+            result => result,
+        };
+        let audit_info = self
+            .audit
+            .record_system_entry(Object::ConfigChangeSet, ConfigChangeSetAction::Apply)
+            .await?;
+        let recorded = match result {
+            Ok(()) => change_set.applied(audit_info),
+            Err(e) => change_set.apply_failed(e.to_string(), audit_info),
+        };
+        if recorded.did_execute() {
+            self.repo.update(&mut change_set).await?;
+        }
+
+        Ok(change_set)
+    }
+
+    /// The outcome is recorded after the change has been made, so a retry can
+    /// find it already in place. It is not made a second time then, as some
+    /// targets can only be configured once.
+    async fn apply_change(&self, change: &ConfigChange) -> Result<(), ConfigChangeSetError> {
+        let sub = Subject::System;
+        if self.current_value(&sub, change).await? == change.proposed_value() {
+            return Ok(());
+        }
+        match change {
+            ConfigChange::CreditModule { config } => {
+                let chart = self
+                    .accounting
+                    .chart_of_accounts()
+                    .find_by_id(config.chart_of_accounts_id)
+                    .await?;
+                self.credit
+                    .set_chart_of_accounts_integration_config(&sub, &chart, config.clone())
+                    .await?;
+            }
+            ConfigChange::DepositModule { config } => {
+                let chart = self
+                    .accounting
+                    .chart_of_accounts()
+                    .find_by_id(config.chart_of_accounts_id)
+                    .await?;
+                self.deposits
+                    .set_chart_of_accounts_integration_config(&sub, &chart, config.clone())
+                    .await?;
+            }
+            ConfigChange::BalanceSheet { config } => {
+                let chart = self
+                    .accounting
+                    .chart_of_accounts()
+                    .find_by_id(config.chart_of_accounts_id)
+                    .await?;
+                self.accounting
+                    .balance_sheets()
+                    .set_chart_of_accounts_integration_config(
+                        &sub,
+                        BALANCE_SHEET_NAME.to_string(),
+                        &chart,
+                        config.clone(),
+                    )
+                    .await?;
+            }
+            ConfigChange::TermsTemplate { id, values } => {
+                self.terms_templates
+                    .update_term_values(&sub, *id, *values)
+                    .await?;
+            }
+            ConfigChange::PolicyCommittee {
+                policy_id,
+                committee_id,
+                threshold,
+            } => {
+                self.governance
+                    .assign_committee_to_policy(&sub, *policy_id, *committee_id, *threshold)
+                    .await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Reads the value the change would replace as `sub` sees it. Proposals
+    /// read it with the proposer's permissions, so the diff shows what they
+    /// were looking at.
+    pub(super) async fn current_value(
+        &self,
+        sub: &Subject,
+        change: &ConfigChange,
+    ) -> Result<serde_json::Value, ConfigChangeSetError> {
+        let value = match change {
+            ConfigChange::CreditModule { .. } => serde_json::to_value(
+                self.credit
+                    .get_chart_of_accounts_integration_config(sub)
+                    .await?,
+            ),
+            ConfigChange::DepositModule { .. } => serde_json::to_value(
+                self.deposits
+                    .get_chart_of_accounts_integration_config(sub)
+                    .await?,
+            ),
+            ConfigChange::BalanceSheet { .. } => serde_json::to_value(
+                self.accounting
+                    .balance_sheets()
+                    .get_chart_of_accounts_integration_config(sub, BALANCE_SHEET_NAME.to_string())
+                    .await?,
+            ),
+            ConfigChange::TermsTemplate { id, .. } => serde_json::to_value(
+                self.terms_templates
+                    .find_by_id(sub, *id)
+                    .await?
+                    .ok_or(ConfigChangeSetError::TermsTemplateNotFound)?
+                    .values,
+            ),
+            ConfigChange::PolicyCommittee { policy_id, .. } => serde_json::to_value(
+                self.governance
+                    .find_policy(sub, *policy_id)
+                    .await?
+                    .ok_or(ConfigChangeSetError::PolicyNotFound)?
+                    .rules,
+            ),
+        };
+        Ok(value.expect("could not serialize current config"))
+    }
+}
@@ -0,0 +1,405 @@
+use derive_builder::Builder;
+use serde::{Deserialize, Serialize};
+
+use es_entity::*;
+
+use crate::{
+    audit::AuditInfo, balance_sheet::ChartOfAccountsIntegrationConfig as BalanceSheetConfig,
+    credit::ChartOfAccountsIntegrationConfig as CreditConfig,
+    deposit::ChartOfAccountsIntegrationConfig as DepositConfig, primitives::*, terms::TermValues,
+};
+
+#[derive(async_graphql::Enum, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConfigChangeSetStatus {
+    PendingApproval,
+    Applied,
+    Denied,
+    Failed,
+}
+
+#[derive(async_graphql::Enum, Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum ConfigChangeKind {
+    CreditModule,
+    DepositModule,
+    BalanceSheet,
+    TermsTemplate,
+    PolicyCommittee,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ConfigChange {
+    CreditModule {
+        config: CreditConfig,
+    },
+    DepositModule {
+        config: DepositConfig,
+    },
+    BalanceSheet {
+        config: BalanceSheetConfig,
+    },
+    TermsTemplate {
+        id: TermsTemplateId,
+        values: TermValues,
+    },
+    PolicyCommittee {
+        policy_id: PolicyId,
+        committee_id: CommitteeId,
+        threshold: usize,
+    },
+}
+
+impl ConfigChange {
+    pub fn kind(&self) -> ConfigChangeKind {
+        match self {
+            ConfigChange::CreditModule { .. } => ConfigChangeKind::CreditModule,
+            ConfigChange::DepositModule { .. } => ConfigChangeKind::DepositModule,
+            ConfigChange::BalanceSheet { .. } => ConfigChangeKind::BalanceSheet,
+            ConfigChange::TermsTemplate { .. } => ConfigChangeKind::TermsTemplate,
+            ConfigChange::PolicyCommittee { .. } => ConfigChangeKind::PolicyCommittee,
+        }
+    }
+
+    /// The value the change would leave in place, shaped like the value read
+    /// back from the owning module so the two can be diffed.
+    pub fn proposed_value(&self) -> serde_json::Value {
+        let value = match self {
+            ConfigChange::CreditModule { config } => serde_json::to_value(config),
+            ConfigChange::DepositModule { config } => serde_json::to_value(config),
+            ConfigChange::BalanceSheet { config } => serde_json::to_value(config),
+            ConfigChange::TermsTemplate { values, .. } => serde_json::to_value(values),
+            ConfigChange::PolicyCommittee {
+                committee_id,
+                threshold,
+                ..
+            } => serde_json::to_value(governance::ApprovalRules::CommitteeThreshold {
+                committee_id: *committee_id,
+                threshold: *threshold,
+            }),
+        };
+        value.expect("could not serialize config change")
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigDiffEntry {
+    pub path: String,
+    pub old_value: Option<serde_json::Value>,
+    pub new_value: Option<serde_json::Value>,
+}
+
+/// Lists every leaf that differs between `old` and `new`. Paths are dotted
+/// object keys, with array positions as plain indices.
+pub fn diff(old: &serde_json::Value, new: &serde_json::Value) -> Vec<ConfigDiffEntry> {
+    let mut old_leaves = Vec::new();
+    flatten("", old, &mut old_leaves);
+    let mut new_leaves = Vec::new();
+    flatten("", new, &mut new_leaves);
+
+    let mut entries: Vec<ConfigDiffEntry> = Vec::new();
+    for (path, old_value) in old_leaves.iter() {
+        let new_value = new_leaves
+            .iter()
+            .find(|(p, _)| p == path)
+            .map(|(_, v)| v.clone());
+        if new_value.as_ref() != Some(old_value) {
+            entries.push(ConfigDiffEntry {
+                path: path.clone(),
+                old_value: Some(old_value.clone()),
+                new_value,
+            });
+        }
+    }
+    for (path, new_value) in new_leaves.into_iter() {
+        if !old_leaves.iter().any(|(p, _)| p == &path) {
+            entries.push(ConfigDiffEntry {
+                path,
+                old_value: None,
+                new_value: Some(new_value),
+            });
+        }
+    }
+    entries.sort_by(|a, b| a.path.cmp(&b.path));
+    entries
+}
+
+fn flatten(prefix: &str, value: &serde_json::Value, out: &mut Vec<(String, serde_json::Value)>) {
+    let join = |key: &str| {
+        if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{prefix}.{key}")
+        }
+    };
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, value) in map.iter() {
+                flatten(&join(key), value, out);
+            }
+        }
+        serde_json::Value::Array(items) => {
+            for (idx, value) in items.iter().enumerate() {
+                flatten(&join(&idx.to_string()), value, out);
+            }
+        }
+        serde_json::Value::Null if prefix.is_empty() => {}
+        leaf => out.push((prefix.to_string(), leaf.clone())),
+    }
+}
+
+#[derive(EsEvent, Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+#[es_event(id = "ConfigChangeSetId")]
+pub enum ConfigChangeSetEvent {
+    Initialized {
+        id: ConfigChangeSetId,
+        approval_process_id: ApprovalProcessId,
+        change: ConfigChange,
+        previous: serde_json::Value,
+        proposed_by: String,
+        audit_info: AuditInfo,
+    },
+    ApprovalProcessConcluded {
+        approval_process_id: ApprovalProcessId,
+        approved: bool,
+        audit_info: AuditInfo,
+    },
+    Applied {
+        audit_info: AuditInfo,
+    },
+    ApplyFailed {
+        reason: String,
+        audit_info: AuditInfo,
+    },
+}
+
+#[derive(EsEntity, Builder)]
+#[builder(pattern = "owned", build_fn(error = "EsEntityError"))]
+pub struct ConfigChangeSet {
+    pub id: ConfigChangeSetId,
+    pub approval_process_id: ApprovalProcessId,
+    pub change: ConfigChange,
+    pub previous: serde_json::Value,
+    pub proposed_by: String,
+    events: EntityEvents<ConfigChangeSetEvent>,
+}
+
+impl ConfigChangeSet {
+    pub fn created_at(&self) -> chrono::DateTime<chrono::Utc> {
+        self.events
+            .entity_first_persisted_at()
+            .expect("ConfigChangeSet has never been persisted")
+    }
+
+    pub fn kind(&self) -> ConfigChangeKind {
+        self.change.kind()
+    }
+
+    pub fn diff(&self) -> Vec<ConfigDiffEntry> {
+        diff(&self.previous, &self.change.proposed_value())
+    }
+
+    pub fn is_approved_or_denied(&self) -> Option<bool> {
+        self.events.iter_all().find_map(|e| {
+            if let ConfigChangeSetEvent::ApprovalProcessConcluded { approved, .. } = e {
+                Some(*approved)
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn failure_reason(&self) -> Option<&str> {
+        self.events.iter_all().rev().find_map(|e| {
+            if let ConfigChangeSetEvent::ApplyFailed { reason, .. } = e {
+                Some(reason.as_str())
+            } else {
+                None
+            }
+        })
+    }
+
+    pub fn status(&self) -> ConfigChangeSetStatus {
+        for event in self.events.iter_all().rev() {
+            match event {
+                ConfigChangeSetEvent::Applied { .. } => return ConfigChangeSetStatus::Applied,
+                ConfigChangeSetEvent::ApplyFailed { .. } => return ConfigChangeSetStatus::Failed,
+                ConfigChangeSetEvent::ApprovalProcessConcluded {
+                    approved: false, ..
+                } => return ConfigChangeSetStatus::Denied,
+                _ => {}
+            }
+        }
+        ConfigChangeSetStatus::PendingApproval
+    }
+
+    /// Records the outcome of the approval process. When approved the caller
+    /// is expected to apply the change and record the result.
+    pub fn approval_process_concluded(
+        &mut self,
+        approved: bool,
+        audit_info: AuditInfo,
+    ) -> Idempotent<()> {
+        idempotency_guard!(
+            self.events.iter_all(),
+            ConfigChangeSetEvent::ApprovalProcessConcluded { .. }
+        );
+        self.events
+            .push(ConfigChangeSetEvent::ApprovalProcessConcluded {
+                approval_process_id: self.approval_process_id,
+                approved,
+                audit_info,
+            });
+        Idempotent::Executed(())
+    }
+
+    fn is_settled(&self) -> bool {
+        self.events.iter_all().any(|e| {
+            matches!(
+                e,
+                ConfigChangeSetEvent::Applied { .. } | ConfigChangeSetEvent::ApplyFailed { .. }
+            )
+        })
+    }
+
+    pub fn applied(&mut self, audit_info: AuditInfo) -> Idempotent<()> {
+        if self.is_settled() {
+            return Idempotent::Ignored;
+        }
+        self.events
+            .push(ConfigChangeSetEvent::Applied { audit_info });
+        Idempotent::Executed(())
+    }
+
+    pub fn apply_failed(&mut self, reason: String, audit_info: AuditInfo) -> Idempotent<()> {
+        if self.is_settled() {
+            return Idempotent::Ignored;
+        }
+        self.events
+            .push(ConfigChangeSetEvent::ApplyFailed { reason, audit_info });
+        Idempotent::Executed(())
+    }
+}
+
+impl TryFromEvents<ConfigChangeSetEvent> for ConfigChangeSet {
+    fn try_from_events(events: EntityEvents<ConfigChangeSetEvent>) -> Result<Self, EsEntityError> {
+        let mut builder = ConfigChangeSetBuilder::default();
+        for event in events.iter_all() {
+            match event {
+                ConfigChangeSetEvent::Initialized {
+                    id,
+                    approval_process_id,
+                    change,
+                    previous,
+                    proposed_by,
+                    ..
+                } => {
+                    builder = builder
+                        .id(*id)
+                        .approval_process_id(*approval_process_id)
+                        .change(change.clone())
+                        .previous(previous.clone())
+                        .proposed_by(proposed_by.clone())
+                }
+                ConfigChangeSetEvent::ApprovalProcessConcluded { .. } => {}
+                ConfigChangeSetEvent::Applied { .. } => {}
+                ConfigChangeSetEvent::ApplyFailed { .. } => {}
+            }
+        }
+        builder.events(events).build()
+    }
+}
+
+#[derive(Debug, Builder)]
+pub struct NewConfigChangeSet {
+    #[builder(setter(into))]
+    pub(super) id: ConfigChangeSetId,
+    #[builder(setter(into))]
+    pub(super) approval_process_id: ApprovalProcessId,
+    change: ConfigChange,
+    previous: serde_json::Value,
+    #[builder(setter(into))]
+    proposed_by: String,
+    #[builder(setter(into))]
+    pub audit_info: AuditInfo,
+}
+
+impl NewConfigChangeSet {
+    pub fn builder() -> NewConfigChangeSetBuilder {
+        NewConfigChangeSetBuilder::default()
+    }
+}
+
+impl IntoEvents<ConfigChangeSetEvent> for NewConfigChangeSet {
+    fn into_events(self) -> EntityEvents<ConfigChangeSetEvent> {
+        EntityEvents::init(
+            self.id,
+            [ConfigChangeSetEvent::Initialized {
+                id: self.id,
+                approval_process_id: self.approval_process_id,
+                change: self.change,
+                previous: self.previous,
+                proposed_by: self.proposed_by,
+                audit_info: self.audit_info,
+            }],
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn diff_lists_changed_added_and_removed_leaves() {
+        let old = json!({
+            "rate": "5",
+            "duration": { "period": "months", "units": 12 },
+            "dropped": true,
+        });
+        let new = json!({
+            "rate": "6",
+            "duration": { "period": "months", "units": 24 },
+            "added": "x",
+        });
+
+        let entries = diff(&old, &new);
+        assert_eq!(
+            entries,
+            vec![
+                ConfigDiffEntry {
+                    path: "added".to_string(),
+                    old_value: None,
+                    new_value: Some(json!("x")),
+                },
+                ConfigDiffEntry {
+                    path: "dropped".to_string(),
+                    old_value: Some(json!(true)),
+                    new_value: None,
+                },
+                ConfigDiffEntry {
+                    path: "duration.units".to_string(),
+                    old_value: Some(json!(12)),
+                    new_value: Some(json!(24)),
+                },
+                ConfigDiffEntry {
+                    path: "rate".to_string(),
+                    old_value: Some(json!("5")),
+                    new_value: Some(json!("6")),
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn diff_against_missing_config_lists_every_leaf() {
+        let new = json!({ "a": 1, "b": [1, 2] });
+
+        let entries = diff(&serde_json::Value::Null, &new);
+        assert_eq!(entries.len(), 3);
+        assert!(entries.iter().all(|e| e.old_value.is_none()));
+        assert_eq!(entries[1].path, "b.0");
+    }
+}
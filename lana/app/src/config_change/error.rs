@@ -0,0 +1,74 @@
+use thiserror::Error;
+
+use crate::primitives::ConfigChangeSetId;
+
+#[derive(Error, Debug)]
+pub enum ConfigChangeSetError {
+    #[error("ConfigChangeSetError - Sqlx: {0}")]
+    Sqlx(#[from] sqlx::Error),
+    #[error("ConfigChangeSetError - EsEntityError: {0}")]
+    EsEntityError(es_entity::EsEntityError),
+    #[error("ConfigChangeSetError - CursorDestructureError: {0}")]
+    CursorDestructureError(#[from] es_entity::CursorDestructureError),
+    #[error("ConfigChangeSetError - CouldNotFindById: {0}")]
+    CouldNotFindById(ConfigChangeSetId),
+    #[error("ConfigChangeSetError - AuthorizationError: {0}")]
+    AuthorizationError(#[from] crate::authorization::error::AuthorizationError),
+    #[error("ConfigChangeSetError - AuditError: {0}")]
+    AuditError(#[from] crate::audit::error::AuditError),
+    #[error("ConfigChangeSetError - JobError: {0}")]
+    JobError(#[from] crate::job::error::JobError),
+    #[error("ConfigChangeSetError - GovernanceError: {0}")]
+    GovernanceError(#[from] governance::error::GovernanceError),
+    #[error("ConfigChangeSetError - CreditError: {0}")]
+    CreditError(#[from] crate::credit::error::CoreCreditError),
+    #[error("ConfigChangeSetError - DepositError: {0}")]
+    DepositError(#[from] crate::deposit::error::CoreDepositError),
+    #[error("ConfigChangeSetError - BalanceSheetError: {0}")]
+    BalanceSheetError(#[from] crate::balance_sheet::error::BalanceSheetError),
+    #[error("ConfigChangeSetError - ChartOfAccountsError: {0}")]
+    ChartOfAccountsError(#[from] crate::accounting::chart_of_accounts::error::ChartOfAccountsError),
+    #[error("ConfigChangeSetError - TermsTemplateError: {0}")]
+    TermsTemplateError(#[from] crate::terms_template::error::TermsTemplateError),
+    #[error("ConfigChangeSetError - TermsTemplateNotFound")]
+    TermsTemplateNotFound,
+    #[error("ConfigChangeSetError - PolicyNotFound")]
+    PolicyNotFound,
+}
+
+es_entity::from_es_entity_error!(ConfigChangeSetError);
+
+impl ConfigChangeSetError {
+    /// Whether the error came from the infrastructure rather than the change
+    /// itself, so that applying the change again may still succeed.
+    pub fn is_transient(&self) -> bool {
+        let mut source: Option<&(dyn std::error::Error + 'static)> = Some(self);
+        while let Some(e) = source {
+            if e.is::<sqlx::Error>()
+                || matches!(
+                    e.downcast_ref::<es_entity::EsEntityError>(),
+                    Some(es_entity::EsEntityError::ConcurrentModification)
+                )
+            {
+                return true;
+            }
+            source = e.source();
+        }
+        self.was_concurrent_modification()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn database_errors_are_transient() {
+        assert!(ConfigChangeSetError::Sqlx(sqlx::Error::PoolTimedOut).is_transient());
+        assert!(ConfigChangeSetError::TermsTemplateError(
+            crate::terms_template::error::TermsTemplateError::Sqlx(sqlx::Error::PoolTimedOut)
+        )
+        .is_transient());
+        assert!(!ConfigChangeSetError::PolicyNotFound.is_transient());
+    }
+}
@@ -0,0 +1,96 @@
+use async_trait::async_trait;
+use futures::StreamExt;
+use serde::{Deserialize, Serialize};
+
+use governance::GovernanceEvent;
+use lana_events::LanaEvent;
+
+use crate::{job::*, outbox::Outbox};
+
+use super::apply::{ApplyConfigChangeSet, APPROVE_CONFIG_CHANGE_SET_PROCESS};
+
+#[derive(Clone, Serialize, Deserialize)]
+pub struct ConfigChangeSetApprovalJobConfig;
+
+impl JobConfig for ConfigChangeSetApprovalJobConfig {
+    type Initializer = ConfigChangeSetApprovalInitializer;
+}
+
+pub struct ConfigChangeSetApprovalInitializer {
+    outbox: Outbox,
+    process: ApplyConfigChangeSet,
+}
+
+impl ConfigChangeSetApprovalInitializer {
+    pub fn new(outbox: &Outbox, process: &ApplyConfigChangeSet) -> Self {
+        Self {
+            outbox: outbox.clone(),
+            process: process.clone(),
+        }
+    }
+}
+
+const CONFIG_CHANGE_SET_APPROVE_JOB: JobType = JobType::new("config-change-set-approval");
+impl JobInitializer for ConfigChangeSetApprovalInitializer {
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        CONFIG_CHANGE_SET_APPROVE_JOB
+    }
+
+    fn init(&self, _: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(ConfigChangeSetApprovalJobRunner {
+            outbox: self.outbox.clone(),
+            process: self.process.clone(),
+        }))
+    }
+
+    fn retry_on_error_settings() -> RetrySettings
+    where
+        Self: Sized,
+    {
+        RetrySettings::repeat_indefinitely()
+    }
+}
+
+#[derive(Default, Clone, Copy, Deserialize, Serialize)]
+struct ConfigChangeSetApprovalJobData {
+    sequence: outbox::EventSequence,
+}
+
+pub struct ConfigChangeSetApprovalJobRunner {
+    outbox: Outbox,
+    process: ApplyConfigChangeSet,
+}
+
+#[async_trait]
+impl JobRunner for ConfigChangeSetApprovalJobRunner {
+    async fn run(
+        &self,
+        mut current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let mut state = current_job
+            .execution_state::<ConfigChangeSetApprovalJobData>()?
+            .unwrap_or_default();
+        let mut stream = self.outbox.listen_persisted(Some(state.sequence)).await?;
+
+        while let Some(message) = stream.next().await {
+            match message.payload {
+                Some(LanaEvent::Governance(GovernanceEvent::ApprovalProcessConcluded {
+                    id,
+                    approved,
+                    ref process_type,
+                    ..
+                })) if process_type == &APPROVE_CONFIG_CHANGE_SET_PROCESS => {
+                    self.process.execute(id, approved).await?;
+                    state.sequence = message.sequence;
+                    current_job.update_execution_state(state).await?;
+                }
+                _ => {}
+            }
+        }
+
+        Ok(JobCompletion::RescheduleNow)
+    }
+}
@@ -0,0 +1,155 @@
+mod apply;
+pub mod entity;
+pub mod error;
+mod job;
+mod repo;
+
+use std::collections::HashMap;
+
+use authz::PermissionCheck;
+use tracing::instrument;
+
+use crate::{
+    accounting::Accounting,
+    authorization::{Authorization, ConfigChangeSetAction, Object},
+    credit::Credit,
+    deposit::Deposits,
+    governance::Governance,
+    job::Jobs,
+    outbox::Outbox,
+    primitives::{ConfigChangeSetId, Subject},
+    terms_template::TermsTemplates,
+};
+
+pub use apply::APPROVE_CONFIG_CHANGE_SET_PROCESS;
+pub use entity::*;
+use error::ConfigChangeSetError;
+use repo::ConfigChangeSetRepo;
+
+/// Critical configuration is not changed directly. A change is proposed as a
+/// change-set that goes through the `config-change-set` approval policy and is
+/// applied by the system once approved.
+#[derive(Clone)]
+pub struct ConfigChangeSets {
+    authz: Authorization,
+    repo: ConfigChangeSetRepo,
+    governance: Governance,
+    apply: apply::ApplyConfigChangeSet,
+}
+
+impl ConfigChangeSets {
+    #[allow(clippy::too_many_arguments)]
+    pub async fn init(
+        pool: &sqlx::PgPool,
+        authz: &Authorization,
+        outbox: &Outbox,
+        jobs: &Jobs,
+        governance: &Governance,
+        accounting: &Accounting,
+        credit: &Credit,
+        deposits: &Deposits,
+        terms_templates: &TermsTemplates,
+    ) -> Result<Self, ConfigChangeSetError> {
+        let repo = ConfigChangeSetRepo::new(pool);
+        let apply = apply::ApplyConfigChangeSet::new(
+            &repo,
+            authz.audit(),
+            governance,
+            accounting,
+            credit,
+            deposits,
+            terms_templates,
+        );
+        jobs.add_initializer_and_spawn_unique(
+            job::ConfigChangeSetApprovalInitializer::new(outbox, &apply),
+            job::ConfigChangeSetApprovalJobConfig,
+        )
+        .await?;
+
+        match governance
+            .init_policy(APPROVE_CONFIG_CHANGE_SET_PROCESS)
+            .await
+        {
+            Err(governance::error::GovernanceError::PolicyError(
+                governance::policy_error::PolicyError::DuplicateApprovalProcessType,
+            )) => (),
+            Err(e) => return Err(e.into()),
+            _ => (),
+        }
+
+        Ok(Self {
+            authz: authz.clone(),
+            repo,
+            governance: governance.clone(),
+            apply,
+        })
+    }
+
+    #[instrument(name = "config_change.propose", skip(self), err)]
+    pub async fn propose(
+        &self,
+        sub: &Subject,
+        change: ConfigChange,
+    ) -> Result<ConfigChangeSet, ConfigChangeSetError> {
+        let audit_info = self
+            .authz
+            .enforce_permission(sub, Object::ConfigChangeSet, ConfigChangeSetAction::Propose)
+            .await?;
+
+        let previous = self.apply.current_value(sub, &change).await?;
+
+        let id = ConfigChangeSetId::new();
+        let new_change_set = NewConfigChangeSet::builder()
+            .id(id)
+            .approval_process_id(id)
+            .change(change)
+            .previous(previous)
+            .proposed_by(sub.to_string())
+            .audit_info(audit_info)
+            .build()
+            .expect("Could not build config change set");
+
+        let mut db = self.repo.begin_op().await?;
+        self.governance
+            .start_process(
+                &mut db,
+                id,
+                id.to_string(),
+                APPROVE_CONFIG_CHANGE_SET_PROCESS,
+                None,
+                sub,
+            )
+            .await?;
+        let change_set = self.repo.create_in_op(&mut db, new_change_set).await?;
+        db.commit().await?;
+
+        Ok(self
+            .apply
+            .execute_from_svc(&change_set)
+            .await?
+            .unwrap_or(change_set))
+    }
+
+    #[instrument(name = "config_change.find_by_id", skip(self), err)]
+    pub async fn find_by_id(
+        &self,
+        sub: &Subject,
+        id: impl Into<ConfigChangeSetId> + std::fmt::Debug,
+    ) -> Result<Option<ConfigChangeSet>, ConfigChangeSetError> {
+        self.authz
+            .enforce_permission(sub, Object::ConfigChangeSet, ConfigChangeSetAction::Read)
+            .await?;
+        match self.repo.find_by_id(id.into()).await {
+            Ok(change_set) => Ok(Some(change_set)),
+            Err(ConfigChangeSetError::CouldNotFindById(_)) => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub async fn find_all<T: From<ConfigChangeSet>>(
+        &self,
+        ids: &[ConfigChangeSetId],
+    ) -> Result<HashMap<ConfigChangeSetId, T>, ConfigChangeSetError> {
+        self.repo.find_all(ids).await
+    }
+}
@@ -0,0 +1,23 @@
+use sqlx::PgPool;
+
+use es_entity::*;
+
+use crate::primitives::*;
+
+use super::{entity::*, error::*};
+
+#[derive(EsRepo, Clone)]
+#[es_repo(
+    entity = "ConfigChangeSet",
+    err = "ConfigChangeSetError",
+    columns(approval_process_id(ty = "ApprovalProcessId"))
+)]
+pub struct ConfigChangeSetRepo {
+    pool: PgPool,
+}
+
+impl ConfigChangeSetRepo {
+    pub fn new(pool: &PgPool) -> Self {
+        Self { pool: pool.clone() }
+    }
+}
@@ -5,6 +5,7 @@ pub mod accounting_init;
 pub mod app;
 pub mod applicant;
 pub mod authorization;
+pub mod config_change;
 pub mod document;
 pub mod general_ledger;
 pub mod primitives;
@@ -62,9 +63,14 @@ pub mod governance {
     use crate::authorization::Authorization;
     use lana_events::LanaEvent;
     pub type Governance = governance::Governance<Authorization, LanaEvent>;
+    pub use crate::config_change::APPROVE_CONFIG_CHANGE_SET_PROCESS;
     pub use crate::credit::APPROVE_CREDIT_FACILITY_PROCESS;
     pub use crate::credit::APPROVE_DISBURSAL_PROCESS;
+    pub use core_accounting::APPROVE_MANUAL_TRANSACTION_PROCESS;
+    pub use deposit::APPROVE_BENEFICIARY_PROCESS;
+    pub use deposit::APPROVE_WITHDRAWAL_LIMIT_OVERRIDE_PROCESS;
     pub use deposit::APPROVE_WITHDRAWAL_PROCESS;
+    pub use governance::ApprovalProcess;
 }
 
 pub mod audit {
@@ -80,10 +86,22 @@ pub mod audit {
 
 pub mod deposit {
     pub use deposit::{
-        error, ChartOfAccountsIntegrationConfig, CoreDepositEvent, Deposit, DepositAccount,
-        DepositAccountBalance, DepositAccountHistoryCursor, DepositAccountHistoryEntry, DepositId,
-        DepositsByCreatedAtCursor, Withdrawal, WithdrawalId, WithdrawalStatus,
-        WithdrawalsByCreatedAtCursor,
+        error, BankStatementCsvMapping, BankStatementException, BankStatementExceptionId,
+        BankStatementExceptionReason, BankStatementExceptionStatus,
+        BankStatementExceptionsByCreatedAtCursor, BankStatementFormat, BankStatementImport,
+        BankStatementImportId, BankStatementImportSummary, BankStatementImportsByCreatedAtCursor,
+        BankStatementLine, Beneficiary, BeneficiaryDetails, BeneficiaryId, BeneficiaryStatus,
+        ChartOfAccountsIntegrationConfig, CoreDepositEvent, Deposit, DepositAccount,
+        DepositAccountBalance, DepositAccountHistoryCursor, DepositAccountHistoryEntry,
+        DepositAccountStatement, DepositAccountStatementDownloadLink,
+        DepositAccountStatementFormat, DepositAccountStatementId, DepositAccountStatementStatus,
+        DepositAccountStatementSummary, DepositConfig, DepositId, DepositsByCreatedAtCursor,
+        PayoutAccountType, PayoutBatch, PayoutBatchDownloadLink, PayoutBatchId, PayoutBatchItem,
+        PayoutBatchStatus, PayoutBatchesByCreatedAtCursor, PayoutConfig, PayoutDestination,
+        PayoutFileFormat, Withdrawal, WithdrawalBeneficiary, WithdrawalId, WithdrawalLimitOverride,
+        WithdrawalLimitOverrideId, WithdrawalLimitOverrideStatus, WithdrawalLimits,
+        WithdrawalLimitsByAccountType, WithdrawalLimitsConfig, WithdrawalPayoutStatus,
+        WithdrawalStatus, WithdrawalsByCreatedAtCursor,
     };
 
     pub type Deposits =
@@ -92,12 +110,18 @@ pub mod deposit {
 
 pub mod accounting {
     pub use core_accounting::{
-        chart_of_accounts, csv, error, journal, ledger_account, ledger_transaction,
-        manual_transaction, transaction_templates, AccountCode, AccountingCsvId, CalaAccountId,
-        LedgerAccountId, TransactionTemplateId, {tree, Chart},
+        accounting_period, chart_of_accounts, comparative, csv, error, journal, ledger_account,
+        ledger_transaction, manual_transaction, recurring_transaction, transaction_templates,
+        AccountCode, AccountName, AccountingCsvId, AccountingPeriod, AccountingPeriodStatus,
+        CalaAccountId, LedgerAccountId, ManualTransaction, ManualTransactionImportError,
+        ManualTransactionStatus, RecurrenceSchedule, RecurringTransaction,
+        RecurringTransactionStatus, TransactionTemplateId, {tree, Chart},
     };
 
-    pub type Accounting = core_accounting::CoreAccounting<crate::authorization::Authorization>;
+    pub type Accounting = core_accounting::CoreAccounting<
+        crate::authorization::Authorization,
+        lana_events::LanaEvent,
+    >;
     pub type ChartOfAccounts =
         core_accounting::ChartOfAccounts<crate::authorization::Authorization>;
 }
@@ -113,6 +137,12 @@ pub mod balance_sheet {
     pub type BalanceSheets = core_accounting::BalanceSheets<crate::authorization::Authorization>;
 }
 
+pub mod cash_flow_statement {
+    pub use core_accounting::cash_flow_statement::*;
+    pub type CashFlowStatements =
+        core_accounting::CashFlowStatements<crate::authorization::Authorization>;
+}
+
 pub mod trial_balance {
     pub use core_accounting::trial_balance::*;
     pub type TrialBalances = core_accounting::TrialBalances<crate::authorization::Authorization>;
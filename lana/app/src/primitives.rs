@@ -2,7 +2,10 @@ use serde::{Deserialize, Serialize};
 
 use std::fmt;
 
-pub use core_accounting::{BalanceRange, Chart, ChartId, LedgerTransactionId, ManualTransactionId};
+pub use core_accounting::{
+    AccountingPeriodId, BalanceRange, Chart, ChartId, LedgerTransactionId, ManualTransactionId,
+    RecurringTransactionId, TrialBalanceSnapshotId,
+};
 pub use core_credit::{
     CollateralAction, CreditFacilityId, CreditFacilityStatus, DisbursalId, DisbursalStatus,
     PaymentId,
@@ -11,8 +14,12 @@ pub use core_customer::CustomerId;
 pub use core_money::*;
 pub use core_price::PriceOfOneBTC;
 pub use core_user::UserId;
-pub use deposit::{DepositAccountHolderId, DepositAccountId, DepositId, WithdrawalId};
-pub use governance::{ApprovalProcessId, CommitteeId, CommitteeMemberId, PolicyId};
+pub use deposit::{
+    BankStatementExceptionId, BankStatementImportId, BeneficiaryId, DepositAccountHolderId,
+    DepositAccountId, DepositAccountStatementId, DepositId, PayoutBatchId, WithdrawalId,
+    WithdrawalLimitOverrideId,
+};
+pub use governance::{ApprovalProcessId, CommitteeId, CommitteeMemberId, DelegationId, PolicyId};
 pub use job::JobId;
 pub use lana_ids::*;
 pub use rbac_types::{LanaRole, Role, Subject};
@@ -7,7 +7,7 @@ pub use lana_app::deposit::{
     DepositAccountHistoryEntry as DomainDepositAccountHistoryEntry,
 };
 
-use super::{deposit::*, deposit_account_history::*, withdrawal::*};
+use super::{deposit::*, deposit_account_history::*, deposit_account_statement::*, withdrawal::*};
 
 #[derive(SimpleObject, Clone)]
 #[graphql(complex)]
@@ -81,6 +81,22 @@ impl DepositAccount {
         Ok(withdrawals.into_iter().map(Withdrawal::from).collect())
     }
 
+    async fn statements(
+        &self,
+        ctx: &Context<'_>,
+    ) -> async_graphql::Result<Vec<DepositAccountStatement>> {
+        let (app, sub) = crate::app_and_sub_from_ctx!(ctx);
+        let statements = app
+            .deposits()
+            .for_subject(sub)?
+            .list_statements_for_account(self.entity.id)
+            .await?;
+        Ok(statements
+            .into_iter()
+            .map(DepositAccountStatement::from)
+            .collect())
+    }
+
     async fn history(
         &self,
         ctx: &Context<'_>,
@@ -6,6 +6,7 @@ mod customer;
 mod deposit;
 mod deposit_account;
 mod deposit_account_history;
+mod deposit_account_statement;
 mod price;
 mod schema;
 mod terms;
@@ -5,8 +5,9 @@ pub use std::sync::Arc;
 
 pub use lana_app::{
     primitives::{
-        CreditFacilityId, CreditFacilityStatus, CustomerId, DepositAccountId, DepositId,
-        DisbursalId, DisbursalStatus, PaymentId, Satoshis, Subject, UsdCents, WithdrawalId,
+        CreditFacilityId, CreditFacilityStatus, CustomerId, DepositAccountId,
+        DepositAccountStatementId, DepositId, DisbursalId, DisbursalStatus, PaymentId, Satoshis,
+        Subject, UsdCents, WithdrawalId,
     },
     terms::CollateralizationState,
 };
@@ -36,6 +37,16 @@ impl From<chrono::DateTime<chrono::Utc>> for Timestamp {
     }
 }
 
+#[derive(Clone, Copy, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Date(chrono::NaiveDate);
+scalar!(Date);
+impl From<chrono::NaiveDate> for Date {
+    fn from(value: chrono::NaiveDate) -> Self {
+        Self(value)
+    }
+}
+
 pub trait ToGlobalId {
     fn to_global_id(&self) -> async_graphql::types::ID;
 }
@@ -55,6 +66,7 @@ macro_rules! impl_to_global_id {
 impl_to_global_id! {
     CustomerId,
     DepositAccountId,
+    DepositAccountStatementId,
     DepositId,
     WithdrawalId,
     CreditFacilityId,
@@ -2,8 +2,10 @@ mod active_sync;
 mod create_deposit_account;
 mod create_kratos_user;
 mod sync_email;
+mod sync_kyc_level;
 
 pub use active_sync::*;
 pub use create_deposit_account::*;
 pub use create_kratos_user::*;
 pub use sync_email::*;
+pub use sync_kyc_level::*;
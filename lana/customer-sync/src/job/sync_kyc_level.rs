@@ -0,0 +1,188 @@
+use async_trait::async_trait;
+use futures::StreamExt;
+use tracing::instrument;
+
+use audit::{AuditSvc, SystemSubject};
+use authz::PermissionCheck;
+use core_customer::{CoreCustomerAction, CoreCustomerEvent, CustomerObject};
+use deposit::{
+    CoreDeposit, CoreDepositAction, CoreDepositEvent, CoreDepositObject, GovernanceAction,
+    GovernanceObject,
+};
+use governance::GovernanceEvent;
+use outbox::{Outbox, OutboxEventMarker, PersistentOutboxEvent};
+
+use job::*;
+
+#[derive(serde::Serialize)]
+pub struct SyncKycLevelJobConfig<Perms, E> {
+    _phantom: std::marker::PhantomData<(Perms, E)>,
+}
+impl<Perms, E> SyncKycLevelJobConfig<Perms, E> {
+    pub fn new() -> Self {
+        Self {
+            _phantom: std::marker::PhantomData,
+        }
+    }
+}
+impl<Perms, E> JobConfig for SyncKycLevelJobConfig<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCustomerAction> + From<CoreDepositAction> + From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CustomerObject> + From<CoreDepositObject> + From<GovernanceObject>,
+    E: OutboxEventMarker<CoreCustomerEvent>
+        + OutboxEventMarker<CoreDepositEvent>
+        + OutboxEventMarker<GovernanceEvent>,
+{
+    type Initializer = SyncKycLevelJobInitializer<Perms, E>;
+}
+
+pub struct SyncKycLevelJobInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCustomerEvent>
+        + OutboxEventMarker<CoreDepositEvent>
+        + OutboxEventMarker<GovernanceEvent>,
+{
+    outbox: Outbox<E>,
+    deposit: CoreDeposit<Perms, E>,
+}
+
+impl<Perms, E> SyncKycLevelJobInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCustomerEvent>
+        + OutboxEventMarker<CoreDepositEvent>
+        + OutboxEventMarker<GovernanceEvent>,
+{
+    pub fn new(outbox: &Outbox<E>, deposit: &CoreDeposit<Perms, E>) -> Self {
+        Self {
+            outbox: outbox.clone(),
+            deposit: deposit.clone(),
+        }
+    }
+}
+
+const SYNC_KYC_LEVEL_JOB: JobType = JobType::new("sync-kyc-level-job");
+impl<Perms, E> JobInitializer for SyncKycLevelJobInitializer<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCustomerAction> + From<CoreDepositAction> + From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CustomerObject> + From<CoreDepositObject> + From<GovernanceObject>,
+    E: OutboxEventMarker<CoreCustomerEvent>
+        + OutboxEventMarker<CoreDepositEvent>
+        + OutboxEventMarker<GovernanceEvent>,
+{
+    fn job_type() -> JobType
+    where
+        Self: Sized,
+    {
+        SYNC_KYC_LEVEL_JOB
+    }
+
+    fn init(&self, _: &Job) -> Result<Box<dyn JobRunner>, Box<dyn std::error::Error>> {
+        Ok(Box::new(SyncKycLevelJobRunner {
+            outbox: self.outbox.clone(),
+            deposit: self.deposit.clone(),
+        }))
+    }
+
+    fn retry_on_error_settings() -> RetrySettings
+    where
+        Self: Sized,
+    {
+        RetrySettings::repeat_indefinitely()
+    }
+}
+
+#[derive(Default, Clone, serde::Deserialize, serde::Serialize)]
+struct SyncKycLevelJobData {
+    sequence: outbox::EventSequence,
+}
+
+pub struct SyncKycLevelJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    E: OutboxEventMarker<CoreCustomerEvent>
+        + OutboxEventMarker<CoreDepositEvent>
+        + OutboxEventMarker<GovernanceEvent>,
+{
+    outbox: Outbox<E>,
+    deposit: CoreDeposit<Perms, E>,
+}
+#[async_trait]
+impl<Perms, E> JobRunner for SyncKycLevelJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCustomerAction> + From<CoreDepositAction> + From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CustomerObject> + From<CoreDepositObject> + From<GovernanceObject>,
+    E: OutboxEventMarker<CoreCustomerEvent>
+        + OutboxEventMarker<CoreDepositEvent>
+        + OutboxEventMarker<GovernanceEvent>,
+{
+    async fn run(
+        &self,
+        mut current_job: CurrentJob,
+    ) -> Result<JobCompletion, Box<dyn std::error::Error>> {
+        let mut state = current_job
+            .execution_state::<SyncKycLevelJobData>()?
+            .unwrap_or_default();
+        let mut stream = self.outbox.listen_persisted(Some(state.sequence)).await?;
+
+        while let Some(message) = stream.next().await {
+            if let Some(CoreCustomerEvent::CustomerKycLevelUpdated { .. }) =
+                &message.as_ref().as_event()
+            {
+                self.handle_kyc_level_updated(message.as_ref()).await?;
+                state.sequence = message.sequence;
+                current_job.update_execution_state(&state).await?;
+            }
+        }
+
+        Ok(JobCompletion::RescheduleNow)
+    }
+}
+
+impl<Perms, E> SyncKycLevelJobRunner<Perms, E>
+where
+    Perms: PermissionCheck,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Action:
+        From<CoreCustomerAction> + From<CoreDepositAction> + From<GovernanceAction>,
+    <<Perms as PermissionCheck>::Audit as AuditSvc>::Object:
+        From<CustomerObject> + From<CoreDepositObject> + From<GovernanceObject>,
+    E: OutboxEventMarker<CoreCustomerEvent>
+        + OutboxEventMarker<CoreDepositEvent>
+        + OutboxEventMarker<GovernanceEvent>,
+{
+    #[instrument(
+        name = "customer_sync.handle_kyc_level_update",
+        skip(self, message),
+        err
+    )]
+    async fn handle_kyc_level_updated(
+        &self,
+        message: &PersistentOutboxEvent<E>,
+    ) -> Result<(), Box<dyn std::error::Error>>
+    where
+        E: OutboxEventMarker<CoreCustomerEvent>,
+    {
+        if let Some(CoreCustomerEvent::CustomerKycLevelUpdated { id, level }) = message.as_event() {
+            message.inject_trace_parent();
+
+            self.deposit
+                .update_account_kyc_level_for_holder(
+                    &<<Perms as PermissionCheck>::Audit as AuditSvc>::Subject::system(),
+                    *id,
+                    *level,
+                )
+                .await?;
+        }
+        Ok(())
+    }
+}
@@ -84,6 +84,11 @@ where
             CustomerActiveSyncJobConfig::new(),
         )
         .await?;
+        jobs.add_initializer_and_spawn_unique(
+            SyncKycLevelJobInitializer::new(outbox, deposit),
+            SyncKycLevelJobConfig::new(),
+        )
+        .await?;
         Ok(Self {
             _phantom: std::marker::PhantomData,
             _outbox: outbox.clone(),
@@ -7,10 +7,12 @@ es_entity::entity_id! {
     PaymentId,
     InterestAccrualCycleId,
     TermsTemplateId,
+    ConfigChangeSetId,
     ReportId;
 
     CreditFacilityId => governance::ApprovalProcessId,
     DisbursalId => governance::ApprovalProcessId,
+    ConfigChangeSetId => governance::ApprovalProcessId,
 
     ReportId => job::JobId,
     CreditFacilityId => job::JobId,
@@ -127,6 +127,7 @@ pub enum AppAction {
     LedgerAccount(LedgerAccountAction),
     GeneralLedger(GeneralLedgerAction),
     Document(DocumentAction),
+    ConfigChangeSet(ConfigChangeSetAction),
 }
 
 impl Display for AppAction {
@@ -141,6 +142,7 @@ impl Display for AppAction {
             LedgerAccount(action) => action.fmt(f),
             GeneralLedger(action) => action.fmt(f),
             Document(action) => action.fmt(f),
+            ConfigChangeSet(action) => action.fmt(f),
         }
     }
 }
@@ -161,6 +163,7 @@ impl FromStr for AppAction {
             LedgerAccount => AppAction::from(action.parse::<LedgerAccountAction>()?),
             GeneralLedger => AppAction::from(action.parse::<GeneralLedgerAction>()?),
             Document => AppAction::from(action.parse::<DocumentAction>()?),
+            ConfigChangeSet => AppAction::from(action.parse::<ConfigChangeSetAction>()?),
         };
         Ok(res)
     }
@@ -235,6 +238,17 @@ pub enum GeneralLedgerAction {
 
 impl_trivial_action!(GeneralLedgerAction, GeneralLedger);
 
+#[derive(PartialEq, Clone, Copy, Debug, strum::Display, strum::EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum ConfigChangeSetAction {
+    Propose,
+    Read,
+    ConcludeApprovalProcess,
+    Apply,
+}
+
+impl_trivial_action!(ConfigChangeSetAction, ConfigChangeSet);
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -119,6 +119,7 @@ pub enum AppObject {
     Ledger,
     LedgerAccount,
     GeneralLedger,
+    ConfigChangeSet,
 }
 
 impl Display for AppObject {
@@ -145,6 +146,7 @@ impl FromStr for AppObject {
             LedgerAccount => AppObject::LedgerAccount,
             GeneralLedger => AppObject::GeneralLedger,
             Document => AppObject::Document,
+            ConfigChangeSet => AppObject::ConfigChangeSet,
         };
         Ok(res)
     }